@@ -1353,6 +1353,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             &parent_root,
             slot,
             &sync_aggregate,
+            &self.spec,
         )
     }
 
@@ -2428,9 +2429,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     }
 
     /// Accept a pre-verified exit and queue it for inclusion in an appropriate block.
-    pub fn import_voluntary_exit(&self, exit: SigVerifiedOp<SignedVoluntaryExit, T::EthSpec>) {
+    ///
+    /// Return `true` if the exit was added to the op pool.
+    pub fn import_voluntary_exit(
+        &self,
+        exit: SigVerifiedOp<SignedVoluntaryExit, T::EthSpec>,
+    ) -> bool {
         if self.eth1_chain.is_some() {
             self.op_pool.insert_voluntary_exit(exit)
+        } else {
+            false
         }
     }
 
@@ -2440,20 +2448,37 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         proposer_slashing: ProposerSlashing,
     ) -> Result<ObservationOutcome<ProposerSlashing, T::EthSpec>, Error> {
         let wall_clock_state = self.wall_clock_state()?;
-        Ok(self.observed_proposer_slashings.lock().verify_and_observe(
-            proposer_slashing,
-            &wall_clock_state,
-            &self.spec,
-        )?)
+        Ok(self
+            .observed_proposer_slashings
+            .lock()
+            .verify_and_observe(proposer_slashing, &wall_clock_state, &self.spec)
+            .map(|slashing| {
+                // this method is called for both API and gossip slashings, so this covers all
+                // proposer slashing events
+                if let Some(event_handler) = self.event_handler.as_ref() {
+                    if event_handler.has_proposer_slashing_subscribers() {
+                        if let ObservationOutcome::New(slashing) = slashing.clone() {
+                            event_handler.register(EventKind::ProposerSlashing(Box::new(
+                                slashing.into_inner(),
+                            )));
+                        }
+                    }
+                }
+                slashing
+            })?)
     }
 
     /// Accept some proposer slashing and queue it for inclusion in an appropriate block.
+    ///
+    /// Return `true` if the slashing was added to the op pool.
     pub fn import_proposer_slashing(
         &self,
         proposer_slashing: SigVerifiedOp<ProposerSlashing, T::EthSpec>,
-    ) {
+    ) -> bool {
         if self.eth1_chain.is_some() {
             self.op_pool.insert_proposer_slashing(proposer_slashing)
+        } else {
+            false
         }
     }
 
@@ -2463,21 +2488,36 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         attester_slashing: AttesterSlashing<T::EthSpec>,
     ) -> Result<ObservationOutcome<AttesterSlashing<T::EthSpec>, T::EthSpec>, Error> {
         let wall_clock_state = self.wall_clock_state()?;
-        Ok(self.observed_attester_slashings.lock().verify_and_observe(
-            attester_slashing,
-            &wall_clock_state,
-            &self.spec,
-        )?)
+        Ok(self
+            .observed_attester_slashings
+            .lock()
+            .verify_and_observe(attester_slashing, &wall_clock_state, &self.spec)
+            .map(|slashing| {
+                // this method is called for both API and gossip slashings, so this covers all
+                // attester slashing events
+                if let Some(event_handler) = self.event_handler.as_ref() {
+                    if event_handler.has_attester_slashing_subscribers() {
+                        if let ObservationOutcome::New(slashing) = slashing.clone() {
+                            event_handler.register(EventKind::AttesterSlashing(Box::new(
+                                slashing.into_inner(),
+                            )));
+                        }
+                    }
+                }
+                slashing
+            })?)
     }
 
     /// Accept a verified attester slashing and:
     ///
     /// 1. Apply it to fork choice.
     /// 2. Add it to the op pool.
+    ///
+    /// Return `true` if the slashing was added to the op pool.
     pub fn import_attester_slashing(
         &self,
         attester_slashing: SigVerifiedOp<AttesterSlashing<T::EthSpec>, T::EthSpec>,
-    ) {
+    ) -> bool {
         // Add to fork choice.
         self.canonical_head
             .fork_choice_write_lock()
@@ -2486,6 +2526,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // Add to the op pool (if we have the ability to propose blocks).
         if self.eth1_chain.is_some() {
             self.op_pool.insert_attester_slashing(attester_slashing)
+        } else {
+            false
         }
     }
 
@@ -2514,7 +2556,21 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(self
             .observed_bls_to_execution_changes
             .lock()
-            .verify_and_observe(bls_to_execution_change, head_state, &self.spec)?)
+            .verify_and_observe(bls_to_execution_change, head_state, &self.spec)
+            .map(|change| {
+                // gossip verification also goes through this method, so this covers all
+                // BLS to execution change events
+                if let Some(event_handler) = self.event_handler.as_ref() {
+                    if event_handler.has_bls_to_execution_change_subscribers() {
+                        if let ObservationOutcome::New(change) = change.clone() {
+                            event_handler.register(EventKind::BlsToExecutionChange(Box::new(
+                                change.into_inner(),
+                            )));
+                        }
+                    }
+                }
+                change
+            })?)
     }
 
     /// Verify a signed BLS to execution change before allowing it to propagate on the gossip network.
@@ -4964,10 +5020,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         let mut attestations = self
             .op_pool
-            .get_attestations(
+            .get_attestations_with_strategy(
                 &state,
                 prev_attestation_filter,
                 curr_attestation_filter,
+                self.config.attestation_packing_strategy,
+                Duration::from_millis(self.config.attestation_packing_time_budget_ms),
                 &self.spec,
             )
             .map_err(BlockProductionError::OpPoolError)?;
@@ -6641,6 +6699,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             ForkName::Base | ForkName::Capella | ForkName::Deneb => Err(Error::UnsupportedFork),
         }
     }
+
+    /// Gets the best `LightClientUpdate` of up to `count` consecutive sync committee periods
+    /// starting at `start_period`.
+    ///
+    /// The result is truncated at the first period for which no update is known.
+    pub fn get_light_client_updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<LightClientUpdate<T::EthSpec>>, Error> {
+        self.light_client_server_cache
+            .get_light_client_updates(&self.store, start_period, count)
+    }
 }
 
 impl<T: BeaconChainTypes> Drop for BeaconChain<T> {
@@ -1,10 +1,13 @@
 use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
-use eth2::lighthouse::{AttestationRewards, BlockReward, BlockRewardMeta};
+use eth2::lighthouse::{
+    AttestationPackingReward, AttestationRewards, BlockReward, BlockRewardMeta,
+};
 use operation_pool::{AttMaxCover, MaxCover, RewardCache, SplitAttestation};
 use state_processing::{
     common::get_attesting_indices_from_state,
     per_block_processing::altair::sync_committee::compute_sync_aggregate_rewards,
 };
+use tree_hash::TreeHash;
 use types::{AbstractExecPayload, BeaconBlockRef, BeaconState, EthSpec, Hash256};
 
 impl<T: BeaconChainTypes> BeaconChain<T> {
@@ -93,12 +96,24 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             vec![]
         };
 
+        // Report how the attestations were packed if this node produced the block.
+        let packing = self
+            .op_pool
+            .get_attestation_packing(block.body().attestations().tree_hash_root())
+            .map(|packing| AttestationPackingReward {
+                strategy: packing.strategy.to_string(),
+                reward: packing.reward,
+                greedy_reward: packing.greedy_reward,
+                timed_out: packing.timed_out,
+            });
+
         let attestation_rewards = AttestationRewards {
             total: attestation_total,
             prev_epoch_total,
             curr_epoch_total,
             per_attestation_rewards,
             attestations,
+            packing,
         };
 
         // Sync committee rewards.
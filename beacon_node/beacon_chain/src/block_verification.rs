@@ -70,7 +70,7 @@ use crate::{
     metrics, BeaconChain, BeaconChainError, BeaconChainTypes,
 };
 use derivative::Derivative;
-use eth2::types::{EventKind, PublishBlockRequest};
+use eth2::types::{EventKind, PublishBlockRequest, SseBlockGossip};
 use execution_layer::PayloadStatus;
 pub use fork_choice::{AttestationFromBlock, PayloadVerificationStatus};
 use parking_lot::RwLockReadGuard;
@@ -784,12 +784,26 @@ impl<T: BeaconChainTypes> GossipVerifiedBlock<T> {
         // The `SignedBeaconBlock` and `SignedBeaconBlockHeader` have the same canonical root,
         // but it's way quicker to calculate root of the header since the hash of the tree rooted
         // at `BeaconBlockBody` is already computed in the header.
-        Self::new_without_slasher_checks(block, &header, chain).map_err(|e| {
-            process_block_slash_info::<_, BlockError<T::EthSpec>>(
-                chain,
-                BlockSlashInfo::from_early_error_block(header, e),
-            )
-        })
+        let gossip_verified_block = Self::new_without_slasher_checks(block, &header, chain)
+            .map_err(|e| {
+                process_block_slash_info::<_, BlockError<T::EthSpec>>(
+                    chain,
+                    BlockSlashInfo::from_early_error_block(header, e),
+                )
+            })?;
+
+        // this method is called for both API and gossip blocks, so this covers all block gossip
+        // events
+        if let Some(event_handler) = chain.event_handler.as_ref() {
+            if event_handler.has_block_gossip_subscribers() {
+                event_handler.register(EventKind::BlockGossip(Box::new(SseBlockGossip {
+                    slot: gossip_verified_block.block.slot(),
+                    block: gossip_verified_block.block_root,
+                })));
+            }
+        }
+
+        Ok(gossip_verified_block)
     }
 
     /// As for new, but doesn't pass the block to the slasher.
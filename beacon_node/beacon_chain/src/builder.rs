@@ -897,6 +897,14 @@ where
                 }
             }
         };
+        // Archiving blobs requires the blocks back to the Deneb fork, to verify the blobs against.
+        let genesis_backfill_slot = match self.spec.deneb_fork_epoch {
+            Some(deneb_fork_epoch) if self.chain_config.archive_blobs => std::cmp::min(
+                genesis_backfill_slot,
+                deneb_fork_epoch.start_slot(TEthSpec::slots_per_epoch()),
+            ),
+            _ => genesis_backfill_slot,
+        };
 
         let beacon_chain = BeaconChain {
             spec: self.spec.clone(),
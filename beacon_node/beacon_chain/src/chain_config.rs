@@ -1,3 +1,4 @@
+pub use operation_pool::AttestationPackingStrategy;
 pub use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -8,6 +9,7 @@ pub const DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION: Epoch = Epoch::new(2);
 /// Default to 1/12th of the slot, which is 1 second on mainnet.
 pub const DEFAULT_RE_ORG_CUTOFF_DENOMINATOR: u32 = 12;
 pub const DEFAULT_FORK_CHOICE_BEFORE_PROPOSAL_TIMEOUT: u64 = 250;
+pub const DEFAULT_ATTESTATION_PACKING_TIME_BUDGET: u64 = 100;
 
 /// Default fraction of a slot lookahead for payload preparation (12/3 = 4 seconds on mainnet).
 pub const DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR: u32 = 3;
@@ -75,6 +77,9 @@ pub struct ChainConfig {
     /// If using a weak-subjectivity sync, whether we should download blocks all the way back to
     /// genesis.
     pub genesis_backfill: bool,
+    /// Whether to backfill and keep the blobs of all blocks since the Deneb fork, rather than
+    /// only those within the data availability boundary.
+    pub archive_blobs: bool,
     /// Whether to send payload attributes every slot, regardless of connected proposers.
     ///
     /// This is useful for block builders and testing.
@@ -85,6 +90,11 @@ pub struct ChainConfig {
     pub epochs_per_migration: u64,
     /// When set to true Light client server computes and caches state proofs for serving updates
     pub enable_light_client_server: bool,
+    /// Algorithm used to select the attestations to include in proposed blocks.
+    pub attestation_packing_strategy: AttestationPackingStrategy,
+    /// Maximum number of milliseconds to spend searching for attestations with the exact
+    /// packing strategy, before falling back to the best solution found.
+    pub attestation_packing_time_budget_ms: u64,
 }
 
 impl Default for ChainConfig {
@@ -113,10 +123,13 @@ impl Default for ChainConfig {
             optimistic_finalized_sync: true,
             shuffling_cache_size: crate::shuffling_cache::DEFAULT_CACHE_SIZE,
             genesis_backfill: false,
+            archive_blobs: false,
             always_prepare_payload: false,
             progressive_balances_mode: ProgressiveBalancesMode::Fast,
             epochs_per_migration: crate::migrate::DEFAULT_EPOCHS_PER_MIGRATION,
             enable_light_client_server: false,
+            attestation_packing_strategy: AttestationPackingStrategy::Greedy,
+            attestation_packing_time_budget_ms: DEFAULT_ATTESTATION_PACKING_TIME_BUDGET,
         }
     }
 }
@@ -579,6 +579,15 @@ impl<E: EthSpec> AvailableBlock<E> {
         }
     }
 
+    /// Create an `AvailableBlock` for a historical block whose blobs are imported separately.
+    pub(crate) fn without_blobs(block_root: Hash256, block: Arc<SignedBeaconBlock<E>>) -> Self {
+        Self {
+            block_root,
+            block,
+            blobs: None,
+        }
+    }
+
     pub fn block(&self) -> &SignedBeaconBlock<E> {
         &self.block
     }
@@ -0,0 +1,162 @@
+use crate::data_availability_checker::AvailableBlock;
+use crate::{errors::BeaconChainError as Error, BeaconChain, BeaconChainTypes};
+use slog::{debug, info, warn};
+use std::collections::{btree_map::Entry, BTreeMap};
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use store::era::{era_block_slots, parse_era_file_name, EraError, EraReader};
+use types::{EthSpec, Slot};
+
+/// The number of blocks whose signatures are verified and stored together.
+const ERA_IMPORT_BATCH_SIZE: usize = 256;
+
+#[derive(Debug)]
+pub enum EraImportError {
+    /// The era import directory could not be read.
+    UnreadableDirectory(io::Error),
+    /// An era file could not be opened.
+    UnreadableFile { path: PathBuf, error: io::Error },
+    /// An era file is malformed.
+    InvalidEraFile { path: PathBuf, error: EraError },
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Import the blocks preceding the oldest stored block from the era files in `dir`.
+    ///
+    /// Era files are imported from the newest era backwards, until the genesis block is reached
+    /// or there is no file for the next era. The blocks are imported using
+    /// `import_historical_block_batch`, so their roots and proposer signatures are verified just
+    /// like blocks from backfill sync. Era files don't contain blobs, so the blobs of blocks within
+    /// the data availability boundary are left for the blob backfill sync to download.
+    ///
+    /// This function must not be called concurrently with backfill sync.
+    ///
+    /// Return the number of blocks imported.
+    pub fn import_era_files(&self, dir: &Path) -> Result<usize, Error> {
+        // Sort the paths so that the choice between duplicate files for an era is deterministic.
+        let mut paths = fs::read_dir(dir)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(EraImportError::UnreadableDirectory)?;
+        paths.sort();
+
+        let mut era_files = BTreeMap::new();
+        for path in paths {
+            let Some((config_name, era)) = path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .and_then(parse_era_file_name)
+            else {
+                continue;
+            };
+            if self
+                .spec
+                .config_name
+                .as_ref()
+                .map_or(false, |network| network != config_name)
+            {
+                warn!(
+                    self.log,
+                    "Ignoring era file for another network";
+                    "file" => ?path,
+                    "network" => config_name,
+                );
+                continue;
+            }
+            match era_files.entry(era) {
+                Entry::Vacant(entry) => {
+                    entry.insert(path);
+                }
+                Entry::Occupied(entry) => {
+                    warn!(
+                        self.log,
+                        "Ignoring duplicate era file";
+                        "era" => era,
+                        "file" => ?path,
+                        "using" => ?entry.get(),
+                    );
+                }
+            }
+        }
+
+        let Some(anchor) = self.store.get_anchor_info() else {
+            return Ok(0);
+        };
+        if anchor.oldest_block_slot == 0 {
+            return Ok(0);
+        }
+        let slots_per_era = T::EthSpec::slots_per_historical_root() as u64;
+        let newest_era = (anchor.oldest_block_slot.as_u64() - 1) / slots_per_era + 1;
+
+        let mut total_imported = 0;
+        for era in (1..=newest_era).rev() {
+            let Some(path) = era_files.get(&era) else {
+                debug!(self.log, "No era file to import"; "era" => era);
+                break;
+            };
+            let imported = self.import_era_file(path, era)?;
+            total_imported += imported;
+            info!(
+                self.log,
+                "Imported era file";
+                "era" => era,
+                "blocks" => imported,
+                "oldest_block_slot" => self.store.get_oldest_block_slot(),
+            );
+            if self.store.get_oldest_block_slot() == 0 {
+                break;
+            }
+        }
+
+        Ok(total_imported)
+    }
+
+    /// Import the blocks of era `era` which precede the oldest stored block.
+    fn import_era_file(&self, path: &Path, era: u64) -> Result<usize, Error> {
+        let invalid_file = |error| EraImportError::InvalidEraFile {
+            path: path.to_path_buf(),
+            error,
+        };
+        let file = File::open(path).map_err(|error| EraImportError::UnreadableFile {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        let mut reader = EraReader::<_, T::EthSpec>::new(BufReader::new(file));
+
+        let slots = era_block_slots::<T::EthSpec>(era);
+        let oldest_block_slot = self.store.get_oldest_block_slot();
+        let blocks: Vec<(Slot, u64)> = reader
+            .block_index(era)
+            .map_err(invalid_file)?
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, position)| Some((slots.start + i as u64, position?)))
+            .filter(|(slot, _)| *slot < oldest_block_slot)
+            .collect();
+
+        // Import the newest blocks first, as each batch must connect to the oldest stored block.
+        let mut imported = 0;
+        for batch in blocks.rchunks(ERA_IMPORT_BATCH_SIZE) {
+            let available_blocks = batch
+                .iter()
+                .map(|(slot, position)| {
+                    let block = reader
+                        .read_block_at(*position, *slot, &self.spec)
+                        .map_err(invalid_file)?;
+                    Ok(AvailableBlock::without_blobs(
+                        block.canonical_root(),
+                        Arc::new(block),
+                    ))
+                })
+                .collect::<Result<Vec<_>, EraImportError>>()?;
+            imported += self.import_historical_block_batch(available_blocks)?;
+        }
+
+        Ok(imported)
+    }
+}
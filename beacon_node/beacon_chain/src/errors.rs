@@ -3,7 +3,9 @@ use crate::beacon_block_streamer::Error as BlockStreamerError;
 use crate::beacon_chain::ForkChoiceError;
 use crate::beacon_fork_choice_store::Error as ForkChoiceStoreError;
 use crate::data_availability_checker::AvailabilityCheckError;
+use crate::era_import::EraImportError;
 use crate::eth1_chain::Error as Eth1ChainError;
+use crate::historical_blobs::HistoricalBlobError;
 use crate::historical_blocks::HistoricalBlockError;
 use crate::migrate::PruningError;
 use crate::naive_aggregation_pool::Error as NaiveAggregationError;
@@ -130,6 +132,8 @@ pub enum BeaconChainError {
         state_slot: Slot,
     },
     HistoricalBlockError(HistoricalBlockError),
+    HistoricalBlobError(HistoricalBlobError),
+    EraImportError(EraImportError),
     InvalidStateForShuffling {
         state_epoch: Epoch,
         shuffling_epoch: Epoch,
@@ -223,6 +227,7 @@ pub enum BeaconChainError {
     AvailabilityCheckError(AvailabilityCheckError),
     LightClientError(LightClientError),
     UnsupportedFork,
+    InvalidImportedOperation(String),
 }
 
 easy_from_to!(SlotProcessingError, BeaconChainError);
@@ -246,6 +251,8 @@ easy_from_to!(PruningError, BeaconChainError);
 easy_from_to!(ArithError, BeaconChainError);
 easy_from_to!(ForkChoiceStoreError, BeaconChainError);
 easy_from_to!(HistoricalBlockError, BeaconChainError);
+easy_from_to!(HistoricalBlobError, BeaconChainError);
+easy_from_to!(EraImportError, BeaconChainError);
 easy_from_to!(StateAdvanceError, BeaconChainError);
 easy_from_to!(BlockReplayError, BeaconChainError);
 easy_from_to!(InconsistentFork, BeaconChainError);
@@ -1,4 +1,4 @@
-pub use eth2::types::{EventKind, SseBlock, SseFinalizedCheckpoint, SseHead};
+pub use eth2::types::{EventKind, SseBlock, SseBlockGossip, SseFinalizedCheckpoint, SseHead};
 use slog::{trace, Logger};
 use tokio::sync::broadcast;
 use tokio::sync::broadcast::{error::SendError, Receiver, Sender};
@@ -20,6 +20,10 @@ pub struct ServerSentEventHandler<T: EthSpec> {
     light_client_finality_update_tx: Sender<EventKind<T>>,
     light_client_optimistic_update_tx: Sender<EventKind<T>>,
     block_reward_tx: Sender<EventKind<T>>,
+    attester_slashing_tx: Sender<EventKind<T>>,
+    proposer_slashing_tx: Sender<EventKind<T>>,
+    bls_to_execution_change_tx: Sender<EventKind<T>>,
+    block_gossip_tx: Sender<EventKind<T>>,
     log: Logger,
 }
 
@@ -45,6 +49,10 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         let (light_client_finality_update_tx, _) = broadcast::channel(capacity);
         let (light_client_optimistic_update_tx, _) = broadcast::channel(capacity);
         let (block_reward_tx, _) = broadcast::channel(capacity);
+        let (attester_slashing_tx, _) = broadcast::channel(capacity);
+        let (proposer_slashing_tx, _) = broadcast::channel(capacity);
+        let (bls_to_execution_change_tx, _) = broadcast::channel(capacity);
+        let (block_gossip_tx, _) = broadcast::channel(capacity);
 
         Self {
             attestation_tx,
@@ -60,6 +68,10 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
             light_client_finality_update_tx,
             light_client_optimistic_update_tx,
             block_reward_tx,
+            attester_slashing_tx,
+            proposer_slashing_tx,
+            bls_to_execution_change_tx,
+            block_gossip_tx,
             log,
         }
     }
@@ -126,6 +138,22 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
                 .block_reward_tx
                 .send(kind)
                 .map(|count| log_count("block reward", count)),
+            EventKind::AttesterSlashing(_) => self
+                .attester_slashing_tx
+                .send(kind)
+                .map(|count| log_count("attester slashing", count)),
+            EventKind::ProposerSlashing(_) => self
+                .proposer_slashing_tx
+                .send(kind)
+                .map(|count| log_count("proposer slashing", count)),
+            EventKind::BlsToExecutionChange(_) => self
+                .bls_to_execution_change_tx
+                .send(kind)
+                .map(|count| log_count("bls to execution change", count)),
+            EventKind::BlockGossip(_) => self
+                .block_gossip_tx
+                .send(kind)
+                .map(|count| log_count("block gossip", count)),
         };
         if let Err(SendError(event)) = result {
             trace!(self.log, "No receivers registered to listen for event"; "event" => ?event);
@@ -184,6 +212,22 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         self.block_reward_tx.subscribe()
     }
 
+    pub fn subscribe_attester_slashing(&self) -> Receiver<EventKind<T>> {
+        self.attester_slashing_tx.subscribe()
+    }
+
+    pub fn subscribe_proposer_slashing(&self) -> Receiver<EventKind<T>> {
+        self.proposer_slashing_tx.subscribe()
+    }
+
+    pub fn subscribe_bls_to_execution_change(&self) -> Receiver<EventKind<T>> {
+        self.bls_to_execution_change_tx.subscribe()
+    }
+
+    pub fn subscribe_block_gossip(&self) -> Receiver<EventKind<T>> {
+        self.block_gossip_tx.subscribe()
+    }
+
     pub fn has_attestation_subscribers(&self) -> bool {
         self.attestation_tx.receiver_count() > 0
     }
@@ -227,4 +271,20 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
     pub fn has_block_reward_subscribers(&self) -> bool {
         self.block_reward_tx.receiver_count() > 0
     }
+
+    pub fn has_attester_slashing_subscribers(&self) -> bool {
+        self.attester_slashing_tx.receiver_count() > 0
+    }
+
+    pub fn has_proposer_slashing_subscribers(&self) -> bool {
+        self.proposer_slashing_tx.receiver_count() > 0
+    }
+
+    pub fn has_bls_to_execution_change_subscribers(&self) -> bool {
+        self.bls_to_execution_change_tx.receiver_count() > 0
+    }
+
+    pub fn has_block_gossip_subscribers(&self) -> bool {
+        self.block_gossip_tx.receiver_count() > 0
+    }
 }
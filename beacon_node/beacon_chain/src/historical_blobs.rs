@@ -0,0 +1,209 @@
+use crate::blob_verification::verify_kzg_for_blob_list;
+use crate::{errors::BeaconChainError as Error, metrics, BeaconChain, BeaconChainTypes};
+use kzg::Error as KzgError;
+use slog::debug;
+use ssz_types::VariableList;
+use std::collections::HashMap;
+use std::sync::Arc;
+use store::BlobInfo;
+use types::{BlobSidecar, EthSpec, Hash256, Slot};
+
+#[derive(Debug)]
+pub enum HistoricalBlobError {
+    /// The Deneb fork is not scheduled, there are no blobs to import.
+    DenebNotScheduled,
+    /// The batch doesn't end at the oldest blob slot, caller should request the blobs preceding
+    /// the oldest blob slot.
+    NonContiguousBatch {
+        end_slot: Slot,
+        oldest_blob_slot: Slot,
+    },
+    /// The batch starts before the Deneb fork, where blobs don't exist.
+    BatchBeforeDenebFork { start_slot: Slot, deneb_slot: Slot },
+    /// A blob is not for a canonical block within the batch, caller should retry with different
+    /// blobs.
+    UnknownBlock { block_root: Hash256, slot: Slot },
+    /// A blob index is not covered by a commitment in its block, or is duplicated. Caller should
+    /// retry with different blobs.
+    InvalidBlobIndex { block_root: Hash256, index: u64 },
+    /// A blob's commitment doesn't match the commitment in its block, caller should retry with
+    /// different blobs.
+    MismatchedCommitment { block_root: Hash256, index: u64 },
+    /// Not all blobs of a block were provided, the caller should retry with blobs from a peer
+    /// which still has them.
+    MissingBlobs {
+        block_root: Hash256,
+        expected: usize,
+        received: usize,
+    },
+    /// The KZG proofs of the blobs are invalid, caller should retry with different blobs.
+    InvalidKzgProof(KzgError),
+    /// The KZG trusted setup is not loaded.
+    KzgNotInitialized,
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Store the blobs of historical blocks in the database, extending the range of stored blobs
+    /// back in time.
+    ///
+    /// The `blobs` must be all the blobs of the canonical blocks in `start_slot..end_slot`, where
+    /// `end_slot` is the `oldest_blob_slot` from the store's `BlobInfo`. The blocks must already
+    /// be in the database. The blobs are checked against the KZG commitments of their blocks and
+    /// their KZG proofs are verified. If any blob is invalid or missing then no blobs are stored.
+    ///
+    /// This function should not be called concurrently with any other function that mutates
+    /// the blob info (including this function itself). If a concurrent mutation occurs that
+    /// would violate consistency then a `BlobInfoConcurrentMutation` error will be returned.
+    ///
+    /// Return the number of blobs successfully imported.
+    pub fn import_historical_blob_batch(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+        blobs: Vec<Arc<BlobSidecar<T::EthSpec>>>,
+    ) -> Result<usize, Error> {
+        let blob_info = self.store.get_blob_info();
+        let oldest_blob_slot = blob_info
+            .oldest_blob_slot
+            .ok_or(HistoricalBlobError::DenebNotScheduled)?;
+        let deneb_slot = self
+            .spec
+            .deneb_fork_epoch
+            .ok_or(HistoricalBlobError::DenebNotScheduled)?
+            .start_slot(T::EthSpec::slots_per_epoch());
+
+        if end_slot != oldest_blob_slot {
+            return Err(HistoricalBlobError::NonContiguousBatch {
+                end_slot,
+                oldest_blob_slot,
+            }
+            .into());
+        }
+        if start_slot < deneb_slot {
+            return Err(HistoricalBlobError::BatchBeforeDenebFork {
+                start_slot,
+                deneb_slot,
+            }
+            .into());
+        }
+        if start_slot >= end_slot {
+            return Ok(0);
+        }
+
+        // Load the canonical blocks of the batch. Skipped slots repeat the root of the previous
+        // block, which may precede the batch.
+        let mut block_roots = vec![];
+        for result in self.forwards_iter_block_roots_until(start_slot, end_slot - 1)? {
+            let (block_root, _) = result?;
+            if block_roots.last() != Some(&block_root) {
+                block_roots.push(block_root);
+            }
+        }
+        let mut blocks = HashMap::with_capacity(block_roots.len());
+        for block_root in block_roots {
+            let block = self
+                .store
+                .get_blinded_block(&block_root)?
+                .ok_or(Error::MissingBeaconBlock(block_root))?;
+            if block.slot() >= start_slot {
+                blocks.insert(block_root, block);
+            }
+        }
+
+        let mut blobs_by_block: HashMap<Hash256, Vec<Arc<BlobSidecar<T::EthSpec>>>> =
+            HashMap::with_capacity(blocks.len());
+        for blob in &blobs {
+            let block_root = blob.block_root();
+            if !blocks.contains_key(&block_root) {
+                return Err(HistoricalBlobError::UnknownBlock {
+                    block_root,
+                    slot: blob.slot(),
+                }
+                .into());
+            }
+            blobs_by_block
+                .entry(block_root)
+                .or_default()
+                .push(blob.clone());
+        }
+
+        // Check that each block has exactly one blob per commitment, with matching commitments.
+        for (block_root, block) in &blocks {
+            let commitments = block
+                .message()
+                .body()
+                .blob_kzg_commitments()
+                .map(|commitments| &commitments[..])
+                .unwrap_or(&[]);
+            let block_blobs = blobs_by_block.entry(*block_root).or_default();
+            block_blobs.sort_unstable_by_key(|blob| blob.index);
+
+            for (expected_index, blob) in block_blobs.iter().enumerate() {
+                if blob.index != expected_index as u64 || blob.index >= commitments.len() as u64 {
+                    return Err(HistoricalBlobError::InvalidBlobIndex {
+                        block_root: *block_root,
+                        index: blob.index,
+                    }
+                    .into());
+                }
+                if commitments[expected_index] != blob.kzg_commitment {
+                    return Err(HistoricalBlobError::MismatchedCommitment {
+                        block_root: *block_root,
+                        index: blob.index,
+                    }
+                    .into());
+                }
+            }
+            if block_blobs.len() != commitments.len() {
+                return Err(HistoricalBlobError::MissingBlobs {
+                    block_root: *block_root,
+                    expected: commitments.len(),
+                    received: block_blobs.len(),
+                }
+                .into());
+            }
+        }
+
+        if !blobs.is_empty() {
+            let kzg = self
+                .kzg
+                .as_ref()
+                .ok_or(HistoricalBlobError::KzgNotInitialized)?;
+            let _timer = metrics::start_timer(&metrics::BACKFILL_BLOB_KZG_VERIFICATION_TIMES);
+            verify_kzg_for_blob_list(blobs.iter(), kzg)
+                .map_err(HistoricalBlobError::InvalidKzgProof)?;
+        }
+
+        let mut blob_batch = Vec::with_capacity(blobs_by_block.len());
+        for (block_root, block_blobs) in blobs_by_block {
+            if !block_blobs.is_empty() {
+                self.store.blobs_as_kv_store_ops(
+                    &block_root,
+                    VariableList::from(block_blobs),
+                    &mut blob_batch,
+                );
+            }
+        }
+        // Write the blobs before updating the blob info, so that the blob info never points to
+        // blobs that don't exist.
+        self.store.blobs_db.do_atomically(blob_batch)?;
+
+        let new_blob_info = BlobInfo {
+            oldest_blob_slot: Some(start_slot),
+            ..blob_info.clone()
+        };
+        self.store
+            .compare_and_set_blob_info_with_write(blob_info, new_blob_info)?;
+
+        debug!(
+            self.log,
+            "Imported historical blobs";
+            "start_slot" => start_slot,
+            "end_slot" => end_slot,
+            "blobs" => blobs.len(),
+        );
+        metrics::inc_counter_by(&metrics::BACKFILL_BLOBS_IMPORTED, blobs.len() as u64);
+
+        Ok(blobs.len())
+    }
+}
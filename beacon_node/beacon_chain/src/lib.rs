@@ -20,6 +20,7 @@ pub mod chain_config;
 pub mod data_availability_checker;
 pub mod deneb_readiness;
 mod early_attester_cache;
+pub mod era_import;
 mod errors;
 pub mod eth1_chain;
 mod eth1_finalization_cache;
@@ -28,6 +29,7 @@ pub mod execution_payload;
 pub mod fork_choice_signal;
 pub mod fork_revert;
 mod head_tracker;
+pub mod historical_blobs;
 pub mod historical_blocks;
 pub mod kzg_utils;
 pub mod light_client_finality_update_verification;
@@ -43,6 +45,7 @@ mod observed_blob_sidecars;
 pub mod observed_block_producers;
 pub mod observed_operations;
 mod observed_slashable;
+pub mod operation_pool_transfer;
 pub mod otb_verification_service;
 mod persisted_beacon_chain;
 mod persisted_fork_choice;
@@ -69,6 +72,7 @@ pub use self::beacon_chain::{
 pub use self::beacon_snapshot::BeaconSnapshot;
 pub use self::chain_config::ChainConfig;
 pub use self::errors::{BeaconChainError, BlockProductionError};
+pub use self::historical_blobs::HistoricalBlobError;
 pub use self::historical_blocks::HistoricalBlockError;
 pub use attestation_verification::Error as AttestationError;
 pub use beacon_fork_choice_store::{BeaconForkChoiceStore, Error as ForkChoiceStoreError};
@@ -1,14 +1,22 @@
 use crate::errors::BeaconChainError;
 use crate::{metrics, BeaconChainTypes, BeaconStore};
 use parking_lot::{Mutex, RwLock};
+use safe_arith::ArithError;
 use slog::{debug, Logger};
+use ssz::{Decode, Encode};
 use ssz_types::FixedVector;
 use std::num::NonZeroUsize;
-use types::light_client_update::{FinalizedRootProofLen, FINALIZED_ROOT_INDEX};
+use std::sync::Arc;
+use store::{DBColumn, KeyValueStore};
+use types::light_client_update::{
+    FinalizedRootProofLen, NextSyncCommitteeProofLen, FINALIZED_ROOT_INDEX,
+    NEXT_SYNC_COMMITTEE_INDEX,
+};
 use types::non_zero_usize::new_non_zero_usize;
 use types::{
-    BeaconBlockRef, BeaconState, ChainSpec, EthSpec, ForkName, Hash256, LightClientFinalityUpdate,
-    LightClientHeader, LightClientOptimisticUpdate, Slot, SyncAggregate,
+    BeaconBlockHeader, BeaconBlockRef, BeaconState, ChainSpec, EthSpec, ForkName, Hash256,
+    LightClientFinalityUpdate, LightClientHeader, LightClientOptimisticUpdate, LightClientUpdate,
+    Slot, SyncAggregate, SyncCommittee,
 };
 
 /// A prev block cache miss requires to re-generate the state of the post-parent block. Items in the
@@ -30,8 +38,11 @@ pub struct LightClientServerCache<T: BeaconChainTypes> {
     latest_finality_update: RwLock<Option<LightClientFinalityUpdate<T::EthSpec>>>,
     /// Tracks a single global latest optimistic update out of all imported blocks.
     latest_optimistic_update: RwLock<Option<LightClientOptimisticUpdate<T::EthSpec>>>,
+    /// Tracks the best update of the most recent sync committee period seen. The best update of
+    /// every period is also persisted to disk in `DBColumn::LightClientUpdate`.
+    latest_light_client_update: RwLock<Option<LightClientUpdate<T::EthSpec>>>,
     /// Caches state proofs by block root
-    prev_block_cache: Mutex<lru::LruCache<Hash256, LightClientCachedData>>,
+    prev_block_cache: Mutex<lru::LruCache<Hash256, LightClientCachedData<T::EthSpec>>>,
 }
 
 impl<T: BeaconChainTypes> LightClientServerCache<T> {
@@ -39,6 +50,7 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         Self {
             latest_finality_update: None.into(),
             latest_optimistic_update: None.into(),
+            latest_light_client_update: None.into(),
             prev_block_cache: lru::LruCache::new(PREV_BLOCK_CACHE_SIZE).into(),
         }
     }
@@ -76,6 +88,7 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         block_parent_root: &Hash256,
         block_slot: Slot,
         sync_aggregate: &SyncAggregate<T::EthSpec>,
+        chain_spec: &ChainSpec,
     ) -> Result<(), BeaconChainError> {
         let _timer =
             metrics::start_timer(&metrics::LIGHT_CLIENT_SERVER_CACHE_RECOMPUTE_UPDATES_TIMES);
@@ -147,9 +160,100 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
             }
         }
 
+        // Spec: Full nodes SHOULD provide the best derivable LightClientUpdate for each sync
+        // committee period
+        let attested_period =
+            sync_committee_period_at_slot::<T::EthSpec>(attested_slot, chain_spec)?;
+        let signature_period =
+            sync_committee_period_at_slot::<T::EthSpec>(signature_slot, chain_spec)?;
+        if attested_period == signature_period
+            && sync_aggregate.num_set_bits() >= chain_spec.min_sync_committee_participants as usize
+        {
+            let (finalized_header, finality_branch) = if cached_parts.finalized_block_root.is_zero()
+            {
+                // The attested state has not finalized anything past genesis.
+                (
+                    LightClientHeader::from(BeaconBlockHeader::empty()),
+                    cached_parts.finality_branch.clone(),
+                )
+            } else if let Some(finalized_block) =
+                store.get_blinded_block(&cached_parts.finalized_block_root)?
+            {
+                (
+                    block_to_light_client_header(finalized_block.message()),
+                    cached_parts.finality_branch.clone(),
+                )
+            } else {
+                // Without the finalized block the update can only be a sync committee update.
+                (
+                    LightClientHeader::from(BeaconBlockHeader::empty()),
+                    FixedVector::from_elem(Hash256::zero()),
+                )
+            };
+
+            let new_update = LightClientUpdate {
+                attested_header: block_to_light_client_header(attested_block.message()),
+                next_sync_committee: cached_parts.next_sync_committee.clone(),
+                next_sync_committee_branch: cached_parts.next_sync_committee_branch.clone(),
+                finalized_header,
+                finality_branch,
+                sync_aggregate: sync_aggregate.clone(),
+                signature_slot,
+            };
+
+            let prev_update = match &*self.latest_light_client_update.read() {
+                Some(update)
+                    if sync_committee_period_at_slot::<T::EthSpec>(
+                        update.attested_header.beacon.slot,
+                        chain_spec,
+                    )? == attested_period =>
+                {
+                    Some(update.clone())
+                }
+                _ => None,
+            };
+            let prev_update = match prev_update {
+                Some(update) => Some(update),
+                None => get_light_client_update::<T>(&store, attested_period)?,
+            };
+
+            let should_persist = match &prev_update {
+                Some(prev_update) => is_better_update(&new_update, prev_update, chain_spec)?,
+                None => true,
+            };
+            if should_persist {
+                store.hot_db.put_bytes(
+                    DBColumn::LightClientUpdate.as_str(),
+                    &attested_period.to_le_bytes(),
+                    &new_update.as_ssz_bytes(),
+                )?;
+                *self.latest_light_client_update.write() = Some(new_update);
+            }
+        }
+
         Ok(())
     }
 
+    /// Returns the best `LightClientUpdate`s of up to `count` consecutive sync committee periods
+    /// starting at `start_period`.
+    ///
+    /// Stops at the first period for which no update is known.
+    pub fn get_light_client_updates(
+        &self,
+        store: &BeaconStore<T>,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<LightClientUpdate<T::EthSpec>>, BeaconChainError> {
+        let mut updates = vec![];
+        for period in start_period..start_period.saturating_add(count) {
+            match get_light_client_update::<T>(store, period)? {
+                Some(update) => updates.push(update),
+                None => break,
+            }
+        }
+        Ok(updates)
+    }
+
     /// Retrieves prev block cached data from cache. If not present re-computes by retrieving the
     /// parent state, and inserts an entry to the cache.
     ///
@@ -160,7 +264,7 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         block_root: &Hash256,
         block_state_root: &Hash256,
         block_slot: Slot,
-    ) -> Result<LightClientCachedData, BeaconChainError> {
+    ) -> Result<LightClientCachedData<T::EthSpec>, BeaconChainError> {
         // Attempt to get the value from the cache first.
         if let Some(cached_parts) = self.prev_block_cache.lock().get(block_root) {
             return Ok(cached_parts.clone());
@@ -199,21 +303,127 @@ impl<T: BeaconChainTypes> Default for LightClientServerCache<T> {
 
 type FinalityBranch = FixedVector<Hash256, FinalizedRootProofLen>;
 
+type NextSyncCommitteeBranch = FixedVector<Hash256, NextSyncCommitteeProofLen>;
+
 #[derive(Clone)]
-struct LightClientCachedData {
+struct LightClientCachedData<T: EthSpec> {
     finality_branch: FinalityBranch,
     finalized_block_root: Hash256,
+    next_sync_committee: Arc<SyncCommittee<T>>,
+    next_sync_committee_branch: NextSyncCommitteeBranch,
 }
 
-impl LightClientCachedData {
-    fn from_state<T: EthSpec>(state: &mut BeaconState<T>) -> Result<Self, BeaconChainError> {
+impl<T: EthSpec> LightClientCachedData<T> {
+    fn from_state(state: &mut BeaconState<T>) -> Result<Self, BeaconChainError> {
         Ok(Self {
             finality_branch: state.compute_merkle_proof(FINALIZED_ROOT_INDEX)?.into(),
             finalized_block_root: state.finalized_checkpoint().root,
+            next_sync_committee: state.next_sync_committee()?.clone(),
+            next_sync_committee_branch: state
+                .compute_merkle_proof(NEXT_SYNC_COMMITTEE_INDEX)?
+                .into(),
         })
     }
 }
 
+/// Reads the persisted best `LightClientUpdate` of `period` from disk.
+fn get_light_client_update<T: BeaconChainTypes>(
+    store: &BeaconStore<T>,
+    period: u64,
+) -> Result<Option<LightClientUpdate<T::EthSpec>>, BeaconChainError> {
+    store
+        .hot_db
+        .get_bytes(DBColumn::LightClientUpdate.as_str(), &period.to_le_bytes())?
+        .map(|bytes| {
+            LightClientUpdate::from_ssz_bytes(&bytes)
+                .map_err(|e| BeaconChainError::DBError(store::Error::SszDecodeError(e)))
+        })
+        .transpose()
+}
+
+fn sync_committee_period_at_slot<T: EthSpec>(
+    slot: Slot,
+    spec: &ChainSpec,
+) -> Result<u64, ArithError> {
+    slot.epoch(T::slots_per_epoch()).sync_committee_period(spec)
+}
+
+// Implements spec priorization rules:
+// > Full nodes SHOULD provide the best derivable LightClientUpdate for each sync committee period
+//
+// ref: https://github.com/ethereum/consensus-specs/blob/113c58f9bf9c08867f6f5f633c4d98e0364d612a/specs/altair/light-client/sync-protocol.md#is_better_update
+fn is_better_update<T: EthSpec>(
+    new: &LightClientUpdate<T>,
+    old: &LightClientUpdate<T>,
+    spec: &ChainSpec,
+) -> Result<bool, ArithError> {
+    // Compare supermajority (> 2/3) sync committee participation
+    let max_active_participants = T::sync_committee_size();
+    let new_active_participants = new.sync_aggregate.num_set_bits();
+    let old_active_participants = old.sync_aggregate.num_set_bits();
+    let new_has_supermajority = new_active_participants * 3 >= max_active_participants * 2;
+    let old_has_supermajority = old_active_participants * 3 >= max_active_participants * 2;
+    if new_has_supermajority != old_has_supermajority {
+        return Ok(new_has_supermajority);
+    }
+    if !new_has_supermajority && new_active_participants != old_active_participants {
+        return Ok(new_active_participants > old_active_participants);
+    }
+
+    // Compare presence of relevant sync committee
+    let new_has_relevant_sync_committee = is_sync_committee_update(new)
+        && sync_committee_period_at_slot::<T>(new.attested_header.beacon.slot, spec)?
+            == sync_committee_period_at_slot::<T>(new.signature_slot, spec)?;
+    let old_has_relevant_sync_committee = is_sync_committee_update(old)
+        && sync_committee_period_at_slot::<T>(old.attested_header.beacon.slot, spec)?
+            == sync_committee_period_at_slot::<T>(old.signature_slot, spec)?;
+    if new_has_relevant_sync_committee != old_has_relevant_sync_committee {
+        return Ok(new_has_relevant_sync_committee);
+    }
+
+    // Compare indication of any finality
+    let new_has_finality = is_finality_update(new);
+    let old_has_finality = is_finality_update(old);
+    if new_has_finality != old_has_finality {
+        return Ok(new_has_finality);
+    }
+
+    // Compare sync committee finality
+    if new_has_finality {
+        let new_has_sync_committee_finality =
+            sync_committee_period_at_slot::<T>(new.finalized_header.beacon.slot, spec)?
+                == sync_committee_period_at_slot::<T>(new.attested_header.beacon.slot, spec)?;
+        let old_has_sync_committee_finality =
+            sync_committee_period_at_slot::<T>(old.finalized_header.beacon.slot, spec)?
+                == sync_committee_period_at_slot::<T>(old.attested_header.beacon.slot, spec)?;
+        if new_has_sync_committee_finality != old_has_sync_committee_finality {
+            return Ok(new_has_sync_committee_finality);
+        }
+    }
+
+    // Tiebreaker 1: Sync committee participation beyond supermajority
+    if new_active_participants != old_active_participants {
+        return Ok(new_active_participants > old_active_participants);
+    }
+
+    // Tiebreaker 2: Prefer older data (fewer changes to best)
+    if new.attested_header.beacon.slot != old.attested_header.beacon.slot {
+        return Ok(new.attested_header.beacon.slot < old.attested_header.beacon.slot);
+    }
+    Ok(new.signature_slot < old.signature_slot)
+}
+
+fn is_sync_committee_update<T: EthSpec>(update: &LightClientUpdate<T>) -> bool {
+    update
+        .next_sync_committee_branch
+        .iter()
+        .any(|node| !node.is_zero())
+}
+
+fn is_finality_update<T: EthSpec>(update: &LightClientUpdate<T>) -> bool {
+    update.finality_branch.iter().any(|node| !node.is_zero())
+}
+
 // Implements spec priorization rules:
 // > Full nodes SHOULD provide the LightClientFinalityUpdate with the highest attested_header.beacon.slot (if multiple, highest signature_slot)
 //
@@ -992,6 +992,14 @@ lazy_static! {
         "beacon_backfill_signature_total_seconds",
         "Time spent verifying the signature set during backfill sync, including setup"
     );
+    pub static ref BACKFILL_BLOB_KZG_VERIFICATION_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_backfill_blob_kzg_verification_seconds",
+        "Time spent verifying the KZG proofs of historical blobs during blob backfill"
+    );
+    pub static ref BACKFILL_BLOBS_IMPORTED: Result<IntCounter> = try_create_int_counter(
+        "beacon_backfill_blobs_imported_total",
+        "Count of historical blobs imported by blob backfill"
+    );
 
     /*
      * Pre-finalization block cache.
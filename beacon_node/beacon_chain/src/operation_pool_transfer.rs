@@ -0,0 +1,321 @@
+//! Export and import the operation pool, e.g. to move it to a standby beacon node.
+use crate::beacon_chain::VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT;
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use bls::{verify_signature_sets, PublicKeyBytes, SignatureSet};
+use eth2::lighthouse::{ExportedOperationPool, OperationImportCount, OperationPoolImport};
+use operation_pool::ReceivedPreCapella;
+use slog::debug;
+use state_processing::{
+    common::get_indexed_attestation,
+    per_block_processing::signature_sets::{
+        indexed_attestation_signature_set_from_pubkeys,
+        sync_committee_contribution_signature_set_from_pubkeys,
+    },
+    VerifyOperation, VerifyOperationAt,
+};
+use std::borrow::Cow;
+use std::fmt::Debug;
+use types::{
+    consts::altair::SYNC_COMMITTEE_SUBNET_COUNT, Attestation, BeaconState, EthSpec,
+    SyncCommitteeContribution,
+};
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Export every operation in the op pool, so that it can be imported by another node.
+    pub fn export_operation_pool(&self) -> ExportedOperationPool<T::EthSpec> {
+        ExportedOperationPool {
+            attestations: self.op_pool.get_all_attestations(),
+            sync_contributions: self.op_pool.get_all_sync_contributions(),
+            attester_slashings: self.op_pool.get_all_attester_slashings(),
+            proposer_slashings: self.op_pool.get_all_proposer_slashings(),
+            voluntary_exits: self.op_pool.get_all_voluntary_exits(),
+            bls_to_execution_changes: self.op_pool.get_all_bls_to_execution_changes(),
+        }
+    }
+
+    /// Import operations exported from another node's op pool.
+    ///
+    /// Every operation is re-verified against the head state, including its signature. The
+    /// signatures of attestations and sync contributions are verified in batches. Invalid
+    /// operations are skipped and counted as rejected, and valid operations are only counted as
+    /// imported if they were not already in the op pool.
+    pub fn import_operation_pool(
+        &self,
+        operation_pool: ExportedOperationPool<T::EthSpec>,
+    ) -> Result<OperationPoolImport, BeaconChainError> {
+        let head_snapshot = self.head_snapshot();
+        let head_state = &head_snapshot.beacon_state;
+        let wall_clock_epoch = self.epoch()?;
+        // Operations are only added to the op pool of a node which can propose blocks.
+        let op_pool_enabled = self.eth1_chain.is_some();
+
+        let mut result = OperationPoolImport::default();
+
+        let verified_attestations =
+            self.verify_attestations_for_op_pool(&operation_pool.attestations, head_state)?;
+        for (attestation, verified) in operation_pool
+            .attestations
+            .into_iter()
+            .zip(verified_attestations)
+        {
+            if let Some(attesting_indices) =
+                self.check_import(&mut result.attestations, "attestation", verified)
+            {
+                if op_pool_enabled
+                    && self
+                        .op_pool
+                        .insert_attestation(attestation, attesting_indices)?
+                {
+                    result.attestations.imported += 1;
+                }
+            }
+        }
+
+        let verified_contributions =
+            self.verify_sync_contributions_for_op_pool(&operation_pool.sync_contributions)?;
+        for (contribution, verified) in operation_pool
+            .sync_contributions
+            .into_iter()
+            .zip(verified_contributions)
+        {
+            if self
+                .check_import(
+                    &mut result.sync_contributions,
+                    "sync contribution",
+                    verified,
+                )
+                .is_some()
+                && op_pool_enabled
+                && self.op_pool.insert_sync_contribution(contribution)?
+            {
+                result.sync_contributions.imported += 1;
+            }
+        }
+
+        for slashing in operation_pool.attester_slashings {
+            let verified = slashing.validate(head_state, &self.spec);
+            if let Some(slashing) = self.check_import(
+                &mut result.attester_slashings,
+                "attester slashing",
+                verified,
+            ) {
+                if self.import_attester_slashing(slashing) {
+                    result.attester_slashings.imported += 1;
+                }
+            }
+        }
+
+        for slashing in operation_pool.proposer_slashings {
+            let verified = slashing.validate(head_state, &self.spec);
+            if let Some(slashing) = self.check_import(
+                &mut result.proposer_slashings,
+                "proposer slashing",
+                verified,
+            ) {
+                if self.import_proposer_slashing(slashing) {
+                    result.proposer_slashings.imported += 1;
+                }
+            }
+        }
+
+        for exit in operation_pool.voluntary_exits {
+            // Exits may be valid at the wall clock epoch before they are valid at the head.
+            let verified = exit.validate_at(head_state, wall_clock_epoch, &self.spec);
+            if let Some(exit) =
+                self.check_import(&mut result.voluntary_exits, "voluntary exit", verified)
+            {
+                if self.import_voluntary_exit(exit) {
+                    result.voluntary_exits.imported += 1;
+                }
+            }
+        }
+
+        for change in operation_pool.bls_to_execution_changes {
+            let verified = change.validate(head_state, &self.spec);
+            if let Some(change) = self.check_import(
+                &mut result.bls_to_execution_changes,
+                "BLS to execution change",
+                verified,
+            ) {
+                if self.import_bls_to_execution_change(change, ReceivedPreCapella::No) {
+                    result.bls_to_execution_changes.imported += 1;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Record an imported operation which failed verification in `count`, otherwise return the
+    /// verified operation.
+    fn check_import<V, E: Debug>(
+        &self,
+        count: &mut OperationImportCount,
+        kind: &str,
+        verified: Result<V, E>,
+    ) -> Option<V> {
+        match verified {
+            Ok(verified) => Some(verified),
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Rejected imported operation";
+                    "kind" => kind,
+                    "error" => ?e,
+                );
+                count.rejected += 1;
+                None
+            }
+        }
+    }
+
+    /// Verify attestations from the previous or current epoch of `state`, returning the attesting
+    /// indices of each valid attestation.
+    fn verify_attestations_for_op_pool(
+        &self,
+        attestations: &[Attestation<T::EthSpec>],
+        state: &BeaconState<T::EthSpec>,
+    ) -> Result<Vec<Result<Vec<u64>, BeaconChainError>>, BeaconChainError> {
+        let indexed_attestations = attestations
+            .iter()
+            .map(|attestation| {
+                let committee =
+                    state.get_beacon_committee(attestation.data.slot, attestation.data.index)?;
+                get_indexed_attestation(committee.committee, attestation)
+                    .map_err(|e| BeaconChainError::InvalidImportedOperation(format!("{e:?}")))
+            })
+            .collect::<Vec<_>>();
+
+        let pubkey_cache = self
+            .validator_pubkey_cache
+            .try_read_for(VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT)
+            .ok_or(BeaconChainError::ValidatorPubkeyCacheLockTimeout)?;
+        let signature_sets: Vec<Result<_, BeaconChainError>> = attestations
+            .iter()
+            .zip(indexed_attestations)
+            .map(|(attestation, indexed_attestation)| {
+                let indexed_attestation = indexed_attestation?;
+                let fork = self.spec.fork_at_epoch(attestation.data.target.epoch);
+                let signature_set = indexed_attestation_signature_set_from_pubkeys(
+                    |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
+                    &attestation.signature,
+                    &indexed_attestation,
+                    &fork,
+                    self.genesis_validators_root,
+                    &self.spec,
+                )
+                .map_err(BeaconChainError::SignatureSetError)?;
+                Ok((
+                    indexed_attestation.attesting_indices.to_vec(),
+                    signature_set,
+                ))
+            })
+            .collect();
+
+        Ok(verify_signature_sets_for_op_pool(
+            signature_sets,
+            "attestation",
+        ))
+    }
+
+    /// Verify sync committee contributions.
+    fn verify_sync_contributions_for_op_pool(
+        &self,
+        contributions: &[SyncCommitteeContribution<T::EthSpec>],
+    ) -> Result<Vec<Result<(), BeaconChainError>>, BeaconChainError> {
+        let participant_pubkeys = contributions
+            .iter()
+            .map(|contribution| self.sync_contribution_participant_pubkeys(contribution))
+            .collect::<Vec<_>>();
+
+        let pubkey_cache = self
+            .validator_pubkey_cache
+            .try_read_for(VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT)
+            .ok_or(BeaconChainError::ValidatorPubkeyCacheLockTimeout)?;
+        let signature_sets: Vec<Result<_, BeaconChainError>> = contributions
+            .iter()
+            .zip(participant_pubkeys)
+            .map(|(contribution, participant_pubkeys)| {
+                let participant_pubkeys = participant_pubkeys?;
+                let next_slot_epoch = (contribution.slot + 1).epoch(T::EthSpec::slots_per_epoch());
+                let fork = self.spec.fork_at_epoch(next_slot_epoch);
+                let signature_set =
+                    sync_committee_contribution_signature_set_from_pubkeys::<T::EthSpec, _>(
+                        |pubkey| {
+                            pubkey_cache
+                                .get_pubkey_from_pubkey_bytes(pubkey)
+                                .map(Cow::Borrowed)
+                        },
+                        &participant_pubkeys,
+                        &contribution.signature,
+                        contribution.slot.epoch(T::EthSpec::slots_per_epoch()),
+                        contribution.beacon_block_root,
+                        &fork,
+                        self.genesis_validators_root,
+                        &self.spec,
+                    )
+                    .map_err(BeaconChainError::SignatureSetError)?;
+                Ok(((), signature_set))
+            })
+            .collect();
+
+        Ok(verify_signature_sets_for_op_pool(
+            signature_sets,
+            "sync contribution",
+        ))
+    }
+
+    /// Return the pubkeys of the sync committee members who participated in `contribution`.
+    fn sync_contribution_participant_pubkeys(
+        &self,
+        contribution: &SyncCommitteeContribution<T::EthSpec>,
+    ) -> Result<Vec<PublicKeyBytes>, BeaconChainError> {
+        if contribution.subcommittee_index >= SYNC_COMMITTEE_SUBNET_COUNT
+            || contribution.aggregation_bits.is_zero()
+        {
+            return Err(BeaconChainError::InvalidImportedOperation(format!(
+                "invalid sync contribution for subcommittee {}",
+                contribution.subcommittee_index
+            )));
+        }
+
+        Ok(self
+            .sync_committee_at_next_slot(contribution.slot)?
+            .get_subcommittee_pubkeys(contribution.subcommittee_index as usize)
+            .map_err(|e| BeaconChainError::InvalidImportedOperation(format!("{e:?}")))?
+            .into_iter()
+            .zip(contribution.aggregation_bits.iter())
+            .filter_map(|(pubkey, bit)| bit.then_some(pubkey))
+            .collect())
+    }
+}
+
+/// Verify the signatures of imported operations in a batch, returning the value paired with each
+/// valid signature.
+///
+/// If the batch fails to verify then each signature is verified individually, so that only the
+/// invalid operations are rejected.
+fn verify_signature_sets_for_op_pool<V>(
+    signature_sets: Vec<Result<(V, SignatureSet<'_>), BeaconChainError>>,
+    kind: &str,
+) -> Vec<Result<V, BeaconChainError>> {
+    let batch = signature_sets
+        .iter()
+        .filter_map(|result| result.as_ref().ok().map(|(_, signature_set)| signature_set))
+        .collect::<Vec<_>>();
+    let batch_valid = verify_signature_sets(batch.into_iter());
+
+    signature_sets
+        .into_iter()
+        .map(|result| {
+            let (value, signature_set) = result?;
+            if batch_valid || signature_set.verify() {
+                Ok(value)
+            } else {
+                Err(BeaconChainError::InvalidImportedOperation(format!(
+                    "invalid {kind} signature"
+                )))
+            }
+        })
+        .collect()
+}
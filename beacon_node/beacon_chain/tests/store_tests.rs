@@ -12,7 +12,8 @@ use beacon_chain::test_utils::{
 use beacon_chain::{
     data_availability_checker::MaybeAvailableBlock, historical_blocks::HistoricalBlockError,
     migrate::MigratorConfig, BeaconChain, BeaconChainError, BeaconChainTypes, BeaconSnapshot,
-    BlockError, ChainConfig, NotifyExecutionLayer, ServerSentEventHandler, WhenSlotSkipped,
+    BlockError, ChainConfig, HistoricalBlobError, NotifyExecutionLayer, ServerSentEventHandler,
+    WhenSlotSkipped,
 };
 use eth2_network_config::TRUSTED_SETUP_BYTES;
 use kzg::TrustedSetup;
@@ -25,15 +26,18 @@ use state_processing::{state_advance::complete_state_advance, BlockReplayer};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::convert::TryInto;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use store::chunked_vector::Chunk;
+use store::era::{era_block_slots, era_file_name, EraWriter};
 use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION, STATE_UPPER_LIMIT_NO_RETAIN};
 use store::{
     chunked_vector::{chunk_key, Field},
     get_key_for_col,
     iter::{BlockRootsIterator, StateRootsIterator},
-    BlobInfo, DBColumn, HotColdDB, KeyValueStore, KeyValueStoreOp, LevelDB, StoreConfig,
+    BeaconNodeBackend, BlobInfo, DBColumn, FreezerLayout, HotColdDB, KeyValueStore,
+    KeyValueStoreOp, StoreConfig,
 };
 use tempfile::{tempdir, TempDir};
 use tokio::time::sleep;
@@ -53,7 +57,7 @@ lazy_static! {
 type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<DiskHarnessType<E>>;
 
-fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     get_store_generic(db_path, StoreConfig::default(), test_spec::<E>())
 }
 
@@ -61,7 +65,7 @@ fn get_store_generic(
     db_path: &TempDir,
     config: StoreConfig,
     spec: ChainSpec,
-) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
     let blobs_path = db_path.path().join("blobs_db");
@@ -80,7 +84,7 @@ fn get_store_generic(
 }
 
 fn get_harness(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
     validator_count: usize,
 ) -> TestHarness {
     // Most tests expect to retain historic states, so we use this as the default.
@@ -92,7 +96,7 @@ fn get_harness(
 }
 
 fn get_harness_generic(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
     validator_count: usize,
     chain_config: ChainConfig,
 ) -> TestHarness {
@@ -563,6 +567,140 @@ async fn split_slot_restore() {
     assert_eq!(store.get_split_slot(), split_slot);
 }
 
+// Check that frozen states are unchanged by migrating the freezer to diffs and back.
+#[tokio::test]
+async fn freezer_layout_migration() {
+    let db_path = tempdir().unwrap();
+    let num_blocks = 20 * E::slots_per_epoch();
+
+    let (split_slot, states) = {
+        let store = get_store(&db_path);
+        let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+        harness
+            .extend_chain(
+                num_blocks as usize,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+
+        let split_slot = store.get_split_slot();
+        let states = (0..split_slot.as_u64())
+            .map(|slot| {
+                store
+                    .load_cold_state_by_slot(Slot::new(slot))
+                    .unwrap()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        (split_slot, states)
+    };
+    assert!(split_slot > StoreConfig::default().slots_per_restore_point);
+
+    let count_diffs = |store: &HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>| {
+        store
+            .cold_db
+            .iter_column_keys::<Vec<u8>>(DBColumn::BeaconStateDiff)
+            .count() as u64
+    };
+    let diffs_config = StoreConfig {
+        freezer_layout: FreezerLayout::Diffs,
+        ..StoreConfig::default()
+    };
+
+    // The on-disk layout takes precedence over the config until the freezer is migrated.
+    let store = get_store_generic(&db_path, diffs_config.clone(), test_spec::<E>());
+    assert_eq!(
+        store.get_config().freezer_layout,
+        FreezerLayout::RestorePoints
+    );
+    assert_eq!(count_diffs(&store), 0);
+    store.migrate_freezer_layout(FreezerLayout::Diffs).unwrap();
+    drop(store);
+
+    // Every epoch boundary state between restore points should now be stored as a diff.
+    let store = get_store_generic(&db_path, StoreConfig::default(), test_spec::<E>());
+    assert_eq!(store.get_config().freezer_layout, FreezerLayout::Diffs);
+    let slots_per_restore_point = store.get_config().slots_per_restore_point;
+    let num_restore_points = (split_slot.as_u64() - 1) / slots_per_restore_point + 1;
+    assert_eq!(
+        count_diffs(&store),
+        split_slot.epoch(E::slots_per_epoch()).as_u64() - num_restore_points
+    );
+    for (slot, state) in states.iter().enumerate() {
+        assert_eq!(
+            &store
+                .load_cold_state_by_slot(Slot::new(slot as u64))
+                .unwrap()
+                .unwrap(),
+            state,
+            "state at slot {slot} should match"
+        );
+    }
+
+    // Migrating back deletes the diffs.
+    store
+        .migrate_freezer_layout(FreezerLayout::RestorePoints)
+        .unwrap();
+    drop(store);
+
+    let store = get_store_generic(&db_path, diffs_config, test_spec::<E>());
+    assert_eq!(
+        store.get_config().freezer_layout,
+        FreezerLayout::RestorePoints
+    );
+    assert_eq!(count_diffs(&store), 0);
+    for (slot, state) in states.iter().enumerate() {
+        assert_eq!(
+            &store
+                .load_cold_state_by_slot(Slot::new(slot as u64))
+                .unwrap()
+                .unwrap(),
+            state
+        );
+    }
+}
+
+// Check that frozen states can be loaded with the diffs layout when restore points are further apart
+// than `SLOTS_PER_HISTORICAL_ROOT`.
+#[tokio::test]
+async fn freezer_diffs_sparse_restore_points() {
+    let db_path = tempdir().unwrap();
+    let slots_per_restore_point = 4 * E::slots_per_historical_root() as u64;
+    let store = get_store_generic(
+        &db_path,
+        StoreConfig {
+            slots_per_restore_point,
+            freezer_layout: FreezerLayout::Diffs,
+            ..StoreConfig::default()
+        },
+        test_spec::<E>(),
+    );
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            (slots_per_restore_point + 20 * E::slots_per_epoch()) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let split_slot = store.get_split_slot();
+    assert!(split_slot > slots_per_restore_point + E::slots_per_historical_root() as u64);
+
+    for slot in (0..split_slot.as_u64()).map(Slot::new) {
+        let state = store.load_cold_state_by_slot(slot).unwrap().unwrap();
+        assert_eq!(state.slot(), slot);
+        assert_eq!(
+            Some(state.canonical_root()),
+            harness.chain.state_root_at_slot(slot).unwrap(),
+            "state at slot {slot} should match"
+        );
+    }
+}
+
 // Check attestation processing and `load_epoch_boundary_state` in the presence of a split DB.
 // This is a bit of a monster test in that it tests lots of different things, but until they're
 // tested elsewhere, this is as good a place as any.
@@ -2642,6 +2780,169 @@ async fn weak_subjectivity_sync_test(slots: Vec<Slot>, checkpoint_slot: Slot) {
     assert_eq!(store.get_anchor_slot(), None);
 }
 
+/// Write era `era` of the harness chain to `dir`, optionally corrupting the signature of its first
+/// block.
+async fn write_era_file(harness: &TestHarness, dir: &Path, era: u64, corrupt_first_block: bool) {
+    let slots = era_block_slots::<E>(era);
+    let mut blocks: Vec<SignedBeaconBlock<E>> = vec![];
+    for (block_root, _) in harness
+        .chain
+        .forwards_iter_block_roots_until(slots.start, slots.end - 1)
+        .unwrap()
+        .map(Result::unwrap)
+    {
+        let block = harness.chain.get_block(&block_root).await.unwrap().unwrap();
+        if block.slot() >= slots.start && blocks.last().map(|b| b.slot()) != Some(block.slot()) {
+            blocks.push(block);
+        }
+    }
+    if corrupt_first_block {
+        *blocks[0].signature_mut() = Signature::empty();
+    }
+
+    let state_root = harness
+        .chain
+        .state_root_at_slot(slots.end)
+        .unwrap()
+        .unwrap();
+    let state = harness
+        .chain
+        .get_state(&state_root, Some(slots.end))
+        .unwrap()
+        .unwrap();
+
+    let mut writer = EraWriter::<_, E>::new(vec![], era).unwrap();
+    for block in &blocks {
+        writer.write_block(block).unwrap();
+    }
+    let bytes = writer.finish(&state).unwrap();
+    std::fs::write(dir.join(era_file_name("minimal", era, &state)), bytes).unwrap();
+}
+
+#[tokio::test]
+async fn weak_subjectivity_sync_from_era_files() {
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let num_initial_slots = slots_per_era * 3 + E::slots_per_epoch();
+    let checkpoint_slot = Slot::new(slots_per_era * 2 + E::slots_per_epoch() * 2);
+    // Skip some slots, including the first slot of an era.
+    let skipped_slots = [3, slots_per_era, slots_per_era * 2 - 1];
+    let slots = (1..num_initial_slots)
+        .filter(|slot| !skipped_slots.contains(slot))
+        .map(Slot::new)
+        .collect::<Vec<_>>();
+
+    let temp1 = tempdir().unwrap();
+    let full_store = get_store(&temp1);
+    let harness = get_harness(full_store.clone(), LOW_VALIDATOR_COUNT);
+    let all_validators = (0..LOW_VALIDATOR_COUNT).collect::<Vec<_>>();
+
+    let (genesis_state, genesis_state_root) = harness.get_current_state_and_root();
+    harness
+        .add_attested_blocks_at_slots(
+            genesis_state.clone(),
+            genesis_state_root,
+            &slots,
+            &all_validators,
+        )
+        .await;
+
+    let wss_block_root = harness
+        .chain
+        .block_root_at_slot(checkpoint_slot, WhenSlotSkipped::Prev)
+        .unwrap()
+        .unwrap();
+    let wss_state_root = harness
+        .chain
+        .state_root_at_slot(checkpoint_slot)
+        .unwrap()
+        .unwrap();
+    let wss_block = full_store.get_full_block(&wss_block_root).unwrap().unwrap();
+    let wss_blobs_opt = full_store.get_blobs(&wss_block_root).unwrap();
+    let wss_state = full_store
+        .get_state(&wss_state_root, Some(checkpoint_slot))
+        .unwrap()
+        .unwrap();
+
+    // Initialise a new beacon chain from the checkpoint.
+    let (shutdown_tx, _shutdown_rx) = futures::channel::mpsc::channel(1);
+    let log = test_logger();
+    let temp2 = tempdir().unwrap();
+    let store = get_store(&temp2);
+    let spec = test_spec::<E>();
+    let trusted_setup: TrustedSetup = serde_json::from_reader(TRUSTED_SETUP_BYTES)
+        .map_err(|e| println!("Unable to read trusted setup file: {}", e))
+        .unwrap();
+    let mock =
+        mock_execution_layer_from_parts(&harness.spec, harness.runtime.task_executor.clone());
+    let slot_clock = TestingSlotClock::new(
+        Slot::new(0),
+        Duration::from_secs(harness.chain.genesis_time),
+        Duration::from_secs(spec.seconds_per_slot),
+    );
+    slot_clock.set_slot(harness.get_current_slot().as_u64());
+
+    let beacon_chain = BeaconChainBuilder::<DiskHarnessType<E>>::new(MinimalEthSpec)
+        .store(store.clone())
+        .custom_spec(spec)
+        .task_executor(harness.chain.task_executor.clone())
+        .logger(log.clone())
+        .weak_subjectivity_state(wss_state, wss_block.clone(), wss_blobs_opt, genesis_state)
+        .unwrap()
+        .store_migrator_config(MigratorConfig::default().blocking())
+        .dummy_eth1_backend()
+        .expect("should build dummy backend")
+        .slot_clock(slot_clock)
+        .shutdown_sender(shutdown_tx)
+        .chain_config(ChainConfig::default())
+        .event_handler(Some(ServerSentEventHandler::new_with_capacity(log, 1)))
+        .execution_layer(Some(mock.el))
+        .trusted_setup(trusted_setup)
+        .build()
+        .expect("should build");
+    assert_eq!(store.get_oldest_block_slot(), wss_block.slot());
+
+    let era_dir = tempdir().unwrap();
+    for era in 1..=3 {
+        write_era_file(&harness, era_dir.path(), era, era == 3).await;
+    }
+
+    // The newest era has an invalid signature, so nothing is imported.
+    assert!(matches!(
+        beacon_chain.import_era_files(era_dir.path()).unwrap_err(),
+        BeaconChainError::HistoricalBlockError(HistoricalBlockError::InvalidSignature)
+    ));
+    assert_eq!(store.get_oldest_block_slot(), wss_block.slot());
+
+    // Without the oldest era, the import stops at the start of era 2.
+    std::fs::remove_dir_all(era_dir.path()).unwrap();
+    std::fs::create_dir(era_dir.path()).unwrap();
+    for era in 2..=3 {
+        write_era_file(&harness, era_dir.path(), era, false).await;
+    }
+    beacon_chain.import_era_files(era_dir.path()).unwrap();
+    let oldest_block_slot = store.get_oldest_block_slot();
+    assert!(oldest_block_slot >= era_block_slots::<E>(2).start);
+    assert!(oldest_block_slot < era_block_slots::<E>(3).start);
+
+    // With all eras, the import reaches genesis.
+    write_era_file(&harness, era_dir.path(), 1, false).await;
+    beacon_chain.import_era_files(era_dir.path()).unwrap();
+    assert_eq!(store.get_oldest_block_slot(), 0);
+
+    let forwards = beacon_chain
+        .forwards_iter_block_roots_until(Slot::new(0), checkpoint_slot)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+    let expected = harness
+        .chain
+        .forwards_iter_block_roots_until(Slot::new(0), checkpoint_slot)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+    assert_eq!(forwards, expected);
+}
+
 /// Test that blocks and attestations that refer to states around an unaligned split state are
 /// processed correctly.
 #[tokio::test]
@@ -3421,6 +3722,155 @@ async fn change_to_separate_blobs_db_before_deneb() {
     assert_eq!(store.get_blob_info(), init_blob_info);
 }
 
+/// Return the blobs of the canonical blocks in `start_slot..end_slot`, ordered by slot and index.
+fn get_blobs_in_range(
+    harness: &TestHarness,
+    start_slot: Slot,
+    end_slot: Slot,
+) -> Vec<Arc<BlobSidecar<E>>> {
+    let mut block_roots = vec![];
+    for (block_root, _) in harness
+        .chain
+        .forwards_iter_block_roots_until(start_slot, end_slot - 1)
+        .unwrap()
+        .map(Result::unwrap)
+    {
+        if block_roots.last() != Some(&block_root) {
+            block_roots.push(block_root);
+        }
+    }
+    block_roots
+        .into_iter()
+        .filter(|block_root| {
+            let block = harness
+                .chain
+                .get_blinded_block(block_root)
+                .unwrap()
+                .unwrap();
+            block.slot() >= start_slot
+        })
+        .flat_map(|block_root| {
+            harness
+                .chain
+                .store
+                .get_blobs(&block_root)
+                .unwrap()
+                .map_or(vec![], |blobs| blobs.to_vec())
+        })
+        .collect()
+}
+
+/// Check that historical blobs are only imported if every blob of the batch is valid.
+#[tokio::test]
+async fn import_historical_blob_batch() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+
+    let Some(deneb_fork_epoch) = store.get_chain_spec().deneb_fork_epoch else {
+        // No-op prior to Deneb.
+        return;
+    };
+    let start_slot = deneb_fork_epoch.start_slot(E::slots_per_epoch());
+
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+    harness
+        .extend_chain(
+            (E::slots_per_epoch() * 8) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    // Prune the blobs of the first epochs after the fork, keeping a copy of them to import.
+    let data_availability_boundary = deneb_fork_epoch + 2;
+    let end_slot = data_availability_boundary.start_slot(E::slots_per_epoch());
+    let blobs = get_blobs_in_range(&harness, start_slot, end_slot);
+    assert!(blobs.len() >= 2, "expected at least two blobs");
+    store
+        .try_prune_blobs(true, data_availability_boundary)
+        .unwrap();
+    assert_eq!(store.get_blob_info().oldest_blob_slot, Some(end_slot));
+    assert!(get_blobs_in_range(&harness, start_slot, end_slot).is_empty());
+
+    let import = |blobs: Vec<Arc<BlobSidecar<E>>>| {
+        harness
+            .chain
+            .import_historical_blob_batch(start_slot, end_slot, blobs)
+    };
+    let modify = |i: usize, f: &dyn Fn(&mut BlobSidecar<E>)| {
+        let mut modified = blobs.clone();
+        let mut blob = (*modified[i]).clone();
+        f(&mut blob);
+        modified[i] = Arc::new(blob);
+        modified
+    };
+
+    // The batch must end at the oldest blob slot.
+    assert!(matches!(
+        harness
+            .chain
+            .import_historical_blob_batch(start_slot, end_slot - 1, blobs.clone()),
+        Err(BeaconChainError::HistoricalBlobError(
+            HistoricalBlobError::NonContiguousBatch { .. }
+        ))
+    ));
+
+    // Every blob of every block must be provided.
+    let missing = blobs[..blobs.len() - 1].to_vec();
+    assert!(matches!(
+        import(missing),
+        Err(BeaconChainError::HistoricalBlobError(
+            HistoricalBlobError::MissingBlobs { .. }
+        ))
+    ));
+
+    // Blobs must not be duplicated.
+    let mut duplicated = blobs.clone();
+    duplicated.push(blobs[0].clone());
+    assert!(matches!(
+        import(duplicated),
+        Err(BeaconChainError::HistoricalBlobError(
+            HistoricalBlobError::InvalidBlobIndex { .. }
+        ))
+    ));
+
+    // Blob indices must be covered by the commitments of the block.
+    let wrong_index = modify(0, &|blob| blob.index = E::max_blobs_per_block() as u64);
+    assert!(matches!(
+        import(wrong_index),
+        Err(BeaconChainError::HistoricalBlobError(
+            HistoricalBlobError::InvalidBlobIndex { .. }
+        ))
+    ));
+
+    // Blob commitments must match the commitments of the block.
+    let mismatched_commitment = modify(0, &|blob| blob.kzg_commitment = blobs[1].kzg_commitment);
+    assert!(matches!(
+        import(mismatched_commitment),
+        Err(BeaconChainError::HistoricalBlobError(
+            HistoricalBlobError::MismatchedCommitment { .. }
+        ))
+    ));
+
+    // Blob proofs must be valid.
+    let bad_proof = modify(0, &|blob| blob.kzg_proof = blobs[1].kzg_proof);
+    assert!(matches!(
+        import(bad_proof),
+        Err(BeaconChainError::HistoricalBlobError(
+            HistoricalBlobError::InvalidKzgProof(_)
+        ))
+    ));
+
+    // Nothing is imported from an invalid batch.
+    assert_eq!(store.get_blob_info().oldest_blob_slot, Some(end_slot));
+    assert!(get_blobs_in_range(&harness, start_slot, end_slot).is_empty());
+
+    // A valid batch is imported, and extends the stored blobs back to its start.
+    assert_eq!(import(blobs.clone()).unwrap(), blobs.len());
+    assert_eq!(store.get_blob_info().oldest_blob_slot, Some(start_slot));
+    assert_eq!(get_blobs_in_range(&harness, start_slot, end_slot), blobs);
+}
+
 /// Check that there are blob sidecars (or not) at every slot in the range.
 fn check_blob_existence(
     harness: &TestHarness,
@@ -3593,7 +4043,10 @@ fn check_finalization(harness: &TestHarness, expected_slot: u64) {
 }
 
 /// Check that the HotColdDB's split_slot is equal to the start slot of the last finalized epoch.
-fn check_split_slot(harness: &TestHarness, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) {
+fn check_split_slot(
+    harness: &TestHarness,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+) {
     let split_slot = store.get_split_slot();
     assert_eq!(
         harness
@@ -38,6 +38,8 @@
 //! checks the queues to see if there are more parcels of work that can be spawned in a new worker
 //! task.
 
+use crate::load_shedding::LoadShedder;
+use crate::queue_state::QUEUE_STATE_UPDATE_INTERVAL;
 use crate::work_reprocessing_queue::{
     QueuedBackfillBatch, QueuedGossipBlock, ReprocessQueueMessage,
 };
@@ -46,6 +48,9 @@ use futures::task::Poll;
 use lighthouse_network::{MessageId, NetworkGlobals, PeerId};
 use logging::TimeLatch;
 use parking_lot::Mutex;
+pub use queue_state::{
+    BeaconProcessorQueueState, QueueStateHandle, ReprocessQueueState, WorkQueueState,
+};
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, trace, warn, Logger};
 use slot_clock::SlotClock;
@@ -56,7 +61,7 @@ use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::Context;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use task_executor::TaskExecutor;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::TrySendError;
@@ -68,7 +73,9 @@ use work_reprocessing_queue::{
     QueuedUnaggregate, ReadyWork,
 };
 
+mod load_shedding;
 mod metrics;
+mod queue_state;
 pub mod work_reprocessing_queue;
 
 /// The maximum size of the channel for work events to the `BeaconProcessor`.
@@ -187,6 +194,10 @@ const MAX_BLS_TO_EXECUTION_CHANGE_QUEUE_LEN: usize = 16_384;
 /// will be stored before we start dropping them.
 const MAX_LIGHT_CLIENT_BOOTSTRAP_QUEUE_LEN: usize = 1_024;
 
+/// The maximum number of queued `LightClientUpdatesByRangeRequest` objects received from the
+/// network RPC that will be stored before we start dropping them.
+const MAX_LIGHT_CLIENT_UPDATES_BY_RANGE_QUEUE_LEN: usize = 1_024;
+
 /// The maximum number of priority-0 (highest priority) messages that will be queued before
 /// they begin to be dropped.
 const MAX_API_REQUEST_P0_QUEUE_LEN: usize = 1_024;
@@ -243,6 +254,7 @@ pub const BLOCKS_BY_ROOTS_REQUEST: &str = "blocks_by_roots_request";
 pub const BLOBS_BY_RANGE_REQUEST: &str = "blobs_by_range_request";
 pub const BLOBS_BY_ROOTS_REQUEST: &str = "blobs_by_roots_request";
 pub const LIGHT_CLIENT_BOOTSTRAP_REQUEST: &str = "light_client_bootstrap";
+pub const LIGHT_CLIENT_UPDATES_BY_RANGE_REQUEST: &str = "light_client_updates_by_range";
 pub const UNKNOWN_BLOCK_ATTESTATION: &str = "unknown_block_attestation";
 pub const UNKNOWN_BLOCK_AGGREGATE: &str = "unknown_block_aggregate";
 pub const UNKNOWN_LIGHT_CLIENT_UPDATE: &str = "unknown_light_client_update";
@@ -258,6 +270,7 @@ pub struct BeaconProcessorConfig {
     pub max_gossip_attestation_batch_size: usize,
     pub max_gossip_aggregate_batch_size: usize,
     pub enable_backfill_rate_limiting: bool,
+    pub enable_adaptive_scheduling: bool,
 }
 
 impl Default for BeaconProcessorConfig {
@@ -269,6 +282,7 @@ impl Default for BeaconProcessorConfig {
             max_gossip_attestation_batch_size: DEFAULT_MAX_GOSSIP_ATTESTATION_BATCH_SIZE,
             max_gossip_aggregate_batch_size: DEFAULT_MAX_GOSSIP_AGGREGATE_BATCH_SIZE,
             enable_backfill_rate_limiting: true,
+            enable_adaptive_scheduling: false,
         }
     }
 }
@@ -279,6 +293,7 @@ pub struct BeaconProcessorChannels<E: EthSpec> {
     pub beacon_processor_rx: mpsc::Receiver<WorkEvent<E>>,
     pub work_reprocessing_tx: mpsc::Sender<ReprocessQueueMessage>,
     pub work_reprocessing_rx: mpsc::Receiver<ReprocessQueueMessage>,
+    pub queue_state: QueueStateHandle,
 }
 
 impl<E: EthSpec> BeaconProcessorChannels<E> {
@@ -293,6 +308,7 @@ impl<E: EthSpec> BeaconProcessorChannels<E> {
             beacon_processor_rx,
             work_reprocessing_rx,
             work_reprocessing_tx,
+            queue_state: QueueStateHandle::default(),
         }
     }
 }
@@ -305,8 +321,9 @@ impl<E: EthSpec> Default for BeaconProcessorChannels<E> {
 
 /// A simple first-in-first-out queue with a maximum length.
 struct FifoQueue<T> {
-    queue: VecDeque<T>,
+    queue: VecDeque<(Instant, T)>,
     max_length: usize,
+    dropped: u64,
 }
 
 impl<T> FifoQueue<T> {
@@ -315,6 +332,7 @@ impl<T> FifoQueue<T> {
         Self {
             queue: VecDeque::default(),
             max_length,
+            dropped: 0,
         }
     }
 
@@ -323,6 +341,7 @@ impl<T> FifoQueue<T> {
     /// Drops `item` if the queue is full.
     pub fn push(&mut self, item: T, item_desc: &str, log: &Logger) {
         if self.queue.len() == self.max_length {
+            self.dropped += 1;
             error!(
                 log,
                 "Work queue is full";
@@ -331,25 +350,50 @@ impl<T> FifoQueue<T> {
                 "queue" => item_desc,
             )
         } else {
-            self.queue.push_back(item);
+            self.queue.push_back((Instant::now(), item));
         }
     }
 
     /// Remove the next item from the queue.
     pub fn pop(&mut self) -> Option<T> {
-        self.queue.pop_front()
+        self.queue.pop_front().map(|(_, item)| item)
+    }
+
+    /// Remove the next item from the queue, unless `defer` returns `true`.
+    ///
+    /// `defer` is only called if the queue is not empty, with the time that the next item has
+    /// spent in the queue.
+    pub fn pop_unless(&mut self, defer: impl FnOnce(Duration) -> bool) -> Option<T> {
+        let (added, _) = self.queue.front()?;
+        if defer(added.elapsed()) {
+            None
+        } else {
+            self.pop()
+        }
     }
 
     /// Returns the current length of the queue.
     pub fn len(&self) -> usize {
         self.queue.len()
     }
+
+    /// Returns the state of the queue, which holds work of type `work_type`.
+    pub fn state(&self, work_type: &'static str) -> WorkQueueState {
+        WorkQueueState {
+            work_type,
+            length: self.queue.len(),
+            capacity: self.max_length,
+            dropped: self.dropped,
+            oldest_item_age: self.queue.front().map(|(added, _)| added.elapsed()),
+        }
+    }
 }
 
 /// A simple last-in-first-out queue with a maximum length.
 struct LifoQueue<T> {
-    queue: VecDeque<T>,
+    queue: VecDeque<(Instant, T)>,
     max_length: usize,
+    dropped: u64,
 }
 
 impl<T> LifoQueue<T> {
@@ -358,6 +402,7 @@ impl<T> LifoQueue<T> {
         Self {
             queue: VecDeque::default(),
             max_length,
+            dropped: 0,
         }
     }
 
@@ -367,13 +412,14 @@ impl<T> LifoQueue<T> {
     pub fn push(&mut self, item: T) {
         if self.queue.len() == self.max_length {
             self.queue.pop_back();
+            self.dropped += 1;
         }
-        self.queue.push_front(item);
+        self.queue.push_front((Instant::now(), item));
     }
 
     /// Remove the next item from the queue.
     pub fn pop(&mut self) -> Option<T> {
-        self.queue.pop_front()
+        self.queue.pop_front().map(|(_, item)| item)
     }
 
     /// Returns `true` if the queue is full.
@@ -385,6 +431,17 @@ impl<T> LifoQueue<T> {
     pub fn len(&self) -> usize {
         self.queue.len()
     }
+
+    /// Returns the state of the queue, which holds work of type `work_type`.
+    pub fn state(&self, work_type: &'static str) -> WorkQueueState {
+        WorkQueueState {
+            work_type,
+            length: self.queue.len(),
+            capacity: self.max_length,
+            dropped: self.dropped,
+            oldest_item_age: self.queue.back().map(|(added, _)| added.elapsed()),
+        }
+    }
 }
 
 /// A handle that sends a message on the provided channel to a receiver when it gets dropped.
@@ -620,6 +677,7 @@ pub enum Work<E: EthSpec> {
     BlobsByRootsRequest(BlockingFn),
     GossipBlsToExecutionChange(BlockingFn),
     LightClientBootstrapRequest(BlockingFn),
+    LightClientUpdatesByRangeRequest(BlockingFn),
     ApiRequestP0(BlockingOrAsync),
     ApiRequestP1(BlockingOrAsync),
 }
@@ -659,6 +717,7 @@ impl<E: EthSpec> Work<E> {
             Work::BlobsByRangeRequest(_) => BLOBS_BY_RANGE_REQUEST,
             Work::BlobsByRootsRequest(_) => BLOBS_BY_ROOTS_REQUEST,
             Work::LightClientBootstrapRequest(_) => LIGHT_CLIENT_BOOTSTRAP_REQUEST,
+            Work::LightClientUpdatesByRangeRequest(_) => LIGHT_CLIENT_UPDATES_BY_RANGE_REQUEST,
             Work::UnknownBlockAttestation { .. } => UNKNOWN_BLOCK_ATTESTATION,
             Work::UnknownBlockAggregate { .. } => UNKNOWN_BLOCK_AGGREGATE,
             Work::GossipBlsToExecutionChange(_) => GOSSIP_BLS_TO_EXECUTION_CHANGE,
@@ -669,10 +728,16 @@ impl<E: EthSpec> Work<E> {
     }
 }
 
+/// Sent by a worker when it has completed a task.
+struct FinishedWork {
+    work_id: &'static str,
+    elapsed: Duration,
+}
+
 /// Unifies all the messages processed by the `BeaconProcessor`.
 enum InboundEvent<E: EthSpec> {
     /// A worker has completed a task and is free.
-    WorkerIdle,
+    WorkerIdle(FinishedWork),
     /// There is new work to be done.
     WorkEvent(WorkEvent<E>),
     /// A work event that was queued for re-processing has become ready.
@@ -685,7 +750,7 @@ enum InboundEvent<E: EthSpec> {
 /// control (specifically in the ordering of event processing).
 struct InboundEvents<E: EthSpec> {
     /// Used by workers when they finish a task.
-    idle_rx: mpsc::Receiver<()>,
+    idle_rx: mpsc::Receiver<FinishedWork>,
     /// Used by upstream processes to send new work to the `BeaconProcessor`.
     event_rx: mpsc::Receiver<WorkEvent<E>>,
     /// Used internally for queuing work ready to be re-processed.
@@ -699,8 +764,8 @@ impl<E: EthSpec> Stream for InboundEvents<E> {
         // Always check for idle workers before anything else. This allows us to ensure that a big
         // stream of new events doesn't suppress the processing of existing events.
         match self.idle_rx.poll_recv(cx) {
-            Poll::Ready(Some(())) => {
-                return Poll::Ready(Some(InboundEvent::WorkerIdle));
+            Poll::Ready(Some(finished_work)) => {
+                return Poll::Ready(Some(InboundEvent::WorkerIdle(finished_work)));
             }
             Poll::Ready(None) => {
                 return Poll::Ready(None);
@@ -743,6 +808,7 @@ pub struct BeaconProcessor<E: EthSpec> {
     pub executor: TaskExecutor,
     pub current_workers: usize,
     pub config: BeaconProcessorConfig,
+    pub queue_state: QueueStateHandle,
     pub log: Logger,
 }
 
@@ -768,7 +834,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
         maximum_gossip_clock_disparity: Duration,
     ) -> Result<(), String> {
         // Used by workers to communicate that they are finished a task.
-        let (idle_tx, idle_rx) = mpsc::channel::<()>(MAX_IDLE_QUEUE_LEN);
+        let (idle_tx, idle_rx) = mpsc::channel::<FinishedWork>(MAX_IDLE_QUEUE_LEN);
 
         // Using LIFO queues for attestations since validator profits rely upon getting fresh
         // attestations into blocks. Additionally, later attestations contain more information than
@@ -821,6 +887,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
             FifoQueue::new(MAX_BLS_TO_EXECUTION_CHANGE_QUEUE_LEN);
 
         let mut lcbootstrap_queue = FifoQueue::new(MAX_LIGHT_CLIENT_BOOTSTRAP_QUEUE_LEN);
+        let mut lcupdates_queue = FifoQueue::new(MAX_LIGHT_CLIENT_UPDATES_BY_RANGE_QUEUE_LEN);
 
         let mut api_request_p0_queue = FifoQueue::new(MAX_API_REQUEST_P0_QUEUE_LEN);
         let mut api_request_p1_queue = FifoQueue::new(MAX_API_REQUEST_P1_QUEUE_LEN);
@@ -833,7 +900,8 @@ impl<E: EthSpec> BeaconProcessor<E> {
             ready_work_tx,
             work_reprocessing_rx,
             &self.executor,
-            slot_clock,
+            slot_clock.clone(),
+            self.queue_state.clone(),
             self.log.clone(),
             maximum_gossip_clock_disparity,
         )?;
@@ -850,11 +918,20 @@ impl<E: EthSpec> BeaconProcessor<E> {
             };
 
             let enable_backfill_rate_limiting = self.config.enable_backfill_rate_limiting;
+            let mut load_shedder = self
+                .config
+                .enable_adaptive_scheduling
+                .then(LoadShedder::default);
+            let mut last_queue_state_update = Instant::now();
 
             loop {
                 let work_event = match inbound_events.next().await {
-                    Some(InboundEvent::WorkerIdle) => {
+                    Some(InboundEvent::WorkerIdle(finished_work)) => {
                         self.current_workers = self.current_workers.saturating_sub(1);
+                        if let Some(load_shedder) = load_shedder.as_mut() {
+                            load_shedder
+                                .observe_latency(finished_work.work_id, finished_work.elapsed);
+                        }
                         None
                     }
                     Some(InboundEvent::WorkEvent(event)) if enable_backfill_rate_limiting => {
@@ -933,6 +1010,60 @@ impl<E: EthSpec> BeaconProcessor<E> {
                 }
 
                 let can_spawn = self.current_workers < self.config.max_workers;
+                let free_workers = self.config.max_workers.saturating_sub(self.current_workers);
+
+                // Estimate whether the queued high-priority work can be completed before the next
+                // deadline in the slot. If not, low-value work will be shed or deferred.
+                if let Some(load_shedder) = load_shedder.as_mut() {
+                    load_shedder.update_pressure(
+                        &[
+                            (CHAIN_SEGMENT, chain_segment_queue.len()),
+                            (RPC_BLOCK, rpc_block_queue.len()),
+                            (RPC_BLOBS, rpc_blob_queue.len()),
+                            (DELAYED_IMPORT_BLOCK, delayed_block_queue.len()),
+                            (GOSSIP_BLOCK, gossip_block_queue.len()),
+                            (GOSSIP_BLOBS_SIDECAR, gossip_blob_queue.len()),
+                            (API_REQUEST_P0, api_request_p0_queue.len()),
+                            (
+                                GOSSIP_AGGREGATE_BATCH,
+                                aggregate_queue
+                                    .len()
+                                    .div_ceil(self.config.max_gossip_aggregate_batch_size.max(1)),
+                            ),
+                            (
+                                GOSSIP_ATTESTATION_BATCH,
+                                attestation_queue
+                                    .len()
+                                    .div_ceil(self.config.max_gossip_attestation_batch_size.max(1)),
+                            ),
+                            (GOSSIP_SYNC_CONTRIBUTION, sync_contribution_queue.len()),
+                            (GOSSIP_SYNC_SIGNATURE, sync_message_queue.len()),
+                        ],
+                        self.config.max_workers,
+                        load_shedding::time_to_next_deadline(&slot_clock),
+                    );
+                }
+                let should_shed = |work_id| {
+                    load_shedder
+                        .as_ref()
+                        .map_or(false, |load_shedder| load_shedder.should_shed(work_id))
+                };
+                let should_defer = |work_id, waited| {
+                    load_shedder.as_ref().map_or(false, |load_shedder| {
+                        load_shedder.should_defer(work_id, free_workers, waited)
+                    })
+                };
+                let defer_new_work = |work_id| {
+                    let defer = should_defer(work_id, Duration::ZERO);
+                    if defer {
+                        metrics::inc_counter_vec(
+                            &metrics::BEACON_PROCESSOR_ADAPTIVE_DEFERRED_TOTAL,
+                            &[work_id],
+                        );
+                    }
+                    defer
+                };
+
                 let drop_during_sync = work_event
                     .as_ref()
                     .map_or(false, |event| event.drop_during_sync);
@@ -1135,10 +1266,21 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         } else if let Some(item) = api_request_p1_queue.pop() {
                             self.spawn_worker(item, idle_tx);
                         // Handle backfill sync chain segments.
-                        } else if let Some(item) = backfill_chain_segment.pop() {
+                        //
+                        // Backfill and light client requests are low-value, so they may be deferred
+                        // by the adaptive scheduler.
+                        } else if let Some(item) = backfill_chain_segment
+                            .pop_unless(|waited| should_defer(CHAIN_SEGMENT_BACKFILL, waited))
+                        {
                             self.spawn_worker(item, idle_tx);
                         // This statement should always be the final else statement.
-                        } else if let Some(item) = lcbootstrap_queue.pop() {
+                        } else if let Some(item) = lcbootstrap_queue.pop_unless(|waited| {
+                            should_defer(LIGHT_CLIENT_BOOTSTRAP_REQUEST, waited)
+                        }) {
+                            self.spawn_worker(item, idle_tx);
+                        } else if let Some(item) = lcupdates_queue.pop_unless(|waited| {
+                            should_defer(LIGHT_CLIENT_UPDATES_BY_RANGE_REQUEST, waited)
+                        }) {
                             self.spawn_worker(item, idle_tx);
                         } else {
                             // Let the journal know that a worker is freed and there's nothing else
@@ -1183,6 +1325,33 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         let work_id = work.str_id();
 
                         match work {
+                            // Drop low-value attestations rather than queueing them whilst the
+                            // processor is under pressure. Attestations on subnets we aren't
+                            // aggregating on are only being propagated.
+                            Work::GossipAttestation {
+                                ref attestation, ..
+                            } if !can_spawn
+                                && !attestation.should_import
+                                && should_shed(work_id) => {}
+                            Work::UnknownBlockAttestation { .. }
+                                if !can_spawn && should_shed(work_id) => {}
+                            // Queue low-value work rather than starting it whilst it is being
+                            // deferred.
+                            Work::ChainSegmentBackfill { .. }
+                                if can_spawn && defer_new_work(work_id) =>
+                            {
+                                backfill_chain_segment.push(work, work_id, &self.log)
+                            }
+                            Work::LightClientBootstrapRequest { .. }
+                                if can_spawn && defer_new_work(work_id) =>
+                            {
+                                lcbootstrap_queue.push(work, work_id, &self.log)
+                            }
+                            Work::LightClientUpdatesByRangeRequest { .. }
+                                if can_spawn && defer_new_work(work_id) =>
+                            {
+                                lcupdates_queue.push(work, work_id, &self.log)
+                            }
                             _ if can_spawn => self.spawn_worker(work, idle_tx),
                             Work::GossipAttestation { .. } => attestation_queue.push(work),
                             // Attestation batches are formed internally within the
@@ -1251,6 +1420,9 @@ impl<E: EthSpec> BeaconProcessor<E> {
                             Work::LightClientBootstrapRequest { .. } => {
                                 lcbootstrap_queue.push(work, work_id, &self.log)
                             }
+                            Work::LightClientUpdatesByRangeRequest { .. } => {
+                                lcupdates_queue.push(work, work_id, &self.log)
+                            }
                             Work::UnknownBlockAttestation { .. } => {
                                 unknown_block_attestation_queue.push(work)
                             }
@@ -1362,6 +1534,46 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         "queue_len" => attestation_queue.max_length,
                     )
                 }
+
+                if last_queue_state_update.elapsed() >= QUEUE_STATE_UPDATE_INTERVAL {
+                    last_queue_state_update = Instant::now();
+                    let work_queues = vec![
+                        chain_segment_queue.state(CHAIN_SEGMENT),
+                        rpc_block_queue.state(RPC_BLOCK),
+                        rpc_blob_queue.state(RPC_BLOBS),
+                        delayed_block_queue.state(DELAYED_IMPORT_BLOCK),
+                        gossip_block_queue.state(GOSSIP_BLOCK),
+                        gossip_blob_queue.state(GOSSIP_BLOBS_SIDECAR),
+                        api_request_p0_queue.state(API_REQUEST_P0),
+                        aggregate_queue.state(GOSSIP_AGGREGATE),
+                        attestation_queue.state(GOSSIP_ATTESTATION),
+                        sync_contribution_queue.state(GOSSIP_SYNC_CONTRIBUTION),
+                        sync_message_queue.state(GOSSIP_SYNC_SIGNATURE),
+                        unknown_block_aggregate_queue.state(UNKNOWN_BLOCK_AGGREGATE),
+                        unknown_block_attestation_queue.state(UNKNOWN_BLOCK_ATTESTATION),
+                        status_queue.state(STATUS_PROCESSING),
+                        bbrange_queue.state(BLOCKS_BY_RANGE_REQUEST),
+                        bbroots_queue.state(BLOCKS_BY_ROOTS_REQUEST),
+                        blbrange_queue.state(BLOBS_BY_RANGE_REQUEST),
+                        blbroots_queue.state(BLOBS_BY_ROOTS_REQUEST),
+                        gossip_attester_slashing_queue.state(GOSSIP_ATTESTER_SLASHING),
+                        gossip_proposer_slashing_queue.state(GOSSIP_PROPOSER_SLASHING),
+                        gossip_voluntary_exit_queue.state(GOSSIP_VOLUNTARY_EXIT),
+                        gossip_bls_to_execution_change_queue.state(GOSSIP_BLS_TO_EXECUTION_CHANGE),
+                        api_request_p1_queue.state(API_REQUEST_P1),
+                        backfill_chain_segment.state(CHAIN_SEGMENT_BACKFILL),
+                        lcbootstrap_queue.state(LIGHT_CLIENT_BOOTSTRAP_REQUEST),
+                        lcupdates_queue.state(LIGHT_CLIENT_UPDATES_BY_RANGE_REQUEST),
+                        finality_update_queue.state(GOSSIP_LIGHT_CLIENT_FINALITY_UPDATE),
+                        optimistic_update_queue.state(GOSSIP_LIGHT_CLIENT_OPTIMISTIC_UPDATE),
+                        unknown_light_client_update_queue.state(UNKNOWN_LIGHT_CLIENT_UPDATE),
+                    ];
+                    self.queue_state.update(|state| {
+                        state.active_workers = self.current_workers;
+                        state.max_workers = self.config.max_workers;
+                        state.work_queues = work_queues;
+                    });
+                }
             }
         };
 
@@ -1373,7 +1585,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
     /// Spawns a blocking worker thread to process some `Work`.
     ///
     /// Sends an message on `idle_tx` when the work is complete and the task is stopping.
-    fn spawn_worker(&mut self, work: Work<E>, idle_tx: mpsc::Sender<()>) {
+    fn spawn_worker(&mut self, work: Work<E>, idle_tx: mpsc::Sender<FinishedWork>) {
         let work_id = work.str_id();
         let worker_timer =
             metrics::start_timer_vec(&metrics::BEACON_PROCESSOR_WORKER_TIME, &[work_id]);
@@ -1389,6 +1601,8 @@ impl<E: EthSpec> BeaconProcessor<E> {
         // As such, this instantiation should happen as early in the function as possible.
         let send_idle_on_drop = SendOnDrop {
             tx: idle_tx,
+            work_id,
+            started: Instant::now(),
             _worker_timer: worker_timer,
             log: self.log.clone(),
         };
@@ -1480,7 +1694,8 @@ impl<E: EthSpec> BeaconProcessor<E> {
             | Work::GossipLightClientOptimisticUpdate(process_fn)
             | Work::Status(process_fn)
             | Work::GossipBlsToExecutionChange(process_fn)
-            | Work::LightClientBootstrapRequest(process_fn) => {
+            | Work::LightClientBootstrapRequest(process_fn)
+            | Work::LightClientUpdatesByRangeRequest(process_fn) => {
                 task_spawner.spawn_blocking(process_fn)
             }
         };
@@ -1554,7 +1769,9 @@ impl TaskSpawner {
 ///
 /// https://doc.rust-lang.org/std/ops/trait.Drop.html#panics
 pub struct SendOnDrop {
-    tx: mpsc::Sender<()>,
+    tx: mpsc::Sender<FinishedWork>,
+    work_id: &'static str,
+    started: Instant,
     // The field is unused, but it's here to ensure the timer is dropped once the task has finished.
     _worker_timer: Option<metrics::HistogramTimer>,
     log: Logger,
@@ -1562,7 +1779,11 @@ pub struct SendOnDrop {
 
 impl Drop for SendOnDrop {
     fn drop(&mut self) {
-        if let Err(e) = self.tx.try_send(()) {
+        let finished_work = FinishedWork {
+            work_id: self.work_id,
+            elapsed: self.started.elapsed(),
+        };
+        if let Err(e) = self.tx.try_send(finished_work) {
             warn!(
                 self.log,
                 "Unable to free worker";
@@ -0,0 +1,242 @@
+//! Adaptive scheduling for the `BeaconProcessor`.
+//!
+//! The `LoadShedder` keeps a moving average of the time taken to process each type of work. From
+//! these it estimates how long it would take to clear the queued high-priority work (blocks,
+//! aggregates, attestations, etc.) and compares it to the time remaining until the next deadline in
+//! the slot: attestation production at 1/3 of the slot, aggregation at 2/3 of the slot and the start
+//! of the next slot.
+//!
+//! If the high-priority work can't be cleared before the deadline the processor is "under
+//! pressure" and:
+//!
+//! - Unaggregated attestations that we are only propagating (i.e., for subnets we aren't
+//!   aggregating on) and attestations for unknown blocks are dropped instead of being queued.
+//! - Backfill batches and light client requests are left in their queues until the pressure is
+//!   relieved.
+//!
+//! Regardless of the pressure, backfill batches and light client requests that are expected to take
+//! longer than the time remaining until the next deadline are not started on the last free worker.
+//! This keeps the worker available for any block or aggregate that arrives before the deadline.
+//!
+//! Work is never deferred for longer than `MAX_DEFERRAL_TIME`, so that it isn't starved when the
+//! processor stays under pressure, or when the deadlines are closer together than its latency.
+use crate::metrics;
+use slot_clock::SlotClock;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// The weight given to a new observation in the latency moving averages.
+const LATENCY_SMOOTHING_FACTOR: f64 = 0.1;
+
+/// The maximum time that work may be left in its queue by `LoadShedder::should_defer`.
+pub const MAX_DEFERRAL_TIME: Duration = Duration::from_secs(12);
+
+#[derive(Default)]
+pub struct LoadShedder {
+    /// Moving average of the time taken to process each type of work.
+    latencies: HashMap<&'static str, Duration>,
+    /// The time remaining until the next deadline, as of the last call to `update_pressure`.
+    time_to_deadline: Option<Duration>,
+    /// Set if the queued high-priority work can't be processed before the next deadline.
+    under_pressure: bool,
+}
+
+impl LoadShedder {
+    /// Adds an observation of the time taken to process some work of type `work_id`.
+    pub fn observe_latency(&mut self, work_id: &'static str, elapsed: Duration) {
+        let latency = self
+            .latencies
+            .entry(work_id)
+            .and_modify(|latency| {
+                *latency = latency.mul_f64(1.0 - LATENCY_SMOOTHING_FACTOR)
+                    + elapsed.mul_f64(LATENCY_SMOOTHING_FACTOR)
+            })
+            .or_insert(elapsed);
+        metrics::set_float_gauge_vec(
+            &metrics::BEACON_PROCESSOR_ADAPTIVE_WORK_LATENCY_SECONDS,
+            &[work_id],
+            latency.as_secs_f64(),
+        );
+    }
+
+    /// Returns the expected time taken to process some work of type `work_id`, or zero if the work
+    /// type has not been observed yet.
+    pub fn latency(&self, work_id: &str) -> Duration {
+        self.latencies.get(work_id).copied().unwrap_or_default()
+    }
+
+    /// Determines whether the processor is under pressure.
+    ///
+    /// The `backlog` contains the number of queued jobs of each type of high-priority work. These
+    /// jobs are assumed to be spread evenly across `max_workers`.
+    pub fn update_pressure(
+        &mut self,
+        backlog: &[(&'static str, usize)],
+        max_workers: usize,
+        time_to_deadline: Option<Duration>,
+    ) -> bool {
+        let backlog_time = backlog
+            .iter()
+            .map(|(work_id, jobs)| self.latency(work_id).saturating_mul(*jobs as u32))
+            .sum::<Duration>()
+            / max_workers.max(1) as u32;
+
+        self.time_to_deadline = time_to_deadline;
+        self.under_pressure = time_to_deadline.map_or(false, |deadline| backlog_time > deadline);
+
+        metrics::set_float_gauge(
+            &metrics::BEACON_PROCESSOR_ADAPTIVE_BACKLOG_SECONDS,
+            backlog_time.as_secs_f64(),
+        );
+        metrics::maybe_set_float_gauge(
+            &metrics::BEACON_PROCESSOR_ADAPTIVE_TIME_TO_DEADLINE_SECONDS,
+            time_to_deadline.map(|deadline| deadline.as_secs_f64()),
+        );
+        metrics::set_gauge(
+            &metrics::BEACON_PROCESSOR_ADAPTIVE_UNDER_PRESSURE,
+            self.under_pressure as i64,
+        );
+
+        self.under_pressure
+    }
+
+    /// Returns `true` if low-value work of type `work_id` should be dropped instead of queued.
+    pub fn should_shed(&self, work_id: &'static str) -> bool {
+        if self.under_pressure {
+            metrics::inc_counter_vec(&metrics::BEACON_PROCESSOR_ADAPTIVE_SHED_TOTAL, &[work_id]);
+        }
+        self.under_pressure
+    }
+
+    /// Returns `true` if low-value work of type `work_id`, which has already `waited` in its
+    /// queue, should be left in its queue instead of being started on one of the `free_workers`.
+    pub fn should_defer(
+        &self,
+        work_id: &'static str,
+        free_workers: usize,
+        waited: Duration,
+    ) -> bool {
+        if waited >= MAX_DEFERRAL_TIME {
+            return false;
+        }
+        let would_miss_deadline = free_workers <= 1
+            && self
+                .time_to_deadline
+                .map_or(false, |deadline| self.latency(work_id) > deadline);
+        self.under_pressure || would_miss_deadline
+    }
+}
+
+/// Returns the time remaining until the next attestation, aggregation or slot deadline.
+pub fn time_to_next_deadline<S: SlotClock>(slot_clock: &S) -> Option<Duration> {
+    let into_slot = slot_clock.millis_from_current_slot_start()?;
+    [
+        slot_clock.unagg_attestation_production_delay(),
+        slot_clock.agg_attestation_production_delay(),
+        slot_clock.slot_duration(),
+    ]
+    .into_iter()
+    .find_map(|deadline| {
+        deadline
+            .checked_sub(into_slot)
+            .filter(|remaining| !remaining.is_zero())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slot_clock::ManualSlotClock;
+    use types::Slot;
+
+    const WORK: &str = "work";
+    const OTHER_WORK: &str = "other_work";
+
+    #[test]
+    fn latency_moving_average() {
+        let mut shedder = LoadShedder::default();
+        assert_eq!(shedder.latency(WORK), Duration::ZERO);
+
+        shedder.observe_latency(WORK, Duration::from_millis(100));
+        assert_eq!(shedder.latency(WORK), Duration::from_millis(100));
+
+        shedder.observe_latency(WORK, Duration::from_millis(200));
+        assert!((shedder.latency(WORK).as_secs_f64() - 0.11).abs() < 1e-6);
+        assert_eq!(shedder.latency(OTHER_WORK), Duration::ZERO);
+    }
+
+    #[test]
+    fn pressure_from_backlog() {
+        let mut shedder = LoadShedder::default();
+        shedder.observe_latency(WORK, Duration::from_millis(100));
+        shedder.observe_latency(OTHER_WORK, Duration::from_millis(50));
+        let deadline = Some(Duration::from_secs(1));
+
+        // 10 * 100ms + 20 * 50ms spread across 4 workers takes 500ms.
+        let backlog = [(WORK, 10), (OTHER_WORK, 20)];
+        assert!(!shedder.update_pressure(&backlog, 4, deadline));
+        assert!(!shedder.should_shed(WORK));
+        assert!(!shedder.should_defer(WORK, 2, Duration::ZERO));
+
+        // Spread across a single worker it takes 2s.
+        assert!(shedder.update_pressure(&backlog, 1, deadline));
+        assert!(shedder.should_shed(WORK));
+        assert!(shedder.should_defer(WORK, 2, Duration::ZERO));
+
+        // There's never pressure without a deadline.
+        assert!(!shedder.update_pressure(&backlog, 1, None));
+    }
+
+    #[test]
+    fn defer_long_work_near_deadline() {
+        let mut shedder = LoadShedder::default();
+        shedder.observe_latency(WORK, Duration::from_secs(2));
+        assert!(!shedder.update_pressure(&[], 4, Some(Duration::from_secs(1))));
+
+        // Long work is only deferred if it would occupy the last free worker.
+        assert!(!shedder.should_defer(WORK, 2, Duration::ZERO));
+        assert!(shedder.should_defer(WORK, 1, Duration::ZERO));
+
+        // Short work is never deferred.
+        assert!(!shedder.should_defer(OTHER_WORK, 1, Duration::ZERO));
+
+        assert!(!shedder.update_pressure(&[], 4, Some(Duration::from_secs(3))));
+        assert!(!shedder.should_defer(WORK, 1, Duration::ZERO));
+    }
+
+    #[test]
+    fn deferral_is_bounded() {
+        let mut shedder = LoadShedder::default();
+        shedder.observe_latency(WORK, Duration::from_secs(5));
+        let just_deferred = MAX_DEFERRAL_TIME - Duration::from_millis(1);
+
+        // Work longer than the gap between deadlines would otherwise be deferred indefinitely.
+        assert!(!shedder.update_pressure(&[], 4, Some(Duration::from_secs(4))));
+        assert!(shedder.should_defer(WORK, 1, just_deferred));
+        assert!(!shedder.should_defer(WORK, 1, MAX_DEFERRAL_TIME));
+
+        // Likewise whilst the processor stays under pressure.
+        assert!(shedder.update_pressure(&[(WORK, 10)], 1, Some(Duration::from_secs(4))));
+        assert!(shedder.should_defer(WORK, 2, just_deferred));
+        assert!(!shedder.should_defer(WORK, 2, MAX_DEFERRAL_TIME));
+    }
+
+    #[test]
+    fn deadlines_within_slot() {
+        let slot_clock = ManualSlotClock::new(
+            Slot::new(0),
+            Duration::from_secs(0),
+            Duration::from_secs(12),
+        );
+        let time_to_deadline = |millis| {
+            slot_clock.set_current_time(Duration::from_millis(millis));
+            time_to_next_deadline(&slot_clock).unwrap()
+        };
+
+        assert_eq!(time_to_deadline(12_000), Duration::from_secs(4));
+        assert_eq!(time_to_deadline(13_000), Duration::from_secs(3));
+        assert_eq!(time_to_deadline(16_000), Duration::from_secs(4));
+        assert_eq!(time_to_deadline(19_500), Duration::from_millis(500));
+        assert_eq!(time_to_deadline(23_999), Duration::from_millis(1));
+    }
+}
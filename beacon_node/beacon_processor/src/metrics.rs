@@ -150,6 +150,38 @@ lazy_static::lazy_static! {
         "Number of queued light client optimistic updates where as matching block has been imported."
     );
 
+    /*
+     * Adaptive scheduling metrics.
+     */
+    pub static ref BEACON_PROCESSOR_ADAPTIVE_WORK_LATENCY_SECONDS: Result<GaugeVec> = try_create_float_gauge_vec(
+        "beacon_processor_adaptive_work_latency_seconds",
+        "Moving average of the time taken to process each type of work.",
+        &["type"]
+    );
+    pub static ref BEACON_PROCESSOR_ADAPTIVE_BACKLOG_SECONDS: Result<Gauge> = try_create_float_gauge(
+        "beacon_processor_adaptive_backlog_seconds",
+        "Estimated time required to process the queued high-priority work."
+    );
+    pub static ref BEACON_PROCESSOR_ADAPTIVE_TIME_TO_DEADLINE_SECONDS: Result<Gauge> = try_create_float_gauge(
+        "beacon_processor_adaptive_time_to_deadline_seconds",
+        "Time remaining until the next attestation, aggregation or slot deadline."
+    );
+    pub static ref BEACON_PROCESSOR_ADAPTIVE_UNDER_PRESSURE: Result<IntGauge> = try_create_int_gauge(
+        "beacon_processor_adaptive_under_pressure",
+        "Set to 1 if the queued high-priority work cannot be processed before the next deadline."
+    );
+    pub static ref BEACON_PROCESSOR_ADAPTIVE_SHED_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_processor_adaptive_shed_total",
+        "Count of work events dropped because the processor was under pressure.",
+        &["type"]
+    );
+    pub static ref BEACON_PROCESSOR_ADAPTIVE_DEFERRED_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_processor_adaptive_deferred_total",
+        "Count of work events queued instead of being started because the processor was under \
+        pressure or close to a deadline.",
+        &["type"]
+    );
+
     /// Errors and Debugging Stats
     pub static ref BEACON_PROCESSOR_SEND_ERROR_PER_WORK_TYPE: Result<IntCounterVec> =
         try_create_int_counter_vec(
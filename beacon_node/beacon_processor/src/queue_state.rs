@@ -0,0 +1,136 @@
+//! Snapshots of the `BeaconProcessor` queues, used to inspect them via the HTTP API.
+//!
+//! The manager task and the re-process scheduler periodically write the state of their queues to a
+//! shared `QueueStateHandle`, which notifies any subscribers of the new state.
+use parking_lot::RwLock;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+
+/// The minimum time between updates of the queue state.
+pub const QUEUE_STATE_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The number of updates that may be buffered for a subscriber before it starts missing updates.
+const QUEUE_STATE_CHANNEL_CAPACITY: usize = 16;
+
+/// The state of the queue for one type of `Work`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkQueueState {
+    pub work_type: &'static str,
+    pub length: usize,
+    pub capacity: usize,
+    /// The number of items dropped because the queue was full.
+    pub dropped: u64,
+    /// The time since the oldest item in the queue was added.
+    pub oldest_item_age: Option<Duration>,
+}
+
+/// A summary of the work waiting in the re-process queue.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReprocessQueueState {
+    /// Gossip blocks received early and waiting for their slot.
+    pub gossip_blocks: usize,
+    /// RPC blocks waiting for the gossip block with the same root to be processed.
+    pub rpc_blocks: usize,
+    /// Aggregates waiting for their block to be imported.
+    pub aggregates: usize,
+    /// Unaggregated attestations waiting for their block to be imported.
+    pub unaggregates: usize,
+    /// The number of distinct block roots that attestations are waiting for.
+    pub awaited_block_roots: usize,
+    /// Light client updates waiting for their parent block to be imported.
+    pub light_client_updates: usize,
+    /// Backfill batches waiting for their scheduled processing time.
+    pub backfill_batches: usize,
+}
+
+/// The state of all the queues of the `BeaconProcessor`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BeaconProcessorQueueState {
+    pub active_workers: usize,
+    pub max_workers: usize,
+    pub work_queues: Vec<WorkQueueState>,
+    pub reprocess_queue: ReprocessQueueState,
+    /// The time of the last update, as a duration since the UNIX epoch. Not set if the state has
+    /// not been updated yet.
+    pub updated_at: Option<Duration>,
+}
+
+/// Provides the latest `BeaconProcessorQueueState` and a stream of updates to it.
+#[derive(Clone)]
+pub struct QueueStateHandle {
+    state: Arc<RwLock<BeaconProcessorQueueState>>,
+    sender: broadcast::Sender<BeaconProcessorQueueState>,
+}
+
+impl Default for QueueStateHandle {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(QUEUE_STATE_CHANNEL_CAPACITY);
+        Self {
+            state: <_>::default(),
+            sender,
+        }
+    }
+}
+
+impl QueueStateHandle {
+    /// Returns the most recent state of the queues.
+    pub fn current(&self) -> BeaconProcessorQueueState {
+        self.state.read().clone()
+    }
+
+    /// Returns a receiver of all future changes to the state of the queues.
+    pub fn subscribe(&self) -> broadcast::Receiver<BeaconProcessorQueueState> {
+        self.sender.subscribe()
+    }
+
+    /// Modifies the state with `update` and notifies subscribers if it changed.
+    ///
+    /// The update time is recorded even if the state is unchanged, but a new update time alone does
+    /// not notify subscribers.
+    pub(crate) fn update(&self, update: impl FnOnce(&mut BeaconProcessorQueueState)) {
+        let mut state = self.state.write();
+        let previous = state.clone();
+        update(&mut state);
+        let changed = *state != previous;
+        state.updated_at = SystemTime::now().duration_since(UNIX_EPOCH).ok();
+        if changed {
+            // An error just means there are no subscribers.
+            let _ = self.sender.send(state.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscribers_only_receive_changes() {
+        let handle = QueueStateHandle::default();
+        let mut receiver = handle.subscribe();
+
+        handle.update(|state| state.max_workers = 4);
+        handle.update(|state| state.max_workers = 4);
+        handle.update(|state| state.reprocess_queue.backfill_batches = 1);
+
+        assert_eq!(receiver.try_recv().unwrap().max_workers, 4);
+        let state = receiver.try_recv().unwrap();
+        assert!(state.updated_at.is_some());
+        assert_eq!(
+            state,
+            BeaconProcessorQueueState {
+                max_workers: 4,
+                reprocess_queue: ReprocessQueueState {
+                    backfill_batches: 1,
+                    ..<_>::default()
+                },
+                updated_at: state.updated_at,
+                ..<_>::default()
+            }
+        );
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(handle.current().reprocess_queue.backfill_batches, 1);
+        assert!(handle.current().updated_at >= state.updated_at);
+    }
+}
@@ -11,6 +11,7 @@
 //! Aggregated and unaggregated attestations that failed verification due to referencing an unknown
 //! block will be re-queued until their block is imported, or until they expire.
 use crate::metrics;
+use crate::queue_state::{QueueStateHandle, ReprocessQueueState, QUEUE_STATE_UPDATE_INTERVAL};
 use crate::{AsyncFn, BlockingFn, Work, WorkEvent};
 use fnv::FnvHashMap;
 use futures::task::Poll;
@@ -23,7 +24,7 @@ use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::pin::Pin;
 use std::task::Context;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use strum::AsRefStr;
 use task_executor::TaskExecutor;
 use tokio::sync::mpsc::{self, Receiver, Sender};
@@ -244,6 +245,8 @@ struct ReprocessQueue<S> {
     lc_update_delay_debounce: TimeLatch,
     next_backfill_batch_event: Option<Pin<Box<tokio::time::Sleep>>>,
     slot_clock: Pin<Box<S>>,
+    queue_state: QueueStateHandle,
+    last_queue_state_update: Instant,
 }
 
 pub type QueuedLightClientUpdateId = usize;
@@ -363,6 +366,7 @@ pub fn spawn_reprocess_scheduler<S: SlotClock + 'static>(
     work_reprocessing_rx: Receiver<ReprocessQueueMessage>,
     executor: &TaskExecutor,
     slot_clock: S,
+    queue_state: QueueStateHandle,
     log: Logger,
     maximum_gossip_clock_disparity: Duration,
 ) -> Result<(), String> {
@@ -392,6 +396,8 @@ pub fn spawn_reprocess_scheduler<S: SlotClock + 'static>(
         lc_update_delay_debounce: TimeLatch::default(),
         next_backfill_batch_event: None,
         slot_clock: Box::pin(slot_clock.clone()),
+        queue_state,
+        last_queue_state_update: Instant::now(),
     };
 
     executor.spawn(
@@ -918,6 +924,21 @@ impl<S: SlotClock> ReprocessQueue<S> {
             &[LIGHT_CLIENT_UPDATES],
             self.lc_updates_delay_queue.len() as i64,
         );
+
+        if self.last_queue_state_update.elapsed() >= QUEUE_STATE_UPDATE_INTERVAL {
+            self.last_queue_state_update = Instant::now();
+            let reprocess_queue = ReprocessQueueState {
+                gossip_blocks: self.gossip_block_delay_queue.len(),
+                rpc_blocks: self.rpc_block_delay_queue.len(),
+                aggregates: self.queued_aggregates.len(),
+                unaggregates: self.queued_unaggregates.len(),
+                awaited_block_roots: self.awaiting_attestations_per_root.len(),
+                light_client_updates: self.queued_lc_updates.len(),
+                backfill_batches: self.queued_backfill_batches.len(),
+            };
+            self.queue_state
+                .update(|state| state.reprocess_queue = reprocess_queue);
+        }
     }
 
     fn recompute_next_backfill_batch_event(&mut self) {
@@ -1,15 +1,21 @@
 use eth2::types::builder_bid::SignedBuilderBid;
 use eth2::types::{
-    EthSpec, ExecutionBlockHash, ForkVersionedResponse, PublicKeyBytes,
+    ContentType, EthSpec, ExecutionBlockHash, ForkName, ForkVersionedResponse, PublicKeyBytes,
     SignedValidatorRegistrationData, Slot,
 };
 use eth2::types::{FullPayloadContents, SignedBlindedBeaconBlock};
 pub use eth2::Error;
-use eth2::{ok_or_error, StatusCode};
+use eth2::{
+    ok_or_error, StatusCode, CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER,
+};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT};
 use reqwest::{IntoUrl, Response};
 use sensitive_url::SensitiveUrl;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use ssz::Encode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 pub const DEFAULT_TIMEOUT_MILLIS: u64 = 15000;
@@ -20,6 +26,14 @@ pub const DEFAULT_GET_HEADER_TIMEOUT_MILLIS: u64 = 1000;
 /// Default user agent for HTTP requests.
 pub const DEFAULT_USER_AGENT: &str = lighthouse_version::VERSION;
 
+/// The value of the `Accept` header used when SSZ is enabled.
+///
+/// SSZ is preferred, but builders that only speak JSON remain usable.
+pub const PREFERENCE_ACCEPT_VALUE: &str = "application/octet-stream;q=1.0,application/json;q=0.9";
+
+/// The value of the `Accept` header used when SSZ is disabled.
+pub const JSON_ACCEPT_VALUE: &str = "application/json";
+
 #[derive(Clone)]
 pub struct Timeouts {
     get_header: Duration,
@@ -45,10 +59,19 @@ pub struct BuilderHttpClient {
     server: SensitiveUrl,
     timeouts: Timeouts,
     user_agent: String,
+    /// Never request or send SSZ, even if the builder supports it.
+    disable_ssz: bool,
+    /// Set to `true` once the builder has responded with SSZ, indicating that it is also safe to
+    /// submit blinded blocks as SSZ.
+    ssz_enabled: Arc<AtomicBool>,
 }
 
 impl BuilderHttpClient {
-    pub fn new(server: SensitiveUrl, user_agent: Option<String>) -> Result<Self, Error> {
+    pub fn new(
+        server: SensitiveUrl,
+        user_agent: Option<String>,
+        disable_ssz: bool,
+    ) -> Result<Self, Error> {
         let user_agent = user_agent.unwrap_or(DEFAULT_USER_AGENT.to_string());
         let client = reqwest::Client::builder().user_agent(&user_agent).build()?;
         Ok(Self {
@@ -56,6 +79,8 @@ impl BuilderHttpClient {
             server,
             timeouts: Timeouts::default(),
             user_agent,
+            disable_ssz,
+            ssz_enabled: Arc::new(AtomicBool::new(false)),
         })
     }
 
@@ -63,6 +88,20 @@ impl BuilderHttpClient {
         &self.user_agent
     }
 
+    /// Returns `true` if blinded blocks will be submitted to the builder as SSZ.
+    pub fn is_ssz_enabled(&self) -> bool {
+        !self.disable_ssz && self.ssz_enabled.load(Ordering::Relaxed)
+    }
+
+    /// The `Accept` header to send with requests that may be answered in SSZ.
+    fn accept_header(&self) -> HeaderValue {
+        if self.disable_ssz {
+            HeaderValue::from_static(JSON_ACCEPT_VALUE)
+        } else {
+            HeaderValue::from_static(PREFERENCE_ACCEPT_VALUE)
+        }
+    }
+
     async fn get_with_timeout<T: DeserializeOwned, U: IntoUrl>(
         &self,
         url: U,
@@ -81,7 +120,19 @@ impl BuilderHttpClient {
         url: U,
         timeout: Option<Duration>,
     ) -> Result<Response, Error> {
-        let mut builder = self.client.get(url);
+        self.get_response_with_headers(url, timeout, HeaderMap::new())
+            .await
+    }
+
+    /// Perform a HTTP GET request with additional headers, returning the `Response` for further
+    /// processing.
+    async fn get_response_with_headers<U: IntoUrl>(
+        &self,
+        url: U,
+        timeout: Option<Duration>,
+        headers: HeaderMap,
+    ) -> Result<Response, Error> {
+        let mut builder = self.client.get(url).headers(headers);
         if let Some(timeout) = timeout {
             builder = builder.timeout(timeout);
         }
@@ -118,6 +169,34 @@ impl BuilderHttpClient {
         ok_or_error(response).await
     }
 
+    /// POST an SSZ-encoded body with the given fork in the `Eth-Consensus-Version` header.
+    async fn post_ssz_with_raw_response<U: IntoUrl>(
+        &self,
+        url: U,
+        body: Vec<u8>,
+        fork_name: ForkName,
+        timeout: Option<Duration>,
+    ) -> Result<Response, Error> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE_HEADER,
+            HeaderValue::from_static(SSZ_CONTENT_TYPE_HEADER),
+        );
+        headers.insert(
+            CONSENSUS_VERSION_HEADER,
+            HeaderValue::from_str(&fork_name.to_string())
+                .map_err(|e| Error::InvalidHeaders(format!("{e:?}")))?,
+        );
+        headers.insert(ACCEPT, self.accept_header());
+
+        let mut builder = self.client.post(url).headers(headers).body(body);
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+        let response = builder.send().await.map_err(Error::from)?;
+        ok_or_error(response).await
+    }
+
     /// `POST /eth/v1/builder/validators`
     pub async fn post_builder_validators(
         &self,
@@ -138,6 +217,10 @@ impl BuilderHttpClient {
     }
 
     /// `POST /eth/v1/builder/blinded_blocks`
+    ///
+    /// The block is sent as SSZ if the builder has previously responded with SSZ, otherwise it is
+    /// sent as JSON. If the builder rejects the SSZ body as an unsupported media type we retry once
+    /// with JSON and stop using SSZ for future submissions.
     pub async fn post_builder_blinded_blocks<E: EthSpec>(
         &self,
         blinded_block: &SignedBlindedBeaconBlock<E>,
@@ -151,15 +234,45 @@ impl BuilderHttpClient {
             .push("builder")
             .push("blinded_blocks");
 
-        Ok(self
-            .post_with_raw_response(
-                path,
-                &blinded_block,
-                Some(self.timeouts.post_blinded_blocks),
-            )
-            .await?
-            .json()
-            .await?)
+        let fork_name = blinded_block.fork_name_unchecked();
+        let timeout = Some(self.timeouts.post_blinded_blocks);
+
+        let response = if self.is_ssz_enabled() {
+            match self
+                .post_ssz_with_raw_response(
+                    path.clone(),
+                    blinded_block.as_ssz_bytes(),
+                    fork_name,
+                    timeout,
+                )
+                .await
+            {
+                Err(e) if e.status() == Some(StatusCode::UNSUPPORTED_MEDIA_TYPE) => {
+                    self.ssz_enabled.store(false, Ordering::Relaxed);
+                    self.post_with_raw_response(path, &blinded_block, timeout)
+                        .await?
+                }
+                result => result?,
+            }
+        } else {
+            self.post_with_raw_response(path, &blinded_block, timeout)
+                .await?
+        };
+
+        match ContentType::from(response.headers()) {
+            ContentType::Ssz => {
+                let fork_name = response_fork_name(response.headers())?.unwrap_or(fork_name);
+                let bytes = response.bytes().await?;
+                let data = FullPayloadContents::from_ssz_bytes_for_fork(&bytes, fork_name)
+                    .map_err(Error::InvalidSsz)?;
+                Ok(ForkVersionedResponse {
+                    version: Some(fork_name),
+                    metadata: Default::default(),
+                    data,
+                })
+            }
+            ContentType::Json => Ok(response.json().await?),
+        }
     }
 
     /// `GET /eth/v1/builder/header`
@@ -181,12 +294,38 @@ impl BuilderHttpClient {
             .push(format!("{parent_hash:?}").as_str())
             .push(pubkey.as_hex_string().as_str());
 
-        let resp = self.get_with_timeout(path, self.timeouts.get_header).await;
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, self.accept_header());
 
-        if matches!(resp, Err(Error::StatusCode(StatusCode::NO_CONTENT))) {
-            Ok(None)
-        } else {
-            resp.map(Some)
+        let response = match self
+            .get_response_with_headers(path, Some(self.timeouts.get_header), headers)
+            .await
+        {
+            Err(Error::StatusCode(StatusCode::NO_CONTENT)) => return Ok(None),
+            result => result?,
+        };
+
+        match ContentType::from(response.headers()) {
+            ContentType::Ssz => {
+                let fork_name = response_fork_name(response.headers())?.ok_or_else(|| {
+                    Error::InvalidHeaders(format!(
+                        "missing {CONSENSUS_VERSION_HEADER} header on SSZ response"
+                    ))
+                })?;
+                let bytes = response.bytes().await?;
+                let data = SignedBuilderBid::from_ssz_bytes_for_fork(&bytes, fork_name)
+                    .map_err(Error::InvalidSsz)?;
+                self.ssz_enabled.store(true, Ordering::Relaxed);
+                Ok(Some(ForkVersionedResponse {
+                    version: Some(fork_name),
+                    metadata: Default::default(),
+                    data,
+                }))
+            }
+            ContentType::Json => {
+                self.ssz_enabled.store(false, Ordering::Relaxed);
+                Ok(Some(response.json().await?))
+            }
         }
     }
 
@@ -205,3 +344,17 @@ impl BuilderHttpClient {
             .await
     }
 }
+
+/// Read the fork from the `Eth-Consensus-Version` header of a response, if present.
+fn response_fork_name(headers: &HeaderMap) -> Result<Option<ForkName>, Error> {
+    headers
+        .get(CONSENSUS_VERSION_HEADER)
+        .map(|value| {
+            value
+                .to_str()
+                .map_err(|e| Error::InvalidHeaders(format!("{e:?}")))?
+                .parse::<ForkName>()
+                .map_err(Error::InvalidHeaders)
+        })
+        .transpose()
+}
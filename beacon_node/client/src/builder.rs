@@ -1,4 +1,5 @@
 use crate::address_change_broadcast::broadcast_address_changes_at_capella;
+use crate::checkpoint_sync::{checkpoint_from_ssz_bytes, load_checkpoint};
 use crate::compute_light_client_updates::{
     compute_light_client_updates, LIGHT_CLIENT_SERVER_CHANNEL_CAPACITY,
 };
@@ -16,17 +17,13 @@ use beacon_chain::{
     eth1_chain::{CachingEth1Backend, Eth1Chain},
     slot_clock::{SlotClock, SystemTimeSlotClock},
     state_advance_timer::spawn_state_advance_timer,
-    store::{HotColdDB, ItemStore, LevelDB, StoreConfig},
+    store::{BeaconNodeBackend, HotColdDB, ItemStore, StoreConfig},
     BeaconChain, BeaconChainTypes, Eth1ChainBackend, MigratorConfig, ServerSentEventHandler,
 };
 use beacon_processor::BeaconProcessorConfig;
 use beacon_processor::{BeaconProcessor, BeaconProcessorChannels};
 use environment::RuntimeContext;
 use eth1::{Config as Eth1Config, Service as Eth1Service};
-use eth2::{
-    types::{BlockId, StateId},
-    BeaconNodeHttpClient, Error as ApiError, Timeouts,
-};
 use execution_layer::ExecutionLayer;
 use futures::channel::mpsc::Receiver;
 use genesis::{interop_genesis_state, Eth1GenesisService, DEFAULT_ETH1_BLOCK_HASH};
@@ -45,8 +42,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use timer::spawn_timer;
 use tokio::sync::oneshot;
 use types::{
-    test_utils::generate_deterministic_keypairs, BeaconState, BlobSidecarList, ChainSpec, EthSpec,
-    ExecutionBlockHash, Hash256, SignedBeaconBlock,
+    test_utils::generate_deterministic_keypairs, BeaconState, ChainSpec, EthSpec,
+    ExecutionBlockHash, Hash256,
 };
 
 /// Interval between polling the eth1 node for genesis information.
@@ -239,7 +236,7 @@ where
             ClientGenesis::DepositContract
         } else if chain_exists {
             if matches!(client_genesis, ClientGenesis::WeakSubjSszBytes { .. })
-                || matches!(client_genesis, ClientGenesis::CheckpointSyncUrl { .. })
+                || matches!(client_genesis, ClientGenesis::CheckpointSync { .. })
             {
                 info!(
                     context.log(),
@@ -321,6 +318,7 @@ where
                 anchor_state_bytes,
                 anchor_block_bytes,
                 anchor_blobs_bytes,
+                genesis_state_bytes,
             } => {
                 info!(context.log(), "Starting checkpoint sync");
                 if config.chain.genesis_backfill {
@@ -330,21 +328,18 @@ where
                     );
                 }
 
-                let anchor_state = BeaconState::from_ssz_bytes(&anchor_state_bytes, &spec)
-                    .map_err(|e| format!("Unable to parse weak subj state SSZ: {:?}", e))?;
-                let anchor_block = SignedBeaconBlock::from_ssz_bytes(&anchor_block_bytes, &spec)
-                    .map_err(|e| format!("Unable to parse weak subj block SSZ: {:?}", e))?;
-                let anchor_blobs = if anchor_block.message().body().has_blobs() {
-                    let anchor_blobs_bytes = anchor_blobs_bytes
-                        .ok_or("Blobs for checkpoint must be provided using --checkpoint-blobs")?;
-                    Some(
-                        BlobSidecarList::from_ssz_bytes(&anchor_blobs_bytes)
-                            .map_err(|e| format!("Unable to parse weak subj blobs SSZ: {e:?}"))?,
-                    )
+                let (anchor_state, anchor_block, anchor_blobs) = checkpoint_from_ssz_bytes(
+                    &anchor_state_bytes,
+                    &anchor_block_bytes,
+                    anchor_blobs_bytes.as_deref(),
+                    &spec,
+                )?;
+                let genesis_state = if let Some(genesis_state_bytes) = genesis_state_bytes {
+                    BeaconState::from_ssz_bytes(&genesis_state_bytes, &spec)
+                        .map_err(|e| format!("Unable to parse genesis state SSZ: {e:?}"))?
                 } else {
-                    None
+                    genesis_state(&runtime_context, &config, log).await?
                 };
-                let genesis_state = genesis_state(&runtime_context, &config, log).await?;
 
                 builder
                     .weak_subjectivity_state(
@@ -355,11 +350,17 @@ where
                     )
                     .map(|v| (v, None))?
             }
-            ClientGenesis::CheckpointSyncUrl { url } => {
+            ClientGenesis::CheckpointSync {
+                urls,
+                local_checkpoint,
+                quorum,
+            } => {
                 info!(
                     context.log(),
                     "Starting checkpoint sync";
-                    "remote_url" => %url,
+                    "remote_urls" => ?urls,
+                    "local_files" => local_checkpoint.is_some(),
+                    "quorum" => quorum,
                 );
                 if config.chain.genesis_backfill {
                     info!(
@@ -368,112 +369,18 @@ where
                     );
                 }
 
-                let remote = BeaconNodeHttpClient::new(
-                    url,
-                    Timeouts::set_all(Duration::from_secs(
-                        config.chain.checkpoint_sync_url_timeout,
-                    )),
-                );
-
-                let deposit_snapshot = if config.sync_eth1_chain {
-                    // We want to fetch deposit snapshot before fetching the finalized beacon state to
-                    // ensure that the snapshot is not newer than the beacon state that satisfies the
-                    // deposit finalization conditions
-                    debug!(context.log(), "Downloading deposit snapshot");
-                    let deposit_snapshot_result = remote
-                        .get_deposit_snapshot()
-                        .await
-                        .map_err(|e| match e {
-                            ApiError::InvalidSsz(e) => format!(
-                                "Unable to parse SSZ: {:?}. Ensure the checkpoint-sync-url refers to a \
-                                node for the correct network",
-                                e
-                            ),
-                            e => format!("Error fetching deposit snapshot from remote: {:?}", e),
-                        });
-                    match deposit_snapshot_result {
-                        Ok(Some(deposit_snapshot)) => {
-                            if deposit_snapshot.is_valid() {
-                                Some(deposit_snapshot)
-                            } else {
-                                warn!(context.log(), "Remote BN sent invalid deposit snapshot!");
-                                None
-                            }
-                        }
-                        Ok(None) => {
-                            warn!(
-                                context.log(),
-                                "Remote BN does not support EIP-4881 fast deposit sync"
-                            );
-                            None
-                        }
-                        Err(e) => {
-                            warn!(
-                                context.log(),
-                                "Remote BN does not support EIP-4881 fast deposit sync";
-                                "error" => e
-                            );
-                            None
-                        }
-                    }
-                } else {
-                    None
-                };
-
-                debug!(
+                let ((state, block, blobs), deposit_snapshot) = load_checkpoint::<TEthSpec>(
+                    urls,
+                    local_checkpoint,
+                    quorum,
+                    Duration::from_secs(config.chain.checkpoint_sync_url_timeout),
+                    config.sync_eth1_chain,
+                    &spec,
                     context.log(),
-                    "Downloading finalized state";
-                );
-                let state = remote
-                    .get_debug_beacon_states_ssz::<TEthSpec>(StateId::Finalized, &spec)
-                    .await
-                    .map_err(|e| format!("Error loading checkpoint state from remote: {:?}", e))?
-                    .ok_or_else(|| "Checkpoint state missing from remote".to_string())?;
-
-                debug!(context.log(), "Downloaded finalized state"; "slot" => ?state.slot());
-
-                let finalized_block_slot = state.latest_block_header().slot;
-
-                debug!(context.log(), "Downloading finalized block"; "block_slot" => ?finalized_block_slot);
-                let block = remote
-                    .get_beacon_blocks_ssz::<TEthSpec>(BlockId::Slot(finalized_block_slot), &spec)
-                    .await
-                    .map_err(|e| match e {
-                        ApiError::InvalidSsz(e) => format!(
-                            "Unable to parse SSZ: {:?}. Ensure the checkpoint-sync-url refers to a \
-                            node for the correct network",
-                            e
-                        ),
-                        e => format!("Error fetching finalized block from remote: {:?}", e),
-                    })?
-                    .ok_or("Finalized block missing from remote, it returned 404")?;
+                )
+                .await?;
                 let block_root = block.canonical_root();
 
-                debug!(context.log(), "Downloaded finalized block");
-
-                let blobs = if block.message().body().has_blobs() {
-                    debug!(context.log(), "Downloading finalized blobs");
-                    if let Some(response) = remote
-                        .get_blobs::<TEthSpec>(BlockId::Root(block_root), None)
-                        .await
-                        .map_err(|e| format!("Error fetching finalized blobs from remote: {e:?}"))?
-                    {
-                        debug!(context.log(), "Downloaded finalized blobs");
-                        Some(response.data)
-                    } else {
-                        warn!(
-                            context.log(),
-                            "Checkpoint server is missing blobs";
-                            "block_root" => %block_root,
-                            "hint" => "use a different URL or ask the provider to update",
-                            "impact" => "db will be slightly corrupt until these blobs are pruned",
-                        );
-                        None
-                    }
-                } else {
-                    None
-                };
-
                 let genesis_state = genesis_state(&runtime_context, &config, log).await?;
 
                 info!(
@@ -547,6 +454,7 @@ where
                         network_globals: None,
                         beacon_processor_send: None,
                         beacon_processor_reprocess_send: None,
+                        beacon_processor_queue_state: None,
                         eth1_service: Some(genesis_service.eth1_service.clone()),
                         log: context.log().clone(),
                         sse_logging_components: runtime_context.sse_logging_components.clone(),
@@ -619,6 +527,38 @@ where
         Ok(self)
     }
 
+    /// Imports finalized history from the era files in `era_dir`, before the network is started
+    /// so that backfill sync only has to download the history not covered by the files.
+    pub async fn import_era_files(self, era_dir: PathBuf) -> Result<Self, String> {
+        let beacon_chain = self
+            .beacon_chain
+            .clone()
+            .ok_or("import_era_files requires a beacon chain")?;
+        let context = self
+            .runtime_context
+            .as_ref()
+            .ok_or("import_era_files requires a runtime_context")?;
+        let log = context.log().clone();
+
+        info!(log, "Importing era files"; "dir" => ?era_dir);
+        let imported = context
+            .executor
+            .spawn_blocking_handle(
+                move || {
+                    beacon_chain
+                        .import_era_files(&era_dir)
+                        .map_err(|e| format!("Failed to import era files: {:?}", e))
+                },
+                "import_era_files",
+            )
+            .ok_or("Shutting down")?
+            .await
+            .map_err(|e| format!("Era import task failed: {:?}", e))??;
+        info!(log, "Finished importing era files"; "blocks" => imported);
+
+        Ok(self)
+    }
+
     /// Starts the networking stack.
     pub async fn network(mut self, config: &NetworkConfig) -> Result<Self, String> {
         let beacon_chain = self
@@ -792,6 +732,7 @@ where
                 beacon_processor_reprocess_send: Some(
                     beacon_processor_channels.work_reprocessing_tx.clone(),
                 ),
+                beacon_processor_queue_state: Some(beacon_processor_channels.queue_state.clone()),
                 sse_logging_components: runtime_context.sse_logging_components.clone(),
                 log: log.clone(),
             });
@@ -855,6 +796,7 @@ where
                     executor: beacon_processor_context.executor.clone(),
                     current_workers: 0,
                     config: beacon_processor_config,
+                    queue_state: beacon_processor_channels.queue_state,
                     log: beacon_processor_context.log().clone(),
                 }
                 .spawn_manager(
@@ -1020,7 +962,15 @@ where
 }
 
 impl<TSlotClock, TEth1Backend, TEthSpec>
-    ClientBuilder<Witness<TSlotClock, TEth1Backend, TEthSpec, LevelDB<TEthSpec>, LevelDB<TEthSpec>>>
+    ClientBuilder<
+        Witness<
+            TSlotClock,
+            TEth1Backend,
+            TEthSpec,
+            BeaconNodeBackend<TEthSpec>,
+            BeaconNodeBackend<TEthSpec>,
+        >,
+    >
 where
     TSlotClock: SlotClock + 'static,
     TEth1Backend: Eth1ChainBackend<TEthSpec> + 'static,
@@ -0,0 +1,672 @@
+//! Loading of the checkpoint state and block for checkpoint sync, from local SSZ files or remote
+//! beacon nodes.
+//!
+//! When several providers are configured, the checkpoint is loaded from the first provider able
+//! to serve it (local files take precedence). Every remote provider is then asked for the roots of
+//! its finalized block at the slot of the checkpoint block and of its finalized state at the slot
+//! of the checkpoint state, so that the providers are compared at the same checkpoint even if
+//! finalization advances in the meantime. Comparing the state root is necessary because the block
+//! doesn't commit to the checkpoint state when the state is advanced through skipped slots.
+//!
+//! Each remote is also asked for its current finalized checkpoint, and rejects a checkpoint that is
+//! more than `MAX_CHECKPOINT_LAG_EPOCHS` behind it, so that an old (but canonical) checkpoint can't
+//! be used. The checkpoint is only used if a quorum of providers, including the one it was loaded
+//! from, agree on it.
+use crate::config::CheckpointSszBytes;
+use eth2::{
+    types::{BlockId, StateId},
+    BeaconNodeHttpClient, Error as ApiError, Timeouts,
+};
+use sensitive_url::SensitiveUrl;
+use slog::{debug, info, warn, Logger};
+use ssz::Decode;
+use std::fmt;
+use std::time::Duration;
+use types::{
+    BeaconState, BlobSidecarList, ChainSpec, DepositTreeSnapshot, Epoch, EthSpec, Hash256,
+    SignedBeaconBlock, Slot,
+};
+
+/// The number of epochs that a checkpoint may be behind the finalized checkpoint of a provider.
+///
+/// Finalization may advance whilst the checkpoint is being downloaded and compared, so the
+/// checkpoint isn't required to be the latest finalized checkpoint of each provider.
+pub const MAX_CHECKPOINT_LAG_EPOCHS: u64 = 4;
+
+/// A checkpoint state, its block and the block's blobs.
+pub type Checkpoint<E> = (
+    BeaconState<E>,
+    SignedBeaconBlock<E>,
+    Option<BlobSidecarList<E>>,
+);
+
+/// A source of the checkpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckpointProvider {
+    /// The files given with `--checkpoint-state` and `--checkpoint-block`.
+    LocalFiles,
+    /// A remote beacon node.
+    Remote(SensitiveUrl),
+}
+
+impl fmt::Display for CheckpointProvider {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckpointProvider::LocalFiles => write!(f, "local files"),
+            CheckpointProvider::Remote(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+/// The roots and slots by which the providers are compared.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CheckpointRoots {
+    pub block_slot: Slot,
+    pub block_root: Hash256,
+    pub state_slot: Slot,
+    pub state_root: Hash256,
+}
+
+impl CheckpointRoots {
+    pub fn new<E: EthSpec>(state: &BeaconState<E>, block: &SignedBeaconBlock<E>) -> Self {
+        Self {
+            block_slot: block.slot(),
+            block_root: block.canonical_root(),
+            state_slot: state.slot(),
+            state_root: state.canonical_root(),
+        }
+    }
+
+    /// The epoch of the checkpoint state.
+    pub fn epoch<E: EthSpec>(&self) -> Epoch {
+        self.state_slot.epoch(E::slots_per_epoch())
+    }
+}
+
+/// The response of a provider when asked for its finalized block and state at the checkpoint
+/// slots.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Vote {
+    /// The provider has finalized the checkpoint block and state.
+    Agree,
+    /// The provider has a different block at the checkpoint block slot.
+    BlockRootMismatch { block_root: Hash256 },
+    /// The provider has a different state at the checkpoint state slot.
+    StateRootMismatch { state_root: Hash256 },
+    /// The provider has the checkpoint block and state, but hasn't finalized them.
+    NotFinalized,
+    /// The provider has finalized an epoch more than `MAX_CHECKPOINT_LAG_EPOCHS` after the
+    /// checkpoint.
+    Stale { finalized_epoch: Epoch },
+    /// The provider couldn't be queried.
+    Unavailable(String),
+}
+
+/// Parse a checkpoint from SSZ bytes.
+pub fn checkpoint_from_ssz_bytes<E: EthSpec>(
+    anchor_state_bytes: &[u8],
+    anchor_block_bytes: &[u8],
+    anchor_blobs_bytes: Option<&[u8]>,
+    spec: &ChainSpec,
+) -> Result<Checkpoint<E>, String> {
+    let anchor_state = BeaconState::from_ssz_bytes(anchor_state_bytes, spec)
+        .map_err(|e| format!("Unable to parse weak subj state SSZ: {:?}", e))?;
+    let anchor_block = SignedBeaconBlock::from_ssz_bytes(anchor_block_bytes, spec)
+        .map_err(|e| format!("Unable to parse weak subj block SSZ: {:?}", e))?;
+    let anchor_blobs = if anchor_block.message().body().has_blobs() {
+        let anchor_blobs_bytes = anchor_blobs_bytes
+            .ok_or("Blobs for checkpoint must be provided using --checkpoint-blobs")?;
+        Some(
+            BlobSidecarList::from_ssz_bytes(anchor_blobs_bytes)
+                .map_err(|e| format!("Unable to parse weak subj blobs SSZ: {e:?}"))?,
+        )
+    } else {
+        None
+    };
+    Ok((anchor_state, anchor_block, anchor_blobs))
+}
+
+/// Download the deposit snapshot from a remote beacon node.
+///
+/// The snapshot must be downloaded before the finalized state, to ensure that the snapshot is not
+/// newer than the beacon state that satisfies the deposit finalization conditions.
+pub async fn download_deposit_snapshot(
+    remote: &BeaconNodeHttpClient,
+    log: &Logger,
+) -> Option<DepositTreeSnapshot> {
+    debug!(log, "Downloading deposit snapshot");
+    let deposit_snapshot_result = remote.get_deposit_snapshot().await.map_err(|e| match e {
+        ApiError::InvalidSsz(e) => format!(
+            "Unable to parse SSZ: {:?}. Ensure the checkpoint-sync-url refers to a \
+            node for the correct network",
+            e
+        ),
+        e => format!("Error fetching deposit snapshot from remote: {:?}", e),
+    });
+    match deposit_snapshot_result {
+        Ok(Some(deposit_snapshot)) => {
+            if deposit_snapshot.is_valid() {
+                Some(deposit_snapshot)
+            } else {
+                warn!(log, "Remote BN sent invalid deposit snapshot!");
+                None
+            }
+        }
+        Ok(None) => {
+            warn!(log, "Remote BN does not support EIP-4881 fast deposit sync");
+            None
+        }
+        Err(e) => {
+            warn!(
+                log,
+                "Remote BN does not support EIP-4881 fast deposit sync";
+                "error" => e
+            );
+            None
+        }
+    }
+}
+
+/// Download the finalized state, its block and the block's blobs from a remote beacon node.
+pub async fn download_checkpoint<E: EthSpec>(
+    remote: &BeaconNodeHttpClient,
+    spec: &ChainSpec,
+    log: &Logger,
+) -> Result<Checkpoint<E>, String> {
+    debug!(log, "Downloading finalized state");
+    let state = remote
+        .get_debug_beacon_states_ssz::<E>(StateId::Finalized, spec)
+        .await
+        .map_err(|e| format!("Error loading checkpoint state from remote: {:?}", e))?
+        .ok_or_else(|| "Checkpoint state missing from remote".to_string())?;
+
+    debug!(log, "Downloaded finalized state"; "slot" => ?state.slot());
+
+    let finalized_block_slot = state.latest_block_header().slot;
+
+    debug!(log, "Downloading finalized block"; "block_slot" => ?finalized_block_slot);
+    let block = remote
+        .get_beacon_blocks_ssz::<E>(BlockId::Slot(finalized_block_slot), spec)
+        .await
+        .map_err(|e| match e {
+            ApiError::InvalidSsz(e) => format!(
+                "Unable to parse SSZ: {:?}. Ensure the checkpoint-sync-url refers to a \
+                node for the correct network",
+                e
+            ),
+            e => format!("Error fetching finalized block from remote: {:?}", e),
+        })?
+        .ok_or("Finalized block missing from remote, it returned 404")?;
+    let block_root = block.canonical_root();
+
+    debug!(log, "Downloaded finalized block");
+
+    let blobs = if block.message().body().has_blobs() {
+        debug!(log, "Downloading finalized blobs");
+        if let Some(response) = remote
+            .get_blobs::<E>(BlockId::Root(block_root), None)
+            .await
+            .map_err(|e| format!("Error fetching finalized blobs from remote: {e:?}"))?
+        {
+            debug!(log, "Downloaded finalized blobs");
+            Some(response.data)
+        } else {
+            warn!(
+                log,
+                "Checkpoint server is missing blobs";
+                "block_root" => %block_root,
+                "hint" => "use a different URL or ask the provider to update",
+                "impact" => "db will be slightly corrupt until these blobs are pruned",
+            );
+            None
+        }
+    } else {
+        None
+    };
+
+    Ok((state, block, blobs))
+}
+
+/// Ask a remote beacon node whether it has finalized the checkpoint with the given `roots`.
+pub async fn remote_vote<E: EthSpec>(
+    remote: &BeaconNodeHttpClient,
+    roots: CheckpointRoots,
+) -> Vote {
+    let (block_root, state_root, finality) = futures::join!(
+        remote.get_beacon_blocks_root(BlockId::Slot(roots.block_slot)),
+        remote.get_beacon_states_root(StateId::Slot(roots.state_slot)),
+        remote.get_beacon_states_finality_checkpoints(StateId::Head),
+    );
+    let (block_root, state_root, finality) = match (block_root, state_root, finality) {
+        (Ok(Some(block_root)), Ok(Some(state_root)), Ok(Some(finality))) => {
+            (block_root, state_root, finality)
+        }
+        (Ok(None), _, _) => {
+            return Vote::Unavailable("no block at the checkpoint block slot".to_string())
+        }
+        (_, Ok(None), _) => {
+            return Vote::Unavailable("no state at the checkpoint state slot".to_string())
+        }
+        (_, _, Ok(None)) => return Vote::Unavailable("no finality checkpoints".to_string()),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            return Vote::Unavailable(format!("{:?}", e))
+        }
+    };
+
+    let finalized_epoch = finality.data.finalized.epoch;
+    if block_root.data.root != roots.block_root {
+        Vote::BlockRootMismatch {
+            block_root: block_root.data.root,
+        }
+    } else if state_root.data.root != roots.state_root {
+        Vote::StateRootMismatch {
+            state_root: state_root.data.root,
+        }
+    } else if block_root.finalized == Some(false) || state_root.finalized == Some(false) {
+        Vote::NotFinalized
+    } else if roots.epoch::<E>() + MAX_CHECKPOINT_LAG_EPOCHS < finalized_epoch {
+        Vote::Stale { finalized_epoch }
+    } else {
+        Vote::Agree
+    }
+}
+
+/// Check that at least `quorum` of the providers agree on the checkpoint with the given `roots`.
+///
+/// Every provider which doesn't agree is logged, whether or not the quorum is reached.
+pub fn check_quorum(
+    roots: CheckpointRoots,
+    votes: &[(CheckpointProvider, Vote)],
+    quorum: usize,
+    log: &Logger,
+) -> Result<(), String> {
+    let mut agreed = 0;
+    for (provider, vote) in votes {
+        match vote {
+            Vote::Agree => agreed += 1,
+            Vote::BlockRootMismatch { block_root } => warn!(
+                log,
+                "Checkpoint provider disagrees on the block";
+                "provider" => %provider,
+                "slot" => roots.block_slot,
+                "block_root" => ?roots.block_root,
+                "provider_block_root" => ?block_root,
+            ),
+            Vote::StateRootMismatch { state_root } => warn!(
+                log,
+                "Checkpoint provider disagrees on the state";
+                "provider" => %provider,
+                "slot" => roots.state_slot,
+                "state_root" => ?roots.state_root,
+                "provider_state_root" => ?state_root,
+            ),
+            Vote::NotFinalized => warn!(
+                log,
+                "Checkpoint provider has not finalized the checkpoint";
+                "provider" => %provider,
+                "slot" => roots.block_slot,
+                "block_root" => ?roots.block_root,
+            ),
+            Vote::Stale { finalized_epoch } => warn!(
+                log,
+                "Checkpoint is too far behind the provider's finalized checkpoint";
+                "provider" => %provider,
+                "slot" => roots.state_slot,
+                "provider_finalized_epoch" => finalized_epoch,
+                "max_lag_epochs" => MAX_CHECKPOINT_LAG_EPOCHS,
+            ),
+            Vote::Unavailable(error) => warn!(
+                log,
+                "Checkpoint provider unavailable";
+                "provider" => %provider,
+                "error" => error,
+            ),
+        }
+    }
+
+    if agreed < quorum {
+        return Err(format!(
+            "Only {} of {} checkpoint providers agree on block {:?} at slot {} and state {:?} at \
+             slot {}, a quorum of {} is required. Refusing to checkpoint sync",
+            agreed,
+            votes.len(),
+            roots.block_root,
+            roots.block_slot,
+            roots.state_root,
+            roots.state_slot,
+            quorum
+        ));
+    }
+
+    info!(
+        log,
+        "Checkpoint providers reached quorum";
+        "agreed" => agreed,
+        "providers" => votes.len(),
+        "quorum" => quorum,
+        "block_root" => ?roots.block_root,
+        "state_root" => ?roots.state_root,
+    );
+    Ok(())
+}
+
+/// Load the checkpoint from `local_checkpoint`, or otherwise from the first of the remote beacon
+/// nodes at `urls` able to serve it, and check that a `quorum` of the providers agree on it.
+///
+/// If `fetch_deposit_snapshot` is set, the deposit snapshot of the remote that served the
+/// checkpoint is also returned.
+pub async fn load_checkpoint<E: EthSpec>(
+    urls: Vec<SensitiveUrl>,
+    local_checkpoint: Option<CheckpointSszBytes>,
+    quorum: usize,
+    timeout: Duration,
+    fetch_deposit_snapshot: bool,
+    spec: &ChainSpec,
+    log: &Logger,
+) -> Result<(Checkpoint<E>, Option<DepositTreeSnapshot>), String> {
+    let timeouts = Timeouts::set_all(timeout);
+    let remotes = urls
+        .into_iter()
+        .map(|url| {
+            (
+                BeaconNodeHttpClient::new(url.clone(), timeouts.clone()),
+                url,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // Load the checkpoint from the local files if they are provided, otherwise from the first
+    // remote able to serve it. Remotes that fail to serve it are counted as unavailable.
+    let mut votes = vec![];
+    let mut deposit_snapshot = None;
+    let mut checkpoint = None;
+    let mut voting_remotes = vec![];
+    if let Some(local_checkpoint) = local_checkpoint {
+        checkpoint = Some(checkpoint_from_ssz_bytes(
+            &local_checkpoint.anchor_state_bytes,
+            &local_checkpoint.anchor_block_bytes,
+            local_checkpoint.anchor_blobs_bytes.as_deref(),
+            spec,
+        )?);
+        votes.push((CheckpointProvider::LocalFiles, Vote::Agree));
+    }
+    for (remote, url) in remotes {
+        if checkpoint.is_some() {
+            voting_remotes.push((remote, url));
+            continue;
+        }
+        let snapshot = if fetch_deposit_snapshot {
+            download_deposit_snapshot(&remote, log).await
+        } else {
+            None
+        };
+        match download_checkpoint::<E>(&remote, spec, log).await {
+            Ok(downloaded) => {
+                checkpoint = Some(downloaded);
+                deposit_snapshot = snapshot;
+                // The remote which served the checkpoint still votes, since it may have served a
+                // stale checkpoint.
+                voting_remotes.push((remote, url));
+            }
+            Err(e) => {
+                votes.push((CheckpointProvider::Remote(url), Vote::Unavailable(e)));
+            }
+        }
+    }
+    let (state, block, blobs) = match checkpoint {
+        Some(checkpoint) => checkpoint,
+        None => {
+            let errors = votes
+                .iter()
+                .map(|(provider, vote)| format!("{provider}: {vote:?}"))
+                .collect::<Vec<_>>();
+            return Err(format!(
+                "Unable to load the checkpoint from any provider: {}",
+                errors.join(", ")
+            ));
+        }
+    };
+
+    // Cross-check the checkpoint against the remotes.
+    let roots = CheckpointRoots::new(&state, &block);
+    let remote_votes = futures::future::join_all(
+        voting_remotes
+            .iter()
+            .map(|(remote, _)| remote_vote::<E>(remote, roots)),
+    )
+    .await;
+    votes.extend(
+        voting_remotes
+            .into_iter()
+            .map(|(_, url)| CheckpointProvider::Remote(url))
+            .zip(remote_votes),
+    );
+    check_quorum(roots, &votes, quorum, log)?;
+
+    Ok(((state, block, blobs), deposit_snapshot))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{AttestationStrategy, BlockStrategy};
+    use http_api::test_utils::{create_api_server, ApiServer, InteractiveTester};
+    use logging::test_logger;
+    use ssz::Encode;
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 32;
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn remote(i: u64) -> CheckpointProvider {
+        CheckpointProvider::Remote(
+            SensitiveUrl::parse(&format!("http://provider-{i}.example.com")).unwrap(),
+        )
+    }
+
+    /// Create a chain which has finalized its first epochs, skipping `skip_slots` slots at the
+    /// start.
+    async fn finalized_chain(skip_slots: u64) -> InteractiveTester<E> {
+        let tester = InteractiveTester::<E>::new(None, VALIDATOR_COUNT).await;
+        for _ in 0..=skip_slots {
+            tester.harness.advance_slot();
+        }
+        extend_chain(&tester, 5).await;
+        tester
+    }
+
+    async fn extend_chain(tester: &InteractiveTester<E>, epochs: u64) {
+        tester
+            .harness
+            .extend_chain(
+                (epochs * E::slots_per_epoch()) as usize,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+    }
+
+    /// Serve the chain of `tester` from a new HTTP API server, returning its URL.
+    async fn serve(tester: &InteractiveTester<E>) -> SensitiveUrl {
+        let ApiServer {
+            server,
+            listening_socket,
+            ..
+        } = create_api_server(
+            tester.harness.chain.clone(),
+            &tester.harness.runtime,
+            test_logger(),
+        )
+        .await;
+        tokio::spawn(server);
+        SensitiveUrl::parse(&format!("http://{listening_socket}")).unwrap()
+    }
+
+    fn local_checkpoint(
+        state: &BeaconState<E>,
+        block: &SignedBeaconBlock<E>,
+    ) -> CheckpointSszBytes {
+        CheckpointSszBytes {
+            anchor_state_bytes: state.as_ssz_bytes(),
+            anchor_block_bytes: block.as_ssz_bytes(),
+            anchor_blobs_bytes: None,
+        }
+    }
+
+    async fn load(
+        tester: &InteractiveTester<E>,
+        urls: &[SensitiveUrl],
+        local_checkpoint: Option<CheckpointSszBytes>,
+        quorum: usize,
+    ) -> Result<Checkpoint<E>, String> {
+        load_checkpoint::<E>(
+            urls.to_vec(),
+            local_checkpoint,
+            quorum,
+            TIMEOUT,
+            false,
+            &tester.harness.chain.spec,
+            &test_logger(),
+        )
+        .await
+        .map(|(checkpoint, deposit_snapshot)| {
+            assert!(deposit_snapshot.is_none());
+            checkpoint
+        })
+    }
+
+    #[test]
+    fn quorum() {
+        let log = test_logger();
+        let roots = CheckpointRoots {
+            block_slot: Slot::new(63),
+            block_root: Hash256::repeat_byte(1),
+            state_slot: Slot::new(64),
+            state_root: Hash256::repeat_byte(2),
+        };
+        let votes = vec![
+            (CheckpointProvider::LocalFiles, Vote::Agree),
+            (remote(1), Vote::Agree),
+            (
+                remote(2),
+                Vote::BlockRootMismatch {
+                    block_root: Hash256::repeat_byte(3),
+                },
+            ),
+            (
+                remote(3),
+                Vote::StateRootMismatch {
+                    state_root: Hash256::repeat_byte(4),
+                },
+            ),
+            (remote(4), Vote::NotFinalized),
+            (
+                remote(5),
+                Vote::Stale {
+                    finalized_epoch: Epoch::new(10),
+                },
+            ),
+            (remote(6), Vote::Unavailable("timeout".to_string())),
+        ];
+
+        assert_eq!(check_quorum(roots, &votes, 1, &log), Ok(()));
+        assert_eq!(check_quorum(roots, &votes, 2, &log), Ok(()));
+        // Only agreeing providers count towards the quorum.
+        assert!(check_quorum(roots, &votes, 3, &log).is_err());
+        assert!(check_quorum(roots, &votes[1..], 2, &log).is_err());
+    }
+
+    #[test]
+    fn provider_display() {
+        assert_eq!(CheckpointProvider::LocalFiles.to_string(), "local files");
+        assert_eq!(remote(1).to_string(), "http://provider-1.example.com/");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn load_from_remotes() {
+        let tester = finalized_chain(0).await;
+        // A chain which diverges from `tester` from the first slot.
+        let other = finalized_chain(1).await;
+        let honest = [serve(&tester).await, serve(&tester).await];
+        let dishonest = serve(&other).await;
+
+        let finalized = tester.harness.finalized_checkpoint();
+        assert!(finalized.epoch > 0);
+
+        let (state, block, blobs) = load(&tester, &honest, None, 2).await.unwrap();
+        assert_eq!(block.canonical_root(), finalized.root);
+        assert_eq!(
+            state.slot(),
+            finalized.epoch.start_slot(E::slots_per_epoch())
+        );
+        assert!(blobs.is_none());
+
+        // The dishonest provider doesn't prevent the honest quorum.
+        let urls = [honest[0].clone(), honest[1].clone(), dishonest.clone()];
+        let (_, block, _) = load(&tester, &urls, None, 2).await.unwrap();
+        assert_eq!(block.canonical_root(), finalized.root);
+        assert!(load(&tester, &urls, None, 3).await.is_err());
+
+        // The checkpoint of the dishonest provider is rejected by the honest providers.
+        let urls = [dishonest, honest[0].clone(), honest[1].clone()];
+        assert!(load(&tester, &urls, None, 2).await.is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn reject_forged_state() {
+        let tester = finalized_chain(0).await;
+        let urls = [serve(&tester).await, serve(&tester).await];
+        let (state, block, _) = load(&tester, &urls, None, 2).await.unwrap();
+
+        // The honest checkpoint is accepted from the local files.
+        let (_, loaded_block, _) = load(&tester, &urls, Some(local_checkpoint(&state, &block)), 3)
+            .await
+            .unwrap();
+        assert_eq!(loaded_block, block);
+
+        // A forged state with the honest block is rejected, even though the providers agree on
+        // the block.
+        let mut forged_state = state.clone();
+        *forged_state.get_balance_mut(0).unwrap() += 1;
+        let roots = CheckpointRoots::new(&forged_state, &block);
+        assert_eq!(
+            remote_vote::<E>(&tester.client, roots).await,
+            Vote::StateRootMismatch {
+                state_root: state.canonical_root()
+            }
+        );
+        let forged = local_checkpoint(&forged_state, &block);
+        assert!(load(&tester, &urls, Some(forged), 2).await.is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn reject_stale_checkpoint() {
+        let tester = finalized_chain(0).await;
+        let urls = [serve(&tester).await, serve(&tester).await];
+        let (state, block, _) = load(&tester, &urls, None, 2).await.unwrap();
+        let roots = CheckpointRoots::new(&state, &block);
+        assert_eq!(remote_vote::<E>(&tester.client, roots).await, Vote::Agree);
+
+        // Finalize beyond the lag allowed for the checkpoint.
+        extend_chain(&tester, MAX_CHECKPOINT_LAG_EPOCHS + 2).await;
+        let finalized_epoch = tester.harness.finalized_checkpoint().epoch;
+        assert!(roots.epoch::<E>() + MAX_CHECKPOINT_LAG_EPOCHS < finalized_epoch);
+
+        // The old checkpoint is still canonical, but is rejected.
+        assert_eq!(
+            remote_vote::<E>(&tester.client, roots).await,
+            Vote::Stale { finalized_epoch }
+        );
+        let old = local_checkpoint(&state, &block);
+        assert!(load(&tester, &urls, Some(old), 2).await.is_err());
+
+        // The latest checkpoint is accepted.
+        let (_, block, _) = load(&tester, &urls, None, 2).await.unwrap();
+        assert_eq!(
+            block.canonical_root(),
+            tester.harness.finalized_checkpoint().root
+        );
+    }
+}
@@ -36,12 +36,30 @@ pub enum ClientGenesis {
         anchor_state_bytes: Vec<u8>,
         anchor_block_bytes: Vec<u8>,
         anchor_blobs_bytes: Option<Vec<u8>>,
+        /// The genesis state to use instead of the one from the `Eth2NetworkConfig`.
+        genesis_state_bytes: Option<Vec<u8>>,
     },
-    CheckpointSyncUrl {
-        url: SensitiveUrl,
+    /// Downloads the checkpoint from the remote beacon nodes at `urls`, or loads it from
+    /// `local_checkpoint`, and only uses it if at least `quorum` of these providers agree on it.
+    ///
+    /// A remote provider agrees if it has the same block root and state root at the checkpoint's
+    /// slots, considers them finalized, and hasn't finalized a checkpoint more than
+    /// `MAX_CHECKPOINT_LAG_EPOCHS` after it.
+    CheckpointSync {
+        urls: Vec<SensitiveUrl>,
+        local_checkpoint: Option<CheckpointSszBytes>,
+        quorum: usize,
     },
 }
 
+/// A checkpoint state, block and blobs loaded from local SSZ files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointSszBytes {
+    pub anchor_state_bytes: Vec<u8>,
+    pub anchor_block_bytes: Vec<u8>,
+    pub anchor_blobs_bytes: Option<Vec<u8>>,
+}
+
 /// The core configuration of a Lighthouse beacon node.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -80,6 +98,8 @@ pub struct Config {
     pub genesis_state_url: Option<String>,
     pub genesis_state_url_timeout: Duration,
     pub allow_insecure_genesis_sync: bool,
+    /// Directory of era files to import finalized history from, instead of backfill sync.
+    pub era_import_dir: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -111,6 +131,7 @@ impl Default for Config {
             // This default value should always be overwritten by the CLI default value.
             genesis_state_url_timeout: Duration::from_secs(60),
             allow_insecure_genesis_sync: false,
+            era_import_dir: None,
         }
     }
 }
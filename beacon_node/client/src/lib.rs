@@ -1,6 +1,7 @@
 extern crate slog;
 
 mod address_change_broadcast;
+mod checkpoint_sync;
 mod compute_light_client_updates;
 pub mod config;
 mod metrics;
@@ -16,7 +17,7 @@ use std::sync::Arc;
 
 pub use beacon_chain::{BeaconChainTypes, Eth1ChainBackend};
 pub use builder::ClientBuilder;
-pub use config::{ClientGenesis, Config as ClientConfig};
+pub use config::{CheckpointSszBytes, ClientGenesis, Config as ClientConfig};
 pub use eth2_config::Eth2Config;
 
 /// The core "beacon node" client.
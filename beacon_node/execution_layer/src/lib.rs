@@ -353,6 +353,8 @@ pub struct Config {
     pub builder_url: Option<SensitiveUrl>,
     /// User agent to send with requests to the builder API.
     pub builder_user_agent: Option<String>,
+    /// Disable the use of SSZ-encoded requests and responses with the builder API.
+    pub builder_disable_ssz: bool,
     /// JWT secrets for the above endpoints running the engine api.
     pub secret_files: Vec<PathBuf>,
     /// The default fee recipient to use on the beacon node if none if provided from
@@ -381,6 +383,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
             execution_endpoints: urls,
             builder_url,
             builder_user_agent,
+            builder_disable_ssz,
             secret_files,
             suggested_fee_recipient,
             jwt_id,
@@ -456,7 +459,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
         };
 
         if let Some(builder_url) = builder_url {
-            el.set_builder_url(builder_url, builder_user_agent)?;
+            el.set_builder_url(builder_url, builder_user_agent, builder_disable_ssz)?;
         }
 
         Ok(el)
@@ -478,14 +481,17 @@ impl<T: EthSpec> ExecutionLayer<T> {
         &self,
         builder_url: SensitiveUrl,
         builder_user_agent: Option<String>,
+        builder_disable_ssz: bool,
     ) -> Result<(), Error> {
-        let builder_client = BuilderHttpClient::new(builder_url.clone(), builder_user_agent)
-            .map_err(Error::Builder)?;
+        let builder_client =
+            BuilderHttpClient::new(builder_url.clone(), builder_user_agent, builder_disable_ssz)
+                .map_err(Error::Builder)?;
         info!(
             self.log(),
             "Using external block builder";
             "builder_url" => ?builder_url,
             "local_user_agent" => builder_client.get_user_agent(),
+            "ssz_disabled" => builder_disable_ssz,
         );
         self.inner.builder.swap(Some(Arc::new(builder_client)));
         Ok(())
@@ -1,10 +1,15 @@
 use crate::test_utils::{DEFAULT_BUILDER_PAYLOAD_VALUE_WEI, DEFAULT_JWT_SECRET};
 use crate::{Config, ExecutionLayer, PayloadAttributes};
-use eth2::types::{BlobsBundle, BlockId, StateId, ValidatorId};
-use eth2::{BeaconNodeHttpClient, Timeouts};
+use eth2::types::{Accept, BlobsBundle, BlockId, StateId, ValidatorId};
+use eth2::{
+    BeaconNodeHttpClient, Timeouts, CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER,
+    SSZ_CONTENT_TYPE_HEADER,
+};
 use fork_choice::ForkchoiceUpdateParameters;
 use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
+use serde::Serialize;
+use ssz::Encode;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::future::Future;
@@ -201,6 +206,8 @@ pub struct MockBuilder<E: EthSpec> {
     builder_sk: SecretKey,
     operations: Arc<RwLock<Vec<Operation>>>,
     invalidate_signatures: Arc<RwLock<bool>>,
+    /// Respond with SSZ when the client's `Accept` header prefers it.
+    ssz_enabled: Arc<RwLock<bool>>,
 }
 
 impl<E: EthSpec> MockBuilder<E> {
@@ -251,6 +258,7 @@ impl<E: EthSpec> MockBuilder<E> {
             builder_sk: sk,
             operations: Arc::new(RwLock::new(vec![])),
             invalidate_signatures: Arc::new(RwLock::new(false)),
+            ssz_enabled: Arc::new(RwLock::new(false)),
         }
     }
 
@@ -268,6 +276,21 @@ impl<E: EthSpec> MockBuilder<E> {
         *self.invalidate_signatures.write() = false;
     }
 
+    /// Serve SSZ responses to clients that ask for them.
+    pub fn enable_ssz(&self) {
+        *self.ssz_enabled.write() = true;
+    }
+
+    /// Only serve JSON responses, regardless of the client's `Accept` header.
+    pub fn disable_ssz(&self) {
+        *self.ssz_enabled.write() = false;
+    }
+
+    /// Returns `true` if a response to a request with the given `Accept` header should be SSZ.
+    fn should_respond_with_ssz(&self, accept: Option<Accept>) -> bool {
+        *self.ssz_enabled.read() && matches!(accept, Some(Accept::Ssz))
+    }
+
     fn apply_operations<B: BidStuff<E>>(&self, bid: &mut B) {
         let mut guard = self.operations.write();
         while let Some(op) = guard.pop() {
@@ -310,11 +333,23 @@ pub fn serve<E: EthSpec>(
 
     let blinded_block = prefix
         .and(warp::path("blinded_blocks"))
-        .and(warp::body::json())
         .and(warp::path::end())
+        .and(warp::header::optional::<String>(CONTENT_TYPE_HEADER))
+        .and(warp::header::optional::<Accept>("accept"))
+        .and(warp::body::bytes())
         .and(ctx_filter.clone())
         .and_then(
-            |block: SignedBlindedBeaconBlock<E>, builder: MockBuilder<E>| async move {
+            |content_type: Option<String>,
+             accept: Option<Accept>,
+             body: bytes::Bytes,
+             builder: MockBuilder<E>| async move {
+                let block = if content_type.as_deref() == Some(SSZ_CONTENT_TYPE_HEADER) {
+                    SignedBlindedBeaconBlock::<E>::from_ssz_bytes(&body, &builder.spec)
+                        .map_err(|_| reject("invalid SSZ block"))?
+                } else {
+                    serde_json::from_slice::<SignedBlindedBeaconBlock<E>>(&body)
+                        .map_err(|_| reject("invalid JSON block"))?
+                };
                 let slot = block.slot();
                 let root = match block {
                     SignedBlindedBeaconBlock::Base(_) | types::SignedBeaconBlock::Altair(_) => {
@@ -342,17 +377,7 @@ pub fn serve<E: EthSpec>(
                     data: payload,
                 };
 
-                let json_payload = serde_json::to_string(&resp)
-                    .map_err(|_| reject("coudn't serialize response"))?;
-                Ok::<_, warp::reject::Rejection>(
-                    warp::http::Response::builder()
-                        .status(200)
-                        .body(
-                            serde_json::to_string(&json_payload)
-                                .map_err(|_| reject("nvalid JSON"))?,
-                        )
-                        .unwrap(),
-                )
+                encode_response(resp, fork_name, builder.should_respond_with_ssz(accept))
             },
         );
 
@@ -372,11 +397,13 @@ pub fn serve<E: EthSpec>(
                 .or_else(|_| async { Err(reject("Invalid pubkey")) }),
         )
         .and(warp::path::end())
+        .and(warp::header::optional::<Accept>("accept"))
         .and(ctx_filter.clone())
         .and_then(
             |slot: Slot,
              parent_hash: ExecutionBlockHash,
              pubkey: PublicKeyBytes,
+             accept: Option<Accept>,
              builder: MockBuilder<E>| async move {
                 let fork = builder.spec.fork_name_at_slot::<E>(slot);
                 let signed_cached_data = builder
@@ -623,14 +650,7 @@ pub fn serve<E: EthSpec>(
                     metadata: Default::default(),
                     data: signed_bid,
                 };
-                let json_bid = serde_json::to_string(&resp)
-                    .map_err(|_| reject("coudn't serialize signed bid"))?;
-                Ok::<_, Rejection>(
-                    warp::http::Response::builder()
-                        .status(200)
-                        .body(json_bid)
-                        .unwrap(),
-                )
+                encode_response(resp, fork_name, builder.should_respond_with_ssz(accept))
             },
         );
 
@@ -645,6 +665,30 @@ pub fn serve<E: EthSpec>(
     Ok((listening_socket, server))
 }
 
+/// Encode a fork-versioned response as either SSZ or JSON.
+///
+/// SSZ responses carry the fork in the `Eth-Consensus-Version` header, since the body does not.
+fn encode_response<T: Serialize + Encode>(
+    resp: ForkVersionedResponse<T>,
+    fork_name: ForkName,
+    ssz: bool,
+) -> Result<warp::http::Response<Vec<u8>>, Rejection> {
+    let response = if ssz {
+        warp::http::Response::builder()
+            .status(200)
+            .header(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER)
+            .header(CONSENSUS_VERSION_HEADER, fork_name.to_string())
+            .body(resp.data.as_ssz_bytes())
+    } else {
+        let json = serde_json::to_vec(&resp).map_err(|_| reject("couldn't serialize response"))?;
+        warp::http::Response::builder()
+            .status(200)
+            .header(CONTENT_TYPE_HEADER, "application/json")
+            .body(json)
+    };
+    response.map_err(|_| reject("couldn't build response"))
+}
+
 fn reject(msg: &'static str) -> Rejection {
     warp::reject::custom(Custom(msg.to_string()))
 }
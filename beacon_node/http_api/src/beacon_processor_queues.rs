@@ -0,0 +1,37 @@
+//! Reports the state of the `BeaconProcessor` queues.
+use beacon_processor::{BeaconProcessorQueueState, ReprocessQueueState, WorkQueueState};
+use eth2::lighthouse::{BeaconProcessorQueues, ReprocessQueueSummary, WorkQueue};
+
+pub fn beacon_processor_queues(state: BeaconProcessorQueueState) -> BeaconProcessorQueues {
+    BeaconProcessorQueues {
+        active_workers: state.active_workers,
+        max_workers: state.max_workers,
+        work_queues: state.work_queues.into_iter().map(work_queue).collect(),
+        reprocess_queue: reprocess_queue_summary(state.reprocess_queue),
+        updated_at: state
+            .updated_at
+            .map(|updated_at| updated_at.as_millis() as u64),
+    }
+}
+
+fn work_queue(state: WorkQueueState) -> WorkQueue {
+    WorkQueue {
+        work_type: state.work_type.to_string(),
+        length: state.length,
+        capacity: state.capacity,
+        dropped: state.dropped,
+        oldest_item_age_ms: state.oldest_item_age.map(|age| age.as_millis() as u64),
+    }
+}
+
+fn reprocess_queue_summary(state: ReprocessQueueState) -> ReprocessQueueSummary {
+    ReprocessQueueSummary {
+        gossip_blocks: state.gossip_blocks,
+        rpc_blocks: state.rpc_blocks,
+        aggregates: state.aggregates,
+        unaggregates: state.unaggregates,
+        awaited_block_roots: state.awaited_block_roots,
+        light_client_updates: state.light_client_updates,
+        backfill_batches: state.backfill_batches,
+    }
+}
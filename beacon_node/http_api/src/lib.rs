@@ -7,6 +7,7 @@
 
 mod attestation_performance;
 mod attester_duties;
+mod beacon_processor_queues;
 mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
@@ -14,12 +15,15 @@ mod build_block_contents;
 mod builder_states;
 mod database;
 mod metrics;
+mod peer_management;
 mod produce_block;
 mod proposer_duties;
 mod publish_attestations;
 mod publish_blocks;
+mod slasher_queries;
 mod standard_block_rewards;
 mod state_id;
+mod state_proof;
 mod sync_committee_rewards;
 mod sync_committees;
 mod task_spawner;
@@ -36,7 +40,9 @@ use beacon_chain::{
     validator_monitor::timestamp_now, AttestationError as AttnError, BeaconChain, BeaconChainError,
     BeaconChainTypes, WhenSlotSkipped,
 };
-use beacon_processor::{work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorSend};
+use beacon_processor::{
+    work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorSend, QueueStateHandle,
+};
 pub use block_id::BlockId;
 use builder_states::get_next_withdrawals;
 use bytes::Bytes;
@@ -47,6 +53,7 @@ use eth2::types::{
     ValidatorsRequestBody,
 };
 use eth2::{CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER};
+use lighthouse_network::rpc::methods::MAX_REQUEST_LIGHT_CLIENT_UPDATES;
 use lighthouse_network::{types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage};
 use lighthouse_version::version_with_platform;
 use logging::SSELoggingComponents;
@@ -59,7 +66,7 @@ pub use publish_blocks::{
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
-use ssz::Encode;
+use ssz::{Decode, Encode};
 pub use state_id::StateId;
 use std::borrow::Cow;
 use std::future::Future;
@@ -80,11 +87,12 @@ use tokio_stream::{
 };
 use types::{
     fork_versioned_response::EmptyMetadata, Attestation, AttestationData, AttestationShufflingId,
-    AttesterSlashing, BeaconStateError, CommitteeCache, ConfigAndPreset, Epoch, EthSpec, ForkName,
-    ForkVersionedResponse, Hash256, ProposerPreparationData, ProposerSlashing, RelativeEpoch,
-    SignedAggregateAndProof, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
-    SignedContributionAndProof, SignedValidatorRegistrationData, SignedVoluntaryExit, Slot,
-    SyncCommitteeMessage, SyncContributionData,
+    AttesterSlashing, BeaconStateError, ChainSpec, CommitteeCache, ConfigAndPreset, Epoch, EthSpec,
+    ForkName, ForkVersionedResponse, Hash256, IndexedAttestation, LightClientUpdate,
+    ProposerPreparationData, ProposerSlashing, RelativeEpoch, SignedAggregateAndProof,
+    SignedBlindedBeaconBlock, SignedBlsToExecutionChange, SignedContributionAndProof,
+    SignedValidatorRegistrationData, SignedVoluntaryExit, Slot, SyncCommitteeMessage,
+    SyncContributionData,
 };
 use validator::pubkey_to_validator_index;
 use version::{
@@ -131,6 +139,7 @@ pub struct Context<T: BeaconChainTypes> {
     pub network_globals: Option<Arc<NetworkGlobals<T::EthSpec>>>,
     pub beacon_processor_send: Option<BeaconProcessorSend<T::EthSpec>>,
     pub beacon_processor_reprocess_send: Option<Sender<ReprocessQueueMessage>>,
+    pub beacon_processor_queue_state: Option<QueueStateHandle>,
     pub eth1_service: Option<eth1::Service>,
     pub sse_logging_components: Option<SSELoggingComponents>,
     pub log: Logger,
@@ -542,6 +551,20 @@ pub fn serve<T: BeaconChainTypes>(
         .filter(|_| config.enable_beacon_processor);
     let reprocess_send_filter = warp::any().map(move || beacon_processor_reprocess_send.clone());
 
+    // Create a `warp` filter that provides access to the state of the beacon processor queues.
+    let inner_queue_state = ctx.beacon_processor_queue_state.clone();
+    let queue_state_filter =
+        warp::any()
+            .map(move || inner_queue_state.clone())
+            .and_then(|queue_state| async move {
+                match queue_state {
+                    Some(queue_state) => Ok(queue_state),
+                    None => Err(warp_utils::reject::custom_not_found(
+                        "The beacon processor is not running.".to_string(),
+                    )),
+                }
+            });
+
     let duplicate_block_status_code = ctx.config.duplicate_block_status_code;
 
     /*
@@ -2408,6 +2431,83 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET beacon/light_client/updates?start_period,count
+    let get_beacon_light_client_updates = beacon_light_client_path
+        .clone()
+        .and(task_spawner_filter.clone())
+        .and(warp::path("updates"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::LightClientUpdatesQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .then(
+            |chain: Arc<BeaconChain<T>>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             query: api_types::LightClientUpdatesQuery,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    if query.count > MAX_REQUEST_LIGHT_CLIENT_UPDATES {
+                        return Err(warp_utils::reject::custom_bad_request(format!(
+                            "count must not exceed {MAX_REQUEST_LIGHT_CLIENT_UPDATES}"
+                        )));
+                    }
+
+                    let updates = chain
+                        .get_light_client_updates(query.start_period, query.count)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "Unable to obtain LightClientUpdates: {e:?}"
+                            ))
+                        })?;
+
+                    let fork_name_of = |update: &LightClientUpdate<T::EthSpec>| {
+                        chain
+                            .spec
+                            .fork_name_at_slot::<T::EthSpec>(update.attested_header.beacon.slot)
+                    };
+
+                    match accept_header {
+                        // Each update is sent as a chunk of its length (uint64 little-endian),
+                        // the fork digest of its attested header and its SSZ bytes.
+                        Some(api_types::Accept::Ssz) => {
+                            let mut bytes = vec![];
+                            for update in &updates {
+                                let fork_digest = ChainSpec::compute_fork_digest(
+                                    chain.spec.fork_version_for_name(fork_name_of(update)),
+                                    chain.genesis_validators_root,
+                                );
+                                let update_bytes = update.as_ssz_bytes();
+                                let chunk_len = fork_digest.len() + update_bytes.len();
+                                bytes.extend_from_slice(&(chunk_len as u64).to_le_bytes());
+                                bytes.extend_from_slice(&fork_digest);
+                                bytes.extend_from_slice(&update_bytes);
+                            }
+                            Response::builder()
+                                .status(200)
+                                .body(bytes.into())
+                                .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                                .map_err(|e| {
+                                    warp_utils::reject::custom_server_error(format!(
+                                        "failed to create response: {}",
+                                        e
+                                    ))
+                                })
+                        }
+                        _ => {
+                            let updates = updates
+                                .into_iter()
+                                .map(|update| ForkVersionedResponse {
+                                    version: Some(fork_name_of(&update)),
+                                    metadata: EmptyMetadata {},
+                                    data: update,
+                                })
+                                .collect::<Vec<_>>();
+                            Ok(warp::reply::json(&updates).into_response())
+                        }
+                    }
+                })
+            },
+        );
+
     /*
      * beacon/rewards
      */
@@ -4004,7 +4104,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("connected"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(network_globals)
+        .and(network_globals.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
@@ -4022,6 +4122,155 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    let peers_path = warp::path("lighthouse").and(warp::path("peers"));
+
+    // GET lighthouse/peers/bans
+    let get_lighthouse_peers_bans = peers_path
+        .and(warp::path("bans"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(peer_management::bans(&network_globals))
+                })
+            },
+        );
+
+    // GET lighthouse/peers/trusted
+    let get_lighthouse_peers_trusted = peers_path
+        .and(warp::path("trusted"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals)
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(peer_management::trusted_peers(&network_globals))
+                })
+            },
+        );
+
+    // POST lighthouse/peers/ban
+    let post_lighthouse_peers_ban = peers_path
+        .and(warp::path("ban"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |request: eth2::lighthouse::PeerBanRequest,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    publish_network_message(&network_tx, peer_management::ban_message(request)?)
+                })
+            },
+        );
+
+    // POST lighthouse/peers/unban
+    let post_lighthouse_peers_unban = peers_path
+        .and(warp::path("unban"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |request: eth2::lighthouse::PeerUnbanRequest,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    publish_network_message(&network_tx, peer_management::unban_message(request)?)
+                })
+            },
+        );
+
+    // POST lighthouse/peers/trust
+    let post_lighthouse_peers_trust = peers_path
+        .and(warp::path("trust"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |request: eth2::lighthouse::TrustedPeerRequest,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::SetTrustedPeer {
+                            peer_id: peer_management::parse_peer_id(&request.peer_id)?,
+                            trusted: true,
+                        },
+                    )
+                })
+            },
+        );
+
+    // POST lighthouse/peers/untrust
+    let post_lighthouse_peers_untrust = peers_path
+        .and(warp::path("untrust"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |request: eth2::lighthouse::TrustedPeerRequest,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::SetTrustedPeer {
+                            peer_id: peer_management::parse_peer_id(&request.peer_id)?,
+                            trusted: false,
+                        },
+                    )
+                })
+            },
+        );
+
+    // POST lighthouse/peers/dial
+    let post_lighthouse_peers_dial = peers_path
+        .and(warp::path("dial"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |request: eth2::lighthouse::PeerDialRequest,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    publish_network_message(&network_tx, peer_management::dial_message(request)?)
+                })
+            },
+        );
+
+    // POST lighthouse/peers/disconnect
+    let post_lighthouse_peers_disconnect = peers_path
+        .and(warp::path("disconnect"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |request: eth2::lighthouse::PeerDisconnectRequest,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    publish_network_message(
+                        &network_tx,
+                        peer_management::disconnect_message(request)?,
+                    )
+                })
+            },
+        );
+
     // GET lighthouse/proto_array
     let get_lighthouse_proto_array = warp::path("lighthouse")
         .and(warp::path("proto_array"))
@@ -4181,6 +4430,32 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/beacon/states/{state_id}/proof
+    let get_lighthouse_beacon_states_proof = warp::path("lighthouse")
+        .and(warp::path("beacon"))
+        .and(warp::path("states"))
+        .and(warp::path::param::<StateId>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid state ID".to_string(),
+            ))
+        }))
+        .and(warp::path("proof"))
+        .and(warp::path::end())
+        .and(multi_key_query::<api_types::StateProofQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |state_id: StateId,
+             query_res: Result<api_types::StateProofQuery, warp::Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let query = query_res?;
+                    state_proof::get_state_proof(state_id, query, &chain)
+                })
+            },
+        );
+
     let database_path = warp::path("lighthouse").and(warp::path("database"));
 
     // GET lighthouse/database/info
@@ -4211,6 +4486,106 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    let slasher_path = warp::path("lighthouse").and(warp::path("slasher"));
+
+    // GET lighthouse/slasher/slashings
+    let get_lighthouse_slasher_slashings = slasher_path
+        .and(warp::path("slashings"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner
+                    .blocking_json_task(Priority::P1, move || slasher_queries::get_slashings(chain))
+            },
+        );
+
+    // GET lighthouse/slasher/targets/{validator_index}
+    let get_lighthouse_slasher_targets = slasher_path
+        .and(warp::path("targets"))
+        .and(warp::path::param::<u64>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid validator index".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |validator_index: u64,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    slasher_queries::get_targets(validator_index, chain)
+                })
+            },
+        );
+
+    // POST lighthouse/slasher/check_attestation
+    let post_lighthouse_slasher_check_attestation = slasher_path
+        .and(warp::path("check_attestation"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |attestation: IndexedAttestation<T::EthSpec>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    slasher_queries::check_attestation(attestation, chain)
+                })
+            },
+        );
+
+    let operation_pool_path = warp::path("lighthouse")
+        .and(warp::path("operation_pool"))
+        .and(warp::path::end());
+
+    // GET lighthouse/operation_pool
+    let get_lighthouse_operation_pool = operation_pool_path
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    Response::builder()
+                        .status(200)
+                        .body(chain.export_operation_pool().as_ssz_bytes().into())
+                        .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "failed to create response: {}",
+                                e
+                            ))
+                        })
+                })
+            },
+        );
+
+    // POST lighthouse/operation_pool
+    let post_lighthouse_operation_pool = operation_pool_path
+        .and(warp::body::bytes())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |body: Bytes, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let operation_pool =
+                        eth2::lighthouse::ExportedOperationPool::<T::EthSpec>::from_ssz_bytes(
+                            &body,
+                        )
+                        .map_err(|e| {
+                            warp_utils::reject::custom_bad_request(format!("invalid SSZ: {e:?}"))
+                        })?;
+                    chain
+                        .import_operation_pool(operation_pool)
+                        .map_err(warp_utils::reject::beacon_chain_error)
+                })
+            },
+        );
+
     // GET lighthouse/analysis/block_rewards
     let get_lighthouse_block_rewards = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4348,6 +4723,18 @@ pub fn serve<T: BeaconChainTypes>(
                                 api_types::EventTopic::BlockReward => {
                                     event_handler.subscribe_block_reward()
                                 }
+                                api_types::EventTopic::AttesterSlashing => {
+                                    event_handler.subscribe_attester_slashing()
+                                }
+                                api_types::EventTopic::ProposerSlashing => {
+                                    event_handler.subscribe_proposer_slashing()
+                                }
+                                api_types::EventTopic::BlsToExecutionChange => {
+                                    event_handler.subscribe_bls_to_execution_change()
+                                }
+                                api_types::EventTopic::BlockGossip => {
+                                    event_handler.subscribe_block_gossip()
+                                }
                             };
 
                             receivers.push(
@@ -4433,6 +4820,50 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/beacon_processor
+    //
+    // This and the event stream below are served without using the beacon processor, so that they
+    // remain available when it is overwhelmed.
+    let beacon_processor_path = warp::path("lighthouse").and(warp::path("beacon_processor"));
+    let get_lighthouse_beacon_processor = beacon_processor_path
+        .and(warp::path::end())
+        .and(queue_state_filter.clone())
+        .map(|queue_state: QueueStateHandle| {
+            warp::reply::json(&beacon_processor_queues::beacon_processor_queues(
+                queue_state.current(),
+            ))
+            .into_response()
+        });
+
+    // Subscribe to changes to the beacon processor queues via Server Side Events, starting with
+    // the current state.
+    // GET lighthouse/beacon_processor/events
+    let get_lighthouse_beacon_processor_events = beacon_processor_path
+        .and(warp::path("events"))
+        .and(warp::path::end())
+        .and(queue_state_filter)
+        .map(|queue_state: QueueStateHandle| {
+            // Subscribe before reading the current state, so that no update is missed.
+            let updates = BroadcastStream::new(queue_state.subscribe());
+            let s = tokio_stream::once(Ok(queue_state.current()))
+                .chain(updates)
+                .map(|msg| match msg {
+                    Ok(state) => Event::default()
+                        .json_data(beacon_processor_queues::beacon_processor_queues(state))
+                        .unwrap_or_else(|e| {
+                            Event::default().comment(format!("error - bad json: {e:?}"))
+                        }),
+                    // Do not terminate the stream if the channel fills up, the next update will
+                    // contain the complete state.
+                    Err(BroadcastStreamRecvError::Lagged(n)) => {
+                        Event::default().comment(format!("error - dropped {n} messages"))
+                    }
+                })
+                .map(Ok::<_, std::convert::Infallible>);
+
+            warp::sse::reply(warp::sse::keep_alive().stream(s)).into_response()
+        });
+
     // Define the ultimate set of routes that will be provided to the server.
     // Use `uor` rather than `or` in order to simplify types (see `UnifyingOrFilter`).
     let routes = warp::get()
@@ -4487,6 +4918,8 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_nat)
                 .uor(get_lighthouse_peers)
                 .uor(get_lighthouse_peers_connected)
+                .uor(get_lighthouse_peers_bans)
+                .uor(get_lighthouse_peers_trusted)
                 .uor(get_lighthouse_proto_array)
                 .uor(get_lighthouse_validator_inclusion_global)
                 .uor(get_lighthouse_validator_inclusion)
@@ -4494,7 +4927,11 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_eth1_block_cache)
                 .uor(get_lighthouse_eth1_deposit_cache)
                 .uor(get_lighthouse_staking)
+                .uor(get_lighthouse_beacon_states_proof)
                 .uor(get_lighthouse_database_info)
+                .uor(get_lighthouse_slasher_slashings)
+                .uor(get_lighthouse_slasher_targets)
+                .uor(get_lighthouse_operation_pool)
                 .uor(get_lighthouse_block_rewards)
                 .uor(get_lighthouse_attestation_performance)
                 .uor(
@@ -4509,11 +4946,17 @@ pub fn serve<T: BeaconChainTypes>(
                     enable(ctx.config.enable_light_client_server)
                         .and(get_beacon_light_client_bootstrap),
                 )
+                .uor(
+                    enable(ctx.config.enable_light_client_server)
+                        .and(get_beacon_light_client_updates),
+                )
                 .uor(get_lighthouse_block_packing_efficiency)
                 .uor(get_lighthouse_merge_readiness)
                 .uor(get_events)
                 .uor(get_expected_withdrawals)
                 .uor(lighthouse_log_events.boxed())
+                .uor(get_lighthouse_beacon_processor)
+                .uor(get_lighthouse_beacon_processor_events)
                 .recover(warp_utils::reject::handle_rejection),
         )
         .boxed()
@@ -4525,7 +4968,8 @@ pub fn serve<T: BeaconChainTypes>(
                         post_beacon_blocks_ssz
                             .uor(post_beacon_blocks_v2_ssz)
                             .uor(post_beacon_blinded_blocks_ssz)
-                            .uor(post_beacon_blinded_blocks_v2_ssz),
+                            .uor(post_beacon_blinded_blocks_v2_ssz)
+                            .uor(post_lighthouse_operation_pool),
                     )
                     .uor(post_beacon_blocks)
                     .uor(post_beacon_blinded_blocks)
@@ -4552,6 +4996,13 @@ pub fn serve<T: BeaconChainTypes>(
                     .uor(post_validator_liveness_epoch)
                     .uor(post_lighthouse_liveness)
                     .uor(post_lighthouse_database_reconstruct)
+                    .uor(post_lighthouse_peers_ban)
+                    .uor(post_lighthouse_peers_unban)
+                    .uor(post_lighthouse_peers_trust)
+                    .uor(post_lighthouse_peers_untrust)
+                    .uor(post_lighthouse_peers_dial)
+                    .uor(post_lighthouse_peers_disconnect)
+                    .uor(post_lighthouse_slasher_check_attestation)
                     .uor(post_lighthouse_block_rewards)
                     .uor(post_lighthouse_ui_validator_metrics)
                     .uor(post_lighthouse_ui_validator_info)
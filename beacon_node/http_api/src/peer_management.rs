@@ -0,0 +1,120 @@
+//! Ban, trust, dial and disconnect peers on behalf of the node operator.
+use eth2::lighthouse::{
+    BannedIp, BannedPeer, PeerBanRequest, PeerBans, PeerDialRequest, PeerDisconnectRequest,
+    PeerUnbanRequest,
+};
+use lighthouse_network::{rpc::GoodbyeReason, Enr, Multiaddr, NetworkGlobals, PeerId};
+use network::NetworkMessage;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use types::EthSpec;
+
+/// The peers and IP addresses currently banned by the node operator.
+pub fn bans<E: EthSpec>(network_globals: &NetworkGlobals<E>) -> PeerBans {
+    let peers = network_globals.peers.read();
+    let manual_bans = peers.manual_bans();
+    PeerBans {
+        peers: manual_bans
+            .banned_peers()
+            .filter(|(peer_id, _)| manual_bans.is_peer_banned(peer_id))
+            .map(|(peer_id, expires)| BannedPeer {
+                peer_id: peer_id.to_string(),
+                expires: expires.map(unix_timestamp),
+            })
+            .collect(),
+        ips: manual_bans
+            .banned_ips()
+            .filter(|(ip, _)| manual_bans.is_ip_banned(ip))
+            .map(|(ip, expires)| BannedIp {
+                ip: *ip,
+                expires: expires.map(unix_timestamp),
+            })
+            .collect(),
+    }
+}
+
+/// The ids of the peers that are currently trusted.
+pub fn trusted_peers<E: EthSpec>(network_globals: &NetworkGlobals<E>) -> Vec<String> {
+    network_globals
+        .peers
+        .read()
+        .trusted_peers()
+        .map(ToString::to_string)
+        .collect()
+}
+
+pub fn ban_message<E: EthSpec>(
+    request: PeerBanRequest,
+) -> Result<NetworkMessage<E>, warp::Rejection> {
+    let expires = request
+        .duration_seconds
+        .map(|duration| {
+            SystemTime::now()
+                .checked_add(Duration::from_secs(duration))
+                .ok_or_else(|| {
+                    warp_utils::reject::custom_bad_request(format!(
+                        "duration_seconds is too large: {}",
+                        duration
+                    ))
+                })
+        })
+        .transpose()?;
+    match (request.peer_id, request.ip) {
+        (Some(peer_id), None) => Ok(NetworkMessage::BanPeer {
+            peer_id: parse_peer_id(&peer_id)?,
+            expires,
+        }),
+        (None, Some(ip)) => Ok(NetworkMessage::BanIp { ip, expires }),
+        _ => Err(warp_utils::reject::custom_bad_request(
+            "exactly one of peer_id and ip must be provided".to_string(),
+        )),
+    }
+}
+
+pub fn unban_message<E: EthSpec>(
+    request: PeerUnbanRequest,
+) -> Result<NetworkMessage<E>, warp::Rejection> {
+    match (request.peer_id, request.ip) {
+        (Some(peer_id), None) => Ok(NetworkMessage::UnbanPeer {
+            peer_id: parse_peer_id(&peer_id)?,
+        }),
+        (None, Some(ip)) => Ok(NetworkMessage::UnbanIp { ip }),
+        _ => Err(warp_utils::reject::custom_bad_request(
+            "exactly one of peer_id and ip must be provided".to_string(),
+        )),
+    }
+}
+
+pub fn dial_message<E: EthSpec>(
+    request: PeerDialRequest,
+) -> Result<NetworkMessage<E>, warp::Rejection> {
+    if request.address.starts_with("enr:") {
+        let enr = Enr::from_str(&request.address)
+            .map_err(|e| warp_utils::reject::custom_bad_request(format!("invalid ENR: {}", e)))?;
+        Ok(NetworkMessage::DialEnr { enr })
+    } else {
+        let multiaddr = Multiaddr::from_str(&request.address).map_err(|e| {
+            warp_utils::reject::custom_bad_request(format!("invalid multiaddr: {}", e))
+        })?;
+        Ok(NetworkMessage::DialMultiaddr { multiaddr })
+    }
+}
+
+pub fn disconnect_message<E: EthSpec>(
+    request: PeerDisconnectRequest,
+) -> Result<NetworkMessage<E>, warp::Rejection> {
+    Ok(NetworkMessage::DisconnectPeer {
+        peer_id: parse_peer_id(&request.peer_id)?,
+        reason: GoodbyeReason::from(request.reason),
+    })
+}
+
+pub fn parse_peer_id(peer_id: &str) -> Result<PeerId, warp::Rejection> {
+    PeerId::from_str(peer_id)
+        .map_err(|e| warp_utils::reject::custom_bad_request(format!("invalid peer id: {}", e)))
+}
+
+fn unix_timestamp(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
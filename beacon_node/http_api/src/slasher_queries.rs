@@ -0,0 +1,84 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{DetectedSlashing, SlasherSlashings, SlasherTargets};
+use slasher::Slasher;
+use std::sync::Arc;
+use types::{AttesterSlashing, IndexedAttestation};
+use warp_utils::reject::{
+    beacon_chain_error, custom_bad_request, custom_not_found, custom_server_error,
+};
+
+fn slasher<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<&Arc<Slasher<T::EthSpec>>, warp::Rejection> {
+    chain.slasher.as_ref().ok_or_else(|| {
+        custom_not_found("slasher is not enabled, see the --slasher CLI flag".to_string())
+    })
+}
+
+/// Return the slashings most recently detected by the slasher.
+pub fn get_slashings<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> Result<SlasherSlashings<T::EthSpec>, warp::Rejection> {
+    let slasher = slasher(&chain)?;
+
+    Ok(SlasherSlashings {
+        attester_slashings: slasher
+            .get_detected_attester_slashings()
+            .into_iter()
+            .map(|detected| DetectedSlashing {
+                seen_timestamp: detected.seen_timestamp.as_secs(),
+                slashing: detected.slashing,
+            })
+            .collect(),
+        proposer_slashings: slasher
+            .get_detected_proposer_slashings()
+            .into_iter()
+            .map(|detected| DetectedSlashing {
+                seen_timestamp: detected.seen_timestamp.as_secs(),
+                slashing: detected.slashing,
+            })
+            .collect(),
+    })
+}
+
+/// Return the min and max targets of `validator_index` for every epoch of the slasher's history.
+pub fn get_targets<T: BeaconChainTypes>(
+    validator_index: u64,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<SlasherTargets>, warp::Rejection> {
+    let slasher = slasher(&chain)?;
+    let current_epoch = chain.epoch().map_err(beacon_chain_error)?;
+
+    let targets = slasher
+        .get_targets_for_validator(validator_index, current_epoch)
+        .map_err(|e| custom_server_error(format!("unable to load targets: {:?}", e)))?;
+
+    Ok(targets
+        .into_iter()
+        .map(|targets| SlasherTargets {
+            epoch: targets.epoch,
+            min_target: targets.min_target,
+            max_target: targets.max_target,
+        })
+        .collect())
+}
+
+/// Return the slashings that `attestation` would cause against the slasher's history.
+pub fn check_attestation<T: BeaconChainTypes>(
+    attestation: IndexedAttestation<T::EthSpec>,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<AttesterSlashing<T::EthSpec>>, warp::Rejection> {
+    let slasher = slasher(&chain)?;
+    let current_epoch = chain.epoch().map_err(beacon_chain_error)?;
+
+    match slasher.check_attestation(attestation, current_epoch) {
+        Ok(slashings) => Ok(slashings.into_iter().collect()),
+        Err(e @ slasher::Error::AttestationOutsideHistory { .. }) => Err(custom_bad_request(
+            format!("attestation can't be checked: {:?}", e),
+        )),
+        Err(e) => Err(custom_server_error(format!(
+            "unable to check attestation: {:?}",
+            e
+        ))),
+    }
+}
@@ -0,0 +1,72 @@
+use crate::StateId;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::StateProof;
+use eth2::types::{ExecutionOptimisticFinalizedResponse, StateProofQuery};
+use types::BeaconStateError;
+use warp_utils::reject::{beacon_state_error, custom_bad_request};
+
+/// The maximum number of nodes that may be proven in a single request.
+pub const MAX_PROOF_INDICES: usize = 64;
+
+/// Compute a Merkle multiproof of the nodes of the state at `state_id` selected by `query`.
+pub fn get_state_proof<T: BeaconChainTypes>(
+    state_id: StateId,
+    query: StateProofQuery,
+    chain: &BeaconChain<T>,
+) -> Result<ExecutionOptimisticFinalizedResponse<StateProof>, warp::Rejection> {
+    let paths = query.path.unwrap_or_default();
+    let gindices = query.gindex.unwrap_or_default();
+
+    let num_indices = paths.len() + gindices.len();
+    if num_indices == 0 {
+        return Err(custom_bad_request(
+            "at least one path or gindex is required".to_string(),
+        ));
+    }
+    if num_indices > MAX_PROOF_INDICES {
+        return Err(custom_bad_request(format!(
+            "at most {} paths and gindices may be requested",
+            MAX_PROOF_INDICES
+        )));
+    }
+
+    let (mut state, execution_optimistic, finalized) = state_id.state(chain)?;
+
+    let mut indices = paths
+        .iter()
+        .map(|path| state.generalized_index_for_path(path))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(proof_error)?;
+    for gindex in gindices {
+        let gindex = usize::try_from(gindex)
+            .map_err(|_| custom_bad_request(format!("gindex too large: {}", gindex)))?;
+        indices.push(gindex);
+    }
+
+    let state_root = state.update_tree_hash_cache().map_err(beacon_state_error)?;
+    let (leaves, branch) = state
+        .compute_merkle_multiproof(&indices)
+        .map_err(proof_error)?;
+
+    Ok(ExecutionOptimisticFinalizedResponse {
+        data: StateProof {
+            state_root,
+            gindices: indices.into_iter().map(|index| index as u64).collect(),
+            leaves,
+            branch,
+        },
+        execution_optimistic: Some(execution_optimistic),
+        finalized: Some(finalized),
+    })
+}
+
+/// Map errors caused by the request to a 400, and all other errors to a 500.
+fn proof_error(e: BeaconStateError) -> warp::Rejection {
+    match e {
+        BeaconStateError::InvalidProofPath(msg) => custom_bad_request(msg),
+        BeaconStateError::IndexNotSupported(index) => {
+            custom_bad_request(format!("proofs of gindex {} are not supported", index))
+        }
+        e => beacon_state_error(e),
+    }
+}
@@ -188,6 +188,7 @@ pub async fn create_api_server<T: BeaconChainTypes>(
         beacon_processor_rx,
         work_reprocessing_tx,
         work_reprocessing_rx,
+        queue_state,
     } = BeaconProcessorChannels::new(&beacon_processor_config);
 
     let beacon_processor_send = beacon_processor_tx;
@@ -197,6 +198,7 @@ pub async fn create_api_server<T: BeaconChainTypes>(
         executor: test_runtime.task_executor.clone(),
         current_workers: 0,
         config: beacon_processor_config,
+        queue_state: queue_state.clone(),
         log: log.clone(),
     }
     .spawn_manager(
@@ -222,6 +224,7 @@ pub async fn create_api_server<T: BeaconChainTypes>(
         network_globals: Some(network_globals),
         beacon_processor_send: Some(beacon_processor_send),
         beacon_processor_reprocess_send: Some(reprocess_send),
+        beacon_processor_queue_state: Some(queue_state),
         eth1_service: Some(eth1_service),
         sse_logging_components: None,
         log,
@@ -7,9 +7,14 @@ use beacon_chain::{
 use beacon_processor::work_reprocessing_queue::ReprocessQueueMessage;
 use eth2::types::ProduceBlockV3Response;
 use eth2::types::{DepositContractData, StateId};
+use eth2::StatusCode;
 use execution_layer::{ForkchoiceState, PayloadAttributes};
 use http_api::test_utils::InteractiveTester;
 use parking_lot::Mutex;
+use slasher::{
+    test_utils::{att_slashing, indexed_att},
+    Config as SlasherConfig, Slasher,
+};
 use slot_clock::SlotClock;
 use state_processing::{
     per_block_processing::get_expected_withdrawals, state_advance::complete_state_advance,
@@ -916,3 +921,86 @@ async fn queue_attestations_from_http() {
 
     attestation_future.await.unwrap();
 }
+
+// Test the slasher endpoints against an enabled slasher, checking that they read from the same
+// database as the slasher itself.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_slasher_queries() {
+    let validator_count = 24;
+    let slasher_dir = tempfile::tempdir().unwrap();
+    let slasher = Arc::new(
+        Slasher::open(
+            SlasherConfig::new(slasher_dir.path().into()),
+            logging::test_logger(),
+        )
+        .unwrap(),
+    );
+
+    let inner_slasher = slasher.clone();
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        None,
+        validator_count,
+        None,
+        Some(Box::new(move |builder| builder.slasher(inner_slasher))),
+    )
+    .await;
+    let client = &tester.client;
+
+    let current_epoch = Epoch::new(10);
+    tester
+        .harness
+        .set_current_slot(current_epoch.start_slot(E::slots_per_epoch()));
+
+    let existing = indexed_att([0], 3, 5, 0);
+    slasher.accept_attestation(existing.clone());
+    slasher.process_queued(current_epoch).unwrap();
+
+    let slashings = client
+        .get_lighthouse_slasher_slashings::<E>()
+        .await
+        .unwrap();
+    assert!(slashings.attester_slashings.is_empty());
+    assert!(slashings.proposer_slashings.is_empty());
+
+    let targets = client.get_lighthouse_slasher_targets(0).await.unwrap();
+    assert_eq!(targets.len(), current_epoch.as_usize() + 1);
+    assert_eq!(targets[2].min_target, Some(Epoch::new(5)));
+    assert_eq!(targets[4].max_target, Some(Epoch::new(5)));
+
+    // Checking an attestation neither stores it nor reports the slashing.
+    let double_vote = indexed_att([0], 3, 5, 1);
+    let slashings = client
+        .post_lighthouse_slasher_check_attestation(&double_vote)
+        .await
+        .unwrap();
+    assert_eq!(slashings, vec![att_slashing(&existing, &double_vote)]);
+    assert!(client
+        .post_lighthouse_slasher_check_attestation(&existing)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(client
+        .get_lighthouse_slasher_slashings::<E>()
+        .await
+        .unwrap()
+        .attester_slashings
+        .is_empty());
+
+    // Attestations from the future can't be checked.
+    let result = client
+        .post_lighthouse_slasher_check_attestation(&indexed_att([0], 10, 11, 0))
+        .await;
+    assert_eq!(result.unwrap_err().status(), Some(StatusCode::BAD_REQUEST));
+
+    slasher.accept_attestation(double_vote.clone());
+    slasher.process_queued(current_epoch).unwrap();
+    let slashings = client
+        .get_lighthouse_slasher_slashings::<E>()
+        .await
+        .unwrap();
+    assert_eq!(slashings.attester_slashings.len(), 1);
+    assert_eq!(
+        slashings.attester_slashings[0].slashing,
+        att_slashing(&existing, &double_vote)
+    );
+}
@@ -1,10 +1,17 @@
 use beacon_chain::test_utils::RelativeSyncCommittee;
 use beacon_chain::{
-    test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType},
+    test_utils::{
+        AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
+        SyncCommitteeStrategy,
+    },
     BeaconChain, ChainConfig, StateSkipConfig, WhenSlotSkipped,
 };
 use environment::null_logger;
 use eth2::{
+    lighthouse::{
+        ExportedOperationPool, OperationImportCount, PeerBanRequest, PeerBans, PeerDialRequest,
+        PeerDisconnectRequest, TrustedPeerRequest,
+    },
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{
@@ -21,8 +28,8 @@ use http_api::{
     test_utils::{create_api_server, ApiServer},
     BlockId, StateId,
 };
-use lighthouse_network::{Enr, EnrExt, PeerId};
-use network::NetworkReceivers;
+use lighthouse_network::{rpc::GoodbyeReason, Enr, EnrExt, PeerId};
+use network::{NetworkMessage, NetworkReceivers};
 use proto_array::ExecutionStatus;
 use sensitive_url::SensitiveUrl;
 use slot_clock::SlotClock;
@@ -1761,6 +1768,72 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_beacon_light_client_updates(self) -> Self {
+        // A block only has sync committee signatures once the sync committee has signed its
+        // parent.
+        self.harness
+            .extend_chain_with_sync(
+                2,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+                SyncCommitteeStrategy::AllValidators,
+            )
+            .await;
+
+        // The harness doesn't run the light client server, so compute an update from the head.
+        let head = self.chain.head_snapshot();
+        let sync_aggregate = head
+            .beacon_block
+            .message()
+            .body()
+            .sync_aggregate()
+            .unwrap()
+            .clone();
+        self.chain
+            .recompute_and_cache_light_client_updates((
+                head.beacon_block.parent_root(),
+                head.beacon_block.slot(),
+                sync_aggregate,
+            ))
+            .unwrap();
+
+        let current_period = self
+            .chain
+            .epoch()
+            .unwrap()
+            .sync_committee_period(&self.chain.spec)
+            .unwrap();
+
+        let result = match self
+            .client
+            .get_light_client_updates::<E>(0, current_period + 1)
+            .await
+        {
+            Ok(result) => result
+                .unwrap()
+                .into_iter()
+                .map(|res| res.data)
+                .collect::<Vec<_>>(),
+            Err(e) => panic!("query failed incorrectly: {e:?}"),
+        };
+
+        let expected = self
+            .chain
+            .get_light_client_updates(0, current_period + 1)
+            .unwrap();
+        assert!(!result.is_empty(), "there should be light client updates");
+        assert_eq!(result, expected);
+
+        // Requesting more than `MAX_REQUEST_LIGHT_CLIENT_UPDATES` is rejected.
+        assert!(self
+            .client
+            .get_light_client_updates::<E>(0, 129)
+            .await
+            .is_err());
+
+        self
+    }
+
     pub async fn test_get_beacon_pool_attestations(self) -> Self {
         let result = self
             .client
@@ -4921,6 +4994,23 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_builder_works_post_deneb_ssz(self) -> Self {
+        self.mock_builder.as_ref().unwrap().enable_ssz();
+
+        let tester = self.test_builder_works_post_deneb().await;
+
+        // The bid was served as SSZ, so blinded blocks should now be submitted as SSZ too.
+        assert!(tester
+            .chain
+            .execution_layer
+            .as_ref()
+            .unwrap()
+            .builder()
+            .unwrap()
+            .is_ssz_enabled());
+        tester
+    }
+
     pub async fn test_lighthouse_rejects_invalid_withdrawals_root(self) -> Self {
         // Ensure builder payload *would be* chosen
         self.mock_builder
@@ -5073,6 +5163,64 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_beacon_states_proof(self) -> Self {
+        let paths = [
+            "finalized_checkpoint.root",
+            "validators.1.effective_balance",
+            "balances.2",
+        ];
+        // The `slot` field of the state.
+        let gindices = [34];
+
+        let response = self
+            .client
+            .get_lighthouse_beacon_states_proof(
+                CoreStateId::Head,
+                paths.as_slice(),
+                gindices.as_slice(),
+            )
+            .await
+            .unwrap();
+        let proof = response.data;
+
+        let mut state = self.chain.head_beacon_state_cloned();
+        let state_root = state.update_tree_hash_cache().unwrap();
+        let mut indices = paths
+            .iter()
+            .map(|path| state.generalized_index_for_path(path).unwrap())
+            .collect::<Vec<_>>();
+        indices.extend(gindices.iter().map(|&i| i as usize));
+        let (leaves, branch) = state.compute_merkle_multiproof(&indices).unwrap();
+
+        assert_eq!(proof.state_root, state_root);
+        assert_eq!(
+            proof.gindices,
+            indices.iter().map(|&i| i as u64).collect::<Vec<_>>()
+        );
+        assert_eq!(proof.leaves, leaves);
+        assert_eq!(proof.branch, branch);
+        assert_eq!(proof.leaves[0], state.finalized_checkpoint().root);
+        assert_eq!(proof.leaves[3], state.slot().tree_hash_root());
+
+        // Unknown paths and unsupported indices are rejected.
+        for (paths, gindices) in [
+            (vec!["finalized_checkpoint.slot"], vec![]),
+            (vec![], vec![68]),
+        ] {
+            let result = self
+                .client
+                .get_lighthouse_beacon_states_proof(
+                    CoreStateId::Head,
+                    paths.as_slice(),
+                    gindices.as_slice(),
+                )
+                .await;
+            assert_eq!(result.unwrap_err().status(), Some(StatusCode::BAD_REQUEST));
+        }
+
+        self
+    }
+
     pub async fn test_get_lighthouse_database_info(self) -> Self {
         let info = self.client.get_lighthouse_database_info().await.unwrap();
 
@@ -5086,6 +5234,61 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_beacon_processor(self) -> Self {
+        // The queue state is updated at most once per second, whenever the beacon processor
+        // handles an event. Requests to the node version endpoint are handled by the processor.
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+        let queues = loop {
+            self.client.get_node_version().await.unwrap();
+            let queues = self.client.get_lighthouse_beacon_processor().await.unwrap();
+            if queues.updated_at.is_some() {
+                break queues;
+            }
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "beacon processor queue state should be updated"
+            );
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        };
+
+        assert_eq!(queues.max_workers, 2);
+        assert!(queues.active_workers <= queues.max_workers);
+        let gossip_blocks = queues
+            .work_queues
+            .iter()
+            .find(|queue| queue.work_type == "gossip_block")
+            .expect("gossip block queue is reported");
+        assert_eq!(gossip_blocks.length, 0);
+        assert_eq!(gossip_blocks.dropped, 0);
+        assert_eq!(gossip_blocks.oldest_item_age_ms, None);
+        assert!(gossip_blocks.capacity > 0);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_beacon_processor_events(self) -> Self {
+        let mut events = self
+            .client
+            .get_lighthouse_beacon_processor_events()
+            .await
+            .unwrap();
+
+        // The current state is sent as soon as the stream is opened.
+        let queues = tokio::time::timeout(Duration::from_secs(10), events.next())
+            .await
+            .expect("the current queue state should be streamed")
+            .expect("the stream should not end")
+            .unwrap();
+        let current = self.client.get_lighthouse_beacon_processor().await.unwrap();
+
+        assert_eq!(queues.max_workers, 2);
+        assert!(queues.updated_at.is_some());
+        assert!(queues.updated_at <= current.updated_at);
+        assert_eq!(queues.work_queues.len(), current.work_queues.len());
+
+        self
+    }
+
     pub async fn test_post_lighthouse_database_reconstruct(self) -> Self {
         let response = self
             .client
@@ -5096,6 +5299,285 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_lighthouse_slasher_disabled(self) -> Self {
+        assert!(self.chain.slasher.is_none());
+
+        let result = self.client.get_lighthouse_slasher_slashings::<E>().await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::NOT_FOUND));
+
+        let result = self.client.get_lighthouse_slasher_targets(0).await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::NOT_FOUND));
+
+        let result = self
+            .client
+            .post_lighthouse_slasher_check_attestation(&self.attester_slashing.attestation_1)
+            .await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::NOT_FOUND));
+
+        self
+    }
+
+    pub async fn test_lighthouse_peer_management(mut self) -> Self {
+        let peer_id = self.external_peer_id;
+
+        self.client
+            .post_lighthouse_peers_ban(&PeerBanRequest {
+                peer_id: Some(peer_id.to_string()),
+                duration_seconds: Some(3600),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::BanPeer { peer_id: banned, expires: Some(_) }) if banned == peer_id
+        ));
+
+        self.client
+            .post_lighthouse_peers_ban(&PeerBanRequest {
+                ip: Some("1.2.3.4".parse().unwrap()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::BanIp { expires: None, .. })
+        ));
+
+        // Exactly one of the peer id and IP address must be provided.
+        let result = self
+            .client
+            .post_lighthouse_peers_ban(&PeerBanRequest {
+                peer_id: Some(peer_id.to_string()),
+                ip: Some("1.2.3.4".parse().unwrap()),
+                duration_seconds: None,
+            })
+            .await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::BAD_REQUEST));
+
+        // A ban which would expire beyond the representable time is rejected.
+        let result = self
+            .client
+            .post_lighthouse_peers_ban(&PeerBanRequest {
+                peer_id: Some(peer_id.to_string()),
+                duration_seconds: Some(u64::MAX),
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::BAD_REQUEST));
+
+        self.client
+            .post_lighthouse_peers_trust(&TrustedPeerRequest {
+                peer_id: peer_id.to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::SetTrustedPeer { trusted: true, .. })
+        ));
+
+        self.client
+            .post_lighthouse_peers_dial(&PeerDialRequest {
+                address: self.local_enr.to_base64(),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::DialEnr { .. })
+        ));
+
+        self.client
+            .post_lighthouse_peers_dial(&PeerDialRequest {
+                address: "/ip4/127.0.0.1/tcp/9000".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::DialMultiaddr { .. })
+        ));
+
+        let result = self
+            .client
+            .post_lighthouse_peers_dial(&PeerDialRequest {
+                address: "not an address".to_string(),
+            })
+            .await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::BAD_REQUEST));
+
+        self.client
+            .post_lighthouse_peers_disconnect(&PeerDisconnectRequest {
+                peer_id: peer_id.to_string(),
+                reason: GoodbyeReason::TooManyPeers as u64,
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::DisconnectPeer {
+                reason: GoodbyeReason::TooManyPeers,
+                ..
+            })
+        ));
+
+        // The messages are not processed without a network service, so nothing is banned.
+        assert_eq!(
+            self.client.get_lighthouse_peers_bans().await.unwrap(),
+            PeerBans::default()
+        );
+
+        self
+    }
+
+    pub async fn test_lighthouse_operation_pool(self) -> Self {
+        self.client
+            .post_beacon_pool_attester_slashings(&self.attester_slashing)
+            .await
+            .unwrap();
+
+        let exported = self
+            .client
+            .get_lighthouse_operation_pool::<E>()
+            .await
+            .unwrap();
+        assert_eq!(exported, self.chain.export_operation_pool());
+        assert_eq!(
+            exported.attester_slashings,
+            vec![self.attester_slashing.clone()]
+        );
+
+        let mut invalid_exit = self.voluntary_exit.clone();
+        invalid_exit.message.epoch += 1;
+        // An attestation with the signature of another attestation fails batch verification, so
+        // each attestation is verified individually.
+        let mut invalid_attestation = self.attestations[0].clone();
+        invalid_attestation.signature = self.attestations[1].signature.clone();
+        let attestations = self
+            .attestations
+            .iter()
+            .cloned()
+            .chain(std::iter::once(invalid_attestation))
+            .collect();
+        let sync_contributions = self
+            .contribution_and_proofs
+            .iter()
+            .map(|signed| signed.message.contribution.clone())
+            .collect::<Vec<_>>();
+        assert!(
+            !sync_contributions.is_empty(),
+            "precondition: sync contributions for testing"
+        );
+        let operation_pool = ExportedOperationPool {
+            attestations,
+            sync_contributions: sync_contributions.clone(),
+            proposer_slashings: vec![self.proposer_slashing.clone()],
+            voluntary_exits: vec![self.voluntary_exit.clone(), invalid_exit],
+            ..exported
+        };
+
+        let result = self
+            .client
+            .post_lighthouse_operation_pool(&operation_pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.attestations,
+            OperationImportCount {
+                imported: self.attestations.len(),
+                rejected: 1
+            }
+        );
+        assert_eq!(
+            result.sync_contributions,
+            OperationImportCount {
+                imported: sync_contributions.len(),
+                rejected: 0
+            }
+        );
+        // The attester slashing was already in the op pool.
+        assert_eq!(
+            result.attester_slashings,
+            OperationImportCount {
+                imported: 0,
+                rejected: 0
+            }
+        );
+        assert_eq!(
+            result.proposer_slashings,
+            OperationImportCount {
+                imported: 1,
+                rejected: 0
+            }
+        );
+        assert_eq!(
+            result.voluntary_exits,
+            OperationImportCount {
+                imported: 1,
+                rejected: 1
+            }
+        );
+
+        assert_eq!(
+            self.chain.op_pool.get_all_proposer_slashings(),
+            vec![self.proposer_slashing.clone()]
+        );
+        assert_eq!(
+            self.chain.op_pool.get_all_voluntary_exits(),
+            vec![self.voluntary_exit.clone()]
+        );
+
+        // Importing the same operations again doesn't import any duplicates.
+        let result = self
+            .client
+            .post_lighthouse_operation_pool(&operation_pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.attestations,
+            OperationImportCount {
+                imported: 0,
+                rejected: 1
+            }
+        );
+        assert_eq!(
+            result.sync_contributions,
+            OperationImportCount {
+                imported: 0,
+                rejected: 0
+            }
+        );
+        assert_eq!(
+            result.attester_slashings,
+            OperationImportCount {
+                imported: 0,
+                rejected: 0
+            }
+        );
+        assert_eq!(
+            result.proposer_slashings,
+            OperationImportCount {
+                imported: 0,
+                rejected: 0
+            }
+        );
+        assert_eq!(
+            result.voluntary_exits,
+            OperationImportCount {
+                imported: 0,
+                rejected: 1
+            }
+        );
+        assert_eq!(
+            self.chain.op_pool.get_all_proposer_slashings(),
+            vec![self.proposer_slashing.clone()]
+        );
+
+        self
+    }
+
     pub async fn test_post_lighthouse_liveness(self) -> Self {
         let epoch = self.chain.epoch().unwrap();
         let head_state = self.chain.head_beacon_state_cloned();
@@ -5168,6 +5650,8 @@ impl ApiTester {
             EventTopic::Block,
             EventTopic::Head,
             EventTopic::FinalizedCheckpoint,
+            EventTopic::AttesterSlashing,
+            EventTopic::ProposerSlashing,
         ];
         let mut events_future = self
             .client
@@ -5210,6 +5694,43 @@ impl ApiTester {
             &[EventKind::VoluntaryExit(self.voluntary_exit.clone())]
         );
 
+        // Produce an attester slashing event
+        self.client
+            .post_beacon_pool_attester_slashings(&self.attester_slashing)
+            .await
+            .unwrap();
+
+        let attester_slashing_events =
+            poll_events(&mut events_future, 1, Duration::from_millis(10000)).await;
+        assert_eq!(
+            attester_slashing_events.as_slice(),
+            &[EventKind::AttesterSlashing(Box::new(
+                self.attester_slashing.clone()
+            ))]
+        );
+
+        // Produce a proposer slashing event
+        self.client
+            .post_beacon_pool_proposer_slashings(&self.proposer_slashing)
+            .await
+            .unwrap();
+
+        let proposer_slashing_events =
+            poll_events(&mut events_future, 1, Duration::from_millis(10000)).await;
+        assert_eq!(
+            proposer_slashing_events.as_slice(),
+            &[EventKind::ProposerSlashing(Box::new(
+                self.proposer_slashing.clone()
+            ))]
+        );
+
+        // Subscribe to block gossip separately, since it fires before the block is imported
+        let mut block_gossip_future = self
+            .client
+            .get_events::<E>(&[EventTopic::BlockGossip])
+            .await
+            .unwrap();
+
         // Submit the next block, which is on an epoch boundary, so this will produce a finalized
         // checkpoint event, head event, and block event
         let block_root = self.next_block.signed_block().canonical_root();
@@ -5270,6 +5791,16 @@ impl ApiTester {
             &[expected_block, expected_head, expected_finalized]
         );
 
+        let block_gossip_events =
+            poll_events(&mut block_gossip_future, 1, Duration::from_millis(10000)).await;
+        assert_eq!(
+            block_gossip_events.as_slice(),
+            &[EventKind::BlockGossip(Box::new(SseBlockGossip {
+                slot: next_slot,
+                block: block_root,
+            }))]
+        );
+
         // Test a reorg event
         let mut chain_reorg_event_future = self
             .client
@@ -5770,6 +6301,18 @@ async fn get_light_client_finality_update() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_light_client_updates() {
+    let config = ApiTesterConfig {
+        spec: ForkName::Altair.make_genesis_spec(E::default_spec()),
+        ..<_>::default()
+    };
+    ApiTester::new_from_config(config)
+        .await
+        .test_get_beacon_light_client_updates()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_validator_duties_early() {
     ApiTester::new()
@@ -6312,6 +6855,25 @@ async fn builder_works_post_deneb() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_works_post_deneb_ssz() {
+    let mut config = ApiTesterConfig {
+        retain_historic_states: false,
+        spec: E::default_spec(),
+    };
+    config.spec.altair_fork_epoch = Some(Epoch::new(0));
+    config.spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+    config.spec.capella_fork_epoch = Some(Epoch::new(0));
+    config.spec.deneb_fork_epoch = Some(Epoch::new(0));
+
+    ApiTester::new_from_config(config)
+        .await
+        .test_post_validator_register_validator()
+        .await
+        .test_builder_works_post_deneb_ssz()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_blob_sidecars() {
     let mut config = ApiTesterConfig {
@@ -6363,14 +6925,38 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_staking()
         .await
+        .test_get_lighthouse_beacon_states_proof()
+        .await
         .test_get_lighthouse_database_info()
         .await
+        .test_get_lighthouse_beacon_processor()
+        .await
+        .test_get_lighthouse_beacon_processor_events()
+        .await
         .test_post_lighthouse_database_reconstruct()
         .await
+        .test_lighthouse_slasher_disabled()
+        .await
         .test_post_lighthouse_liveness()
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_peer_management() {
+    ApiTester::new()
+        .await
+        .test_lighthouse_peer_management()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_operation_pool() {
+    ApiTester::new_with_hard_forks(true, false)
+        .await
+        .test_lighthouse_operation_pool()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn optimistic_responses() {
     ApiTester::new_with_hard_forks(true, true)
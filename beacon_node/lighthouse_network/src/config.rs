@@ -20,6 +20,8 @@ pub const DEFAULT_IPV4_ADDRESS: Ipv4Addr = Ipv4Addr::UNSPECIFIED;
 pub const DEFAULT_TCP_PORT: u16 = 9000u16;
 pub const DEFAULT_DISC_PORT: u16 = 9000u16;
 pub const DEFAULT_QUIC_PORT: u16 = 9001u16;
+pub const DEFAULT_GOSSIP_RECORD_MAX_FILE_SIZE: u64 = 256 * 1024 * 1024;
+pub const DEFAULT_GOSSIP_RECORD_MAX_FILES: usize = 8;
 
 /// The cache time is set to accommodate the circulation time of an attestation.
 ///
@@ -107,6 +109,9 @@ pub struct Config {
     /// Disables peer scoring altogether.
     pub disable_peer_scoring: bool,
 
+    /// The bytes per second a peer may use before it is deprioritized when pruning peers.
+    pub peer_bandwidth_cap: Option<u64>,
+
     /// Client version
     pub client_version: String,
 
@@ -155,6 +160,15 @@ pub struct Config {
     /// Configures if/where invalid blocks should be stored.
     pub invalid_block_storage: Option<PathBuf>,
 
+    /// Configures if/where received gossip messages should be recorded.
+    pub gossip_record_dir: Option<PathBuf>,
+
+    /// The maximum size of a single gossip recording file, in bytes.
+    pub gossip_record_max_file_size: u64,
+
+    /// The maximum number of gossip recording files to keep.
+    pub gossip_record_max_files: usize,
+
     /// Configuration for the inbound rate limiter (requests received by this node).
     pub inbound_rate_limiter_config: Option<InboundRateLimiterConfig>,
 }
@@ -358,6 +372,7 @@ impl Default for Config {
             libp2p_nodes: vec![],
             trusted_peers: vec![],
             disable_peer_scoring: false,
+            peer_bandwidth_cap: None,
             client_version: lighthouse_version::version_with_platform(),
             disable_discovery: false,
             disable_quic_support: false,
@@ -373,6 +388,9 @@ impl Default for Config {
             enable_light_client_server: false,
             outbound_rate_limiter_config: None,
             invalid_block_storage: None,
+            gossip_record_dir: None,
+            gossip_record_max_file_size: DEFAULT_GOSSIP_RECORD_MAX_FILE_SIZE,
+            gossip_record_max_files: DEFAULT_GOSSIP_RECORD_MAX_FILES,
             inbound_rate_limiter_config: None,
         }
     }
@@ -106,8 +106,8 @@ impl<'a> std::fmt::Display for ClearDialError<'a> {
 }
 
 pub use crate::types::{
-    error, Enr, EnrSyncCommitteeBitfield, GossipTopic, NetworkGlobals, PubsubMessage, Subnet,
-    SubnetDiscovery,
+    error, Enr, EnrSyncCommitteeBitfield, GossipRecord, GossipRecorder, GossipTopic,
+    NetworkGlobals, PubsubMessage, Subnet, SubnetDiscovery,
 };
 
 pub use prometheus_client;
@@ -146,6 +146,29 @@ lazy_static! {
     pub static ref NETWORK_OUTBOUND_PEERS: Result<IntGauge> =
         try_create_int_gauge("network_outbound_peers","The number of peers that are currently connected that we dialed.");
 
+    /*
+     * Bandwidth
+     */
+    pub static ref GOSSIP_BYTES_PER_TOPIC_KIND: Result<IntCounterVec> = try_create_int_counter_vec(
+        "gossipsub_bytes_per_topic_kind_total",
+        "Bytes of gossip messages received and published per topic kind",
+        &["topic_kind", "direction"]
+    );
+    pub static ref RPC_BYTES_PER_PROTOCOL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_rpc_bytes_per_protocol_total",
+        "Bytes of RPC requests and responses per protocol",
+        &["protocol", "direction"]
+    );
+    pub static ref BYTES_PER_CLIENT: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_bytes_per_client_total",
+        "Bytes exchanged with peers per client",
+        &["client", "direction"]
+    );
+    pub static ref PEERS_OVER_BANDWIDTH_CAP: Result<IntGauge> = try_create_int_gauge(
+        "libp2p_peers_over_bandwidth_cap",
+        "Count of connected peers using more bandwidth than the peer bandwidth cap"
+    );
+
     /*
      * Peer Reporting
      */
@@ -154,6 +177,14 @@ lazy_static! {
         "Number of peer reports per msg",
         &["msg"]
     );
+
+    /*
+     * Gossip Recording
+     */
+    pub static ref GOSSIP_RECORDS_DROPPED: Result<IntCounter> = try_create_int_counter(
+        "libp2p_gossip_records_dropped_total",
+        "Count of received gossip messages which were not recorded because the recorder fell behind"
+    );
 }
 
 /// Checks if we consider the NAT open.
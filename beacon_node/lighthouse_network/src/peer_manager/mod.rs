@@ -16,7 +16,7 @@ use slog::{debug, error, trace, warn};
 use smallvec::SmallVec;
 use std::{
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 use strum::IntoEnumIterator;
 use types::{EthSpec, SyncSubnetId};
@@ -27,6 +27,7 @@ pub use libp2p::identity::Keypair;
 #[allow(clippy::mutable_key_type)] // PeerId in hashmaps are no longer permitted by clippy
 pub mod peerdb;
 
+pub use peerdb::bandwidth::{BandwidthBuffer, ByteCount, ByteDirection, PeerBandwidth};
 pub use peerdb::peer_info::{
     ConnectionDirection, PeerConnectionStatus, PeerConnectionStatus::*, PeerInfo,
 };
@@ -77,6 +78,8 @@ pub struct PeerManager<TSpec: EthSpec> {
     status_peers: HashSetDelay<PeerId>,
     /// The target number of peers we would like to connect to.
     target_peers: usize,
+    /// The bytes per second a peer may use before it is pruned ahead of other peers.
+    peer_bandwidth_cap: Option<u64>,
     /// Peers queued to be dialed.
     peers_to_dial: Vec<Enr>,
     /// The number of temporarily banned peers. This is used to prevent instantaneous
@@ -148,6 +151,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             discovery_enabled,
             metrics_enabled,
             target_peer_count,
+            peer_bandwidth_cap,
             status_interval,
             ping_interval_inbound,
             ping_interval_outbound,
@@ -165,6 +169,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             outbound_ping_peers: HashSetDelay::new(Duration::from_secs(ping_interval_outbound)),
             status_peers: HashSetDelay::new(Duration::from_secs(status_interval)),
             target_peers: target_peer_count,
+            peer_bandwidth_cap,
             temporary_banned_peers: LRUTimeCache::new(PEER_RECONNECTION_TIMEOUT),
             sync_committee_subnets: Default::default(),
             heartbeat,
@@ -200,6 +205,83 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         );
     }
 
+    /// Bans a peer on behalf of the node operator until `expires`, or permanently if `expires` is
+    /// `None`. The peer is disconnected if it is connected.
+    pub fn ban_peer_manually(&mut self, peer_id: &PeerId, expires: Option<SystemTime>) {
+        debug!(self.log, "Banning peer"; "peer_id" => %peer_id, "expires" => ?expires);
+        self.network_globals
+            .peers
+            .write()
+            .manual_bans_mut()
+            .ban_peer(*peer_id, expires);
+        if self.is_connected(peer_id) {
+            self.disconnect_peer(*peer_id, GoodbyeReason::Banned);
+        }
+        self.events.push(PeerManagerEvent::Banned(*peer_id, vec![]));
+    }
+
+    /// Lifts a ban made by the node operator. Peers banned because of their score remain banned.
+    pub fn unban_peer_manually(&mut self, peer_id: &PeerId) {
+        let mut peers = self.network_globals.peers.write();
+        if peers.manual_bans_mut().unban_peer(peer_id) && peers.ban_status(peer_id).is_none() {
+            debug!(self.log, "Unbanning peer"; "peer_id" => %peer_id);
+            self.events
+                .push(PeerManagerEvent::UnBanned(*peer_id, vec![]));
+        }
+    }
+
+    /// Bans an IP address on behalf of the node operator until `expires`, or permanently if
+    /// `expires` is `None`. Connected peers using the address are disconnected.
+    pub fn ban_ip_manually(&mut self, ip: IpAddr, expires: Option<SystemTime>) {
+        debug!(self.log, "Banning IP address"; "ip" => %ip, "expires" => ?expires);
+        let peers_to_disconnect = {
+            let mut peers = self.network_globals.peers.write();
+            peers.manual_bans_mut().ban_ip(ip, expires);
+            peers
+                .connected_peers()
+                .filter(|(_, info)| info.seen_ip_addresses().any(|seen| seen == ip))
+                .map(|(peer_id, _)| *peer_id)
+                .collect::<Vec<_>>()
+        };
+        for peer_id in peers_to_disconnect {
+            self.disconnect_peer(peer_id, GoodbyeReason::BannedIP);
+        }
+    }
+
+    /// Lifts an IP address ban made by the node operator.
+    pub fn unban_ip_manually(&mut self, ip: &IpAddr) {
+        if self
+            .network_globals
+            .peers
+            .write()
+            .manual_bans_mut()
+            .unban_ip(ip)
+        {
+            debug!(self.log, "Unbanning IP address"; "ip" => %ip);
+        }
+    }
+
+    /// Marks a peer as trusted, or no longer trusted, on behalf of the node operator.
+    pub fn set_trusted_peer(&mut self, peer_id: &PeerId, trusted: bool) {
+        debug!(self.log, "Updating trusted peer"; "peer_id" => %peer_id, "trusted" => trusted);
+        self.network_globals
+            .peers
+            .write()
+            .set_trusted(peer_id, trusted);
+    }
+
+    /// Disconnects a connected peer, sending it the given goodbye reason, without banning it.
+    ///
+    /// Returns false if the peer is not connected.
+    pub fn disconnect_peer_manually(&mut self, peer_id: &PeerId, reason: GoodbyeReason) -> bool {
+        if self.is_connected(peer_id) {
+            self.disconnect_peer(*peer_id, reason);
+            true
+        } else {
+            false
+        }
+    }
+
     /// Reports a peer for some action.
     ///
     /// If the peer doesn't exist, log a warning and insert defaults.
@@ -534,6 +616,14 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
                         return;
                     }
 
+                    // Peers only need to serve blobs within the data availability boundary, which
+                    // only the requester knows about, so the requester penalises the peer instead.
+                    if matches!(protocol, Protocol::BlobsByRange)
+                        && matches!(direction, ConnectionDirection::Outgoing)
+                    {
+                        return;
+                    }
+
                     // NOTE: This error only makes sense for the `BlocksByRange` and `BlocksByRoot`
                     // protocols.
                     //
@@ -564,6 +654,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
                     Protocol::BlocksByRoot => PeerAction::MidToleranceError,
                     Protocol::BlobsByRange => PeerAction::MidToleranceError,
                     Protocol::LightClientBootstrap => PeerAction::LowToleranceError,
+                    Protocol::LightClientUpdatesByRange => PeerAction::LowToleranceError,
                     Protocol::BlobsByRoot => PeerAction::MidToleranceError,
                     Protocol::Goodbye => PeerAction::LowToleranceError,
                     Protocol::MetaData => PeerAction::LowToleranceError,
@@ -585,6 +676,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
                     Protocol::BlobsByRoot => return,
                     Protocol::Goodbye => return,
                     Protocol::LightClientBootstrap => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::MetaData => PeerAction::Fatal,
                     Protocol::Status => PeerAction::Fatal,
                 }
@@ -602,6 +694,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
                     Protocol::BlobsByRange => PeerAction::MidToleranceError,
                     Protocol::BlobsByRoot => PeerAction::MidToleranceError,
                     Protocol::LightClientBootstrap => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::Goodbye => return,
                     Protocol::MetaData => return,
                     Protocol::Status => return,
@@ -973,9 +1066,10 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
     ///
     /// Prune peers in the following order:
     /// 1. Remove worst scoring peers
-    /// 2. Remove peers that are not subscribed to a subnet (they have less value)
-    /// 3. Remove peers that we have many on any particular subnet
-    /// 4. Randomly remove peers if all the above are satisfied
+    /// 2. Remove peers using more bandwidth than the peer bandwidth cap, if one is set
+    /// 3. Remove peers that are not subscribed to a subnet (they have less value)
+    /// 4. Remove peers that we have many on any particular subnet
+    /// 5. Randomly remove peers if all the above are satisfied
     ///
     fn prune_excess_peers(&mut self) {
         // The current number of connected peers.
@@ -1032,13 +1126,22 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         // 1. Look through peers that have the worst score (ignoring non-penalized scored peers).
         prune_peers!(|info: &PeerInfo<TSpec>| { info.score().score() < 0.0 });
 
-        // 2. Attempt to remove peers that are not subscribed to a subnet, if we still need to
+        // 2. Remove peers that exceed the bandwidth cap, if we still need to prune more.
+        if let Some(cap) = self.peer_bandwidth_cap {
+            if peers_to_prune.len() < connected_peer_count.saturating_sub(self.target_peers) {
+                prune_peers!(|info: &PeerInfo<TSpec>| {
+                    info.bandwidth().bytes_per_second() > cap
+                });
+            }
+        }
+
+        // 3. Attempt to remove peers that are not subscribed to a subnet, if we still need to
         //    prune more.
         if peers_to_prune.len() < connected_peer_count.saturating_sub(self.target_peers) {
             prune_peers!(|info: &PeerInfo<TSpec>| { !info.has_long_lived_subnet() });
         }
 
-        // 3. and 4. Remove peers that are too grouped on any given subnet. If all subnets are
+        // 4. and 5. Remove peers that are too grouped on any given subnet. If all subnets are
         //    uniformly distributed, remove random peers.
         if peers_to_prune.len() < connected_peer_count.saturating_sub(self.target_peers) {
             // Of our connected peers, build a map from subnet_id -> Vec<(PeerId, PeerInfo)>
@@ -1192,6 +1295,22 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         }
     }
 
+    /// Lifts any bans made by the node operator that have expired.
+    fn unban_expired_manual_bans(&mut self) {
+        let mut peers = self.network_globals.peers.write();
+        let (expired_peers, expired_ips) = peers.manual_bans_mut().remove_expired();
+        for peer_id in expired_peers {
+            debug!(self.log, "Manual peer ban expired"; "peer_id" => %peer_id);
+            if peers.ban_status(&peer_id).is_none() {
+                self.events
+                    .push(PeerManagerEvent::UnBanned(peer_id, Vec::new()));
+            }
+        }
+        for ip in expired_ips {
+            debug!(self.log, "Manual IP address ban expired"; "ip" => %ip);
+        }
+    }
+
     /// The Peer manager's heartbeat maintains the peer count and maintains peer reputations.
     ///
     /// It will request discovery queries if the peer count has not reached the desired number of
@@ -1218,6 +1337,9 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         // Update peer score metrics;
         self.update_peer_score_metrics();
 
+        // Update the bandwidth used by each peer.
+        self.update_peer_bandwidth();
+
         // Maintain minimum count for sync committee peers.
         self.maintain_sync_committee_peers();
 
@@ -1228,10 +1350,37 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         // Unban any peers that have served their temporary ban timeout
         self.unban_temporary_banned_peers();
 
+        // Lift any manual bans that have expired
+        self.unban_expired_manual_bans();
+
         // Maintains memory by shrinking mappings
         self.shrink_mappings();
     }
 
+    /// Updates the bytes per second used by each peer, and counts the peers exceeding the peer
+    /// bandwidth cap.
+    fn update_peer_bandwidth(&self) {
+        let mut peers = self.network_globals.peers.write();
+        peers.update_bandwidth_rates();
+
+        let Some(cap) = self.peer_bandwidth_cap else {
+            return;
+        };
+        let mut peers_over_cap = 0;
+        for (peer_id, info) in peers.connected_peers() {
+            let bytes_per_second = info.bandwidth().bytes_per_second();
+            if bytes_per_second > cap {
+                peers_over_cap += 1;
+                debug!(self.log, "Peer exceeds bandwidth cap";
+                    "peer_id" => %peer_id,
+                    "bytes_per_second" => bytes_per_second,
+                    "cap" => cap,
+                );
+            }
+        }
+        metrics::set_gauge(&metrics::PEERS_OVER_BANDWIDTH_CAP, peers_over_cap);
+    }
+
     // Reduce memory footprint by routinely shrinking associating mappings.
     fn shrink_mappings(&mut self) {
         self.inbound_ping_peers.shrink_to(5);
@@ -1715,6 +1864,108 @@ mod tests {
         assert!(peers_should_have_removed.is_empty());
     }
 
+    #[tokio::test]
+    /// We want to test that peers exceeding the bandwidth cap are pruned before peers that are
+    /// not subscribed to a subnet.
+    async fn test_peer_manager_prune_peers_over_bandwidth_cap() {
+        let config = config::Config {
+            target_peer_count: 3,
+            discovery_enabled: false,
+            peer_bandwidth_cap: Some(1_000),
+            ..Default::default()
+        };
+        let log = build_log(slog::Level::Debug, false);
+        let globals = NetworkGlobals::new_test_globals(vec![], &log);
+        let mut peer_manager: PeerManager<E> =
+            PeerManager::new(config, Arc::new(globals), &log).unwrap();
+
+        let peers = (0..4).map(|_| PeerId::random()).collect::<Vec<_>>();
+        for peer in &peers {
+            peer_manager.inject_connect_ingoing(peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+        }
+        let heavy_peer = peers[2];
+        let mut bandwidth = PeerBandwidth::default();
+        bandwidth.add_rpc(
+            Protocol::BlocksByRange.as_ref(),
+            ByteDirection::Outbound,
+            100_000_000,
+        );
+        peer_manager
+            .network_globals
+            .peers
+            .write()
+            .record_bandwidth(&heavy_peer, &bandwidth);
+
+        peer_manager.heartbeat();
+
+        assert_eq!(peer_manager.network_globals.connected_or_dialing_peers(), 3);
+        assert!(matches!(
+            peer_manager
+                .network_globals
+                .peers
+                .read()
+                .connection_status(&heavy_peer),
+            Some(PeerConnectionStatus::Disconnecting { .. })
+        ));
+        assert!(
+            peer_manager
+                .network_globals
+                .peers
+                .read()
+                .peer_info(&heavy_peer)
+                .unwrap()
+                .bandwidth()
+                .bytes_per_second()
+                > 1_000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_peer_manager_manual_bans_and_trust() {
+        let mut peer_manager = build_peer_manager(3).await;
+        let banned_peer = PeerId::random();
+        let peer_on_banned_ip = PeerId::random();
+        let trusted_peer = PeerId::random();
+        peer_manager.inject_connect_ingoing(&banned_peer, "/ip4/1.1.1.1".parse().unwrap(), None);
+        peer_manager.inject_connect_ingoing(
+            &peer_on_banned_ip,
+            "/ip4/2.2.2.2".parse().unwrap(),
+            None,
+        );
+
+        peer_manager.ban_peer_manually(&banned_peer, None);
+        peer_manager.ban_ip_manually("2.2.2.2".parse().unwrap(), None);
+        peer_manager.set_trusted_peer(&trusted_peer, true);
+
+        {
+            let peers = peer_manager.network_globals.peers.read();
+            for peer in [banned_peer, peer_on_banned_ip] {
+                assert!(matches!(
+                    peers.connection_status(&peer),
+                    Some(PeerConnectionStatus::Disconnecting { .. })
+                ));
+            }
+            assert!(matches!(
+                peers.ban_status(&banned_peer),
+                Some(BanResult::BannedByOperator)
+            ));
+            assert!(matches!(
+                peers.ban_status(&peer_on_banned_ip),
+                Some(BanResult::BannedIp(_))
+            ));
+            assert!(peers.peer_info(&trusted_peer).unwrap().is_trusted());
+        }
+
+        peer_manager.unban_peer_manually(&banned_peer);
+        peer_manager.unban_ip_manually(&"2.2.2.2".parse().unwrap());
+        peer_manager.set_trusted_peer(&trusted_peer, false);
+
+        let peers = peer_manager.network_globals.peers.read();
+        assert!(peers.ban_status(&banned_peer).is_none());
+        assert!(peers.ban_status(&peer_on_banned_ip).is_none());
+        assert!(!peers.peer_info(&trusted_peer).unwrap().is_trusted());
+    }
+
     #[tokio::test]
     /// Test the pruning logic to remove grouped subnet peers
     async fn test_peer_manager_prune_grouped_subnet_peers() {
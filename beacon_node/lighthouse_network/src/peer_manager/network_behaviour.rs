@@ -201,10 +201,18 @@ impl<TSpec: EthSpec> NetworkBehaviour for PeerManager<TSpec> {
     ) -> Result<libp2p::swarm::THandler<Self>, ConnectionDenied> {
         trace!(self.log, "Inbound connection"; "peer_id" => %peer_id, "multiaddr" => %remote_addr);
         // We already checked if the peer was banned on `handle_pending_inbound_connection`.
-        if let Some(BanResult::BadScore) = self.ban_status(&peer_id) {
-            return Err(ConnectionDenied::new(
-                "Connection to peer rejected: peer has a bad score",
-            ));
+        match self.ban_status(&peer_id) {
+            Some(BanResult::BadScore) => {
+                return Err(ConnectionDenied::new(
+                    "Connection to peer rejected: peer has a bad score",
+                ));
+            }
+            Some(BanResult::BannedByOperator) => {
+                return Err(ConnectionDenied::new(
+                    "Connection to peer rejected: peer is banned by the node operator",
+                ));
+            }
+            Some(BanResult::BannedIp(_)) | None => {}
         }
         Ok(ConnectionHandler)
     }
@@ -217,6 +225,17 @@ impl<TSpec: EthSpec> NetworkBehaviour for PeerManager<TSpec> {
         _role_override: libp2p::core::Endpoint,
     ) -> Result<libp2p::swarm::THandler<Self>, libp2p::swarm::ConnectionDenied> {
         trace!(self.log, "Outbound connection"; "peer_id" => %peer_id, "multiaddr" => %addr);
+        // Peers that have not been seen before are only banned by the address we dialed.
+        let dialed_ip = addr.iter().find_map(|protocol| match protocol {
+            libp2p::multiaddr::Protocol::Ip4(ip) => Some(IpAddr::V4(ip)),
+            libp2p::multiaddr::Protocol::Ip6(ip) => Some(IpAddr::V6(ip)),
+            _ => None,
+        });
+        if let Some(ip) = dialed_ip.filter(|ip| self.network_globals.peers.read().is_ip_banned(ip))
+        {
+            debug!(self.log, "Dialed a peer on a banned IP. Rejecting connection"; "peer_id" => %peer_id, "ip" => %ip);
+            return Err(ConnectionDenied::new(BanResult::BannedIp(ip)));
+        }
         match self.ban_status(&peer_id) {
             Some(cause) => {
                 error!(self.log, "Connected a banned peer. Rejecting connection"; "peer_id" => %peer_id);
@@ -1,4 +1,6 @@
 use crate::{metrics, multiaddr::Multiaddr, types::Subnet, Enr, Gossipsub, PeerId};
+use bandwidth::PeerBandwidth;
+use manual_bans::ManualBans;
 use peer_info::{ConnectionDirection, PeerConnectionStatus, PeerInfo};
 use rand::seq::SliceRandom;
 use score::{PeerAction, ReportSource, Score, ScoreState};
@@ -13,7 +15,9 @@ use std::{
 use sync_status::SyncStatus;
 use types::EthSpec;
 
+pub mod bandwidth;
 pub mod client;
+pub mod manual_bans;
 pub mod peer_info;
 pub mod score;
 pub mod sync_status;
@@ -39,6 +43,8 @@ pub struct PeerDB<TSpec: EthSpec> {
     disconnected_peers: usize,
     /// Counts banned peers in total and per ip
     banned_peers_count: BannedPeersCount,
+    /// Peers and IP addresses banned by the node operator.
+    manual_bans: ManualBans,
     /// Specifies if peer scoring is disabled.
     disable_peer_scoring: bool,
     /// PeerDB's logger
@@ -56,6 +62,7 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
             log: log.clone(),
             disconnected_peers: 0,
             banned_peers_count: BannedPeersCount::default(),
+            manual_bans: ManualBans::default(),
             disable_peer_scoring,
             peers,
         }
@@ -128,6 +135,7 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
                 | Some(PeerConnectionStatus::Unknown { .. })
                 | None
         ) && !self.score_state_banned_or_disconnected(peer_id)
+            && !self.manual_bans.is_peer_banned(peer_id)
     }
 
     /// Returns true if the peer is synced at least to our current head.
@@ -145,6 +153,9 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
     ///
     /// This is used to determine if we should accept incoming connections or not.
     pub fn ban_status(&self, peer_id: &PeerId) -> Option<BanResult> {
+        if self.manual_bans.is_peer_banned(peer_id) {
+            return Some(BanResult::BannedByOperator);
+        }
         self.peers
             .get(peer_id)
             .and_then(|peer| match peer.score_state() {
@@ -155,13 +166,31 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
 
     /// Checks if the peer's known addresses are currently banned.
     fn ip_is_banned(&self, peer: &PeerInfo<TSpec>) -> Option<IpAddr> {
-        peer.seen_ip_addresses()
-            .find(|ip| self.banned_peers_count.ip_is_banned(ip))
+        peer.seen_ip_addresses().find(|ip| self.is_ip_banned(ip))
     }
 
     /// Returns true if the IP is banned.
     pub fn is_ip_banned(&self, ip: &IpAddr) -> bool {
-        self.banned_peers_count.ip_is_banned(ip)
+        self.banned_peers_count.ip_is_banned(ip) || self.manual_bans.is_ip_banned(ip)
+    }
+
+    /// Returns the peers and IP addresses banned by the node operator.
+    pub fn manual_bans(&self) -> &ManualBans {
+        &self.manual_bans
+    }
+
+    /// Returns a mutable reference to the peers and IP addresses banned by the node operator.
+    // VISIBILITY: Only the peer manager can ban and unban peers, as it must inform the swarm.
+    pub(super) fn manual_bans_mut(&mut self) -> &mut ManualBans {
+        &mut self.manual_bans
+    }
+
+    /// Returns the ids of all trusted peers.
+    pub fn trusted_peers(&self) -> impl Iterator<Item = &PeerId> {
+        self.peers
+            .iter()
+            .filter(|(_, info)| info.is_trusted())
+            .map(|(peer_id, _)| peer_id)
     }
 
     /// Returns true if the Peer is either banned or in the disconnected state.
@@ -592,6 +621,19 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
     }
 
+    /// Marks a peer as trusted or untrusted. Trusted peers keep the maximum score and are never
+    /// pruned.
+    // VISIBILITY: Only the peer manager can change which peers are trusted.
+    pub(super) fn set_trusted(&mut self, peer_id: &PeerId, trusted: bool) {
+        match self.peers.get_mut(peer_id) {
+            Some(info) => info.set_trusted(trusted),
+            None if trusted => {
+                self.peers.insert(*peer_id, PeerInfo::trusted_peer_info());
+            }
+            None => {}
+        }
+    }
+
     /// Adds a gossipsub subscription to a peer in the peerdb.
     // VISIBILITY: The behaviour is able to adjust subscriptions.
     pub(crate) fn add_subscription(&mut self, peer_id: &PeerId, subnet: Subnet) {
@@ -608,6 +650,22 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
     }
 
+    /// Adds the bytes exchanged with a peer since the last flush of the behaviour's buffer.
+    // VISIBILITY: The behaviour is able to record bandwidth.
+    pub(crate) fn record_bandwidth(&mut self, peer_id: &PeerId, bandwidth: &PeerBandwidth) {
+        if let Some(info) = self.peers.get_mut(peer_id) {
+            info.bandwidth_mut().merge(bandwidth);
+        }
+    }
+
+    /// Updates the bytes per second exchanged with each connected peer.
+    // VISIBILITY: Only the peer manager can update bandwidth rates.
+    pub(super) fn update_bandwidth_rates(&mut self) {
+        for info in self.peers.values_mut().filter(|info| info.is_connected()) {
+            info.bandwidth_mut().update_rate();
+        }
+    }
+
     /// Extends the ttl of all peers on the given subnet that have a shorter
     /// min_ttl than what's given.
     // VISIBILITY: The behaviour is able to adjust subscriptions.
@@ -1184,6 +1242,8 @@ pub enum BanResult {
     BadScore,
     /// The peer should be banned because it is connecting from a banned IP address.
     BannedIp(IpAddr),
+    /// The peer has been banned by the node operator.
+    BannedByOperator,
 }
 
 impl Display for BanResult {
@@ -1191,6 +1251,7 @@ impl Display for BanResult {
         match self {
             BanResult::BadScore => write!(f, "Peer has a bad score"),
             BanResult::BannedIp(addr) => write!(f, "Peer address: {} is banned", addr),
+            BanResult::BannedByOperator => write!(f, "Peer is banned by the node operator"),
         }
     }
 }
@@ -0,0 +1,294 @@
+//! Tracks the bytes exchanged with individual peers.
+//!
+//! Byte counts are of SSZ-encoded message payloads, before compression and excluding protocol
+//! framing. For gossip this is the message data as seen by the application, as gossipsub applies
+//! snappy compression after publishing and removes it before delivering received messages.
+
+use libp2p::PeerId;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Instant;
+use strum::AsRefStr;
+
+/// The direction in which bytes were exchanged with a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum ByteDirection {
+    /// Bytes received from the peer.
+    Inbound,
+    /// Bytes sent to the peer.
+    Outbound,
+}
+
+/// The number of bytes received from and sent to a peer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct ByteCount {
+    /// Bytes received from the peer.
+    pub inbound: u64,
+    /// Bytes sent to the peer.
+    pub outbound: u64,
+}
+
+impl ByteCount {
+    fn add(&mut self, direction: ByteDirection, bytes: u64) {
+        let count = match direction {
+            ByteDirection::Inbound => &mut self.inbound,
+            ByteDirection::Outbound => &mut self.outbound,
+        };
+        *count = count.saturating_add(bytes);
+    }
+
+    fn merge(&mut self, other: &ByteCount) {
+        self.add(ByteDirection::Inbound, other.inbound);
+        self.add(ByteDirection::Outbound, other.outbound);
+    }
+
+    /// The number of bytes exchanged in both directions.
+    pub fn total(&self) -> u64 {
+        self.inbound.saturating_add(self.outbound)
+    }
+}
+
+/// The bytes exchanged with a peer, per gossip topic kind and per RPC protocol.
+///
+/// Gossip messages are attributed to the peer that propagated them to us. Messages we publish or
+/// forward are only attributed to their topic, as gossipsub chooses the peers they are sent to.
+#[derive(Clone, Debug, Serialize)]
+pub struct PeerBandwidth {
+    /// The bytes exchanged over all topics and protocols.
+    total: ByteCount,
+    /// The bytes exchanged per gossip topic kind.
+    gossip: HashMap<String, ByteCount>,
+    /// The bytes exchanged per RPC protocol.
+    rpc: HashMap<String, ByteCount>,
+    /// The bytes per second exchanged with the peer between the last two updates.
+    bytes_per_second: u64,
+    /// The total bytes exchanged at the last update.
+    #[serde(skip)]
+    total_at_last_update: u64,
+    /// The time of the last update.
+    #[serde(skip)]
+    last_update: Instant,
+}
+
+impl Default for PeerBandwidth {
+    fn default() -> Self {
+        PeerBandwidth {
+            total: ByteCount::default(),
+            gossip: HashMap::new(),
+            rpc: HashMap::new(),
+            bytes_per_second: 0,
+            total_at_last_update: 0,
+            last_update: Instant::now(),
+        }
+    }
+}
+
+impl PeerBandwidth {
+    /// The bytes exchanged over all topics and protocols.
+    pub fn total(&self) -> &ByteCount {
+        &self.total
+    }
+
+    /// The bytes exchanged on the given gossip topic kind.
+    pub fn gossip(&self, topic_kind: &str) -> ByteCount {
+        self.gossip.get(topic_kind).copied().unwrap_or_default()
+    }
+
+    /// The bytes exchanged using the given RPC protocol.
+    pub fn rpc(&self, protocol: &str) -> ByteCount {
+        self.rpc.get(protocol).copied().unwrap_or_default()
+    }
+
+    /// The bytes per second exchanged with the peer, as of the last call to `update_rate`.
+    pub fn bytes_per_second(&self) -> u64 {
+        self.bytes_per_second
+    }
+
+    /// Records a gossip message exchanged with the peer.
+    pub fn add_gossip(&mut self, topic_kind: &str, direction: ByteDirection, bytes: u64) {
+        self.total.add(direction, bytes);
+        match self.gossip.get_mut(topic_kind) {
+            Some(count) => count.add(direction, bytes),
+            None => {
+                let mut count = ByteCount::default();
+                count.add(direction, bytes);
+                self.gossip.insert(topic_kind.to_string(), count);
+            }
+        }
+    }
+
+    /// Records an RPC request or response exchanged with the peer.
+    pub fn add_rpc(&mut self, protocol: &str, direction: ByteDirection, bytes: u64) {
+        self.total.add(direction, bytes);
+        match self.rpc.get_mut(protocol) {
+            Some(count) => count.add(direction, bytes),
+            None => {
+                let mut count = ByteCount::default();
+                count.add(direction, bytes);
+                self.rpc.insert(protocol.to_string(), count);
+            }
+        }
+    }
+
+    /// Adds the bytes recorded in `other` to these counts. Rates are left unchanged.
+    pub fn merge(&mut self, other: &PeerBandwidth) {
+        self.total.merge(&other.total);
+        for (topic_kind, count) in &other.gossip {
+            self.gossip
+                .entry(topic_kind.clone())
+                .or_default()
+                .merge(count);
+        }
+        for (protocol, count) in &other.rpc {
+            self.rpc.entry(protocol.clone()).or_default().merge(count);
+        }
+    }
+
+    /// Updates the bytes per second exchanged with the peer since the last update.
+    pub fn update_rate(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_update).as_secs_f64();
+        if elapsed > 0.0 {
+            let bytes = self.total.total().saturating_sub(self.total_at_last_update);
+            self.bytes_per_second = (bytes as f64 / elapsed) as u64;
+        }
+        self.total_at_last_update = self.total.total();
+        self.last_update = now;
+    }
+}
+
+/// Accumulates the bytes exchanged with peers between flushes to the peer database, so that the
+/// database does not need to be locked for every message.
+#[derive(Debug, Default)]
+pub struct BandwidthBuffer {
+    peers: HashMap<PeerId, PeerBandwidth>,
+}
+
+impl BandwidthBuffer {
+    /// Records a gossip message exchanged with the peer.
+    pub fn add_gossip(
+        &mut self,
+        peer_id: PeerId,
+        topic_kind: &str,
+        direction: ByteDirection,
+        bytes: u64,
+    ) {
+        self.peers
+            .entry(peer_id)
+            .or_default()
+            .add_gossip(topic_kind, direction, bytes);
+    }
+
+    /// Records an RPC request or response exchanged with the peer.
+    pub fn add_rpc(
+        &mut self,
+        peer_id: PeerId,
+        protocol: &str,
+        direction: ByteDirection,
+        bytes: u64,
+    ) {
+        self.peers
+            .entry(peer_id)
+            .or_default()
+            .add_rpc(protocol, direction, bytes);
+    }
+
+    /// Returns `true` if no bytes have been recorded since the last drain.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    /// Removes and returns the bytes recorded per peer.
+    pub fn drain(&mut self) -> impl Iterator<Item = (PeerId, PeerBandwidth)> + '_ {
+        self.peers.drain()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_bytes_per_topic_and_protocol() {
+        let mut bandwidth = PeerBandwidth::default();
+        bandwidth.add_gossip("beacon_block", ByteDirection::Inbound, 100);
+        bandwidth.add_gossip("beacon_block", ByteDirection::Inbound, 50);
+        bandwidth.add_rpc("beacon_blocks_by_range", ByteDirection::Outbound, 1000);
+        bandwidth.add_rpc("status", ByteDirection::Inbound, 84);
+
+        assert_eq!(
+            bandwidth.gossip("beacon_block"),
+            ByteCount {
+                inbound: 150,
+                outbound: 0
+            }
+        );
+        assert_eq!(
+            bandwidth.rpc("beacon_blocks_by_range"),
+            ByteCount {
+                inbound: 0,
+                outbound: 1000
+            }
+        );
+        assert_eq!(bandwidth.gossip("voluntary_exit"), ByteCount::default());
+        assert_eq!(
+            *bandwidth.total(),
+            ByteCount {
+                inbound: 234,
+                outbound: 1000
+            }
+        );
+    }
+
+    #[test]
+    fn rate_only_counts_bytes_since_last_update() {
+        let mut bandwidth = PeerBandwidth::default();
+        bandwidth.add_rpc("status", ByteDirection::Inbound, 1_000_000);
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        bandwidth.update_rate();
+        assert!(bandwidth.bytes_per_second() > 0);
+
+        bandwidth.update_rate();
+        assert_eq!(bandwidth.bytes_per_second(), 0);
+    }
+
+    #[test]
+    fn merges_buffered_bytes() {
+        let peer_id = PeerId::random();
+        let mut buffer = BandwidthBuffer::default();
+        buffer.add_gossip(peer_id, "beacon_block", ByteDirection::Inbound, 100);
+        buffer.add_rpc(peer_id, "status", ByteDirection::Outbound, 84);
+        buffer.add_rpc(peer_id, "status", ByteDirection::Inbound, 84);
+
+        let mut bandwidth = PeerBandwidth::default();
+        bandwidth.add_gossip("beacon_block", ByteDirection::Inbound, 50);
+        for (buffered_peer, buffered) in buffer.drain() {
+            assert_eq!(buffered_peer, peer_id);
+            bandwidth.merge(&buffered);
+        }
+
+        assert!(buffer.is_empty());
+        assert_eq!(
+            bandwidth.gossip("beacon_block"),
+            ByteCount {
+                inbound: 150,
+                outbound: 0
+            }
+        );
+        assert_eq!(
+            bandwidth.rpc("status"),
+            ByteCount {
+                inbound: 84,
+                outbound: 84
+            }
+        );
+        assert_eq!(
+            *bandwidth.total(),
+            ByteCount {
+                inbound: 234,
+                outbound: 84
+            }
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Peers and IP addresses banned by the node operator, rather than because of their score.
+//!
+//! Manual bans are not affected by score decay. They last until they expire, or until they are
+//! lifted by the operator.
+
+use crate::PeerId;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::SystemTime;
+
+/// The peers and IP addresses banned by the node operator, along with the time each ban expires.
+/// A ban without an expiry time is permanent.
+#[derive(Debug, Default)]
+pub struct ManualBans {
+    peers: HashMap<PeerId, Option<SystemTime>>,
+    ips: HashMap<IpAddr, Option<SystemTime>>,
+}
+
+impl ManualBans {
+    /// Bans a peer until `expires`, replacing any existing ban of the peer.
+    pub fn ban_peer(&mut self, peer_id: PeerId, expires: Option<SystemTime>) {
+        self.peers.insert(peer_id, expires);
+    }
+
+    /// Lifts the ban of a peer. Returns true if the peer was banned.
+    pub fn unban_peer(&mut self, peer_id: &PeerId) -> bool {
+        self.peers.remove(peer_id).is_some()
+    }
+
+    /// Bans an IP address until `expires`, replacing any existing ban of the address.
+    pub fn ban_ip(&mut self, ip: IpAddr, expires: Option<SystemTime>) {
+        self.ips.insert(ip, expires);
+    }
+
+    /// Lifts the ban of an IP address. Returns true if the address was banned.
+    pub fn unban_ip(&mut self, ip: &IpAddr) -> bool {
+        self.ips.remove(ip).is_some()
+    }
+
+    /// Returns true if the peer is banned and its ban has not expired.
+    pub fn is_peer_banned(&self, peer_id: &PeerId) -> bool {
+        self.peers
+            .get(peer_id)
+            .map_or(false, |expires| !has_expired(expires))
+    }
+
+    /// Returns true if the IP address is banned and its ban has not expired.
+    pub fn is_ip_banned(&self, ip: &IpAddr) -> bool {
+        self.ips
+            .get(ip)
+            .map_or(false, |expires| !has_expired(expires))
+    }
+
+    /// The banned peers and the time their bans expire.
+    pub fn banned_peers(&self) -> impl Iterator<Item = (&PeerId, &Option<SystemTime>)> {
+        self.peers.iter()
+    }
+
+    /// The banned IP addresses and the time their bans expire.
+    pub fn banned_ips(&self) -> impl Iterator<Item = (&IpAddr, &Option<SystemTime>)> {
+        self.ips.iter()
+    }
+
+    /// Removes the bans that have expired, returning the peers and IP addresses that were
+    /// unbanned.
+    pub fn remove_expired(&mut self) -> (Vec<PeerId>, Vec<IpAddr>) {
+        let expired_peers = self
+            .peers
+            .iter()
+            .filter(|(_, expires)| has_expired(expires))
+            .map(|(peer_id, _)| *peer_id)
+            .collect::<Vec<_>>();
+        let expired_ips = self
+            .ips
+            .iter()
+            .filter(|(_, expires)| has_expired(expires))
+            .map(|(ip, _)| *ip)
+            .collect::<Vec<_>>();
+
+        for peer_id in &expired_peers {
+            self.peers.remove(peer_id);
+        }
+        for ip in &expired_ips {
+            self.ips.remove(ip);
+        }
+        (expired_peers, expired_ips)
+    }
+}
+
+fn has_expired(expires: &Option<SystemTime>) -> bool {
+    expires.map_or(false, |expires| expires <= SystemTime::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn bans_expire() {
+        let mut bans = ManualBans::default();
+        let permanent = PeerId::random();
+        let expired = PeerId::random();
+        let ip: IpAddr = "1.2.3.4".parse().unwrap();
+        let past = SystemTime::now() - Duration::from_secs(1);
+        let future = SystemTime::now() + Duration::from_secs(3600);
+
+        bans.ban_peer(permanent, None);
+        bans.ban_peer(expired, Some(past));
+        bans.ban_ip(ip, Some(future));
+
+        assert!(bans.is_peer_banned(&permanent));
+        assert!(!bans.is_peer_banned(&expired));
+        assert!(bans.is_ip_banned(&ip));
+
+        assert_eq!(bans.remove_expired(), (vec![expired], vec![]));
+        assert_eq!(bans.banned_peers().count(), 1);
+
+        assert!(bans.unban_ip(&ip));
+        assert!(!bans.unban_ip(&ip));
+        assert!(!bans.is_ip_banned(&ip));
+    }
+}
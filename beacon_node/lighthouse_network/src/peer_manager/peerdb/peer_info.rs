@@ -1,3 +1,4 @@
+use super::bandwidth::PeerBandwidth;
 use super::client::Client;
 use super::score::{PeerAction, Score, ScoreState};
 use super::sync_status::SyncStatus;
@@ -51,6 +52,8 @@ pub struct PeerInfo<T: EthSpec> {
     connection_direction: Option<ConnectionDirection>,
     /// The enr of the peer, if known.
     enr: Option<Enr>,
+    /// The bytes exchanged with the peer.
+    bandwidth: PeerBandwidth,
 }
 
 impl<TSpec: EthSpec> Default for PeerInfo<TSpec> {
@@ -68,6 +71,7 @@ impl<TSpec: EthSpec> Default for PeerInfo<TSpec> {
             is_trusted: false,
             connection_direction: None,
             enr: None,
+            bandwidth: PeerBandwidth::default(),
         }
     }
 }
@@ -119,6 +123,11 @@ impl<T: EthSpec> PeerInfo<T> {
         &self.sync_status
     }
 
+    /// Returns the bytes exchanged with the peer.
+    pub fn bandwidth(&self) -> &PeerBandwidth {
+        &self.bandwidth
+    }
+
     /// Returns the metadata for the peer if currently known.
     pub fn meta_data(&self) -> Option<&MetaData<T>> {
         self.meta_data.as_ref()
@@ -358,6 +367,24 @@ impl<T: EthSpec> PeerInfo<T> {
         self.enr = Some(enr)
     }
 
+    /// Returns the bytes exchanged with the peer, for updating.
+    pub(super) fn bandwidth_mut(&mut self) -> &mut PeerBandwidth {
+        &mut self.bandwidth
+    }
+
+    /// Marks the peer as trusted or untrusted. Trusted peers have the maximum score, and an
+    /// untrusted peer starts again from the default score.
+    pub(super) fn set_trusted(&mut self, trusted: bool) {
+        if trusted != self.is_trusted {
+            self.is_trusted = trusted;
+            self.score = if trusted {
+                Score::max_score()
+            } else {
+                Score::default()
+            };
+        }
+    }
+
     /// Sets the time that the peer is expected to be needed until for an attached validator duty.
     pub(super) fn set_min_ttl(&mut self, min_ttl: Instant) {
         self.min_ttl = Some(min_ttl)
@@ -17,7 +17,7 @@ use std::sync::Arc;
 use tokio_util::codec::{Decoder, Encoder};
 use types::ChainSpec;
 use types::{
-    BlobSidecar, EthSpec, ForkContext, ForkName, Hash256, LightClientBootstrap,
+    BlobSidecar, EthSpec, ForkContext, ForkName, Hash256, LightClientBootstrap, LightClientUpdate,
     RuntimeVariableList, SignedBeaconBlock, SignedBeaconBlockAltair, SignedBeaconBlockBase,
     SignedBeaconBlockCapella, SignedBeaconBlockDeneb, SignedBeaconBlockMerge,
 };
@@ -75,6 +75,7 @@ impl<TSpec: EthSpec> Encoder<RPCCodedResponse<TSpec>> for SSZSnappyInboundCodec<
                 RPCResponse::BlobsByRange(res) => res.as_ssz_bytes(),
                 RPCResponse::BlobsByRoot(res) => res.as_ssz_bytes(),
                 RPCResponse::LightClientBootstrap(res) => res.as_ssz_bytes(),
+                RPCResponse::LightClientUpdatesByRange(res) => res.as_ssz_bytes(),
                 RPCResponse::Pong(res) => res.data.as_ssz_bytes(),
                 RPCResponse::MetaData(res) =>
                 // Encode the correct version of the MetaData response based on the negotiated version.
@@ -420,6 +421,13 @@ fn context_bytes<T: EthSpec>(
             if let RPCResponse::BlobsByRange(_) | RPCResponse::BlobsByRoot(_) = rpc_variant {
                 return fork_context.to_context_bytes(ForkName::Deneb);
             }
+            if let RPCResponse::LightClientUpdatesByRange(update) = rpc_variant {
+                // The update is versioned by the fork of its attested header.
+                let fork_name = fork_context
+                    .spec
+                    .fork_name_at_slot::<T>(update.attested_header.beacon.slot);
+                return fork_context.to_context_bytes(fork_name);
+            }
         }
     }
     None
@@ -506,6 +514,11 @@ fn handle_rpc_request<T: EthSpec>(
                 root: Hash256::from_ssz_bytes(decoded_buffer)?,
             }),
         )),
+        SupportedProtocol::LightClientUpdatesByRangeV1 => {
+            Ok(Some(InboundRequest::LightClientUpdatesByRange(
+                LightClientUpdatesByRangeRequest::from_ssz_bytes(decoded_buffer)?,
+            )))
+        }
         // MetaData requests return early from InboundUpgrade and do not reach the decoder.
         // Handle this case just for completeness.
         SupportedProtocol::MetaDataV2 => {
@@ -593,6 +606,22 @@ fn handle_rpc_response<T: EthSpec>(
         SupportedProtocol::LightClientBootstrapV1 => Ok(Some(RPCResponse::LightClientBootstrap(
             LightClientBootstrap::from_ssz_bytes(decoded_buffer)?,
         ))),
+        SupportedProtocol::LightClientUpdatesByRangeV1 => match fork_name {
+            Some(ForkName::Base) => Err(RPCError::ErrorResponse(
+                RPCResponseErrorCode::InvalidRequest,
+                "Invalid fork name for light client updates by range".to_string(),
+            )),
+            Some(_) => Ok(Some(RPCResponse::LightClientUpdatesByRange(Arc::new(
+                LightClientUpdate::from_ssz_bytes(decoded_buffer)?,
+            )))),
+            None => Err(RPCError::ErrorResponse(
+                RPCResponseErrorCode::InvalidRequest,
+                format!(
+                    "No context bytes provided for {:?} response",
+                    versioned_protocol
+                ),
+            )),
+        },
         // MetaData V2 responses have no context bytes, so behave similarly to V1 responses
         SupportedProtocol::MetaDataV2 => Ok(Some(RPCResponse::MetaData(MetaData::V2(
             MetaDataV2::from_ssz_bytes(decoded_buffer)?,
@@ -684,8 +713,9 @@ mod tests {
     use std::sync::Arc;
     use types::{
         blob_sidecar::BlobIdentifier, BeaconBlock, BeaconBlockAltair, BeaconBlockBase,
-        BeaconBlockMerge, ChainSpec, EmptyBlock, Epoch, ForkContext, FullPayload, Hash256,
-        Signature, SignedBeaconBlock, Slot,
+        BeaconBlockHeader, BeaconBlockMerge, ChainSpec, EmptyBlock, Epoch, FixedVector,
+        ForkContext, FullPayload, Hash256, LightClientHeader, Signature, SignedBeaconBlock, Slot,
+        SyncAggregate, SyncCommittee,
     };
 
     use snap::write::FrameEncoder;
@@ -733,6 +763,27 @@ mod tests {
         Arc::new(BlobSidecar::empty())
     }
 
+    fn light_client_update(fork_context: &ForkContext) -> Arc<LightClientUpdate<Spec>> {
+        let slot = fork_context
+            .spec
+            .altair_fork_epoch
+            .unwrap()
+            .start_slot(Spec::slots_per_epoch());
+        let header = LightClientHeader::from(BeaconBlockHeader {
+            slot,
+            ..BeaconBlockHeader::empty()
+        });
+        Arc::new(LightClientUpdate {
+            attested_header: header.clone(),
+            next_sync_committee: Arc::new(SyncCommittee::temporary()),
+            next_sync_committee_branch: FixedVector::from_elem(Hash256::zero()),
+            finalized_header: header,
+            finality_branch: FixedVector::from_elem(Hash256::zero()),
+            sync_aggregate: SyncAggregate::new(),
+            signature_slot: slot + 1,
+        })
+    }
+
     /// Merge block with length < max_rpc_size.
     fn merge_block_small(fork_context: &ForkContext, spec: &ChainSpec) -> SignedBeaconBlock<Spec> {
         let mut block: BeaconBlockMerge<_, FullPayload<Spec>> =
@@ -1073,6 +1124,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_then_decode_light_client_updates_by_range() {
+        let chain_spec = Spec::default_spec();
+        let update = light_client_update(&fork_context(ForkName::Altair));
+
+        // The update is sent with the context bytes of the fork of its attested header,
+        // regardless of the current fork.
+        for fork_name in [ForkName::Altair, ForkName::Deneb] {
+            assert_eq!(
+                encode_then_decode_response(
+                    SupportedProtocol::LightClientUpdatesByRangeV1,
+                    RPCCodedResponse::Success(RPCResponse::LightClientUpdatesByRange(
+                        update.clone()
+                    )),
+                    fork_name,
+                    &chain_spec,
+                ),
+                Ok(Some(RPCResponse::LightClientUpdatesByRange(update.clone()))),
+            );
+        }
+
+        // A base fork update is invalid.
+        assert!(matches!(
+            decode_response(
+                SupportedProtocol::LightClientUpdatesByRangeV1,
+                &mut encode_without_length_checks(update.as_ssz_bytes(), ForkName::Base).unwrap(),
+                ForkName::Base,
+                &chain_spec,
+            )
+            .unwrap_err(),
+            RPCError::ErrorResponse(RPCResponseErrorCode::InvalidRequest, _),
+        ));
+    }
+
     // Test RPCResponse encoding/decoding for V1 messages
     #[test]
     fn test_encode_then_decode_v2() {
@@ -14,13 +14,17 @@ use superstruct::superstruct;
 use types::blob_sidecar::BlobIdentifier;
 use types::{
     blob_sidecar::BlobSidecar, ChainSpec, Epoch, EthSpec, Hash256, LightClientBootstrap,
-    RuntimeVariableList, SignedBeaconBlock, Slot,
+    LightClientUpdate, RuntimeVariableList, SignedBeaconBlock, Slot,
 };
 
 /// Maximum length of error message.
 pub type MaxErrorLen = U256;
 pub const MAX_ERROR_LEN: u64 = 256;
 
+/// Maximum number of `LightClientUpdate`s that can be requested in a single
+/// `LightClientUpdatesByRange` request.
+pub const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
 /// Wrapper over SSZ List to represent error message in rpc responses.
 #[derive(Debug, Clone)]
 pub struct ErrorType(pub VariableList<u8, MaxErrorLen>);
@@ -387,6 +391,9 @@ pub enum RPCResponse<T: EthSpec> {
     /// A response to a get LIGHT_CLIENT_BOOTSTRAP request.
     LightClientBootstrap(LightClientBootstrap<T>),
 
+    /// A response to a get LIGHT_CLIENT_UPDATES_BY_RANGE request.
+    LightClientUpdatesByRange(Arc<LightClientUpdate<T>>),
+
     /// A response to a get BLOBS_BY_ROOT request.
     BlobsByRoot(Arc<BlobSidecar<T>>),
 
@@ -411,6 +418,9 @@ pub enum ResponseTermination {
 
     /// Blobs by root stream termination.
     BlobsByRoot,
+
+    /// Light client updates by range stream termination.
+    LightClientUpdatesByRange,
 }
 
 /// The structured response containing a result/code indicating success or failure
@@ -432,6 +442,25 @@ pub struct LightClientBootstrapRequest {
     pub root: Hash256,
 }
 
+/// Request a range of `LightClientUpdate`s, one per sync committee period, for light_clients
+/// peers.
+#[derive(Encode, Decode, Clone, Debug, PartialEq)]
+pub struct LightClientUpdatesByRangeRequest {
+    /// The sync committee period to start from.
+    pub start_period: u64,
+
+    /// The number of periods to return.
+    pub count: u64,
+}
+
+impl LightClientUpdatesByRangeRequest {
+    /// The number of updates that will be served for this request, capped at
+    /// `MAX_REQUEST_LIGHT_CLIENT_UPDATES`.
+    pub fn max_requested(&self) -> u64 {
+        std::cmp::min(self.count, MAX_REQUEST_LIGHT_CLIENT_UPDATES)
+    }
+}
+
 /// The code assigned to an erroneous `RPCResponse`.
 #[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
@@ -485,6 +514,7 @@ impl<T: EthSpec> RPCCodedResponse<T> {
                 RPCResponse::Pong(_) => false,
                 RPCResponse::MetaData(_) => false,
                 RPCResponse::LightClientBootstrap(_) => false,
+                RPCResponse::LightClientUpdatesByRange(_) => true,
             },
             RPCCodedResponse::Error(_, _) => true,
             // Stream terminations are part of responses that have chunks
@@ -523,6 +553,22 @@ impl<T: EthSpec> RPCResponse<T> {
             RPCResponse::Pong(_) => Protocol::Ping,
             RPCResponse::MetaData(_) => Protocol::MetaData,
             RPCResponse::LightClientBootstrap(_) => Protocol::LightClientBootstrap,
+            RPCResponse::LightClientUpdatesByRange(_) => Protocol::LightClientUpdatesByRange,
+        }
+    }
+
+    /// The length of the SSZ encoding of this response, as sent or received by the codec.
+    pub fn ssz_bytes_len(&self) -> usize {
+        match self {
+            RPCResponse::Status(res) => res.ssz_bytes_len(),
+            RPCResponse::BlocksByRange(res) => res.ssz_bytes_len(),
+            RPCResponse::BlocksByRoot(res) => res.ssz_bytes_len(),
+            RPCResponse::BlobsByRange(res) => res.ssz_bytes_len(),
+            RPCResponse::BlobsByRoot(res) => res.ssz_bytes_len(),
+            RPCResponse::Pong(res) => res.data.ssz_bytes_len(),
+            RPCResponse::MetaData(res) => res.as_ssz_bytes().len(),
+            RPCResponse::LightClientBootstrap(res) => res.ssz_bytes_len(),
+            RPCResponse::LightClientUpdatesByRange(res) => res.ssz_bytes_len(),
         }
     }
 }
@@ -572,6 +618,13 @@ impl<T: EthSpec> std::fmt::Display for RPCResponse<T> {
                     bootstrap.header.beacon.slot
                 )
             }
+            RPCResponse::LightClientUpdatesByRange(update) => {
+                write!(
+                    f,
+                    "LightClientUpdatesByRange: Attested slot: {}, Signature slot: {}",
+                    update.attested_header.beacon.slot, update.signature_slot
+                )
+            }
         }
     }
 }
@@ -645,6 +698,16 @@ impl std::fmt::Display for BlobsByRangeRequest {
     }
 }
 
+impl std::fmt::Display for LightClientUpdatesByRangeRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Request: LightClientUpdatesByRange: Start Period: {}, Count: {}",
+            self.start_period, self.count
+        )
+    }
+}
+
 impl slog::KV for StatusMessage {
     fn serialize(
         &self,
@@ -11,6 +11,7 @@ use futures::future::BoxFuture;
 use futures::prelude::{AsyncRead, AsyncWrite};
 use futures::{FutureExt, SinkExt};
 use libp2p::core::{OutboundUpgrade, UpgradeInfo};
+use ssz::Encode;
 use std::sync::Arc;
 use tokio_util::{
     codec::Framed,
@@ -129,6 +130,23 @@ impl<TSpec: EthSpec> OutboundRequest<TSpec> {
         }
     }
 
+    /// The length of the SSZ encoding of this request, as sent by the codec.
+    pub fn ssz_bytes_len(&self) -> usize {
+        match self {
+            OutboundRequest::Status(req) => req.ssz_bytes_len(),
+            OutboundRequest::Goodbye(req) => req.ssz_bytes_len(),
+            OutboundRequest::BlocksByRange(req) => match req {
+                OldBlocksByRangeRequest::V1(req) => req.ssz_bytes_len(),
+                OldBlocksByRangeRequest::V2(req) => req.ssz_bytes_len(),
+            },
+            OutboundRequest::BlocksByRoot(req) => req.block_roots().ssz_bytes_len(),
+            OutboundRequest::BlobsByRange(req) => req.ssz_bytes_len(),
+            OutboundRequest::BlobsByRoot(req) => req.blob_ids.ssz_bytes_len(),
+            OutboundRequest::Ping(req) => req.ssz_bytes_len(),
+            OutboundRequest::MetaData(_) => 0,
+        }
+    }
+
     /// Returns the `ResponseTermination` type associated with the request if a stream gets
     /// terminated.
     pub fn stream_termination(&self) -> ResponseTermination {
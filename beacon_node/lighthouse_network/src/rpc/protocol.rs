@@ -21,8 +21,8 @@ use tokio_util::{
 };
 use types::{
     BeaconBlock, BeaconBlockAltair, BeaconBlockBase, BeaconBlockCapella, BeaconBlockMerge,
-    BlobSidecar, ChainSpec, EmptyBlock, EthSpec, ForkContext, ForkName, MainnetEthSpec, Signature,
-    SignedBeaconBlock,
+    BlobSidecar, ChainSpec, EmptyBlock, EthSpec, ForkContext, ForkName, LightClientUpdate,
+    MainnetEthSpec, Signature, SignedBeaconBlock,
 };
 
 lazy_static! {
@@ -174,6 +174,9 @@ pub enum Protocol {
     /// The `LightClientBootstrap` protocol name.
     #[strum(serialize = "light_client_bootstrap")]
     LightClientBootstrap,
+    /// The `LightClientUpdatesByRange` protocol name.
+    #[strum(serialize = "light_client_updates_by_range")]
+    LightClientUpdatesByRange,
 }
 
 impl Protocol {
@@ -188,6 +191,9 @@ impl Protocol {
             Protocol::Ping => None,
             Protocol::MetaData => None,
             Protocol::LightClientBootstrap => None,
+            Protocol::LightClientUpdatesByRange => {
+                Some(ResponseTermination::LightClientUpdatesByRange)
+            }
         }
     }
 }
@@ -213,6 +219,7 @@ pub enum SupportedProtocol {
     MetaDataV1,
     MetaDataV2,
     LightClientBootstrapV1,
+    LightClientUpdatesByRangeV1,
 }
 
 impl SupportedProtocol {
@@ -230,6 +237,7 @@ impl SupportedProtocol {
             SupportedProtocol::MetaDataV1 => "1",
             SupportedProtocol::MetaDataV2 => "2",
             SupportedProtocol::LightClientBootstrapV1 => "1",
+            SupportedProtocol::LightClientUpdatesByRangeV1 => "1",
         }
     }
 
@@ -247,6 +255,7 @@ impl SupportedProtocol {
             SupportedProtocol::MetaDataV1 => Protocol::MetaData,
             SupportedProtocol::MetaDataV2 => Protocol::MetaData,
             SupportedProtocol::LightClientBootstrapV1 => Protocol::LightClientBootstrap,
+            SupportedProtocol::LightClientUpdatesByRangeV1 => Protocol::LightClientUpdatesByRange,
         }
     }
 
@@ -303,6 +312,10 @@ impl<TSpec: EthSpec> UpgradeInfo for RPCProtocol<TSpec> {
                 SupportedProtocol::LightClientBootstrapV1,
                 Encoding::SSZSnappy,
             ));
+            supported_protocols.push(ProtocolId::new(
+                SupportedProtocol::LightClientUpdatesByRangeV1,
+                Encoding::SSZSnappy,
+            ));
         }
         supported_protocols
     }
@@ -377,6 +390,10 @@ impl ProtocolId {
                 <LightClientBootstrapRequest as Encode>::ssz_fixed_len(),
                 <LightClientBootstrapRequest as Encode>::ssz_fixed_len(),
             ),
+            Protocol::LightClientUpdatesByRange => RpcLimits::new(
+                <LightClientUpdatesByRangeRequest as Encode>::ssz_fixed_len(),
+                <LightClientUpdatesByRangeRequest as Encode>::ssz_fixed_len(),
+            ),
             Protocol::MetaData => RpcLimits::new(0, 0), // Metadata requests are empty
         }
     }
@@ -405,6 +422,11 @@ impl ProtocolId {
                 <LightClientBootstrapRequest as Encode>::ssz_fixed_len(),
                 <LightClientBootstrapRequest as Encode>::ssz_fixed_len(),
             ),
+            // `LightClientUpdate` is a fixed length container.
+            Protocol::LightClientUpdatesByRange => RpcLimits::new(
+                <LightClientUpdate<T> as Encode>::ssz_fixed_len(),
+                <LightClientUpdate<T> as Encode>::ssz_fixed_len(),
+            ),
         }
     }
 
@@ -416,7 +438,8 @@ impl ProtocolId {
             | SupportedProtocol::BlocksByRootV2
             | SupportedProtocol::BlobsByRangeV1
             | SupportedProtocol::BlobsByRootV1
-            | SupportedProtocol::LightClientBootstrapV1 => true,
+            | SupportedProtocol::LightClientBootstrapV1
+            | SupportedProtocol::LightClientUpdatesByRangeV1 => true,
             SupportedProtocol::StatusV1
             | SupportedProtocol::BlocksByRootV1
             | SupportedProtocol::BlocksByRangeV1
@@ -528,6 +551,7 @@ pub enum InboundRequest<TSpec: EthSpec> {
     BlobsByRange(BlobsByRangeRequest),
     BlobsByRoot(BlobsByRootRequest),
     LightClientBootstrap(LightClientBootstrapRequest),
+    LightClientUpdatesByRange(LightClientUpdatesByRangeRequest),
     Ping(Ping),
     MetaData(MetadataRequest<TSpec>),
 }
@@ -548,6 +572,7 @@ impl<TSpec: EthSpec> InboundRequest<TSpec> {
             InboundRequest::Ping(_) => 1,
             InboundRequest::MetaData(_) => 1,
             InboundRequest::LightClientBootstrap(_) => 1,
+            InboundRequest::LightClientUpdatesByRange(req) => req.max_requested(),
         }
     }
 
@@ -572,6 +597,28 @@ impl<TSpec: EthSpec> InboundRequest<TSpec> {
                 MetadataRequest::V2(_) => SupportedProtocol::MetaDataV2,
             },
             InboundRequest::LightClientBootstrap(_) => SupportedProtocol::LightClientBootstrapV1,
+            InboundRequest::LightClientUpdatesByRange(_) => {
+                SupportedProtocol::LightClientUpdatesByRangeV1
+            }
+        }
+    }
+
+    /// The length of the SSZ encoding of this request, as received by the codec.
+    pub fn ssz_bytes_len(&self) -> usize {
+        match self {
+            InboundRequest::Status(req) => req.ssz_bytes_len(),
+            InboundRequest::Goodbye(req) => req.ssz_bytes_len(),
+            InboundRequest::BlocksByRange(req) => match req {
+                OldBlocksByRangeRequest::V1(req) => req.ssz_bytes_len(),
+                OldBlocksByRangeRequest::V2(req) => req.ssz_bytes_len(),
+            },
+            InboundRequest::BlocksByRoot(req) => req.block_roots().ssz_bytes_len(),
+            InboundRequest::BlobsByRange(req) => req.ssz_bytes_len(),
+            InboundRequest::BlobsByRoot(req) => req.blob_ids.ssz_bytes_len(),
+            InboundRequest::LightClientBootstrap(req) => req.ssz_bytes_len(),
+            InboundRequest::LightClientUpdatesByRange(req) => req.ssz_bytes_len(),
+            InboundRequest::Ping(req) => req.ssz_bytes_len(),
+            InboundRequest::MetaData(_) => 0,
         }
     }
 
@@ -585,6 +632,9 @@ impl<TSpec: EthSpec> InboundRequest<TSpec> {
             InboundRequest::BlocksByRoot(_) => ResponseTermination::BlocksByRoot,
             InboundRequest::BlobsByRange(_) => ResponseTermination::BlobsByRange,
             InboundRequest::BlobsByRoot(_) => ResponseTermination::BlobsByRoot,
+            InboundRequest::LightClientUpdatesByRange(_) => {
+                ResponseTermination::LightClientUpdatesByRange
+            }
             InboundRequest::Status(_) => unreachable!(),
             InboundRequest::Goodbye(_) => unreachable!(),
             InboundRequest::Ping(_) => unreachable!(),
@@ -698,6 +748,9 @@ impl<TSpec: EthSpec> std::fmt::Display for InboundRequest<TSpec> {
             InboundRequest::LightClientBootstrap(bootstrap) => {
                 write!(f, "LightClientBootstrap: {}", bootstrap.root)
             }
+            InboundRequest::LightClientUpdatesByRange(req) => {
+                write!(f, "LightClientUpdatesByRange: {}", req)
+            }
         }
     }
 }
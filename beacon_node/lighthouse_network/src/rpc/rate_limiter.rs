@@ -100,6 +100,8 @@ pub struct RPCRateLimiter {
     blbroot_rl: Limiter<PeerId>,
     /// LightClientBootstrap rate limiter.
     lcbootstrap_rl: Limiter<PeerId>,
+    /// LightClientUpdatesByRange rate limiter.
+    lcupdates_rl: Limiter<PeerId>,
 }
 
 /// Error type for non conformant requests
@@ -132,6 +134,8 @@ pub struct RPCRateLimiterBuilder {
     blbroot_quota: Option<Quota>,
     /// Quota for the LightClientBootstrap protocol.
     lcbootstrap_quota: Option<Quota>,
+    /// Quota for the LightClientUpdatesByRange protocol.
+    lcupdates_quota: Option<Quota>,
 }
 
 impl RPCRateLimiterBuilder {
@@ -148,6 +152,7 @@ impl RPCRateLimiterBuilder {
             Protocol::BlobsByRange => self.blbrange_quota = q,
             Protocol::BlobsByRoot => self.blbroot_quota = q,
             Protocol::LightClientBootstrap => self.lcbootstrap_quota = q,
+            Protocol::LightClientUpdatesByRange => self.lcupdates_quota = q,
         }
         self
     }
@@ -167,6 +172,9 @@ impl RPCRateLimiterBuilder {
         let lcbootstrap_quote = self
             .lcbootstrap_quota
             .ok_or("LightClientBootstrap quota not specified")?;
+        let lcupdates_quota = self
+            .lcupdates_quota
+            .ok_or("LightClientUpdatesByRange quota not specified")?;
 
         let blbrange_quota = self
             .blbrange_quota
@@ -186,6 +194,7 @@ impl RPCRateLimiterBuilder {
         let blbrange_rl = Limiter::from_quota(blbrange_quota)?;
         let blbroot_rl = Limiter::from_quota(blbroots_quota)?;
         let lcbootstrap_rl = Limiter::from_quota(lcbootstrap_quote)?;
+        let lcupdates_rl = Limiter::from_quota(lcupdates_quota)?;
 
         // check for peers to prune every 30 seconds, starting in 30 seconds
         let prune_every = tokio::time::Duration::from_secs(30);
@@ -202,6 +211,7 @@ impl RPCRateLimiterBuilder {
             blbrange_rl,
             blbroot_rl,
             lcbootstrap_rl,
+            lcupdates_rl,
             init_time: Instant::now(),
         })
     }
@@ -244,6 +254,7 @@ impl RPCRateLimiter {
             blobs_by_range_quota,
             blobs_by_root_quota,
             light_client_bootstrap_quota,
+            light_client_updates_by_range_quota,
         } = config;
 
         Self::builder()
@@ -256,6 +267,10 @@ impl RPCRateLimiter {
             .set_quota(Protocol::BlobsByRange, blobs_by_range_quota)
             .set_quota(Protocol::BlobsByRoot, blobs_by_root_quota)
             .set_quota(Protocol::LightClientBootstrap, light_client_bootstrap_quota)
+            .set_quota(
+                Protocol::LightClientUpdatesByRange,
+                light_client_updates_by_range_quota,
+            )
             .build()
     }
 
@@ -284,6 +299,7 @@ impl RPCRateLimiter {
             Protocol::BlobsByRange => &mut self.blbrange_rl,
             Protocol::BlobsByRoot => &mut self.blbroot_rl,
             Protocol::LightClientBootstrap => &mut self.lcbootstrap_rl,
+            Protocol::LightClientUpdatesByRange => &mut self.lcupdates_rl,
         };
         check(limiter)
     }
@@ -298,6 +314,7 @@ impl RPCRateLimiter {
         self.bbroots_rl.prune(time_since_start);
         self.blbrange_rl.prune(time_since_start);
         self.blbroot_rl.prune(time_since_start);
+        self.lcupdates_rl.prune(time_since_start);
     }
 }
 
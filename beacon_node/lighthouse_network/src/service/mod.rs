@@ -10,7 +10,7 @@ use crate::gossipsub::{
 };
 use crate::peer_manager::{
     config::Config as PeerManagerCfg, peerdb::score::PeerAction, peerdb::score::ReportSource,
-    ConnectionDirection, PeerManager, PeerManagerEvent,
+    BandwidthBuffer, ByteDirection, ConnectionDirection, PeerManager, PeerManagerEvent,
 };
 use crate::peer_manager::{MIN_OUTBOUND_ONLY_FACTOR, PEER_EXCESS_FACTOR, PRIORITY_PEER_EXCESS};
 use crate::rpc::methods::MetadataRequest;
@@ -18,9 +18,10 @@ use crate::rpc::*;
 use crate::service::behaviour::BehaviourEvent;
 pub use crate::service::behaviour::Gossipsub;
 use crate::types::{
-    fork_core_topics, subnet_from_topic_hash, GossipEncoding, GossipKind, GossipTopic,
-    SnappyTransform, Subnet, SubnetDiscovery, ALTAIR_CORE_TOPICS, BASE_CORE_TOPICS,
-    CAPELLA_CORE_TOPICS, DENEB_CORE_TOPICS, LIGHT_CLIENT_GOSSIP_TOPICS,
+    fork_core_topics, subnet_from_topic_hash, GossipEncoding, GossipKind, GossipRecord,
+    GossipRecordSender, GossipRecorder, GossipTopic, SnappyTransform, Subnet, SubnetDiscovery,
+    ALTAIR_CORE_TOPICS, BASE_CORE_TOPICS, CAPELLA_CORE_TOPICS, DENEB_CORE_TOPICS,
+    LIGHT_CLIENT_GOSSIP_TOPICS,
 };
 use crate::EnrExt;
 use crate::Eth2Enr;
@@ -33,11 +34,13 @@ use libp2p::swarm::{Swarm, SwarmEvent};
 use libp2p::PeerId;
 use libp2p::{identify, SwarmBuilder};
 use slog::{crit, debug, info, o, trace, warn};
+use std::net::IpAddr;
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::{
     sync::Arc,
     task::{Context, Poll},
+    time::{Duration, SystemTime},
 };
 use types::ForkName;
 use types::{
@@ -54,6 +57,8 @@ pub mod utils;
 pub const TARGET_SUBNET_PEERS: usize = 3;
 
 const MAX_IDENTIFY_ADDRESSES: usize = 10;
+/// The interval at which the bytes exchanged with peers are flushed to the peer database.
+const BANDWIDTH_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
 
 /// The types of events than can be obtained from polling the behaviour.
 #[derive(Debug)]
@@ -125,6 +130,12 @@ pub struct Network<AppReqId: ReqId, TSpec: EthSpec> {
     /// The interval for updating gossipsub scores
     update_gossipsub_scores: tokio::time::Interval,
     gossip_cache: GossipCache,
+    /// The bytes exchanged with peers since the last flush to the peer database.
+    bandwidth_buffer: BandwidthBuffer,
+    /// The interval for flushing `bandwidth_buffer`.
+    flush_bandwidth: tokio::time::Interval,
+    /// Records received gossip messages, if enabled.
+    gossip_recorder: Option<GossipRecordSender>,
     /// This node's PeerId.
     pub local_peer_id: PeerId,
     /// Logger for behaviour actions.
@@ -329,6 +340,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                 quic_enabled: !config.disable_quic_support,
                 metrics_enabled: config.metrics_enabled,
                 target_peer_count: config.target_peers,
+                peer_bandwidth_cap: config.peer_bandwidth_cap,
                 ..Default::default()
             };
             PeerManager::new(peer_manager_cfg, network_globals.clone(), &log)?
@@ -370,6 +382,24 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         let transport = build_transport(local_keypair.clone(), !config.disable_quic_support)
             .map_err(|e| format!("Failed to build transport: {:?}", e))?;
 
+        let gossip_recorder = match &config.gossip_record_dir {
+            Some(dir) => {
+                let recorder = GossipRecorder::new(
+                    dir.clone(),
+                    config.gossip_record_max_file_size,
+                    config.gossip_record_max_files,
+                )?;
+                info!(
+                    log,
+                    "Recording gossip messages";
+                    "dir" => ?dir,
+                    "max_files" => config.gossip_record_max_files,
+                );
+                Some(recorder.spawn(&executor, log.clone()))
+            }
+            None => None,
+        };
+
         // use the executor for libp2p
         struct Executor(task_executor::TaskExecutor);
         impl libp2p::swarm::Executor for Executor {
@@ -424,6 +454,9 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             score_settings,
             update_gossipsub_scores,
             gossip_cache,
+            bandwidth_buffer: BandwidthBuffer::default(),
+            flush_bandwidth: tokio::time::interval(BANDWIDTH_FLUSH_INTERVAL),
+            gossip_recorder,
             local_peer_id,
             log,
         };
@@ -739,10 +772,18 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         for message in messages {
             for topic in message.topics(GossipEncoding::default(), self.enr_fork_id.fork_digest) {
                 let message_data = message.encode(GossipEncoding::default());
-                if let Err(e) = self
+                let publish_result = self
                     .gossipsub_mut()
-                    .publish(Topic::from(topic.clone()), message_data.clone())
-                {
+                    .publish(Topic::from(topic.clone()), message_data.clone());
+                if publish_result.is_ok() {
+                    self.record_gossip_bytes(
+                        None,
+                        topic.kind(),
+                        ByteDirection::Outbound,
+                        message_data.len(),
+                    );
+                }
+                if let Err(e) = publish_result {
                     match e {
                         PublishError::Duplicate => {
                             debug!(
@@ -871,17 +912,12 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
 
     /// Send a request to a peer over RPC.
     pub fn send_request(&mut self, peer_id: PeerId, request_id: AppReqId, request: Request) {
-        self.eth2_rpc_mut().send_request(
-            peer_id,
-            RequestId::Application(request_id),
-            request.into(),
-        )
+        self.send_rpc_request(peer_id, RequestId::Application(request_id), request.into())
     }
 
     /// Send a successful response to a peer over RPC.
     pub fn send_response(&mut self, peer_id: PeerId, id: PeerRequestId, response: Response<TSpec>) {
-        self.eth2_rpc_mut()
-            .send_response(peer_id, id, response.into())
+        self.send_rpc_response(peer_id, id, response.into())
     }
 
     /// Inform the peer that their request produced an error.
@@ -892,11 +928,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         error: RPCResponseErrorCode,
         reason: String,
     ) {
-        self.eth2_rpc_mut().send_response(
-            peer_id,
-            id,
-            RPCCodedResponse::Error(error, reason.into()),
-        )
+        self.send_rpc_response(peer_id, id, RPCCodedResponse::Error(error, reason.into()))
     }
 
     /* Peer management functions */
@@ -925,6 +957,51 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             .goodbye_peer(peer_id, reason, source);
     }
 
+    /// Bans a peer on behalf of the node operator until `expires`, or permanently if `expires` is
+    /// `None`.
+    pub fn ban_peer(&mut self, peer_id: &PeerId, expires: Option<SystemTime>) {
+        self.peer_manager_mut().ban_peer_manually(peer_id, expires);
+    }
+
+    /// Lifts a ban made by the node operator.
+    pub fn unban_peer(&mut self, peer_id: &PeerId) {
+        self.peer_manager_mut().unban_peer_manually(peer_id);
+    }
+
+    /// Bans an IP address on behalf of the node operator until `expires`, or permanently if
+    /// `expires` is `None`.
+    pub fn ban_ip(&mut self, ip: IpAddr, expires: Option<SystemTime>) {
+        self.peer_manager_mut().ban_ip_manually(ip, expires);
+    }
+
+    /// Lifts an IP address ban made by the node operator.
+    pub fn unban_ip(&mut self, ip: &IpAddr) {
+        self.peer_manager_mut().unban_ip_manually(ip);
+    }
+
+    /// Marks a peer as trusted, or no longer trusted.
+    pub fn set_trusted_peer(&mut self, peer_id: &PeerId, trusted: bool) {
+        self.peer_manager_mut().set_trusted_peer(peer_id, trusted);
+    }
+
+    /// Dials a peer by its ENR. Returns false if the peer is connected, being dialed or banned.
+    pub fn dial_enr(&mut self, enr: Enr) -> bool {
+        self.peer_manager_mut().dial_peer(enr)
+    }
+
+    /// Dials a multiaddr.
+    pub fn dial_multiaddr(&mut self, addr: Multiaddr) -> Result<(), libp2p::swarm::DialError> {
+        self.swarm.dial(addr)
+    }
+
+    /// Disconnects from a peer with the given reason, without banning it.
+    ///
+    /// Returns false if the peer is not connected.
+    pub fn disconnect_peer(&mut self, peer_id: &PeerId, reason: GoodbyeReason) -> bool {
+        self.peer_manager_mut()
+            .disconnect_peer_manually(peer_id, reason)
+    }
+
     /// Returns an iterator over all enr entries in the DHT.
     pub fn enr_entries(&self) -> Vec<Enr> {
         self.discovery().table_entries_enr()
@@ -1050,8 +1127,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         };
         trace!(self.log, "Sending Ping"; "peer_id" => %peer_id);
         let id = RequestId::Internal;
-        self.eth2_rpc_mut()
-            .send_request(peer_id, id, OutboundRequest::Ping(ping));
+        self.send_rpc_request(peer_id, id, OutboundRequest::Ping(ping));
     }
 
     /// Sends a Pong response to the peer.
@@ -1061,15 +1137,14 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         };
         trace!(self.log, "Sending Pong"; "request_id" => id.1, "peer_id" => %peer_id);
         let event = RPCCodedResponse::Success(RPCResponse::Pong(ping));
-        self.eth2_rpc_mut().send_response(peer_id, id, event);
+        self.send_rpc_response(peer_id, id, event);
     }
 
     /// Sends a METADATA request to a peer.
     fn send_meta_data_request(&mut self, peer_id: PeerId) {
         // We always prefer sending V2 requests
         let event = OutboundRequest::MetaData(MetadataRequest::new_v2());
-        self.eth2_rpc_mut()
-            .send_request(peer_id, RequestId::Internal, event);
+        self.send_rpc_request(peer_id, RequestId::Internal, event);
     }
 
     /// Sends a METADATA response to a peer.
@@ -1085,25 +1160,104 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             MetadataRequest::V2(_) => metadata,
         };
         let event = RPCCodedResponse::Success(RPCResponse::MetaData(metadata));
-        self.eth2_rpc_mut().send_response(peer_id, id, event);
+        self.send_rpc_response(peer_id, id, event);
     }
 
-    // RPC Propagation methods
-    /// Queues the response to be sent upwards as long at it was requested outside the Behaviour.
-    #[must_use = "return the response"]
-    fn build_response(
+    /// Sends an RPC request, recording its size.
+    fn send_rpc_request(
         &mut self,
+        peer_id: PeerId,
         id: RequestId<AppReqId>,
+        request: OutboundRequest<TSpec>,
+    ) {
+        self.record_rpc_bytes(
+            &peer_id,
+            request.versioned_protocol().protocol(),
+            ByteDirection::Outbound,
+            request.ssz_bytes_len(),
+        );
+        self.eth2_rpc_mut().send_request(peer_id, id, request);
+    }
+
+    /// Sends an RPC response, recording its size.
+    fn send_rpc_response(
+        &mut self,
         peer_id: PeerId,
-        response: Response<TSpec>,
-    ) -> Option<NetworkEvent<AppReqId, TSpec>> {
-        match id {
-            RequestId::Application(id) => Some(NetworkEvent::ResponseReceived {
-                peer_id,
-                id,
-                response,
-            }),
-            RequestId::Internal => None,
+        id: PeerRequestId,
+        response: RPCCodedResponse<TSpec>,
+    ) {
+        if let RPCCodedResponse::Success(response) = &response {
+            self.record_rpc_bytes(
+                &peer_id,
+                response.protocol(),
+                ByteDirection::Outbound,
+                response.ssz_bytes_len(),
+            );
+        }
+        self.eth2_rpc_mut().send_response(peer_id, id, response);
+    }
+
+    /// Records the bytes of an RPC request or response exchanged with a peer.
+    fn record_rpc_bytes(
+        &mut self,
+        peer_id: &PeerId,
+        protocol: Protocol,
+        direction: ByteDirection,
+        bytes: usize,
+    ) {
+        let bytes = bytes as u64;
+        metrics::inc_counter_vec_by(
+            &metrics::RPC_BYTES_PER_PROTOCOL,
+            &[protocol.as_ref(), direction.as_ref()],
+            bytes,
+        );
+        self.bandwidth_buffer
+            .add_rpc(*peer_id, protocol.as_ref(), direction, bytes);
+    }
+
+    /// Records the bytes of a gossip message. Received messages are also attributed to the peer
+    /// that propagated them.
+    fn record_gossip_bytes(
+        &mut self,
+        propagation_source: Option<&PeerId>,
+        kind: &GossipKind,
+        direction: ByteDirection,
+        bytes: usize,
+    ) {
+        let topic_kind: &str = kind.as_ref();
+        let bytes = bytes as u64;
+        metrics::inc_counter_vec_by(
+            &metrics::GOSSIP_BYTES_PER_TOPIC_KIND,
+            &[topic_kind, direction.as_ref()],
+            bytes,
+        );
+        if let Some(peer_id) = propagation_source {
+            self.bandwidth_buffer
+                .add_gossip(*peer_id, topic_kind, direction, bytes);
+        }
+    }
+
+    /// Flushes the bytes exchanged with peers since the last flush to the peer database.
+    fn flush_bandwidth_buffer(&mut self) {
+        if self.bandwidth_buffer.is_empty() {
+            return;
+        }
+        let mut peers = self.network_globals.peers.write();
+        for (peer_id, bandwidth) in self.bandwidth_buffer.drain() {
+            peers.record_bandwidth(&peer_id, &bandwidth);
+            if let Some(info) = peers.peer_info(&peer_id) {
+                let client = info.client().kind.as_ref();
+                for (direction, bytes) in [
+                    (ByteDirection::Inbound, bandwidth.total().inbound),
+                    (ByteDirection::Outbound, bandwidth.total().outbound),
+                ] {
+                    metrics::inc_counter_vec_by(
+                        &metrics::BYTES_PER_CLIENT,
+                        &[client, direction.as_ref()],
+                        bytes,
+                    );
+                }
+            }
         }
     }
 
@@ -1123,6 +1277,10 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             Request::LightClientBootstrap(_) => {
                 metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["light_client_bootstrap"])
             }
+            Request::LightClientUpdatesByRange(_) => metrics::inc_counter_vec(
+                &metrics::TOTAL_RPC_REQUESTS,
+                &["light_client_updates_by_range"],
+            ),
             Request::BlocksByRange { .. } => {
                 metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["blocks_by_range"])
             }
@@ -1182,8 +1340,25 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                 message_id: id,
                 message: gs_msg,
             } => {
+                // Record the message as it was received, so that messages which fail to decode
+                // are recorded too.
+                if let Some(recorder) = &self.gossip_recorder {
+                    let record =
+                        GossipRecord::new(&propagation_source, &gs_msg.topic, gs_msg.data.clone());
+                    if !recorder.record(record) {
+                        self.gossip_recorder = None;
+                    }
+                }
                 // Note: We are keeping track here of the peer that sent us the message, not the
                 // peer that originally published the message.
+                if let Ok(topic) = GossipTopic::decode(gs_msg.topic.as_str()) {
+                    self.record_gossip_bytes(
+                        Some(&propagation_source),
+                        topic.kind(),
+                        ByteDirection::Inbound,
+                        gs_msg.data.len(),
+                    );
+                }
                 match PubsubMessage::decode(&gs_msg.topic, &gs_msg.data, &self.fork_context) {
                     Err(e) => {
                         debug!(self.log, "Could not decode gossipsub message"; "topic" => ?gs_msg.topic,"error" => e);
@@ -1217,8 +1392,11 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                     }
                     // Try to send the cached messages for this topic
                     if let Some(msgs) = self.gossip_cache.retrieve(&topic) {
+                        // Release the borrow of the cache so that the sent bytes can be recorded.
+                        let msgs = msgs.collect::<Vec<_>>();
                         for data in msgs {
                             let topic_str: &str = topic.kind().as_ref();
+                            let bytes = data.len();
                             match self
                                 .swarm
                                 .behaviour_mut()
@@ -1231,6 +1409,12 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                                         "Gossip message published on retry";
                                         "topic" => topic_str
                                     );
+                                    self.record_gossip_bytes(
+                                        None,
+                                        topic.kind(),
+                                        ByteDirection::Outbound,
+                                        bytes,
+                                    );
                                     metrics::inc_counter_vec(
                                         &metrics::GOSSIP_LATE_PUBLISH_PER_TOPIC_KIND,
                                         &[topic_str],
@@ -1329,6 +1513,22 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             return None;
         }
 
+        match &event.event {
+            HandlerEvent::Ok(RPCReceived::Request(_, request)) => self.record_rpc_bytes(
+                &peer_id,
+                request.versioned_protocol().protocol(),
+                ByteDirection::Inbound,
+                request.ssz_bytes_len(),
+            ),
+            HandlerEvent::Ok(RPCReceived::Response(_, response)) => self.record_rpc_bytes(
+                &peer_id,
+                response.protocol(),
+                ByteDirection::Inbound,
+                response.ssz_bytes_len(),
+            ),
+            _ => {}
+        }
+
         let handler_id = event.conn_id;
         // The METADATA and PING RPC responses are handled within the behaviour and not propagated
         match event.event {
@@ -1464,6 +1664,14 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                         );
                         Some(event)
                     }
+                    InboundRequest::LightClientUpdatesByRange(req) => {
+                        let event = self.build_request(
+                            peer_request_id,
+                            peer_id,
+                            Request::LightClientUpdatesByRange(req),
+                        );
+                        Some(event)
+                    }
                 }
             }
             HandlerEvent::Ok(RPCReceived::Response(id, resp)) => {
@@ -1501,6 +1709,12 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                     RPCResponse::LightClientBootstrap(bootstrap) => {
                         self.build_response(id, peer_id, Response::LightClientBootstrap(bootstrap))
                     }
+                    // Should never be reached
+                    RPCResponse::LightClientUpdatesByRange(update) => self.build_response(
+                        id,
+                        peer_id,
+                        Response::LightClientUpdatesByRange(Some(update)),
+                    ),
                 }
             }
             HandlerEvent::Ok(RPCReceived::EndOfStream(id, termination)) => {
@@ -1509,6 +1723,9 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                     ResponseTermination::BlocksByRoot => Response::BlocksByRoot(None),
                     ResponseTermination::BlobsByRange => Response::BlobsByRange(None),
                     ResponseTermination::BlobsByRoot => Response::BlobsByRoot(None),
+                    ResponseTermination::LightClientUpdatesByRange => {
+                        Response::LightClientUpdatesByRange(None)
+                    }
                 };
                 self.build_response(id, peer_id, response)
             }
@@ -1727,6 +1944,11 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             this.peer_manager.update_gossipsub_scores(&this.gossipsub);
         }
 
+        // flush the bytes exchanged with peers to the peer database
+        while self.flush_bandwidth.poll_tick(cx).is_ready() {
+            self.flush_bandwidth_buffer();
+        }
+
         // poll the gossipsub cache to clear expired messages
         while let Poll::Ready(Some(result)) = self.gossip_cache.poll_next_unpin(cx) {
             match result {
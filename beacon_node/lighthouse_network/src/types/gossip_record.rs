@@ -0,0 +1,267 @@
+//! Recordings of received gossip messages, used to replay gossip traffic offline.
+//!
+//! A recording is a directory of files. Each file is a sequence of SSZ encoded `GossipRecord`s,
+//! each prefixed with its length as a little-endian `u32`. Files are named after the time of the
+//! first message they contain, so sorting the file names sorts the recording.
+//!
+//! The `GossipRecorder` starts a new file once the current one reaches its maximum size and
+//! deletes the oldest files once there are more than the maximum number of files. It runs on a
+//! blocking thread and receives records through a `GossipRecordSender`, so that the network task
+//! never waits on the disk.
+
+use crate::metrics;
+use crate::{PeerId, TopicHash};
+use slog::{error, Logger};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, TrySendError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use task_executor::TaskExecutor;
+
+const FILE_PREFIX: &str = "gossip_";
+const FILE_EXTENSION: &str = "ssz";
+const LENGTH_PREFIX_BYTES: usize = 4;
+/// The maximum number of records waiting to be written. Further records are dropped.
+const MAX_QUEUED_RECORDS: usize = 16_384;
+/// Buffered records are flushed to disk once no record has been received for this long.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A gossip message as it was received from the network.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct GossipRecord {
+    /// The time the message was received, in milliseconds since the UNIX epoch.
+    pub timestamp_millis: u64,
+    /// The topic the message was received on.
+    pub topic: Vec<u8>,
+    /// The peer that sent us the message, encoded as bytes.
+    pub peer_id: Vec<u8>,
+    /// The message data, exactly as it was received and before it was decoded.
+    pub data: Vec<u8>,
+}
+
+impl GossipRecord {
+    /// Creates a record of a message received now.
+    pub fn new(peer_id: &PeerId, topic: &TopicHash, data: Vec<u8>) -> Self {
+        Self {
+            timestamp_millis: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_millis() as u64),
+            topic: topic.as_str().as_bytes().to_vec(),
+            peer_id: peer_id.to_bytes(),
+            data,
+        }
+    }
+}
+
+/// Queues records to be written by a `GossipRecorder` running on a blocking thread.
+pub struct GossipRecordSender {
+    tx: mpsc::SyncSender<GossipRecord>,
+}
+
+impl GossipRecordSender {
+    /// Queues a record to be written, dropping it if the writer has fallen behind.
+    ///
+    /// Returns `false` if the recorder has stopped, in which case the sender should be dropped.
+    pub fn record(&self, record: GossipRecord) -> bool {
+        match self.tx.try_send(record) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                metrics::inc_counter(&metrics::GOSSIP_RECORDS_DROPPED);
+                true
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+/// Writes gossip records to a rotating set of files.
+pub struct GossipRecorder {
+    dir: PathBuf,
+    max_file_size: u64,
+    max_files: usize,
+    file: Option<BufWriter<File>>,
+    file_size: u64,
+}
+
+impl GossipRecorder {
+    /// Creates a recorder that writes to `dir`, creating it if required.
+    pub fn new(dir: PathBuf, max_file_size: u64, max_files: usize) -> Result<Self, String> {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Unable to create {}: {:?}", dir.display(), e))?;
+        Ok(Self {
+            dir,
+            max_file_size,
+            max_files: max_files.max(1),
+            file: None,
+            file_size: 0,
+        })
+    }
+
+    /// Moves the recorder to a blocking thread which writes the records queued with the returned
+    /// sender. The recorder stops once the sender is dropped or a record can't be written.
+    pub fn spawn(mut self, executor: &TaskExecutor, log: Logger) -> GossipRecordSender {
+        let (tx, rx) = mpsc::sync_channel(MAX_QUEUED_RECORDS);
+        executor.spawn_blocking(
+            move || loop {
+                let result = match rx.recv_timeout(FLUSH_INTERVAL) {
+                    Ok(record) => self.record(&record),
+                    Err(RecvTimeoutError::Timeout) => self.flush(),
+                    Err(RecvTimeoutError::Disconnected) => return,
+                };
+                if let Err(e) = result {
+                    error!(
+                        log,
+                        "Failed to record gossip, disabling the recorder";
+                        "error" => e
+                    );
+                    return;
+                }
+            },
+            "gossip_recorder",
+        );
+        GossipRecordSender { tx }
+    }
+
+    /// Appends a record to the current file, starting a new file if the current one is full.
+    pub fn record(&mut self, record: &GossipRecord) -> Result<(), String> {
+        if self.file.is_none() || self.file_size >= self.max_file_size {
+            self.rotate(record.timestamp_millis)?;
+        }
+
+        let bytes = record.as_ssz_bytes();
+        let file = self.file.as_mut().ok_or("No recording file")?;
+        file.write_all(&(bytes.len() as u32).to_le_bytes())
+            .and_then(|_| file.write_all(&bytes))
+            .map_err(|e| format!("Unable to write gossip record: {:?}", e))?;
+        self.file_size += (LENGTH_PREFIX_BYTES + bytes.len()) as u64;
+        Ok(())
+    }
+
+    /// Flushes any buffered records to disk.
+    pub fn flush(&mut self) -> Result<(), String> {
+        if let Some(file) = self.file.as_mut() {
+            file.flush()
+                .map_err(|e| format!("Unable to flush gossip records: {:?}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Closes the current file, starts a new one and deletes the oldest files beyond the limit.
+    fn rotate(&mut self, timestamp_millis: u64) -> Result<(), String> {
+        self.flush()?;
+
+        let path = self.dir.join(format!(
+            "{}{:020}.{}",
+            FILE_PREFIX, timestamp_millis, FILE_EXTENSION
+        ));
+        let file = File::create(&path)
+            .map_err(|e| format!("Unable to create {}: {:?}", path.display(), e))?;
+        self.file = Some(BufWriter::new(file));
+        self.file_size = 0;
+
+        let files = recording_files(&self.dir)?;
+        let excess = files.len().saturating_sub(self.max_files);
+        for old_file in &files[..excess] {
+            fs::remove_file(old_file)
+                .map_err(|e| format!("Unable to remove {}: {:?}", old_file.display(), e))?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for GossipRecorder {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// The files of the recording in `dir`, oldest first.
+pub fn recording_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = fs::read_dir(dir)
+        .map_err(|e| format!("Unable to read {}: {:?}", dir.display(), e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension().map_or(false, |ext| ext == FILE_EXTENSION)
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map_or(false, |name| name.starts_with(FILE_PREFIX))
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+/// Reads the records of a recording, which is either a single file or a directory of files.
+pub fn read_gossip_records(path: &Path) -> Result<Vec<GossipRecord>, String> {
+    let files = if path.is_dir() {
+        recording_files(path)?
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut records = vec![];
+    for file in files {
+        let mut bytes = vec![];
+        File::open(&file)
+            .and_then(|mut f| f.read_to_end(&mut bytes))
+            .map_err(|e| format!("Unable to read {}: {:?}", file.display(), e))?;
+
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let length_bytes = bytes
+                .get(offset..offset + LENGTH_PREFIX_BYTES)
+                .ok_or_else(|| format!("Truncated record length in {}", file.display()))?;
+            let length =
+                u32::from_le_bytes(length_bytes.try_into().expect("length prefix is 4 bytes"))
+                    as usize;
+            offset += LENGTH_PREFIX_BYTES;
+
+            let record_bytes = bytes
+                .get(offset..offset + length)
+                .ok_or_else(|| format!("Truncated record in {}", file.display()))?;
+            let record = GossipRecord::from_ssz_bytes(record_bytes)
+                .map_err(|e| format!("Invalid record in {}: {:?}", file.display(), e))?;
+            records.push(record);
+            offset += length;
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(timestamp_millis: u64) -> GossipRecord {
+        GossipRecord {
+            timestamp_millis,
+            topic: b"/eth2/00000000/beacon_block/ssz_snappy".to_vec(),
+            peer_id: vec![1, 2, 3],
+            data: vec![42; 100],
+        }
+    }
+
+    #[test]
+    fn records_rotate_and_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let records = (0..10).map(record).collect::<Vec<_>>();
+        {
+            // Every file holds two records.
+            let mut recorder = GossipRecorder::new(dir.path().to_path_buf(), 200, 3).unwrap();
+            for record in &records {
+                recorder.record(record).unwrap();
+            }
+        }
+
+        // Only the three newest files are kept.
+        assert_eq!(recording_files(dir.path()).unwrap().len(), 3);
+        assert_eq!(read_gossip_records(dir.path()).unwrap(), records[4..]);
+
+        let newest = recording_files(dir.path()).unwrap().pop().unwrap();
+        assert_eq!(read_gossip_records(&newest).unwrap(), records[8..]);
+    }
+}
@@ -236,6 +236,21 @@ lazy_static! {
         "Number of Syncing chains in range, per range type",
         &["range_type"]
     );
+    pub static ref SYNC_RANGE_RESPONSE_BYTES: Result<IntCounterVec> = try_create_int_counter_vec(
+        "sync_range_response_bytes_total",
+        "Total SSZ bytes received for range sync requests, per protocol",
+        &["protocol"]
+    );
+    pub static ref SYNC_RANGE_REQUEST_ERRORS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "sync_range_request_errors_total",
+        "Total number of failed range sync requests, per protocol",
+        &["protocol"]
+    );
+    pub static ref SYNC_RANGE_PEER_THROUGHPUT: Result<Histogram> = try_create_histogram_with_buckets(
+        "sync_range_peer_throughput_bytes_per_second",
+        "Effective throughput of peers serving range sync requests",
+        decimal_buckets(3, 8)
+    );
     pub static ref SYNC_SINGLE_BLOCK_LOOKUPS: Result<IntGauge> = try_create_int_gauge(
         "sync_single_block_lookups",
         "Number of single block lookups underway"
@@ -15,7 +15,10 @@ use beacon_processor::{
 use environment::null_logger;
 use lighthouse_network::rpc::methods::{BlobsByRangeRequest, BlobsByRootRequest};
 use lighthouse_network::{
-    rpc::{BlocksByRangeRequest, BlocksByRootRequest, LightClientBootstrapRequest, StatusMessage},
+    rpc::{
+        BlocksByRangeRequest, BlocksByRootRequest, LightClientBootstrapRequest,
+        LightClientUpdatesByRangeRequest, StatusMessage,
+    },
     Client, MessageId, NetworkGlobals, PeerId, PeerRequestId,
 };
 use slog::{debug, Logger};
@@ -489,6 +492,25 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         })
     }
 
+    /// Create a new work event to import the `blobs` of the historical blocks in
+    /// `start_slot..end_slot`.
+    pub fn send_historical_blobs(
+        self: &Arc<Self>,
+        start_slot: Slot,
+        end_slot: Slot,
+        blobs: Vec<Arc<BlobSidecar<T::EthSpec>>>,
+    ) -> Result<(), Error<T::EthSpec>> {
+        let processor = self.clone();
+        let process_fn =
+            async move { processor.process_historical_blobs(start_slot, end_slot, blobs) };
+
+        // Blob backfill batches are rate-limited along with the block backfill batches.
+        self.try_send(BeaconWorkEvent {
+            drop_during_sync: false,
+            work: Work::ChainSegmentBackfill(Box::pin(process_fn)),
+        })
+    }
+
     /// Create a new work event to process `StatusMessage`s from the RPC network.
     pub fn send_status_message(
         self: &Arc<Self>,
@@ -605,6 +627,24 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         })
     }
 
+    /// Create a new work event to process `LightClientUpdatesByRange` requests from the RPC
+    /// network.
+    pub fn send_light_client_updates_by_range_request(
+        self: &Arc<Self>,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        request: LightClientUpdatesByRangeRequest,
+    ) -> Result<(), Error<T::EthSpec>> {
+        let processor = self.clone();
+        let process_fn =
+            move || processor.handle_light_client_updates_by_range(peer_id, request_id, request);
+
+        self.try_send(BeaconWorkEvent {
+            drop_during_sync: true,
+            work: Work::LightClientUpdatesByRangeRequest(Box::new(process_fn)),
+        })
+    }
+
     /// Send a message to `sync_tx`.
     ///
     /// Creates a log if there is an internal error.
@@ -642,6 +682,7 @@ impl<E: EthSpec> NetworkBeaconProcessor<TestBeaconChainType<E>> {
             beacon_processor_rx,
             work_reprocessing_tx,
             work_reprocessing_rx: _work_reprocessing_rx,
+            queue_state: _queue_state,
         } = <_>::default();
 
         let (network_tx, _network_rx) = mpsc::unbounded_channel();
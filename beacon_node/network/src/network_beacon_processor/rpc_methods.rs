@@ -330,6 +330,64 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         };
     }
 
+    /// Handle a `LightClientUpdatesByRange` request from the peer.
+    pub fn handle_light_client_updates_by_range(
+        self: &Arc<Self>,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        req: LightClientUpdatesByRangeRequest,
+    ) {
+        debug!(self.log, "Received LightClientUpdatesByRange Request";
+            "peer_id" => %peer_id,
+            "start_period" => req.start_period,
+            "count" => req.count,
+        );
+
+        let updates = match self
+            .chain
+            .get_light_client_updates(req.start_period, req.max_requested())
+        {
+            Ok(updates) => updates,
+            Err(e) => {
+                self.send_error_response(
+                    peer_id,
+                    RPCResponseErrorCode::ServerError,
+                    "Failed fetching light client updates".into(),
+                    request_id,
+                );
+                error!(self.log, "Error getting LightClientUpdates";
+                    "start_period" => req.start_period,
+                    "count" => req.count,
+                    "peer" => %peer_id,
+                    "error" => ?e
+                );
+                return;
+            }
+        };
+
+        debug!(self.log, "LightClientUpdatesByRange outgoing response processed";
+            "peer" => %peer_id,
+            "start_period" => req.start_period,
+            "requested" => req.count,
+            "returned" => updates.len(),
+        );
+
+        for update in updates {
+            self.send_response(
+                peer_id,
+                Response::LightClientUpdatesByRange(Some(Arc::new(update))),
+                request_id,
+            );
+        }
+
+        // send the stream terminator
+        self.send_response(
+            peer_id,
+            Response::LightClientUpdatesByRange(None),
+            request_id,
+        );
+    }
+
     /// Handle a `BlocksByRange` request from the peer.
     pub fn handle_blocks_by_range_request(
         self: Arc<Self>,
@@ -10,7 +10,8 @@ use beacon_chain::data_availability_checker::AvailabilityCheckError;
 use beacon_chain::data_availability_checker::MaybeAvailableBlock;
 use beacon_chain::{
     validator_monitor::get_slot_delay_ms, AvailabilityProcessingStatus, BeaconChainError,
-    BeaconChainTypes, BlockError, ChainSegmentResult, HistoricalBlockError, NotifyExecutionLayer,
+    BeaconChainTypes, BlockError, ChainSegmentResult, HistoricalBlobError, HistoricalBlockError,
+    NotifyExecutionLayer,
 };
 use beacon_processor::{
     work_reprocessing_queue::{QueuedRpcBlock, ReprocessQueueMessage},
@@ -24,7 +25,7 @@ use store::KzgCommitment;
 use tokio::sync::mpsc;
 use types::beacon_block_body::format_kzg_commitments;
 use types::blob_sidecar::FixedBlobSidecarList;
-use types::{Epoch, Hash256};
+use types::{BlobSidecar, Epoch, Hash256, Slot};
 
 /// Id associated to a batch processing request, either a sync batch or a parent lookup.
 #[derive(Clone, Debug, PartialEq)]
@@ -625,6 +626,70 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         }
     }
 
+    /// Attempt to import the `blobs` of the historical blocks in `start_slot..end_slot`, sending
+    /// the result to the blob backfill sync.
+    pub fn process_historical_blobs(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+        blobs: Vec<Arc<BlobSidecar<T::EthSpec>>>,
+    ) {
+        let sent_blobs = blobs.len();
+        let result = match self
+            .chain
+            .import_historical_blob_batch(start_slot, end_slot, blobs)
+        {
+            Ok(imported_blobs) => {
+                debug!(self.log, "Blob backfill batch processed";
+                    "start_slot" => start_slot,
+                    "end_slot" => end_slot,
+                    "imported_blobs" => imported_blobs,
+                    "service" => "sync");
+                BatchProcessResult::Success {
+                    was_non_empty: imported_blobs > 0,
+                }
+            }
+            Err(error) => {
+                let peer_action = match &error {
+                    BeaconChainError::HistoricalBlobError(e) => match e {
+                        // The peer is faulty if it sends blobs that don't match our blocks.
+                        HistoricalBlobError::UnknownBlock { .. }
+                        | HistoricalBlobError::InvalidBlobIndex { .. }
+                        | HistoricalBlobError::MismatchedCommitment { .. }
+                        | HistoricalBlobError::InvalidKzgProof(_) => {
+                            Some(PeerAction::LowToleranceError)
+                        }
+                        // Peers are only required to serve blobs within the data availability
+                        // boundary, not having older blobs is not a fault.
+                        HistoricalBlobError::MissingBlobs { .. } => None,
+                        // These are internal errors, don't penalize the peer.
+                        HistoricalBlobError::DenebNotScheduled
+                        | HistoricalBlobError::NonContiguousBatch { .. }
+                        | HistoricalBlobError::BatchBeforeDenebFork { .. }
+                        | HistoricalBlobError::KzgNotInitialized => None,
+                    },
+                    // This is an internal error, don't penalize the peer.
+                    _ => None,
+                };
+                debug!(self.log, "Blob backfill batch processing failed";
+                    "start_slot" => start_slot,
+                    "end_slot" => end_slot,
+                    "sent_blobs" => sent_blobs,
+                    "error" => ?error,
+                    "service" => "sync");
+                match peer_action {
+                    Some(penalty) => BatchProcessResult::FaultyFailure {
+                        imported_blocks: false,
+                        penalty,
+                    },
+                    None => BatchProcessResult::NonFaultyFailure,
+                }
+            }
+        };
+
+        self.send_sync_message(SyncMessage::BlobBackfillBatchProcessed { start_slot, result });
+    }
+
     /// Helper function to handle a `BlockError` from `process_chain_segment`
     fn handle_failed_chain_segment(
         &self,
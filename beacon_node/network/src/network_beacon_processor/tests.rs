@@ -192,6 +192,7 @@ impl TestRig {
             beacon_processor_rx,
             work_reprocessing_tx,
             work_reprocessing_rx,
+            queue_state,
         } = BeaconProcessorChannels::new(&beacon_processor_config);
 
         let (sync_tx, _sync_rx) = mpsc::unbounded_channel();
@@ -230,6 +231,7 @@ impl TestRig {
             executor,
             current_workers: 0,
             config: beacon_processor_config,
+            queue_state,
             log: log.clone(),
         }
         .spawn_manager(
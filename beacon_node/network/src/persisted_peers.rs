@@ -0,0 +1,158 @@
+use lighthouse_network::PeerId;
+use rlp::{Rlp, RlpStream};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use store::{DBColumn, Error as StoreError, HotColdDB, ItemStore, StoreItem};
+use types::{EthSpec, Hash256};
+
+/// 32-byte key for accessing the `PersistedPeers`. All zero because `PersistedPeers` has its own
+/// column.
+pub const PERSISTED_PEERS_DB_KEY: Hash256 = Hash256::zero();
+
+pub fn load_peers<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+) -> PersistedPeers {
+    match store.get_item(&PERSISTED_PEERS_DB_KEY) {
+        Ok(Some(p)) => p,
+        _ => PersistedPeers::default(),
+    }
+}
+
+/// Attempt to persist the peers and IP addresses banned or trusted by the node operator.
+pub fn persist_peers<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+    peers: &PersistedPeers,
+) -> Result<(), store::Error> {
+    store.put_item(&PERSISTED_PEERS_DB_KEY, peers)
+}
+
+/// The peers and IP addresses banned or trusted by the node operator at runtime, along with the
+/// time each ban expires.
+#[derive(Debug, Default, PartialEq)]
+pub struct PersistedPeers {
+    pub banned_peers: Vec<(PeerId, Option<SystemTime>)>,
+    pub banned_ips: Vec<(IpAddr, Option<SystemTime>)>,
+    pub trusted_peers: Vec<PeerId>,
+}
+
+impl StoreItem for PersistedPeers {
+    fn db_column() -> DBColumn {
+        DBColumn::PersistedPeers
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(3);
+        stream.begin_list(self.banned_peers.len());
+        for (peer_id, expires) in &self.banned_peers {
+            stream.begin_list(2);
+            stream.append(&peer_id.to_bytes());
+            stream.append(&expiry_to_secs(expires));
+        }
+        stream.begin_list(self.banned_ips.len());
+        for (ip, expires) in &self.banned_ips {
+            let ip_bytes = match ip {
+                IpAddr::V4(ip) => ip.octets().to_vec(),
+                IpAddr::V6(ip) => ip.octets().to_vec(),
+            };
+            stream.begin_list(2);
+            stream.append(&ip_bytes);
+            stream.append(&expiry_to_secs(expires));
+        }
+        stream.begin_list(self.trusted_peers.len());
+        for peer_id in &self.trusted_peers {
+            stream.append(&peer_id.to_bytes());
+        }
+        stream.out().to_vec()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        let rlp_error = |e: rlp::DecoderError| StoreError::RlpError(format!("{}", e));
+        let rlp = Rlp::new(bytes);
+
+        let mut banned_peers = vec![];
+        for ban in rlp.at(0).map_err(rlp_error)?.iter() {
+            let peer_id = peer_id_from_bytes(&ban.val_at::<Vec<u8>>(0).map_err(rlp_error)?)?;
+            let expires = secs_to_expiry(ban.val_at(1).map_err(rlp_error)?);
+            banned_peers.push((peer_id, expires));
+        }
+
+        let mut banned_ips = vec![];
+        for ban in rlp.at(1).map_err(rlp_error)?.iter() {
+            let ip_bytes = ban.val_at::<Vec<u8>>(0).map_err(rlp_error)?;
+            let ip = match ip_bytes.len() {
+                4 => IpAddr::from(<[u8; 4]>::try_from(ip_bytes.as_slice()).expect("length is 4")),
+                16 => {
+                    IpAddr::from(<[u8; 16]>::try_from(ip_bytes.as_slice()).expect("length is 16"))
+                }
+                len => {
+                    return Err(StoreError::RlpError(format!(
+                        "invalid IP address length: {}",
+                        len
+                    )))
+                }
+            };
+            let expires = secs_to_expiry(ban.val_at(1).map_err(rlp_error)?);
+            banned_ips.push((ip, expires));
+        }
+
+        let mut trusted_peers = vec![];
+        for peer_id in rlp.at(2).map_err(rlp_error)?.iter() {
+            trusted_peers.push(peer_id_from_bytes(
+                &peer_id.as_val::<Vec<u8>>().map_err(rlp_error)?,
+            )?);
+        }
+
+        Ok(PersistedPeers {
+            banned_peers,
+            banned_ips,
+            trusted_peers,
+        })
+    }
+}
+
+fn peer_id_from_bytes(bytes: &[u8]) -> Result<PeerId, StoreError> {
+    PeerId::from_bytes(bytes).map_err(|e| StoreError::RlpError(format!("invalid peer id: {}", e)))
+}
+
+/// Permanent bans are stored with an expiry of zero.
+fn expiry_to_secs(expires: &Option<SystemTime>) -> u64 {
+    expires
+        .and_then(|expires| expires.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |expires| expires.as_secs())
+}
+
+fn secs_to_expiry(secs: u64) -> Option<SystemTime> {
+    (secs != 0).then(|| UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use store::config::StoreConfig;
+    use store::{HotColdDB, MemoryStore};
+    use types::{ChainSpec, MinimalEthSpec};
+
+    #[test]
+    fn test_persisted_peers() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let store: HotColdDB<
+            MinimalEthSpec,
+            MemoryStore<MinimalEthSpec>,
+            MemoryStore<MinimalEthSpec>,
+        > = HotColdDB::open_ephemeral(StoreConfig::default(), ChainSpec::minimal(), log).unwrap();
+        let expires = Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+        let peers = PersistedPeers {
+            banned_peers: vec![(PeerId::random(), None), (PeerId::random(), expires)],
+            banned_ips: vec![
+                ("1.2.3.4".parse().unwrap(), expires),
+                ("::1".parse().unwrap(), None),
+            ],
+            trusted_peers: vec![PeerId::random()],
+        };
+        store.put_item(&PERSISTED_PEERS_DB_KEY, &peers).unwrap();
+        let loaded: PersistedPeers = store.get_item(&PERSISTED_PEERS_DB_KEY).unwrap().unwrap();
+        assert_eq!(loaded, peers);
+    }
+}
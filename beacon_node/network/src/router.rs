@@ -220,6 +220,11 @@ impl<T: BeaconChainTypes> Router<T> {
                 self.network_beacon_processor
                     .send_light_client_bootstrap_request(peer_id, request_id, request),
             ),
+            Request::LightClientUpdatesByRange(request) => self
+                .handle_beacon_processor_send_result(
+                    self.network_beacon_processor
+                        .send_light_client_updates_by_range_request(peer_id, request_id, request),
+                ),
         }
     }
 
@@ -250,7 +255,9 @@ impl<T: BeaconChainTypes> Router<T> {
             Response::BlobsByRoot(blob) => {
                 self.on_blobs_by_root_response(peer_id, request_id, blob);
             }
-            Response::LightClientBootstrap(_) => unreachable!(),
+            Response::LightClientBootstrap(_) | Response::LightClientUpdatesByRange(_) => {
+                unreachable!()
+            }
         }
     }
 
@@ -489,6 +496,10 @@ impl<T: BeaconChainTypes> Router<T> {
                     crit!(self.log, "Block lookups do not request BBRange requests"; "peer_id" => %peer_id);
                     return;
                 }
+                SyncId::BlobBackfill { .. } => {
+                    crit!(self.log, "Blob backfill does not request BBRange requests"; "peer_id" => %peer_id);
+                    return;
+                }
                 id @ (SyncId::BackFillBlocks { .. }
                 | SyncId::RangeBlocks { .. }
                 | SyncId::BackFillBlockAndBlobs { .. }
@@ -554,7 +565,8 @@ impl<T: BeaconChainTypes> Router<T> {
                 SyncId::BackFillBlocks { .. }
                 | SyncId::RangeBlocks { .. }
                 | SyncId::RangeBlockAndBlobs { .. }
-                | SyncId::BackFillBlockAndBlobs { .. } => {
+                | SyncId::BackFillBlockAndBlobs { .. }
+                | SyncId::BlobBackfill { .. } => {
                     crit!(self.log, "Batch syncing do not request BBRoot requests"; "peer_id" => %peer_id);
                     return;
                 }
@@ -599,7 +611,8 @@ impl<T: BeaconChainTypes> Router<T> {
                 SyncId::BackFillBlocks { .. }
                 | SyncId::RangeBlocks { .. }
                 | SyncId::RangeBlockAndBlobs { .. }
-                | SyncId::BackFillBlockAndBlobs { .. } => {
+                | SyncId::BackFillBlockAndBlobs { .. }
+                | SyncId::BlobBackfill { .. } => {
                     crit!(self.log, "Batch syncing does not request BBRoot requests"; "peer_id" => %peer_id);
                     return;
                 }
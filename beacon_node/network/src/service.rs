@@ -2,6 +2,7 @@ use super::sync::manager::RequestId as SyncId;
 use crate::nat::EstablishedUPnPMappings;
 use crate::network_beacon_processor::InvalidBlockStorage;
 use crate::persisted_dht::{clear_dht, load_dht, persist_dht};
+use crate::persisted_peers::{load_peers, persist_peers, PersistedPeers};
 use crate::router::{Router, RouterMessage};
 use crate::subnet_service::SyncCommitteeService;
 use crate::{error, metrics};
@@ -24,9 +25,11 @@ use lighthouse_network::{
 };
 use lighthouse_network::{
     types::{core_topics_to_subscribe, GossipEncoding, GossipTopic},
-    MessageId, NetworkEvent, NetworkGlobals, PeerId,
+    Enr, EnrExt, MessageId, Multiaddr, NetworkEvent, NetworkGlobals, PeerId,
 };
 use slog::{crit, debug, error, info, o, trace, warn};
+use std::net::IpAddr;
+use std::time::SystemTime;
 use std::{collections::HashSet, pin::Pin, sync::Arc, time::Duration};
 use store::HotColdDB;
 use strum::IntoStaticStr;
@@ -112,6 +115,31 @@ pub enum NetworkMessage<T: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Ban a peer on behalf of the node operator, until `expires` or permanently.
+    BanPeer {
+        peer_id: PeerId,
+        expires: Option<SystemTime>,
+    },
+    /// Lift a ban made by the node operator.
+    UnbanPeer { peer_id: PeerId },
+    /// Ban an IP address on behalf of the node operator, until `expires` or permanently.
+    BanIp {
+        ip: IpAddr,
+        expires: Option<SystemTime>,
+    },
+    /// Lift an IP address ban made by the node operator.
+    UnbanIp { ip: IpAddr },
+    /// Mark a peer as trusted, or no longer trusted.
+    SetTrustedPeer { peer_id: PeerId, trusted: bool },
+    /// Dial a peer by its ENR.
+    DialEnr { enr: Enr },
+    /// Dial a multiaddr.
+    DialMultiaddr { multiaddr: Multiaddr },
+    /// Disconnect a peer with a reason, without banning it.
+    DisconnectPeer {
+        peer_id: PeerId,
+        reason: GoodbyeReason,
+    },
 }
 
 /// Messages triggered by validators that may trigger a subscription to a subnet.
@@ -188,6 +216,9 @@ pub struct NetworkService<T: BeaconChainTypes> {
     store: Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
     /// A collection of global variables, accessible outside of the network service.
     network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+    /// The peers trusted by the node operator at runtime. Unlike the `--trusted-peers` flag these
+    /// are persisted, so that they remain trusted after a restart.
+    trusted_peers: HashSet<PeerId>,
     /// Stores potentially created UPnP mappings to be removed on shutdown. (TCP port and UDP
     /// ports).
     upnp_mappings: EstablishedUPnPMappings,
@@ -304,6 +335,23 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             }
         }
 
+        // Restore the peers banned or trusted by the node operator.
+        let persisted_peers = load_peers::<T::EthSpec, T::HotStore, T::ColdStore>(store.clone());
+        let now = SystemTime::now();
+        for (peer_id, expires) in persisted_peers.banned_peers {
+            if expires.map_or(true, |expires| expires > now) {
+                libp2p.ban_peer(&peer_id, expires);
+            }
+        }
+        for (ip, expires) in persisted_peers.banned_ips {
+            if expires.map_or(true, |expires| expires > now) {
+                libp2p.ban_ip(ip, expires);
+            }
+        }
+        for peer_id in &persisted_peers.trusted_peers {
+            libp2p.set_trusted_peer(peer_id, true);
+        }
+
         let invalid_block_storage = config
             .invalid_block_storage
             .clone()
@@ -358,6 +406,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             router_send,
             store,
             network_globals: network_globals.clone(),
+            trusted_peers: persisted_peers.trusted_peers.into_iter().collect(),
             upnp_mappings: EstablishedUPnPMappings::default(),
             next_fork_update,
             next_fork_subscriptions,
@@ -442,6 +491,33 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         }
     }
 
+    /// Persists the peers and IP addresses banned or trusted by the node operator, so that they are
+    /// restored after a restart.
+    fn persist_peers(&self) {
+        let persisted_peers = {
+            let peers = self.network_globals.peers.read();
+            PersistedPeers {
+                banned_peers: peers
+                    .manual_bans()
+                    .banned_peers()
+                    .map(|(peer_id, expires)| (*peer_id, *expires))
+                    .collect(),
+                banned_ips: peers
+                    .manual_bans()
+                    .banned_ips()
+                    .map(|(ip, expires)| (*ip, *expires))
+                    .collect(),
+                trusted_peers: self.trusted_peers.iter().copied().collect(),
+            }
+        };
+        if let Err(e) = persist_peers::<T::EthSpec, T::HotStore, T::ColdStore>(
+            self.store.clone(),
+            &persisted_peers,
+        ) {
+            error!(self.log, "Failed to persist banned and trusted peers"; "error" => ?e);
+        }
+    }
+
     fn spawn_service(mut self, executor: task_executor::TaskExecutor) {
         let mut shutdown_sender = executor.shutdown_sender();
 
@@ -693,6 +769,47 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 reason,
                 source,
             } => self.libp2p.goodbye_peer(&peer_id, reason, source),
+            NetworkMessage::BanPeer { peer_id, expires } => {
+                self.libp2p.ban_peer(&peer_id, expires);
+                self.persist_peers();
+            }
+            NetworkMessage::UnbanPeer { peer_id } => {
+                self.libp2p.unban_peer(&peer_id);
+                self.persist_peers();
+            }
+            NetworkMessage::BanIp { ip, expires } => {
+                self.libp2p.ban_ip(ip, expires);
+                self.persist_peers();
+            }
+            NetworkMessage::UnbanIp { ip } => {
+                self.libp2p.unban_ip(&ip);
+                self.persist_peers();
+            }
+            NetworkMessage::SetTrustedPeer { peer_id, trusted } => {
+                self.libp2p.set_trusted_peer(&peer_id, trusted);
+                if trusted {
+                    self.trusted_peers.insert(peer_id);
+                } else {
+                    self.trusted_peers.remove(&peer_id);
+                }
+                self.persist_peers();
+            }
+            NetworkMessage::DialEnr { enr } => {
+                let peer_id = enr.peer_id();
+                if !self.libp2p.dial_enr(enr) {
+                    debug!(self.log, "Not dialing peer"; "peer_id" => %peer_id);
+                }
+            }
+            NetworkMessage::DialMultiaddr { multiaddr } => {
+                if let Err(e) = self.libp2p.dial_multiaddr(multiaddr.clone()) {
+                    warn!(self.log, "Failed to dial multiaddr"; "multiaddr" => %multiaddr, "error" => %e);
+                }
+            }
+            NetworkMessage::DisconnectPeer { peer_id, reason } => {
+                if !self.libp2p.disconnect_peer(&peer_id, reason) {
+                    debug!(self.log, "Not disconnecting peer that is not connected"; "peer_id" => %peer_id);
+                }
+            }
             NetworkMessage::SubscribeCoreTopics => {
                 if self.subscribed_core_topics() {
                     return;
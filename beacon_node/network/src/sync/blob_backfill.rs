@@ -0,0 +1,336 @@
+//! This module contains the logic for downloading the blobs of historical blocks that were stored
+//! without them. This is the case for nodes that keep the blobs of all blocks since the Deneb fork,
+//! and for blocks imported from era files, which don't contain blobs.
+//!
+//! Peers are only required to serve blobs within the data availability boundary, so older blobs
+//! can only be obtained from the peers that archive them as well. There is no way to tell these
+//! peers apart, so batches are requested from random synced peers. Peers that fail to serve a batch
+//! are not asked again while they remain connected. Once no peer is left to ask, the sync pauses
+//! until a new synced peer joins.
+//!
+//! Blobs are downloaded one batch at a time, backwards from the oldest stored blob, down to the
+//! Deneb fork when archiving blobs and to the data availability boundary otherwise. They can only
+//! be verified against the commitments of stored blocks, so this sync only runs once the node is
+//! synced and the block backfill sync has completed.
+
+use crate::sync::manager::{BatchProcessResult, Id};
+use crate::sync::network_context::SyncNetworkContext;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use lighthouse_network::rpc::methods::BlobsByRangeRequest;
+use lighthouse_network::rpc::{RPCError, RPCResponseErrorCode};
+use lighthouse_network::types::{BackFillState, NetworkGlobals, SyncState};
+use lighthouse_network::{PeerAction, PeerId};
+use rand::seq::IteratorRandom;
+use slog::{debug, info, warn};
+use std::collections::HashSet;
+use std::sync::Arc;
+use types::{BlobSidecar, EthSpec, Slot};
+
+/// The number of epochs worth of blobs requested in a single batch.
+const BLOB_BACKFILL_EPOCHS_PER_BATCH: u64 = 1;
+
+/// The blobs of the blocks in `start_slot..end_slot`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BlobBatch {
+    start_slot: Slot,
+    end_slot: Slot,
+}
+
+enum BlobBackfillState<E: EthSpec> {
+    /// No batch is being downloaded or processed.
+    Idle,
+    /// The blobs of a batch are being downloaded from a peer.
+    Downloading {
+        request_id: Id,
+        peer_id: PeerId,
+        batch: BlobBatch,
+        blobs: Vec<Arc<BlobSidecar<E>>>,
+    },
+    /// The blobs of a batch are being imported.
+    Processing { peer_id: PeerId, batch: BlobBatch },
+    /// None of the connected peers could serve the next batch.
+    Paused,
+    /// The blobs of all blocks since the Deneb fork are stored.
+    Completed,
+}
+
+pub struct BlobBackfillSync<T: BeaconChainTypes> {
+    state: BlobBackfillState<T::EthSpec>,
+
+    /// Peers that failed to serve a batch. They are removed when they disconnect.
+    failed_peers: HashSet<PeerId>,
+
+    /// Reference to the beacon chain to obtain the stored blobs and blocks.
+    beacon_chain: Arc<BeaconChain<T>>,
+
+    /// Reference to the network globals in order to obtain valid peers and the sync state.
+    network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+
+    /// A logger for the blob backfill sync.
+    log: slog::Logger,
+}
+
+impl<T: BeaconChainTypes> BlobBackfillSync<T> {
+    pub fn new(
+        beacon_chain: Arc<BeaconChain<T>>,
+        network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+        log: slog::Logger,
+    ) -> Self {
+        BlobBackfillSync {
+            state: BlobBackfillState::Idle,
+            failed_peers: HashSet::new(),
+            beacon_chain,
+            network_globals,
+            log,
+        }
+    }
+
+    /// Requests the next batch if the node is synced and no batch is in progress.
+    pub fn start(&mut self, network: &mut SyncNetworkContext<T>) {
+        if !matches!(self.state, BlobBackfillState::Idle)
+            || !matches!(*self.network_globals.sync_state.read(), SyncState::Synced)
+            || !matches!(
+                *self.network_globals.backfill_state.read(),
+                BackFillState::Completed | BackFillState::NotRequired
+            )
+        {
+            return;
+        }
+
+        let batch = match self.next_batch() {
+            Some(batch) => batch,
+            None => return,
+        };
+
+        let peer_id = self
+            .network_globals
+            .peers
+            .read()
+            .synced_peers()
+            .filter(|peer_id| !self.failed_peers.contains(peer_id))
+            .choose(&mut rand::thread_rng())
+            .copied();
+        let peer_id = match peer_id {
+            Some(peer_id) => peer_id,
+            None => {
+                info!(
+                    self.log,
+                    "Blob backfill sync paused";
+                    "reason" => "no connected peer serves the blobs",
+                    "oldest_blob_slot" => batch.end_slot,
+                );
+                self.state = BlobBackfillState::Paused;
+                return;
+            }
+        };
+
+        let request = BlobsByRangeRequest {
+            start_slot: batch.start_slot.as_u64(),
+            count: (batch.end_slot - batch.start_slot).as_u64(),
+        };
+        match network.blob_backfill_request(peer_id, request) {
+            Ok(request_id) => {
+                self.state = BlobBackfillState::Downloading {
+                    request_id,
+                    peer_id,
+                    batch,
+                    blobs: vec![],
+                };
+            }
+            Err(e) => {
+                warn!(self.log, "Could not send blob backfill request"; "error" => e);
+            }
+        }
+    }
+
+    /// A fully synced peer has joined. If the sync was paused, it can now be resumed.
+    pub fn fully_synced_peer_joined(&mut self) {
+        if matches!(self.state, BlobBackfillState::Paused) {
+            self.state = BlobBackfillState::Idle;
+        }
+    }
+
+    /// A peer has disconnected. If it was serving the current batch, the batch is re-requested.
+    pub fn peer_disconnected(&mut self, peer_id: &PeerId, network: &mut SyncNetworkContext<T>) {
+        self.failed_peers.remove(peer_id);
+
+        if matches!(
+            &self.state,
+            BlobBackfillState::Downloading { peer_id: batch_peer, .. } if batch_peer == peer_id
+        ) {
+            self.state = BlobBackfillState::Idle;
+            self.start(network);
+        }
+    }
+
+    /// An RPC error has occurred for a blob backfill request.
+    pub fn inject_error(
+        &mut self,
+        network: &mut SyncNetworkContext<T>,
+        request_id: Id,
+        peer_id: PeerId,
+        error: &RPCError,
+    ) {
+        if matches!(
+            &self.state,
+            BlobBackfillState::Downloading { request_id: batch_request_id, .. }
+                if *batch_request_id == request_id
+        ) {
+            debug!(
+                self.log,
+                "Blob backfill batch download failed";
+                "peer" => %peer_id,
+                "error" => %error,
+            );
+            // Peers only need to serve blobs within the data availability boundary, and blob
+            // backfill requests older blobs to find the peers which still have them. These peers
+            // are likely useful for everything else, so they aren't banned.
+            if matches!(
+                error,
+                RPCError::ErrorResponse(RPCResponseErrorCode::ResourceUnavailable, _)
+            ) {
+                network.report_peer(
+                    peer_id,
+                    PeerAction::LowToleranceError,
+                    "blob_backfill_blobs_unavailable",
+                );
+            }
+            self.failed_peers.insert(peer_id);
+            self.state = BlobBackfillState::Idle;
+            self.start(network);
+        }
+    }
+
+    /// A blob, or the end of the stream of blobs, has been received for a blob backfill request.
+    pub fn on_blob_response(
+        &mut self,
+        network: &mut SyncNetworkContext<T>,
+        request_id: Id,
+        blob: Option<Arc<BlobSidecar<T::EthSpec>>>,
+    ) {
+        match (&mut self.state, blob) {
+            (
+                BlobBackfillState::Downloading {
+                    request_id: batch_request_id,
+                    blobs,
+                    ..
+                },
+                Some(blob),
+            ) if *batch_request_id == request_id => {
+                blobs.push(blob);
+                return;
+            }
+            (
+                BlobBackfillState::Downloading {
+                    request_id: batch_request_id,
+                    ..
+                },
+                None,
+            ) if *batch_request_id == request_id => {}
+            _ => {
+                debug!(self.log, "Received blobs for an unknown blob backfill request"; "request_id" => request_id);
+                return;
+            }
+        }
+
+        // The stream has terminated, import the blobs of the batch.
+        if let BlobBackfillState::Downloading {
+            peer_id,
+            batch,
+            blobs,
+            ..
+        } = std::mem::replace(&mut self.state, BlobBackfillState::Idle)
+        {
+            match network.beacon_processor().send_historical_blobs(
+                batch.start_slot,
+                batch.end_slot,
+                blobs,
+            ) {
+                Ok(()) => self.state = BlobBackfillState::Processing { peer_id, batch },
+                Err(e) => {
+                    // The batch is requested again the next time the sync is started.
+                    warn!(self.log, "Could not send blob backfill batch for processing"; "error" => %e);
+                }
+            }
+        }
+    }
+
+    /// The beacon processor has finished importing the blobs of the batch starting at
+    /// `start_slot`.
+    pub fn on_batch_process_result(
+        &mut self,
+        network: &mut SyncNetworkContext<T>,
+        start_slot: Slot,
+        result: &BatchProcessResult,
+    ) {
+        let peer_id = match &self.state {
+            BlobBackfillState::Processing { peer_id, batch } if batch.start_slot == start_slot => {
+                *peer_id
+            }
+            _ => {
+                debug!(self.log, "Unexpected blob backfill batch processing result"; "start_slot" => start_slot);
+                return;
+            }
+        };
+        self.state = BlobBackfillState::Idle;
+
+        match result {
+            BatchProcessResult::Success { .. } => {}
+            BatchProcessResult::FaultyFailure { penalty, .. } => {
+                network.report_peer(peer_id, *penalty, "faulty_blob_backfill_batch");
+                self.failed_peers.insert(peer_id);
+            }
+            BatchProcessResult::NonFaultyFailure => {
+                // Most likely the peer doesn't have the blobs this far back.
+                self.failed_peers.insert(peer_id);
+            }
+        }
+
+        self.start(network);
+    }
+
+    /// Returns the next batch of blobs to download, or `None` if no blobs can be downloaded yet.
+    fn next_batch(&mut self) -> Option<BlobBatch> {
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
+        let oldest_blob_slot = self.beacon_chain.store.get_blob_info().oldest_blob_slot?;
+        let deneb_slot = self
+            .beacon_chain
+            .spec
+            .deneb_fork_epoch?
+            .start_slot(slots_per_epoch);
+
+        if oldest_blob_slot <= deneb_slot {
+            info!(self.log, "Blob backfill sync completed"; "oldest_blob_slot" => oldest_blob_slot);
+            self.state = BlobBackfillState::Completed;
+            return None;
+        }
+
+        // Blobs can only be verified against the blocks that are already stored. Unless blobs are
+        // archived, blobs beyond the data availability boundary would be pruned right away.
+        let oldest_block_slot = self
+            .beacon_chain
+            .store
+            .get_anchor_info()
+            .map_or(deneb_slot, |anchor| anchor.oldest_block_slot);
+        let mut lowest_slot = std::cmp::max(deneb_slot, oldest_block_slot);
+        if !self.beacon_chain.config.archive_blobs {
+            let boundary_slot = self
+                .beacon_chain
+                .data_availability_boundary()?
+                .start_slot(slots_per_epoch);
+            lowest_slot = std::cmp::max(lowest_slot, boundary_slot);
+        }
+        if oldest_blob_slot <= lowest_slot {
+            return None;
+        }
+
+        let start_slot = std::cmp::max(
+            oldest_blob_slot.saturating_sub(BLOB_BACKFILL_EPOCHS_PER_BATCH * slots_per_epoch),
+            lowest_slot,
+        );
+        Some(BlobBatch {
+            start_slot,
+            end_slot: oldest_blob_slot,
+        })
+    }
+}
@@ -34,6 +34,7 @@
 //! search for the block and subsequently search for parents if needed.
 
 use super::backfill_sync::{BackFillSync, ProcessResult, SyncStart};
+use super::blob_backfill::BlobBackfillSync;
 use super::block_lookups::BlockLookups;
 use super::network_context::{BlockOrBlob, SyncNetworkContext};
 use super::peer_sync_info::{remote_sync_type, PeerSyncType};
@@ -52,7 +53,7 @@ use beacon_chain::{
     AvailabilityProcessingStatus, BeaconChain, BeaconChainTypes, BlockError, EngineState,
 };
 use futures::StreamExt;
-use lighthouse_network::rpc::RPCError;
+use lighthouse_network::rpc::{RPCError, RPCResponseErrorCode};
 use lighthouse_network::types::{NetworkGlobals, SyncState};
 use lighthouse_network::SyncInfo;
 use lighthouse_network::{PeerAction, PeerId};
@@ -104,6 +105,8 @@ pub enum RequestId {
     RangeBlocks { id: Id },
     /// Range request that is composed by both a block range request and a blob range request.
     RangeBlockAndBlobs { id: Id },
+    /// Request for the blobs of historical blocks from the blob backfill sync algorithm.
+    BlobBackfill { id: Id },
 }
 
 #[derive(Debug)]
@@ -154,6 +157,12 @@ pub enum SyncMessage<T: EthSpec> {
         result: BatchProcessResult,
     },
 
+    /// A batch of historical blobs has been processed by the block processor thread.
+    BlobBackfillBatchProcessed {
+        start_slot: Slot,
+        result: BatchProcessResult,
+    },
+
     /// Block processed
     BlockComponentProcessed {
         process_type: BlockProcessType,
@@ -211,6 +220,9 @@ pub struct SyncManager<T: BeaconChainTypes> {
     /// Backfill syncing.
     backfill_sync: BackFillSync<T>,
 
+    /// Backfill syncing of the blobs outside the data availability boundary.
+    blob_backfill_sync: BlobBackfillSync<T>,
+
     block_lookups: BlockLookups<T>,
 
     /// The logger for the import manager.
@@ -245,7 +257,16 @@ pub fn spawn<T: BeaconChainTypes>(
             log.clone(),
         ),
         range_sync: RangeSync::new(beacon_chain.clone(), log.clone()),
-        backfill_sync: BackFillSync::new(beacon_chain.clone(), network_globals, log.clone()),
+        backfill_sync: BackFillSync::new(
+            beacon_chain.clone(),
+            network_globals.clone(),
+            log.clone(),
+        ),
+        blob_backfill_sync: BlobBackfillSync::new(
+            beacon_chain.clone(),
+            network_globals,
+            log.clone(),
+        ),
         block_lookups: BlockLookups::new(
             beacon_chain.data_availability_checker.clone(),
             log.clone(),
@@ -353,6 +374,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             }
 
             RequestId::BackFillBlockAndBlobs { id } => {
+                self.penalize_unavailable_blobs(peer_id, &error);
                 if let Some(batch_id) = self
                     .network
                     .backfill_request_failed(id, ByRangeRequestType::BlocksAndBlobs)
@@ -382,6 +404,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 }
             }
             RequestId::RangeBlockAndBlobs { id } => {
+                self.penalize_unavailable_blobs(peer_id, &error);
                 if let Some((chain_id, batch_id)) = self
                     .network
                     .range_sync_request_failed(id, ByRangeRequestType::BlocksAndBlobs)
@@ -396,6 +419,25 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                     self.update_sync_state()
                 }
             }
+            RequestId::BlobBackfill { id } => {
+                self.blob_backfill_sync
+                    .inject_error(&mut self.network, id, peer_id, &error)
+            }
+        }
+    }
+
+    /// Ban a peer which has refused to serve the blobs of a range or backfill sync request.
+    ///
+    /// The peer manager leaves the penalty for unavailable blobs to the requester. The blobs
+    /// requested by range and backfill sync are within the data availability boundary, so the
+    /// peer is handled as if it had refused to serve blocks.
+    fn penalize_unavailable_blobs(&self, peer_id: PeerId, error: &RPCError) {
+        if matches!(
+            error,
+            RPCError::ErrorResponse(RPCResponseErrorCode::ResourceUnavailable, _)
+        ) {
+            self.network
+                .report_peer(peer_id, PeerAction::Fatal, "blobs_by_range_unavailable");
         }
     }
 
@@ -407,6 +449,8 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         let _ = self
             .backfill_sync
             .peer_disconnected(peer_id, &mut self.network);
+        self.blob_backfill_sync
+            .peer_disconnected(peer_id, &mut self.network);
         self.update_sync_state();
     }
 
@@ -450,6 +494,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 // inform the backfill sync that a new synced peer has joined us.
                 if new_state.is_synced() {
                     self.backfill_sync.fully_synced_peer_joined();
+                    self.blob_backfill_sync.fully_synced_peer_joined();
                 }
             }
             is_connected
@@ -569,6 +614,12 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 self.network.subscribe_core_topics();
             }
         }
+
+        // Download the blobs outside the data availability boundary once everything else is
+        // synced.
+        if matches!(new_state, SyncState::Synced) {
+            self.blob_backfill_sync.start(&mut self.network);
+        }
     }
 
     /// The main driving future for the sync manager.
@@ -713,6 +764,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                     .block_lookups
                     .parent_chain_processed(chain_hash, result, &self.network),
             },
+            SyncMessage::BlobBackfillBatchProcessed { start_slot, result } => self
+                .blob_backfill_sync
+                .on_batch_process_result(&mut self.network, start_slot, &result),
         }
     }
 
@@ -902,6 +956,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             RequestId::RangeBlockAndBlobs { id } => {
                 self.range_block_and_blobs_response(id, peer_id, block.into())
             }
+            RequestId::BlobBackfill { .. } => {
+                crit!(self.log, "Block received during blob backfill request"; "peer_id" => %peer_id  );
+            }
         }
     }
 
@@ -957,6 +1014,10 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             RequestId::BackFillBlocks { id: _ } => {
                 crit!(self.log, "Blob received during backfill block request"; "peer_id" => %peer_id  );
             }
+            RequestId::BlobBackfill { id } => {
+                self.blob_backfill_sync
+                    .on_blob_response(&mut self.network, id, blob)
+            }
             RequestId::RangeBlocks { id: _ } => {
                 crit!(self.log, "Blob received during range block request"; "peer_id" => %peer_id  );
             }
@@ -3,7 +3,7 @@
 
 use super::block_sidecar_coupling::BlocksAndBlobsRequestInfo;
 use super::manager::{Id, RequestId as SyncRequestId};
-use super::range_sync::{BatchId, ByRangeRequestType, ChainId};
+use super::range_sync::{BatchId, ByRangeRequestType, ChainId, PeerThroughput};
 use crate::network_beacon_processor::NetworkBeaconProcessor;
 use crate::service::{NetworkMessage, RequestId};
 use crate::status::ToStatusMessage;
@@ -53,6 +53,9 @@ pub struct SyncNetworkContext<T: BeaconChainTypes> {
     backfill_blocks_and_blobs_requests:
         FnvHashMap<Id, (BatchId, BlocksAndBlobsRequestInfo<T::EthSpec>)>,
 
+    /// How fast peers have served the requests made by the range syncing algorithm.
+    range_peer_throughput: PeerThroughput,
+
     /// Whether the ee is online. If it's not, we don't allow access to the
     /// `beacon_processor_send`.
     execution_engine_state: EngineState,
@@ -99,6 +102,7 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             backfill_requests: FnvHashMap::default(),
             range_blocks_and_blobs_requests: FnvHashMap::default(),
             backfill_blocks_and_blobs_requests: FnvHashMap::default(),
+            range_peer_throughput: PeerThroughput::default(),
             network_beacon_processor,
             chain,
             log,
@@ -109,6 +113,14 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         &self.network_beacon_processor.network_globals
     }
 
+    pub fn range_peer_throughput(&self) -> &PeerThroughput {
+        &self.range_peer_throughput
+    }
+
+    pub fn range_peer_throughput_mut(&mut self) -> &mut PeerThroughput {
+        &mut self.range_peer_throughput
+    }
+
     /// Returns the Client type of the peer if known
     pub fn client_type(&self, peer_id: &PeerId) -> Client {
         self.network_globals()
@@ -284,6 +296,30 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         }
     }
 
+    /// A blobs by range request sent by the blob backfill sync algorithm.
+    pub fn blob_backfill_request(
+        &mut self,
+        peer_id: PeerId,
+        request: BlobsByRangeRequest,
+    ) -> Result<Id, &'static str> {
+        debug!(
+            self.log,
+            "Sending blob backfill BlobsByRange request";
+            "method" => "BlobsByRange",
+            "start_slot" => request.start_slot,
+            "count" => request.count,
+            "peer" => %peer_id,
+        );
+        let id = self.next_id();
+        let request_id = RequestId::Sync(SyncRequestId::BlobBackfill { id });
+        self.send_network_msg(NetworkMessage::SendRequest {
+            peer_id,
+            request: Request::BlobsByRange(request),
+            request_id,
+        })?;
+        Ok(id)
+    }
+
     /// Response for a request that is only for blocks.
     pub fn range_sync_block_only_response(
         &mut self,
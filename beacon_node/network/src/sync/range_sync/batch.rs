@@ -1,10 +1,13 @@
+use super::peer_throughput::ResponseSize;
 use crate::sync::manager::Id;
 use beacon_chain::block_verification_types::{AsBlock, RpcBlock};
 use lighthouse_network::rpc::methods::BlocksByRangeRequest;
 use lighthouse_network::PeerId;
+use ssz::Encode;
 use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 use std::ops::Sub;
+use std::time::{Duration, Instant};
 use strum::Display;
 use types::{Epoch, EthSpec, Slot};
 
@@ -16,7 +19,7 @@ const MAX_BATCH_DOWNLOAD_ATTEMPTS: u8 = 5;
 const MAX_BATCH_PROCESSING_ATTEMPTS: u8 = 3;
 
 /// Type of expected batch.
-#[derive(Debug, Copy, Clone, Display)]
+#[derive(Debug, Copy, Clone, PartialEq, Display)]
 #[strum(serialize_all = "snake_case")]
 pub enum ByRangeRequestType {
     BlocksAndBlobs,
@@ -91,6 +94,32 @@ pub enum BatchProcessingResult {
     NonFaultyFailure,
 }
 
+/// Measures a batch's share of the time a peer spends serving requests, so that the throughput of
+/// peers serving several batches concurrently is not underestimated.
+struct DownloadTimer {
+    /// When `concurrent_requests` last changed.
+    last_update: Instant,
+    /// The number of requests the peer has been serving since `last_update`.
+    concurrent_requests: usize,
+    /// The batch's share of the time before `last_update`.
+    share: Duration,
+}
+
+impl DownloadTimer {
+    fn new() -> Self {
+        DownloadTimer {
+            last_update: Instant::now(),
+            concurrent_requests: 1,
+            share: Duration::ZERO,
+        }
+    }
+
+    fn share(&self, now: Instant) -> Duration {
+        self.share
+            + now.saturating_duration_since(self.last_update) / self.concurrent_requests as u32
+    }
+}
+
 /// A segment of a chain.
 pub struct BatchInfo<T: EthSpec, B: BatchConfig = RangeSyncBatchConfig> {
     /// Start slot of the batch.
@@ -105,6 +134,8 @@ pub struct BatchInfo<T: EthSpec, B: BatchConfig = RangeSyncBatchConfig> {
     failed_download_attempts: Vec<PeerId>,
     /// State of the batch.
     state: BatchState<T>,
+    /// Measures the time spent downloading the batch while it is downloading.
+    download_timer: Option<DownloadTimer>,
     /// Whether this batch contains all blocks or all blocks and blobs.
     batch_type: ByRangeRequestType,
     /// Pin the generic
@@ -165,6 +196,7 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
             failed_download_attempts: Vec::new(),
             non_faulty_processing_attempts: 0,
             state: BatchState::AwaitingDownload,
+            download_timer: None,
             batch_type,
             marker: std::marker::PhantomData,
         }
@@ -217,6 +249,40 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
         }
     }
 
+    /// Returns the batch's share of the time the peer has spent serving requests since the current
+    /// download was requested, and the SSZ size of the blocks and blobs received so far. Returns
+    /// `None` if the batch is not downloading.
+    pub fn download_progress(&self) -> Option<(Duration, ResponseSize)> {
+        let BatchState::Downloading(_, blocks, _) = &self.state else {
+            return None;
+        };
+        let elapsed = self.download_timer.as_ref()?.share(Instant::now());
+        let mut size = ResponseSize::default();
+        for block in blocks {
+            size.block_bytes += block.as_block().ssz_bytes_len();
+            if let Some(blobs) = block.blobs() {
+                size.blob_bytes += blobs.iter().map(|blob| blob.ssz_bytes_len()).sum::<usize>();
+            }
+        }
+        Some((elapsed, size))
+    }
+
+    /// Sets the number of requests the peer downloading this batch is serving concurrently,
+    /// including this batch. The time the peer spends serving the requests is shared evenly
+    /// between them.
+    pub fn set_concurrent_requests(&mut self, concurrent_requests: usize) {
+        if let Some(timer) = self.download_timer.as_mut() {
+            let now = Instant::now();
+            timer.share = timer.share(now);
+            timer.last_update = now;
+            timer.concurrent_requests = concurrent_requests.max(1);
+        }
+    }
+
+    pub fn batch_type(&self) -> ByRangeRequestType {
+        self.batch_type
+    }
+
     /// Returns a BlocksByRange request associated with the batch.
     pub fn to_blocks_by_range_request(&self) -> (BlocksByRangeRequest, ByRangeRequestType) {
         (
@@ -370,6 +436,7 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
         match self.state.poison() {
             BatchState::AwaitingDownload => {
                 self.state = BatchState::Downloading(peer, Vec::new(), request_id);
+                self.download_timer = Some(DownloadTimer::new());
                 Ok(())
             }
             BatchState::Poisoned => unreachable!("Poisoned batch"),
@@ -9,6 +9,7 @@ use fnv::FnvHashMap;
 use lighthouse_network::{PeerAction, PeerId};
 use rand::seq::SliceRandom;
 use slog::{crit, debug, o, warn};
+use std::cmp::Reverse;
 use std::collections::{btree_map::Entry, BTreeMap, HashSet};
 use std::hash::{Hash, Hasher};
 use types::{Epoch, EthSpec, Hash256, Slot};
@@ -24,6 +25,11 @@ pub const EPOCHS_PER_BATCH: u64 = 1;
 /// The maximum number of batches to queue before requesting more.
 const BATCH_BUFFER_SIZE: u8 = 5;
 
+/// The maximum number of batches to queue when peers are able to serve more than one batch at a
+/// time. Each additional concurrent batch a peer can serve extends the buffer by one, up to this
+/// limit.
+const MAX_BATCH_BUFFER_SIZE: usize = 16;
+
 /// A return type for functions that act on a `Chain` which informs the caller whether the chain
 /// has been completed and should be removed or to be kept if further processing is
 /// required.
@@ -253,8 +259,17 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                 .get_mut(peer_id)
                 .map(|active_requests| active_requests.remove(&batch_id));
 
+            let batch_type = batch.batch_type();
+            let download_progress = batch.download_progress();
             match batch.download_completed() {
                 Ok(received) => {
+                    self.update_concurrent_requests(peer_id);
+                    if let Some((elapsed, size)) = download_progress {
+                        network
+                            .range_peer_throughput_mut()
+                            .on_request_completed(*peer_id, batch_type, size, elapsed);
+                    }
+
                     let awaiting_batches = batch_id
                         .saturating_sub(self.optimistic_start.unwrap_or(self.processing_target))
                         / EPOCHS_PER_BATCH;
@@ -268,6 +283,10 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                     let (expected_boundary, received_boundary, outcome) = result?;
                     warn!(self.log, "Batch received out of range blocks"; "expected_boundary" => expected_boundary, "received_boundary" => received_boundary,
                         "peer_id" => %peer_id, batch);
+                    self.update_concurrent_requests(peer_id);
+                    network
+                        .range_peer_throughput_mut()
+                        .on_request_failed(*peer_id, batch_type);
 
                     if let BatchOperationOutcome::Failed { blacklist } = outcome {
                         return Err(RemoveChain::ChainFailed {
@@ -844,12 +863,16 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             if let Some(active_requests) = self.peers.get_mut(peer_id) {
                 active_requests.remove(&batch_id);
             }
+            network
+                .range_peer_throughput_mut()
+                .on_request_failed(*peer_id, batch.batch_type());
             if let BatchOperationOutcome::Failed { blacklist } = batch.download_failed(true)? {
                 return Err(RemoveChain::ChainFailed {
                     blacklist,
                     failing_batch: batch_id,
                 });
             }
+            self.update_concurrent_requests(peer_id);
             self.retry_batch_download(network, batch_id)
         } else {
             debug!(
@@ -878,14 +901,22 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
         let failed_peers = batch.failed_peers();
 
         let new_peer = {
+            let throughput = network.range_peer_throughput();
             let mut priorized_peers = self
                 .peers
                 .iter()
-                .map(|(peer, requests)| (failed_peers.contains(peer), requests.len(), *peer))
+                .map(|(peer, requests)| {
+                    (
+                        failed_peers.contains(peer),
+                        requests.len(),
+                        Reverse(throughput.score(peer)),
+                        *peer,
+                    )
+                })
                 .collect::<Vec<_>>();
-            // Sort peers prioritizing unrelated peers with less active requests.
+            // Sort peers prioritizing unrelated peers with less active requests, then the fastest.
             priorized_peers.sort_unstable();
-            priorized_peers.first().map(|&(_, _, peer)| peer)
+            priorized_peers.first().map(|&(_, _, _, peer)| peer)
         };
 
         if let Some(peer) = new_peer {
@@ -919,19 +950,15 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                         debug!(self.log, "Requesting batch"; "epoch" => batch_id, &batch);
                     }
                     // register the batch for this peer
-                    return self
-                        .peers
-                        .get_mut(&peer)
-                        .map(|requests| {
-                            requests.insert(batch_id);
-                            Ok(KeepChain)
-                        })
-                        .unwrap_or_else(|| {
-                            Err(RemoveChain::WrongChainState(format!(
-                                "Sending batch to a peer that is not in the chain: {}",
-                                peer
-                            )))
-                        });
+                    let Some(requests) = self.peers.get_mut(&peer) else {
+                        return Err(RemoveChain::WrongChainState(format!(
+                            "Sending batch to a peer that is not in the chain: {}",
+                            peer
+                        )));
+                    };
+                    requests.insert(batch_id);
+                    self.update_concurrent_requests(&peer);
+                    return Ok(KeepChain);
                 }
                 Err(e) => {
                     // NOTE: under normal conditions this shouldn't happen but we handle it anyway
@@ -960,6 +987,20 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
         Ok(KeepChain)
     }
 
+    /// Informs the batches downloading from `peer_id` of the number of batches the peer is serving
+    /// on this chain, so that their download times reflect the peer's throughput across all of
+    /// them.
+    fn update_concurrent_requests(&mut self, peer_id: &PeerId) {
+        let Some(requests) = self.peers.get(peer_id) else {
+            return;
+        };
+        for batch_id in requests {
+            if let Some(batch) = self.batches.get_mut(batch_id) {
+                batch.set_concurrent_requests(requests.len());
+            }
+        }
+    }
+
     /// Returns true if this chain is currently syncing.
     pub fn is_syncing(&self) -> bool {
         match self.state {
@@ -982,6 +1023,9 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
 
     /// Attempts to request the next required batches from the peer pool if the chain is syncing. It will exhaust the peer
     /// pool and left over batches until the batch buffer is reached or all peers are exhausted.
+    ///
+    /// Peers that have served batches faster than the other peers of the chain are allowed to
+    /// download more than one batch at a time, and are given batches before slower peers.
     fn request_batches(&mut self, network: &mut SyncNetworkContext<T>) -> ProcessingResult {
         if !matches!(self.state, ChainSyncingState::Syncing) {
             return Ok(KeepChain);
@@ -989,26 +1033,35 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
 
         // find the next pending batch and request it from the peer
 
-        // randomize the peers for load balancing
-        let mut rng = rand::thread_rng();
-        let mut idle_peers = self
+        let throughput = network.range_peer_throughput();
+        let capacities = throughput.batch_capacities(self.peers.keys());
+        let mut available_peers = self
             .peers
             .iter()
             .filter_map(|(peer, requests)| {
-                if requests.is_empty() {
-                    Some(*peer)
+                let free_slots = capacities
+                    .get(peer)
+                    .copied()
+                    .unwrap_or(1)
+                    .saturating_sub(requests.len());
+                if free_slots > 0 {
+                    Some((*peer, free_slots))
                 } else {
                     None
                 }
             })
             .collect::<Vec<_>>();
-        idle_peers.shuffle(&mut rng);
+        // randomize the peers for load balancing, then prefer the fastest peers. The sort is
+        // stable so peers with the same throughput remain shuffled.
+        let mut rng = rand::thread_rng();
+        available_peers.shuffle(&mut rng);
+        available_peers.sort_by_key(|(peer, _)| Reverse(throughput.score(peer)));
 
         // check if we have the batch for our optimistic start. If not, request it first.
         // We wait for this batch before requesting any other batches.
         if let Some(epoch) = self.optimistic_start {
             if let Entry::Vacant(entry) = self.batches.entry(epoch) {
-                if let Some(peer) = idle_peers.pop() {
+                if let Some(&(peer, _)) = available_peers.first() {
                     let batch_type = network.batch_type(epoch);
                     let optimistic_batch = BatchInfo::new(&epoch, EPOCHS_PER_BATCH, batch_type);
                     entry.insert(optimistic_batch);
@@ -1018,14 +1071,29 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             return Ok(KeepChain);
         }
 
-        while let Some(peer) = idle_peers.pop() {
-            if let Some(batch_id) = self.include_next_batch(network) {
-                // send the batch
-                self.send_batch(network, batch_id, peer)?;
-            } else {
-                // No more batches, simply stop
-                return Ok(KeepChain);
+        // Every extra batch a peer can download concurrently lets the chain download one more
+        // batch ahead of the processing target.
+        let buffer_size = capacities
+            .values()
+            .map(|capacity| capacity.saturating_sub(1))
+            .sum::<usize>()
+            .saturating_add(BATCH_BUFFER_SIZE as usize)
+            .min(MAX_BATCH_BUFFER_SIZE);
+
+        // Hand out one batch per peer in each round so the fastest peers get the first batches,
+        // and peers with spare capacity get more batches without starving the others.
+        while !available_peers.is_empty() {
+            for (peer, free_slots) in available_peers.iter_mut() {
+                if let Some(batch_id) = self.include_next_batch(network, buffer_size) {
+                    // send the batch
+                    self.send_batch(network, batch_id, *peer)?;
+                    *free_slots -= 1;
+                } else {
+                    // No more batches, simply stop
+                    return Ok(KeepChain);
+                }
             }
+            available_peers.retain(|(_, free_slots)| *free_slots > 0);
         }
 
         Ok(KeepChain)
@@ -1033,7 +1101,11 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
 
     /// Creates the next required batch from the chain. If there are no more batches required,
     /// `false` is returned.
-    fn include_next_batch(&mut self, network: &mut SyncNetworkContext<T>) -> Option<BatchId> {
+    fn include_next_batch(
+        &mut self,
+        network: &mut SyncNetworkContext<T>,
+        buffer_size: usize,
+    ) -> Option<BatchId> {
         // don't request batches beyond the target head slot
         if self
             .to_be_downloaded
@@ -1056,7 +1128,7 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             .iter()
             .filter(|&(_epoch, batch)| in_buffer(batch))
             .count()
-            > BATCH_BUFFER_SIZE as usize
+            > buffer_size
         {
             return None;
         }
@@ -1067,7 +1139,7 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             Entry::Occupied(_) => {
                 // this batch doesn't need downloading, let this same function decide the next batch
                 self.to_be_downloaded += EPOCHS_PER_BATCH;
-                self.include_next_batch(network)
+                self.include_next_batch(network, buffer_size)
             }
             Entry::Vacant(entry) => {
                 let batch_type = network.batch_type(batch_id);
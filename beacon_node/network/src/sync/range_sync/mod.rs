@@ -5,6 +5,7 @@ mod batch;
 mod block_storage;
 mod chain;
 mod chain_collection;
+mod peer_throughput;
 mod range;
 mod sync_type;
 
@@ -13,5 +14,6 @@ pub use batch::{
     ByRangeRequestType,
 };
 pub use chain::{BatchId, ChainId, EPOCHS_PER_BATCH};
+pub use peer_throughput::PeerThroughput;
 pub use range::RangeSync;
 pub use sync_type::RangeSyncType;
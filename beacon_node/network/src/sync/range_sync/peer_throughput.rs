@@ -0,0 +1,300 @@
+//! Tracks how fast peers serve `BlocksByRange` and `BlobsByRange` requests.
+//!
+//! Range sync uses these measurements to download more batches concurrently from peers that serve
+//! them quickly and reliably, and to prefer those peers when a batch needs to be re-requested.
+
+use super::batch::ByRangeRequestType;
+use crate::metrics;
+use fnv::FnvHashMap;
+use lighthouse_network::PeerId;
+use std::time::Duration;
+
+/// Weight given to the latest sample when updating the moving averages of a peer.
+const EWMA_WEIGHT: f64 = 0.3;
+
+/// The maximum number of batches that can be downloaded concurrently from a single peer.
+const MAX_BATCHES_PER_PEER: usize = 3;
+
+/// Peers with an error rate above this value are only ever assigned a single batch at a time.
+const MAX_ERROR_RATE_FOR_EXTRA_BATCHES: f64 = 0.25;
+
+/// Responses smaller than this are not used to measure throughput, since their download time is
+/// dominated by latency rather than bandwidth. They still count towards the error rate.
+const MIN_BYTES_FOR_THROUGHPUT_SAMPLE: usize = 16 * 1024;
+
+/// Exponentially weighted moving averages of a peer's performance for a single protocol.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ProtocolStats {
+    /// The observed throughput in bytes per second, `None` until a sample has been taken.
+    bytes_per_sec: Option<f64>,
+    /// The fraction of requests that have failed.
+    error_rate: f64,
+}
+
+impl ProtocolStats {
+    fn on_success(&mut self, bytes: usize, elapsed: Duration) {
+        self.error_rate = ewma(self.error_rate, 0.0);
+        if bytes >= MIN_BYTES_FOR_THROUGHPUT_SAMPLE {
+            // Guard against a zero duration when the clock is coarse.
+            let secs = elapsed.as_secs_f64().max(0.001);
+            let sample = bytes as f64 / secs;
+            self.bytes_per_sec = Some(
+                self.bytes_per_sec
+                    .map_or(sample, |average| ewma(average, sample)),
+            );
+        }
+    }
+
+    fn on_error(&mut self) {
+        self.error_rate = ewma(self.error_rate, 1.0);
+    }
+}
+
+/// The performance of a peer serving by range requests.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PeerStats {
+    pub blocks_by_range: ProtocolStats,
+    pub blobs_by_range: ProtocolStats,
+}
+
+impl PeerStats {
+    /// The bytes per second this peer is expected to deliver, discounted by its error rate.
+    ///
+    /// Returns `None` if the peer hasn't delivered enough data to be measured.
+    pub fn effective_throughput(&self) -> Option<f64> {
+        let bytes_per_sec = match (
+            self.blocks_by_range.bytes_per_sec,
+            self.blobs_by_range.bytes_per_sec,
+        ) {
+            (None, None) => return None,
+            (blocks, blobs) => blocks.unwrap_or(0.0) + blobs.unwrap_or(0.0),
+        };
+        Some(bytes_per_sec * (1.0 - self.error_rate()))
+    }
+
+    /// The highest error rate of the protocols served by this peer.
+    pub fn error_rate(&self) -> f64 {
+        self.blocks_by_range
+            .error_rate
+            .max(self.blobs_by_range.error_rate)
+    }
+}
+
+/// Bytes received for a by range request.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ResponseSize {
+    pub block_bytes: usize,
+    pub blob_bytes: usize,
+}
+
+/// Keeps the `PeerStats` of the peers serving range sync requests.
+#[derive(Default)]
+pub struct PeerThroughput {
+    peers: FnvHashMap<PeerId, PeerStats>,
+}
+
+impl PeerThroughput {
+    /// Registers a by range request of `batch_type` that was fully served by `peer_id`.
+    ///
+    /// `elapsed` is the request's share of the time the peer spent serving requests, so that peers
+    /// serving several requests concurrently are measured by their combined throughput.
+    pub fn on_request_completed(
+        &mut self,
+        peer_id: PeerId,
+        batch_type: ByRangeRequestType,
+        size: ResponseSize,
+        elapsed: Duration,
+    ) {
+        let stats = self.peers.entry(peer_id).or_default();
+        stats.blocks_by_range.on_success(size.block_bytes, elapsed);
+        metrics::inc_counter_vec_by(
+            &metrics::SYNC_RANGE_RESPONSE_BYTES,
+            &["blocks_by_range"],
+            size.block_bytes as u64,
+        );
+        if batch_type == ByRangeRequestType::BlocksAndBlobs {
+            stats.blobs_by_range.on_success(size.blob_bytes, elapsed);
+            metrics::inc_counter_vec_by(
+                &metrics::SYNC_RANGE_RESPONSE_BYTES,
+                &["blobs_by_range"],
+                size.blob_bytes as u64,
+            );
+        }
+        if let Some(throughput) = stats.effective_throughput() {
+            metrics::observe(&metrics::SYNC_RANGE_PEER_THROUGHPUT, throughput);
+        }
+    }
+
+    /// Registers a by range request of `batch_type` that `peer_id` failed to serve.
+    ///
+    /// Errors of requests for blocks and blobs can't be attributed to either protocol, so they are
+    /// registered for both.
+    pub fn on_request_failed(&mut self, peer_id: PeerId, batch_type: ByRangeRequestType) {
+        let stats = self.peers.entry(peer_id).or_default();
+        stats.blocks_by_range.on_error();
+        metrics::inc_counter_vec(&metrics::SYNC_RANGE_REQUEST_ERRORS, &["blocks_by_range"]);
+        if batch_type == ByRangeRequestType::BlocksAndBlobs {
+            stats.blobs_by_range.on_error();
+            metrics::inc_counter_vec(&metrics::SYNC_RANGE_REQUEST_ERRORS, &["blobs_by_range"]);
+        }
+    }
+
+    /// Forgets everything known about a peer.
+    pub fn remove_peer(&mut self, peer_id: &PeerId) {
+        self.peers.remove(peer_id);
+    }
+
+    /// Returns the effective throughput of a peer, in bytes per second, or zero if it hasn't been
+    /// measured yet.
+    pub fn score(&self, peer_id: &PeerId) -> u64 {
+        self.peers
+            .get(peer_id)
+            .and_then(PeerStats::effective_throughput)
+            .unwrap_or(0.0) as u64
+    }
+
+    /// Returns the number of batches that should be downloaded concurrently from each of `peers`.
+    ///
+    /// Every peer can download a single batch. Peers that are measurably faster than the median
+    /// peer of the group get one additional batch for each multiple of the median throughput, up
+    /// to `MAX_BATCHES_PER_PEER`. Peers that fail often never get more than one batch.
+    pub fn batch_capacities<'a>(
+        &self,
+        peers: impl Iterator<Item = &'a PeerId>,
+    ) -> FnvHashMap<PeerId, usize> {
+        let peers = peers
+            .map(|peer_id| (*peer_id, self.peers.get(peer_id)))
+            .collect::<Vec<_>>();
+
+        let mut throughputs = peers
+            .iter()
+            .filter_map(|(_, stats)| stats.and_then(PeerStats::effective_throughput))
+            .collect::<Vec<_>>();
+        throughputs.sort_by(f64::total_cmp);
+        let median = throughputs
+            .get(throughputs.len().saturating_sub(1) / 2)
+            .copied();
+
+        peers
+            .into_iter()
+            .map(|(peer_id, stats)| {
+                let capacity = match (stats, median) {
+                    (Some(stats), Some(median))
+                        if median > 0.0
+                            && stats.error_rate() <= MAX_ERROR_RATE_FOR_EXTRA_BATCHES =>
+                    {
+                        let throughput = stats.effective_throughput().unwrap_or(0.0);
+                        ((throughput / median) as usize).clamp(1, MAX_BATCHES_PER_PEER)
+                    }
+                    _ => 1,
+                };
+                (peer_id, capacity)
+            })
+            .collect()
+    }
+}
+
+fn ewma(average: f64, sample: f64) -> f64 {
+    average + EWMA_WEIGHT * (sample - average)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCKS: ByRangeRequestType = ByRangeRequestType::Blocks;
+    const BLOCKS_AND_BLOBS: ByRangeRequestType = ByRangeRequestType::BlocksAndBlobs;
+    const ONE_SEC: Duration = Duration::from_secs(1);
+
+    fn blocks(block_bytes: usize) -> ResponseSize {
+        ResponseSize {
+            block_bytes,
+            blob_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn unmeasured_peers_have_no_score() {
+        let mut throughput = PeerThroughput::default();
+        let peer = PeerId::random();
+        assert_eq!(throughput.score(&peer), 0);
+
+        // Small responses are not used to measure throughput.
+        throughput.on_request_completed(peer, BLOCKS, blocks(1024), ONE_SEC);
+        assert_eq!(throughput.score(&peer), 0);
+
+        throughput.on_request_completed(peer, BLOCKS, blocks(1_000_000), ONE_SEC);
+        assert_eq!(throughput.score(&peer), 1_000_000);
+    }
+
+    #[test]
+    fn blobs_add_to_the_throughput() {
+        let mut throughput = PeerThroughput::default();
+        let peer = PeerId::random();
+        let size = ResponseSize {
+            block_bytes: 1_000_000,
+            blob_bytes: 3_000_000,
+        };
+        throughput.on_request_completed(peer, BLOCKS_AND_BLOBS, size, Duration::from_secs(2));
+
+        let stats = throughput.peers[&peer];
+        assert_eq!(stats.blocks_by_range.bytes_per_sec, Some(500_000.0));
+        assert_eq!(stats.blobs_by_range.bytes_per_sec, Some(1_500_000.0));
+        assert_eq!(throughput.score(&peer), 2_000_000);
+    }
+
+    #[test]
+    fn errors_reduce_the_score() {
+        let mut throughput = PeerThroughput::default();
+        let peer = PeerId::random();
+        throughput.on_request_completed(peer, BLOCKS, blocks(1_000_000), ONE_SEC);
+        throughput.on_request_failed(peer, BLOCKS);
+
+        let stats = throughput.peers[&peer];
+        assert_eq!(stats.error_rate(), EWMA_WEIGHT);
+        assert_eq!(stats.blobs_by_range.error_rate, 0.0);
+        assert_eq!(
+            throughput.score(&peer),
+            (1_000_000.0 * (1.0 - EWMA_WEIGHT)) as u64
+        );
+
+        // Errors of coupled requests count against both protocols.
+        throughput.on_request_failed(peer, BLOCKS_AND_BLOBS);
+        let stats = throughput.peers[&peer];
+        assert_eq!(stats.blobs_by_range.error_rate, EWMA_WEIGHT);
+    }
+
+    #[test]
+    fn fast_peers_get_more_batches() {
+        let mut throughput = PeerThroughput::default();
+        let slow = PeerId::random();
+        let median = PeerId::random();
+        let fast = PeerId::random();
+        let very_fast = PeerId::random();
+        let unmeasured = PeerId::random();
+        for (peer, bytes) in [
+            (slow, 100_000),
+            (median, 1_000_000),
+            (fast, 2_000_000),
+            (very_fast, 50_000_000),
+        ] {
+            throughput.on_request_completed(peer, BLOCKS, blocks(bytes), ONE_SEC);
+        }
+
+        let peers = [slow, median, fast, very_fast, unmeasured];
+        let capacities = throughput.batch_capacities(peers.iter());
+        assert_eq!(capacities[&slow], 1);
+        assert_eq!(capacities[&median], 1);
+        assert_eq!(capacities[&fast], 2);
+        assert_eq!(capacities[&very_fast], MAX_BATCHES_PER_PEER);
+        assert_eq!(capacities[&unmeasured], 1);
+
+        // An unreliable peer doesn't get extra batches, however fast it is.
+        throughput.on_request_failed(very_fast, BLOCKS);
+        let capacities = throughput.batch_capacities(peers.iter());
+        assert_eq!(capacities[&very_fast], 1);
+
+        throughput.remove_peer(&very_fast);
+        assert!(!throughput.peers.contains_key(&very_fast));
+    }
+}
@@ -268,6 +268,7 @@ where
     pub fn peer_disconnect(&mut self, network: &mut SyncNetworkContext<T>, peer_id: &PeerId) {
         // if the peer is in the awaiting head mapping, remove it
         self.awaiting_head_peers.remove(peer_id);
+        network.range_peer_throughput_mut().remove_peer(peer_id);
 
         // remove the peer from any peer pool, failing its batches
         self.remove_peer(network, peer_id);
@@ -384,18 +385,22 @@ mod tests {
     use crate::NetworkMessage;
 
     use super::*;
+    use crate::sync::manager::RequestId as SyncRequestId;
     use crate::sync::network_context::BlockOrBlob;
+    use crate::sync::range_sync::peer_throughput::ResponseSize;
+    use crate::sync::range_sync::ByRangeRequestType;
     use beacon_chain::builder::Witness;
     use beacon_chain::eth1_chain::CachingEth1Backend;
     use beacon_chain::parking_lot::RwLock;
     use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
     use beacon_chain::EngineState;
     use beacon_processor::WorkEvent as BeaconWorkEvent;
-    use lighthouse_network::{rpc::StatusMessage, NetworkGlobals};
+    use lighthouse_network::{rpc::StatusMessage, NetworkGlobals, Request};
     use slog::{o, Drain};
     use slot_clock::TestingSlotClock;
     use std::collections::HashSet;
     use std::sync::Arc;
+    use std::time::Duration;
     use store::MemoryStore;
     use tokio::sync::mpsc;
     use types::{ForkName, Hash256, MinimalEthSpec as E};
@@ -549,6 +554,47 @@ mod tests {
             (block_req_id, blob_req_id)
         }
 
+        /// Reads all the BlocksByRange requests sent to the network, returning the peer, id and
+        /// type of each request.
+        fn grab_block_requests(&mut self) -> Vec<(PeerId, Id, ByRangeRequestType)> {
+            let mut requests = vec![];
+            while let Ok(message) = self.network_rx.try_recv() {
+                let NetworkMessage::SendRequest {
+                    peer_id,
+                    request: Request::BlocksByRange(_),
+                    request_id,
+                } = message
+                else {
+                    continue;
+                };
+                match request_id {
+                    RequestId::Sync(SyncRequestId::RangeBlocks { id }) => {
+                        requests.push((peer_id, id, ByRangeRequestType::Blocks))
+                    }
+                    RequestId::Sync(SyncRequestId::RangeBlockAndBlobs { id }) => {
+                        requests.push((peer_id, id, ByRangeRequestType::BlocksAndBlobs))
+                    }
+                    other => panic!("unexpected request {:?}", other),
+                }
+            }
+            requests
+        }
+
+        /// Registers a completed request for `peer_id`, measuring its throughput as
+        /// `bytes_per_sec`.
+        fn set_throughput(&mut self, peer_id: PeerId, bytes_per_sec: usize) {
+            let size = ResponseSize {
+                block_bytes: bytes_per_sec,
+                blob_bytes: 0,
+            };
+            self.cx.range_peer_throughput_mut().on_request_completed(
+                peer_id,
+                ByRangeRequestType::Blocks,
+                size,
+                Duration::from_secs(1),
+            );
+        }
+
         /// Produce a head peer
         fn head_peer(
             &self,
@@ -595,6 +641,20 @@ mod tests {
             (peer_id, local_info, remote_info)
         }
 
+        /// Produce a finalized peer far enough ahead to download many batches from.
+        fn distant_finalized_peer(
+            &self,
+        ) -> (
+            PeerId,
+            SyncInfo, /* Local info */
+            SyncInfo, /* Remote info */
+        ) {
+            let (peer_id, local_info, mut remote_info) = self.finalized_peer();
+            remote_info.finalized_epoch = remote_info.finalized_epoch + 8;
+            remote_info.head_slot = remote_info.finalized_epoch.start_slot(E::slots_per_epoch());
+            (peer_id, local_info, remote_info)
+        }
+
         #[track_caller]
         fn expect_empty_processor(&mut self) {
             match self.beacon_processor_rx.try_recv() {
@@ -817,4 +877,68 @@ mod tests {
         rig.expect_chain_segment();
         rig.expect_chain_segment();
     }
+
+    fn request_peers(requests: &[(PeerId, Id, ByRangeRequestType)]) -> Vec<PeerId> {
+        requests.iter().map(|(peer_id, _, _)| *peer_id).collect()
+    }
+
+    #[test]
+    fn fast_peers_are_assigned_more_batches() {
+        let (mut rig, mut range) = range(false);
+        let (slow_peer, local_info, remote_info) = rig.distant_finalized_peer();
+        let fast_peer = PeerId::random();
+        rig.set_throughput(slow_peer, 1_000_000);
+        rig.set_throughput(fast_peer, 10_000_000);
+
+        // A peer on its own can't be compared to others, so it only gets a single batch.
+        range.add_peer(
+            &mut rig.cx,
+            local_info.clone(),
+            slow_peer,
+            remote_info.clone(),
+        );
+        range.assert_state(RangeSyncType::Finalized);
+        let requests = rig.grab_block_requests();
+        assert_eq!(request_peers(&requests), vec![slow_peer]);
+
+        // The fast peer is ten times faster than the median peer, so it gets the maximum of three
+        // concurrent batches.
+        range.add_peer(&mut rig.cx, local_info, fast_peer, remote_info);
+        let requests = rig.grab_block_requests();
+        assert_eq!(request_peers(&requests), vec![fast_peer; 3]);
+    }
+
+    #[test]
+    fn failed_batches_are_retried_from_the_fastest_peer() {
+        let (mut rig, mut range) = range(false);
+        let (failing_peer, local_info, remote_info) = rig.distant_finalized_peer();
+        let slow_peer = PeerId::random();
+        let fast_peer = PeerId::random();
+        rig.set_throughput(failing_peer, 1_000_000);
+        rig.set_throughput(slow_peer, 1_000_000);
+        // Faster than the other peers, but not enough to download more than one batch at a time.
+        rig.set_throughput(fast_peer, 1_500_000);
+
+        for peer_id in [failing_peer, slow_peer, fast_peer] {
+            range.add_peer(
+                &mut rig.cx,
+                local_info.clone(),
+                peer_id,
+                remote_info.clone(),
+            );
+        }
+        let requests = rig.grab_block_requests();
+        assert_eq!(
+            request_peers(&requests),
+            vec![failing_peer, slow_peer, fast_peer]
+        );
+
+        // The failing peer has no active requests left, but it failed the batch. The other peers
+        // have one active request each, so the batch is retried from the fastest of them.
+        let (_, id, batch_type) = requests[0];
+        let (chain_id, batch_id) = rig.cx.range_sync_request_failed(id, batch_type).unwrap();
+        range.inject_error(&mut rig.cx, failing_peer, batch_id, chain_id, id);
+        let requests = rig.grab_block_requests();
+        assert_eq!(request_peers(&requests), vec![fast_peer]);
+    }
 }
@@ -144,7 +144,10 @@ impl<T: EthSpec> CompactIndexedAttestation<T> {
 }
 
 impl<T: EthSpec> AttestationMap<T> {
-    pub fn insert(&mut self, attestation: Attestation<T>, attesting_indices: Vec<u64>) {
+    /// Insert an attestation into the map.
+    ///
+    /// Return `true` if the attestation has signers that were not already in the map.
+    pub fn insert(&mut self, attestation: Attestation<T>, attesting_indices: Vec<u64>) -> bool {
         let SplitAttestation {
             checkpoint,
             data,
@@ -154,6 +157,13 @@ impl<T: EthSpec> AttestationMap<T> {
         let attestation_map = self.checkpoint_map.entry(checkpoint).or_default();
         let attestations = attestation_map.attestations.entry(data).or_default();
 
+        let new_signers = attestations
+            .iter()
+            .fold(indexed.aggregation_bits.clone(), |signers, existing| {
+                signers.difference(&existing.aggregation_bits)
+            });
+        let is_new = !new_signers.is_zero();
+
         // Greedily aggregate the attestation with all existing attestations.
         // NOTE: this is sub-optimal and in future we will remove this in favour of max-clique
         // aggregation.
@@ -170,6 +180,8 @@ impl<T: EthSpec> AttestationMap<T> {
         if !aggregated {
             attestations.push(indexed);
         }
+
+        is_new
     }
 
     /// Iterate all attestations matching the given `checkpoint_key`.
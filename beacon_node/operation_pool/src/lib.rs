@@ -23,10 +23,11 @@ use crate::attestation_storage::{AttestationMap, CheckpointKey};
 use crate::bls_to_execution_changes::BlsToExecutionChanges;
 use crate::sync_aggregate_id::SyncAggregateId;
 use attester_slashing::AttesterSlashingMaxCover;
-use max_cover::maximum_cover;
+use max_cover::{exact_maximum_cover, maximum_cover, ExactCover};
 use parking_lot::{RwLock, RwLockWriteGuard};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use serde::{Deserialize, Serialize};
 use state_processing::per_block_processing::errors::AttestationValidationError;
 use state_processing::per_block_processing::{
     get_slashable_indices_modular, verify_exit, VerifySignatures,
@@ -35,11 +36,14 @@ use state_processing::{SigVerifiedOp, VerifyOperation};
 use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
+use std::time::{Duration, Instant};
+use strum::{Display, EnumString, EnumVariantNames};
+use tree_hash::TreeHash;
 use types::{
     sync_aggregate::Error as SyncAggregateError, typenum::Unsigned, AbstractExecPayload,
     Attestation, AttestationData, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec,
-    Epoch, EthSpec, ProposerSlashing, SignedBeaconBlock, SignedBlsToExecutionChange,
-    SignedVoluntaryExit, Slot, SyncAggregate, SyncCommitteeContribution, Validator,
+    Epoch, EthSpec, Hash256, ProposerSlashing, SignedBeaconBlock, SignedBlsToExecutionChange,
+    SignedVoluntaryExit, Slot, SyncAggregate, SyncCommitteeContribution, Validator, VariableList,
 };
 
 type SyncContributions<T> = RwLock<HashMap<SyncAggregateId, Vec<SyncCommitteeContribution<T>>>>;
@@ -60,6 +64,8 @@ pub struct OperationPool<T: EthSpec + Default> {
     bls_to_execution_changes: RwLock<BlsToExecutionChanges<T>>,
     /// Reward cache for accelerating attestation packing.
     reward_cache: RwLock<RewardCache>,
+    /// Outcomes of recent attestation packings, keyed by the tree hash root of the attestations.
+    attestation_packings: RwLock<HashMap<Hash256, AttestationPacking>>,
     _phantom: PhantomData<T>,
 }
 
@@ -77,6 +83,45 @@ pub enum OpPoolError {
     IncorrectOpPoolVariant,
 }
 
+/// Algorithm used to select the attestations to include in a block.
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Default,
+    Deserialize,
+    Serialize,
+    Display,
+    EnumString,
+    EnumVariantNames,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum AttestationPackingStrategy {
+    /// Greedy maximum coverage, which is fast but may leave reward on the table.
+    #[default]
+    Greedy,
+    /// Branch and bound search for the optimal attestations, bounded by a time budget. Falls back
+    /// to the best solution found so far, starting from greedy, when the time budget runs out.
+    Exact,
+}
+
+/// The outcome of packing the attestations for a block.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AttestationPacking {
+    /// Slot of the block that the attestations were packed for.
+    pub slot: Slot,
+    /// The strategy used to select the attestations.
+    pub strategy: AttestationPackingStrategy,
+    /// Proposer reward for the selected attestations, in gwei.
+    pub reward: u64,
+    /// Proposer reward for the attestations that greedy packing would have selected, in gwei.
+    pub greedy_reward: u64,
+    /// Whether the exact search ran out of time before it was complete.
+    pub timed_out: bool,
+}
+
 #[derive(Default)]
 pub struct AttestationStats {
     /// Total number of attestations for all committeees/indices/votes.
@@ -102,20 +147,24 @@ impl<T: EthSpec> OperationPool<T> {
     /// Insert a sync contribution into the pool. We don't aggregate these contributions until they
     /// are retrieved from the pool.
     ///
+    /// Return `true` if the contribution was added to the pool, i.e. if there was no contribution
+    /// for the same subcommittee with at least as many aggregation bits set.
+    ///
     /// ## Note
     ///
     /// This function assumes the given `contribution` is valid.
     pub fn insert_sync_contribution(
         &self,
         contribution: SyncCommitteeContribution<T>,
-    ) -> Result<(), OpPoolError> {
+    ) -> Result<bool, OpPoolError> {
         let aggregate_id = SyncAggregateId::new(contribution.slot, contribution.beacon_block_root);
         let mut contributions = self.sync_contributions.write();
 
-        match contributions.entry(aggregate_id) {
+        let inserted = match contributions.entry(aggregate_id) {
             Entry::Vacant(entry) => {
                 // If no contributions exist for the key, insert the given contribution.
                 entry.insert(vec![contribution]);
+                true
             }
             Entry::Occupied(mut entry) => {
                 // If contributions exists for this key, check whether there exists a contribution
@@ -135,17 +184,21 @@ impl<T: EthSpec> OperationPool<T> {
                             < contribution.aggregation_bits.num_set_bits()
                         {
                             *existing_contribution = contribution;
+                            true
+                        } else {
+                            false
                         }
                     }
                     None => {
                         // If there has been no previous sync contribution for this subcommittee index,
                         // add it to the pool.
                         existing_contributions.push(contribution);
+                        true
                     }
                 }
             }
         };
-        Ok(())
+        Ok(inserted)
     }
 
     /// Calculate the `SyncAggregate` from the sync contributions that exist in the pool for the
@@ -192,6 +245,8 @@ impl<T: EthSpec> OperationPool<T> {
 
     /// Insert an attestation into the pool, aggregating it with existing attestations if possible.
     ///
+    /// Return `true` if the attestation has signers that were not already in the pool.
+    ///
     /// ## Note
     ///
     /// This function assumes the given `attestation` is valid.
@@ -199,11 +254,11 @@ impl<T: EthSpec> OperationPool<T> {
         &self,
         attestation: Attestation<T>,
         attesting_indices: Vec<u64>,
-    ) -> Result<(), AttestationValidationError> {
-        self.attestations
+    ) -> Result<bool, AttestationValidationError> {
+        Ok(self
+            .attestations
             .write()
-            .insert(attestation, attesting_indices);
-        Ok(())
+            .insert(attestation, attesting_indices))
     }
 
     /// Total number of attestations in the pool, including attestations for the same data.
@@ -239,7 +294,7 @@ impl<T: EthSpec> OperationPool<T> {
             })
     }
 
-    /// Get a list of attestations for inclusion in a block.
+    /// Get a list of attestations for inclusion in a block, using greedy packing.
     ///
     /// The `validity_filter` is a closure that provides extra filtering of the attestations
     /// before an approximately optimal bundle is constructed. We use it to provide access
@@ -251,6 +306,29 @@ impl<T: EthSpec> OperationPool<T> {
         prev_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
         curr_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
         spec: &ChainSpec,
+    ) -> Result<Vec<Attestation<T>>, OpPoolError> {
+        self.get_attestations_with_strategy(
+            state,
+            prev_epoch_validity_filter,
+            curr_epoch_validity_filter,
+            AttestationPackingStrategy::Greedy,
+            Duration::ZERO,
+            spec,
+        )
+    }
+
+    /// Get a list of attestations for inclusion in a block, selected using `strategy`.
+    ///
+    /// The `time_budget` bounds the time spent searching when using the exact strategy. The
+    /// outcome of the packing is recorded, see `get_attestation_packing`.
+    pub fn get_attestations_with_strategy(
+        &self,
+        state: &BeaconState<T>,
+        prev_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
+        curr_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
+        strategy: AttestationPackingStrategy,
+        time_budget: Duration,
+        spec: &ChainSpec,
     ) -> Result<Vec<Attestation<T>>, OpPoolError> {
         // Attestations for the current fork, which may be from the current or previous epoch.
         let (prev_epoch_key, curr_epoch_key) = CheckpointKey::keys_for_state(state);
@@ -304,21 +382,35 @@ impl<T: EthSpec> OperationPool<T> {
             T::MaxAttestations::to_usize()
         };
 
+        // Both epochs are packed in parallel, so they share the same deadline.
+        let deadline = Instant::now() + time_budget;
         let (prev_cover, curr_cover) = rayon::join(
             move || {
                 let _timer = metrics::start_timer(&metrics::ATTESTATION_PREV_EPOCH_PACKING_TIME);
                 // If we're in the genesis epoch, just use the current epoch attestations.
                 if prev_epoch_key == curr_epoch_key {
-                    vec![]
+                    ExactCover {
+                        solution: vec![],
+                        greedy: vec![],
+                        timed_out: false,
+                    }
                 } else {
-                    maximum_cover(prev_epoch_att, prev_epoch_limit, "prev_epoch_attestations")
+                    pack_attestations(
+                        prev_epoch_att,
+                        prev_epoch_limit,
+                        strategy,
+                        deadline,
+                        "prev_epoch_attestations",
+                    )
                 }
             },
             move || {
                 let _timer = metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
-                maximum_cover(
+                pack_attestations(
                     curr_epoch_att,
                     T::MaxAttestations::to_usize(),
+                    strategy,
+                    deadline,
                     "curr_epoch_attestations",
                 )
             },
@@ -327,35 +419,105 @@ impl<T: EthSpec> OperationPool<T> {
         metrics::set_gauge(&metrics::NUM_PREV_EPOCH_ATTESTATIONS, num_prev_valid);
         metrics::set_gauge(&metrics::NUM_CURR_EPOCH_ATTESTATIONS, num_curr_valid);
 
-        Ok(max_cover::merge_solutions(
-            curr_cover,
-            prev_cover,
+        let timed_out = prev_cover.timed_out || curr_cover.timed_out;
+        if timed_out {
+            metrics::inc_counter(&metrics::ATTESTATION_PACKING_TIMEOUTS);
+        }
+        // The greedy solutions are merged under the same limit as the final solutions, so that the
+        // two rewards are comparable.
+        let greedy_reward = max_cover::merged_score(
+            &curr_cover.greedy,
+            &prev_cover.greedy,
             T::MaxAttestations::to_usize(),
-        ))
+        ) as u64;
+        let reward = max_cover::merged_score(
+            &curr_cover.solution,
+            &prev_cover.solution,
+            T::MaxAttestations::to_usize(),
+        );
+        let attestations = max_cover::merge_solutions(
+            curr_cover.solution,
+            prev_cover.solution,
+            T::MaxAttestations::to_usize(),
+        );
+
+        self.record_attestation_packing(
+            &attestations,
+            AttestationPacking {
+                slot: state.slot(),
+                strategy,
+                reward: reward as u64,
+                greedy_reward,
+                timed_out,
+            },
+        );
+
+        Ok(attestations)
+    }
+
+    /// Record the outcome of packing `attestations`, so that it can be reported alongside the
+    /// rewards for the block that includes them.
+    fn record_attestation_packing(
+        &self,
+        attestations: &[Attestation<T>],
+        packing: AttestationPacking,
+    ) {
+        let Ok(attestations) = VariableList::<_, T::MaxAttestations>::new(attestations.to_vec())
+        else {
+            return;
+        };
+        self.attestation_packings
+            .write()
+            .insert(attestations.tree_hash_root(), packing);
+    }
+
+    /// Get the outcome of packing the attestations with tree hash root `attestations_root`.
+    ///
+    /// Only packings for blocks produced by this node since the last pruning are known.
+    pub fn get_attestation_packing(
+        &self,
+        attestations_root: Hash256,
+    ) -> Option<AttestationPacking> {
+        self.attestation_packings
+            .read()
+            .get(&attestations_root)
+            .cloned()
     }
 
     /// Remove attestations which are too old to be included in a block.
     pub fn prune_attestations(&self, current_epoch: Epoch) {
         self.attestations.write().prune(current_epoch);
+        self.attestation_packings
+            .write()
+            .retain(|_, packing| packing.slot.epoch(T::slots_per_epoch()) + 1 >= current_epoch);
     }
 
     /// Insert a proposer slashing into the pool.
+    ///
+    /// Return `true` if the slashing was not already in the pool.
     pub fn insert_proposer_slashing(
         &self,
         verified_proposer_slashing: SigVerifiedOp<ProposerSlashing, T>,
-    ) {
-        self.proposer_slashings.write().insert(
-            verified_proposer_slashing.as_inner().proposer_index(),
-            verified_proposer_slashing,
-        );
+    ) -> bool {
+        let mut proposer_slashings = self.proposer_slashings.write();
+        let proposer_index = verified_proposer_slashing.as_inner().proposer_index();
+        let is_new = proposer_slashings
+            .get(&proposer_index)
+            .map_or(true, |existing| {
+                existing.as_inner() != verified_proposer_slashing.as_inner()
+            });
+        proposer_slashings.insert(proposer_index, verified_proposer_slashing);
+        is_new
     }
 
     /// Insert an attester slashing into the pool.
+    ///
+    /// Return `true` if the slashing was not already in the pool.
     pub fn insert_attester_slashing(
         &self,
         verified_slashing: SigVerifiedOp<AttesterSlashing<T>, T>,
-    ) {
-        self.attester_slashings.write().insert(verified_slashing);
+    ) -> bool {
+        self.attester_slashings.write().insert(verified_slashing)
     }
 
     /// Get proposer and attester slashings for inclusion in a block.
@@ -476,10 +638,16 @@ impl<T: EthSpec> OperationPool<T> {
     }
 
     /// Insert a voluntary exit that has previously been checked elsewhere.
-    pub fn insert_voluntary_exit(&self, exit: SigVerifiedOp<SignedVoluntaryExit, T>) {
-        self.voluntary_exits
-            .write()
-            .insert(exit.as_inner().message.validator_index, exit);
+    ///
+    /// Return `true` if the exit was not already in the pool.
+    pub fn insert_voluntary_exit(&self, exit: SigVerifiedOp<SignedVoluntaryExit, T>) -> bool {
+        let mut voluntary_exits = self.voluntary_exits.write();
+        let validator_index = exit.as_inner().message.validator_index;
+        let is_new = voluntary_exits
+            .get(&validator_index)
+            .map_or(true, |existing| existing.as_inner() != exit.as_inner());
+        voluntary_exits.insert(validator_index, exit);
+        is_new
     }
 
     /// Get a list of voluntary exits for inclusion in a block.
@@ -662,6 +830,18 @@ impl<T: EthSpec> OperationPool<T> {
             .collect()
     }
 
+    /// Returns all known `SyncCommitteeContribution` objects.
+    ///
+    /// This method may return objects that are invalid for block inclusion.
+    pub fn get_all_sync_contributions(&self) -> Vec<SyncCommitteeContribution<T>> {
+        self.sync_contributions
+            .read()
+            .values()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
     /// Returns all known `AttesterSlashing` objects.
     ///
     /// This method may return objects that are invalid for block inclusion.
@@ -750,6 +930,29 @@ fn prune_validator_hash_map<T, F, E: EthSpec>(
     });
 }
 
+/// Select at most `limit` of `attestations` using `strategy`.
+fn pack_attestations<'a, T: EthSpec>(
+    attestations: impl IntoIterator<Item = AttMaxCover<'a, T>>,
+    limit: usize,
+    strategy: AttestationPackingStrategy,
+    deadline: Instant,
+    label: &str,
+) -> ExactCover<AttMaxCover<'a, T>> {
+    match strategy {
+        AttestationPackingStrategy::Greedy => {
+            let solution = maximum_cover(attestations, limit, label);
+            ExactCover {
+                greedy: solution.clone(),
+                solution,
+                timed_out: false,
+            }
+        }
+        AttestationPackingStrategy::Exact => {
+            exact_maximum_cover(attestations, limit, deadline, label)
+        }
+    }
+}
+
 /// Compare two operation pools.
 impl<T: EthSpec + Default> PartialEq for OperationPool<T> {
     fn eq(&self, other: &Self) -> bool {
@@ -1029,10 +1232,10 @@ mod release_tests {
         for (_, aggregate) in attestations {
             let att = aggregate.unwrap().message.aggregate;
             let attesting_indices = get_attesting_indices_from_state(&state, &att).unwrap();
-            op_pool
+            assert!(op_pool
                 .insert_attestation(att.clone(), attesting_indices.clone())
-                .unwrap();
-            op_pool.insert_attestation(att, attesting_indices).unwrap();
+                .unwrap());
+            assert!(!op_pool.insert_attestation(att, attesting_indices).unwrap());
         }
 
         assert_eq!(op_pool.num_attestations(), committees.len());
@@ -1350,6 +1553,69 @@ mod release_tests {
         }
     }
 
+    /// Pack overlapping aggregates exactly, and check the outcome is recorded.
+    #[test]
+    fn exact_attestation_packing() {
+        let num_committees = 4;
+        let (harness, ref spec) = attestation_test_state::<MainnetEthSpec>(num_committees);
+
+        let mut state = harness.get_current_state();
+        let op_pool = OperationPool::<MainnetEthSpec>::new();
+        let slot = state.slot();
+
+        let num_validators = num_committees
+            * MainnetEthSpec::slots_per_epoch() as usize
+            * spec.target_committee_size;
+        let attestations = harness.make_attestations(
+            (0..num_validators).collect::<Vec<_>>().as_slice(),
+            &state,
+            Hash256::zero(),
+            SignedBeaconBlockHash::from(Hash256::zero()),
+            slot,
+        );
+
+        // Insert aggregates of 3 and 4 validators, which overlap each other.
+        for (atts, _) in attestations {
+            for step_size in [3, 4] {
+                for chunk in atts.chunks_exact(step_size) {
+                    let mut agg = chunk[0].0.clone();
+                    for (att, _) in &chunk[1..] {
+                        agg.aggregate(att);
+                    }
+                    let attesting_indices = get_attesting_indices_from_state(&state, &agg).unwrap();
+                    op_pool.insert_attestation(agg, attesting_indices).unwrap();
+                }
+            }
+        }
+
+        *state.slot_mut() += spec.min_attestation_inclusion_delay;
+        let best_attestations = op_pool
+            .get_attestations_with_strategy(
+                &state,
+                |_| true,
+                |_| true,
+                AttestationPackingStrategy::Exact,
+                Duration::from_secs(1),
+                spec,
+            )
+            .unwrap();
+        assert!(!best_attestations.is_empty());
+
+        let attestations_root =
+            VariableList::<_, <MainnetEthSpec as EthSpec>::MaxAttestations>::new(best_attestations)
+                .unwrap()
+                .tree_hash_root();
+        let packing = op_pool.get_attestation_packing(attestations_root).unwrap();
+        assert_eq!(packing.slot, state.slot());
+        assert_eq!(packing.strategy, AttestationPackingStrategy::Exact);
+        assert!(packing.reward >= packing.greedy_reward);
+        assert!(packing.reward > 0);
+
+        // Packings are forgotten once their attestations are pruned.
+        op_pool.prune_attestations(state.current_epoch() + 2);
+        assert_eq!(op_pool.get_attestation_packing(attestations_root), None);
+    }
+
     /// Insert two slashings for the same proposer and ensure only one is returned.
     #[test]
     fn duplicate_proposer_slashing() {
@@ -1606,10 +1872,10 @@ mod release_tests {
                 .expect("contribution exists for committee")
                 .message
                 .contribution;
-            op_pool
+            assert!(op_pool
                 .insert_sync_contribution(contribution.clone())
-                .unwrap();
-            op_pool.insert_sync_contribution(contribution).unwrap();
+                .unwrap());
+            assert!(!op_pool.insert_sync_contribution(contribution).unwrap());
         }
 
         assert_eq!(op_pool.sync_contributions.read().len(), 1);
@@ -1,5 +1,7 @@
 use crate::metrics;
 use itertools::Itertools;
+use std::cmp::Reverse;
+use std::time::Instant;
 
 /// Trait for types that we can compute a maximum cover for.
 ///
@@ -100,6 +102,118 @@ where
     result
 }
 
+/// Result of an exact maximum cover computation.
+pub struct ExactCover<T> {
+    /// The best solution found.
+    pub solution: Vec<T>,
+    /// The greedy solution that the search started from.
+    pub greedy: Vec<T>,
+    /// Whether the deadline was reached before the search was complete.
+    pub timed_out: bool,
+}
+
+/// Compute a maximum cover using branch and bound, giving up at `deadline`.
+///
+/// The search starts from the greedy solution and only replaces it with strictly better ones, so
+/// the result is never worse than `maximum_cover`. If the deadline is reached, the best solution
+/// found so far is returned.
+///
+/// The search relies on an item's score never increasing when its covering set is updated, which
+/// allows it to bound the quality of any solution by the sum of the highest remaining scores.
+pub fn exact_maximum_cover<I, T>(
+    items_iter: I,
+    limit: usize,
+    deadline: Instant,
+    label: &str,
+) -> ExactCover<T>
+where
+    I: IntoIterator<Item = T>,
+    T: MaxCover,
+{
+    let mut items: Vec<T> = items_iter
+        .into_iter()
+        .filter(|item| item.score() != 0)
+        .collect();
+
+    let greedy = maximum_cover(items.clone(), limit, label);
+
+    items.sort_by_key(|item| Reverse(item.score()));
+    let mut search = BranchAndBound {
+        deadline,
+        best_score: solution_score(&greedy),
+        best: greedy.clone(),
+        timed_out: false,
+    };
+    search.search(&items, &mut Vec::with_capacity(limit), 0, limit);
+
+    ExactCover {
+        solution: search.best,
+        greedy,
+        timed_out: search.timed_out,
+    }
+}
+
+/// State of a branch and bound search for `exact_maximum_cover`.
+struct BranchAndBound<T> {
+    deadline: Instant,
+    best: Vec<T>,
+    best_score: usize,
+    timed_out: bool,
+}
+
+impl<T: MaxCover> BranchAndBound<T> {
+    /// Search for solutions which extend `chosen` using at most `limit` of `items`.
+    ///
+    /// `items` must be sorted by descending score, and have their covering sets updated for the
+    /// inclusion of every item in `chosen`.
+    fn search(&mut self, items: &[T], chosen: &mut Vec<T>, score: usize, limit: usize) {
+        for (i, item) in items.iter().enumerate() {
+            if self.timed_out || Instant::now() >= self.deadline {
+                self.timed_out = true;
+                return;
+            }
+
+            // Solutions which don't include `items[..i]` can score no more than the top `limit`
+            // of the remaining items. This bound only decreases as `i` increases.
+            let bound = score
+                + items[i..]
+                    .iter()
+                    .take(limit)
+                    .map(MaxCover::score)
+                    .sum::<usize>();
+            if bound <= self.best_score {
+                return;
+            }
+
+            let new_score = score + item.score();
+            chosen.push(item.clone());
+            if new_score > self.best_score {
+                self.best = chosen.clone();
+                self.best_score = new_score;
+            }
+
+            if limit > 1 {
+                let mut remaining: Vec<T> = items[i + 1..]
+                    .iter()
+                    .filter_map(|other| {
+                        let mut other = other.clone();
+                        other.update_covering_set(item.intermediate(), item.covering_set());
+                        (other.score() != 0).then_some(other)
+                    })
+                    .collect();
+                remaining.sort_by_key(|other| Reverse(other.score()));
+                self.search(&remaining, chosen, new_score, limit - 1);
+            }
+            chosen.pop();
+        }
+    }
+}
+
+/// Sum the scores of the items in a solution.
+pub fn solution_score<T: MaxCover>(solution: &[T]) -> usize {
+    solution.iter().map(MaxCover::score).sum()
+}
+
 /// Perform a greedy merge of two max cover solutions, preferring higher-score values.
 pub fn merge_solutions<I1, I2, T>(cover1: I1, cover2: I2, limit: usize) -> Vec<T::Object>
 where
@@ -115,6 +229,18 @@ where
         .collect()
 }
 
+/// Compute the quality of the solution produced by `merge_solutions`.
+pub fn merged_score<T: MaxCover>(cover1: &[T], cover2: &[T], limit: usize) -> usize {
+    cover1
+        .iter()
+        .map(MaxCover::score)
+        .merge_by(cover2.iter().map(MaxCover::score), |score1, score2| {
+            score1 >= score2
+        })
+        .take(limit)
+        .sum()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -208,6 +334,75 @@ mod test {
         assert_eq!(quality(&cover), 11);
     }
 
+    #[test]
+    fn exact_optimal() {
+        let sets = vec![
+            HashSet::from_iter(vec![0, 1, 8, 11, 14]),
+            HashSet::from_iter(vec![2, 3, 7, 9, 10]),
+            HashSet::from_iter(vec![4, 5, 6, 12, 13]),
+            HashSet::from_iter(vec![9, 10]),
+            HashSet::from_iter(vec![5, 6, 7, 8]),
+            HashSet::from_iter(vec![0, 1, 2, 3, 4]),
+        ];
+        let deadline = Instant::now() + std::time::Duration::from_secs(60);
+        let cover = exact_maximum_cover(sets, 3, deadline, "test");
+        assert_eq!(quality(&cover.greedy), 11);
+        assert_eq!(quality(&cover.solution), 15);
+        assert!(!cover.timed_out);
+    }
+
+    #[test]
+    fn exact_matches_greedy_when_optimal() {
+        let sets = example_system();
+        let deadline = Instant::now() + std::time::Duration::from_secs(60);
+        for k in 0..10 {
+            let greedy = maximum_cover(sets.clone(), k, "test");
+            let exact = exact_maximum_cover(sets.clone(), k, deadline, "test");
+            assert_eq!(quality(&exact.solution), quality(&greedy));
+            assert_eq!(quality(&exact.greedy), quality(&greedy));
+        }
+    }
+
+    #[test]
+    fn exact_timeout_falls_back_to_greedy() {
+        let sets = vec![
+            HashSet::from_iter(vec![0, 1, 8, 11, 14]),
+            HashSet::from_iter(vec![2, 3, 7, 9, 10]),
+            HashSet::from_iter(vec![4, 5, 6, 12, 13]),
+            HashSet::from_iter(vec![9, 10]),
+            HashSet::from_iter(vec![5, 6, 7, 8]),
+            HashSet::from_iter(vec![0, 1, 2, 3, 4]),
+        ];
+        let cover = exact_maximum_cover(sets, 3, Instant::now(), "test");
+        assert!(cover.timed_out);
+        assert_eq!(quality(&cover.solution), 11);
+    }
+
+    // Merging two covers under a limit can drop items from either, so the merged greedy solutions
+    // must be scored under the same limit as the merged exact solutions to be comparable.
+    #[test]
+    fn merged_score_truncates() {
+        let sets = vec![
+            HashSet::from_iter(vec![0, 1, 8, 11, 14]),
+            HashSet::from_iter(vec![2, 3, 7, 9, 10]),
+            HashSet::from_iter(vec![4, 5, 6, 12, 13]),
+            HashSet::from_iter(vec![9, 10]),
+            HashSet::from_iter(vec![5, 6, 7, 8]),
+            HashSet::from_iter(vec![0, 1, 2, 3, 4]),
+        ];
+        let deadline = Instant::now() + std::time::Duration::from_secs(60);
+        let cover1 = exact_maximum_cover(sets, 3, deadline, "test");
+        let cover2 = exact_maximum_cover(example_system(), 2, deadline, "test");
+
+        assert_eq!(quality(&cover1.greedy) + quality(&cover2.greedy), 16);
+        assert_eq!(merged_score(&cover1.greedy, &cover2.greedy, 3), 13);
+        assert_eq!(merged_score(&cover1.solution, &cover2.solution, 3), 15);
+
+        let merged = merge_solutions(cover1.solution, cover2.solution, 3);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(quality(&merged), 15);
+    }
+
     #[test]
     fn intersecting_ok() {
         let sets = vec![
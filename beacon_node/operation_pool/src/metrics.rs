@@ -23,6 +23,10 @@ lazy_static! {
         "op_pool_curr_epoch_attestations",
         "Number of valid attestations considered for packing from the current epoch"
     );
+    pub static ref ATTESTATION_PACKING_TIMEOUTS: Result<IntCounter> = try_create_int_counter(
+        "op_pool_attestation_packing_timeouts_total",
+        "Number of times exact attestation packing ran out of time before completing its search"
+    );
     pub static ref MAX_COVER_NON_ZERO_ITEMS: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "op_pool_max_cover_non_zero_items",
         "Number of non-trivial items considered in a max coverage optimisation",
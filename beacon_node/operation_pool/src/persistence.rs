@@ -194,6 +194,7 @@ impl<T: EthSpec> PersistedOperationPool<T> {
             voluntary_exits,
             bls_to_execution_changes: RwLock::new(bls_to_execution_changes),
             reward_cache: Default::default(),
+            attestation_packings: Default::default(),
             _phantom: Default::default(),
         };
         Ok(op_pool)
@@ -1,3 +1,4 @@
+use beacon_chain::chain_config::AttestationPackingStrategy;
 use clap::{App, Arg, ArgGroup};
 use strum::VariantNames;
 use types::ProgressiveBalancesMode;
@@ -148,6 +149,15 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("The target number of peers.")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("peer-bandwidth-cap")
+                .long("peer-bandwidth-cap")
+                .value_name("KB_PER_SECOND")
+                .help("The bandwidth in kilobytes per second that a peer may use before it is \
+                       deprioritized. Peers exceeding this cap are the first to be disconnected \
+                       when the node has more peers than its target.")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("boot-nodes")
                 .long("boot-nodes")
@@ -595,7 +605,8 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .value_name("SLOT_COUNT")
                 .help("Specifies how often a freezer DB restore point should be stored. \
                        Cannot be changed after initialization. \
-                       [default: 8192 (mainnet) or 64 (minimal)]")
+                       [default: 8192 (mainnet) or 64 (minimal), or 262144 with \
+                       `--freezer-layout diffs`]")
                 .takes_value(true)
         )
         .arg(
@@ -723,6 +734,30 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .long("purge-db")
                 .help("If present, the chain database will be deleted. Use with caution.")
         )
+        .arg(
+            Arg::with_name("beacon-node-backend")
+                .long("beacon-node-backend")
+                .value_name("DATABASE")
+                .help("Set the database backend to be used by the beacon node. Defaults to the \
+                       backend of the existing database, or leveldb for a new database. The \
+                       backend cannot be changed for an existing database without first copying \
+                       it using `lighthouse db copy`.")
+                .takes_value(true)
+                .possible_values(store::DatabaseBackend::VARIANTS)
+        )
+        .arg(
+            Arg::with_name("freezer-layout")
+                .long("freezer-layout")
+                .value_name("LAYOUT")
+                .help("Set the layout of historic states in the freezer database. The `diffs` \
+                       layout stores a compressed diff of each epoch boundary state in place of \
+                       most restore points, so that historic state queries replay at most one \
+                       epoch of blocks. The layout of an existing database can only be changed \
+                       using `lighthouse db migrate-freezer`.")
+                .takes_value(true)
+                .possible_values(store::FreezerLayout::VARIANTS)
+                .default_value("restore-points")
+        )
         .arg(
             Arg::with_name("compact-db")
                 .long("compact-db")
@@ -764,6 +799,15 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
                 .default_value("1")
         )
+        .arg(
+            Arg::with_name("archive-blobs")
+                .long("archive-blobs")
+                .help("Keep the blobs of all blocks since the Deneb fork. Blobs older than the \
+                       data availability boundary are downloaded from peers which still have \
+                       them, and blobs are never pruned. Blocks are backfilled at least as far \
+                       as the Deneb fork when checkpoint syncing.")
+                .takes_value(false)
+        )
         .arg(
             Arg::with_name("blob-prune-margin-epochs")
                 .long("blob-prune-margin-epochs")
@@ -906,7 +950,7 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .value_name("DATABASE")
                 .help("Set the database backend to be used by the slasher.")
                 .takes_value(true)
-                .possible_values(slasher::DatabaseBackend::VARIANTS)
+                .possible_values(slasher::DatabaseBackend::CLI_VARIANTS)
                 .requires("slasher")
         )
         .arg(
@@ -951,10 +995,28 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         .arg(
             Arg::with_name("checkpoint-sync-url")
                 .long("checkpoint-sync-url")
-                .help("Set the remote beacon node HTTP endpoint to use for checkpoint sync.")
-                .value_name("BEACON_NODE")
+                .help("Set the remote beacon node HTTP endpoint to use for checkpoint sync. \
+                       Multiple endpoints can be given as a comma-separated list, in which case \
+                       the checkpoint is downloaded from the first available endpoint and \
+                       checked against the others. This flag can be combined with \
+                       --checkpoint-state and --checkpoint-block, in which case the checkpoint \
+                       is loaded from these files and checked against the endpoints.")
+                .value_name("BEACON_NODES")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("checkpoint-sync-quorum")
+                .long("checkpoint-sync-quorum")
+                .help("The number of checkpoint providers that must agree on the checkpoint \
+                       for checkpoint sync to proceed. An endpoint agrees if it has the same \
+                       block root and state root at the checkpoint, considers them finalized, \
+                       and has not finalized more than 4 epochs past the checkpoint. The \
+                       providers are the --checkpoint-sync-url endpoints and the \
+                       --checkpoint-state files, if given. Defaults to a majority of the \
+                       providers.")
+                .value_name("N")
                 .takes_value(true)
-                .conflicts_with("checkpoint-state")
+                .requires("checkpoint-sync-url")
         )
         .arg(
             Arg::with_name("checkpoint-sync-url-timeout")
@@ -980,6 +1042,16 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("After a checkpoint sync, reconstruct historic states in the database. This requires syncing all the way back to genesis.")
                 .takes_value(false)
         )
+        .arg(
+            Arg::with_name("era-import-dir")
+                .long("era-import-dir")
+                .help("Import finalized blocks from the era files in this directory at startup, \
+                    instead of downloading them with backfill sync. The roots and signatures of \
+                    the blocks are verified. Blocks older than the oldest era file are downloaded \
+                    by backfill sync as usual.")
+                .value_name("DIR")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("validator-monitor-auto")
                 .long("validator-monitor-auto")
@@ -1096,6 +1168,27 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .default_value("250")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("attestation-packing")
+                .long("attestation-packing")
+                .value_name("STRATEGY")
+                .help("Set the algorithm used to select attestations for block proposals. The \
+                       `exact` strategy searches for the most profitable attestations within \
+                       the time budget set by --attestation-packing-time-budget, falling back \
+                       to the best solution found, which is never worse than `greedy`.")
+                .possible_values(AttestationPackingStrategy::VARIANTS)
+                .default_value("greedy")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("attestation-packing-time-budget")
+                .long("attestation-packing-time-budget")
+                .value_name("MILLISECONDS")
+                .help("Set the maximum number of milliseconds to spend searching for \
+                       attestations with the `exact` attestation packing strategy.")
+                .default_value("100")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("paranoid-block-proposal")
                 .long("paranoid-block-proposal")
@@ -1162,6 +1255,14 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .requires("builder")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("builder-disable-ssz")
+                .long("builder-disable-ssz")
+                .help("Disable the use of SSZ to communicate with the builder. When present, \
+                       all requests and responses will be JSON-encoded.")
+                .requires("builder")
+                .takes_value(false)
+        )
         .arg(
             Arg::with_name("reset-payload-statuses")
                 .long("reset-payload-statuses")
@@ -1213,6 +1314,35 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     developers. This directory is not pruned, users should be careful to avoid \
                     filling up their disks.")
         )
+        .arg(
+            Arg::with_name("gossip-record-dir")
+            .long("gossip-record-dir")
+            .value_name("PATH")
+            .help("Record every gossip message received from the network, along with the time it \
+                    was received, its topic and the peer that sent it, to files in this \
+                    directory. Recordings can be replayed with `lcli replay-gossip`. This feature \
+                    is only recommended for developers.")
+            .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("gossip-record-max-file-size")
+            .long("gossip-record-max-file-size")
+            .value_name("MEGABYTES")
+            .help("The size at which a new gossip recording file is started.")
+            .requires("gossip-record-dir")
+            .default_value_if("gossip-record-dir", None, "256")
+            .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("gossip-record-max-files")
+            .long("gossip-record-max-files")
+            .value_name("COUNT")
+            .help("The number of gossip recording files to keep. The oldest files are deleted \
+                    once this number is exceeded.")
+            .requires("gossip-record-dir")
+            .default_value_if("gossip-record-dir", None, "8")
+            .takes_value(true)
+        )
         .arg(
             Arg::with_name("progressive-balances")
                 .long("progressive-balances")
@@ -1226,6 +1356,16 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
                 .possible_values(ProgressiveBalancesMode::VARIANTS)
         )
+        .arg(
+            Arg::with_name("beacon-processor-adaptive")
+                .long("beacon-processor-adaptive")
+                .help("Enable adaptive scheduling of work by the beacon processor. The processing \
+                        time of each type of work is measured and used to determine whether the \
+                        queued blocks and attestations can be processed before the next deadline in \
+                        the slot. If they can't, low-value work such as attestations that are only \
+                        being propagated and backfill batches is dropped or deferred.")
+                .takes_value(false)
+        )
         .arg(
             Arg::with_name("beacon-processor-max-workers")
                 .long("beacon-processor-max-workers")
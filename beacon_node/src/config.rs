@@ -6,7 +6,7 @@ use beacon_chain::TrustedSetup;
 use clap::ArgMatches;
 use clap_utils::flags::DISABLE_MALLOC_TUNING_FLAG;
 use clap_utils::parse_required;
-use client::{ClientConfig, ClientGenesis};
+use client::{CheckpointSszBytes, ClientConfig, ClientGenesis};
 use directory::{DEFAULT_BEACON_NODE_DIR, DEFAULT_NETWORK_DIR, DEFAULT_ROOT_DIR};
 use environment::RuntimeContext;
 use execution_layer::DEFAULT_JWT_FILE;
@@ -318,6 +318,8 @@ pub fn get_config<E: EthSpec>(
 
             el_config.builder_user_agent =
                 clap_utils::parse_optional(cli_args, "builder-user-agent")?;
+
+            el_config.builder_disable_ssz = cli_args.is_present("builder-disable-ssz");
         }
 
         if cli_args.is_present("builder-profit-threshold") {
@@ -400,6 +402,14 @@ pub fn get_config<E: EthSpec>(
             .map_err(|_| "historic-state-cache-size is not a valid integer".to_string())?;
     }
 
+    if let Some(backend) = clap_utils::parse_optional(cli_args, "beacon-node-backend")? {
+        client_config.store.backend = Some(backend);
+    }
+
+    if let Some(freezer_layout) = clap_utils::parse_optional(cli_args, "freezer-layout")? {
+        client_config.store.freezer_layout = freezer_layout;
+    }
+
     client_config.store.compact_on_init = cli_args.is_present("compact-db");
     if let Some(compact_on_prune) = cli_args.value_of("auto-compact-db") {
         client_config.store.compact_on_prune = compact_on_prune
@@ -421,6 +431,14 @@ pub fn get_config<E: EthSpec>(
         client_config.store.prune_blobs = prune_blobs;
     }
 
+    if cli_args.is_present("archive-blobs") {
+        if cli_args.occurrences_of("prune-blobs") > 0 && client_config.store.prune_blobs {
+            return Err("--archive-blobs is incompatible with --prune-blobs true".to_string());
+        }
+        client_config.chain.archive_blobs = true;
+        client_config.store.prune_blobs = false;
+    }
+
     if let Some(epochs_per_blob_prune) =
         clap_utils::parse_optional(cli_args, "epochs-per-blob-prune")?
     {
@@ -491,19 +509,29 @@ pub fn get_config<E: EthSpec>(
 
     let genesis_state_url_opt =
         clap_utils::parse_optional::<String>(cli_args, "genesis-state-url")?;
-    let checkpoint_sync_url_opt =
-        clap_utils::parse_optional::<String>(cli_args, "checkpoint-sync-url")?;
+    let checkpoint_sync_urls = cli_args
+        .value_of("checkpoint-sync-url")
+        .map(|urls| {
+            urls.split(',')
+                .map(|url| {
+                    SensitiveUrl::parse(url.trim())
+                        .map_err(|e| format!("Invalid checkpoint sync URL: {:?}", e))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
 
     // If the `--genesis-state-url` is defined, use that to download the
-    // genesis state bytes. If it's not defined, try `--checkpoint-sync-url`.
+    // genesis state bytes. If it's not defined, try the first `--checkpoint-sync-url`.
     client_config.genesis_state_url = if let Some(genesis_state_url) = genesis_state_url_opt {
         Some(genesis_state_url)
-    } else if let Some(checkpoint_sync_url) = checkpoint_sync_url_opt {
+    } else if let Some(checkpoint_sync_url) = checkpoint_sync_urls.first() {
         // If the checkpoint sync URL is going to be used to download the
         // genesis state, adopt the timeout from the checkpoint sync URL too.
         client_config.genesis_state_url_timeout =
             Duration::from_secs(client_config.chain.checkpoint_sync_url_timeout);
-        Some(checkpoint_sync_url)
+        Some(checkpoint_sync_url.full.to_string())
     } else {
         None
     };
@@ -533,16 +561,34 @@ pub fn get_config<E: EthSpec>(
             let anchor_block_bytes = read(initial_block_path)?;
             let anchor_blobs_bytes = opt_initial_blobs_path.map(read).transpose()?;
 
-            ClientGenesis::WeakSubjSszBytes {
-                anchor_state_bytes,
-                anchor_block_bytes,
-                anchor_blobs_bytes,
+            if checkpoint_sync_urls.is_empty() {
+                ClientGenesis::WeakSubjSszBytes {
+                    anchor_state_bytes,
+                    anchor_block_bytes,
+                    anchor_blobs_bytes,
+                    genesis_state_bytes: None,
+                }
+            } else {
+                // Check the local checkpoint against the remote beacon nodes.
+                let quorum =
+                    parse_checkpoint_sync_quorum(cli_args, checkpoint_sync_urls.len() + 1)?;
+                ClientGenesis::CheckpointSync {
+                    urls: checkpoint_sync_urls,
+                    local_checkpoint: Some(CheckpointSszBytes {
+                        anchor_state_bytes,
+                        anchor_block_bytes,
+                        anchor_blobs_bytes,
+                    }),
+                    quorum,
+                }
+            }
+        } else if !checkpoint_sync_urls.is_empty() {
+            let quorum = parse_checkpoint_sync_quorum(cli_args, checkpoint_sync_urls.len())?;
+            ClientGenesis::CheckpointSync {
+                urls: checkpoint_sync_urls,
+                local_checkpoint: None,
+                quorum,
             }
-        } else if let Some(remote_bn_url) = cli_args.value_of("checkpoint-sync-url") {
-            let url = SensitiveUrl::parse(remote_bn_url)
-                .map_err(|e| format!("Invalid checkpoint sync URL: {:?}", e))?;
-
-            ClientGenesis::CheckpointSyncUrl { url }
         } else {
             ClientGenesis::GenesisState
         }
@@ -561,6 +607,8 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.genesis_backfill = true;
     }
 
+    client_config.era_import_dir = clap_utils::parse_optional(cli_args, "era-import-dir")?;
+
     let raw_graffiti = if let Some(graffiti) = cli_args.value_of("graffiti") {
         if graffiti.len() > GRAFFITI_BYTES_LEN {
             return Err(format!(
@@ -799,6 +847,16 @@ pub fn get_config<E: EthSpec>(
 
     client_config.chain.paranoid_block_proposal = cli_args.is_present("paranoid-block-proposal");
 
+    if let Some(strategy) = clap_utils::parse_optional(cli_args, "attestation-packing")? {
+        client_config.chain.attestation_packing_strategy = strategy;
+    }
+
+    if let Some(time_budget) =
+        clap_utils::parse_optional(cli_args, "attestation-packing-time-budget")?
+    {
+        client_config.chain.attestation_packing_time_budget_ms = time_budget;
+    }
+
     /*
      * Builder fallback configs.
      */
@@ -836,6 +894,16 @@ pub fn get_config<E: EthSpec>(
         client_config.network.invalid_block_storage = Some(path);
     }
 
+    if let Some(path) = clap_utils::parse_optional(cli_args, "gossip-record-dir")? {
+        client_config.network.gossip_record_dir = Some(path);
+        client_config.network.gossip_record_max_file_size =
+            clap_utils::parse_required::<u64>(cli_args, "gossip-record-max-file-size")?
+                * 1024
+                * 1024;
+        client_config.network.gossip_record_max_files =
+            clap_utils::parse_required(cli_args, "gossip-record-max-files")?;
+    }
+
     if let Some(progressive_balances_mode) =
         clap_utils::parse_optional(cli_args, "progressive-balances")?
     {
@@ -863,6 +931,8 @@ pub fn get_config<E: EthSpec>(
         .beacon_processor
         .max_gossip_aggregate_batch_size =
         clap_utils::parse_required(cli_args, "beacon-processor-aggregate-batch-size")?;
+    client_config.beacon_processor.enable_adaptive_scheduling =
+        cli_args.is_present("beacon-processor-adaptive");
 
     Ok(client_config)
 }
@@ -1174,6 +1244,11 @@ pub fn set_network_config(
         config.disable_peer_scoring = true;
     }
 
+    if let Some(kb_per_second) = clap_utils::parse_optional::<u64>(cli_args, "peer-bandwidth-cap")?
+    {
+        config.peer_bandwidth_cap = Some(kb_per_second.saturating_mul(1000));
+    }
+
     if let Some(trusted_peers_str) = cli_args.value_of("trusted-peers") {
         config.trusted_peers = trusted_peers_str
             .split(',')
@@ -1452,6 +1527,8 @@ pub fn get_data_dir(cli_args: &ArgMatches) -> PathBuf {
 /// Get the `slots_per_restore_point` value to use for the database.
 ///
 /// Return `(sprp, set_explicitly)` where `set_explicitly` is `true` if the user provided the value.
+/// The default depends on the `--freezer-layout`, as the diffs layout stores far fewer restore
+/// points.
 pub fn get_slots_per_restore_point<E: EthSpec>(
     cli_args: &ArgMatches,
 ) -> Result<(u64, bool), String> {
@@ -1460,14 +1537,34 @@ pub fn get_slots_per_restore_point<E: EthSpec>(
     {
         Ok((slots_per_restore_point, true))
     } else {
-        let default = std::cmp::min(
-            E::slots_per_historical_root() as u64,
-            store::config::DEFAULT_SLOTS_PER_RESTORE_POINT,
-        );
+        let freezer_layout: Option<store::FreezerLayout> =
+            clap_utils::parse_optional(cli_args, "freezer-layout")?;
+        let default = match freezer_layout.unwrap_or_default() {
+            store::FreezerLayout::RestorePoints => std::cmp::min(
+                E::slots_per_historical_root() as u64,
+                store::config::DEFAULT_SLOTS_PER_RESTORE_POINT,
+            ),
+            store::FreezerLayout::Diffs => store::config::DEFAULT_DIFFS_SLOTS_PER_RESTORE_POINT,
+        };
         Ok((default, false))
     }
 }
 
+/// Get the number of checkpoint providers that must agree on the checkpoint, out of `providers`.
+///
+/// Defaults to a majority of the providers.
+fn parse_checkpoint_sync_quorum(cli_args: &ArgMatches, providers: usize) -> Result<usize, String> {
+    let quorum = clap_utils::parse_optional(cli_args, "checkpoint-sync-quorum")?
+        .unwrap_or(providers / 2 + 1);
+    if quorum == 0 || quorum > providers {
+        return Err(format!(
+            "Invalid --checkpoint-sync-quorum {quorum}, it must be between 1 and the number of \
+             checkpoint providers ({providers})"
+        ));
+    }
+    Ok(quorum)
+}
+
 /// Parses the `cli_value` as a comma-separated string of values to be parsed with `parser`.
 ///
 /// If there is more than one value, log a warning. If there are no values, return an error.
@@ -0,0 +1,177 @@
+//! A key-value store whose engine is selected at runtime by `StoreConfig::backend`.
+use crate::config::{DatabaseBackend, StoreConfigError};
+use crate::{
+    redb_store, ColumnIter, ColumnKeyIter, DBColumn, Error, ItemStore, Key, KeyValueStore,
+    KeyValueStoreOp, LevelDB, RawEntryIter, RawKeyIter, Redb,
+};
+use parking_lot::MutexGuard;
+use std::fs;
+use std::path::Path;
+use types::EthSpec;
+
+/// The name of the file which records the backend of the database within the database directory.
+pub const BACKEND_FILE_NAME: &str = "BACKEND";
+
+/// A file which LevelDB creates within every database directory.
+const LEVELDB_CURRENT_FILE_NAME: &str = "CURRENT";
+
+pub enum BeaconNodeBackend<E: EthSpec> {
+    LevelDb(LevelDB<E>),
+    Redb(Redb<E>),
+}
+
+impl<E: EthSpec> BeaconNodeBackend<E> {
+    /// Open a database at `path` using `backend`, creating a new database if one does not
+    /// already exist.
+    ///
+    /// The backend is recorded in the database directory, and an existing database is never
+    /// opened with a different backend.
+    pub fn open(backend: DatabaseBackend, path: &Path) -> Result<Self, Error> {
+        if let Some(on_disk) = Self::backend_on_disk(path)? {
+            if on_disk != backend {
+                return Err(StoreConfigError::MismatchedBackend {
+                    config: backend,
+                    on_disk,
+                }
+                .into());
+            }
+        }
+
+        let db = match backend {
+            DatabaseBackend::LevelDb => LevelDB::open(path).map(Self::LevelDb),
+            DatabaseBackend::Redb => Redb::open(path).map(Self::Redb),
+        }?;
+
+        let backend_file = path.join(BACKEND_FILE_NAME);
+        if !backend_file.exists() {
+            fs::write(&backend_file, backend.to_string()).map_err(|e| Error::DBError {
+                message: format!("Unable to write {:?}: {:?}", backend_file, e),
+            })?;
+        }
+        Ok(db)
+    }
+
+    /// Return `backend` if it is set, otherwise the backend of the first existing database in
+    /// `paths`, or the default backend if none of them hold a database.
+    ///
+    /// The databases of a beacon node share a backend, so an existing database determines the
+    /// backend of any database which is yet to be created.
+    pub fn resolve_backend(
+        backend: Option<DatabaseBackend>,
+        paths: &[&Path],
+    ) -> Result<DatabaseBackend, Error> {
+        if let Some(backend) = backend {
+            return Ok(backend);
+        }
+        for path in paths {
+            if let Some(on_disk) = Self::backend_on_disk(path)? {
+                return Ok(on_disk);
+            }
+        }
+        Ok(DatabaseBackend::default())
+    }
+
+    /// Return the backend of the database at `path`, or `None` if there is no database.
+    ///
+    /// Databases created before the backend was recorded are identified by their files.
+    fn backend_on_disk(path: &Path) -> Result<Option<DatabaseBackend>, Error> {
+        let backend_file = path.join(BACKEND_FILE_NAME);
+        if backend_file.exists() {
+            let backend = fs::read_to_string(&backend_file).map_err(|e| Error::DBError {
+                message: format!("Unable to read {:?}: {:?}", backend_file, e),
+            })?;
+            return backend
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|e| Error::DBError {
+                    message: format!("Invalid backend in {:?}: {:?}", backend_file, e),
+                });
+        }
+
+        if path.join(LEVELDB_CURRENT_FILE_NAME).exists() {
+            Ok(Some(DatabaseBackend::LevelDb))
+        } else if path.join(redb_store::DB_FILE_NAME).exists() {
+            Ok(Some(DatabaseBackend::Redb))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn backend(&self) -> DatabaseBackend {
+        match self {
+            Self::LevelDb(_) => DatabaseBackend::LevelDb,
+            Self::Redb(_) => DatabaseBackend::Redb,
+        }
+    }
+}
+
+/// Forward a method call to whichever database is wrapped.
+macro_rules! delegate {
+    ($self:ident, $db:ident => $e:expr) => {
+        match $self {
+            BeaconNodeBackend::LevelDb($db) => $e,
+            BeaconNodeBackend::Redb($db) => $e,
+        }
+    };
+}
+
+impl<E: EthSpec> KeyValueStore<E> for BeaconNodeBackend<E> {
+    fn get_bytes(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        delegate!(self, db => db.get_bytes(column, key))
+    }
+
+    fn put_bytes(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        delegate!(self, db => db.put_bytes(column, key, value))
+    }
+
+    fn put_bytes_sync(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        delegate!(self, db => db.put_bytes_sync(column, key, value))
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        delegate!(self, db => db.sync())
+    }
+
+    fn key_exists(&self, column: &str, key: &[u8]) -> Result<bool, Error> {
+        delegate!(self, db => db.key_exists(column, key))
+    }
+
+    fn key_delete(&self, column: &str, key: &[u8]) -> Result<(), Error> {
+        delegate!(self, db => db.key_delete(column, key))
+    }
+
+    fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        delegate!(self, db => db.do_atomically(batch))
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        delegate!(self, db => db.begin_rw_transaction())
+    }
+
+    fn compact_column(&self, column: DBColumn) -> Result<(), Error> {
+        delegate!(self, db => db.compact_column(column))
+    }
+
+    fn compact(&self) -> Result<(), Error> {
+        delegate!(self, db => db.compact())
+    }
+
+    fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
+        delegate!(self, db => db.iter_column_from(column, from))
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn, prefix: &[u8]) -> RawEntryIter {
+        delegate!(self, db => db.iter_raw_entries(column, prefix))
+    }
+
+    fn iter_raw_keys(&self, column: DBColumn, prefix: &[u8]) -> RawKeyIter {
+        delegate!(self, db => db.iter_raw_keys(column, prefix))
+    }
+
+    fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
+        delegate!(self, db => db.iter_column_keys(column))
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for BeaconNodeBackend<E> {}
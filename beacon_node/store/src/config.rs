@@ -3,11 +3,13 @@ use serde::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use std::num::NonZeroUsize;
+use strum::{Display, EnumString, EnumVariantNames};
 use types::non_zero_usize::new_non_zero_usize;
 use types::{EthSpec, MinimalEthSpec};
 
 pub const PREV_DEFAULT_SLOTS_PER_RESTORE_POINT: u64 = 2048;
 pub const DEFAULT_SLOTS_PER_RESTORE_POINT: u64 = 8192;
+pub const DEFAULT_DIFFS_SLOTS_PER_RESTORE_POINT: u64 = 262144;
 pub const DEFAULT_BLOCK_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(5);
 pub const DEFAULT_HISTORIC_STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(1);
 pub const DEFAULT_EPOCHS_PER_BLOB_PRUNE: u64 = 1;
@@ -37,6 +39,82 @@ pub struct StoreConfig {
     /// The margin for blob pruning in epochs. The oldest blobs are pruned up until
     /// data_availability_boundary - blob_prune_margin_epochs. Default: 0.
     pub blob_prune_margin_epochs: u64,
+    /// The key-value store backing the hot, cold and blobs databases.
+    ///
+    /// If `None`, the backend of the existing database is used, or the default backend for a new
+    /// database.
+    pub backend: Option<DatabaseBackend>,
+    /// How historic states are laid out in the freezer database.
+    pub freezer_layout: FreezerLayout,
+}
+
+/// The on-disk database engine used by the beacon node.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    EnumVariantNames,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum DatabaseBackend {
+    #[default]
+    LevelDb,
+    Redb,
+}
+
+/// The layout of historic states in the freezer database.
+///
+/// The layout of an existing database is recorded on disk, and can only be changed by migrating
+/// the database with `lighthouse db migrate-freezer`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    Display,
+    EnumString,
+    EnumVariantNames,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+#[ssz(enum_behaviour = "tag")]
+pub enum FreezerLayout {
+    /// Full states are stored at each restore point, and all other states are reconstructed by
+    /// replaying blocks.
+    #[default]
+    RestorePoints,
+    /// Each epoch boundary state between restore points is stored as a compressed diff against an
+    /// earlier epoch boundary state (see `state_diff`), allowing restore points to be much further
+    /// apart. Other states are reconstructed by replaying at most an epoch of blocks.
+    Diffs,
+}
+
+impl StoreItem for FreezerLayout {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
 }
 
 /// Variant of `StoreConfig` that gets written to disk. Contains immutable configuration params.
@@ -47,7 +125,14 @@ pub struct OnDiskStoreConfig {
 
 #[derive(Debug, Clone)]
 pub enum StoreConfigError {
-    MismatchedSlotsPerRestorePoint { config: u64, on_disk: u64 },
+    MismatchedSlotsPerRestorePoint {
+        config: u64,
+        on_disk: u64,
+    },
+    MismatchedBackend {
+        config: DatabaseBackend,
+        on_disk: DatabaseBackend,
+    },
 }
 
 impl Default for StoreConfig {
@@ -64,6 +149,8 @@ impl Default for StoreConfig {
             prune_blobs: true,
             epochs_per_blob_prune: DEFAULT_EPOCHS_PER_BLOB_PRUNE,
             blob_prune_margin_epochs: DEFAULT_BLOB_PUNE_MARGIN_EPOCHS,
+            backend: None,
+            freezer_layout: FreezerLayout::default(),
         }
     }
 }
@@ -0,0 +1,562 @@
+//! Reading and writing of era files, the format shared by consensus clients for archiving
+//! finalized history.
+//!
+//! An era file is a sequence of e2store records: an 8 byte header (2 byte type, 4 byte little
+//! endian length and 2 reserved bytes) followed by the record data. Era `N` contains the blocks
+//! of the `SLOTS_PER_HISTORICAL_ROOT` slots preceding slot `N * SLOTS_PER_HISTORICAL_ROOT`,
+//! followed by the state at that slot:
+//!
+//! ```text
+//! Version | block* | state | block slot index (era > 0) | state slot index
+//! ```
+//!
+//! Blocks and states are stored as snappy framed SSZ. A slot index records the offset of the
+//! record for each slot relative to the start of the index record, or 0 for skipped slots.
+use crate::Error;
+use ssz::{Decode, Encode};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use tree_hash::TreeHash;
+use types::{BeaconState, ChainSpec, EthSpec, SignedBeaconBlock, Slot};
+
+pub const VERSION: [u8; 2] = [0x65, 0x32];
+pub const COMPRESSED_SIGNED_BEACON_BLOCK: [u8; 2] = [0x01, 0x00];
+pub const COMPRESSED_BEACON_STATE: [u8; 2] = [0x02, 0x00];
+pub const SLOT_INDEX: [u8; 2] = [0x69, 0x32];
+
+/// The size of an e2store record header.
+const HEADER_SIZE: usize = 8;
+
+/// The extension of era file names.
+pub const ERA_FILE_EXTENSION: &str = "era";
+
+#[derive(Debug)]
+pub enum EraError {
+    Io(io::Error),
+    SszDecodeError(ssz::DecodeError),
+    /// A record header has non-zero reserved bytes.
+    InvalidRecordHeader {
+        record_type: [u8; 2],
+    },
+    /// The record data doesn't fit in an e2store record.
+    RecordTooLarge {
+        len: usize,
+    },
+    /// The file doesn't start with a version record.
+    MissingVersion,
+    /// The file ended before a state record was found.
+    MissingState,
+    /// The block slot index is malformed, or points at a record which isn't the block for its
+    /// slot.
+    InvalidSlotIndex,
+    /// A block written to era `era` is not within its slot range.
+    BlockOutsideEra {
+        slot: Slot,
+        era: u64,
+    },
+    /// Blocks were not written in strictly ascending slot order.
+    BlockOutOfOrder {
+        slot: Slot,
+        previous_slot: Slot,
+    },
+    /// The state written to era `era` is not at its last slot.
+    WrongStateSlot {
+        slot: Slot,
+        era: u64,
+    },
+}
+
+impl From<io::Error> for EraError {
+    fn from(e: io::Error) -> Self {
+        EraError::Io(e)
+    }
+}
+
+impl From<ssz::DecodeError> for EraError {
+    fn from(e: ssz::DecodeError) -> Self {
+        EraError::SszDecodeError(e)
+    }
+}
+
+impl From<EraError> for Error {
+    fn from(e: EraError) -> Self {
+        Error::EraError(e)
+    }
+}
+
+/// The slot of the state stored in era `era`.
+pub fn era_state_slot<E: EthSpec>(era: u64) -> Slot {
+    Slot::new(era * E::slots_per_historical_root() as u64)
+}
+
+/// The slot range of the blocks stored in era `era`, which is empty for era 0.
+pub fn era_block_slots<E: EthSpec>(era: u64) -> std::ops::Range<Slot> {
+    era_state_slot::<E>(era.saturating_sub(1))..era_state_slot::<E>(era)
+}
+
+/// The name of the file for era `era`, whose state is `state`.
+///
+/// The name is `<config-name>-<era>-<short-historical-root>.era`, where the short historical root
+/// is the first 4 bytes of the last historical root of the state, or of the genesis validators
+/// root for era 0.
+pub fn era_file_name<E: EthSpec>(config_name: &str, era: u64, state: &BeaconState<E>) -> String {
+    let root = if era == 0 {
+        state.genesis_validators_root()
+    } else if let Some(summary) = state
+        .historical_summaries()
+        .ok()
+        .and_then(|summaries| summaries.last())
+    {
+        summary.tree_hash_root()
+    } else {
+        state.historical_roots().last().copied().unwrap_or_default()
+    };
+    let short_root = root.as_bytes()[..4]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("{config_name}-{era:05}-{short_root}.{ERA_FILE_EXTENSION}")
+}
+
+/// Parse an era file name into its config name and era number.
+pub fn parse_era_file_name(file_name: &str) -> Option<(&str, u64)> {
+    let stem = file_name
+        .strip_suffix(ERA_FILE_EXTENSION)?
+        .strip_suffix('.')?;
+    let mut parts = stem.rsplitn(3, '-');
+    let _short_root = parts.next()?;
+    let era = parts.next()?;
+    let config_name = parts.next()?;
+    if era.len() != 5 {
+        return None;
+    }
+    Some((config_name, era.parse().ok()?))
+}
+
+/// Writes a single era to `writer`.
+pub struct EraWriter<W: Write, E: EthSpec> {
+    writer: W,
+    era: u64,
+    /// The number of bytes written so far.
+    position: u64,
+    /// The position of the record of each block slot written so far.
+    block_positions: Vec<Option<u64>>,
+    _phantom: std::marker::PhantomData<E>,
+}
+
+impl<W: Write, E: EthSpec> EraWriter<W, E> {
+    /// Start writing era `era`.
+    pub fn new(writer: W, era: u64) -> Result<Self, EraError> {
+        let mut era_writer = Self {
+            writer,
+            era,
+            position: 0,
+            block_positions: vec![],
+            _phantom: <_>::default(),
+        };
+        era_writer.write_record(VERSION, &[])?;
+        Ok(era_writer)
+    }
+
+    /// Append a block. Blocks must be written in ascending slot order.
+    pub fn write_block(&mut self, block: &SignedBeaconBlock<E>) -> Result<(), EraError> {
+        let slots = era_block_slots::<E>(self.era);
+        let slot = block.slot();
+        if self.era == 0 || !slots.contains(&slot) {
+            return Err(EraError::BlockOutsideEra {
+                slot,
+                era: self.era,
+            });
+        }
+        let index = (slot - slots.start).as_usize();
+        if index < self.block_positions.len() {
+            return Err(EraError::BlockOutOfOrder {
+                slot,
+                previous_slot: slots.start + self.block_positions.len() as u64 - 1,
+            });
+        }
+        self.block_positions.resize(index, None);
+        self.block_positions.push(Some(self.position));
+        self.write_compressed_record(COMPRESSED_SIGNED_BEACON_BLOCK, &block.as_ssz_bytes())
+    }
+
+    /// Append the state and the slot indices, and return the underlying writer.
+    pub fn finish(mut self, state: &BeaconState<E>) -> Result<W, EraError> {
+        if state.slot() != era_state_slot::<E>(self.era) {
+            return Err(EraError::WrongStateSlot {
+                slot: state.slot(),
+                era: self.era,
+            });
+        }
+        let state_position = self.position;
+        self.write_compressed_record(COMPRESSED_BEACON_STATE, &state.as_ssz_bytes())?;
+
+        if self.era > 0 {
+            let slots = era_block_slots::<E>(self.era);
+            let mut block_positions = std::mem::take(&mut self.block_positions);
+            block_positions.resize(E::slots_per_historical_root(), None);
+            self.write_slot_index(slots.start, &block_positions)?;
+        }
+        self.write_slot_index(state.slot(), &[Some(state_position)])?;
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_slot_index(
+        &mut self,
+        start_slot: Slot,
+        positions: &[Option<u64>],
+    ) -> Result<(), EraError> {
+        let index_position = self.position;
+        let mut data = Vec::with_capacity((positions.len() + 2) * 8);
+        data.extend_from_slice(&(start_slot.as_u64() as i64).to_le_bytes());
+        for position in positions.iter().copied() {
+            let offset = position.map_or(0, |position| position as i64 - index_position as i64);
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        data.extend_from_slice(&(positions.len() as i64).to_le_bytes());
+        self.write_record(SLOT_INDEX, &data)
+    }
+
+    fn write_compressed_record(
+        &mut self,
+        record_type: [u8; 2],
+        ssz: &[u8],
+    ) -> Result<(), EraError> {
+        let mut data = vec![];
+        {
+            let mut encoder = snap::write::FrameEncoder::new(&mut data);
+            encoder.write_all(ssz)?;
+            encoder.flush()?;
+        }
+        self.write_record(record_type, &data)
+    }
+
+    fn write_record(&mut self, record_type: [u8; 2], data: &[u8]) -> Result<(), EraError> {
+        let len =
+            u32::try_from(data.len()).map_err(|_| EraError::RecordTooLarge { len: data.len() })?;
+        let mut header = [0; HEADER_SIZE];
+        header[0..2].copy_from_slice(&record_type);
+        header[2..6].copy_from_slice(&len.to_le_bytes());
+        self.writer.write_all(&header)?;
+        self.writer.write_all(data)?;
+        self.position += (HEADER_SIZE + data.len()) as u64;
+        Ok(())
+    }
+}
+
+/// Reads the blocks and state of an era from `reader`.
+pub struct EraReader<R: Read, E: EthSpec> {
+    reader: R,
+    /// Set once the version record has been read.
+    started: bool,
+    _phantom: std::marker::PhantomData<E>,
+}
+
+impl<R: Read, E: EthSpec> EraReader<R, E> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            started: false,
+            _phantom: <_>::default(),
+        }
+    }
+
+    /// Read the next block, or return `None` once all blocks have been read.
+    pub fn next_block(
+        &mut self,
+        spec: &ChainSpec,
+    ) -> Result<Option<SignedBeaconBlock<E>>, EraError> {
+        while let Some((record_type, data)) = self.next_record()? {
+            match record_type {
+                COMPRESSED_SIGNED_BEACON_BLOCK => {
+                    let ssz = decompress(&data)?;
+                    return Ok(Some(SignedBeaconBlock::from_ssz_bytes(&ssz, spec)?));
+                }
+                // The blocks are followed by the state.
+                COMPRESSED_BEACON_STATE => return Ok(None),
+                _ => (),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Read the state, skipping any blocks that haven't been read yet.
+    pub fn read_state(mut self, spec: &ChainSpec) -> Result<BeaconState<E>, EraError> {
+        while let Some((record_type, data)) = self.next_record()? {
+            if record_type == COMPRESSED_BEACON_STATE {
+                let ssz = decompress(&data)?;
+                return Ok(BeaconState::from_ssz_bytes(&ssz, spec)?);
+            }
+        }
+        Err(EraError::MissingState)
+    }
+
+    /// Read the next record, or return `None` at the end of the file.
+    fn next_record(&mut self) -> Result<Option<([u8; 2], Vec<u8>)>, EraError> {
+        let mut header = [0; HEADER_SIZE];
+        let mut filled = 0;
+        while filled < HEADER_SIZE {
+            match self.reader.read(&mut header[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let record_type = [header[0], header[1]];
+        if header[6..8] != [0, 0] {
+            return Err(EraError::InvalidRecordHeader { record_type });
+        }
+        if !self.started && record_type != VERSION {
+            return Err(EraError::MissingVersion);
+        }
+        self.started = true;
+
+        let len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]);
+        let mut data = vec![0; len as usize];
+        self.reader.read_exact(&mut data)?;
+        Ok(Some((record_type, data)))
+    }
+}
+
+impl<R: Read + Seek, E: EthSpec> EraReader<R, E> {
+    /// Read the block slot index of era `era`.
+    ///
+    /// Return the position of the block record for each slot of the era, or `None` for skipped
+    /// slots.
+    pub fn block_index(&mut self, era: u64) -> Result<Vec<Option<u64>>, EraError> {
+        // Check the version record at the start of the file.
+        self.reader.seek(SeekFrom::Start(0))?;
+        self.started = false;
+        self.next_record()?.ok_or(EraError::MissingVersion)?;
+
+        // The block index precedes the state index, which is the last record of the file.
+        let count = E::slots_per_historical_root();
+        let state_index_len = HEADER_SIZE + 3 * 8;
+        let block_index_len = HEADER_SIZE + (count + 2) * 8;
+        let end = self.reader.seek(SeekFrom::End(0))?;
+        let index_position = end
+            .checked_sub((state_index_len + block_index_len) as u64)
+            .ok_or(EraError::InvalidSlotIndex)?;
+        self.reader.seek(SeekFrom::Start(index_position))?;
+        let (record_type, data) = self.next_record()?.ok_or(EraError::InvalidSlotIndex)?;
+        if record_type != SLOT_INDEX || data.len() != (count + 2) * 8 {
+            return Err(EraError::InvalidSlotIndex);
+        }
+
+        let read_i64 = |i: usize| {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(&data[i * 8..(i + 1) * 8]);
+            i64::from_le_bytes(bytes)
+        };
+        if read_i64(0) != era_block_slots::<E>(era).start.as_u64() as i64
+            || read_i64(count + 1) != count as i64
+        {
+            return Err(EraError::InvalidSlotIndex);
+        }
+        (1..=count)
+            .map(|i| match read_i64(i) {
+                0 => Ok(None),
+                offset => u64::try_from(index_position as i64 + offset)
+                    .ok()
+                    .filter(|position| *position < index_position)
+                    .map(Some)
+                    .ok_or(EraError::InvalidSlotIndex),
+            })
+            .collect()
+    }
+
+    /// Read the block at `position`, as listed in the block slot index for `slot`.
+    pub fn read_block_at(
+        &mut self,
+        position: u64,
+        slot: Slot,
+        spec: &ChainSpec,
+    ) -> Result<SignedBeaconBlock<E>, EraError> {
+        self.reader.seek(SeekFrom::Start(position))?;
+        match self.next_record()? {
+            Some((COMPRESSED_SIGNED_BEACON_BLOCK, data)) => {
+                let ssz = decompress(&data)?;
+                let block = SignedBeaconBlock::from_ssz_bytes(&ssz, spec)?;
+                if block.slot() != slot {
+                    return Err(EraError::InvalidSlotIndex);
+                }
+                Ok(block)
+            }
+            _ => Err(EraError::InvalidSlotIndex),
+        }
+    }
+}
+
+fn decompress(data: &[u8]) -> Result<Vec<u8>, io::Error> {
+    let mut ssz = vec![];
+    snap::read::FrameDecoder::new(data).read_to_end(&mut ssz)?;
+    Ok(ssz)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::{BeaconBlock, Eth1Data, MinimalEthSpec, Signature};
+
+    type E = MinimalEthSpec;
+
+    fn block(slot: u64, spec: &ChainSpec) -> SignedBeaconBlock<E> {
+        let mut block = BeaconBlock::empty(spec);
+        *block.slot_mut() = Slot::new(slot);
+        SignedBeaconBlock::from_block(block, Signature::empty())
+    }
+
+    fn state(slot: u64, spec: &ChainSpec) -> BeaconState<E> {
+        let mut state = BeaconState::<E>::new(0, Eth1Data::default(), spec);
+        *state.slot_mut() = Slot::new(slot);
+        state
+    }
+
+    fn read_i64(bytes: &[u8], offset: usize) -> i64 {
+        i64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+    }
+
+    #[test]
+    fn era_round_trip() {
+        let spec = E::default_spec();
+        let era = 2;
+        let slots = era_block_slots::<E>(era);
+        let blocks = [
+            slots.start.as_u64(),
+            slots.start.as_u64() + 3,
+            slots.end.as_u64() - 1,
+        ]
+        .map(|slot| block(slot, &spec));
+        let state = state(slots.end.as_u64(), &spec);
+
+        let mut writer = EraWriter::<_, E>::new(vec![], era).unwrap();
+        for block in &blocks {
+            writer.write_block(block).unwrap();
+        }
+        let bytes = writer.finish(&state).unwrap();
+
+        let mut reader = EraReader::<_, E>::new(bytes.as_slice());
+        for expected in &blocks {
+            assert_eq!(reader.next_block(&spec).unwrap().as_ref(), Some(expected));
+        }
+        assert_eq!(reader.next_block(&spec).unwrap(), None);
+        let reader = EraReader::<_, E>::new(bytes.as_slice());
+        assert_eq!(reader.read_state(&spec).unwrap(), state);
+
+        // The blocks can also be read through the block index.
+        let mut reader = EraReader::<_, E>::new(std::io::Cursor::new(&bytes));
+        let positions = reader.block_index(era).unwrap();
+        assert_eq!(positions.len(), E::slots_per_historical_root());
+        for (i, position) in positions.into_iter().enumerate() {
+            let slot = slots.start + i as u64;
+            match blocks.iter().find(|block| block.slot() == slot) {
+                Some(expected) => assert_eq!(
+                    &reader
+                        .read_block_at(position.unwrap(), slot, &spec)
+                        .unwrap(),
+                    expected
+                ),
+                None => assert_eq!(position, None),
+            }
+        }
+        assert!(matches!(
+            reader.block_index(era + 1),
+            Err(EraError::InvalidSlotIndex)
+        ));
+
+        // The state index is the last record, and points at the state record.
+        let state_index_len = HEADER_SIZE + 3 * 8;
+        let state_index = &bytes[bytes.len() - state_index_len..];
+        assert_eq!(state_index[0..2], SLOT_INDEX);
+        assert_eq!(
+            read_i64(state_index, HEADER_SIZE),
+            slots.end.as_u64() as i64
+        );
+        assert_eq!(read_i64(state_index, HEADER_SIZE + 16), 1);
+        let state_position =
+            (bytes.len() - state_index_len) as i64 + read_i64(state_index, HEADER_SIZE + 8);
+        assert_eq!(
+            bytes[state_position as usize..state_position as usize + 2],
+            COMPRESSED_BEACON_STATE
+        );
+
+        // The block index precedes it, with an offset for every slot of the era.
+        let count = E::slots_per_historical_root();
+        let block_index_len = HEADER_SIZE + (count + 2) * 8;
+        let block_index_position = bytes.len() - state_index_len - block_index_len;
+        let block_index = &bytes[block_index_position..bytes.len() - state_index_len];
+        assert_eq!(block_index[0..2], SLOT_INDEX);
+        assert_eq!(
+            read_i64(block_index, HEADER_SIZE),
+            slots.start.as_u64() as i64
+        );
+        assert_eq!(
+            read_i64(block_index, HEADER_SIZE + (count + 1) * 8),
+            count as i64
+        );
+        for i in 0..count {
+            let offset = read_i64(block_index, HEADER_SIZE + (i + 1) * 8);
+            let slot = slots.start + i as u64;
+            if blocks.iter().any(|block| block.slot() == slot) {
+                let position = (block_index_position as i64 + offset) as usize;
+                assert_eq!(
+                    bytes[position..position + 2],
+                    COMPRESSED_SIGNED_BEACON_BLOCK
+                );
+            } else {
+                assert_eq!(offset, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn genesis_era_has_no_blocks() {
+        let spec = E::default_spec();
+        let mut writer = EraWriter::<_, E>::new(vec![], 0).unwrap();
+        assert!(matches!(
+            writer.write_block(&block(0, &spec)),
+            Err(EraError::BlockOutsideEra { .. })
+        ));
+        let bytes = writer.finish(&state(0, &spec)).unwrap();
+
+        let mut reader = EraReader::<_, E>::new(bytes.as_slice());
+        assert_eq!(reader.next_block(&spec).unwrap(), None);
+        // Version, state and a single slot index.
+        let state_index_len = HEADER_SIZE + 3 * 8;
+        assert_eq!(bytes[bytes.len() - state_index_len..][0..2], SLOT_INDEX);
+    }
+
+    #[test]
+    fn blocks_must_be_ascending() {
+        let spec = E::default_spec();
+        let mut writer = EraWriter::<_, E>::new(vec![], 1).unwrap();
+        writer.write_block(&block(5, &spec)).unwrap();
+        assert!(matches!(
+            writer.write_block(&block(5, &spec)),
+            Err(EraError::BlockOutOfOrder { .. })
+        ));
+        assert!(matches!(
+            writer.finish(&state(1, &spec)),
+            Err(EraError::WrongStateSlot { .. })
+        ));
+    }
+
+    #[test]
+    fn file_names() {
+        let spec = E::default_spec();
+        let state = state(0, &spec);
+        let name = era_file_name("mainnet", 0, &state);
+        assert_eq!(name, "mainnet-00000-00000000.era");
+        assert_eq!(parse_era_file_name(&name), Some(("mainnet", 0)));
+        assert_eq!(
+            parse_era_file_name("my-devnet-01234-abcdef01.era"),
+            Some(("my-devnet", 1234))
+        );
+        assert_eq!(parse_era_file_name("mainnet-1234-abcdef01.era"), None);
+        assert_eq!(parse_era_file_name("mainnet-01234-abcdef01.txt"), None);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::chunked_vector::ChunkError;
 use crate::config::StoreConfigError;
+use crate::era::EraError;
 use crate::hot_cold_store::HotColdDBError;
 use ssz::DecodeError;
 use state_processing::BlockReplayError;
@@ -49,6 +50,8 @@ pub enum Error {
     InvalidBytes,
     UnableToDowngrade,
     InconsistentFork(InconsistentFork),
+    StateDiffError(String),
+    EraError(EraError),
 }
 
 pub trait HandleUnavailable<T> {
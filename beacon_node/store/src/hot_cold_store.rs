@@ -1,28 +1,25 @@
 use crate::chunked_vector::{
     store_updated_vector, BlockRoots, HistoricalRoots, HistoricalSummaries, RandaoMixes, StateRoots,
 };
-use crate::config::{
-    OnDiskStoreConfig, StoreConfig, DEFAULT_SLOTS_PER_RESTORE_POINT,
-    PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
-};
+use crate::config::{FreezerLayout, OnDiskStoreConfig, StoreConfig};
 use crate::forwards_iter::{HybridForwardsBlockRootsIterator, HybridForwardsStateRootsIterator};
 use crate::impls::beacon_state::{get_full_state, store_full_state};
 use crate::iter::{BlockRootsIterator, ParentRootBlockIterator, RootsIterator};
 use crate::leveldb_store::BytesKey;
-use crate::leveldb_store::LevelDB;
 use crate::memory_store::MemoryStore;
 use crate::metadata::{
     AnchorInfo, BlobInfo, CompactionTimestamp, PruningCheckpoint, SchemaVersion, ANCHOR_INFO_KEY,
     BLOB_INFO_KEY, COMPACTION_TIMESTAMP_KEY, CONFIG_KEY, CURRENT_SCHEMA_VERSION,
-    PRUNING_CHECKPOINT_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY, STATE_UPPER_LIMIT_NO_RETAIN,
+    FREEZER_LAYOUT_KEY, PRUNING_CHECKPOINT_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY,
+    STATE_UPPER_LIMIT_NO_RETAIN,
 };
 use crate::metrics;
+use crate::state_diff::{diff_base_epoch_offset, StateDiff, StateDiffBase};
 use crate::{
-    get_key_for_col, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore, KeyValueStoreOp,
-    PartialBeaconState, StoreItem, StoreOp,
+    get_key_for_col, BeaconNodeBackend, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore,
+    KeyValueStore, KeyValueStoreOp, PartialBeaconState, StoreItem, StoreOp,
 };
 use itertools::process_results;
-use leveldb::iterator::LevelDBIterator;
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
@@ -32,7 +29,7 @@ use ssz_derive::{Decode, Encode};
 use state_processing::{
     BlockProcessingError, BlockReplayer, SlotProcessingError, StateProcessingStrategy,
 };
-use std::cmp::min;
+use std::cmp::{max, min};
 use std::convert::TryInto;
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
@@ -40,8 +37,15 @@ use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use types::blob_sidecar::BlobSidecarList;
+use types::non_zero_usize::new_non_zero_usize;
 use types::*;
 
+/// Number of state diff bases to keep in memory.
+///
+/// Storing the diffs of consecutive epochs only needs the bases of the last few epochs, and each
+/// base is about the size of the validator registry.
+const DIFF_BASE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(4);
+
 /// On-disk database that stores finalized states efficiently.
 ///
 /// Stores vector fields like the `block_roots` and `state_roots` separately, and only stores
@@ -70,6 +74,9 @@ pub struct HotColdDB<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
     block_cache: Mutex<BlockCache<E>>,
     /// LRU cache of replayed states.
     state_cache: Mutex<LruCache<Slot, BeaconState<E>>>,
+    /// LRU cache of the bases of recently stored or loaded frozen states, for computing and
+    /// applying state diffs.
+    diff_base_cache: Mutex<LruCache<Slot, Arc<StateDiffBase>>>,
     /// Chain spec.
     pub(crate) spec: ChainSpec,
     /// Logger.
@@ -145,6 +152,7 @@ pub enum HotColdDBError {
     BlockReplaySlotError(SlotProcessingError),
     BlockReplayBlockError(BlockProcessingError),
     MissingLowerLimitState(Slot),
+    MissingStateDiff(Slot),
     InvalidSlotsPerRestorePoint {
         slots_per_restore_point: u64,
         slots_per_historical_root: u64,
@@ -181,6 +189,7 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
             hot_db: MemoryStore::open(),
             block_cache: Mutex::new(BlockCache::new(config.block_cache_size)),
             state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
+            diff_base_cache: Mutex::new(LruCache::new(DIFF_BASE_CACHE_SIZE)),
             config,
             spec,
             log,
@@ -191,10 +200,14 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
     }
 }
 
-impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
+impl<E: EthSpec> HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>> {
     /// Open a new or existing database, with the given paths to the hot and cold DBs.
     ///
-    /// The `slots_per_restore_point` parameter must be a divisor of `SLOTS_PER_HISTORICAL_ROOT`.
+    /// All three databases are opened using the engine selected by `config.backend`, or if it is
+    /// unset, the engine of the existing databases.
+    ///
+    /// The `slots_per_restore_point` parameter must be a divisor of `SLOTS_PER_HISTORICAL_ROOT`,
+    /// unless the freezer uses the diffs layout.
     ///
     /// The `migrate_schema` function is passed in so that the parent `BeaconChain` can provide
     /// context and access `BeaconChain`-level code without creating a circular dependency.
@@ -207,30 +220,33 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
         spec: ChainSpec,
         log: Logger,
     ) -> Result<Arc<Self>, Error> {
-        Self::verify_slots_per_restore_point(config.slots_per_restore_point)?;
+        let backend = BeaconNodeBackend::<E>::resolve_backend(
+            config.backend,
+            &[hot_path, cold_path, blobs_db_path],
+        )?;
 
         let mut db = HotColdDB {
             split: RwLock::new(Split::default()),
             anchor_info: RwLock::new(None),
             blob_info: RwLock::new(BlobInfo::default()),
-            cold_db: LevelDB::open(cold_path)?,
-            blobs_db: LevelDB::open(blobs_db_path)?,
-            hot_db: LevelDB::open(hot_path)?,
+            cold_db: BeaconNodeBackend::open(backend, cold_path)?,
+            blobs_db: BeaconNodeBackend::open(backend, blobs_db_path)?,
+            hot_db: BeaconNodeBackend::open(backend, hot_path)?,
             block_cache: Mutex::new(BlockCache::new(config.block_cache_size)),
             state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
+            diff_base_cache: Mutex::new(LruCache::new(DIFF_BASE_CACHE_SIZE)),
             config,
             spec,
             log,
             _phantom: PhantomData,
         };
 
-        // Allow the slots-per-restore-point value to stay at the previous default if the config
-        // uses the new default. Don't error on a failed read because the config itself may need
-        // migrating.
+        // Allow the slots-per-restore-point value to stay at its on-disk value if it wasn't set
+        // explicitly, e.g. because the default has changed or differs between freezer layouts.
+        // Don't error on a failed read because the config itself may need migrating.
         if let Ok(Some(disk_config)) = db.load_config() {
             if !db.config.slots_per_restore_point_set_explicitly
-                && disk_config.slots_per_restore_point == PREV_DEFAULT_SLOTS_PER_RESTORE_POINT
-                && db.config.slots_per_restore_point == DEFAULT_SLOTS_PER_RESTORE_POINT
+                && disk_config.slots_per_restore_point != db.config.slots_per_restore_point
             {
                 debug!(
                     db.log,
@@ -240,7 +256,7 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
                 );
 
                 // Mutate the in-memory config so that it's compatible.
-                db.config.slots_per_restore_point = PREV_DEFAULT_SLOTS_PER_RESTORE_POINT;
+                db.config.slots_per_restore_point = disk_config.slots_per_restore_point;
             }
         }
 
@@ -259,6 +275,31 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             );
         }
 
+        // The freezer layout can only be changed by migrating the database, so the layout on disk
+        // takes precedence over the config. Databases created before the layout was recorded use
+        // restore points.
+        let disk_freezer_layout = match db.load_freezer_layout()? {
+            Some(layout) => Some(layout),
+            None if db.load_split_partial()?.is_some() => Some(FreezerLayout::RestorePoints),
+            None => None,
+        };
+        if let Some(disk_freezer_layout) = disk_freezer_layout {
+            if disk_freezer_layout != db.config.freezer_layout {
+                warn!(
+                    db.log,
+                    "Ignoring freezer layout config in favour of on-disk value";
+                    "config" => %db.config.freezer_layout,
+                    "on_disk" => %disk_freezer_layout,
+                    "advice" => "use `lighthouse db migrate-freezer` to change the layout",
+                );
+                db.config.freezer_layout = disk_freezer_layout;
+            }
+        }
+        Self::verify_slots_per_restore_point(
+            db.config.slots_per_restore_point,
+            db.config.freezer_layout,
+        )?;
+
         // Open separate blobs directory if configured and same configuration was used on previous
         // run.
         let blob_info = db.load_blob_info()?;
@@ -317,6 +358,7 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             db.config.check_compatibility(&disk_config)?;
         }
         db.store_config()?;
+        db.store_freezer_layout(db.config.freezer_layout)?;
 
         // Run a garbage collection pass.
         db.remove_garbage()?;
@@ -333,23 +375,8 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
 
     /// Return an iterator over the state roots of all temporary states.
     pub fn iter_temporary_state_roots(&self) -> impl Iterator<Item = Result<Hash256, Error>> + '_ {
-        let column = DBColumn::BeaconStateTemporary;
-        let start_key =
-            BytesKey::from_vec(get_key_for_col(column.into(), Hash256::zero().as_bytes()));
-
-        let keys_iter = self.hot_db.keys_iter();
-        keys_iter.seek(&start_key);
-
-        keys_iter
-            .take_while(move |key| key.matches_column(column))
-            .map(move |bytes_key| {
-                bytes_key.remove_column(column).ok_or_else(|| {
-                    HotColdDBError::IterationError {
-                        unexpected_key: bytes_key,
-                    }
-                    .into()
-                })
-            })
+        self.hot_db
+            .iter_column_keys::<Hash256>(DBColumn::BeaconStateTemporary)
     }
 }
 
@@ -1144,7 +1171,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Store a pre-finalization state in the freezer database.
     ///
-    /// If the state doesn't lie on a restore point boundary then just its summary will be stored.
+    /// If the state doesn't lie on a restore point boundary then just its summary will be stored,
+    /// along with a diff if it lies on an epoch boundary and the freezer layout uses diffs.
     pub fn store_cold_state(
         &self,
         state_root: &Hash256,
@@ -1154,6 +1182,9 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         ops.push(ColdStateSummary { slot: state.slot() }.as_kv_store_op(*state_root));
 
         if state.slot() % self.config.slots_per_restore_point != 0 {
+            if self.is_cold_diff_slot(state.slot()) {
+                self.store_cold_state_diff(state, ops)?;
+            }
             return Ok(());
         }
 
@@ -1184,9 +1215,127 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         let restore_point_index = state.slot().as_u64() / self.config.slots_per_restore_point;
         self.store_restore_point_hash(restore_point_index, *state_root, ops);
 
+        // Cache the restore point as the base for the diffs that follow it.
+        if self.config.freezer_layout == FreezerLayout::Diffs {
+            self.diff_base_cache
+                .lock()
+                .put(state.slot(), Arc::new(StateDiffBase::from_state(state)));
+        }
+
+        Ok(())
+    }
+
+    /// Store a diff of an epoch boundary state against the state at its `diff_base_slot`.
+    ///
+    /// The diff's base must already be stored, so diffs between two restore points should be
+    /// stored in ascending slot order.
+    fn store_cold_state_diff(
+        &self,
+        state: &BeaconState<E>,
+        ops: &mut Vec<KeyValueStoreOp>,
+    ) -> Result<(), Error> {
+        trace!(
+            self.log,
+            "Creating state diff";
+            "slot" => state.slot(),
+        );
+
+        // 1. Store the diff.
+        let base = self.get_diff_base(self.diff_base_slot(state.slot()))?;
+        let diff = StateDiff::compute(&base, state);
+        ops.push(KeyValueStoreOp::PutKeyValue(
+            get_key_for_col(
+                DBColumn::BeaconStateDiff.into(),
+                &state.slot().as_u64().to_be_bytes(),
+            ),
+            diff.as_compressed_bytes()?,
+        ));
+
+        // 2. Store updated vector entries, as for a restore point.
+        let db = &self.cold_db;
+        store_updated_vector(BlockRoots, db, state, &self.spec, ops)?;
+        store_updated_vector(StateRoots, db, state, &self.spec, ops)?;
+        store_updated_vector(HistoricalRoots, db, state, &self.spec, ops)?;
+        store_updated_vector(RandaoMixes, db, state, &self.spec, ops)?;
+        store_updated_vector(HistoricalSummaries, db, state, &self.spec, ops)?;
+
+        // 3. Cache the state as the base for the diffs of the following epochs.
+        self.diff_base_cache
+            .lock()
+            .put(state.slot(), Arc::new(StateDiffBase::from_state(state)));
+
         Ok(())
     }
 
+    /// Return `true` if the frozen state at `slot` is stored as a diff.
+    pub(crate) fn is_cold_diff_slot(&self, slot: Slot) -> bool {
+        self.config.freezer_layout == FreezerLayout::Diffs
+            && slot % E::slots_per_epoch() == 0
+            && slot % self.config.slots_per_restore_point != 0
+    }
+
+    /// Return the slot of the state that the diff of the epoch boundary state at `slot` is
+    /// computed against.
+    ///
+    /// This is either an earlier diff or the preceding restore point, see `diff_base_epoch_offset`.
+    fn diff_base_slot(&self, slot: Slot) -> Slot {
+        let slots_per_epoch = E::slots_per_epoch();
+        let restore_point_slot = slot - slot % self.config.slots_per_restore_point;
+        let epoch_offset = (slot - restore_point_slot).as_u64() / slots_per_epoch;
+        restore_point_slot + diff_base_epoch_offset(epoch_offset) * slots_per_epoch
+    }
+
+    /// Load the stored diff of the epoch boundary state at `slot`.
+    fn load_state_diff(&self, slot: Slot) -> Result<StateDiff, Error> {
+        let diff_bytes = self
+            .cold_db
+            .get_bytes(
+                DBColumn::BeaconStateDiff.into(),
+                &slot.as_u64().to_be_bytes(),
+            )?
+            .ok_or(HotColdDBError::MissingStateDiff(slot))?;
+        StateDiff::from_compressed_bytes(&diff_bytes)
+    }
+
+    /// Get the diff base of the frozen restore point or diff state at `slot`.
+    ///
+    /// The base is rebuilt from the closest cached state in its hierarchy of diffs, or failing
+    /// that from its restore point.
+    fn get_diff_base(&self, slot: Slot) -> Result<Arc<StateDiffBase>, Error> {
+        // Walk down the hierarchy until a cached base or the restore point is found, keeping track
+        // of the diffs that need to be applied to it.
+        let mut diff_slots = vec![];
+        let mut ancestor_slot = slot;
+        let ancestor = loop {
+            if let Some(base) = self.diff_base_cache.lock().get(&ancestor_slot) {
+                break base.clone();
+            }
+            if ancestor_slot % self.config.slots_per_restore_point == 0 {
+                let restore_point_index =
+                    ancestor_slot.as_u64() / self.config.slots_per_restore_point;
+                let state_root = self.load_restore_point_hash(restore_point_index)?;
+                let partial_state = self.load_restore_point_partial_state(&state_root)?;
+                let base = Arc::new(StateDiffBase::from_partial_state(&partial_state));
+                self.diff_base_cache.lock().put(ancestor_slot, base.clone());
+                break base;
+            }
+            diff_slots.push(ancestor_slot);
+            ancestor_slot = self.diff_base_slot(ancestor_slot);
+        };
+
+        if diff_slots.is_empty() {
+            return Ok(ancestor);
+        }
+
+        let mut base = (*ancestor).clone();
+        for diff_slot in diff_slots.into_iter().rev() {
+            self.load_state_diff(diff_slot)?.apply_to_base(&mut base)?;
+        }
+        let base = Arc::new(base);
+        self.diff_base_cache.lock().put(slot, base.clone());
+        Ok(base)
+    }
+
     /// Try to load a pre-finalization state from the freezer database.
     ///
     /// Return `None` if no state with `state_root` lies in the freezer.
@@ -1210,6 +1359,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             if slot % self.config.slots_per_restore_point == 0 {
                 let restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
                 self.load_restore_point_by_index(restore_point_idx)
+            } else if self.is_cold_diff_slot(slot) {
+                self.load_cold_diff_state(slot)
             } else {
                 self.load_cold_intermediate_state(slot)
             }
@@ -1221,14 +1372,39 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Load a restore point state by its `state_root`.
     fn load_restore_point(&self, state_root: &Hash256) -> Result<BeaconState<E>, Error> {
+        let partial_state = self.load_restore_point_partial_state(state_root)?;
+        self.complete_partial_state(partial_state)
+    }
+
+    /// Load a restore point state by its `state_root`, without its vector fields.
+    fn load_restore_point_partial_state(
+        &self,
+        state_root: &Hash256,
+    ) -> Result<PartialBeaconState<E>, Error> {
         let partial_state_bytes = self
             .cold_db
             .get_bytes(DBColumn::BeaconState.into(), state_root.as_bytes())?
             .ok_or(HotColdDBError::MissingRestorePoint(*state_root))?;
-        let mut partial_state: PartialBeaconState<E> =
-            PartialBeaconState::from_ssz_bytes(&partial_state_bytes, &self.spec)?;
+        Ok(PartialBeaconState::from_ssz_bytes(
+            &partial_state_bytes,
+            &self.spec,
+        )?)
+    }
+
+    /// Load an epoch boundary state that is stored as a diff.
+    fn load_cold_diff_state(&self, slot: Slot) -> Result<BeaconState<E>, Error> {
+        let diff = self.load_state_diff(slot)?;
+        let mut base = (*self.get_diff_base(self.diff_base_slot(slot))?).clone();
+        let partial_state = diff.apply(&mut base, &self.spec)?;
+        self.diff_base_cache.lock().put(slot, Arc::new(base));
+        self.complete_partial_state(partial_state)
+    }
 
-        // Fill in the fields of the partial state.
+    /// Fill in the vector fields of a frozen partial state and convert it to a full state.
+    fn complete_partial_state(
+        &self,
+        mut partial_state: PartialBeaconState<E>,
+    ) -> Result<BeaconState<E>, Error> {
         partial_state.load_block_roots(&self.cold_db, &self.spec)?;
         partial_state.load_state_roots(&self.cold_db, &self.spec)?;
         partial_state.load_historical_roots(&self.cold_db, &self.spec)?;
@@ -1257,9 +1433,16 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         let low_restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
         let high_restore_point_idx = low_restore_point_idx + 1;
 
-        // Use low restore point as the base state.
-        let mut low_slot: Slot =
-            Slot::new(low_restore_point_idx * self.config.slots_per_restore_point);
+        // Use low restore point as the base state, or the epoch boundary state if it is stored as
+        // a diff.
+        let epoch_start_slot = slot
+            .epoch(E::slots_per_epoch())
+            .start_slot(E::slots_per_epoch());
+        let mut low_slot: Slot = if self.is_cold_diff_slot(epoch_start_slot) {
+            epoch_start_slot
+        } else {
+            Slot::new(low_restore_point_idx * self.config.slots_per_restore_point)
+        };
         let mut low_state: Option<BeaconState<E>> = None;
 
         // Try to get a more recent state from the cache to avoid massive blocks replay.
@@ -1273,16 +1456,30 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             }
         }
 
-        // If low_state is still None, use load_restore_point_by_index to load the state.
+        // If low_state is still None, load the restore point or diff at `low_slot`.
         let low_state = match low_state {
             Some(state) => state,
+            None if self.is_cold_diff_slot(low_slot) => self.load_cold_diff_state(low_slot)?,
             None => self.load_restore_point_by_index(low_restore_point_idx)?,
         };
 
         // Acquire the read lock, so that the split can't change while this is happening.
         let split = self.split.read_recursive();
 
-        let high_restore_point = self.get_restore_point(high_restore_point_idx, &split)?;
+        // With the diffs layout, use the following epoch boundary state as the high point, as
+        // restore points may be too far apart for their block roots to reach back to `slot`.
+        let high_restore_point = if self.config.freezer_layout == FreezerLayout::Diffs {
+            let high_slot = epoch_start_slot + E::slots_per_epoch();
+            if high_slot >= split.slot {
+                self.get_restore_point(high_restore_point_idx, &split)?
+            } else if self.is_cold_diff_slot(high_slot) {
+                self.load_cold_diff_state(high_slot)?
+            } else {
+                self.load_restore_point_by_index(high_restore_point_idx)?
+            }
+        } else {
+            self.get_restore_point(high_restore_point_idx, &split)?
+        };
 
         // 2. Load the blocks from the high restore point back to the low point.
         let blocks = self.load_blocks_to_replay(
@@ -1477,6 +1674,9 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 
     /// Fetch the slot of the most recently stored restore point (if any).
+    ///
+    /// With the diffs freezer layout, this is the slot of the most recently stored state diff if it
+    /// is more recent, as diffs store the vector fields of the state in the same way.
     pub fn get_latest_restore_point_slot(&self) -> Option<Slot> {
         let split_slot = self.get_split_slot();
         let anchor = self.get_anchor_info();
@@ -1492,6 +1692,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             .filter(|limit| *limit > 0)
         {
             Some(lower_limit)
+        } else if self.config.freezer_layout == FreezerLayout::Diffs {
+            Some((split_slot - 1) / E::slots_per_epoch() * E::slots_per_epoch())
         } else {
             Some(
                 (split_slot - 1) / self.config.slots_per_restore_point
@@ -1746,6 +1948,16 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.hot_db.put(&CONFIG_KEY, &self.config.as_disk_config())
     }
 
+    /// Load the freezer layout from disk.
+    fn load_freezer_layout(&self) -> Result<Option<FreezerLayout>, Error> {
+        self.hot_db.get(&FREEZER_LAYOUT_KEY)
+    }
+
+    /// Write the freezer layout to disk.
+    fn store_freezer_layout(&self, freezer_layout: FreezerLayout) -> Result<(), Error> {
+        self.hot_db.put(&FREEZER_LAYOUT_KEY, &freezer_layout)
+    }
+
     /// Load the split point from disk, sans block root.
     fn load_split_partial(&self) -> Result<Option<Split>, Error> {
         self.hot_db.get(&SPLIT_KEY)
@@ -1826,7 +2038,10 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Verify that a parsed config is valid.
     fn verify_config(config: &StoreConfig) -> Result<(), HotColdDBError> {
-        Self::verify_slots_per_restore_point(config.slots_per_restore_point)?;
+        Self::verify_slots_per_restore_point(
+            config.slots_per_restore_point,
+            config.freezer_layout,
+        )?;
         Self::verify_epochs_per_blob_prune(config.epochs_per_blob_prune)
     }
 
@@ -1843,11 +2058,18 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     ///
     /// (2) ensures that restore points align with hot state summaries, making it
     /// quick to migrate hot to cold.
-    fn verify_slots_per_restore_point(slots_per_restore_point: u64) -> Result<(), HotColdDBError> {
+    ///
+    /// With the diffs freezer layout only (2) is required, because every epoch boundary state is
+    /// stored as a diff, and these are always within range of each other's state root history.
+    fn verify_slots_per_restore_point(
+        slots_per_restore_point: u64,
+        freezer_layout: FreezerLayout,
+    ) -> Result<(), HotColdDBError> {
         let slots_per_historical_root = E::SlotsPerHistoricalRoot::to_u64();
         let slots_per_epoch = E::slots_per_epoch();
         if slots_per_restore_point > 0
-            && slots_per_historical_root % slots_per_restore_point == 0
+            && (freezer_layout == FreezerLayout::Diffs
+                || slots_per_historical_root % slots_per_restore_point == 0)
             && slots_per_restore_point % slots_per_epoch == 0
         {
             Ok(())
@@ -2218,8 +2440,15 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     /// slot, if any.  
     pub fn heal_freezer_block_roots_at_split(&self) -> Result<(), Error> {
         let split = self.get_split_info();
-        let last_restore_point_slot = (split.slot - 1) / self.config.slots_per_restore_point
-            * self.config.slots_per_restore_point;
+        // The split state only has the block roots of the last `SLOTS_PER_HISTORICAL_ROOT` slots,
+        // which may not reach back to the last restore point with the diffs freezer layout.
+        let last_restore_point_slot = max(
+            (split.slot - 1) / self.config.slots_per_restore_point
+                * self.config.slots_per_restore_point,
+            split
+                .slot
+                .saturating_sub(E::SlotsPerHistoricalRoot::to_u64()),
+        );
 
         // Load split state (which has access to block roots).
         let (_, split_state) = self
@@ -2341,7 +2570,9 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             DBColumn::BeaconHistoricalRoots,
             DBColumn::BeaconRandaoMixes,
             DBColumn::BeaconHistoricalSummaries,
+            DBColumn::BeaconStateDiff,
         ];
+        self.diff_base_cache.lock().clear();
 
         for column in columns {
             for res in self.cold_db.iter_column_keys::<Vec<u8>>(column) {
@@ -2381,6 +2612,99 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
         Ok(())
     }
+
+    /// Migrate the historic states in the freezer database to `freezer_layout`.
+    ///
+    /// Migrating to `FreezerLayout::Diffs` stores a diff for every frozen epoch boundary state,
+    /// whereas migrating to `FreezerLayout::RestorePoints` deletes them. The database must be
+    /// re-opened to use the new layout.
+    pub fn migrate_freezer_layout(&self, freezer_layout: FreezerLayout) -> Result<(), Error> {
+        if freezer_layout == self.config.freezer_layout {
+            info!(
+                self.log,
+                "Freezer layout unchanged";
+                "freezer_layout" => %freezer_layout,
+            );
+            return Ok(());
+        }
+
+        match freezer_layout {
+            FreezerLayout::Diffs => {
+                let slots_per_epoch = E::slots_per_epoch();
+                let slots_per_restore_point = self.config.slots_per_restore_point;
+                let split_slot = self.get_split_slot();
+
+                // Load the states in ascending order so that each state is replayed from the
+                // previous one in the state cache, rather than from its restore point.
+                for slot in (0..split_slot.as_u64())
+                    .step_by(slots_per_epoch as usize)
+                    .map(Slot::new)
+                {
+                    if slot % slots_per_restore_point == 0 {
+                        continue;
+                    }
+                    let state = match self.load_cold_state_by_slot(slot)? {
+                        Some(state) => state,
+                        // Not stored due to a gap in the historic state database.
+                        None => continue,
+                    };
+
+                    let mut ops = vec![];
+                    self.store_cold_state_diff(&state, &mut ops)?;
+                    self.cold_db.do_atomically(ops)?;
+
+                    if (slot + slots_per_epoch) % slots_per_restore_point == 0 {
+                        info!(
+                            self.log,
+                            "Freezer migration in progress";
+                            "slot" => slot,
+                            "remaining" => split_slot - slot,
+                        );
+                    }
+                }
+
+                self.store_freezer_layout(freezer_layout)?;
+            }
+            FreezerLayout::RestorePoints => {
+                // Restore points alone must be close enough together to rebuild every state.
+                Self::verify_slots_per_restore_point(
+                    self.config.slots_per_restore_point,
+                    freezer_layout,
+                )?;
+
+                // Switch layouts before deleting the diffs so that an interrupted migration never
+                // leaves the database referring to missing diffs.
+                self.store_freezer_layout(freezer_layout)?;
+
+                let mut cold_ops = vec![];
+                for res in self
+                    .cold_db
+                    .iter_column_keys::<Vec<u8>>(DBColumn::BeaconStateDiff)
+                {
+                    let key = res?;
+                    cold_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                        DBColumn::BeaconStateDiff.as_str(),
+                        &key,
+                    )));
+                }
+                info!(
+                    self.log,
+                    "Deleting state diffs";
+                    "num_diffs" => cold_ops.len(),
+                );
+                self.cold_db.do_atomically(cold_ops)?;
+                self.cold_db.compact()?;
+            }
+        }
+
+        info!(
+            self.log,
+            "Freezer migration complete";
+            "freezer_layout" => %freezer_layout,
+        );
+
+        Ok(())
+    }
 }
 
 /// Advance the split point of the store, moving new finalized states to the freezer.
@@ -2417,6 +2741,7 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
 
     let mut hot_db_ops = vec![];
     let mut cold_db_ops = vec![];
+    let mut diff_state_roots = vec![];
 
     // Chunk writer for the linear block roots in the freezer DB.
     // Start at the new upper limit because we iterate backwards.
@@ -2480,6 +2805,8 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
             store
                 .cold_db
                 .do_atomically(std::mem::take(&mut cold_db_ops))?;
+        } else if store.is_cold_diff_slot(slot) {
+            diff_state_roots.push(state_root);
         }
     }
 
@@ -2487,6 +2814,18 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
     block_root_writer.write(&mut cold_db_ops)?;
     store.cold_db.do_atomically(cold_db_ops)?;
 
+    // Store the epoch boundary diffs in ascending slot order, now that the restore points have
+    // been committed. Each diff is committed separately as the following diffs may be computed
+    // against it.
+    for state_root in diff_state_roots.into_iter().rev() {
+        let state: BeaconState<E> = get_full_state(&store.hot_db, &state_root, &store.spec)?
+            .ok_or(HotColdDBError::MissingStateToFreeze(state_root))?;
+
+        let mut diff_ops = vec![];
+        store.store_cold_state_diff(&state, &mut diff_ops)?;
+        store.cold_db.do_atomically(diff_ops)?;
+    }
+
     // Warning: Critical section.  We have to take care not to put any of the two databases in an
     //          inconsistent state if the OS process dies at any point during the freezing
     //          procedure.
@@ -2,7 +2,7 @@
 //!
 //! Provides the following stores:
 //!
-//! - `HotColdDB`: an on-disk store backed by leveldb. Used in production.
+//! - `HotColdDB`: an on-disk store backed by leveldb or redb. Used in production.
 //! - `MemoryStore`: an in-memory store backed by a hash-map. Used for testing.
 //!
 //! Provides a simple API for storing/retrieving all types that sometimes needs type-hints. See
@@ -10,10 +10,12 @@
 #[macro_use]
 extern crate lazy_static;
 
+mod beacon_node_backend;
 mod chunk_writer;
 pub mod chunked_iter;
 pub mod chunked_vector;
 pub mod config;
+pub mod era;
 pub mod errors;
 mod forwards_iter;
 mod garbage_collection;
@@ -25,15 +27,19 @@ pub mod metadata;
 pub mod metrics;
 mod partial_beacon_state;
 pub mod reconstruct;
+mod redb_store;
+mod state_diff;
 
 pub mod iter;
 
+pub use self::beacon_node_backend::BeaconNodeBackend;
 pub use self::chunk_writer::ChunkWriter;
-pub use self::config::StoreConfig;
+pub use self::config::{DatabaseBackend, FreezerLayout, StoreConfig};
 pub use self::hot_cold_store::{HotColdDB, HotStateSummary, Split};
 pub use self::leveldb_store::LevelDB;
 pub use self::memory_store::MemoryStore;
 pub use self::partial_beacon_state::PartialBeaconState;
+pub use self::redb_store::Redb;
 pub use crate::metadata::BlobInfo;
 pub use errors::Error;
 pub use impls::beacon_state::StorageContainer as BeaconStateStorageContainer;
@@ -41,7 +47,7 @@ pub use metadata::AnchorInfo;
 pub use metrics::scrape_for_metrics;
 use parking_lot::MutexGuard;
 use std::sync::Arc;
-use strum::{EnumString, IntoStaticStr};
+use strum::{EnumIter, EnumString, IntoStaticStr};
 pub use types::*;
 
 pub type ColumnIter<'a, K> = Box<dyn Iterator<Item = Result<(K, Vec<u8>), Error>> + 'a>;
@@ -214,7 +220,7 @@ pub enum StoreOp<'a, E: EthSpec> {
 }
 
 /// A unique column identifier.
-#[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr, EnumString, EnumIter)]
 pub enum DBColumn {
     /// For data related to the database itself.
     #[strum(serialize = "bma")]
@@ -260,6 +266,9 @@ pub enum DBColumn {
     BeaconRandaoMixes,
     #[strum(serialize = "dht")]
     DhtEnrs,
+    /// For the peers and IP addresses banned or trusted by the node operator.
+    #[strum(serialize = "ppr")]
+    PersistedPeers,
     /// For Optimistically Imported Merge Transition Blocks
     #[strum(serialize = "otb")]
     OptimisticTransitionBlock,
@@ -267,6 +276,12 @@ pub enum DBColumn {
     BeaconHistoricalSummaries,
     #[strum(serialize = "olc")]
     OverflowLRUCache,
+    /// For the best `LightClientUpdate` of each sync committee period, keyed by period.
+    #[strum(serialize = "lcu")]
+    LightClientUpdate,
+    /// For compressed diffs of epoch boundary states in the freezer database, keyed by slot.
+    #[strum(serialize = "bsd")]
+    BeaconStateDiff,
 }
 
 /// A block from the database, which might have an execution payload or not.
@@ -304,12 +319,15 @@ impl DBColumn {
             | Self::PubkeyCache
             | Self::BeaconRestorePoint
             | Self::DhtEnrs
+            | Self::PersistedPeers
             | Self::OptimisticTransitionBlock => 32,
             Self::BeaconBlockRoots
             | Self::BeaconStateRoots
             | Self::BeaconHistoricalRoots
             | Self::BeaconHistoricalSummaries
-            | Self::BeaconRandaoMixes => 8,
+            | Self::BeaconRandaoMixes
+            | Self::LightClientUpdate
+            | Self::BeaconStateDiff => 8,
         }
     }
 }
@@ -389,6 +407,106 @@ mod tests {
         test_impl(store);
     }
 
+    #[test]
+    fn redb() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let store = Redb::open(path).unwrap();
+
+        test_impl(store);
+    }
+
+    #[test]
+    fn redb_iter_column() {
+        let dir = tempdir().unwrap();
+        let store = Redb::<MinimalEthSpec>::open(dir.path()).unwrap();
+
+        let mut keys = (0..4).map(|_| Hash256::random()).collect::<Vec<_>>();
+        for key in &keys {
+            store.put(key, &StorableThing { a: 1, b: 42 }).unwrap();
+        }
+        // Items in other columns must not be returned.
+        store
+            .put_bytes(
+                DBColumn::BeaconState.into(),
+                Hash256::random().as_bytes(),
+                &[1],
+            )
+            .unwrap();
+
+        keys.sort();
+        let iter_keys = store
+            .iter_column_keys::<Hash256>(DBColumn::BeaconBlock)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(iter_keys, keys);
+    }
+
+    #[test]
+    fn beacon_node_backend() {
+        for backend in [DatabaseBackend::LevelDb, DatabaseBackend::Redb] {
+            let dir = tempdir().unwrap();
+            let store = BeaconNodeBackend::open(backend, dir.path()).unwrap();
+            assert_eq!(store.backend(), backend);
+
+            test_impl(store);
+        }
+    }
+
+    #[test]
+    fn beacon_node_backend_mismatch() {
+        let dir = tempdir().unwrap();
+        BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::LevelDb, dir.path()).unwrap();
+
+        // The database is never opened with a different backend.
+        assert!(matches!(
+            BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::Redb, dir.path()),
+            Err(Error::ConfigError(
+                config::StoreConfigError::MismatchedBackend {
+                    config: DatabaseBackend::Redb,
+                    on_disk: DatabaseBackend::LevelDb,
+                }
+            ))
+        ));
+        assert!(!dir.path().join(redb_store::DB_FILE_NAME).exists());
+
+        // Databases created before the backend was recorded are identified by their files.
+        std::fs::remove_file(dir.path().join(beacon_node_backend::BACKEND_FILE_NAME)).unwrap();
+        assert!(
+            BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::Redb, dir.path()).is_err()
+        );
+        BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::LevelDb, dir.path()).unwrap();
+        assert!(dir
+            .path()
+            .join(beacon_node_backend::BACKEND_FILE_NAME)
+            .exists());
+    }
+
+    #[test]
+    fn beacon_node_backend_resolve() {
+        type Backend = BeaconNodeBackend<MinimalEthSpec>;
+
+        let empty = tempdir().unwrap();
+        let redb = tempdir().unwrap();
+        Backend::open(DatabaseBackend::Redb, redb.path()).unwrap();
+
+        // A new database uses the default backend, and an existing database its own backend.
+        assert_eq!(
+            Backend::resolve_backend(None, &[empty.path()]).unwrap(),
+            DatabaseBackend::LevelDb
+        );
+        assert_eq!(
+            Backend::resolve_backend(None, &[empty.path(), redb.path()]).unwrap(),
+            DatabaseBackend::Redb
+        );
+
+        // A backend set by the user is never overridden.
+        assert_eq!(
+            Backend::resolve_backend(Some(DatabaseBackend::LevelDb), &[redb.path()]).unwrap(),
+            DatabaseBackend::LevelDb
+        );
+    }
+
     #[test]
     fn memorydb() {
         let store = MemoryStore::open();
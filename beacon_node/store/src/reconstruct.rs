@@ -7,6 +7,7 @@ use state_processing::{
     per_block_processing, per_slot_processing, BlockSignatureStrategy, ConsensusContext,
     StateProcessingStrategy, VerifyBlockRoot,
 };
+use std::cmp::min;
 use std::sync::Arc;
 use types::{EthSpec, Hash256};
 
@@ -36,6 +37,11 @@ where
         );
 
         let slots_per_restore_point = self.config.slots_per_restore_point;
+        // Log progress at each restore point, or more often if they are far apart.
+        let log_interval = min(
+            slots_per_restore_point,
+            E::slots_per_historical_root() as u64,
+        );
 
         // Iterate blocks from the state lower limit to the upper limit.
         let lower_limit_slot = anchor.state_lower_limit;
@@ -111,14 +117,21 @@ where
                 // Stage state for storage in freezer DB.
                 self.store_cold_state(&state_root, &state, &mut io_batch)?;
 
-                // If the slot lies on an epoch boundary, commit the batch and update the anchor.
-                if slot % slots_per_restore_point == 0 || slot + 1 == upper_limit_slot {
-                    info!(
-                        self.log,
-                        "State reconstruction in progress";
-                        "slot" => slot,
-                        "remaining" => upper_limit_slot - 1 - slot
-                    );
+                // If the slot lies on a restore point or state diff, commit the batch and update
+                // the anchor. Diffs are committed as they are stored because later diffs may be
+                // computed against them.
+                if slot % slots_per_restore_point == 0
+                    || self.is_cold_diff_slot(slot)
+                    || slot + 1 == upper_limit_slot
+                {
+                    if slot % log_interval == 0 || slot + 1 == upper_limit_slot {
+                        info!(
+                            self.log,
+                            "State reconstruction in progress";
+                            "slot" => slot,
+                            "remaining" => upper_limit_slot - 1 - slot
+                        );
+                    }
 
                     self.cold_db.do_atomically(std::mem::take(&mut io_batch))?;
 
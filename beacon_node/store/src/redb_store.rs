@@ -0,0 +1,286 @@
+use super::*;
+use crate::hot_cold_store::HotColdDBError;
+use crate::leveldb_store::BytesKey;
+use crate::metrics;
+use parking_lot::{Mutex, MutexGuard, RwLock};
+use redb::{Durability, ReadableTable, TableDefinition};
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// The name of the database file within the database directory.
+pub const DB_FILE_NAME: &str = "database.redb";
+
+/// All columns are stored in a single table, using the same `column ++ key` layout as LevelDB.
+const TABLE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("lighthouse");
+
+/// A wrapped redb database.
+///
+/// Like unsynced LevelDB writes, writes are committed with `Durability::Eventual`, so they survive
+/// a crash of the process but not of the OS. Each commit is atomic, so after a crash the database
+/// reverts to an earlier commit rather than being left inconsistent. `put_bytes_sync` and `sync`
+/// commit with `Durability::Immediate`, which also persists every earlier commit.
+pub struct Redb<E: EthSpec> {
+    /// The database is only locked for writing in order to compact it.
+    db: RwLock<redb::Database>,
+    /// A mutex to synchronise sensitive read-write transactions.
+    transaction_mutex: Mutex<()>,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> Redb<E> {
+    /// Open a database in the directory at `path`, creating a new database if one does not
+    /// already exist.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        std::fs::create_dir_all(path).map_err(|e| Error::DBError {
+            message: format!("Unable to create database directory {:?}: {:?}", path, e),
+        })?;
+
+        let db = redb::Database::create(path.join(DB_FILE_NAME)).map_err(redb_error)?;
+
+        // Create the table up-front so that read transactions never find it missing.
+        let write_txn = db.begin_write().map_err(redb_error)?;
+        write_txn.open_table(TABLE).map_err(redb_error)?;
+        write_txn.commit().map_err(redb_error)?;
+
+        Ok(Self {
+            db: RwLock::new(db),
+            transaction_mutex: Mutex::new(()),
+            _phantom: PhantomData,
+        })
+    }
+
+    fn write(
+        &self,
+        ops: impl IntoIterator<Item = KeyValueStoreOp>,
+        durability: Durability,
+    ) -> Result<(), Error> {
+        let db = self.db.read();
+        let mut write_txn = db.begin_write().map_err(redb_error)?;
+        write_txn.set_durability(durability);
+        {
+            let mut table = write_txn.open_table(TABLE).map_err(redb_error)?;
+            for op in ops {
+                match op {
+                    KeyValueStoreOp::PutKeyValue(key, value) => {
+                        table
+                            .insert(key.as_slice(), value.as_slice())
+                            .map_err(redb_error)?;
+                    }
+                    KeyValueStoreOp::DeleteKey(key) => {
+                        table.remove(key.as_slice()).map_err(redb_error)?;
+                    }
+                }
+            }
+        }
+        write_txn.commit().map_err(redb_error)
+    }
+
+    fn put_bytes_with_durability(
+        &self,
+        col: &str,
+        key: &[u8],
+        val: &[u8],
+        durability: Durability,
+    ) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_WRITE_COUNT);
+        metrics::inc_counter_by(&metrics::DISK_DB_WRITE_BYTES, val.len() as u64);
+        let timer = metrics::start_timer(&metrics::DISK_DB_WRITE_TIMES);
+
+        self.write(
+            [KeyValueStoreOp::PutKeyValue(column_key, val.to_vec())],
+            durability,
+        )
+        .map(|()| {
+            metrics::stop_timer(timer);
+        })
+    }
+
+    /// Iterate over all entries with keys greater than or equal to `start_key`, for as long as
+    /// `predicate` holds on the key, mapping each entry with `read`.
+    ///
+    /// Only the bytes copied by `read` are read from the entry.
+    fn iter_from_while<T: 'static>(
+        &self,
+        start_key: Vec<u8>,
+        predicate: impl Fn(&[u8]) -> bool + 'static,
+        read: impl Fn(&[u8], &[u8]) -> T + 'static,
+    ) -> Box<dyn Iterator<Item = Result<T, Error>>> {
+        let range = (|| {
+            let read_txn = self.db.read().begin_read().map_err(redb_error)?;
+            let table = read_txn.open_table(TABLE).map_err(redb_error)?;
+            table
+                .range::<&[u8]>(start_key.as_slice()..)
+                .map_err(redb_error)
+        })();
+
+        match range {
+            Ok(range) => Box::new(
+                range
+                    .map(|res| res.map_err(redb_error))
+                    .take_while(move |res| match res {
+                        Ok((key, _)) => predicate(key.value()),
+                        Err(_) => true,
+                    })
+                    .map(move |res| res.map(|(key, value)| read(key.value(), value.value()))),
+            ),
+            Err(e) => Box::new(std::iter::once(Err(e))),
+        }
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for Redb<E> {
+    /// Store some `value` in `column`, indexed with `key`.
+    fn put_bytes(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_durability(col, key, val, Durability::Eventual)
+    }
+
+    fn put_bytes_sync(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_durability(col, key, val, Durability::Immediate)
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        // An empty commit with immediate durability persists all earlier commits.
+        self.write([], Durability::Immediate)
+    }
+
+    /// Retrieve some bytes in `column` with `key`.
+    fn get_bytes(&self, col: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_READ_COUNT);
+        let timer = metrics::start_timer(&metrics::DISK_DB_READ_TIMES);
+
+        let read_txn = self.db.read().begin_read().map_err(redb_error)?;
+        let table = read_txn.open_table(TABLE).map_err(redb_error)?;
+        let value = table
+            .get(column_key.as_slice())
+            .map_err(redb_error)?
+            .map(|value| value.value().to_vec());
+
+        if let Some(bytes) = &value {
+            metrics::inc_counter_by(&metrics::DISK_DB_READ_BYTES, bytes.len() as u64);
+            metrics::stop_timer(timer);
+        }
+        Ok(value)
+    }
+
+    /// Return `true` if `key` exists in `column`.
+    fn key_exists(&self, col: &str, key: &[u8]) -> Result<bool, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_EXISTS_COUNT);
+
+        let read_txn = self.db.read().begin_read().map_err(redb_error)?;
+        let table = read_txn.open_table(TABLE).map_err(redb_error)?;
+        Ok(table
+            .get(column_key.as_slice())
+            .map_err(redb_error)?
+            .is_some())
+    }
+
+    /// Removes `key` from `column`.
+    fn key_delete(&self, col: &str, key: &[u8]) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_DELETE_COUNT);
+
+        self.write(
+            [KeyValueStoreOp::DeleteKey(column_key)],
+            Durability::Eventual,
+        )
+    }
+
+    fn do_atomically(&self, ops_batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        self.write(ops_batch, Durability::Eventual)
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        self.transaction_mutex.lock()
+    }
+
+    /// Compact the whole database, as all columns share a single table.
+    fn compact_column(&self, _column: DBColumn) -> Result<(), Error> {
+        self.db.write().compact().map_err(redb_error)?;
+        Ok(())
+    }
+
+    fn compact(&self) -> Result<(), Error> {
+        self.compact_column(DBColumn::BeaconState)
+    }
+
+    fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
+        let start_key = get_key_for_col(column.into(), from);
+
+        Box::new(
+            self.iter_from_while(
+                start_key,
+                move |key| key.starts_with(column.as_bytes()),
+                |key, value| (key.to_vec(), value.to_vec()),
+            )
+            .map(move |res| {
+                let (key, value) = res?;
+                let bytes_key = BytesKey::from_vec(key);
+                let key = bytes_key.remove_column_variable(column).ok_or_else(|| {
+                    HotColdDBError::IterationError {
+                        unexpected_key: bytes_key.clone(),
+                    }
+                })?;
+                Ok((K::from_bytes(key)?, value))
+            }),
+        )
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn, prefix: &[u8]) -> RawEntryIter {
+        let start_key = get_key_for_col(column.into(), prefix);
+        let key_prefix = start_key.clone();
+
+        self.iter_from_while(
+            start_key,
+            move |key| key.starts_with(&key_prefix),
+            move |key, value| (key[column.as_bytes().len()..].to_vec(), value.to_vec()),
+        )
+    }
+
+    fn iter_raw_keys(&self, column: DBColumn, prefix: &[u8]) -> RawKeyIter {
+        let start_key = get_key_for_col(column.into(), prefix);
+        let key_prefix = start_key.clone();
+
+        self.iter_from_while(
+            start_key,
+            move |key| key.starts_with(&key_prefix),
+            move |key, _| key[column.as_bytes().len()..].to_vec(),
+        )
+    }
+
+    /// Iterate through all keys in a particular column, without reading their values.
+    fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
+        let start_key = get_key_for_col(column.into(), &[]);
+
+        Box::new(
+            self.iter_from_while(
+                start_key,
+                move |key| key.starts_with(column.as_bytes()),
+                |key, _| key.to_vec(),
+            )
+            .map(move |res| {
+                let bytes_key = BytesKey::from_vec(res?);
+                let key = bytes_key.remove_column_variable(column).ok_or_else(|| {
+                    HotColdDBError::IterationError {
+                        unexpected_key: bytes_key.clone(),
+                    }
+                })?;
+                K::from_bytes(key)
+            }),
+        )
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for Redb<E> {}
+
+fn redb_error(e: impl Into<redb::Error>) -> Error {
+    Error::DBError {
+        message: format!("{:?}", e.into()),
+    }
+}
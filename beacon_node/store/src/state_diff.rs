@@ -0,0 +1,367 @@
+//! Compressed diffs of epoch boundary states, for the `FreezerLayout::Diffs` freezer layout.
+//!
+//! The bulk of a `BeaconState` is made up of the validator registry, balances and inactivity
+//! scores, which change very little from one epoch to the next. A `StateDiff` stores these fields
+//! relative to a `StateDiffBase` taken from an earlier frozen state, along with the remaining
+//! fields of the `PartialBeaconState` in full. Balance and inactivity score deltas are zigzag and
+//! varint encoded, so that most of them take one or two bytes. Like restore points, the vector
+//! fields (block roots, state roots, etc.) are stored separately in their chunked columns.
+//!
+//! The diffs between two restore points form a hierarchy: the diff `n` epochs after a restore
+//! point is computed against the state `diff_base_epoch_offset(n)` epochs after it. Most diffs
+//! therefore span only a few epochs, and any state can be rebuilt from its restore point by
+//! applying at most `log2` of the number of epochs per restore point diffs.
+use crate::{Error, PartialBeaconState};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use types::{BeaconState, ChainSpec, EthSpec, Validator, VariableList};
+
+/// Return the offset in epochs from its restore point of the base of the diff `epoch_offset`
+/// epochs after the restore point.
+///
+/// The base is found by clearing the lowest set bit of `epoch_offset`, so an offset of 0 refers to
+/// the restore point itself.
+pub fn diff_base_epoch_offset(epoch_offset: u64) -> u64 {
+    epoch_offset & epoch_offset.saturating_sub(1)
+}
+
+/// The fields of a frozen state that diffs are computed against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateDiffBase {
+    validators: Vec<Validator>,
+    balances: Vec<u64>,
+    inactivity_scores: Vec<u64>,
+}
+
+impl StateDiffBase {
+    pub fn from_state<E: EthSpec>(state: &BeaconState<E>) -> Self {
+        Self {
+            validators: state.validators().to_vec(),
+            balances: state.balances().to_vec(),
+            inactivity_scores: state
+                .inactivity_scores()
+                .map(|scores| scores.to_vec())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn from_partial_state<E: EthSpec>(partial_state: &PartialBeaconState<E>) -> Self {
+        Self {
+            validators: partial_state.validators().to_vec(),
+            balances: partial_state.balances().to_vec(),
+            inactivity_scores: partial_state
+                .inactivity_scores()
+                .map(|scores| scores.to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct StateDiff {
+    /// Varint-encoded indices of the validators that differ from the base, in ascending order.
+    ///
+    /// Each index is stored as its distance from the index following the previous one. Validators
+    /// that are absent from the base are always included.
+    changed_validator_indices: Vec<u8>,
+    changed_validators: Vec<Validator>,
+    /// Varint-encoded zigzag difference of each balance from the base balance.
+    balances: Vec<u8>,
+    /// Varint-encoded zigzag difference of each inactivity score from the base score.
+    ///
+    /// Empty prior to Altair.
+    inactivity_scores: Vec<u8>,
+    /// SSZ bytes of the `PartialBeaconState` with the diffed fields left empty.
+    partial_state: Vec<u8>,
+}
+
+impl StateDiff {
+    /// Compute the diff of `state` against `base`.
+    pub fn compute<E: EthSpec>(base: &StateDiffBase, state: &BeaconState<E>) -> Self {
+        let mut changed_validator_indices = vec![];
+        let mut changed_validators = vec![];
+        let mut next_index = 0;
+        for (i, validator) in state.validators().iter().enumerate() {
+            if base.validators.get(i) != Some(validator) {
+                write_varint(&mut changed_validator_indices, i as u64 - next_index);
+                changed_validators.push(validator.clone());
+                next_index = i as u64 + 1;
+            }
+        }
+
+        let balances = diff_u64_list(&base.balances, state.balances());
+        let inactivity_scores = state
+            .inactivity_scores()
+            .map(|scores| diff_u64_list(&base.inactivity_scores, scores))
+            .unwrap_or_default();
+
+        let mut partial_state = PartialBeaconState::from_state_forgetful(state);
+        *partial_state.validators_mut() = VariableList::empty();
+        *partial_state.balances_mut() = VariableList::empty();
+        if let Ok(scores) = partial_state.inactivity_scores_mut() {
+            *scores = VariableList::empty();
+        }
+
+        Self {
+            changed_validator_indices,
+            changed_validators,
+            balances,
+            inactivity_scores,
+            partial_state: partial_state.as_ssz_bytes(),
+        }
+    }
+
+    /// Apply the diff to `base` in place, turning it into the base of the state the diff was
+    /// computed from.
+    pub fn apply_to_base(&self, base: &mut StateDiffBase) -> Result<(), Error> {
+        let indices = read_varints(&self.changed_validator_indices)?;
+        if indices.len() != self.changed_validators.len() {
+            return Err(Error::StateDiffError(format!(
+                "{} changed validator indices for {} validators",
+                indices.len(),
+                self.changed_validators.len()
+            )));
+        }
+
+        // The registry only grows, so every validator has a balance.
+        let balances = apply_u64_list_diff(&base.balances, &self.balances)?;
+        let num_validators = balances.len();
+        base.validators.truncate(num_validators);
+
+        let mut next_index = 0u64;
+        for (gap, validator) in indices.into_iter().zip(&self.changed_validators) {
+            let index = next_index
+                .checked_add(gap)
+                .ok_or_else(|| Error::StateDiffError("validator index overflow".into()))?;
+            if let Some(existing) = base.validators.get_mut(index as usize) {
+                *existing = validator.clone();
+            } else if index as usize == base.validators.len() {
+                base.validators.push(validator.clone());
+            } else {
+                return Err(Error::StateDiffError(format!(
+                    "validator index {index} out of order"
+                )));
+            }
+            next_index = index + 1;
+        }
+        if base.validators.len() != num_validators {
+            return Err(Error::StateDiffError(format!(
+                "{} validators for {num_validators} balances",
+                base.validators.len()
+            )));
+        }
+
+        base.balances = balances;
+        base.inactivity_scores =
+            apply_u64_list_diff(&base.inactivity_scores, &self.inactivity_scores)?;
+
+        Ok(())
+    }
+
+    /// Apply the diff to `base` in place, returning the partial state it was computed from.
+    pub fn apply<E: EthSpec>(
+        &self,
+        base: &mut StateDiffBase,
+        spec: &ChainSpec,
+    ) -> Result<PartialBeaconState<E>, Error> {
+        let mut partial_state = PartialBeaconState::from_ssz_bytes(&self.partial_state, spec)?;
+        self.apply_to_base(base)?;
+
+        *partial_state.validators_mut() = to_variable_list(base.validators.clone())?;
+        *partial_state.balances_mut() = to_variable_list(base.balances.clone())?;
+        if let Ok(scores) = partial_state.inactivity_scores_mut() {
+            *scores = to_variable_list(base.inactivity_scores.clone())?;
+        }
+
+        Ok(partial_state)
+    }
+
+    pub fn as_compressed_bytes(&self) -> Result<Vec<u8>, Error> {
+        snap::raw::Encoder::new()
+            .compress_vec(&self.as_ssz_bytes())
+            .map_err(|e| Error::StateDiffError(format!("compression failed: {e:?}")))
+    }
+
+    pub fn from_compressed_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let ssz_bytes = snap::raw::Decoder::new()
+            .decompress_vec(bytes)
+            .map_err(|e| Error::StateDiffError(format!("decompression failed: {e:?}")))?;
+        Ok(Self::from_ssz_bytes(&ssz_bytes)?)
+    }
+}
+
+/// Encode the difference of each value from the base value at the same index, treating missing
+/// base values as 0.
+///
+/// Each difference is zigzag encoded so that small negative differences are small integers, and
+/// then varint encoded.
+fn diff_u64_list(base: &[u64], values: &[u64]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(values.len());
+    for (i, value) in values.iter().enumerate() {
+        let delta = value.wrapping_sub(base.get(i).copied().unwrap_or(0)) as i64;
+        write_varint(&mut bytes, ((delta << 1) ^ (delta >> 63)) as u64);
+    }
+    bytes
+}
+
+/// Inverse of `diff_u64_list`.
+fn apply_u64_list_diff(base: &[u64], bytes: &[u8]) -> Result<Vec<u64>, Error> {
+    Ok(read_varints(bytes)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, diff)| {
+            let delta = ((diff >> 1) as i64) ^ -((diff & 1) as i64);
+            base.get(i).copied().unwrap_or(0).wrapping_add(delta as u64)
+        })
+        .collect())
+}
+
+/// Append the LEB128 encoding of `value` to `bytes`.
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Decode a sequence of LEB128-encoded values.
+fn read_varints(bytes: &[u8]) -> Result<Vec<u64>, Error> {
+    let mut values = vec![];
+    let mut value = 0u64;
+    let mut shift = 0;
+    for &byte in bytes {
+        let low_bits = u64::from(byte & 0x7f);
+        if shift > 63 || (low_bits << shift) >> shift != low_bits {
+            return Err(Error::StateDiffError("varint overflow".into()));
+        }
+        value |= low_bits << shift;
+
+        if byte & 0x80 == 0 {
+            values.push(value);
+            value = 0;
+            shift = 0;
+        } else {
+            shift += 7;
+        }
+    }
+    if shift != 0 {
+        return Err(Error::StateDiffError("truncated varint".into()));
+    }
+    Ok(values)
+}
+
+fn to_variable_list<T, N: types::Unsigned>(values: Vec<T>) -> Result<VariableList<T, N>, Error> {
+    VariableList::new(values).map_err(|e| Error::StateDiffError(format!("{e:?}")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::{Eth1Data, MinimalEthSpec, Slot};
+
+    type E = MinimalEthSpec;
+
+    fn validator(i: u64) -> Validator {
+        Validator {
+            effective_balance: i,
+            ..Validator::default()
+        }
+    }
+
+    #[test]
+    fn diff_base_hierarchy() {
+        assert_eq!(diff_base_epoch_offset(0), 0);
+        assert_eq!(diff_base_epoch_offset(1), 0);
+        assert_eq!(diff_base_epoch_offset(6), 4);
+        assert_eq!(diff_base_epoch_offset(7), 6);
+        assert_eq!(diff_base_epoch_offset(8), 0);
+        assert_eq!(diff_base_epoch_offset(13), 12);
+    }
+
+    #[test]
+    fn varint_round_trip() {
+        let values = [0, 1, 127, 128, 300, u64::MAX];
+        let mut bytes = vec![];
+        for value in &values {
+            write_varint(&mut bytes, *value);
+        }
+        assert_eq!(bytes.len(), 1 + 1 + 1 + 2 + 2 + 10);
+        assert_eq!(read_varints(&bytes).unwrap(), values);
+
+        // Truncated and overlong encodings are rejected.
+        assert!(read_varints(&bytes[..bytes.len() - 1]).is_err());
+        let overlong = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02];
+        assert!(read_varints(&overlong).is_err());
+    }
+
+    #[test]
+    fn u64_list_round_trip() {
+        let base = vec![0, 5, u64::MAX, 32_000_000_000];
+        let values = vec![u64::MAX, 3, 0, 32_000_000_001, 7];
+        let bytes = diff_u64_list(&base, &values);
+        assert_eq!(read_varints(&bytes).unwrap()[3], 2);
+        assert_eq!(apply_u64_list_diff(&base, &bytes).unwrap(), values);
+
+        // Small changes to large balances take a single byte each.
+        let base = [32_000_000_000; 4];
+        let values = [
+            32_000_000_010,
+            31_999_999_990,
+            32_000_000_000,
+            32_000_000_063,
+        ];
+        assert_eq!(diff_u64_list(&base, &values).len(), 4);
+    }
+
+    #[test]
+    fn state_diff_round_trip() {
+        let spec = E::default_spec();
+        let mut state = BeaconState::<E>::new(0, Eth1Data::default(), &spec);
+        for i in 0..8 {
+            state.validators_mut().push(validator(i)).unwrap();
+            state.balances_mut().push(i * 1000).unwrap();
+        }
+        let restore_point = StateDiffBase::from_state(&state);
+
+        // Modify one validator, change some balances and add a new validator.
+        *state.get_validator_mut(2).unwrap() = validator(100);
+        *state.get_balance_mut(3).unwrap() = 0;
+        *state.get_balance_mut(5).unwrap() += 17;
+        state.validators_mut().push(validator(8)).unwrap();
+        state.balances_mut().push(8000).unwrap();
+        *state.slot_mut() = Slot::new(E::slots_per_epoch());
+
+        let diff = StateDiff::compute(&restore_point, &state);
+        assert_eq!(
+            read_varints(&diff.changed_validator_indices).unwrap(),
+            vec![2, 5]
+        );
+
+        let bytes = diff.as_compressed_bytes().unwrap();
+        let decoded = StateDiff::from_compressed_bytes(&bytes).unwrap();
+        assert_eq!(decoded, diff);
+
+        let mut base = restore_point.clone();
+        let partial_state = decoded.apply::<E>(&mut base, &spec).unwrap();
+        assert_eq!(
+            partial_state,
+            PartialBeaconState::from_state_forgetful(&state)
+        );
+        assert_eq!(base, StateDiffBase::from_state(&state));
+
+        // Chain a second diff onto the first, and rebuild the state from the restore point.
+        *state.get_validator_mut(7).unwrap() = validator(700);
+        *state.get_balance_mut(0).unwrap() += 1;
+        *state.slot_mut() = Slot::new(2 * E::slots_per_epoch());
+
+        let next_diff = StateDiff::compute(&base, &state);
+        let mut base = restore_point;
+        diff.apply_to_base(&mut base).unwrap();
+        let partial_state = next_diff.apply::<E>(&mut base, &spec).unwrap();
+        assert_eq!(
+            partial_state,
+            PartialBeaconState::from_state_forgetful(&state)
+        );
+    }
+}
@@ -80,6 +80,42 @@ pub struct Web3SignerDefinition {
     /// An empty password will be used if this is omitted.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_identity_password: Option<String>,
+
+    /// URL of a persistent WebSocket channel to the remote signer.
+    ///
+    /// If set, signatures are requested over this channel rather than by an HTTP request to `url`
+    /// for each signature. The channel is shared by all validators with the same definition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_url: Option<String>,
+}
+
+/// Defines this validator client's role in a distributed validator, where the voting key is split
+/// into shares between several validator clients such that any `threshold` of them must sign.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistributedDefinition {
+    /// The index of the key share held by this validator client.
+    pub share_index: u64,
+    /// The number of key shares required to produce a signature.
+    pub threshold: usize,
+    /// The time to wait for partial signatures from peers, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// The validator clients which hold the other key shares.
+    pub peers: Vec<DistributedPeerDefinition>,
+}
+
+/// A validator client which holds another share of a distributed validator's key.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistributedPeerDefinition {
+    /// The index of the key share held by the peer.
+    pub share_index: u64,
+    /// The public key of the peer's key share, used to verify its partial signatures.
+    pub public_key_share: PublicKey,
+    /// The URL of the peer's validator client HTTP API.
+    pub url: String,
+    /// Path to the peer token of the peer's validator client HTTP API, which only grants access to
+    /// the routes used between validator clients.
+    pub peer_token_path: PathBuf,
 }
 
 /// Defines how the validator client should attempt to sign messages for this validator.
@@ -94,6 +130,9 @@ pub enum SigningDefinition {
         voting_keystore_password_path: Option<PathBuf>,
         #[serde(skip_serializing_if = "Option::is_none")]
         voting_keystore_password: Option<ZeroizeString>,
+        /// Set if the keystore holds one share of the key of a distributed validator.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        distributed: Option<DistributedDefinition>,
     },
     /// A validator that defers to a Web3Signer HTTP server for signing.
     ///
@@ -211,6 +250,7 @@ impl ValidatorDefinition {
                 voting_keystore_path,
                 voting_keystore_password_path,
                 voting_keystore_password,
+                distributed: None,
             },
         })
     }
@@ -361,6 +401,7 @@ impl ValidatorDefinitions {
                         voting_keystore_path,
                         voting_keystore_password_path,
                         voting_keystore_password: None,
+                        distributed: None,
                     },
                 })
             })
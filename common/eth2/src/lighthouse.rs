@@ -2,20 +2,28 @@
 
 mod attestation_performance;
 pub mod attestation_rewards;
+mod beacon_processor;
 mod block_packing_efficiency;
 mod block_rewards;
+mod operation_pool;
+mod peer_management;
+mod slasher;
 mod standard_block_rewards;
 mod sync_committee_rewards;
 
 use crate::{
     types::{
-        DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock, GenericResponse, ValidatorId,
+        Accept, AttesterSlashing, DepositTreeSnapshot, Epoch, EthSpec,
+        ExecutionOptimisticFinalizedResponse, FinalizedExecutionBlock, GenericResponse,
+        IndexedAttestation, StateId, ValidatorId,
     },
-    BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot,
+    BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot, StatusCode,
 };
+use futures::{future, Stream};
+use futures_util::StreamExt;
 use proto_array::core::ProtoArray;
 use serde::{Deserialize, Serialize};
-use ssz::four_byte_option_impl;
+use ssz::{four_byte_option_impl, Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use store::{AnchorInfo, BlobInfo, Split, StoreConfig};
 
@@ -23,11 +31,20 @@ pub use attestation_performance::{
     AttestationPerformance, AttestationPerformanceQuery, AttestationPerformanceStatistics,
 };
 pub use attestation_rewards::StandardAttestationRewards;
+pub use beacon_processor::{BeaconProcessorQueues, ReprocessQueueSummary, WorkQueue};
 pub use block_packing_efficiency::{
     BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
 };
-pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
+pub use block_rewards::{
+    AttestationPackingReward, AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery,
+};
 pub use lighthouse_network::{types::SyncState, PeerInfo};
+pub use operation_pool::{ExportedOperationPool, OperationImportCount, OperationPoolImport};
+pub use peer_management::{
+    BannedIp, BannedPeer, PeerBanRequest, PeerBans, PeerDialRequest, PeerDisconnectRequest,
+    PeerUnbanRequest, TrustedPeerRequest,
+};
+pub use slasher::{DetectedSlashing, SlasherSlashings, SlasherTargets};
 pub use standard_block_rewards::StandardBlockReward;
 pub use sync_committee_rewards::SyncCommitteeReward;
 
@@ -367,6 +384,22 @@ pub struct DatabaseInfo {
     pub blob_info: BlobInfo,
 }
 
+/// A Merkle multiproof of nodes in a `BeaconState`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateProof {
+    pub state_root: Hash256,
+    /// Generalized indices of the proven nodes, for the requested paths followed by the requested
+    /// indices.
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub gindices: Vec<u64>,
+    /// The node at each of `gindices`.
+    pub leaves: Vec<Hash256>,
+    /// The helper nodes required to verify the proof, in descending order of generalized index.
+    ///
+    /// For a single node this is its Merkle branch, in bottom-up order.
+    pub branch: Vec<Hash256>,
+}
+
 impl BeaconNodeHttpClient {
     /// `GET lighthouse/health`
     pub async fn get_lighthouse_health(&self) -> Result<GenericResponse<Health>, Error> {
@@ -530,6 +563,34 @@ impl BeaconNodeHttpClient {
         self.post_with_response(path, &()).await
     }
 
+    /// `GET lighthouse/beacon/states/{state_id}/proof?path,gindex`
+    pub async fn get_lighthouse_beacon_states_proof(
+        &self,
+        state_id: StateId,
+        paths: &[&str],
+        gindices: &[u64],
+    ) -> Result<ExecutionOptimisticFinalizedResponse<StateProof>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("proof");
+
+        for state_path in paths {
+            path.query_pairs_mut().append_pair("path", state_path);
+        }
+        for gindex in gindices {
+            path.query_pairs_mut()
+                .append_pair("gindex", &gindex.to_string());
+        }
+
+        self.get(path).await
+    }
+
     ///
     /// Analysis endpoints.
     ///
@@ -598,4 +659,243 @@ impl BeaconNodeHttpClient {
 
         self.get(path).await
     }
+
+    /// `GET lighthouse/slasher/slashings`
+    pub async fn get_lighthouse_slasher_slashings<E: EthSpec>(
+        &self,
+    ) -> Result<SlasherSlashings<E>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("slashings");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/slasher/targets/{validator_index}`
+    pub async fn get_lighthouse_slasher_targets(
+        &self,
+        validator_index: u64,
+    ) -> Result<Vec<SlasherTargets>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("targets")
+            .push(&validator_index.to_string());
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/slasher/check_attestation`
+    pub async fn post_lighthouse_slasher_check_attestation<E: EthSpec>(
+        &self,
+        attestation: &IndexedAttestation<E>,
+    ) -> Result<Vec<AttesterSlashing<E>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("check_attestation");
+
+        self.post_with_response(path, attestation).await
+    }
+
+    /// `GET lighthouse/operation_pool`
+    pub async fn get_lighthouse_operation_pool<E: EthSpec>(
+        &self,
+    ) -> Result<ExportedOperationPool<E>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("operation_pool");
+
+        let bytes = self
+            .get_bytes_opt_accept_header(path, Accept::Ssz, self.timeouts.get_debug_beacon_states)
+            .await?
+            .ok_or(Error::StatusCode(StatusCode::NOT_FOUND))?;
+        ExportedOperationPool::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz)
+    }
+
+    /// `POST lighthouse/operation_pool`
+    pub async fn post_lighthouse_operation_pool<E: EthSpec>(
+        &self,
+        operation_pool: &ExportedOperationPool<E>,
+    ) -> Result<OperationPoolImport, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("operation_pool");
+
+        self.post_generic_with_ssz_body(path, operation_pool.as_ssz_bytes(), None)
+            .await?
+            .json()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// `GET lighthouse/beacon_processor`
+    pub async fn get_lighthouse_beacon_processor(&self) -> Result<BeaconProcessorQueues, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon_processor");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/beacon_processor/events`
+    pub async fn get_lighthouse_beacon_processor_events(
+        &self,
+    ) -> Result<impl Stream<Item = Result<BeaconProcessorQueues, Error>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon_processor")
+            .push("events");
+
+        Ok(self
+            .client
+            .get(path)
+            .send()
+            .await?
+            .bytes_stream()
+            .filter_map(|next| {
+                future::ready(match next {
+                    Ok(bytes) => BeaconProcessorQueues::from_sse_bytes(bytes.as_ref()).transpose(),
+                    Err(e) => Some(Err(Error::HttpClient(e.into()))),
+                })
+            }))
+    }
+
+    /// `GET lighthouse/peers/bans`
+    pub async fn get_lighthouse_peers_bans(&self) -> Result<PeerBans, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("bans");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/peers/ban`
+    pub async fn post_lighthouse_peers_ban(&self, request: &PeerBanRequest) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("ban");
+
+        self.post(path, request).await
+    }
+
+    /// `POST lighthouse/peers/unban`
+    pub async fn post_lighthouse_peers_unban(
+        &self,
+        request: &PeerUnbanRequest,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("unban");
+
+        self.post(path, request).await
+    }
+
+    /// `GET lighthouse/peers/trusted`
+    pub async fn get_lighthouse_peers_trusted(&self) -> Result<Vec<String>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("trusted");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/peers/trust`
+    pub async fn post_lighthouse_peers_trust(
+        &self,
+        request: &TrustedPeerRequest,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("trust");
+
+        self.post(path, request).await
+    }
+
+    /// `POST lighthouse/peers/untrust`
+    pub async fn post_lighthouse_peers_untrust(
+        &self,
+        request: &TrustedPeerRequest,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("untrust");
+
+        self.post(path, request).await
+    }
+
+    /// `POST lighthouse/peers/dial`
+    pub async fn post_lighthouse_peers_dial(&self, request: &PeerDialRequest) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("dial");
+
+        self.post(path, request).await
+    }
+
+    /// `POST lighthouse/peers/disconnect`
+    pub async fn post_lighthouse_peers_disconnect(
+        &self,
+        request: &PeerDisconnectRequest,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("disconnect");
+
+        self.post(path, request).await
+    }
 }
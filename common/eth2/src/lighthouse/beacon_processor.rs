@@ -0,0 +1,62 @@
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use std::str::from_utf8;
+
+/// The state of the beacon processor queue for one type of work.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct WorkQueue {
+    pub work_type: String,
+    pub length: usize,
+    pub capacity: usize,
+    /// The number of items dropped because the queue was full.
+    pub dropped: u64,
+    /// The number of milliseconds since the oldest item in the queue was added. Not set if the
+    /// queue is empty.
+    pub oldest_item_age_ms: Option<u64>,
+}
+
+/// A summary of the work waiting in the beacon processor's re-process queue.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct ReprocessQueueSummary {
+    /// Gossip blocks received early and waiting for their slot.
+    pub gossip_blocks: usize,
+    /// RPC blocks waiting for the gossip block with the same root to be processed.
+    pub rpc_blocks: usize,
+    /// Aggregates waiting for their block to be imported.
+    pub aggregates: usize,
+    /// Unaggregated attestations waiting for their block to be imported.
+    pub unaggregates: usize,
+    /// The number of distinct block roots that attestations are waiting for.
+    pub awaited_block_roots: usize,
+    /// Light client updates waiting for their parent block to be imported.
+    pub light_client_updates: usize,
+    /// Backfill batches waiting for their scheduled processing time.
+    pub backfill_batches: usize,
+}
+
+/// The state of the beacon processor queues, as returned by `GET lighthouse/beacon_processor`.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct BeaconProcessorQueues {
+    pub active_workers: usize,
+    pub max_workers: usize,
+    pub work_queues: Vec<WorkQueue>,
+    pub reprocess_queue: ReprocessQueueSummary,
+    /// The time at which the queue state was last updated, in milliseconds since the UNIX epoch.
+    /// Not set if the beacon processor has not reported its state yet.
+    pub updated_at: Option<u64>,
+}
+
+impl BeaconProcessorQueues {
+    /// Parses a message from the `lighthouse/beacon_processor/events` stream, returning `None` for
+    /// comments such as keep-alives.
+    pub fn from_sse_bytes(message: &[u8]) -> Result<Option<Self>, Error> {
+        let s =
+            from_utf8(message).map_err(|e| Error::InvalidServerSentEvent(format!("{:?}", e)))?;
+        let Some(data) = s.lines().find_map(|line| line.strip_prefix("data:")) else {
+            return Ok(None);
+        };
+        serde_json::from_str(data)
+            .map(Some)
+            .map_err(|e| Error::InvalidServerSentEvent(format!("Beacon processor: {:?}", e)))
+    }
+}
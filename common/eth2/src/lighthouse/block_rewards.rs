@@ -45,6 +45,22 @@ pub struct AttestationRewards {
     /// The attestations themselves (optional).
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub attestations: Vec<AttestationData>,
+    /// How the attestations were packed, if the block was produced by this node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packing: Option<AttestationPackingReward>,
+}
+
+/// The outcome of packing the attestations for a block produced by this node.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AttestationPackingReward {
+    /// The packing strategy used, `greedy` or `exact`.
+    pub strategy: String,
+    /// Reward for the attestations selected by the strategy.
+    pub reward: u64,
+    /// Reward for the attestations that greedy packing would have selected.
+    pub greedy_reward: u64,
+    /// Whether the exact strategy ran out of time before completing its search.
+    pub timed_out: bool,
 }
 
 /// Query parameters for the `/lighthouse/block_rewards` endpoint.
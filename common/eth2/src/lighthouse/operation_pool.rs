@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use types::{
+    Attestation, AttesterSlashing, EthSpec, ProposerSlashing, SignedBlsToExecutionChange,
+    SignedVoluntaryExit, SyncCommitteeContribution,
+};
+
+/// The contents of an operation pool, as exported by `GET lighthouse/operation_pool`.
+///
+/// The operations are not known to be valid, and are re-verified when imported.
+#[derive(Debug, PartialEq, Clone, Default, Encode, Decode)]
+pub struct ExportedOperationPool<E: EthSpec> {
+    pub attestations: Vec<Attestation<E>>,
+    pub sync_contributions: Vec<SyncCommitteeContribution<E>>,
+    pub attester_slashings: Vec<AttesterSlashing<E>>,
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub bls_to_execution_changes: Vec<SignedBlsToExecutionChange>,
+}
+
+/// The number of operations of one type that were imported into the operation pool.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct OperationImportCount {
+    /// Operations which were verified and added to the pool.
+    pub imported: usize,
+    /// Operations which failed verification.
+    pub rejected: usize,
+}
+
+/// The result of importing an `ExportedOperationPool`.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct OperationPoolImport {
+    pub attestations: OperationImportCount,
+    pub sync_contributions: OperationImportCount,
+    pub attester_slashings: OperationImportCount,
+    pub proposer_slashings: OperationImportCount,
+    pub voluntary_exits: OperationImportCount,
+    pub bls_to_execution_changes: OperationImportCount,
+}
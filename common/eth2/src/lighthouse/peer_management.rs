@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+/// A request to ban a peer or an IP address. Exactly one of `peer_id` and `ip` must be set.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct PeerBanRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip: Option<IpAddr>,
+    /// The number of seconds the ban lasts for. The ban is permanent if this is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<u64>,
+}
+
+/// A request to lift the ban of a peer or an IP address. Exactly one of `peer_id` and `ip` must be
+/// set.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct PeerUnbanRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip: Option<IpAddr>,
+}
+
+/// A request to trust, or stop trusting, a peer.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TrustedPeerRequest {
+    pub peer_id: String,
+}
+
+/// A request to dial a peer.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PeerDialRequest {
+    /// Either a multiaddr or a base64 encoded ENR.
+    pub address: String,
+}
+
+/// A request to disconnect a peer.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PeerDisconnectRequest {
+    pub peer_id: String,
+    /// The goodbye reason code sent to the peer.
+    pub reason: u64,
+}
+
+/// A peer banned by the node operator.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BannedPeer {
+    pub peer_id: String,
+    /// The time the ban expires, in seconds since the UNIX epoch. The ban is permanent if this is
+    /// not set.
+    pub expires: Option<u64>,
+}
+
+/// An IP address banned by the node operator.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BannedIp {
+    pub ip: IpAddr,
+    /// The time the ban expires, in seconds since the UNIX epoch. The ban is permanent if this is
+    /// not set.
+    pub expires: Option<u64>,
+}
+
+/// The peers and IP addresses banned by the node operator.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct PeerBans {
+    pub peers: Vec<BannedPeer>,
+    pub ips: Vec<BannedIp>,
+}
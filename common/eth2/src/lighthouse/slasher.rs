@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use types::{AttesterSlashing, Epoch, EthSpec, ProposerSlashing};
+
+/// A slashing detected by the slasher.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct DetectedSlashing<T> {
+    /// The time at which the slashing was detected, in seconds since the UNIX epoch.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub seen_timestamp: u64,
+    pub slashing: T,
+}
+
+/// The most recently detected slashings, oldest first.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(bound = "E: EthSpec")]
+pub struct SlasherSlashings<E: EthSpec> {
+    pub attester_slashings: Vec<DetectedSlashing<AttesterSlashing<E>>>,
+    pub proposer_slashings: Vec<DetectedSlashing<ProposerSlashing>>,
+}
+
+/// The min and max targets of a validator at some epoch, as stored by the slasher.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SlasherTargets {
+    pub epoch: Epoch,
+    /// The minimum target of the validator's attestations with a source greater than `epoch`.
+    pub min_target: Option<Epoch>,
+    /// The maximum target of the validator's attestations with a source less than `epoch`.
+    pub max_target: Option<Epoch>,
+}
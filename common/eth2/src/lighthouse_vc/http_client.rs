@@ -482,6 +482,38 @@ impl ValidatorClientHttpClient {
         self.post(path, &request).await
     }
 
+    /// `POST lighthouse/distributed/partial_signatures`
+    pub async fn post_lighthouse_distributed_partial_signatures(
+        &self,
+        request: &PartialSignatureRequest,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("distributed")
+            .push("partial_signatures");
+
+        self.post(path, &request).await
+    }
+
+    /// `POST lighthouse/slashing_protection/replicate`
+    pub async fn post_lighthouse_slashing_protection_replicate(
+        &self,
+        request: &ReplicateSlashingProtectionRequest,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slashing_protection")
+            .push("replicate");
+
+        self.post(path, &request).await
+    }
+
     /// `PATCH lighthouse/validators/{validator_pubkey}`
     #[allow(clippy::too_many_arguments)]
     pub async fn patch_lighthouse_validators(
@@ -7,6 +7,7 @@ use std::path::PathBuf;
 pub use crate::lighthouse::Health;
 pub use crate::lighthouse_vc::std_types::*;
 pub use crate::types::{GenericResponse, VersionData};
+pub use slashing_protection::ReplicationRecord;
 pub use types::*;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -197,3 +198,21 @@ pub struct SingleExportKeystoresResponse {
 pub struct SetGraffitiRequest {
     pub graffiti: GraffitiString,
 }
+
+/// A partial signature by one key share of a distributed validator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartialSignatureRequest {
+    /// The voting public key of the distributed validator.
+    pub voting_pubkey: PublicKeyBytes,
+    pub signing_root: Hash256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub share_index: u64,
+    pub signature: Signature,
+}
+
+/// A slashing protection record replicated from another validator client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplicateSlashingProtectionRequest {
+    pub genesis_validators_root: Hash256,
+    pub record: ReplicationRecord,
+}
@@ -3,7 +3,8 @@
 
 use crate::{
     Error as ServerError, CONSENSUS_BLOCK_VALUE_HEADER, CONSENSUS_VERSION_HEADER,
-    EXECUTION_PAYLOAD_BLINDED_HEADER, EXECUTION_PAYLOAD_VALUE_HEADER,
+    CONTENT_TYPE_HEADER, EXECUTION_PAYLOAD_BLINDED_HEADER, EXECUTION_PAYLOAD_VALUE_HEADER,
+    SSZ_CONTENT_TYPE_HEADER,
 };
 use lighthouse_network::{ConnectionDirection, Enr, Multiaddr, PeerConnectionStatus};
 use mediatype::{names, MediaType, MediaTypeList};
@@ -489,6 +490,12 @@ pub struct RandaoQuery {
     pub epoch: Option<Epoch>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct LightClientUpdatesQuery {
+    pub start_period: u64,
+    pub count: u64,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct AttestationPoolQuery {
     pub slot: Option<Slot>,
@@ -504,6 +511,15 @@ pub struct ValidatorsQuery {
     pub status: Option<Vec<ValidatorStatus>>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateProofQuery {
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub path: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub gindex: Option<Vec<u64>>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ValidatorsRequestBody {
@@ -914,6 +930,12 @@ pub struct SseBlock {
     pub execution_optimistic: bool,
 }
 
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseBlockGossip {
+    pub slot: Slot,
+    pub block: Hash256,
+}
+
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
 pub struct SseBlobSidecar {
     pub block_root: Hash256,
@@ -1078,6 +1100,10 @@ pub enum EventKind<T: EthSpec> {
     #[cfg(feature = "lighthouse")]
     BlockReward(BlockReward),
     PayloadAttributes(VersionedSsePayloadAttributes),
+    AttesterSlashing(Box<AttesterSlashing<T>>),
+    ProposerSlashing(Box<ProposerSlashing>),
+    BlsToExecutionChange(Box<SignedBlsToExecutionChange>),
+    BlockGossip(Box<SseBlockGossip>),
 }
 
 impl<T: EthSpec> EventKind<T> {
@@ -1097,6 +1123,10 @@ impl<T: EthSpec> EventKind<T> {
             EventKind::LightClientOptimisticUpdate(_) => "light_client_optimistic_update",
             #[cfg(feature = "lighthouse")]
             EventKind::BlockReward(_) => "block_reward",
+            EventKind::AttesterSlashing(_) => "attester_slashing",
+            EventKind::ProposerSlashing(_) => "proposer_slashing",
+            EventKind::BlsToExecutionChange(_) => "bls_to_execution_change",
+            EventKind::BlockGossip(_) => "block_gossip",
         }
     }
 
@@ -1177,6 +1207,24 @@ impl<T: EthSpec> EventKind<T> {
             "block_reward" => Ok(EventKind::BlockReward(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Block Reward: {:?}", e)),
             )?)),
+            "attester_slashing" => Ok(EventKind::AttesterSlashing(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Attester Slashing: {:?}", e))
+                })?,
+            )),
+            "proposer_slashing" => Ok(EventKind::ProposerSlashing(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Proposer Slashing: {:?}", e))
+                })?,
+            )),
+            "bls_to_execution_change" => Ok(EventKind::BlsToExecutionChange(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Bls To Execution Change: {:?}", e))
+                })?,
+            )),
+            "block_gossip" => Ok(EventKind::BlockGossip(serde_json::from_str(data).map_err(
+                |e| ServerError::InvalidServerSentEvent(format!("Block Gossip: {:?}", e)),
+            )?)),
             _ => Err(ServerError::InvalidServerSentEvent(
                 "Could not parse event tag".to_string(),
             )),
@@ -1208,6 +1256,10 @@ pub enum EventTopic {
     LightClientOptimisticUpdate,
     #[cfg(feature = "lighthouse")]
     BlockReward,
+    AttesterSlashing,
+    ProposerSlashing,
+    BlsToExecutionChange,
+    BlockGossip,
 }
 
 impl FromStr for EventTopic {
@@ -1229,6 +1281,10 @@ impl FromStr for EventTopic {
             "light_client_optimistic_update" => Ok(EventTopic::LightClientOptimisticUpdate),
             #[cfg(feature = "lighthouse")]
             "block_reward" => Ok(EventTopic::BlockReward),
+            "attester_slashing" => Ok(EventTopic::AttesterSlashing),
+            "proposer_slashing" => Ok(EventTopic::ProposerSlashing),
+            "bls_to_execution_change" => Ok(EventTopic::BlsToExecutionChange),
+            "block_gossip" => Ok(EventTopic::BlockGossip),
             _ => Err("event topic cannot be parsed.".to_string()),
         }
     }
@@ -1251,6 +1307,10 @@ impl fmt::Display for EventTopic {
             EventTopic::LightClientOptimisticUpdate => write!(f, "light_client_optimistic_update"),
             #[cfg(feature = "lighthouse")]
             EventTopic::BlockReward => write!(f, "block_reward"),
+            EventTopic::AttesterSlashing => write!(f, "attester_slashing"),
+            EventTopic::ProposerSlashing => write!(f, "proposer_slashing"),
+            EventTopic::BlsToExecutionChange => write!(f, "bls_to_execution_change"),
+            EventTopic::BlockGossip => write!(f, "block_gossip"),
         }
     }
 }
@@ -1330,6 +1390,29 @@ impl FromStr for Accept {
     }
 }
 
+/// The encoding of a response body, as advertised by its `Content-Type` header.
+///
+/// Anything other than `application/octet-stream` is treated as JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentType {
+    Json,
+    Ssz,
+}
+
+impl From<&HeaderMap> for ContentType {
+    fn from(headers: &HeaderMap) -> Self {
+        let is_ssz = headers
+            .get(CONTENT_TYPE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map_or(false, |value| value.starts_with(SSZ_CONTENT_TYPE_HEADER));
+        if is_ssz {
+            ContentType::Ssz
+        } else {
+            ContentType::Json
+        }
+    }
+}
+
 #[derive(PartialEq, Debug, Serialize, Deserialize)]
 pub struct StandardLivenessResponseData {
     #[serde(with = "serde_utils::quoted_u64")]
@@ -1934,6 +2017,37 @@ impl<E: EthSpec> FullPayloadContents<E> {
         self.payload_ref().block_hash()
     }
 
+    /// SSZ decode with fork variant passed in explicitly.
+    pub fn from_ssz_bytes_for_fork(
+        bytes: &[u8],
+        fork_name: ForkName,
+    ) -> Result<Self, ssz::DecodeError> {
+        match fork_name {
+            ForkName::Merge | ForkName::Capella => {
+                ExecutionPayload::from_ssz_bytes(bytes, fork_name).map(Self::Payload)
+            }
+            ForkName::Deneb => {
+                let mut builder = ssz::SszDecoderBuilder::new(bytes);
+
+                builder.register_anonymous_variable_length_item()?;
+                builder.register_type::<BlobsBundle<E>>()?;
+
+                let mut decoder = builder.build()?;
+                let execution_payload = decoder
+                    .decode_next_with(|bytes| ExecutionPayload::from_ssz_bytes(bytes, fork_name))?;
+                let blobs_bundle = decoder.decode_next()?;
+
+                Ok(Self::PayloadAndBlobs(ExecutionPayloadAndBlobs {
+                    execution_payload,
+                    blobs_bundle,
+                }))
+            }
+            ForkName::Base | ForkName::Altair => Err(ssz::DecodeError::BytesInvalid(format!(
+                "FullPayloadContents decoding for {fork_name} not implemented"
+            ))),
+        }
+    }
+
     pub fn deconstruct(self) -> (ExecutionPayload<E>, Option<BlobsBundle<E>>) {
         match self {
             FullPayloadContents::Payload(payload) => (payload, None),
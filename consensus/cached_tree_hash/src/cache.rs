@@ -158,6 +158,31 @@ impl TreeHashCache {
     pub fn leaves(&mut self) -> &mut CacheArenaAllocation {
         &mut self.layers[self.depth]
     }
+
+    /// The depth of this tree, such that it has a capacity for `2^depth` leaves.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Get the node at `index` within `layer` of this cache, without doing any updates/computation.
+    ///
+    /// Layer `0` contains the root and layer `self.depth()` contains the leaves. Nodes to the right
+    /// of the populated portion of the tree are zero hashes.
+    pub fn node(&self, arena: &CacheArena, layer: usize, index: usize) -> Result<Hash256, Error> {
+        let nodes = self
+            .layers
+            .get(layer)
+            .ok_or(Error::NodeOutOfBounds { layer, index })?;
+
+        if index >= 1 << layer {
+            return Err(Error::NodeOutOfBounds { layer, index });
+        }
+
+        Ok(nodes
+            .get(arena, index)?
+            .copied()
+            .unwrap_or_else(|| Hash256::from_slice(&ZERO_HASHES[self.depth - layer])))
+    }
 }
 
 /// Compute the dirty indices for one layer up.
@@ -219,6 +244,44 @@ mod test {
             .expect("should calculate root");
     }
 
+    #[test]
+    fn node_matches_root_and_leaves() {
+        let arena = &mut CacheArena::default();
+
+        let depth = 3;
+        let leaves = (0..5)
+            .map(|i| Hash256::from_low_u64_be(i + 1))
+            .collect::<Vec<_>>();
+
+        let mut cache = TreeHashCache::new(arena, depth, leaves.len());
+        let root = cache
+            .recalculate_merkle_root(arena, leaves.iter().map(|leaf| leaf.to_fixed_bytes()))
+            .expect("should calculate root");
+
+        assert_eq!(cache.node(arena, 0, 0), Ok(root));
+        for (i, leaf) in leaves.iter().enumerate() {
+            assert_eq!(cache.node(arena, depth, i), Ok(*leaf));
+        }
+        assert_eq!(
+            cache.node(arena, depth, 7),
+            Ok(Hash256::from_slice(&ZERO_HASHES[0]))
+        );
+        assert_eq!(
+            cache.node(arena, 1, 1),
+            Ok(Hash256::from_slice(&hash32_concat(
+                cache.node(arena, 2, 2).unwrap().as_bytes(),
+                &ZERO_HASHES[1]
+            )))
+        );
+        assert_eq!(
+            cache.node(arena, depth, 8),
+            Err(Error::NodeOutOfBounds {
+                layer: depth,
+                index: 8
+            })
+        );
+    }
+
     #[test]
     fn test_node_per_layer_unbalanced_tree() {
         assert_eq!(nodes_per_layer(0, 3, 5), 1);
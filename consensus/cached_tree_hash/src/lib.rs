@@ -24,6 +24,11 @@ pub enum Error {
     CacheArenaError(cache_arena::Error),
     /// Unable to find left index in Merkle tree.
     MissingLeftIdx(usize),
+    /// The requested node does not exist in a tree of this depth.
+    NodeOutOfBounds {
+        layer: usize,
+        index: usize,
+    },
 }
 
 impl From<cache_arena::Error> for Error {
@@ -2,6 +2,7 @@ use ethereum_hashing::{hash, hash32_concat, ZERO_HASHES};
 use ethereum_types::H256;
 use lazy_static::lazy_static;
 use safe_arith::ArithError;
+use std::collections::{BTreeMap, BTreeSet};
 
 const MAX_TREE_DEPTH: usize = 32;
 const EMPTY_SLICE: &[H256] = &[];
@@ -388,6 +389,81 @@ pub fn merkle_root_from_branch(leaf: H256, branch: &[H256], depth: usize, index:
     H256::from_slice(&merkle_root)
 }
 
+/// Return the generalized indices of the nodes required to verify a multiproof of the nodes at
+/// `indices`, in descending order.
+///
+/// See: https://github.com/ethereum/consensus-specs/blob/dev/ssz/merkle-proofs.md#merkle-multiproofs
+pub fn get_helper_indices(indices: &[usize]) -> Vec<usize> {
+    let mut helper_indices = BTreeSet::new();
+    let mut path_indices = BTreeSet::new();
+
+    for &index in indices {
+        let mut index = index;
+        while index > 1 {
+            // The sibling of each node on the path is a helper, and each parent is on the path.
+            helper_indices.insert(index ^ 1);
+            path_indices.insert(index);
+            index /= 2;
+        }
+        path_indices.insert(index);
+    }
+
+    helper_indices
+        .difference(&path_indices)
+        .rev()
+        .copied()
+        .collect()
+}
+
+/// Compute the root of a multiproof of `leaves` at the generalized `indices`.
+///
+/// The `proof` should contain the nodes at `get_helper_indices(indices)`, in the same order.
+/// Returns `None` if the proof is malformed.
+pub fn calculate_multi_merkle_root(
+    leaves: &[H256],
+    proof: &[H256],
+    indices: &[usize],
+) -> Option<H256> {
+    let helper_indices = get_helper_indices(indices);
+    if leaves.len() != indices.len() || proof.len() != helper_indices.len() {
+        return None;
+    }
+
+    let mut objects = indices
+        .iter()
+        .copied()
+        .zip(leaves.iter().copied())
+        .chain(helper_indices.into_iter().zip(proof.iter().copied()))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut keys = objects.keys().rev().copied().collect::<Vec<_>>();
+    let mut pos = 0;
+    while pos < keys.len() {
+        let index = keys[pos];
+        if index > 1 && objects.contains_key(&(index ^ 1)) && !objects.contains_key(&(index / 2)) {
+            let left = objects.get(&(index & !1))?;
+            let right = objects.get(&(index | 1))?;
+            let parent = H256::from_slice(&hash32_concat(left.as_bytes(), right.as_bytes()));
+            objects.insert(index / 2, parent);
+            keys.push(index / 2);
+        }
+        pos += 1;
+    }
+
+    objects.get(&1).copied()
+}
+
+/// Verify a multiproof that `leaves` exist at the generalized `indices` in a Merkle tree rooted
+/// at `root`.
+pub fn verify_merkle_multiproof(
+    leaves: &[H256],
+    proof: &[H256],
+    indices: &[usize],
+    root: H256,
+) -> bool {
+    calculate_multi_merkle_root(leaves, proof, indices) == Some(root)
+}
+
 impl From<ArithError> for MerkleTreeError {
     fn from(_: ArithError) -> Self {
         MerkleTreeError::ArithError
@@ -450,6 +526,81 @@ mod tests {
         TestResult::from_bool(proofs_ok)
     }
 
+    /// Check that multiproofs of arbitrary subsets of leaves verify, and that a single-leaf
+    /// multiproof is the same as the regular bottom-up branch.
+    #[quickcheck]
+    fn quickcheck_multiproof_verify(
+        int_leaves: Vec<u64>,
+        depth: usize,
+        selected: Vec<usize>,
+    ) -> TestResult {
+        if depth == 0
+            || depth > 8
+            || int_leaves.is_empty()
+            || int_leaves.len() > 2usize.pow(depth as u32)
+        {
+            return TestResult::discard();
+        }
+
+        let leaves: Vec<_> = int_leaves.into_iter().map(H256::from_low_u64_be).collect();
+        let merkle_tree = MerkleTree::create(&leaves, depth);
+        let merkle_root = merkle_tree.hash();
+
+        let mut positions = selected
+            .into_iter()
+            .map(|i| i % leaves.len())
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        positions.dedup();
+        if positions.is_empty() {
+            return TestResult::discard();
+        }
+
+        let indices = positions
+            .iter()
+            .map(|i| (1 << depth) + i)
+            .collect::<Vec<_>>();
+        let multi_leaves = positions.iter().map(|&i| leaves[i]).collect::<Vec<_>>();
+
+        // Read each helper node out of the tree, walking down from the root.
+        let proof = get_helper_indices(&indices)
+            .into_iter()
+            .map(|index| {
+                let node_depth = (usize::BITS - 1 - index.leading_zeros()) as usize;
+                let mut node = &merkle_tree;
+                for i in (0..node_depth).rev() {
+                    let (left, right) = node.left_and_right_branches().unwrap();
+                    node = if (index >> i) & 1 == 1 { right } else { left };
+                }
+                node.hash()
+            })
+            .collect::<Vec<_>>();
+
+        if positions.len() == 1 {
+            let (_, branch) = merkle_tree.generate_proof(positions[0], depth).unwrap();
+            if branch != proof {
+                return TestResult::failed();
+            }
+        }
+
+        TestResult::from_bool(verify_merkle_multiproof(
+            &multi_leaves,
+            &proof,
+            &indices,
+            merkle_root,
+        ))
+    }
+
+    #[test]
+    fn helper_indices_small_example() {
+        // Proving leaves 4 and 5 of a depth-2 tree requires only the right-hand subtree (3).
+        assert_eq!(get_helper_indices(&[4, 5]), vec![3]);
+        // Proving leaves 4 and 7 requires their siblings.
+        assert_eq!(get_helper_indices(&[4, 7]), vec![6, 5]);
+        // A single leaf requires its full branch, bottom-up.
+        assert_eq!(get_helper_indices(&[9]), vec![8, 5, 3]);
+    }
+
     #[test]
     fn sparse_zero_correct() {
         let depth = 2;
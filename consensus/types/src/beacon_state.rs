@@ -41,6 +41,7 @@ mod clone_config;
 mod exit_cache;
 mod iter;
 mod progressive_balances_cache;
+mod proof;
 mod pubkey_cache;
 mod tests;
 mod tree_hash_cache;
@@ -135,6 +136,7 @@ pub enum Error {
         epoch: Epoch,
     },
     IndexNotSupported(usize),
+    InvalidProofPath(String),
     MerkleTreeError(merkle_proof::MerkleTreeError),
 }
 
@@ -1887,6 +1889,76 @@ impl<T: EthSpec> BeaconState<T> {
 
         Ok(proof)
     }
+
+    /// Compute the generalized index of the node at `path`, e.g. `finalized_checkpoint.root` or
+    /// `validators.3.effective_balance`.
+    pub fn generalized_index_for_path(&self, path: &str) -> Result<usize, Error> {
+        proof::generalized_index_for_path(self, path)
+    }
+
+    /// Compute a Merkle multiproof for the nodes at `generalized_indices`.
+    ///
+    /// Returns the nodes at each of the `generalized_indices` (in the same order), along with the
+    /// helper nodes at `merkle_proof::get_helper_indices(generalized_indices)`. For a single index
+    /// the helper nodes are the usual bottom-up Merkle branch.
+    pub fn compute_merkle_multiproof(
+        &mut self,
+        generalized_indices: &[usize],
+    ) -> Result<(Vec<Hash256>, Vec<Hash256>), Error> {
+        self.initialize_tree_hash_cache();
+        let mut cache = self
+            .tree_hash_cache_mut()
+            .take()
+            .ok_or(Error::TreeHashCacheNotInitialized)?;
+        let leaves = cache.recalculate_tree_hash_leaves(self)?;
+
+        let result = generalized_indices
+            .iter()
+            .map(|&index| self.tree_hash_node(&cache, &leaves, index))
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(|nodes| {
+                let branch = merkle_proof::get_helper_indices(generalized_indices)
+                    .into_iter()
+                    .map(|index| self.tree_hash_node(&cache, &leaves, index))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((nodes, branch))
+            });
+
+        self.tree_hash_cache_mut().restore(cache);
+        result
+    }
+
+    /// Returns the node at `generalized_index` of the state tree, given the up-to-date `cache`
+    /// and the `leaves` it computed.
+    fn tree_hash_node(
+        &self,
+        cache: &tree_hash_cache::BeaconTreeHashCacheInner<T>,
+        leaves: &[Hash256],
+        generalized_index: usize,
+    ) -> Result<Hash256, Error> {
+        if generalized_index == 0 {
+            return Err(Error::IndexNotSupported(generalized_index));
+        }
+
+        let node = match proof::split_generalized_index(
+            generalized_index,
+            tree_hash_cache::BEACON_STATE_TREE_DEPTH,
+        ) {
+            // Nodes beneath one of the state's fields.
+            Some((field_index, relative_index)) if relative_index > 1 => cache.field_node(
+                self,
+                field_index - tree_hash_cache::NUM_BEACON_STATE_HASH_TREE_ROOT_LEAVES,
+                relative_index,
+            )?,
+            // Fields and the internal nodes above them.
+            _ => proof::subtree_node(
+                leaves,
+                tree_hash_cache::BEACON_STATE_TREE_DEPTH,
+                generalized_index,
+            ),
+        };
+        node.ok_or(Error::IndexNotSupported(generalized_index))
+    }
 }
 
 impl From<RelativeEpochError> for Error {
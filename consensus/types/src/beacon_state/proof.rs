@@ -0,0 +1,459 @@
+//! Helpers for computing Merkle proofs of arbitrary nodes in the `BeaconState` tree.
+//!
+//! Nodes are addressed by their generalized index, as defined in the SSZ spec:
+//!
+//! https://github.com/ethereum/consensus-specs/blob/dev/ssz/merkle-proofs.md#generalized-merkle-tree-index
+use super::tree_hash_cache::NUM_BEACON_STATE_HASH_TREE_ROOT_LEAVES;
+use super::{BeaconState, Error};
+use crate::{
+    Checkpoint, Eth1Data, EthSpec, ExecutionPayloadHeaderRef, Hash256, SyncCommittee, Unsigned,
+    Validator,
+};
+use cached_tree_hash::int_log;
+use merkle_proof::MerkleTree;
+use tree_hash::{TreeHash, BYTES_PER_CHUNK};
+
+/// Field names of the phase0 `BeaconState`, in Merkleization order.
+const BASE_FIELD_NAMES: [&str; 21] = [
+    "genesis_time",
+    "genesis_validators_root",
+    "slot",
+    "fork",
+    "latest_block_header",
+    "block_roots",
+    "state_roots",
+    "historical_roots",
+    "eth1_data",
+    "eth1_data_votes",
+    "eth1_deposit_index",
+    "validators",
+    "balances",
+    "randao_mixes",
+    "slashings",
+    "previous_epoch_attestations",
+    "current_epoch_attestations",
+    "justification_bits",
+    "previous_justified_checkpoint",
+    "current_justified_checkpoint",
+    "finalized_checkpoint",
+];
+
+/// Field names of the latest `BeaconState`, in Merkleization order.
+///
+/// Earlier forks (after phase0) use a prefix of this list.
+const FIELD_NAMES: [&str; 28] = [
+    "genesis_time",
+    "genesis_validators_root",
+    "slot",
+    "fork",
+    "latest_block_header",
+    "block_roots",
+    "state_roots",
+    "historical_roots",
+    "eth1_data",
+    "eth1_data_votes",
+    "eth1_deposit_index",
+    "validators",
+    "balances",
+    "randao_mixes",
+    "slashings",
+    "previous_epoch_participation",
+    "current_epoch_participation",
+    "justification_bits",
+    "previous_justified_checkpoint",
+    "current_justified_checkpoint",
+    "finalized_checkpoint",
+    "inactivity_scores",
+    "current_sync_committee",
+    "next_sync_committee",
+    "latest_execution_payload_header",
+    "next_withdrawal_index",
+    "next_withdrawal_validator_index",
+    "historical_summaries",
+];
+
+const FORK_FIELD_NAMES: &[&str] = &["previous_version", "current_version", "epoch"];
+const BLOCK_HEADER_FIELD_NAMES: &[&str] = &[
+    "slot",
+    "proposer_index",
+    "parent_root",
+    "state_root",
+    "body_root",
+];
+const ETH1_DATA_FIELD_NAMES: &[&str] = &["deposit_root", "deposit_count", "block_hash"];
+const CHECKPOINT_FIELD_NAMES: &[&str] = &["epoch", "root"];
+const SYNC_COMMITTEE_FIELD_NAMES: &[&str] = &["pubkeys", "aggregate_pubkey"];
+const VALIDATOR_FIELD_NAMES: &[&str] = &[
+    "pubkey",
+    "withdrawal_credentials",
+    "effective_balance",
+    "slashed",
+    "activation_eligibility_epoch",
+    "activation_epoch",
+    "exit_epoch",
+    "withdrawable_epoch",
+];
+const EXECUTION_PAYLOAD_HEADER_FIELD_NAMES: [&str; 17] = [
+    "parent_hash",
+    "fee_recipient",
+    "state_root",
+    "receipts_root",
+    "logs_bloom",
+    "prev_randao",
+    "block_number",
+    "gas_limit",
+    "gas_used",
+    "timestamp",
+    "extra_data",
+    "base_fee_per_gas",
+    "block_hash",
+    "transactions_root",
+    "withdrawals_root",
+    "blob_gas_used",
+    "excess_blob_gas",
+];
+
+/// The shape of the Merkle subtree beneath a `BeaconState` field.
+enum FieldShape {
+    /// A value which is a single leaf of the state tree.
+    Leaf,
+    /// A container with the given fields.
+    Container(&'static [&'static str]),
+    /// A vector or list of `len` items, packed `items_per_chunk` to a chunk, with a data tree of
+    /// the given `depth`.
+    Sequence {
+        is_list: bool,
+        depth: usize,
+        items_per_chunk: usize,
+        len: usize,
+        element_fields: Option<&'static [&'static str]>,
+    },
+}
+
+/// Returns the names of the fields of `state`, in Merkleization order.
+pub fn field_names<T: EthSpec>(state: &BeaconState<T>) -> &'static [&'static str] {
+    match state {
+        BeaconState::Base(_) => &BASE_FIELD_NAMES,
+        BeaconState::Altair(_) => &FIELD_NAMES[..24],
+        BeaconState::Merge(_) => &FIELD_NAMES[..25],
+        BeaconState::Capella(_) | BeaconState::Deneb(_) => &FIELD_NAMES,
+    }
+}
+
+fn field_shape<T: EthSpec>(state: &BeaconState<T>, field_name: &str) -> FieldShape {
+    let u64s_per_chunk = BYTES_PER_CHUNK / std::mem::size_of::<u64>();
+    let hash256_vector = |len: usize| FieldShape::Sequence {
+        is_list: false,
+        depth: int_log(len),
+        items_per_chunk: 1,
+        len,
+        element_fields: None,
+    };
+    let u64_list = |limit: usize, len: usize| FieldShape::Sequence {
+        is_list: true,
+        depth: int_log(limit / u64s_per_chunk),
+        items_per_chunk: u64s_per_chunk,
+        len,
+        element_fields: None,
+    };
+
+    match field_name {
+        "fork" => FieldShape::Container(FORK_FIELD_NAMES),
+        "latest_block_header" => FieldShape::Container(BLOCK_HEADER_FIELD_NAMES),
+        "eth1_data" => FieldShape::Container(ETH1_DATA_FIELD_NAMES),
+        "previous_justified_checkpoint"
+        | "current_justified_checkpoint"
+        | "finalized_checkpoint" => FieldShape::Container(CHECKPOINT_FIELD_NAMES),
+        "current_sync_committee" | "next_sync_committee" => {
+            FieldShape::Container(SYNC_COMMITTEE_FIELD_NAMES)
+        }
+        "latest_execution_payload_header" => {
+            let num_fields = match state.latest_execution_payload_header() {
+                Ok(ExecutionPayloadHeaderRef::Merge(_)) => 14,
+                Ok(ExecutionPayloadHeaderRef::Capella(_)) => 15,
+                Ok(ExecutionPayloadHeaderRef::Deneb(_)) | Err(_) => 17,
+            };
+            FieldShape::Container(&EXECUTION_PAYLOAD_HEADER_FIELD_NAMES[..num_fields])
+        }
+        "block_roots" | "state_roots" => hash256_vector(T::SlotsPerHistoricalRoot::to_usize()),
+        "randao_mixes" => hash256_vector(T::EpochsPerHistoricalVector::to_usize()),
+        "slashings" => {
+            let len = T::EpochsPerSlashingsVector::to_usize();
+            FieldShape::Sequence {
+                is_list: false,
+                depth: int_log(len / u64s_per_chunk),
+                items_per_chunk: u64s_per_chunk,
+                len,
+                element_fields: None,
+            }
+        }
+        "historical_roots" => FieldShape::Sequence {
+            is_list: true,
+            depth: int_log(T::HistoricalRootsLimit::to_usize()),
+            items_per_chunk: 1,
+            len: state.historical_roots().len(),
+            element_fields: None,
+        },
+        "historical_summaries" => FieldShape::Sequence {
+            is_list: true,
+            depth: int_log(T::HistoricalRootsLimit::to_usize()),
+            items_per_chunk: 1,
+            len: state
+                .historical_summaries()
+                .map_or(0, |summaries| summaries.len()),
+            element_fields: None,
+        },
+        "eth1_data_votes" => FieldShape::Sequence {
+            is_list: true,
+            depth: int_log(T::SlotsPerEth1VotingPeriod::to_usize()),
+            items_per_chunk: 1,
+            len: state.eth1_data_votes().len(),
+            element_fields: Some(ETH1_DATA_FIELD_NAMES),
+        },
+        "validators" => FieldShape::Sequence {
+            is_list: true,
+            depth: int_log(T::ValidatorRegistryLimit::to_usize()),
+            items_per_chunk: 1,
+            len: state.validators().len(),
+            element_fields: Some(VALIDATOR_FIELD_NAMES),
+        },
+        "balances" => u64_list(
+            T::ValidatorRegistryLimit::to_usize(),
+            state.balances().len(),
+        ),
+        "inactivity_scores" => u64_list(
+            T::ValidatorRegistryLimit::to_usize(),
+            state.inactivity_scores().map_or(0, |scores| scores.len()),
+        ),
+        "previous_epoch_participation" | "current_epoch_participation"
+            if !matches!(state, BeaconState::Base(_)) =>
+        {
+            let len = if field_name == "previous_epoch_participation" {
+                state.previous_epoch_participation().map_or(0, |p| p.len())
+            } else {
+                state.current_epoch_participation().map_or(0, |p| p.len())
+            };
+            FieldShape::Sequence {
+                is_list: true,
+                depth: int_log(T::ValidatorRegistryLimit::to_usize() / BYTES_PER_CHUNK),
+                items_per_chunk: BYTES_PER_CHUNK,
+                len,
+                element_fields: None,
+            }
+        }
+        _ => FieldShape::Leaf,
+    }
+}
+
+/// Compute the generalized index of the node at `path` in the tree of `state`.
+///
+/// The path is a `.`-separated list of field names and list indices, e.g.
+/// `finalized_checkpoint.root` or `validators.3.effective_balance`. Items packed into a chunk
+/// with their neighbours (e.g. `balances.3`) resolve to the chunk containing them.
+pub fn generalized_index_for_path<T: EthSpec>(
+    state: &BeaconState<T>,
+    path: &str,
+) -> Result<usize, Error> {
+    let invalid_path = |reason: &str| Error::InvalidProofPath(format!("{path}: {reason}"));
+
+    let mut segments = path.split('.');
+    let field_name = segments.next().unwrap_or_default();
+    let field_index = field_names(state)
+        .iter()
+        .position(|name| *name == field_name)
+        .ok_or_else(|| invalid_path("unknown field"))?;
+    let field_gindex = NUM_BEACON_STATE_HASH_TREE_ROOT_LEAVES + field_index;
+
+    let rest = segments.collect::<Vec<_>>();
+    let container_gindex = |gindex: usize, fields: &[&str], name: &str| {
+        fields
+            .iter()
+            .position(|field| *field == name)
+            .map(|i| (gindex << int_log(fields.len())) + i)
+            .ok_or_else(|| invalid_path("unknown field"))
+    };
+
+    match (field_shape(state, field_name), rest.as_slice()) {
+        (_, []) => Ok(field_gindex),
+        (FieldShape::Container(fields), [name]) => container_gindex(field_gindex, fields, name),
+        (
+            FieldShape::Sequence {
+                is_list,
+                depth,
+                items_per_chunk,
+                len,
+                element_fields,
+            },
+            [index, element_rest @ ..],
+        ) => {
+            let index = index
+                .parse::<usize>()
+                .map_err(|_| invalid_path("invalid index"))?;
+            if index >= len {
+                return Err(invalid_path("index out of bounds"));
+            }
+            // Lists have their data tree to the left of the length mix-in.
+            let data_gindex = if is_list {
+                field_gindex * 2
+            } else {
+                field_gindex
+            };
+            let chunk_gindex = (data_gindex << depth) + index / items_per_chunk;
+
+            match (element_fields, element_rest) {
+                (_, []) => Ok(chunk_gindex),
+                (Some(fields), [name]) => container_gindex(chunk_gindex, fields, name),
+                _ => Err(invalid_path("unsupported path")),
+            }
+        }
+        _ => Err(invalid_path("unsupported path")),
+    }
+}
+
+/// Returns the Merkle leaves of the container at `field_index` of `state`, if it is a container.
+pub fn container_field_leaves<T: EthSpec>(
+    state: &BeaconState<T>,
+    field_index: usize,
+) -> Option<Vec<Hash256>> {
+    let leaves = match *field_names(state).get(field_index)? {
+        "fork" => {
+            let fork = state.fork();
+            vec![
+                fork.previous_version.tree_hash_root(),
+                fork.current_version.tree_hash_root(),
+                fork.epoch.tree_hash_root(),
+            ]
+        }
+        "latest_block_header" => {
+            let header = state.latest_block_header();
+            vec![
+                header.slot.tree_hash_root(),
+                header.proposer_index.tree_hash_root(),
+                header.parent_root.tree_hash_root(),
+                header.state_root.tree_hash_root(),
+                header.body_root.tree_hash_root(),
+            ]
+        }
+        "eth1_data" => eth1_data_field_leaves(state.eth1_data()),
+        "previous_justified_checkpoint" => {
+            checkpoint_field_leaves(state.previous_justified_checkpoint())
+        }
+        "current_justified_checkpoint" => {
+            checkpoint_field_leaves(state.current_justified_checkpoint())
+        }
+        "finalized_checkpoint" => checkpoint_field_leaves(state.finalized_checkpoint()),
+        "current_sync_committee" => {
+            sync_committee_field_leaves(state.current_sync_committee().ok()?)
+        }
+        "next_sync_committee" => sync_committee_field_leaves(state.next_sync_committee().ok()?),
+        "latest_execution_payload_header" => {
+            execution_payload_header_field_leaves(state.latest_execution_payload_header().ok()?)
+        }
+        _ => return None,
+    };
+    Some(leaves)
+}
+
+pub fn eth1_data_field_leaves(eth1_data: &Eth1Data) -> Vec<Hash256> {
+    vec![
+        eth1_data.deposit_root.tree_hash_root(),
+        eth1_data.deposit_count.tree_hash_root(),
+        eth1_data.block_hash.tree_hash_root(),
+    ]
+}
+
+fn checkpoint_field_leaves(checkpoint: Checkpoint) -> Vec<Hash256> {
+    vec![
+        checkpoint.epoch.tree_hash_root(),
+        checkpoint.root.tree_hash_root(),
+    ]
+}
+
+fn sync_committee_field_leaves<T: EthSpec>(sync_committee: &SyncCommittee<T>) -> Vec<Hash256> {
+    vec![
+        sync_committee.pubkeys.tree_hash_root(),
+        sync_committee.aggregate_pubkey.tree_hash_root(),
+    ]
+}
+
+pub fn validator_field_leaves(validator: &Validator) -> Vec<Hash256> {
+    vec![
+        validator.pubkey.tree_hash_root(),
+        validator.withdrawal_credentials.tree_hash_root(),
+        validator.effective_balance.tree_hash_root(),
+        validator.slashed.tree_hash_root(),
+        validator.activation_eligibility_epoch.tree_hash_root(),
+        validator.activation_epoch.tree_hash_root(),
+        validator.exit_epoch.tree_hash_root(),
+        validator.withdrawable_epoch.tree_hash_root(),
+    ]
+}
+
+fn execution_payload_header_field_leaves<T: EthSpec>(
+    header: ExecutionPayloadHeaderRef<T>,
+) -> Vec<Hash256> {
+    let mut leaves = vec![
+        header.parent_hash().tree_hash_root(),
+        header.fee_recipient().tree_hash_root(),
+        header.state_root().tree_hash_root(),
+        header.receipts_root().tree_hash_root(),
+        header.logs_bloom().tree_hash_root(),
+        header.prev_randao().tree_hash_root(),
+        header.block_number().tree_hash_root(),
+        header.gas_limit().tree_hash_root(),
+        header.gas_used().tree_hash_root(),
+        header.timestamp().tree_hash_root(),
+        header.extra_data().tree_hash_root(),
+        header.base_fee_per_gas().tree_hash_root(),
+        header.block_hash().tree_hash_root(),
+        header.transactions_root().tree_hash_root(),
+    ];
+    if let Ok(withdrawals_root) = header.withdrawals_root() {
+        leaves.push(withdrawals_root.tree_hash_root());
+    }
+    if let Ok(blob_gas_used) = header.blob_gas_used() {
+        leaves.push(blob_gas_used.tree_hash_root());
+    }
+    if let Ok(excess_blob_gas) = header.excess_blob_gas() {
+        leaves.push(excess_blob_gas.tree_hash_root());
+    }
+    leaves
+}
+
+/// Returns the depth of `generalized_index` in the tree (the root has depth `0`).
+pub fn generalized_index_depth(generalized_index: usize) -> usize {
+    (usize::BITS - 1 - generalized_index.leading_zeros()) as usize
+}
+
+/// Split `generalized_index` into the generalized index of its ancestor at `depth`, and its
+/// generalized index relative to that ancestor.
+///
+/// Returns `None` if `generalized_index` is shallower than `depth`.
+pub fn split_generalized_index(generalized_index: usize, depth: usize) -> Option<(usize, usize)> {
+    let relative_depth = generalized_index_depth(generalized_index).checked_sub(depth)?;
+    let ancestor = generalized_index >> relative_depth;
+    let relative = (1 << relative_depth) | (generalized_index & ((1 << relative_depth) - 1));
+    Some((ancestor, relative))
+}
+
+/// Returns the node at `generalized_index` in the tree of the given `depth` built from `leaves`.
+///
+/// Returns `None` if the node lies below the leaves of the tree.
+pub fn subtree_node(leaves: &[Hash256], depth: usize, generalized_index: usize) -> Option<Hash256> {
+    let node_depth = generalized_index_depth(generalized_index);
+    if node_depth > depth {
+        return None;
+    }
+
+    let tree = MerkleTree::create(leaves, depth);
+    let mut node = &tree;
+    for i in (0..node_depth).rev() {
+        let (left, right) = node.left_and_right_branches()?;
+        node = if (generalized_index >> i) & 1 == 1 {
+            right
+        } else {
+            left
+        };
+    }
+    Some(node.hash())
+}
@@ -604,3 +604,83 @@ fn tree_hash_cache_linear_history_long_skip() {
         target_slot
     );
 }
+
+#[test]
+fn merkle_multiproof_for_paths() {
+    let validator_count = 16;
+    let keypairs = generate_deterministic_keypairs(validator_count);
+
+    let spec = &test_spec::<MinimalEthSpec>();
+
+    let mut state: BeaconState<MinimalEthSpec> = interop_genesis_state_with_eth1(
+        &keypairs,
+        0,
+        Hash256::from_slice(DEFAULT_ETH1_BLOCK_HASH),
+        None,
+        spec,
+    )
+    .unwrap();
+
+    // Advance a few slots so the roots vectors are populated.
+    for _ in 0..3 {
+        let state_root = state.update_tree_hash_cache().unwrap();
+        per_slot_processing(&mut state, Some(state_root), spec).unwrap();
+    }
+    let state_root = state.update_tree_hash_cache().unwrap();
+
+    let paths = [
+        "slot",
+        "finalized_checkpoint.root",
+        "latest_block_header.parent_root",
+        "block_roots.1",
+        "validators.3.effective_balance",
+        "validators.7",
+        "balances.5",
+        "randao_mixes.0",
+    ];
+    let indices = paths
+        .iter()
+        .map(|path| state.generalized_index_for_path(path).unwrap())
+        .collect::<Vec<_>>();
+
+    // Each path should be provable on its own.
+    for (path, &index) in paths.iter().zip(&indices) {
+        let (leaves, branch) = state.compute_merkle_multiproof(&[index]).unwrap();
+        assert!(
+            merkle_proof::verify_merkle_multiproof(&leaves, &branch, &[index], state_root),
+            "invalid proof for {path}"
+        );
+    }
+
+    // And as a single multiproof.
+    let (leaves, branch) = state.compute_merkle_multiproof(&indices).unwrap();
+    assert!(merkle_proof::verify_merkle_multiproof(
+        &leaves, &branch, &indices, state_root
+    ));
+
+    assert_eq!(leaves[0], state.slot().tree_hash_root());
+    assert_eq!(leaves[1], state.finalized_checkpoint().root);
+    assert_eq!(leaves[3], *state.block_roots().get(1).unwrap());
+    assert_eq!(
+        leaves[4],
+        state.validators()[3].effective_balance.tree_hash_root()
+    );
+    assert_eq!(leaves[5], state.validators()[7].tree_hash_root());
+
+    // Paths to missing fields and out-of-bounds elements are rejected.
+    assert!(matches!(
+        state.generalized_index_for_path("finalized_checkpoint.slot"),
+        Err(BeaconStateError::InvalidProofPath(_))
+    ));
+    assert!(matches!(
+        state.generalized_index_for_path("validators.16"),
+        Err(BeaconStateError::InvalidProofPath(_))
+    ));
+
+    // Nodes inside a basic field cannot be proven.
+    let slot_index = state.generalized_index_for_path("slot").unwrap();
+    assert_eq!(
+        state.compute_merkle_multiproof(&[slot_index * 2]),
+        Err(BeaconStateError::IndexNotSupported(slot_index * 2))
+    );
+}
@@ -2,7 +2,7 @@
 #![allow(clippy::disallowed_methods)]
 #![allow(clippy::indexing_slicing)]
 
-use super::Error;
+use super::{proof, Error};
 use crate::historical_summary::HistoricalSummaryCache;
 use crate::{BeaconState, EthSpec, Hash256, ParticipationList, Slot, Unsigned, Validator};
 use cached_tree_hash::{int_log, CacheArena, CachedTreeHash, TreeHashCache};
@@ -21,6 +21,9 @@ use tree_hash::{mix_in_length, MerkleHasher, TreeHash};
 /// `BeaconState`. **Tree hashing will fail if this value is set incorrectly.**
 pub const NUM_BEACON_STATE_HASH_TREE_ROOT_LEAVES: usize = 32;
 
+/// The depth of the `BeaconState` Merkle tree, i.e. `log2(NUM_BEACON_STATE_HASH_TREE_ROOT_LEAVES)`.
+pub const BEACON_STATE_TREE_DEPTH: usize = 5;
+
 /// The number of nodes in the Merkle tree of a validator record.
 const NODES_PER_VALIDATOR: usize = 15;
 
@@ -372,6 +375,104 @@ impl<T: EthSpec> BeaconTreeHashCacheInner<T> {
         Ok(root)
     }
 
+    /// Returns the node at `generalized_index` beneath the field at `field_index`, where
+    /// `generalized_index` is relative to the field's root (i.e. `1` is the root of the field).
+    ///
+    /// Returns `None` if proofs of the requested node are not supported.
+    ///
+    /// The cache must be up to date with `state`, e.g. via `Self::recalculate_tree_hash_leaves`.
+    pub fn field_node(
+        &self,
+        state: &BeaconState<T>,
+        field_index: usize,
+        generalized_index: usize,
+    ) -> Result<Option<Hash256>, Error> {
+        let optional_list_node = |cache: &OptionalTreeHashCache, len: usize| {
+            cache.inner.as_ref().map_or(Ok(None), |inner| {
+                list_node(
+                    &inner.arena,
+                    &inner.tree_hash_cache,
+                    len,
+                    generalized_index,
+                    |_, _| Ok(None),
+                )
+            })
+        };
+
+        match proof::field_names(state).get(field_index).copied() {
+            Some("block_roots") => {
+                vector_node(&self.fixed_arena, &self.block_roots, generalized_index)
+            }
+            Some("state_roots") => {
+                vector_node(&self.fixed_arena, &self.state_roots, generalized_index)
+            }
+            Some("historical_roots") => list_node(
+                &self.fixed_arena,
+                &self.historical_roots,
+                state.historical_roots().len(),
+                generalized_index,
+                |_, _| Ok(None),
+            ),
+            Some("eth1_data_votes") => list_node(
+                &self.eth1_data_votes.arena,
+                &self.eth1_data_votes.tree_hash_cache,
+                state.eth1_data_votes().len(),
+                generalized_index,
+                |i, element_gindex| {
+                    Ok(state.eth1_data_votes().get(i).and_then(|eth1_data| {
+                        let leaves = proof::eth1_data_field_leaves(eth1_data);
+                        proof::subtree_node(&leaves, int_log(leaves.len()), element_gindex)
+                    }))
+                },
+            ),
+            Some("validators") => list_node(
+                &self.validators.list_arena,
+                &self.validators.list_cache,
+                state.validators().len(),
+                generalized_index,
+                |i, element_gindex| {
+                    Ok(state.validators().get(i).and_then(|validator| {
+                        let leaves = proof::validator_field_leaves(validator);
+                        proof::subtree_node(&leaves, int_log(leaves.len()), element_gindex)
+                    }))
+                },
+            ),
+            Some("balances") => list_node(
+                &self.balances_arena,
+                &self.balances,
+                state.balances().len(),
+                generalized_index,
+                |_, _| Ok(None),
+            ),
+            Some("randao_mixes") => {
+                vector_node(&self.fixed_arena, &self.randao_mixes, generalized_index)
+            }
+            Some("slashings") => {
+                vector_node(&self.slashings_arena, &self.slashings, generalized_index)
+            }
+            Some("previous_epoch_participation") => optional_list_node(
+                &self.previous_epoch_participation,
+                state.previous_epoch_participation()?.len(),
+            ),
+            Some("current_epoch_participation") => optional_list_node(
+                &self.current_epoch_participation,
+                state.current_epoch_participation()?.len(),
+            ),
+            Some("inactivity_scores") => {
+                optional_list_node(&self.inactivity_scores, state.inactivity_scores()?.len())
+            }
+            Some("historical_summaries") => optional_list_node(
+                &self.historical_summaries,
+                state.historical_summaries()?.len(),
+            ),
+            _ => Ok(
+                proof::container_field_leaves(state, field_index).and_then(|leaves| {
+                    proof::subtree_node(&leaves, int_log(leaves.len()), generalized_index)
+                }),
+            ),
+        }
+    }
+
     /// Updates the cache and provides the root of the given `validators`.
     pub fn recalculate_validators_tree_hash_root(
         &mut self,
@@ -381,6 +482,51 @@ impl<T: EthSpec> BeaconTreeHashCacheInner<T> {
     }
 }
 
+/// Returns the node at `generalized_index` (relative to the root) of the vector stored in `cache`.
+fn vector_node(
+    arena: &CacheArena,
+    cache: &TreeHashCache,
+    generalized_index: usize,
+) -> Result<Option<Hash256>, Error> {
+    let layer = proof::generalized_index_depth(generalized_index);
+    if layer > cache.depth() {
+        return Ok(None);
+    }
+    Ok(Some(cache.node(
+        arena,
+        layer,
+        generalized_index - (1 << layer),
+    )?))
+}
+
+/// Returns the node at `generalized_index` (relative to the root) of the list of length `len`
+/// stored in `cache`.
+///
+/// Nodes beneath the leaves of the list are resolved by `element_node`, which is given the index
+/// of the list element and the generalized index relative to the element's root.
+fn list_node(
+    arena: &CacheArena,
+    cache: &TreeHashCache,
+    len: usize,
+    generalized_index: usize,
+    element_node: impl Fn(usize, usize) -> Result<Option<Hash256>, Error>,
+) -> Result<Option<Hash256>, Error> {
+    // The list root is `hash(data_root, length)`.
+    if generalized_index == 3 {
+        return Ok(Some((len as u64).tree_hash_root()));
+    }
+    let Some((2, data_gindex)) = proof::split_generalized_index(generalized_index, 1) else {
+        return Ok(None);
+    };
+
+    match proof::split_generalized_index(data_gindex, cache.depth()) {
+        Some((leaf_gindex, element_gindex)) if element_gindex > 1 => {
+            element_node(leaf_gindex - (1 << cache.depth()), element_gindex)
+        }
+        _ => vector_node(arena, cache, data_gindex),
+    }
+}
+
 /// A specialized cache for computing the tree hash root of `state.validators`.
 #[derive(Debug, PartialEq, Clone, Default, Encode, Decode)]
 struct ValidatorsListTreeHashCache {
@@ -7,20 +7,32 @@ use crate::{
 use bls::PublicKeyBytes;
 use bls::Signature;
 use serde::{Deserialize, Deserializer, Serialize};
+use ssz::Decode;
+use ssz_derive::{Decode, Encode};
 use superstruct::superstruct;
 use tree_hash_derive::TreeHash;
 
 #[superstruct(
     variants(Merge, Capella, Deneb),
     variant_attributes(
-        derive(PartialEq, Debug, Serialize, Deserialize, TreeHash, Clone),
+        derive(
+            PartialEq,
+            Debug,
+            Encode,
+            Serialize,
+            Deserialize,
+            TreeHash,
+            Decode,
+            Clone
+        ),
         serde(bound = "E: EthSpec", deny_unknown_fields)
     ),
     map_ref_into(ExecutionPayloadHeaderRef),
     map_ref_mut_into(ExecutionPayloadHeaderRefMut)
 )]
-#[derive(PartialEq, Debug, Serialize, Deserialize, TreeHash, Clone)]
+#[derive(PartialEq, Debug, Encode, Serialize, Deserialize, TreeHash, Clone)]
 #[serde(bound = "E: EthSpec", deny_unknown_fields, untagged)]
+#[ssz(enum_behaviour = "transparent")]
 #[tree_hash(enum_behaviour = "transparent")]
 pub struct BuilderBid<E: EthSpec> {
     #[superstruct(only(Merge), partial_getter(rename = "header_merge"))]
@@ -40,6 +52,21 @@ impl<E: EthSpec> BuilderBid<E> {
     pub fn header(&self) -> ExecutionPayloadHeaderRef<'_, E> {
         self.to_ref().header()
     }
+
+    /// SSZ decode with fork variant passed in explicitly.
+    pub fn from_ssz_bytes_for_fork(
+        bytes: &[u8],
+        fork_name: ForkName,
+    ) -> Result<Self, ssz::DecodeError> {
+        match fork_name {
+            ForkName::Merge => BuilderBidMerge::from_ssz_bytes(bytes).map(Self::Merge),
+            ForkName::Capella => BuilderBidCapella::from_ssz_bytes(bytes).map(Self::Capella),
+            ForkName::Deneb => BuilderBidDeneb::from_ssz_bytes(bytes).map(Self::Deneb),
+            ForkName::Base | ForkName::Altair => Err(ssz::DecodeError::BytesInvalid(format!(
+                "unsupported fork for BuilderBid: {fork_name}",
+            ))),
+        }
+    }
 }
 
 impl<'a, E: EthSpec> BuilderBidRef<'a, E> {
@@ -61,7 +88,7 @@ impl<'a, E: EthSpec> BuilderBidRefMut<'a, E> {
 impl<E: EthSpec> SignedRoot for BuilderBid<E> {}
 
 /// Validator registration, for use in interacting with servers implementing the builder API.
-#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+#[derive(PartialEq, Debug, Encode, Serialize, Deserialize, Clone)]
 #[serde(bound = "E: EthSpec")]
 pub struct SignedBuilderBid<E: EthSpec> {
     pub message: BuilderBid<E>,
@@ -110,6 +137,24 @@ impl<T: EthSpec> ForkVersionDeserialize for SignedBuilderBid<T> {
 }
 
 impl<E: EthSpec> SignedBuilderBid<E> {
+    /// SSZ decode with fork variant passed in explicitly.
+    pub fn from_ssz_bytes_for_fork(
+        bytes: &[u8],
+        fork_name: ForkName,
+    ) -> Result<Self, ssz::DecodeError> {
+        let mut builder = ssz::SszDecoderBuilder::new(bytes);
+
+        builder.register_anonymous_variable_length_item()?;
+        builder.register_type::<Signature>()?;
+
+        let mut decoder = builder.build()?;
+        let message = decoder
+            .decode_next_with(|bytes| BuilderBid::from_ssz_bytes_for_fork(bytes, fork_name))?;
+        let signature = decoder.decode_next()?;
+
+        Ok(Self { message, signature })
+    }
+
     pub fn verify_signature(&self, spec: &ChainSpec) -> bool {
         self.message
             .pubkey()
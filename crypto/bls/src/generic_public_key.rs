@@ -25,6 +25,10 @@ pub trait TPublicKey: Sized + Clone {
 
     /// Deserialize `self` from compressed bytes.
     fn deserialize(bytes: &[u8]) -> Result<Self, Error>;
+
+    /// Combine public key shares, each paired with its non-zero and unique share index, into the
+    /// threshold public key via Lagrange interpolation.
+    fn interpolate(shares: &[(u64, &Self)]) -> Result<Self, Error>;
 }
 
 /// A BLS public key that is generic across some BLS point (`Pub`).
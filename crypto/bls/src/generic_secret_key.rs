@@ -25,6 +25,10 @@ pub trait TSecretKey<SignaturePoint, PublicKeyPoint>: Sized {
 
     /// Deserialize `self` from compressed bytes.
     fn deserialize(bytes: &[u8]) -> Result<Self, Error>;
+
+    /// Split `self` into one share for each of the non-zero and unique `indices`, such that the
+    /// signatures of any `threshold` shares may be combined into a signature by `self`.
+    fn split(&self, threshold: usize, indices: &[u64]) -> Result<Vec<Self>, Error>;
 }
 
 #[derive(Clone)]
@@ -50,6 +54,20 @@ where
         }
     }
 
+    /// Instantiates `Self` from a `point`.
+    pub(crate) fn from_point(point: Sec) -> Self {
+        Self {
+            point,
+            _phantom_signature: PhantomData,
+            _phantom_public_key: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the underlying BLS point.
+    pub(crate) fn point(&self) -> &Sec {
+        &self.point
+    }
+
     /// Signs `msg`.
     pub fn sign(&self, msg: Hash256) -> GenericSignature<Pub, Sig> {
         let is_infinity = false;
@@ -36,6 +36,10 @@ pub trait TSignature<GenericPublicKey>: Sized + Clone {
 
     /// Returns `true` if `self` is a signature across `msg` by `pubkey`.
     fn verify(&self, pubkey: &GenericPublicKey, msg: Hash256) -> bool;
+
+    /// Combine signature shares, each paired with its non-zero and unique share index, into the
+    /// signature of the threshold key via Lagrange interpolation.
+    fn interpolate(shares: &[(u64, &Self)]) -> Result<Self, Error>;
 }
 
 /// A BLS signature that is generic across:
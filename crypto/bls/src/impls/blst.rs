@@ -7,9 +7,10 @@ use crate::{
     Error, Hash256, ZeroizeHash, INFINITY_SIGNATURE,
 };
 pub use blst::min_pk as blst_core;
-use blst::{blst_scalar, BLST_ERROR};
+use blst::{blst_fr, blst_p1, blst_p1_affine, blst_p2, blst_p2_affine, blst_scalar, BLST_ERROR};
 use rand::Rng;
 use std::iter::ExactSizeIterator;
+use zeroize::Zeroize;
 
 pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
 pub const RAND_BITS: usize = 64;
@@ -133,6 +134,35 @@ impl TPublicKey for blst_core::PublicKey {
         }
         Self::key_validate(bytes).map_err(Into::into)
     }
+
+    fn interpolate(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        let indices = shares.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+        let mut sum = blst_p1::default();
+        for ((_, share), coefficient) in shares.iter().zip(lagrange_coefficients(&indices)) {
+            let bytes = share.compress();
+            let mut affine = blst_p1_affine::default();
+            let mut point = blst_p1::default();
+            let mut product = blst_p1::default();
+            let sum_ptr: *mut blst_p1 = &mut sum;
+            // Safe since all pointers are to valid points of the expected types, and `blst`
+            // permits the output of an addition to alias its input.
+            unsafe {
+                let err = blst::blst_p1_uncompress(&mut affine, bytes.as_ptr());
+                if err != BLST_ERROR::BLST_SUCCESS {
+                    return Err(err.into());
+                }
+                blst::blst_p1_from_affine(&mut point, &affine);
+                blst::blst_p1_mult(&mut product, &point, coefficient.b.as_ptr(), 255);
+                blst::blst_p1_add_or_double(sum_ptr, sum_ptr, &product);
+            }
+        }
+
+        let mut bytes = [0; PUBLIC_KEY_BYTES_LEN];
+        unsafe {
+            blst::blst_p1_compress(bytes.as_mut_ptr(), &sum);
+        }
+        Self::from_bytes(&bytes).map_err(Into::into)
+    }
 }
 
 /// A wrapper that allows for `PartialEq` and `Clone` impls.
@@ -180,6 +210,35 @@ impl TSignature<blst_core::PublicKey> for blst_core::Signature {
         // Check Signature inside function for subgroup
         self.verify(true, msg.as_bytes(), DST, &[], pubkey, false) == BLST_ERROR::BLST_SUCCESS
     }
+
+    fn interpolate(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        let indices = shares.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+        let mut sum = blst_p2::default();
+        for ((_, share), coefficient) in shares.iter().zip(lagrange_coefficients(&indices)) {
+            let bytes = share.compress();
+            let mut affine = blst_p2_affine::default();
+            let mut point = blst_p2::default();
+            let mut product = blst_p2::default();
+            let sum_ptr: *mut blst_p2 = &mut sum;
+            // Safe since all pointers are to valid points of the expected types, and `blst`
+            // permits the output of an addition to alias its input.
+            unsafe {
+                let err = blst::blst_p2_uncompress(&mut affine, bytes.as_ptr());
+                if err != BLST_ERROR::BLST_SUCCESS {
+                    return Err(err.into());
+                }
+                blst::blst_p2_from_affine(&mut point, &affine);
+                blst::blst_p2_mult(&mut product, &point, coefficient.b.as_ptr(), 255);
+                blst::blst_p2_add_or_double(sum_ptr, sum_ptr, &product);
+            }
+        }
+
+        let mut bytes = [0; SIGNATURE_BYTES_LEN];
+        unsafe {
+            blst::blst_p2_compress(bytes.as_mut_ptr(), &sum);
+        }
+        Self::from_bytes(&bytes).map_err(Into::into)
+    }
 }
 
 /// A wrapper that allows for `PartialEq` and `Clone` impls.
@@ -279,4 +338,104 @@ impl TSecretKey<blst_core::Signature, blst_core::PublicKey> for blst_core::Secre
     fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
         Self::from_bytes(bytes).map_err(Into::into)
     }
+
+    fn split(&self, threshold: usize, indices: &[u64]) -> Result<Vec<Self>, Error> {
+        // The coefficients of a random polynomial of degree `threshold - 1` which is equal to
+        // `self` at zero. The first coefficient is `self`, so they are all zeroized once the shares
+        // are computed.
+        let mut coefficients = std::iter::once(fr_from_secret_key(self))
+            .chain((1..threshold).map(|_| fr_from_secret_key(&Self::random())))
+            .collect::<Vec<_>>();
+
+        let shares = indices
+            .iter()
+            .map(|&index| {
+                let x = fr_from_u64(index);
+                let mut y = blst_fr::default();
+                let y_ptr: *mut blst_fr = &mut y;
+                // Evaluate the polynomial at `index` using Horner's method.
+                for coefficient in coefficients.iter().rev() {
+                    // Safe since `blst` permits the output of field operations to alias inputs.
+                    unsafe {
+                        blst::blst_fr_mul(y_ptr, y_ptr, &x);
+                        blst::blst_fr_add(y_ptr, y_ptr, coefficient);
+                    }
+                }
+
+                let mut scalar = blst_scalar::default();
+                let mut bytes = [0; 32];
+                unsafe {
+                    blst::blst_scalar_from_fr(&mut scalar, &y);
+                    blst::blst_bendian_from_scalar(bytes.as_mut_ptr(), &scalar);
+                }
+                let share = Self::from_bytes(&bytes).map_err(Into::into);
+                bytes.zeroize();
+                scalar.b.zeroize();
+                y.l.zeroize();
+                share
+            })
+            .collect();
+
+        for coefficient in coefficients.iter_mut() {
+            coefficient.l.zeroize();
+        }
+        shares
+    }
+}
+
+fn fr_from_u64(value: u64) -> blst_fr {
+    let mut fr = blst_fr::default();
+    unsafe {
+        blst::blst_fr_from_uint64(&mut fr, [value, 0, 0, 0].as_ptr());
+    }
+    fr
+}
+
+fn fr_from_secret_key(secret_key: &blst_core::SecretKey) -> blst_fr {
+    let mut bytes = secret_key.to_bytes();
+    let mut scalar = blst_scalar::default();
+    let mut fr = blst_fr::default();
+    unsafe {
+        blst::blst_scalar_from_bendian(&mut scalar, bytes.as_ptr());
+        blst::blst_fr_from_scalar(&mut fr, &scalar);
+    }
+    bytes.zeroize();
+    scalar.b.zeroize();
+    fr
+}
+
+/// Returns the Lagrange coefficient of each of the distinct, non-zero `indices` for
+/// interpolating a polynomial at zero.
+fn lagrange_coefficients(indices: &[u64]) -> Vec<blst_scalar> {
+    let xs = indices
+        .iter()
+        .map(|&index| fr_from_u64(index))
+        .collect::<Vec<_>>();
+    xs.iter()
+        .enumerate()
+        .map(|(i, x_i)| {
+            // The coefficient is the product of `x_j / (x_j - x_i)` for all `j != i`.
+            let mut numerator = fr_from_u64(1);
+            let mut denominator = fr_from_u64(1);
+            let mut coefficient = blst_fr::default();
+            let mut scalar = blst_scalar::default();
+            let numerator_ptr: *mut blst_fr = &mut numerator;
+            let denominator_ptr: *mut blst_fr = &mut denominator;
+            unsafe {
+                for (j, x_j) in xs.iter().enumerate() {
+                    if i != j {
+                        let mut difference = blst_fr::default();
+                        blst::blst_fr_sub(&mut difference, x_j, x_i);
+                        blst::blst_fr_mul(numerator_ptr, numerator_ptr, x_j);
+                        blst::blst_fr_mul(denominator_ptr, denominator_ptr, &difference);
+                    }
+                }
+                let mut inverse = blst_fr::default();
+                blst::blst_fr_inverse(&mut inverse, &denominator);
+                blst::blst_fr_mul(&mut coefficient, &numerator, &inverse);
+                blst::blst_scalar_from_fr(&mut scalar, &coefficient);
+            }
+            scalar
+        })
+        .collect()
 }
@@ -84,6 +84,10 @@ impl TPublicKey for milagro::PublicKey {
     fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
         Self::from_bytes(bytes).map_err(Into::into)
     }
+
+    fn interpolate(_shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        Err(Error::ThresholdUnsupported)
+    }
 }
 
 impl TAggregatePublicKey<milagro::PublicKey> for milagro::AggregatePublicKey {
@@ -115,6 +119,10 @@ impl TSignature<milagro::PublicKey> for milagro::Signature {
     fn verify(&self, pubkey: &milagro::PublicKey, msg: Hash256) -> bool {
         self.verify(msg.as_bytes(), pubkey)
     }
+
+    fn interpolate(_shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        Err(Error::ThresholdUnsupported)
+    }
 }
 
 impl TAggregateSignature<milagro::PublicKey, milagro::AggregatePublicKey, milagro::Signature>
@@ -191,4 +199,8 @@ impl TSecretKey<milagro::Signature, milagro::PublicKey> for milagro::SecretKey {
     fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
         Self::from_bytes(bytes).map_err(Into::into)
     }
+
+    fn split(&self, _threshold: usize, _indices: &[u64]) -> Result<Vec<Self>, Error> {
+        Err(Error::ThresholdUnsupported)
+    }
 }
@@ -31,6 +31,7 @@ mod generic_signature;
 mod generic_signature_bytes;
 mod generic_signature_set;
 mod get_withdrawal_credentials;
+mod threshold;
 mod zeroize_hash;
 
 pub mod impls;
@@ -64,6 +65,14 @@ pub enum Error {
     InvalidInfinityPublicKey,
     /// The secret key is all zero bytes, which is invalid.
     InvalidZeroSecretKey,
+    /// A threshold share index was zero or was used for more than one share.
+    InvalidShareIndex(u64),
+    /// The threshold must be non-zero and no greater than the number of shares.
+    InvalidThreshold { threshold: usize, num_shares: usize },
+    /// A signature share was "empty", so it cannot be combined with other shares.
+    EmptySignatureShare,
+    /// The backend does not support threshold signatures.
+    ThresholdUnsupported,
 }
 
 #[cfg(feature = "milagro")]
@@ -0,0 +1,113 @@
+//! Threshold (t-of-n) BLS keys and signatures.
+//!
+//! A secret key is split into shares which are the evaluations of a random polynomial of degree
+//! `threshold - 1` at each share index, where the constant term is the original secret key. The
+//! signatures (or public keys) of any `threshold` shares can then be combined into the signature
+//! (or public key) of the original key by Lagrange interpolation at zero.
+use crate::{
+    generic_public_key::{GenericPublicKey, TPublicKey, INFINITY_PUBLIC_KEY},
+    generic_secret_key::{GenericSecretKey, TSecretKey},
+    generic_signature::{GenericSignature, TSignature, INFINITY_SIGNATURE},
+    Error,
+};
+use std::collections::HashSet;
+
+/// Check that each share index is non-zero and unique.
+///
+/// A zero index would reveal the secret key, whilst duplicate indices make interpolation
+/// impossible.
+fn check_share_indices(indices: impl Iterator<Item = u64>) -> Result<(), Error> {
+    let mut seen = HashSet::new();
+    for index in indices {
+        if index == 0 || !seen.insert(index) {
+            return Err(Error::InvalidShareIndex(index));
+        }
+    }
+    Ok(())
+}
+
+impl<Sig, Pub, Sec> GenericSecretKey<Sig, Pub, Sec>
+where
+    Sig: TSignature<Pub>,
+    Pub: TPublicKey,
+    Sec: TSecretKey<Sig, Pub>,
+{
+    /// Split `self` into one share for each of `indices`, such that the signatures of any
+    /// `threshold` of the shares may be combined with `GenericSignature::combine_shares`.
+    pub fn split(&self, threshold: usize, indices: &[u64]) -> Result<Vec<Self>, Error> {
+        if threshold == 0 || threshold > indices.len() {
+            return Err(Error::InvalidThreshold {
+                threshold,
+                num_shares: indices.len(),
+            });
+        }
+        check_share_indices(indices.iter().copied())?;
+
+        Ok(self
+            .point()
+            .split(threshold, indices)?
+            .into_iter()
+            .map(Self::from_point)
+            .collect())
+    }
+}
+
+impl<Pub> GenericPublicKey<Pub>
+where
+    Pub: TPublicKey,
+{
+    /// Combine the public keys of at least `threshold` shares, each paired with its share index,
+    /// into the public key that was split.
+    pub fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        if shares.is_empty() {
+            return Err(Error::InvalidThreshold {
+                threshold: 0,
+                num_shares: 0,
+            });
+        }
+        check_share_indices(shares.iter().map(|(index, _)| *index))?;
+
+        let points = shares
+            .iter()
+            .map(|(index, pubkey)| (*index, pubkey.point()))
+            .collect::<Vec<_>>();
+        let point = Pub::interpolate(&points)?;
+
+        if point.serialize() == INFINITY_PUBLIC_KEY {
+            Err(Error::InvalidInfinityPublicKey)
+        } else {
+            Ok(Self::from_point(point))
+        }
+    }
+}
+
+impl<Pub, Sig> GenericSignature<Pub, Sig>
+where
+    Sig: TSignature<Pub>,
+{
+    /// Combine the signatures of at least `threshold` shares across the same message, each paired
+    /// with its share index, into the signature of the key that was split.
+    pub fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        if shares.is_empty() {
+            return Err(Error::InvalidThreshold {
+                threshold: 0,
+                num_shares: 0,
+            });
+        }
+        check_share_indices(shares.iter().map(|(index, _)| *index))?;
+
+        let points = shares
+            .iter()
+            .map(|(index, signature)| {
+                signature
+                    .point()
+                    .map(|point| (*index, point))
+                    .ok_or(Error::EmptySignatureShare)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let point = Sig::interpolate(&points)?;
+        let is_infinity = point.serialize() == INFINITY_SIGNATURE;
+
+        Ok(Self::from_point(point, is_infinity))
+    }
+}
@@ -508,6 +508,108 @@ macro_rules! test_suite {
 
 mod blst {
     test_suite!(blst_implementations);
+
+    /// Split `secret` into 2-of-3 shares with indices 1, 2 and 3.
+    fn split_2_of_3(secret: &SecretKey) -> Vec<(u64, SecretKey)> {
+        let indices = [1, 2, 3];
+        let shares = secret.split(2, &indices).unwrap();
+        indices.into_iter().zip(shares).collect()
+    }
+
+    #[test]
+    fn threshold_signature_any_2_of_3() {
+        let secret = secret_from_u64(42);
+        let msg = Hash256::from_low_u64_be(7);
+        let shares = split_2_of_3(&secret);
+        let signatures = shares
+            .iter()
+            .map(|(index, share)| (*index, share.sign(msg)))
+            .collect::<Vec<_>>();
+
+        for (a, b) in [(0, 1), (0, 2), (1, 2), (2, 0)] {
+            let combined = Signature::combine_shares(&[
+                (signatures[a].0, &signatures[a].1),
+                (signatures[b].0, &signatures[b].1),
+            ])
+            .unwrap();
+            assert_eq!(combined, secret.sign(msg));
+            assert!(combined.verify(&secret.public_key(), msg));
+        }
+
+        // Using more shares than the threshold still recovers the signature.
+        let all = signatures
+            .iter()
+            .map(|(index, signature)| (*index, signature))
+            .collect::<Vec<_>>();
+        assert_eq!(Signature::combine_shares(&all).unwrap(), secret.sign(msg));
+    }
+
+    #[test]
+    fn threshold_signature_below_threshold() {
+        let secret = secret_from_u64(42);
+        let msg = Hash256::from_low_u64_be(7);
+        let shares = split_2_of_3(&secret);
+        let signature = shares[0].1.sign(msg);
+
+        let combined = Signature::combine_shares(&[(shares[0].0, &signature)]).unwrap();
+        assert!(!combined.verify(&secret.public_key(), msg));
+    }
+
+    #[test]
+    fn threshold_public_key() {
+        let secret = secret_from_u64(42);
+        let shares = split_2_of_3(&secret);
+        let public_keys = shares
+            .iter()
+            .map(|(index, share)| (*index, share.public_key()))
+            .collect::<Vec<_>>();
+
+        let combined = PublicKey::combine_shares(&[
+            (public_keys[2].0, &public_keys[2].1),
+            (public_keys[0].0, &public_keys[0].1),
+        ])
+        .unwrap();
+        assert_eq!(combined, secret.public_key());
+    }
+
+    #[test]
+    fn threshold_invalid_shares() {
+        let secret = secret_from_u64(42);
+        let msg = Hash256::from_low_u64_be(7);
+
+        assert_eq!(
+            secret.split(0, &[1, 2]).unwrap_err(),
+            bls::Error::InvalidThreshold {
+                threshold: 0,
+                num_shares: 2
+            }
+        );
+        assert_eq!(
+            secret.split(3, &[1, 2]).unwrap_err(),
+            bls::Error::InvalidThreshold {
+                threshold: 3,
+                num_shares: 2
+            }
+        );
+        assert_eq!(
+            secret.split(1, &[0, 1]).unwrap_err(),
+            bls::Error::InvalidShareIndex(0)
+        );
+        assert_eq!(
+            secret.split(1, &[2, 2]).unwrap_err(),
+            bls::Error::InvalidShareIndex(2)
+        );
+
+        let signature = secret.sign(msg);
+        assert_eq!(
+            Signature::combine_shares(&[(1, &signature), (1, &signature)]).unwrap_err(),
+            bls::Error::InvalidShareIndex(1)
+        );
+        assert_eq!(
+            Signature::combine_shares(&[(1, &signature), (2, &Signature::empty())]).unwrap_err(),
+            bls::Error::EmptySignatureShare
+        );
+    }
 }
 
 #[cfg(all(feature = "milagro", not(debug_assertions)))]
@@ -0,0 +1,362 @@
+//! Export and import of finalized checkpoint snapshots.
+//!
+//! A snapshot is a directory containing the finalized state, its block, the block's blobs (if
+//! any) and the genesis state, all as SSZ, alongside a JSON manifest describing them. Importing a
+//! snapshot seeds a fresh database using the same code path as checkpoint sync.
+use beacon_chain::{
+    builder::Witness, eth1_chain::CachingEth1Backend, slot_clock::SystemTimeSlotClock,
+};
+use beacon_node::{ClientBuilder, ClientConfig, ClientGenesis};
+use clap::ArgMatches;
+use environment::RuntimeContext;
+use serde::{Deserialize, Serialize};
+use slog::{info, warn, Logger};
+use ssz::{Decode, Encode};
+use std::fs;
+use std::path::{Path, PathBuf};
+use store::{BeaconNodeBackend, HotColdDB};
+use types::{BeaconState, EthSpec, ForkName, Hash256, SignedBeaconBlock, Slot};
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const STATE_FILE: &str = "state.ssz";
+pub const BLOCK_FILE: &str = "block.ssz";
+pub const BLOBS_FILE: &str = "blobs.ssz";
+pub const GENESIS_STATE_FILE: &str = "genesis_state.ssz";
+
+/// Describes the contents of a checkpoint snapshot.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointManifest {
+    /// The name of the network the snapshot was taken from, if known.
+    pub config_name: Option<String>,
+    pub fork_name: ForkName,
+    pub genesis_validators_root: Hash256,
+    /// The slot and root of the finalized state in `STATE_FILE`.
+    pub state_slot: Slot,
+    pub state_root: Hash256,
+    /// The slot and root of the block in `BLOCK_FILE`.
+    pub block_slot: Slot,
+    pub block_root: Hash256,
+    /// The number of blobs in `BLOBS_FILE`, or 0 if the file is absent.
+    pub num_blobs: usize,
+}
+
+pub struct ExportCheckpointConfig {
+    pub(crate) output_dir: PathBuf,
+}
+
+pub fn parse_export_checkpoint_config(
+    cli_args: &ArgMatches,
+) -> Result<ExportCheckpointConfig, String> {
+    let output_dir = clap_utils::parse_required(cli_args, "output-dir")?;
+    Ok(ExportCheckpointConfig { output_dir })
+}
+
+pub struct ImportCheckpointConfig {
+    pub(crate) input_dir: PathBuf,
+}
+
+pub fn parse_import_checkpoint_config(
+    cli_args: &ArgMatches,
+) -> Result<ImportCheckpointConfig, String> {
+    let input_dir = clap_utils::parse_required(cli_args, "input-dir")?;
+    Ok(ImportCheckpointConfig { input_dir })
+}
+
+/// Write the finalized state, block and blobs of the database to a new snapshot directory.
+pub fn export_checkpoint<E: EthSpec>(
+    export_config: ExportCheckpointConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+    let output_dir = export_config.output_dir;
+
+    if output_dir.exists() {
+        return Err(format!("Output directory already exists: {output_dir:?}"));
+    }
+
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    // The split state is the most recent finalized state, advanced to an epoch boundary.
+    let split = db.get_split_info();
+    let state = db
+        .get_state(&split.state_root, Some(split.slot))
+        .map_err(|e| format!("Error reading finalized state: {e:?}"))?
+        .ok_or_else(|| {
+            format!(
+                "Finalized state {:?} missing from database",
+                split.state_root
+            )
+        })?;
+    let block_root = state.get_latest_block_root(split.state_root);
+    let block = db
+        .get_full_block(&block_root)
+        .map_err(|e| format!("Error reading finalized block: {e:?}"))?
+        .ok_or_else(|| format!("Finalized block {block_root:?} missing from database"))?;
+    let blobs = if block.message().body().has_blobs() {
+        Some(
+            db.get_blobs(&block_root)
+                .map_err(|e| format!("Error reading finalized blobs: {e:?}"))?
+                .ok_or_else(|| format!("Blobs for block {block_root:?} missing from database"))?,
+        )
+    } else {
+        None
+    };
+    let genesis_state = db
+        .load_cold_state_by_slot(spec.genesis_slot)
+        .map_err(|e| format!("Error reading genesis state: {e:?}"))?
+        .ok_or("Genesis state missing from database")?;
+
+    let manifest = CheckpointManifest {
+        config_name: spec.config_name.clone(),
+        fork_name: state.fork_name_unchecked(),
+        genesis_validators_root: state.genesis_validators_root(),
+        state_slot: state.slot(),
+        state_root: split.state_root,
+        block_slot: block.slot(),
+        block_root,
+        num_blobs: blobs.as_ref().map_or(0, |blobs| blobs.len()),
+    };
+
+    fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Unable to create output directory: {e:?}"))?;
+    write_file(&output_dir.join(STATE_FILE), &state.as_ssz_bytes())?;
+    write_file(&output_dir.join(BLOCK_FILE), &block.as_ssz_bytes())?;
+    if let Some(blobs) = &blobs {
+        write_file(&output_dir.join(BLOBS_FILE), &blobs.as_ssz_bytes())?;
+    }
+    write_file(
+        &output_dir.join(GENESIS_STATE_FILE),
+        &genesis_state.as_ssz_bytes(),
+    )?;
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Unable to serialize manifest: {e:?}"))?;
+    write_file(&output_dir.join(MANIFEST_FILE), &manifest_json)?;
+
+    info!(
+        log,
+        "Exported checkpoint";
+        "state_slot" => manifest.state_slot,
+        "state_root" => ?manifest.state_root,
+        "block_root" => ?manifest.block_root,
+        "num_blobs" => manifest.num_blobs,
+        "output_dir" => ?output_dir,
+    );
+
+    Ok(())
+}
+
+/// Initialize a new database from the snapshot in `import_config.input_dir`.
+pub async fn import_checkpoint<E: EthSpec>(
+    import_config: ImportCheckpointConfig,
+    client_config: ClientConfig,
+    runtime_context: RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = runtime_context.eth2_config.spec.clone();
+    let input_dir = import_config.input_dir;
+
+    if client_config.get_db_path().exists() {
+        return Err(format!(
+            "Database already exists at {:?}, refusing to overwrite it",
+            client_config.get_db_path()
+        ));
+    }
+
+    let manifest_bytes = read_file(&input_dir.join(MANIFEST_FILE))?;
+    let manifest: CheckpointManifest = serde_json::from_slice(&manifest_bytes)
+        .map_err(|e| format!("Unable to parse manifest: {e:?}"))?;
+
+    match (&manifest.config_name, &spec.config_name) {
+        (Some(snapshot_network), Some(network)) if snapshot_network != network => {
+            return Err(format!(
+                "Snapshot is for network {snapshot_network} but the database is for {network}"
+            ));
+        }
+        (None, _) | (_, None) => {
+            warn!(
+                log,
+                "Unable to check snapshot network";
+                "snapshot_network" => ?manifest.config_name,
+                "network" => ?spec.config_name,
+            );
+        }
+        _ => (),
+    }
+
+    let anchor_state_bytes = read_file(&input_dir.join(STATE_FILE))?;
+    let anchor_block_bytes = read_file(&input_dir.join(BLOCK_FILE))?;
+    let anchor_blobs_bytes = if manifest.num_blobs > 0 {
+        Some(read_file(&input_dir.join(BLOBS_FILE))?)
+    } else {
+        None
+    };
+    let genesis_state_bytes = read_file(&input_dir.join(GENESIS_STATE_FILE))?;
+
+    // Check the files against the manifest before writing anything to disk.
+    let state_root = BeaconState::<E>::from_ssz_bytes(&anchor_state_bytes, &spec)
+        .map_err(|e| format!("Unable to parse snapshot state: {e:?}"))?
+        .update_tree_hash_cache()
+        .map_err(|e| format!("Unable to compute snapshot state root: {e:?}"))?;
+    if state_root != manifest.state_root {
+        return Err(format!(
+            "Snapshot state root {state_root:?} does not match manifest {:?}",
+            manifest.state_root
+        ));
+    }
+    let block_root = SignedBeaconBlock::<E>::from_ssz_bytes(&anchor_block_bytes, &spec)
+        .map_err(|e| format!("Unable to parse snapshot block: {e:?}"))?
+        .canonical_root();
+    if block_root != manifest.block_root {
+        return Err(format!(
+            "Snapshot block root {block_root:?} does not match manifest {:?}",
+            manifest.block_root
+        ));
+    }
+
+    let db_path = client_config.create_db_path()?;
+    let freezer_db_path = client_config.create_freezer_db_path()?;
+    let blobs_db_path = client_config.create_blobs_db_path()?;
+
+    info!(
+        log,
+        "Importing checkpoint";
+        "state_slot" => manifest.state_slot,
+        "state_root" => ?manifest.state_root,
+        "block_root" => ?manifest.block_root,
+    );
+
+    let client_genesis = ClientGenesis::WeakSubjSszBytes {
+        anchor_state_bytes,
+        anchor_block_bytes,
+        anchor_blobs_bytes,
+        genesis_state_bytes: Some(genesis_state_bytes),
+    };
+    let store_config = client_config.store.clone();
+
+    // Building the beacon chain persists the anchor, fork choice and head to disk, after which a
+    // beacon node started with the same datadir will resume from the snapshot.
+    ClientBuilder::<
+        Witness<
+            SystemTimeSlotClock,
+            CachingEth1Backend<E>,
+            E,
+            BeaconNodeBackend<E>,
+            BeaconNodeBackend<E>,
+        >,
+    >::new(runtime_context.eth_spec_instance.clone())
+    .runtime_context(runtime_context)
+    .chain_spec(spec)
+    .disk_store(
+        &db_path,
+        &freezer_db_path,
+        &blobs_db_path,
+        store_config,
+        log.clone(),
+    )?
+    .beacon_chain_builder(client_genesis, client_config)
+    .await?
+    .no_eth1_backend()?
+    .system_time_slot_clock()?
+    .build_beacon_chain()?;
+
+    info!(log, "Checkpoint imported successfully");
+    Ok(())
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|e| format!("Unable to read {path:?}: {e:?}"))
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    fs::write(path, bytes).map_err(|e| format!("Unable to write {path:?}: {e:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{build_chain, client_config, environment, open_db, E};
+    use tempfile::tempdir;
+
+    #[test]
+    fn export_import_round_trip() {
+        let env = environment();
+        let log = env.core_context().log().clone();
+
+        let source_dir = tempdir().unwrap();
+        let source_config = client_config(source_dir.path());
+        let harness = build_chain(&env, &source_config, 5 * E::slots_per_epoch() as usize);
+        let split = harness.chain.store.get_split_info();
+        let block_root = harness.finalized_checkpoint().root;
+        let block = harness
+            .chain
+            .store
+            .get_full_block(&block_root)
+            .unwrap()
+            .unwrap();
+        assert!(split.slot > 0, "the chain should have finalized");
+        drop(harness);
+
+        let snapshot_dir = tempdir().unwrap();
+        let output_dir = snapshot_dir.path().join("snapshot");
+        export_checkpoint(
+            ExportCheckpointConfig {
+                output_dir: output_dir.clone(),
+            },
+            source_config,
+            &env.core_context(),
+            log.clone(),
+        )
+        .unwrap();
+
+        let manifest: CheckpointManifest =
+            serde_json::from_slice(&read_file(&output_dir.join(MANIFEST_FILE)).unwrap()).unwrap();
+        assert_eq!(manifest.fork_name, ForkName::Altair);
+        assert_eq!(manifest.state_slot, split.slot);
+        assert_eq!(manifest.state_root, split.state_root);
+        assert_eq!(manifest.block_slot, block.slot());
+        assert_eq!(manifest.block_root, block_root);
+        assert_eq!(manifest.num_blobs, 0);
+
+        let target_dir = tempdir().unwrap();
+        let target_config = client_config(target_dir.path());
+        env.runtime()
+            .block_on(import_checkpoint(
+                ImportCheckpointConfig {
+                    input_dir: output_dir,
+                },
+                target_config.clone(),
+                env.core_context(),
+                log,
+            ))
+            .unwrap();
+
+        // The imported database is anchored at the snapshot's block, and holds its state.
+        let db = open_db(&env, &target_config);
+        let anchor = db
+            .get_anchor_info()
+            .expect("imported database should be anchored");
+        assert_eq!(anchor.anchor_slot, block.slot());
+        assert_eq!(anchor.oldest_block_slot, block.slot());
+        assert_eq!(anchor.oldest_block_parent, block.parent_root());
+        let mut state = db
+            .get_state(&split.state_root, Some(split.slot))
+            .unwrap()
+            .expect("imported database should have the snapshot state");
+        assert_eq!(state.update_tree_hash_cache().unwrap(), split.state_root);
+        assert_eq!(db.get_full_block(&block_root).unwrap(), Some(block));
+    }
+}
@@ -0,0 +1,386 @@
+//! Export of finalized history from the freezer database as era files.
+//!
+//! Each era file contains the blocks of `SLOTS_PER_HISTORICAL_ROOT` slots and the state at the
+//! end of them, see `store::era` for the format. Era files can be imported by another beacon node
+//! using `--era-import-dir`, as an alternative to backfill sync.
+use clap::ArgMatches;
+use environment::RuntimeContext;
+use slog::{info, Logger};
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use store::era::{era_block_slots, era_file_name, era_state_slot, EraWriter};
+use store::hot_cold_store::HotColdDBError;
+use store::{BeaconNodeBackend, Error, HotColdDB};
+use types::{ChainSpec, EthSpec, Hash256};
+
+type Db<E> = HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>;
+
+pub struct ExportEraConfig {
+    output_dir: PathBuf,
+    start_era: Option<u64>,
+    end_era: Option<u64>,
+}
+
+pub fn parse_export_era_config(cli_args: &ArgMatches) -> Result<ExportEraConfig, String> {
+    let output_dir = clap_utils::parse_required(cli_args, "output-dir")?;
+    let start_era = clap_utils::parse_optional(cli_args, "start-era")?;
+    let end_era = clap_utils::parse_optional(cli_args, "end-era")?;
+    Ok(ExportEraConfig {
+        output_dir,
+        start_era,
+        end_era,
+    })
+}
+
+/// Write an era file for each era of finalized history in the freezer database.
+///
+/// Files which already exist in the output directory are skipped, so an interrupted export can be
+/// resumed by running it again.
+pub fn export_era<E: EthSpec>(
+    export_config: ExportEraConfig,
+    client_config: beacon_node::ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+    let output_dir = export_config.output_dir;
+
+    let config_name = spec
+        .config_name
+        .clone()
+        .ok_or("The network has no config name, which is required to name era files")?;
+
+    let db = Db::<E>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    // The blocks of an era must all be stored, as must the state at its end, which has to be in
+    // the freezer.
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let oldest_block_slot = db.get_oldest_block_slot();
+    let first_complete_era = if oldest_block_slot == 0 {
+        0
+    } else {
+        oldest_block_slot.as_u64().div_ceil(slots_per_era) + 1
+    };
+    let last_finalized_era = db.get_split_info().slot.as_u64().saturating_sub(1) / slots_per_era;
+
+    let start_era = export_config.start_era.unwrap_or(first_complete_era);
+    let end_era = export_config.end_era.unwrap_or(last_finalized_era);
+    if start_era < first_complete_era {
+        return Err(format!(
+            "Era {start_era} is incomplete, the oldest stored block is at slot {oldest_block_slot}. \
+             The earliest era that can be exported is {first_complete_era}"
+        ));
+    }
+    if end_era > last_finalized_era {
+        return Err(format!(
+            "Era {end_era} is not finalized, the latest era that can be exported is \
+             {last_finalized_era}"
+        ));
+    }
+
+    fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Unable to create output directory: {e:?}"))?;
+
+    info!(
+        log,
+        "Exporting era files";
+        "start_era" => start_era,
+        "end_era" => end_era,
+        "output_dir" => ?output_dir,
+    );
+
+    for era in start_era..=end_era {
+        let state_slot = era_state_slot::<E>(era);
+        let state = db
+            .load_cold_state_by_slot(state_slot)
+            .map_err(|e| format!("Error reading state at slot {state_slot}: {e:?}"))?
+            .ok_or_else(|| {
+                format!(
+                    "State at slot {state_slot} is not stored, historic states can be \
+                     reconstructed with --reconstruct-historic-states"
+                )
+            })?;
+
+        let file_name = era_file_name(&config_name, era, &state);
+        let path = output_dir.join(&file_name);
+        if path.exists() {
+            info!(log, "Skipping existing era file"; "era" => era, "file" => file_name);
+            continue;
+        }
+
+        // Write to a temporary file first so that a partially written file is never mistaken for
+        // a complete one.
+        let tmp_path = output_dir.join(format!("{file_name}.tmp"));
+        let file =
+            File::create(&tmp_path).map_err(|e| format!("Unable to create {tmp_path:?}: {e:?}"))?;
+        let mut writer = EraWriter::<_, E>::new(BufWriter::new(file), era)
+            .map_err(|e| format!("Unable to write {tmp_path:?}: {e:?}"))?;
+
+        let mut num_blocks = 0;
+        for block_root in era_block_roots(&db, era, spec)? {
+            let block = db
+                .get_full_block(&block_root)
+                .map_err(|e| match e {
+                    Error::HotColdDBError(
+                        HotColdDBError::MissingFullBlockExecutionPayloadPruned(_, slot),
+                    ) => format!(
+                        "Execution payload of the block at slot {slot} has been pruned, era \
+                         files can only be exported by nodes running with --prune-payloads false"
+                    ),
+                    e => format!("Error reading block {block_root:?}: {e:?}"),
+                })?
+                .ok_or_else(|| format!("Block {block_root:?} missing from database"))?;
+            if block.slot() < era_block_slots::<E>(era).start {
+                continue;
+            }
+            writer
+                .write_block(&block)
+                .map_err(|e| format!("Unable to write {tmp_path:?}: {e:?}"))?;
+            num_blocks += 1;
+        }
+        writer
+            .finish(&state)
+            .map_err(|e| format!("Unable to write {tmp_path:?}: {e:?}"))?;
+        rename(&tmp_path, &path)?;
+
+        info!(
+            log,
+            "Exported era file";
+            "era" => era,
+            "blocks" => num_blocks,
+            "file" => file_name,
+        );
+    }
+
+    Ok(())
+}
+
+/// Return the roots of the blocks in era `era`, in slot order.
+///
+/// Skipped slots repeat the root of the previous block, so the first root may be that of a block
+/// from an earlier era.
+fn era_block_roots<E: EthSpec>(
+    db: &Db<E>,
+    era: u64,
+    spec: &ChainSpec,
+) -> Result<Vec<Hash256>, String> {
+    let slots = era_block_slots::<E>(era);
+    if slots.is_empty() {
+        return Ok(vec![]);
+    }
+
+    // The range is finalized, so the state used to iterate the hot database is never needed.
+    let iter = db
+        .forwards_block_roots_iterator_until(
+            slots.start,
+            slots.end - 1,
+            || Err(Error::NoContinuationData),
+            spec,
+        )
+        .map_err(|e| format!("Error reading block roots: {e:?}"))?;
+    let mut block_roots: Vec<Hash256> = vec![];
+    for result in iter {
+        let (block_root, _) = result.map_err(|e| format!("Error reading block roots: {e:?}"))?;
+        if block_roots.last() != Some(&block_root) {
+            block_roots.push(block_root);
+        }
+    }
+    Ok(block_roots)
+}
+
+fn rename(from: &Path, to: &Path) -> Result<(), String> {
+    fs::rename(from, to).map_err(|e| format!("Unable to rename {from:?} to {to:?}: {e:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkpoint::{
+        export_checkpoint, import_checkpoint, ExportCheckpointConfig, ImportCheckpointConfig,
+    };
+    use crate::test_utils::{
+        build_chain, client_config, environment, extend_chain, open_db, resume_chain, E,
+    };
+    use tempfile::tempdir;
+    use types::Slot;
+
+    fn export_config(
+        output_dir: &Path,
+        start_era: Option<u64>,
+        end_era: Option<u64>,
+    ) -> ExportEraConfig {
+        ExportEraConfig {
+            output_dir: output_dir.to_path_buf(),
+            start_era,
+            end_era,
+        }
+    }
+
+    fn era_file_path(dir: &Path, era: u64) -> PathBuf {
+        fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| {
+                path.file_name()
+                    .and_then(|file_name| file_name.to_str())
+                    .and_then(store::era::parse_era_file_name)
+                    .map_or(false, |(_, file_era)| file_era == era)
+            })
+            .unwrap_or_else(|| panic!("no file for era {era}"))
+    }
+
+    #[test]
+    fn export_import_round_trip() {
+        let mut env = environment();
+        env.eth2_config.spec.config_name = Some("minimal".into());
+        let log = env.core_context().log().clone();
+        let slots_per_era = E::slots_per_historical_root() as u64;
+
+        // Take a checkpoint of the source chain, then extend the source chain so that it finalizes
+        // the eras preceding the checkpoint.
+        let source_dir = tempdir().unwrap();
+        let source_config = client_config(source_dir.path());
+        let harness = build_chain(&env, &source_config, 160);
+        drop(harness);
+
+        let snapshot_dir = tempdir().unwrap();
+        let snapshot_path = snapshot_dir.path().join("snapshot");
+        export_checkpoint(
+            ExportCheckpointConfig {
+                output_dir: snapshot_path.clone(),
+            },
+            source_config.clone(),
+            &env.core_context(),
+            log.clone(),
+        )
+        .unwrap();
+
+        let harness = resume_chain(&env, &source_config);
+        extend_chain(&harness, 96);
+        let split_slot = harness.chain.store.get_split_info().slot;
+        let last_finalized_era = (split_slot.as_u64() - 1) / slots_per_era;
+        assert_eq!(last_finalized_era, 3);
+        drop(harness);
+
+        // Eras which aren't finalized can't be exported.
+        let era_dir = tempdir().unwrap();
+        let err = export_era(
+            export_config(era_dir.path(), None, Some(last_finalized_era + 1)),
+            source_config.clone(),
+            &env.core_context(),
+            log.clone(),
+        )
+        .unwrap_err();
+        assert!(err.contains("is not finalized"), "{err}");
+
+        export_era(
+            export_config(era_dir.path(), None, None),
+            source_config.clone(),
+            &env.core_context(),
+            log.clone(),
+        )
+        .unwrap();
+        for era in 0..=last_finalized_era {
+            era_file_path(era_dir.path(), era);
+        }
+
+        // Existing files are skipped.
+        let era_1_path = era_file_path(era_dir.path(), 1);
+        let era_1_bytes = fs::read(&era_1_path).unwrap();
+        fs::write(&era_1_path, b"").unwrap();
+        // A missing file is written again, replacing the temporary file of an interrupted export.
+        let era_2_path = era_file_path(era_dir.path(), 2);
+        let era_2_bytes = fs::read(&era_2_path).unwrap();
+        fs::remove_file(&era_2_path).unwrap();
+        let mut tmp_path = era_2_path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        fs::write(&tmp_path, b"interrupted").unwrap();
+
+        export_era(
+            export_config(era_dir.path(), None, None),
+            source_config.clone(),
+            &env.core_context(),
+            log.clone(),
+        )
+        .unwrap();
+        assert!(fs::read(&era_1_path).unwrap().is_empty());
+        assert_eq!(fs::read(&era_2_path).unwrap(), era_2_bytes);
+        assert!(fs::read_dir(era_dir.path()).unwrap().all(|entry| entry
+            .unwrap()
+            .path()
+            .extension()
+            .unwrap()
+            == "era"));
+        fs::write(&era_1_path, &era_1_bytes).unwrap();
+
+        // A later duplicate of an era file is ignored by the import.
+        fs::write(
+            era_dir
+                .path()
+                .join(format!("minimal-{:05}-ffffffff.era", 1)),
+            b"duplicate",
+        )
+        .unwrap();
+
+        // Initialize a new database from the checkpoint.
+        let target_dir = tempdir().unwrap();
+        let target_config = client_config(target_dir.path());
+        env.runtime()
+            .block_on(import_checkpoint(
+                ImportCheckpointConfig {
+                    input_dir: snapshot_path,
+                },
+                target_config.clone(),
+                env.core_context(),
+                log.clone(),
+            ))
+            .unwrap();
+        // Wait for the database to be released by the imported chain.
+        let oldest_block_slot = open_db(&env, &target_config).get_oldest_block_slot();
+        let first_complete_era = oldest_block_slot.as_u64().div_ceil(slots_per_era) + 1;
+
+        // Eras preceding the oldest stored block can't be exported.
+        let err = export_era(
+            export_config(era_dir.path(), Some(first_complete_era - 1), None),
+            target_config.clone(),
+            &env.core_context(),
+            log,
+        )
+        .unwrap_err();
+        assert!(err.contains("is incomplete"), "{err}");
+
+        // The era files reach back from the checkpoint to genesis.
+        let harness = resume_chain(&env, &target_config);
+        assert!(harness.chain.import_era_files(era_dir.path()).unwrap() > 0);
+        assert_eq!(harness.chain.store.get_oldest_block_slot(), 0);
+        let imported = harness
+            .chain
+            .forwards_iter_block_roots_until(Slot::new(0), oldest_block_slot)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+        drop(harness);
+
+        let harness = resume_chain(&env, &source_config);
+        let expected = harness
+            .chain
+            .forwards_iter_block_roots_until(Slot::new(0), oldest_block_slot)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+        assert_eq!(imported, expected);
+    }
+}
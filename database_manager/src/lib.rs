@@ -1,10 +1,20 @@
+mod checkpoint;
+mod era;
+#[cfg(test)]
+mod test_utils;
+
 use beacon_chain::{
     builder::Witness, eth1_chain::CachingEth1Backend, schema_change::migrate_schema,
     slot_clock::SystemTimeSlotClock,
 };
 use beacon_node::{get_data_dir, get_slots_per_restore_point, ClientConfig};
+use checkpoint::{
+    export_checkpoint, import_checkpoint, parse_export_checkpoint_config,
+    parse_import_checkpoint_config,
+};
 use clap::{App, Arg, ArgMatches};
 use environment::{Environment, RuntimeContext};
+use era::{export_era, parse_export_era_config};
 use slog::{info, warn, Logger};
 use std::fs;
 use std::io::Write;
@@ -12,10 +22,12 @@ use std::path::PathBuf;
 use store::metadata::STATE_UPPER_LIMIT_NO_RETAIN;
 use store::{
     errors::Error,
+    get_key_for_col,
     metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION},
-    DBColumn, HotColdDB, KeyValueStore, LevelDB,
+    BeaconNodeBackend, DBColumn, DatabaseBackend, FreezerLayout, HotColdDB, KeyValueStore,
+    KeyValueStoreOp,
 };
-use strum::{EnumString, EnumVariantNames, VariantNames};
+use strum::{EnumString, EnumVariantNames, IntoEnumIterator, VariantNames};
 use types::{BeaconState, EthSpec, Slot};
 
 pub const CMD: &str = "database_manager";
@@ -154,6 +166,168 @@ pub fn prune_states_app<'a, 'b>() -> App<'a, 'b> {
         .about("Prune all beacon states from the freezer database")
 }
 
+pub fn migrate_freezer_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("migrate-freezer")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about("Migrate the historic states in the freezer database to a different layout")
+        .arg(
+            Arg::with_name("to")
+                .long("to")
+                .value_name("LAYOUT")
+                .help("Freezer layout to migrate to")
+                .takes_value(true)
+                .possible_values(FreezerLayout::VARIANTS)
+                .required(true),
+        )
+}
+
+pub fn copy_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("copy")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Copy the hot, freezer and blobs databases into a new directory, optionally \
+             converting them to a different database backend",
+        )
+        .arg(
+            Arg::with_name("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .help(
+                    "Beacon node directory to copy the databases into. The databases are \
+                     written to their default locations within this directory.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("output-backend")
+                .long("output-backend")
+                .value_name("DATABASE")
+                .help("Database backend to use for the copy")
+                .takes_value(true)
+                .possible_values(DatabaseBackend::VARIANTS)
+                .required(true),
+        )
+}
+
+pub fn export_checkpoint_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("export-checkpoint")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Export the finalized state, block and blobs to a directory that can be used to \
+             initialize another database with `import-checkpoint`",
+        )
+        .arg(
+            Arg::with_name("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .help("Directory to write the snapshot to. Must not already exist.")
+                .takes_value(true)
+                .required(true),
+        )
+}
+
+pub fn import_checkpoint_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("import-checkpoint")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Initialize a new database from a snapshot created by `export-checkpoint`, \
+             without connecting to any other node",
+        )
+        .arg(
+            Arg::with_name("input-dir")
+                .long("input-dir")
+                .value_name("DIR")
+                .help("Directory containing the snapshot")
+                .takes_value(true)
+                .required(true),
+        )
+}
+
+pub fn export_era_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("export-era")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Export finalized blocks and states from the freezer database as era files, \
+             which can be imported by another beacon node with `--era-import-dir`",
+        )
+        .arg(
+            Arg::with_name("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .help("Directory to write the era files to. Existing era files are skipped.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("start-era")
+                .long("start-era")
+                .value_name("ERA")
+                .help(
+                    "The first era to export. Defaults to the first era whose blocks are all \
+                     stored in the database.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("end-era")
+                .long("end-era")
+                .value_name("ERA")
+                .help("The last era to export, inclusive. Defaults to the last finalized era.")
+                .takes_value(true),
+        )
+}
+
+pub fn migrate_slasher_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("migrate-slasher")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Copy the slasher database into a new directory using a different database \
+             backend",
+        )
+        .arg(
+            Arg::with_name("slasher-dir")
+                .long("slasher-dir")
+                .value_name("DIR")
+                .help(
+                    "Slasher database directory to copy from. Defaults to the `slasher_db` \
+                     directory within the data directory.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("input-backend")
+                .long("input-backend")
+                .value_name("DATABASE")
+                .help(
+                    "Database backend of the existing slasher database. Defaults to the \
+                     slasher's default backend, or MDBX if an MDBX database is present.",
+                )
+                .takes_value(true)
+                .possible_values(slasher::DatabaseBackend::CLI_VARIANTS),
+        )
+        .arg(
+            Arg::with_name("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .help(
+                    "Directory to write the new slasher database to. It must not contain an \
+                     existing slasher database.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("output-backend")
+                .long("output-backend")
+                .value_name("DATABASE")
+                .help("Database backend to use for the new slasher database")
+                .takes_value(true)
+                .possible_values(slasher::DatabaseBackend::CLI_VARIANTS)
+                .required(true),
+        )
+}
+
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
     App::new(CMD)
         .visible_aliases(&["db"])
@@ -195,6 +369,17 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Data directory for the blobs database.")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("beacon-node-backend")
+                .long("beacon-node-backend")
+                .value_name("DATABASE")
+                .help(
+                    "Set the database backend used by the beacon node. Defaults to the \
+                     backend of the existing database.",
+                )
+                .takes_value(true)
+                .possible_values(DatabaseBackend::VARIANTS),
+        )
         .subcommand(migrate_cli_app())
         .subcommand(version_cli_app())
         .subcommand(inspect_cli_app())
@@ -202,6 +387,12 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         .subcommand(prune_payloads_app())
         .subcommand(prune_blobs_app())
         .subcommand(prune_states_app())
+        .subcommand(migrate_freezer_app())
+        .subcommand(copy_cli_app())
+        .subcommand(export_checkpoint_app())
+        .subcommand(import_checkpoint_app())
+        .subcommand(export_era_app())
+        .subcommand(migrate_slasher_app())
 }
 
 fn parse_client_config<E: EthSpec>(
@@ -230,6 +421,10 @@ fn parse_client_config<E: EthSpec>(
         client_config.store.blob_prune_margin_epochs = blob_prune_margin_epochs;
     }
 
+    if let Some(backend) = clap_utils::parse_optional(cli_args, "beacon-node-backend")? {
+        client_config.store.backend = Some(backend);
+    }
+
     Ok(client_config)
 }
 
@@ -244,7 +439,7 @@ pub fn display_db_version<E: EthSpec>(
     let blobs_path = client_config.get_blobs_db_path();
 
     let mut version = CURRENT_SCHEMA_VERSION;
-    HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -325,12 +520,21 @@ pub fn inspect_db<E: EthSpec>(
     let mut total = 0;
     let mut num_keys = 0;
 
+    let backend = BeaconNodeBackend::<E>::resolve_backend(
+        client_config.store.backend,
+        &[&hot_path, &cold_path, &blobs_path],
+    )
+    .map_err(|e| format!("Unable to determine database backend: {e:?}"))?;
+
     let sub_db = if inspect_config.freezer {
-        LevelDB::<E>::open(&cold_path).map_err(|e| format!("Unable to open freezer DB: {e:?}"))?
+        BeaconNodeBackend::<E>::open(backend, &cold_path)
+            .map_err(|e| format!("Unable to open freezer DB: {e:?}"))?
     } else if inspect_config.blobs_db {
-        LevelDB::<E>::open(&blobs_path).map_err(|e| format!("Unable to open blobs DB: {e:?}"))?
+        BeaconNodeBackend::<E>::open(backend, &blobs_path)
+            .map_err(|e| format!("Unable to open blobs DB: {e:?}"))?
     } else {
-        LevelDB::<E>::open(&hot_path).map_err(|e| format!("Unable to open hot DB: {e:?}"))?
+        BeaconNodeBackend::<E>::open(backend, &hot_path)
+            .map_err(|e| format!("Unable to open hot DB: {e:?}"))?
     };
 
     let skip = inspect_config.skip.unwrap_or(0);
@@ -440,12 +644,26 @@ pub fn compact_db<E: EthSpec>(
     let blobs_path = client_config.get_blobs_db_path();
     let column = compact_config.column;
 
+    let backend = BeaconNodeBackend::<E>::resolve_backend(
+        client_config.store.backend,
+        &[&hot_path, &cold_path, &blobs_path],
+    )?;
+
     let (sub_db, db_name) = if compact_config.freezer {
-        (LevelDB::<E>::open(&cold_path)?, "freezer_db")
+        (
+            BeaconNodeBackend::<E>::open(backend, &cold_path)?,
+            "freezer_db",
+        )
     } else if compact_config.blobs_db {
-        (LevelDB::<E>::open(&blobs_path)?, "blobs_db")
+        (
+            BeaconNodeBackend::<E>::open(backend, &blobs_path)?,
+            "blobs_db",
+        )
     } else {
-        (LevelDB::<E>::open(&hot_path)?, "hot_db")
+        (
+            BeaconNodeBackend::<E>::open(backend, &hot_path)?,
+            "hot_db",
+        )
     };
     info!(
         log,
@@ -480,7 +698,7 @@ pub fn migrate_db<E: EthSpec>(
 
     let mut from = CURRENT_SCHEMA_VERSION;
     let to = migrate_config.to;
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -520,7 +738,7 @@ pub fn prune_payloads<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -546,7 +764,7 @@ pub fn prune_blobs<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -582,7 +800,7 @@ pub fn prune_states<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -644,6 +862,246 @@ pub fn prune_states<E: EthSpec>(
     Ok(())
 }
 
+pub struct MigrateFreezerConfig {
+    to: FreezerLayout,
+}
+
+fn parse_migrate_freezer_config(cli_args: &ArgMatches) -> Result<MigrateFreezerConfig, String> {
+    let to = clap_utils::parse_required(cli_args, "to")?;
+    Ok(MigrateFreezerConfig { to })
+}
+
+pub fn migrate_freezer<E: EthSpec>(
+    migrate_config: MigrateFreezerConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )?;
+
+    info!(
+        log,
+        "Migrating freezer layout";
+        "from" => %db.get_config().freezer_layout,
+        "to" => %migrate_config.to,
+    );
+
+    db.migrate_freezer_layout(migrate_config.to)
+}
+
+pub struct CopyConfig {
+    output_dir: PathBuf,
+    output_backend: DatabaseBackend,
+}
+
+fn parse_copy_config(cli_args: &ArgMatches) -> Result<CopyConfig, String> {
+    let output_dir = clap_utils::parse_required(cli_args, "output-dir")?;
+    let output_backend = clap_utils::parse_required(cli_args, "output-backend")?;
+    Ok(CopyConfig {
+        output_dir,
+        output_backend,
+    })
+}
+
+/// The maximum number of key-value pairs to write to the destination database in one batch.
+const COPY_BATCH_SIZE: usize = 1024;
+
+pub fn copy_db<E: EthSpec>(
+    copy_config: CopyConfig,
+    client_config: ClientConfig,
+    log: Logger,
+) -> Result<(), String> {
+    let mut output_config = client_config.clone();
+    output_config.set_data_dir(copy_config.output_dir);
+    output_config.freezer_db_path = None;
+    output_config.blobs_db_path = None;
+
+    let databases = [
+        (
+            "hot_db",
+            client_config.get_db_path(),
+            output_config.get_db_path(),
+        ),
+        (
+            "freezer_db",
+            client_config.get_freezer_db_path(),
+            output_config.get_freezer_db_path(),
+        ),
+        (
+            "blobs_db",
+            client_config.get_blobs_db_path(),
+            output_config.get_blobs_db_path(),
+        ),
+    ];
+
+    // Refuse to write into an existing database, so that a copy never mixes with other data.
+    for (db_name, source_path, output_path) in &databases {
+        if !source_path.exists() {
+            return Err(format!("Source {db_name} not found at {source_path:?}"));
+        }
+        if output_path.exists() {
+            return Err(format!(
+                "Output directory for {db_name} already exists: {output_path:?}"
+            ));
+        }
+    }
+
+    let source_paths = databases
+        .iter()
+        .map(|(_, source_path, _)| source_path.as_path())
+        .collect::<Vec<_>>();
+    let source_backend =
+        BeaconNodeBackend::<E>::resolve_backend(client_config.store.backend, &source_paths)
+            .map_err(|e| format!("Unable to determine source database backend: {e:?}"))?;
+
+    for (db_name, source_path, output_path) in databases {
+        let source = BeaconNodeBackend::<E>::open(source_backend, &source_path)
+            .map_err(|e| format!("Unable to open source {db_name}: {e:?}"))?;
+        let output = BeaconNodeBackend::<E>::open(copy_config.output_backend, &output_path)
+            .map_err(|e| format!("Unable to open output {db_name}: {e:?}"))?;
+
+        info!(
+            log,
+            "Copying database";
+            "db" => db_name,
+            "from" => %source.backend(),
+            "to" => %output.backend(),
+            "output_path" => ?output_path,
+        );
+
+        let num_keys = copy_kv_store(&source, &output)
+            .map_err(|e| format!("Error copying {db_name}: {e:?}"))?;
+
+        info!(
+            log,
+            "Database copied";
+            "db" => db_name,
+            "num_keys" => num_keys,
+        );
+    }
+
+    Ok(())
+}
+
+/// Copy every key-value pair in every column of `source` to `output`, returning the number of
+/// keys copied.
+fn copy_kv_store<E: EthSpec>(
+    source: &BeaconNodeBackend<E>,
+    output: &BeaconNodeBackend<E>,
+) -> Result<usize, Error> {
+    let mut num_keys = 0;
+    for column in DBColumn::iter() {
+        let mut batch = Vec::with_capacity(COPY_BATCH_SIZE);
+        for res in source.iter_raw_entries(column, &[]) {
+            let (key, value) = res?;
+            batch.push(KeyValueStoreOp::PutKeyValue(
+                get_key_for_col(column.into(), &key),
+                value,
+            ));
+            num_keys += 1;
+
+            if batch.len() >= COPY_BATCH_SIZE {
+                output.do_atomically(std::mem::take(&mut batch))?;
+            }
+        }
+        if !batch.is_empty() {
+            output.do_atomically(batch)?;
+        }
+    }
+    output.sync()?;
+    Ok(num_keys)
+}
+
+pub struct MigrateSlasherConfig {
+    slasher_dir: Option<PathBuf>,
+    input_backend: Option<slasher::DatabaseBackend>,
+    output_dir: PathBuf,
+    output_backend: slasher::DatabaseBackend,
+}
+
+fn parse_migrate_slasher_config(cli_args: &ArgMatches) -> Result<MigrateSlasherConfig, String> {
+    let slasher_dir = clap_utils::parse_optional(cli_args, "slasher-dir")?;
+    let input_backend = clap_utils::parse_optional(cli_args, "input-backend")?;
+    let output_dir = clap_utils::parse_required(cli_args, "output-dir")?;
+    let output_backend = clap_utils::parse_required(cli_args, "output-backend")?;
+    Ok(MigrateSlasherConfig {
+        slasher_dir,
+        input_backend,
+        output_dir,
+        output_backend,
+    })
+}
+
+pub fn migrate_slasher(
+    migrate_config: MigrateSlasherConfig,
+    client_config: ClientConfig,
+    log: Logger,
+) -> Result<(), String> {
+    let slasher_dir = migrate_config
+        .slasher_dir
+        .unwrap_or_else(|| client_config.data_dir().join("slasher_db"));
+
+    let mut input = slasher::Config::new(slasher_dir);
+    if let Some(input_backend) = migrate_config.input_backend {
+        input.backend = input_backend;
+    } else if let slasher::DatabaseBackendOverride::Failure(path) = input.override_backend() {
+        return Err(format!(
+            "Slasher database at {path:?} uses MDBX, which is not enabled in this build. \
+             Use a build with the `slasher-mdbx` feature to migrate it."
+        ));
+    }
+
+    let mut output = slasher::Config::new(migrate_config.output_dir);
+    output.backend = migrate_config.output_backend;
+
+    for backend in [input.backend, output.backend] {
+        if backend.is_in_memory() {
+            return Err(format!(
+                "The {backend} backend is not persisted and can't be migrated"
+            ));
+        }
+    }
+
+    if output.backend == input.backend {
+        return Err(format!(
+            "Slasher database already uses the {} backend",
+            input.backend
+        ));
+    }
+
+    info!(
+        log,
+        "Migrating slasher database";
+        "from" => %input.backend,
+        "to" => %output.backend,
+        "output_path" => ?output.database_path,
+    );
+
+    let num_entries = slasher::copy_database(&input, &output, &log)
+        .map_err(|e| format!("Error migrating slasher database: {e:?}"))?;
+
+    info!(
+        log,
+        "Slasher database migrated";
+        "num_entries" => num_entries,
+    );
+
+    Ok(())
+}
+
 /// Run the database manager, returning an error string if the operation did not succeed.
 pub fn run<T: EthSpec>(cli_args: &ArgMatches<'_>, env: Environment<T>) -> Result<(), String> {
     let client_config = parse_client_config(cli_args, &env)?;
@@ -695,6 +1153,36 @@ pub fn run<T: EthSpec>(cli_args: &ArgMatches<'_>, env: Environment<T>) -> Result
 
             prune_states(client_config, prune_config, genesis_state, &context, log)
         }
+        ("migrate-freezer", Some(cli_args)) => {
+            let migrate_config = parse_migrate_freezer_config(cli_args)?;
+            migrate_freezer(migrate_config, client_config, &context, log).map_err(format_err)
+        }
+        ("copy", Some(cli_args)) => {
+            let copy_config = parse_copy_config(cli_args)?;
+            copy_db::<T>(copy_config, client_config, log)
+        }
+        ("export-checkpoint", Some(cli_args)) => {
+            let export_config = parse_export_checkpoint_config(cli_args)?;
+            export_checkpoint(export_config, client_config, &context, log)
+        }
+        ("import-checkpoint", Some(cli_args)) => {
+            let import_config = parse_import_checkpoint_config(cli_args)?;
+            let executor = context.executor.clone();
+            executor
+                .block_on_dangerous(
+                    import_checkpoint(import_config, client_config, context, log),
+                    "import_checkpoint",
+                )
+                .ok_or("Shutting down")?
+        }
+        ("export-era", Some(cli_args)) => {
+            let export_config = parse_export_era_config(cli_args)?;
+            export_era(export_config, client_config, &context, log)
+        }
+        ("migrate-slasher", Some(cli_args)) => {
+            let migrate_config = parse_migrate_slasher_config(cli_args)?;
+            migrate_slasher(migrate_config, client_config, log)
+        }
         _ => Err("Unknown subcommand, for help `lighthouse database_manager --help`".into()),
     }
 }
@@ -0,0 +1,107 @@
+//! Helpers for testing commands against a database written by a `BeaconChainHarness`.
+use beacon_chain::test_utils::{
+    AttestationStrategy, BeaconChainHarness, BlockStrategy, DiskHarnessType,
+};
+use beacon_node::ClientConfig;
+use environment::{Environment, EnvironmentBuilder};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use store::{BeaconNodeBackend, HotColdDB};
+use types::{ForkName, MinimalEthSpec};
+
+pub type E = MinimalEthSpec;
+pub type Db = HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>;
+pub type Harness = BeaconChainHarness<DiskHarnessType<E>>;
+
+const VALIDATOR_COUNT: usize = 24;
+
+/// The time to wait for a database to be released by a chain which is shutting down.
+const OPEN_DB_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An environment for the minimal preset, with Altair enabled at genesis.
+pub fn environment() -> Environment<E> {
+    let mut env = EnvironmentBuilder::minimal()
+        .null_logger()
+        .expect("should build null logger")
+        .multi_threaded_tokio_runtime()
+        .expect("should build runtime")
+        .build()
+        .expect("should build environment");
+    env.eth2_config.spec = ForkName::Altair.make_genesis_spec(env.eth2_config.spec.clone());
+    env
+}
+
+pub fn client_config(data_dir: &Path) -> ClientConfig {
+    let mut client_config = ClientConfig::default();
+    client_config.set_data_dir(data_dir.to_path_buf());
+    client_config
+}
+
+/// Open the database of `client_config`.
+///
+/// The background threads of a chain that has just been dropped may still hold the database, so
+/// opening it is retried until `OPEN_DB_TIMEOUT`.
+pub fn open_db(env: &Environment<E>, client_config: &ClientConfig) -> Arc<Db> {
+    let deadline = Instant::now() + OPEN_DB_TIMEOUT;
+    loop {
+        match Db::open(
+            &client_config.get_db_path(),
+            &client_config.get_freezer_db_path(),
+            &client_config.get_blobs_db_path(),
+            |_, _, _| Ok(()),
+            client_config.store.clone(),
+            env.eth2_config.spec.clone(),
+            env.core_context().log().clone(),
+        ) {
+            Ok(db) => return db,
+            Err(_) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(100)),
+            Err(e) => panic!("unable to open database: {e:?}"),
+        }
+    }
+}
+
+/// Build a chain of `num_blocks` blocks in the database of `client_config`, attested to by all
+/// validators so that it finalizes.
+///
+/// The harness must be dropped before the database can be opened by the command under test.
+pub fn build_chain(
+    env: &Environment<E>,
+    client_config: &ClientConfig,
+    num_blocks: usize,
+) -> Harness {
+    let harness = Harness::builder(E::default())
+        .spec(env.eth2_config.spec.clone())
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .fresh_disk_store(open_db(env, client_config))
+        .build();
+    extend_chain(&harness, num_blocks);
+    harness
+}
+
+/// Resume the chain in the database of `client_config`, with the slot clock set to the slot after
+/// its head.
+pub fn resume_chain(env: &Environment<E>, client_config: &ClientConfig) -> Harness {
+    let harness = Harness::builder(E::default())
+        .spec(env.eth2_config.spec.clone())
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .resumed_disk_store(open_db(env, client_config))
+        .build();
+    let head_slot = harness.chain.head_snapshot().beacon_block.slot();
+    harness.chain.slot_clock.set_slot(head_slot.as_u64() + 1);
+    harness
+}
+
+/// Extend the chain of `harness` by `num_blocks` blocks, attested to by all validators.
+pub fn extend_chain(harness: &Harness, num_blocks: usize) {
+    harness
+        .runtime
+        .task_executor
+        .handle()
+        .expect("harness runtime should be running")
+        .block_on(harness.extend_chain(
+            num_blocks,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        ));
+}
@@ -15,6 +15,7 @@ mod mock_el;
 mod new_testnet;
 mod parse_ssz;
 mod replace_state_pubkeys;
+mod replay_gossip;
 mod skip_slots;
 mod state_root;
 mod transition_blocks;
@@ -947,6 +948,52 @@ fn main() {
                                 until Cancun is triggered on mainnet.")
                 )
         )
+        .subcommand(
+            SubCommand::with_name("replay-gossip")
+                .about("Replays gossip messages recorded with --gossip-record-dir into a beacon \
+                    chain harness, at the time they were originally received.")
+                .arg(
+                    Arg::with_name("recording")
+                        .long("recording")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to a gossip recording directory or a single recording file."),
+                )
+                .arg(
+                    Arg::with_name("genesis-state")
+                        .long("genesis-state")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to load the genesis BeaconState from as SSZ."),
+                )
+                .arg(
+                    Arg::with_name("checkpoint-state")
+                        .long("checkpoint-state")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .requires("checkpoint-block")
+                        .help("Path to load a finalized BeaconState to start from as SSZ. \
+                            Starts from the genesis state if not supplied."),
+                )
+                .arg(
+                    Arg::with_name("checkpoint-block")
+                        .long("checkpoint-block")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .requires("checkpoint-state")
+                        .help("Path to load the SignedBeaconBlock of the checkpoint state from \
+                            as SSZ."),
+                )
+                .arg(
+                    Arg::with_name("no-delay")
+                        .long("no-delay")
+                        .takes_value(false)
+                        .help("Replay messages as fast as possible, rather than waiting between \
+                            messages for as long as the recording node did."),
+                )
+        )
         .get_matches();
 
     let result = matches
@@ -1090,6 +1137,11 @@ fn run<T: EthSpec>(
         }
         ("mock-el", Some(matches)) => mock_el::run::<T>(env, matches)
             .map_err(|e| format!("Failed to run mock-el command: {}", e)),
+        ("replay-gossip", Some(matches)) => {
+            let network_config = get_network_config()?;
+            replay_gossip::run::<T>(network_config, matches)
+                .map_err(|e| format!("Failed to run replay-gossip command: {}", e))
+        }
         (other, _) => Err(format!("Unknown subcommand {}. See --help.", other)),
     }
 }
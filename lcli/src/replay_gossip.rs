@@ -0,0 +1,455 @@
+//! # Replay-Gossip
+//!
+//! Use this tool to replay gossip messages recorded by a beacon node running with
+//! `--gossip-record-dir`. Useful for reproducing gossip validation, block processing and fork
+//! choice bugs offline.
+//!
+//! The messages are processed in the order they were received by a `BeaconChainHarness`, which
+//! is started from a genesis state or from a checkpoint state and block. The clock of the harness
+//! is set to the time each message was received. By default, the tool also waits between
+//! messages for as long as the recording node did.
+//!
+//! Execution payloads are not executed, the harness assumes that they are all valid.
+//!
+//! ## Examples
+//!
+//! ### Example 1.
+//!
+//! Replay a recording, starting from a finalized checkpoint which precedes the recording:
+//!
+//! ```ignore
+//! lcli replay-gossip \
+//!     --recording /tmp/gossip-recording \
+//!     --genesis-state /tmp/genesis.ssz \
+//!     --checkpoint-state /tmp/checkpoint-state.ssz \
+//!     --checkpoint-block /tmp/checkpoint-block.ssz
+//! ```
+//!
+//! ### Example 2.
+//!
+//! Replay a single recording file from genesis as fast as possible:
+//!
+//! ```ignore
+//! lcli replay-gossip \
+//!     --recording /tmp/gossip-recording/gossip_00000001700000000000.ssz \
+//!     --genesis-state /tmp/genesis.ssz \
+//!     --no-delay
+//! ```
+use crate::transition_blocks::load_from_ssz_with;
+use beacon_chain::{
+    observed_operations::ObservationOutcome,
+    slot_clock::SlotClock,
+    test_utils::{BeaconChainHarness, EphemeralHarnessType},
+    AvailabilityProcessingStatus, NotifyExecutionLayer,
+};
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use eth2_network_config::Eth2NetworkConfig;
+use lighthouse_network::types::gossip_record::read_gossip_records;
+use lighthouse_network::{GossipRecord, PeerId, PubsubMessage, TopicHash};
+use std::path::PathBuf;
+use std::time::Duration;
+use types::{BeaconState, ChainSpec, EthSpec, ForkContext, SignedBeaconBlock};
+
+type Harness<T> = BeaconChainHarness<EphemeralHarnessType<T>>;
+
+/// The number of messages replayed with each outcome.
+#[derive(Debug, Default, PartialEq)]
+struct ReplaySummary {
+    imported: usize,
+    rejected: usize,
+    skipped: usize,
+}
+
+/// The result of replaying a single gossip message.
+enum Outcome {
+    /// The message passed gossip verification and was imported.
+    Imported,
+    /// The message failed gossip verification or could not be imported.
+    Rejected(String),
+    /// Replaying this kind of message is not supported.
+    Skipped,
+}
+
+pub fn run<T: EthSpec>(
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let spec = &network_config.chain_spec::<T>()?;
+
+    let recording_path: PathBuf = parse_required(matches, "recording")?;
+    let genesis_state_path: PathBuf = parse_required(matches, "genesis-state")?;
+    let checkpoint_state_path: Option<PathBuf> = parse_optional(matches, "checkpoint-state")?;
+    let checkpoint_block_path: Option<PathBuf> = parse_optional(matches, "checkpoint-block")?;
+    let no_delay = matches.is_present("no-delay");
+
+    info!("Using {} spec", T::spec_name());
+    info!("Recording path: {:?}", recording_path);
+
+    let records = read_gossip_records(&recording_path)?;
+    info!("Loaded {} gossip records", records.len());
+
+    let genesis_state = load_from_ssz_with(&genesis_state_path, spec, BeaconState::from_ssz_bytes)?;
+
+    let checkpoint = match (checkpoint_state_path, checkpoint_block_path) {
+        (Some(state_path), Some(block_path)) => {
+            info!("Checkpoint state path: {:?}", state_path);
+            info!("Checkpoint block path: {:?}", block_path);
+            let state = load_from_ssz_with(&state_path, spec, BeaconState::from_ssz_bytes)?;
+            let block = load_from_ssz_with(&block_path, spec, SignedBeaconBlock::from_ssz_bytes)?;
+            Some((state, block))
+        }
+        (None, None) => None,
+        _ => {
+            return Err(
+                "--checkpoint-state and --checkpoint-block must be supplied together".into(),
+            )
+        }
+    };
+    let anchor_slot = checkpoint
+        .as_ref()
+        .map_or(spec.genesis_slot, |(state, _)| state.slot());
+
+    let fork_context =
+        ForkContext::new::<T>(anchor_slot, genesis_state.genesis_validators_root(), spec);
+    let harness = build_harness(spec, genesis_state, checkpoint);
+
+    let summary = replay(&harness, &fork_context, records, no_delay)?;
+
+    let head = harness.chain.canonical_head.cached_head();
+    info!(
+        "Replay complete. Imported: {}, rejected: {}, skipped: {}",
+        summary.imported, summary.rejected, summary.skipped
+    );
+    info!(
+        "Head: {:?} (slot {}), justified epoch: {}, finalized epoch: {}",
+        head.head_block_root(),
+        head.head_slot(),
+        head.justified_checkpoint().epoch,
+        head.finalized_checkpoint().epoch
+    );
+
+    Ok(())
+}
+
+/// Builds a harness which starts from the genesis state, or from the checkpoint if one is given.
+fn build_harness<T: EthSpec>(
+    spec: &ChainSpec,
+    genesis_state: BeaconState<T>,
+    checkpoint: Option<(BeaconState<T>, SignedBeaconBlock<T>)>,
+) -> Harness<T> {
+    let genesis_time = genesis_state.genesis_time();
+    let builder = BeaconChainHarness::builder(T::default())
+        .spec(spec.clone())
+        .keypairs(vec![])
+        .genesis_state_ephemeral_store(genesis_state.clone());
+    let builder = if let Some((state, block)) = checkpoint {
+        builder.override_store_mutator(Box::new(move |builder| {
+            builder
+                .weak_subjectivity_state(state, block, None, genesis_state)
+                .expect("should start from checkpoint")
+        }))
+    } else {
+        builder
+    };
+    builder
+        .mock_execution_layer()
+        .recalculate_fork_times_with_genesis(genesis_time)
+        .mock_execution_layer_all_payloads_valid()
+        .build()
+}
+
+/// Replays the records in order, waiting between them for as long as the recording node did
+/// unless `no_delay` is set.
+fn replay<T: EthSpec>(
+    harness: &Harness<T>,
+    fork_context: &ForkContext,
+    records: Vec<GossipRecord>,
+    no_delay: bool,
+) -> Result<ReplaySummary, String> {
+    let handle = harness
+        .runtime
+        .task_executor
+        .handle()
+        .ok_or("shutdown in progress")?;
+
+    let mut summary = ReplaySummary::default();
+    let mut previous_timestamp = None;
+    for record in records {
+        if let Some(previous_timestamp) = previous_timestamp {
+            if !no_delay {
+                std::thread::sleep(Duration::from_millis(
+                    record.timestamp_millis.saturating_sub(previous_timestamp),
+                ));
+            }
+        }
+        previous_timestamp = Some(record.timestamp_millis);
+
+        match handle.block_on(replay_record(harness, fork_context, &record)) {
+            Outcome::Imported => summary.imported += 1,
+            Outcome::Rejected(reason) => {
+                summary.rejected += 1;
+                info!(
+                    "Rejected message from {}: {}",
+                    PeerId::from_bytes(&record.peer_id)
+                        .map_or_else(|_| "unknown peer".to_string(), |peer| peer.to_string()),
+                    reason
+                );
+            }
+            Outcome::Skipped => summary.skipped += 1,
+        }
+    }
+    Ok(summary)
+}
+
+/// Advances the clock of the harness to the time the message was received and processes it the
+/// way the beacon node processes gossip.
+async fn replay_record<T: EthSpec>(
+    harness: &Harness<T>,
+    fork_context: &ForkContext,
+    record: &GossipRecord,
+) -> Outcome {
+    let chain = &harness.chain;
+
+    let previous_slot = chain.slot_clock.now();
+    chain
+        .slot_clock
+        .set_current_time(Duration::from_millis(record.timestamp_millis));
+    if chain.slot_clock.now() != previous_slot {
+        chain.per_slot_task().await;
+    }
+
+    let topic = match String::from_utf8(record.topic.clone()) {
+        Ok(topic) => TopicHash::from_raw(topic),
+        Err(e) => return Outcome::Rejected(format!("invalid topic: {:?}", e)),
+    };
+    let message = match PubsubMessage::<T>::decode(&topic, &record.data, fork_context) {
+        Ok(message) => message,
+        Err(e) => return Outcome::Rejected(format!("unable to decode message: {}", e)),
+    };
+    debug!("Replaying {} message", message.kind());
+
+    match message {
+        PubsubMessage::BeaconBlock(block) => {
+            let verified_block = match chain.verify_block_for_gossip(block).await {
+                Ok(verified_block) => verified_block,
+                Err(e) => return Outcome::Rejected(format!("invalid block: {:?}", e)),
+            };
+            let block_root = verified_block.block_root;
+            match chain
+                .process_block(
+                    block_root,
+                    verified_block,
+                    NotifyExecutionLayer::Yes,
+                    || Ok(()),
+                )
+                .await
+            {
+                Ok(status) => imported_if_available(harness, status).await,
+                Err(e) => Outcome::Rejected(format!("unable to import block: {:?}", e)),
+            }
+        }
+        PubsubMessage::BlobSidecar(blob) => {
+            let (index, blob_sidecar) = *blob;
+            let verified_blob = match chain.verify_blob_sidecar_for_gossip(blob_sidecar, index) {
+                Ok(verified_blob) => verified_blob,
+                Err(e) => return Outcome::Rejected(format!("invalid blob: {:?}", e)),
+            };
+            match chain.process_gossip_blob(verified_blob).await {
+                Ok(status) => imported_if_available(harness, status).await,
+                Err(e) => Outcome::Rejected(format!("unable to import blob: {:?}", e)),
+            }
+        }
+        PubsubMessage::Attestation(attestation) => {
+            let (subnet_id, attestation) = *attestation;
+            let verified_attestation = match chain
+                .verify_unaggregated_attestation_for_gossip(&attestation, Some(subnet_id))
+            {
+                Ok(verified_attestation) => verified_attestation,
+                Err(e) => return Outcome::Rejected(format!("invalid attestation: {:?}", e)),
+            };
+            if let Err(e) = chain.apply_attestation_to_fork_choice(&verified_attestation) {
+                return Outcome::Rejected(format!("invalid for fork choice: {:?}", e));
+            }
+            if let Err(e) = chain.add_to_naive_aggregation_pool(&verified_attestation) {
+                return Outcome::Rejected(format!("invalid for agg pool: {:?}", e));
+            }
+            Outcome::Imported
+        }
+        PubsubMessage::AggregateAndProofAttestation(aggregate) => {
+            let verified_aggregate =
+                match chain.verify_aggregated_attestation_for_gossip(&aggregate) {
+                    Ok(verified_aggregate) => verified_aggregate,
+                    Err(e) => return Outcome::Rejected(format!("invalid aggregate: {:?}", e)),
+                };
+            if let Err(e) = chain.apply_attestation_to_fork_choice(&verified_aggregate) {
+                return Outcome::Rejected(format!("invalid for fork choice: {:?}", e));
+            }
+            if let Err(e) = chain.add_to_block_inclusion_pool(verified_aggregate) {
+                return Outcome::Rejected(format!("invalid for op pool: {:?}", e));
+            }
+            Outcome::Imported
+        }
+        PubsubMessage::VoluntaryExit(exit) => match chain.verify_voluntary_exit_for_gossip(*exit) {
+            Ok(ObservationOutcome::New(exit)) => {
+                chain.import_voluntary_exit(exit);
+                Outcome::Imported
+            }
+            Ok(ObservationOutcome::AlreadyKnown) => {
+                Outcome::Rejected("exit already known".to_string())
+            }
+            Err(e) => Outcome::Rejected(format!("invalid exit: {:?}", e)),
+        },
+        PubsubMessage::ProposerSlashing(slashing) => {
+            match chain.verify_proposer_slashing_for_gossip(*slashing) {
+                Ok(ObservationOutcome::New(slashing)) => {
+                    chain.import_proposer_slashing(slashing);
+                    Outcome::Imported
+                }
+                Ok(ObservationOutcome::AlreadyKnown) => {
+                    Outcome::Rejected("proposer slashing already known".to_string())
+                }
+                Err(e) => Outcome::Rejected(format!("invalid proposer slashing: {:?}", e)),
+            }
+        }
+        PubsubMessage::AttesterSlashing(slashing) => {
+            match chain.verify_attester_slashing_for_gossip(*slashing) {
+                Ok(ObservationOutcome::New(slashing)) => {
+                    chain.import_attester_slashing(slashing);
+                    Outcome::Imported
+                }
+                Ok(ObservationOutcome::AlreadyKnown) => {
+                    Outcome::Rejected("attester slashing already known".to_string())
+                }
+                Err(e) => Outcome::Rejected(format!("invalid attester slashing: {:?}", e)),
+            }
+        }
+        PubsubMessage::SignedContributionAndProof(_)
+        | PubsubMessage::SyncCommitteeMessage(_)
+        | PubsubMessage::BlsToExecutionChange(_)
+        | PubsubMessage::LightClientFinalityUpdate(_)
+        | PubsubMessage::LightClientOptimisticUpdate(_) => Outcome::Skipped,
+    }
+}
+
+/// Updates the head once a block has been imported. A block or blob that is still waiting for
+/// its other components is counted as imported.
+async fn imported_if_available<T: EthSpec>(
+    harness: &Harness<T>,
+    status: AvailabilityProcessingStatus,
+) -> Outcome {
+    if let AvailabilityProcessingStatus::Imported(block_root) = status {
+        debug!("Imported block {:?}", block_root);
+        harness.chain.recompute_head_at_current_slot().await;
+    }
+    Outcome::Imported
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{AttestationStrategy, BlockStrategy};
+    use lighthouse_network::types::GossipEncoding;
+    use std::sync::Arc;
+    use types::{ForkName, MinimalEthSpec, Slot};
+
+    type E = MinimalEthSpec;
+
+    /// Records the blocks of a chain as gossip, along with some invalid messages, and checks that
+    /// replaying the recording rebuilds the chain.
+    #[test]
+    fn replay_recorded_blocks() {
+        let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+        let producer = BeaconChainHarness::builder(E::default())
+            .spec(spec.clone())
+            .deterministic_keypairs(8)
+            .fresh_ephemeral_store()
+            .build();
+        let genesis_state = producer.get_current_state();
+        producer
+            .runtime
+            .task_executor
+            .handle()
+            .unwrap()
+            .block_on(producer.extend_chain(
+                (E::slots_per_epoch() * 4) as usize,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            ));
+
+        let fork_context =
+            ForkContext::new::<E>(Slot::new(0), genesis_state.genesis_validators_root(), &spec);
+        let fork_digest = fork_context.to_context_bytes(ForkName::Altair).unwrap();
+        let peer_id = PeerId::random();
+        let record = |slot: Slot, topic: String, data: Vec<u8>| GossipRecord {
+            timestamp_millis: producer
+                .chain
+                .slot_clock
+                .start_of(slot)
+                .unwrap()
+                .as_millis() as u64,
+            topic: topic.into_bytes(),
+            peer_id: peer_id.to_bytes(),
+            data,
+        };
+
+        let mut block_roots = vec![];
+        for result in producer
+            .chain
+            .forwards_iter_block_roots(Slot::new(1))
+            .unwrap()
+        {
+            let (block_root, _) = result.unwrap();
+            if block_roots.last() != Some(&block_root) {
+                block_roots.push(block_root);
+            }
+        }
+        let mut records = vec![];
+        let mut block_topic = None;
+        for block_root in &block_roots {
+            let block = producer
+                .chain
+                .store
+                .get_full_block(block_root)
+                .unwrap()
+                .unwrap();
+            let slot = block.slot();
+            let message = PubsubMessage::BeaconBlock(Arc::new(block));
+            let topic =
+                String::from(message.topics(GossipEncoding::default(), fork_digest)[0].clone());
+            records.push(record(
+                slot,
+                topic.clone(),
+                message.encode(GossipEncoding::default()),
+            ));
+            block_topic = Some(topic);
+        }
+        let num_blocks = records.len();
+        assert!(num_blocks > 0);
+
+        // A message which can't be decoded, a message on an unknown topic and a duplicate block.
+        let head_slot = producer.chain.head_snapshot().beacon_block.slot();
+        let block_topic = block_topic.unwrap();
+        records.push(record(head_slot, block_topic, vec![0; 32]));
+        records.push(record(
+            head_slot,
+            "/eth2/00000000/unknown/ssz_snappy".to_string(),
+            vec![],
+        ));
+        let duplicate = records[num_blocks - 1].clone();
+        records.push(duplicate);
+
+        let replayer = build_harness(&spec, genesis_state, None);
+        let summary = replay(&replayer, &fork_context, records, true).unwrap();
+
+        assert_eq!(
+            summary,
+            ReplaySummary {
+                imported: num_blocks,
+                rejected: 3,
+                skipped: 0,
+            }
+        );
+        assert_eq!(replayer.head_block_root(), producer.head_block_root());
+    }
+}
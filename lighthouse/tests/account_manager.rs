@@ -499,6 +499,7 @@ fn validator_import_launchpad() {
             voting_keystore_path,
             voting_keystore_password_path: None,
             voting_keystore_password: Some(ZeroizeString::from(PASSWORD.to_string())),
+            distributed: None,
         },
     };
 
@@ -623,6 +624,7 @@ fn validator_import_launchpad_no_password_then_add_password() {
             voting_keystore_path,
             voting_keystore_password_path: None,
             voting_keystore_password: None,
+            distributed: None,
         },
     };
 
@@ -651,6 +653,7 @@ fn validator_import_launchpad_no_password_then_add_password() {
             voting_keystore_path: dst_keystore_dir.join(KEYSTORE_NAME),
             voting_keystore_password_path: None,
             voting_keystore_password: Some(ZeroizeString::from(PASSWORD.to_string())),
+            distributed: None,
         },
     };
 
@@ -754,6 +757,7 @@ fn validator_import_launchpad_password_file() {
             voting_keystore_path,
             voting_keystore_password_path: None,
             voting_keystore_password: Some(ZeroizeString::from(PASSWORD.to_string())),
+            distributed: None,
         },
     };
 
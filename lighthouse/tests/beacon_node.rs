@@ -1,10 +1,14 @@
 use beacon_node::ClientConfig as Config;
+use beacon_node::ClientGenesis;
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use beacon_node::beacon_chain::chain_config::{
-    DisallowedReOrgOffsets, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR,
+    AttestationPackingStrategy, DisallowedReOrgOffsets, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR,
     DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION, DEFAULT_RE_ORG_THRESHOLD,
 };
+use beacon_node::beacon_chain::store::{
+    config::DEFAULT_DIFFS_SLOTS_PER_RESTORE_POINT, DatabaseBackend, FreezerLayout,
+};
 use beacon_processor::BeaconProcessorConfig;
 use eth1::Eth1Endpoint;
 use lighthouse_network::PeerId;
@@ -192,6 +196,37 @@ fn fork_choice_before_proposal_timeout_zero() {
         .with_config(|config| assert_eq!(config.chain.fork_choice_before_proposal_timeout_ms, 0));
 }
 
+#[test]
+fn attestation_packing_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.attestation_packing_strategy,
+                AttestationPackingStrategy::Greedy
+            );
+            assert_eq!(
+                config.chain.attestation_packing_time_budget_ms,
+                beacon_node::beacon_chain::chain_config::DEFAULT_ATTESTATION_PACKING_TIME_BUDGET
+            );
+        });
+}
+
+#[test]
+fn attestation_packing_exact() {
+    CommandLineTest::new()
+        .flag("attestation-packing", Some("exact"))
+        .flag("attestation-packing-time-budget", Some("500"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.attestation_packing_strategy,
+                AttestationPackingStrategy::Exact
+            );
+            assert_eq!(config.chain.attestation_packing_time_budget_ms, 500);
+        });
+}
+
 #[test]
 fn checkpoint_sync_url_timeout_flag() {
     CommandLineTest::new()
@@ -211,6 +246,117 @@ fn checkpoint_sync_url_timeout_default() {
         });
 }
 
+#[test]
+fn checkpoint_sync_url_single() {
+    CommandLineTest::new()
+        .flag("checkpoint-sync-url", Some("http://beacon-1.example.com"))
+        .run_with_zero_port()
+        .with_config(|config| match &config.genesis {
+            ClientGenesis::CheckpointSync {
+                urls,
+                local_checkpoint,
+                quorum,
+            } => {
+                assert_eq!(urls.len(), 1);
+                assert!(local_checkpoint.is_none());
+                assert_eq!(*quorum, 1);
+                assert_eq!(
+                    config.genesis_state_url.as_deref(),
+                    Some("http://beacon-1.example.com/")
+                );
+            }
+            genesis => panic!("unexpected genesis {genesis:?}"),
+        });
+}
+
+#[test]
+fn checkpoint_sync_url_multiple() {
+    CommandLineTest::new()
+        .flag(
+            "checkpoint-sync-url",
+            Some("http://beacon-1.example.com,http://beacon-2.example.com,http://beacon-3.example.com"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| match &config.genesis {
+            ClientGenesis::CheckpointSync { urls, quorum, .. } => {
+                assert_eq!(
+                    urls.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                    vec![
+                        "http://beacon-1.example.com/",
+                        "http://beacon-2.example.com/",
+                        "http://beacon-3.example.com/"
+                    ]
+                );
+                assert_eq!(*quorum, 2);
+            }
+            genesis => panic!("unexpected genesis {genesis:?}"),
+        });
+}
+
+#[test]
+fn checkpoint_sync_quorum_flag() {
+    CommandLineTest::new()
+        .flag(
+            "checkpoint-sync-url",
+            Some("http://beacon-1.example.com,http://beacon-2.example.com,http://beacon-3.example.com"),
+        )
+        .flag("checkpoint-sync-quorum", Some("3"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(matches!(
+                config.genesis,
+                ClientGenesis::CheckpointSync { quorum: 3, .. }
+            ))
+        });
+}
+
+#[test]
+#[should_panic]
+fn checkpoint_sync_quorum_too_high() {
+    CommandLineTest::new()
+        .flag(
+            "checkpoint-sync-url",
+            Some("http://beacon-1.example.com,http://beacon-2.example.com"),
+        )
+        .flag("checkpoint-sync-quorum", Some("3"))
+        .run_with_zero_port();
+}
+
+#[test]
+fn checkpoint_sync_url_with_local_files() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let state_path = dir.path().join("state.ssz");
+    let block_path = dir.path().join("block.ssz");
+    File::create(&state_path)
+        .and_then(|mut file| file.write_all(&[1; 8]))
+        .expect("Unable to write state file");
+    File::create(&block_path)
+        .and_then(|mut file| file.write_all(&[2; 8]))
+        .expect("Unable to write block file");
+    CommandLineTest::new()
+        .flag("checkpoint-state", state_path.to_str())
+        .flag("checkpoint-block", block_path.to_str())
+        .flag(
+            "checkpoint-sync-url",
+            Some("http://beacon-1.example.com,http://beacon-2.example.com"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| match &config.genesis {
+            ClientGenesis::CheckpointSync {
+                urls,
+                local_checkpoint: Some(local_checkpoint),
+                quorum,
+            } => {
+                assert_eq!(urls.len(), 2);
+                assert_eq!(local_checkpoint.anchor_state_bytes, vec![1; 8]);
+                assert_eq!(local_checkpoint.anchor_block_bytes, vec![2; 8]);
+                assert_eq!(local_checkpoint.anchor_blobs_bytes, None);
+                assert_eq!(*quorum, 2);
+            }
+            genesis => panic!("unexpected genesis {genesis:?}"),
+        });
+}
+
 #[test]
 fn prepare_payload_lookahead_default() {
     CommandLineTest::new()
@@ -605,6 +751,28 @@ fn builder_fallback_flags() {
     );
 }
 
+#[test]
+fn builder_disable_ssz_flag() {
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        None,
+        None,
+        |config| {
+            assert!(!config.execution_layer.as_ref().unwrap().builder_disable_ssz);
+        },
+    );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-disable-ssz"),
+        None,
+        |config| {
+            assert!(config.execution_layer.as_ref().unwrap().builder_disable_ssz);
+        },
+    );
+}
+
 #[test]
 fn builder_user_agent() {
     run_payload_builder_flag_test_with_config(
@@ -752,6 +920,19 @@ fn network_target_peers_flag() {
         });
 }
 #[test]
+fn network_peer_bandwidth_cap_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.peer_bandwidth_cap, None));
+}
+#[test]
+fn network_peer_bandwidth_cap_flag() {
+    CommandLineTest::new()
+        .flag("peer-bandwidth-cap", Some("512"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.peer_bandwidth_cap, Some(512_000)));
+}
+#[test]
 fn network_subscribe_all_subnets_flag() {
     CommandLineTest::new()
         .flag("subscribe-all-subnets", None)
@@ -1833,6 +2014,48 @@ fn historic_state_cache_size_default() {
         });
 }
 #[test]
+fn beacon_node_backend_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.backend, None));
+}
+#[test]
+fn beacon_node_backend_redb() {
+    CommandLineTest::new()
+        .flag("beacon-node-backend", Some("redb"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.backend, Some(DatabaseBackend::Redb)));
+}
+#[test]
+fn freezer_layout_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.freezer_layout, FreezerLayout::RestorePoints)
+        });
+}
+#[test]
+fn freezer_layout_diffs() {
+    CommandLineTest::new()
+        .flag("freezer-layout", Some("diffs"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.freezer_layout, FreezerLayout::Diffs);
+            assert_eq!(
+                config.store.slots_per_restore_point,
+                DEFAULT_DIFFS_SLOTS_PER_RESTORE_POINT
+            );
+        });
+}
+#[test]
+fn freezer_layout_diffs_slots_per_restore_point_flag() {
+    CommandLineTest::new()
+        .flag("freezer-layout", Some("diffs"))
+        .flag("slots-per-restore-point", Some("65536"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.slots_per_restore_point, 65536));
+}
+#[test]
 fn auto_compact_db_flag() {
     CommandLineTest::new()
         .flag("auto-compact-db", Some("false"))
@@ -1874,6 +2097,24 @@ fn prune_blobs_on_startup_false() {
         .with_config(|config| assert!(!config.store.prune_blobs));
 }
 #[test]
+fn archive_blobs_flag() {
+    CommandLineTest::new()
+        .flag("archive-blobs", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.chain.archive_blobs);
+            assert!(!config.store.prune_blobs);
+        });
+}
+#[test]
+#[should_panic]
+fn archive_blobs_with_prune_blobs_true() {
+    CommandLineTest::new()
+        .flag("archive-blobs", None)
+        .flag("prune-blobs", Some("true"))
+        .run_with_zero_port();
+}
+#[test]
 fn epochs_per_blob_prune_default() {
     CommandLineTest::new()
         .run_with_zero_port()
@@ -1913,6 +2154,20 @@ fn no_reconstruct_historic_states_flag() {
         .with_config(|config| assert!(!config.chain.reconstruct_historic_states));
 }
 #[test]
+fn era_import_dir_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("era-import-dir", dir.path().as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.era_import_dir, Some(dir.path().to_path_buf())));
+}
+#[test]
+fn era_import_dir_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.era_import_dir, None));
+}
+#[test]
 fn epochs_per_migration_default() {
     CommandLineTest::new()
         .run_with_zero_port()
@@ -2152,6 +2407,14 @@ fn slasher_backend_override_to_default() {
             assert_eq!(slasher_config.backend, slasher::DatabaseBackend::Lmdb);
         });
 }
+#[test]
+#[should_panic]
+fn slasher_backend_memory_rejected() {
+    CommandLineTest::new()
+        .flag("slasher", None)
+        .flag("slasher-backend", Some("memory"))
+        .run_with_zero_port();
+}
 
 #[test]
 fn malloc_tuning_flag() {
@@ -2483,6 +2746,27 @@ fn invalid_gossip_verified_blocks_path() {
         });
 }
 
+#[test]
+fn gossip_record_dir_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.gossip_record_dir, None));
+}
+
+#[test]
+fn gossip_record_dir() {
+    let path = "/tmp/gossip-recording";
+    CommandLineTest::new()
+        .flag("gossip-record-dir", Some(path))
+        .flag("gossip-record-max-file-size", Some("16"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.gossip_record_dir, Some(PathBuf::from(path)));
+            assert_eq!(config.network.gossip_record_max_file_size, 16 * 1024 * 1024);
+            assert_eq!(config.network.gossip_record_max_files, 8);
+        });
+}
+
 #[test]
 fn progressive_balances_default() {
     CommandLineTest::new()
@@ -2521,6 +2805,7 @@ fn beacon_processor() {
         .flag("beacon-processor-attestation-batch-size", Some("4"))
         .flag("beacon-processor-aggregate-batch-size", Some("5"))
         .flag("disable-backfill-rate-limiting", None)
+        .flag("beacon-processor-adaptive", None)
         .run_with_zero_port()
         .with_config(|config| {
             assert_eq!(
@@ -2531,7 +2816,8 @@ fn beacon_processor() {
                     max_scheduled_work_queue_len: 3,
                     max_gossip_attestation_batch_size: 4,
                     max_gossip_aggregate_batch_size: 5,
-                    enable_backfill_rate_limiting: false
+                    enable_backfill_rate_limiting: false,
+                    enable_adaptive_scheduling: true,
                 }
             )
         });
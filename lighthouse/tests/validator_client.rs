@@ -152,6 +152,45 @@ fn beacon_nodes_tls_certs_flag() {
         });
 }
 
+#[test]
+fn slashing_protection_replicas_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "slashing-protection-replicas",
+            Some("http://localhost:5062,http://replica.example.com:5062"),
+        )
+        .flag(
+            "slashing-protection-replica-peer-tokens",
+            Some(
+                vec![
+                    dir.path().join("peer-api-token-1.txt").to_str().unwrap(),
+                    dir.path().join("peer-api-token-2.txt").to_str().unwrap(),
+                ]
+                .join(",")
+                .as_str(),
+            ),
+        )
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.slashing_protection_replicas[0].to_string(),
+                "http://localhost:5062/"
+            );
+            assert_eq!(
+                config.slashing_protection_replicas[1].to_string(),
+                "http://replica.example.com:5062/"
+            );
+            assert_eq!(
+                config.slashing_protection_replica_peer_tokens,
+                vec![
+                    dir.path().join("peer-api-token-1.txt"),
+                    dir.path().join("peer-api-token-2.txt")
+                ]
+            );
+        });
+}
+
 // Tests for Graffiti flags.
 #[test]
 fn graffiti_flag() {
@@ -29,6 +29,10 @@ vm_cli=$($CMD vm --help)
 vm_cli_create=$($CMD vm create --help)
 vm_cli_import=$($CMD vm import --help)
 vm_cli_move=$($CMD vm move --help)
+vm_cli_list=$($CMD vm list --help)
+vm_cli_delete=$($CMD vm delete --help)
+vm_cli_exit=$($CMD vm exit --help)
+vm_cli_export_keystores=$($CMD vm export-keystores --help)
 
 general=./help_general.md
 bn=./help_bn.md
@@ -38,6 +42,10 @@ vm=./help_vm.md
 vm_create=./help_vm_create.md
 vm_import=./help_vm_import.md
 vm_move=./help_vm_move.md
+vm_list=./help_vm_list.md
+vm_delete=./help_vm_delete.md
+vm_exit=./help_vm_exit.md
+vm_export_keystores=./help_vm_export_keystores.md
 
 # create .md files
 write_to_file "$general_cli" "$general" "Lighthouse General Commands"
@@ -47,10 +55,14 @@ write_to_file "$vm_cli" "$vm" "Validator Manager"
 write_to_file "$vm_cli_create" "$vm_create" "Validator Manager Create"
 write_to_file "$vm_cli_import" "$vm_import" "Validator Manager Import"
 write_to_file "$vm_cli_move" "$vm_move" "Validator Manager Move"
+write_to_file "$vm_cli_list" "$vm_list" "Validator Manager List"
+write_to_file "$vm_cli_delete" "$vm_delete" "Validator Manager Delete"
+write_to_file "$vm_cli_exit" "$vm_exit" "Validator Manager Exit"
+write_to_file "$vm_cli_export_keystores" "$vm_export_keystores" "Validator Manager Export Keystores"
 
 #input 1 = $1 = files; input 2 = $2 = new files
-files=(./book/src/help_general.md ./book/src/help_bn.md ./book/src/help_vc.md ./book/src/help_vm.md ./book/src/help_vm_create.md ./book/src/help_vm_import.md ./book/src/help_vm_move.md)
-new_files=($general $bn $vc $vm $vm_create $vm_import $vm_move)
+files=(./book/src/help_general.md ./book/src/help_bn.md ./book/src/help_vc.md ./book/src/help_vm.md ./book/src/help_vm_create.md ./book/src/help_vm_import.md ./book/src/help_vm_move.md ./book/src/help_vm_list.md ./book/src/help_vm_delete.md ./book/src/help_vm_exit.md ./book/src/help_vm_export_keystores.md)
+new_files=($general $bn $vc $vm $vm_create $vm_import $vm_move $vm_list $vm_delete $vm_exit $vm_export_keystores)
 
 # function to check
 check() {
@@ -84,9 +96,13 @@ check ${files[3]} ${new_files[3]}
 check ${files[4]} ${new_files[4]}
 check ${files[5]} ${new_files[5]}
 check ${files[6]} ${new_files[6]}
+check ${files[7]} ${new_files[7]}
+check ${files[8]} ${new_files[8]}
+check ${files[9]} ${new_files[9]}
+check ${files[10]} ${new_files[10]}
 
 # remove help files
-rm -f help_general.md help_bn.md help_vc.md help_am.md help_vm.md help_vm_create.md help_vm_import.md help_vm_move.md
+rm -f help_general.md help_bn.md help_vc.md help_am.md help_vm.md help_vm_create.md help_vm_import.md help_vm_move.md help_vm_list.md help_vm_delete.md help_vm_exit.md help_vm_export_keystores.md
 
 # only exit at the very end
 if [[ $changes == true ]]; then
@@ -1,7 +1,7 @@
 use crate::metrics::{self, SLASHER_COMPRESSION_RATIO, SLASHER_NUM_CHUNKS_UPDATED};
 use crate::{
-    AttesterSlashingStatus, Config, Database, Error, IndexedAttesterRecord, RwTransaction,
-    SlasherDB,
+    AttesterSlashingStatus, Config, Database, Error, IndexedAttesterRecord, ReadTransaction,
+    RwTransaction, SlasherDB,
 };
 use flate2::bufread::{ZlibDecoder, ZlibEncoder};
 use serde::{Deserialize, Serialize};
@@ -125,7 +125,7 @@ pub trait TargetArrayChunk: Sized + serde::Serialize + serde::de::DeserializeOwn
     fn check_slashable<E: EthSpec>(
         &self,
         db: &SlasherDB<E>,
-        txn: &mut RwTransaction<'_>,
+        txn: &mut impl ReadTransaction,
         validator_index: u64,
         attestation: &IndexedAttestation<E>,
         config: &Config,
@@ -153,7 +153,7 @@ pub trait TargetArrayChunk: Sized + serde::Serialize + serde::de::DeserializeOwn
 
     fn load<E: EthSpec>(
         db: &SlasherDB<E>,
-        txn: &mut RwTransaction<'_>,
+        txn: &mut impl ReadTransaction,
         validator_chunk_index: usize,
         chunk_index: usize,
         config: &Config,
@@ -221,7 +221,7 @@ impl TargetArrayChunk for MinTargetChunk {
     fn check_slashable<E: EthSpec>(
         &self,
         db: &SlasherDB<E>,
-        txn: &mut RwTransaction<'_>,
+        txn: &mut impl ReadTransaction,
         validator_index: u64,
         attestation: &IndexedAttestation<E>,
         config: &Config,
@@ -324,7 +324,7 @@ impl TargetArrayChunk for MaxTargetChunk {
     fn check_slashable<E: EthSpec>(
         &self,
         db: &SlasherDB<E>,
-        txn: &mut RwTransaction<'_>,
+        txn: &mut impl ReadTransaction,
         validator_index: u64,
         attestation: &IndexedAttestation<E>,
         config: &Config,
@@ -398,7 +398,7 @@ impl TargetArrayChunk for MaxTargetChunk {
 
 pub fn get_chunk_for_update<'a, E: EthSpec, T: TargetArrayChunk>(
     db: &SlasherDB<E>,
-    txn: &mut RwTransaction<'_>,
+    txn: &mut impl ReadTransaction,
     updated_chunks: &'a mut BTreeMap<usize, T>,
     validator_chunk_index: usize,
     chunk_index: usize,
@@ -526,7 +526,7 @@ pub fn update<E: EthSpec>(
 
 pub fn epoch_update_for_validator<E: EthSpec, T: TargetArrayChunk>(
     db: &SlasherDB<E>,
-    txn: &mut RwTransaction<'_>,
+    txn: &mut impl ReadTransaction,
     updated_chunks: &mut BTreeMap<usize, T>,
     validator_chunk_index: usize,
     validator_index: u64,
@@ -625,3 +625,107 @@ pub fn update_array<E: EthSpec, T: TargetArrayChunk>(
 
     Ok(slashings)
 }
+
+/// The min and max targets of a single validator at some epoch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinMaxTargets {
+    pub epoch: Epoch,
+    /// The minimum target of the validator's attestations with a source greater than `epoch`.
+    pub min_target: Option<Epoch>,
+    /// The maximum target of the validator's attestations with a source less than `epoch`.
+    pub max_target: Option<Epoch>,
+}
+
+/// Load the chunks of `validator_index` which are affected by advancing to `current_epoch`.
+///
+/// Further chunks may be loaded into the returned map with `get_chunk_for_update`. Nothing is
+/// written to the database.
+fn get_chunks_for_read<E: EthSpec, T: TargetArrayChunk>(
+    db: &SlasherDB<E>,
+    txn: &mut impl ReadTransaction,
+    validator_index: u64,
+    current_epoch: Epoch,
+    config: &Config,
+) -> Result<BTreeMap<usize, T>, Error> {
+    let mut chunks = BTreeMap::new();
+    epoch_update_for_validator(
+        db,
+        txn,
+        &mut chunks,
+        config.validator_chunk_index(validator_index),
+        validator_index,
+        current_epoch,
+        config,
+    )?;
+    Ok(chunks)
+}
+
+/// Return the min and max targets of `validator_index` for every epoch in the history at
+/// `current_epoch`, without modifying the database.
+pub fn get_targets_for_validator<E: EthSpec>(
+    db: &SlasherDB<E>,
+    txn: &mut impl ReadTransaction,
+    validator_index: u64,
+    current_epoch: Epoch,
+    config: &Config,
+) -> Result<Vec<MinMaxTargets>, Error> {
+    fn get_target<E: EthSpec, T: TargetArrayChunk>(
+        db: &SlasherDB<E>,
+        txn: &mut impl ReadTransaction,
+        chunks: &mut BTreeMap<usize, T>,
+        validator_index: u64,
+        epoch: Epoch,
+        config: &Config,
+    ) -> Result<Option<Epoch>, Error> {
+        let target = get_chunk_for_update(
+            db,
+            txn,
+            chunks,
+            config.validator_chunk_index(validator_index),
+            config.chunk_index(epoch),
+            config,
+        )?
+        .chunk()
+        .get_target(validator_index, epoch, config)?;
+        Ok(Some(target).filter(|target| *target != epoch + u64::from(T::neutral_element())))
+    }
+
+    let mut min_chunks =
+        get_chunks_for_read::<E, MinTargetChunk>(db, txn, validator_index, current_epoch, config)?;
+    let mut max_chunks =
+        get_chunks_for_read::<E, MaxTargetChunk>(db, txn, validator_index, current_epoch, config)?;
+    let start_epoch = (current_epoch + 1).saturating_sub(config.history_length as u64);
+
+    (start_epoch.as_u64()..=current_epoch.as_u64())
+        .map(Epoch::new)
+        .map(|epoch| {
+            Ok(MinMaxTargets {
+                epoch,
+                min_target: get_target(db, txn, &mut min_chunks, validator_index, epoch, config)?,
+                max_target: get_target(db, txn, &mut max_chunks, validator_index, epoch, config)?,
+            })
+        })
+        .collect()
+}
+
+/// Check whether `attestation` by `validator_index` surrounds or is surrounded by one of the
+/// validator's existing attestations, without modifying the database.
+pub fn check_surround_for_validator<E: EthSpec, T: TargetArrayChunk>(
+    db: &SlasherDB<E>,
+    txn: &mut impl ReadTransaction,
+    validator_index: u64,
+    attestation: &IndexedAttestation<E>,
+    current_epoch: Epoch,
+    config: &Config,
+) -> Result<AttesterSlashingStatus<E>, Error> {
+    let mut chunks = get_chunks_for_read::<E, T>(db, txn, validator_index, current_epoch, config)?;
+    get_chunk_for_update(
+        db,
+        txn,
+        &mut chunks,
+        config.validator_chunk_index(validator_index),
+        config.chunk_index(attestation.data.source.epoch),
+        config,
+    )?
+    .check_slashable(db, txn, validator_index, attestation, config)
+}
@@ -19,12 +19,15 @@ pub const DEFAULT_BROADCAST: bool = false;
 pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Mdbx;
 #[cfg(feature = "lmdb")]
 pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Lmdb;
-#[cfg(not(any(feature = "mdbx", feature = "lmdb")))]
+#[cfg(all(feature = "redb", not(any(feature = "mdbx", feature = "lmdb"))))]
+pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Redb;
+#[cfg(not(any(feature = "mdbx", feature = "lmdb", feature = "redb")))]
 pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Disabled;
 
 pub const MAX_HISTORY_LENGTH: usize = 1 << 16;
 pub const MEGABYTE: usize = 1 << 20;
 pub const MDBX_DATA_FILENAME: &str = "mdbx.dat";
+pub const REDB_DATA_FILENAME: &str = "slasher.redb";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -64,9 +67,39 @@ pub enum DatabaseBackend {
     Mdbx,
     #[cfg(feature = "lmdb")]
     Lmdb,
+    #[cfg(feature = "redb")]
+    Redb,
+    /// Redb with an in-memory store, which is lost on shutdown. Intended for testing.
+    #[cfg(feature = "redb")]
+    Memory,
     Disabled,
 }
 
+impl DatabaseBackend {
+    /// The names of the backends which may be selected on the command line.
+    ///
+    /// `Memory` is excluded, since a slasher database which is lost on shutdown is only useful in
+    /// tests.
+    pub const CLI_VARIANTS: &'static [&'static str] = &[
+        #[cfg(feature = "mdbx")]
+        "mdbx",
+        #[cfg(feature = "lmdb")]
+        "lmdb",
+        #[cfg(feature = "redb")]
+        "redb",
+        "disabled",
+    ];
+
+    /// Return `true` if the database is lost on shutdown.
+    pub fn is_in_memory(&self) -> bool {
+        #[cfg(feature = "redb")]
+        if *self == DatabaseBackend::Memory {
+            return true;
+        }
+        false
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum DatabaseBackendOverride {
     Success(DatabaseBackend),
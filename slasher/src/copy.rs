@@ -0,0 +1,69 @@
+use crate::database::interface::Environment;
+use crate::{Config, Error};
+use slog::{info, Logger};
+
+/// The maximum number of entries to write to the output database in one transaction.
+const COPY_BATCH_SIZE: usize = 16_384;
+
+/// Copy the slasher database at `input` into a new database at `output`.
+///
+/// The databases may use different backends, e.g. to migrate an LMDB or MDBX database to redb.
+/// The output database must not contain any entries. Return the number of entries copied.
+pub fn copy_database(input: &Config, output: &Config, log: &Logger) -> Result<usize, Error> {
+    if !input.database_path.exists() {
+        return Err(Error::CopySourceMissing(input.database_path.clone()));
+    }
+    std::fs::create_dir_all(&output.database_path)?;
+
+    let input_env = Environment::new(input)?;
+    let input_databases = input_env.create_databases()?;
+    let output_env = Environment::new(output)?;
+    let output_databases = output_env.create_databases()?;
+
+    let mut output_txn = output_env.begin_rw_txn()?;
+    for (_, output_db) in output_databases.all() {
+        if output_txn.cursor(output_db)?.first_key()?.is_some() {
+            return Err(Error::CopyDestinationNotEmpty(output.database_path.clone()));
+        }
+    }
+    drop(output_txn);
+
+    let mut total_entries = 0;
+    for ((name, input_db), (_, output_db)) in input_databases
+        .all()
+        .into_iter()
+        .zip(output_databases.all())
+    {
+        // The input transaction is only read from, and is aborted when dropped.
+        let mut input_txn = input_env.begin_rw_txn()?;
+        let mut cursor = input_txn.cursor(input_db)?;
+        let mut output_txn = output_env.begin_rw_txn()?;
+        let mut num_entries = 0;
+
+        if cursor.first_key()?.is_some() {
+            while let Some((key, value)) = cursor.get_current()? {
+                output_txn.put(output_db, key, value)?;
+                num_entries += 1;
+
+                if num_entries % COPY_BATCH_SIZE == 0 {
+                    output_txn.commit()?;
+                    output_txn = output_env.begin_rw_txn()?;
+                }
+                if cursor.next_key()?.is_none() {
+                    break;
+                }
+            }
+        }
+        output_txn.commit()?;
+
+        info!(
+            log,
+            "Copied slasher database table";
+            "table" => name,
+            "entries" => num_entries,
+        );
+        total_entries += num_entries;
+    }
+
+    Ok(total_entries)
+}
@@ -1,13 +1,16 @@
 pub mod interface;
 mod lmdb_impl;
 mod mdbx_impl;
+mod redb_impl;
 
 use crate::{
-    metrics, AttesterRecord, AttesterSlashingStatus, CompactAttesterRecord, Config, Error,
-    ProposerSlashingStatus,
+    metrics, AttesterRecord, AttesterSlashingStatus, CompactAttesterRecord, Config,
+    DetectedSlashing, Error, ProposerSlashingStatus,
 };
 use byteorder::{BigEndian, ByteOrder};
-use interface::{Environment, OpenDatabases, RwTransaction};
+use interface::{
+    Database, Environment, OpenDatabases, ReadTransaction, RoTransaction, RwTransaction,
+};
 use lru::LruCache;
 use parking_lot::Mutex;
 use serde::de::DeserializeOwned;
@@ -16,9 +19,11 @@ use ssz::{Decode, Encode};
 use std::borrow::{Borrow, Cow};
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::Duration;
 use tree_hash::TreeHash;
 use types::{
-    Epoch, EthSpec, Hash256, IndexedAttestation, ProposerSlashing, SignedBeaconBlockHeader, Slot,
+    AttesterSlashing, Epoch, EthSpec, Hash256, IndexedAttestation, ProposerSlashing,
+    SignedBeaconBlockHeader, Slot,
 };
 
 /// Current database schema version, to check compatibility of on-disk DB with software.
@@ -47,9 +52,15 @@ const MAX_TARGETS_DB: &str = "max_targets";
 const CURRENT_EPOCHS_DB: &str = "current_epochs";
 /// Map from `(slot, validator_index)` to `SignedBeaconBlockHeader`.
 const PROPOSERS_DB: &str = "proposers";
+/// Map from `(epoch, slashing_root)` to the detection time and `AttesterSlashing` of each
+/// attester slashing detected by the slasher.
+const DETECTED_ATTESTER_SLASHINGS_DB: &str = "detected_attester_slashings";
+/// Map from `(epoch, slashing_root)` to the detection time and `ProposerSlashing` of each
+/// proposer slashing detected by the slasher.
+const DETECTED_PROPOSER_SLASHINGS_DB: &str = "detected_proposer_slashings";
 
-/// The number of DBs for MDBX to use (equal to the number of DBs defined above).
-const MAX_NUM_DBS: usize = 9;
+/// The number of DBs for MDBX and LMDB to use (equal to the number of DBs defined above).
+const MAX_NUM_DBS: usize = 11;
 
 /// Constant key under which the schema version is stored in the `metadata_db`.
 const METADATA_VERSION_KEY: &[u8] = &[0];
@@ -61,6 +72,9 @@ const PROPOSER_KEY_SIZE: usize = 16;
 const CURRENT_EPOCH_KEY_SIZE: usize = 8;
 const INDEXED_ATTESTATION_ID_SIZE: usize = 6;
 const INDEXED_ATTESTATION_ID_KEY_SIZE: usize = 40;
+const DETECTED_SLASHING_KEY_SIZE: usize = 40;
+/// The detection time is stored as 8 bytes of nanoseconds before the slashing.
+const DETECTED_SLASHING_TIMESTAMP_SIZE: usize = 8;
 
 #[derive(Debug)]
 pub struct SlasherDB<E: EthSpec> {
@@ -72,6 +86,63 @@ pub struct SlasherDB<E: EthSpec> {
     _phantom: PhantomData<E>,
 }
 
+impl<'env> OpenDatabases<'env> {
+    /// Return every database along with its name.
+    pub fn all(&self) -> [(&'static str, &Database<'env>); MAX_NUM_DBS] {
+        [
+            (INDEXED_ATTESTATION_DB, &self.indexed_attestation_db),
+            (INDEXED_ATTESTATION_ID_DB, &self.indexed_attestation_id_db),
+            (ATTESTERS_DB, &self.attesters_db),
+            (ATTESTERS_MAX_TARGETS_DB, &self.attesters_max_targets_db),
+            (MIN_TARGETS_DB, &self.min_targets_db),
+            (MAX_TARGETS_DB, &self.max_targets_db),
+            (CURRENT_EPOCHS_DB, &self.current_epochs_db),
+            (PROPOSERS_DB, &self.proposers_db),
+            (
+                DETECTED_ATTESTER_SLASHINGS_DB,
+                &self.detected_attester_slashings_db,
+            ),
+            (
+                DETECTED_PROPOSER_SLASHINGS_DB,
+                &self.detected_proposer_slashings_db,
+            ),
+            (METADATA_DB, &self.metadata_db),
+        ]
+    }
+}
+
+/// A type of slashing which is recorded in the database once detected.
+pub trait DetectedSlashingKind: Encode + Decode + TreeHash {
+    /// The database in which slashings of this type are recorded.
+    fn detected_db<'a, 'env>(databases: &'a OpenDatabases<'env>) -> &'a Database<'env>;
+
+    /// The epoch of the slashing, which determines when it is pruned from the database.
+    fn epoch(&self, slots_per_epoch: u64) -> Epoch;
+}
+
+impl<E: EthSpec> DetectedSlashingKind for AttesterSlashing<E> {
+    fn detected_db<'a, 'env>(databases: &'a OpenDatabases<'env>) -> &'a Database<'env> {
+        &databases.detected_attester_slashings_db
+    }
+
+    fn epoch(&self, _: u64) -> Epoch {
+        std::cmp::max(
+            self.attestation_1.data.target.epoch,
+            self.attestation_2.data.target.epoch,
+        )
+    }
+}
+
+impl DetectedSlashingKind for ProposerSlashing {
+    fn detected_db<'a, 'env>(databases: &'a OpenDatabases<'env>) -> &'a Database<'env> {
+        &databases.detected_proposer_slashings_db
+    }
+
+    fn epoch(&self, slots_per_epoch: u64) -> Epoch {
+        self.signed_header_1.message.slot.epoch(slots_per_epoch)
+    }
+}
+
 /// Database key for the `attesters` database.
 ///
 /// Stored as big-endian `(target_epoch, validator_index)` to enable efficient iteration
@@ -192,6 +263,39 @@ impl AsRef<[u8]> for IndexedAttestationIdKey {
     }
 }
 
+/// Key for the detected slashings databases.
+///
+/// Stored as big-endian `(epoch, slashing_root)` so that slashings can be pruned by epoch.
+#[derive(Debug)]
+pub struct DetectedSlashingKey {
+    epoch_and_root: [u8; DETECTED_SLASHING_KEY_SIZE],
+}
+
+impl DetectedSlashingKey {
+    pub fn new(epoch: Epoch, slashing_root: Hash256) -> Self {
+        let mut data = [0; DETECTED_SLASHING_KEY_SIZE];
+        data[0..8].copy_from_slice(&epoch.as_u64().to_be_bytes());
+        data[8..DETECTED_SLASHING_KEY_SIZE].copy_from_slice(slashing_root.as_bytes());
+        Self {
+            epoch_and_root: data,
+        }
+    }
+
+    pub fn parse(data: Cow<[u8]>) -> Result<Epoch, Error> {
+        if data.len() == DETECTED_SLASHING_KEY_SIZE {
+            Ok(Epoch::new(BigEndian::read_u64(&data[..8])))
+        } else {
+            Err(Error::DetectedSlashingKeyCorrupt { length: data.len() })
+        }
+    }
+}
+
+impl AsRef<[u8]> for DetectedSlashingKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.epoch_and_root
+    }
+}
+
 /// Key containing a 6-byte indexed attestation ID.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct IndexedAttestationId {
@@ -292,6 +396,10 @@ impl<E: EthSpec> SlasherDB<E> {
         self.env.begin_rw_txn()
     }
 
+    pub fn begin_ro_txn(&self) -> Result<RoTransaction, Error> {
+        self.env.begin_ro_txn()
+    }
+
     pub fn load_schema_version(&self, txn: &mut RwTransaction<'_>) -> Result<Option<u64>, Error> {
         txn.get(&self.databases.metadata_db, METADATA_VERSION_KEY)?
             .map(bincode_deserialize)
@@ -332,7 +440,7 @@ impl<E: EthSpec> SlasherDB<E> {
     pub fn get_attester_max_target(
         &self,
         validator_index: u64,
-        txn: &mut RwTransaction<'_>,
+        txn: &mut impl ReadTransaction,
     ) -> Result<Option<Epoch>, Error> {
         txn.get(
             &self.databases.attesters_max_targets_db,
@@ -383,7 +491,7 @@ impl<E: EthSpec> SlasherDB<E> {
     pub fn get_current_epoch_for_validator(
         &self,
         validator_index: u64,
-        txn: &mut RwTransaction<'_>,
+        txn: &mut impl ReadTransaction,
     ) -> Result<Option<Epoch>, Error> {
         txn.get(
             &self.databases.current_epochs_db,
@@ -470,7 +578,7 @@ impl<E: EthSpec> SlasherDB<E> {
 
     pub fn get_indexed_attestation(
         &self,
-        txn: &mut RwTransaction<'_>,
+        txn: &mut impl ReadTransaction,
         indexed_attestation_id: IndexedAttestationId,
     ) -> Result<IndexedAttestation<E>, Error> {
         let bytes = txn
@@ -588,7 +696,7 @@ impl<E: EthSpec> SlasherDB<E> {
 
     pub fn get_attestation_for_validator(
         &self,
-        txn: &mut RwTransaction<'_>,
+        txn: &mut impl ReadTransaction,
         validator_index: u64,
         target_epoch: Epoch,
     ) -> Result<IndexedAttestation<E>, Error> {
@@ -605,7 +713,7 @@ impl<E: EthSpec> SlasherDB<E> {
 
     pub fn get_attester_record(
         &self,
-        txn: &mut RwTransaction<'_>,
+        txn: &mut impl ReadTransaction,
         validator_index: u64,
         target: Epoch,
         prev_max_target: Option<Epoch>,
@@ -663,6 +771,65 @@ impl<E: EthSpec> SlasherDB<E> {
         }
     }
 
+    /// Record a newly detected slashing, returning `false` if it had already been recorded.
+    ///
+    /// The time at which a slashing was first detected is retained.
+    pub fn store_detected_slashing<T: DetectedSlashingKind>(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        detected: &DetectedSlashing<T>,
+    ) -> Result<bool, Error> {
+        let db = T::detected_db(&self.databases);
+        let key = DetectedSlashingKey::new(
+            detected.slashing.epoch(E::slots_per_epoch()),
+            detected.slashing.tree_hash_root(),
+        );
+
+        if txn.get(db, key.as_ref())?.is_some() {
+            return Ok(false);
+        }
+
+        let mut value = Vec::with_capacity(
+            DETECTED_SLASHING_TIMESTAMP_SIZE + detected.slashing.ssz_bytes_len(),
+        );
+        value.extend_from_slice(&(detected.seen_timestamp.as_nanos() as u64).to_be_bytes());
+        detected.slashing.ssz_append(&mut value);
+        txn.put(db, &key, &value)?;
+        Ok(true)
+    }
+
+    /// Load every detected slashing of type `T` which has not been pruned, in order of epoch.
+    pub fn load_detected_slashings<T: DetectedSlashingKind>(
+        &self,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<Vec<DetectedSlashing<T>>, Error> {
+        let mut detected = vec![];
+        let mut cursor = txn.cursor(T::detected_db(&self.databases))?;
+
+        if cursor.first_key()?.is_none() {
+            return Ok(detected);
+        }
+
+        while let Some((_, value)) = cursor.get_current()? {
+            if value.len() < DETECTED_SLASHING_TIMESTAMP_SIZE {
+                return Err(Error::DetectedSlashingCorrupt {
+                    length: value.len(),
+                });
+            }
+            let (timestamp, slashing) = value.split_at(DETECTED_SLASHING_TIMESTAMP_SIZE);
+            detected.push(DetectedSlashing {
+                seen_timestamp: Duration::from_nanos(BigEndian::read_u64(timestamp)),
+                slashing: T::from_ssz_bytes(slashing)?,
+            });
+
+            if cursor.next_key()?.is_none() {
+                break;
+            }
+        }
+
+        Ok(detected)
+    }
+
     /// Attempt to prune the database, deleting old blocks and attestations.
     pub fn prune(&self, current_epoch: Epoch) -> Result<(), Error> {
         let mut txn = self.begin_rw_txn()?;
@@ -679,6 +846,48 @@ impl<E: EthSpec> SlasherDB<E> {
     ) -> Result<(), Error> {
         self.prune_proposers(current_epoch, txn)?;
         self.prune_indexed_attestations(current_epoch, txn)?;
+        self.prune_detected_slashings::<AttesterSlashing<E>>(current_epoch, txn)?;
+        self.prune_detected_slashings::<ProposerSlashing>(current_epoch, txn)?;
+        Ok(())
+    }
+
+    /// Return the earliest epoch of the history retained at `current_epoch`.
+    pub fn min_history_epoch(&self, current_epoch: Epoch) -> Epoch {
+        current_epoch
+            .saturating_add(1u64)
+            .saturating_sub(self.config.history_length as u64)
+    }
+
+    fn prune_detected_slashings<T: DetectedSlashingKind>(
+        &self,
+        current_epoch: Epoch,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
+        let min_epoch = self.min_history_epoch(current_epoch);
+
+        let mut cursor = txn.cursor(T::detected_db(&self.databases))?;
+
+        // Position cursor at first key, bailing out if the database is empty.
+        if cursor.first_key()?.is_none() {
+            return Ok(());
+        }
+
+        loop {
+            let (key_bytes, _) = cursor
+                .get_current()?
+                .ok_or(Error::MissingDetectedSlashingKey)?;
+
+            if DetectedSlashingKey::parse(key_bytes)? < min_epoch {
+                cursor.delete_current()?;
+
+                if cursor.next_key()?.is_none() {
+                    break;
+                }
+            } else {
+                break;
+            }
+        }
+
         Ok(())
     }
 
@@ -24,6 +24,11 @@ pub struct RwTransaction<'env> {
     txn: lmdb::RwTransaction<'env>,
 }
 
+#[derive(Debug)]
+pub struct RoTransaction<'env> {
+    txn: lmdb::RoTransaction<'env>,
+}
+
 #[derive(Debug)]
 pub struct Database<'env> {
     db: lmdb::Database,
@@ -61,6 +66,12 @@ impl Environment {
             .env
             .create_db(Some(CURRENT_EPOCHS_DB), Self::db_flags())?;
         let proposers_db = self.env.create_db(Some(PROPOSERS_DB), Self::db_flags())?;
+        let detected_attester_slashings_db = self
+            .env
+            .create_db(Some(DETECTED_ATTESTER_SLASHINGS_DB), Self::db_flags())?;
+        let detected_proposer_slashings_db = self
+            .env
+            .create_db(Some(DETECTED_PROPOSER_SLASHINGS_DB), Self::db_flags())?;
         let metadata_db = self.env.create_db(Some(METADATA_DB), Self::db_flags())?;
 
         let wrap = |db| {
@@ -79,6 +90,8 @@ impl Environment {
             max_targets_db: wrap(max_targets_db),
             current_epochs_db: wrap(current_epochs_db),
             proposers_db: wrap(proposers_db),
+            detected_attester_slashings_db: wrap(detected_attester_slashings_db),
+            detected_proposer_slashings_db: wrap(detected_proposer_slashings_db),
             metadata_db: wrap(metadata_db),
         })
     }
@@ -88,6 +101,11 @@ impl Environment {
         Ok(RwTransaction { txn })
     }
 
+    pub fn begin_ro_txn(&self) -> Result<RoTransaction, Error> {
+        let txn = self.env.begin_ro_txn()?;
+        Ok(RoTransaction { txn })
+    }
+
     pub fn filenames(&self, config: &Config) -> Vec<PathBuf> {
         vec![
             config.database_path.join("data.mdb"),
@@ -139,6 +157,16 @@ impl<'env> RwTransaction<'env> {
     }
 }
 
+impl<'env> RoTransaction<'env> {
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &'env self,
+        db: &Database<'env>,
+        key: &K,
+    ) -> Result<Option<Cow<'env, [u8]>>, Error> {
+        Ok(self.txn.get(db.db, key).optional()?.map(Cow::Borrowed))
+    }
+}
+
 impl<'env> Cursor<'env> {
     pub fn first_key(&mut self) -> Result<Option<Key>, Error> {
         let opt_key = self
@@ -25,6 +25,11 @@ pub struct RwTransaction<'env> {
     txn: mdbx::Transaction<'env, mdbx::RW, mdbx::NoWriteMap>,
 }
 
+#[derive(Debug)]
+pub struct RoTransaction<'env> {
+    txn: mdbx::Transaction<'env, mdbx::RO, mdbx::NoWriteMap>,
+}
+
 #[derive(Debug)]
 pub struct Database<'env> {
     db: mdbx::Database<'env>,
@@ -54,6 +59,8 @@ impl Environment {
         txn.create_db(MAX_TARGETS_DB)?;
         txn.create_db(CURRENT_EPOCHS_DB)?;
         txn.create_db(PROPOSERS_DB)?;
+        txn.create_db(DETECTED_ATTESTER_SLASHINGS_DB)?;
+        txn.create_db(DETECTED_PROPOSER_SLASHINGS_DB)?;
         txn.create_db(METADATA_DB)?;
 
         // This is all rather nasty
@@ -73,6 +80,8 @@ impl Environment {
             max_targets_db: next_db(),
             current_epochs_db: next_db(),
             proposers_db: next_db(),
+            detected_attester_slashings_db: next_db(),
+            detected_proposer_slashings_db: next_db(),
             metadata_db: next_db(),
         })
     }
@@ -82,6 +91,11 @@ impl Environment {
         Ok(RwTransaction { txn })
     }
 
+    pub fn begin_ro_txn(&self) -> Result<RoTransaction, Error> {
+        let txn = self.env.begin_ro_txn()?;
+        Ok(RoTransaction { txn })
+    }
+
     pub fn filenames(&self, config: &Config) -> Vec<PathBuf> {
         vec![
             config.database_path.join("mdbx.dat"),
@@ -153,6 +167,16 @@ impl<'env> RwTransaction<'env> {
     }
 }
 
+impl<'env> RoTransaction<'env> {
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &'env self,
+        db: &Database<'env>,
+        key: &K,
+    ) -> Result<Option<Cow<'env, [u8]>>, Error> {
+        Ok(self.txn.get(&db.db, key.as_ref())?)
+    }
+}
+
 impl<'env> Cursor<'env> {
     pub fn first_key(&mut self) -> Result<Option<Cow<'env, [u8]>>, Error> {
         let opt_key = self.cursor.first()?.map(|(key_bytes, ())| key_bytes);
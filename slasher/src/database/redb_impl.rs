@@ -0,0 +1,271 @@
+#![cfg(feature = "redb")]
+
+use crate::{
+    config::REDB_DATA_FILENAME,
+    database::{
+        interface::{Key, OpenDatabases, Value},
+        *,
+    },
+    Config, DatabaseBackend, Error,
+};
+use redb::{backends::InMemoryBackend, ReadableTable, TableDefinition};
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Bound;
+use std::path::PathBuf;
+
+/// All tables map byte strings to byte strings, like the LMDB and MDBX databases.
+type Table<'a> = TableDefinition<'a, &'static [u8], &'static [u8]>;
+
+pub struct Environment {
+    db: redb::Database,
+    in_memory: bool,
+}
+
+pub struct RwTransaction<'env> {
+    txn: redb::WriteTransaction,
+    _phantom: PhantomData<&'env ()>,
+}
+
+pub struct RoTransaction<'env> {
+    txn: redb::ReadTransaction,
+    _phantom: PhantomData<&'env ()>,
+}
+
+#[derive(Debug)]
+pub struct Database<'env> {
+    table_name: &'static str,
+    _phantom: PhantomData<&'env ()>,
+}
+
+pub struct Cursor<'env> {
+    txn: &'env redb::WriteTransaction,
+    table_name: &'static str,
+    /// The key at the cursor's current position, if it has been positioned.
+    current_key: Option<Vec<u8>>,
+}
+
+impl fmt::Debug for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Environment")
+            .field("in_memory", &self.in_memory)
+            .finish()
+    }
+}
+
+impl<'env> fmt::Debug for RwTransaction<'env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RwTransaction").finish()
+    }
+}
+
+impl<'env> fmt::Debug for RoTransaction<'env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoTransaction").finish()
+    }
+}
+
+impl<'env> fmt::Debug for Cursor<'env> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cursor")
+            .field("table_name", &self.table_name)
+            .field("current_key", &self.current_key)
+            .finish()
+    }
+}
+
+impl Environment {
+    pub fn new(config: &Config) -> Result<Environment, Error> {
+        let in_memory = config.backend == DatabaseBackend::Memory;
+        let db = if in_memory {
+            redb::Builder::new().create_with_backend(InMemoryBackend::new())?
+        } else {
+            redb::Database::create(config.database_path.join(REDB_DATA_FILENAME))?
+        };
+        Ok(Environment { db, in_memory })
+    }
+
+    pub fn create_databases(&self) -> Result<OpenDatabases, Error> {
+        // Tables only exist once they have been opened in a committed transaction.
+        let txn = self.db.begin_write()?;
+        for table_name in [
+            INDEXED_ATTESTATION_DB,
+            INDEXED_ATTESTATION_ID_DB,
+            ATTESTERS_DB,
+            ATTESTERS_MAX_TARGETS_DB,
+            MIN_TARGETS_DB,
+            MAX_TARGETS_DB,
+            CURRENT_EPOCHS_DB,
+            PROPOSERS_DB,
+            DETECTED_ATTESTER_SLASHINGS_DB,
+            DETECTED_PROPOSER_SLASHINGS_DB,
+            METADATA_DB,
+        ] {
+            txn.open_table(Table::new(table_name))?;
+        }
+        txn.commit()?;
+
+        let wrap = |table_name| {
+            crate::Database::Redb(Database {
+                table_name,
+                _phantom: PhantomData,
+            })
+        };
+
+        Ok(OpenDatabases {
+            indexed_attestation_db: wrap(INDEXED_ATTESTATION_DB),
+            indexed_attestation_id_db: wrap(INDEXED_ATTESTATION_ID_DB),
+            attesters_db: wrap(ATTESTERS_DB),
+            attesters_max_targets_db: wrap(ATTESTERS_MAX_TARGETS_DB),
+            min_targets_db: wrap(MIN_TARGETS_DB),
+            max_targets_db: wrap(MAX_TARGETS_DB),
+            current_epochs_db: wrap(CURRENT_EPOCHS_DB),
+            proposers_db: wrap(PROPOSERS_DB),
+            detected_attester_slashings_db: wrap(DETECTED_ATTESTER_SLASHINGS_DB),
+            detected_proposer_slashings_db: wrap(DETECTED_PROPOSER_SLASHINGS_DB),
+            metadata_db: wrap(METADATA_DB),
+        })
+    }
+
+    pub fn begin_rw_txn(&self) -> Result<RwTransaction, Error> {
+        let txn = self.db.begin_write()?;
+        Ok(RwTransaction {
+            txn,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn begin_ro_txn(&self) -> Result<RoTransaction, Error> {
+        let txn = self.db.begin_read()?;
+        Ok(RoTransaction {
+            txn,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn filenames(&self, config: &Config) -> Vec<PathBuf> {
+        if self.in_memory {
+            vec![]
+        } else {
+            vec![config.database_path.join(REDB_DATA_FILENAME)]
+        }
+    }
+}
+
+impl<'env> RwTransaction<'env> {
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &'env self,
+        db: &Database<'env>,
+        key: &K,
+    ) -> Result<Option<Cow<'env, [u8]>>, Error> {
+        let table = self.txn.open_table(Table::new(db.table_name))?;
+        let value = table.get(key.as_ref())?;
+        Ok(value.map(|value| Cow::Owned(value.value().to_vec())))
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &mut self,
+        db: &Database,
+        key: K,
+        value: V,
+    ) -> Result<(), Error> {
+        let mut table = self.txn.open_table(Table::new(db.table_name))?;
+        table.insert(key.as_ref(), value.as_ref())?;
+        Ok(())
+    }
+
+    pub fn del<K: AsRef<[u8]>>(&mut self, db: &Database, key: K) -> Result<(), Error> {
+        let mut table = self.txn.open_table(Table::new(db.table_name))?;
+        table.remove(key.as_ref())?;
+        Ok(())
+    }
+
+    pub fn cursor<'a>(&'a mut self, db: &Database) -> Result<Cursor<'a>, Error> {
+        Ok(Cursor {
+            txn: &self.txn,
+            table_name: db.table_name,
+            current_key: None,
+        })
+    }
+
+    pub fn commit(self) -> Result<(), Error> {
+        self.txn.commit()?;
+        Ok(())
+    }
+}
+
+impl<'env> RoTransaction<'env> {
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &'env self,
+        db: &Database<'env>,
+        key: &K,
+    ) -> Result<Option<Cow<'env, [u8]>>, Error> {
+        let table = self.txn.open_table(Table::new(db.table_name))?;
+        let value = table.get(key.as_ref())?;
+        Ok(value.map(|value| Cow::Owned(value.value().to_vec())))
+    }
+}
+
+impl<'env> Cursor<'env> {
+    /// Move the cursor to the first key in `range`, returning it.
+    fn seek(&mut self, range: (Bound<&[u8]>, Bound<&[u8]>)) -> Result<Option<Key>, Error> {
+        let table = self.txn.open_table(Table::new(self.table_name))?;
+        let key = table
+            .range::<&[u8]>(range)?
+            .next()
+            .transpose()?
+            .map(|(key, _)| key.value().to_vec());
+        self.current_key = key;
+        Ok(self.current_key.as_deref().map(Cow::Borrowed))
+    }
+
+    pub fn first_key(&mut self) -> Result<Option<Key>, Error> {
+        self.seek((Bound::Unbounded, Bound::Unbounded))
+    }
+
+    pub fn last_key(&mut self) -> Result<Option<Key>, Error> {
+        let table = self.txn.open_table(Table::new(self.table_name))?;
+        self.current_key = table.last()?.map(|(key, _)| key.value().to_vec());
+        Ok(self.current_key.as_deref().map(Cow::Borrowed))
+    }
+
+    pub fn next_key(&mut self) -> Result<Option<Key>, Error> {
+        let Some(current_key) = self.current_key.take() else {
+            return self.first_key();
+        };
+        self.seek((Bound::Excluded(&current_key), Bound::Unbounded))
+    }
+
+    pub fn get_current(&mut self) -> Result<Option<(Key, Value)>, Error> {
+        let Some(current_key) = &self.current_key else {
+            return Ok(None);
+        };
+        let table = self.txn.open_table(Table::new(self.table_name))?;
+        let value = table.get(current_key.as_slice())?;
+        Ok(value.map(|value| {
+            (
+                Cow::Borrowed(current_key.as_slice()),
+                Cow::Owned(value.value().to_vec()),
+            )
+        }))
+    }
+
+    /// Delete the entry at the current position.
+    ///
+    /// The cursor remains at the deleted key, so that `next_key` moves to the entry after it.
+    pub fn delete_current(&mut self) -> Result<(), Error> {
+        if let Some(current_key) = &self.current_key {
+            let mut table = self.txn.open_table(Table::new(self.table_name))?;
+            table.remove(current_key.as_slice())?;
+        }
+        Ok(())
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(&mut self, key: K, value: V) -> Result<(), Error> {
+        let mut table = self.txn.open_table(Table::new(self.table_name))?;
+        table.insert(key.as_ref(), value.as_ref())?;
+        self.current_key = Some(key.as_ref().to_vec());
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use crate::config::{Config, DiskConfig};
 use std::io;
+use std::path::PathBuf;
 use types::Epoch;
 
 #[derive(Debug)]
@@ -8,6 +9,8 @@ pub enum Error {
     DatabaseMdbxError(mdbx::Error),
     #[cfg(feature = "lmdb")]
     DatabaseLmdbError(lmdb::Error),
+    #[cfg(feature = "redb")]
+    DatabaseRedbError(redb::Error),
     SlasherDatabaseBackendDisabled,
     MismatchedDatabaseVariant,
     DatabaseIOError(io::Error),
@@ -58,6 +61,12 @@ pub enum Error {
     IndexedAttestationIdCorrupt {
         length: usize,
     },
+    DetectedSlashingKeyCorrupt {
+        length: usize,
+    },
+    DetectedSlashingCorrupt {
+        length: usize,
+    },
     MissingIndexedAttestation {
         id: u64,
     },
@@ -65,7 +74,16 @@ pub enum Error {
     MissingProposerKey,
     MissingIndexedAttestationId,
     MissingIndexedAttestationIdKey,
+    MissingDetectedSlashingKey,
     InconsistentAttestationDataRoot,
+    CopySourceMissing(PathBuf),
+    CopyDestinationNotEmpty(PathBuf),
+    /// An attestation which can't be checked against the database at the current epoch.
+    AttestationOutsideHistory {
+        source_epoch: Epoch,
+        target_epoch: Epoch,
+        current_epoch: Epoch,
+    },
 }
 
 #[cfg(feature = "mdbx")]
@@ -88,6 +106,51 @@ impl From<lmdb::Error> for Error {
     }
 }
 
+#[cfg(feature = "redb")]
+impl From<redb::Error> for Error {
+    fn from(e: redb::Error) -> Self {
+        match e {
+            redb::Error::Io(e) => Error::DatabaseIOError(e),
+            _ => Error::DatabaseRedbError(e),
+        }
+    }
+}
+
+#[cfg(feature = "redb")]
+impl From<redb::DatabaseError> for Error {
+    fn from(e: redb::DatabaseError) -> Self {
+        Error::from(redb::Error::from(e))
+    }
+}
+
+#[cfg(feature = "redb")]
+impl From<redb::TransactionError> for Error {
+    fn from(e: redb::TransactionError) -> Self {
+        Error::from(redb::Error::from(e))
+    }
+}
+
+#[cfg(feature = "redb")]
+impl From<redb::TableError> for Error {
+    fn from(e: redb::TableError) -> Self {
+        Error::from(redb::Error::from(e))
+    }
+}
+
+#[cfg(feature = "redb")]
+impl From<redb::StorageError> for Error {
+    fn from(e: redb::StorageError) -> Self {
+        Error::from(redb::Error::from(e))
+    }
+}
+
+#[cfg(feature = "redb")]
+impl From<redb::CommitError> for Error {
+    fn from(e: redb::CommitError) -> Self {
+        Error::from(redb::Error::from(e))
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(e: io::Error) -> Self {
         Error::DatabaseIOError(e)
@@ -1,6 +1,6 @@
 #![deny(missing_debug_implementations)]
 #![cfg_attr(
-    not(any(feature = "mdbx", feature = "lmdb")),
+    not(any(feature = "mdbx", feature = "lmdb", feature = "redb")),
     allow(unused, clippy::drop_non_drop)
 )]
 
@@ -10,6 +10,7 @@ mod attester_record;
 mod batch_stats;
 mod block_queue;
 pub mod config;
+mod copy;
 mod database;
 mod error;
 pub mod metrics;
@@ -17,14 +18,16 @@ mod migrate;
 mod slasher;
 pub mod test_utils;
 
-pub use crate::slasher::Slasher;
+pub use crate::slasher::{DetectedSlashing, Slasher, MAX_DETECTED_SLASHINGS};
+pub use array::MinMaxTargets;
 pub use attestation_queue::{AttestationBatch, AttestationQueue, SimpleBatch};
 pub use attester_record::{AttesterRecord, CompactAttesterRecord, IndexedAttesterRecord};
 pub use block_queue::BlockQueue;
 pub use config::{Config, DatabaseBackend, DatabaseBackendOverride};
+pub use copy::copy_database;
 pub use database::{
-    interface::{Database, Environment, RwTransaction},
-    IndexedAttestationId, SlasherDB,
+    interface::{Database, Environment, ReadTransaction, RoTransaction, RwTransaction},
+    DetectedSlashingKind, IndexedAttestationId, SlasherDB,
 };
 pub use error::Error;
 
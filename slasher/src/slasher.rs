@@ -1,3 +1,4 @@
+use crate::array::{MaxTargetChunk, MinMaxTargets, MinTargetChunk};
 use crate::batch_stats::{AttestationStats, BatchStats, BlockStats};
 use crate::metrics::{
     self, SLASHER_NUM_ATTESTATIONS_DEFERRED, SLASHER_NUM_ATTESTATIONS_DROPPED,
@@ -5,17 +6,31 @@ use crate::metrics::{
     SLASHER_NUM_BLOCKS_PROCESSED,
 };
 use crate::{
-    array, AttestationBatch, AttestationQueue, AttesterRecord, BlockQueue, Config, Error,
-    IndexedAttestationId, ProposerSlashingStatus, RwTransaction, SimpleBatch, SlasherDB,
+    array, AttestationBatch, AttestationQueue, AttesterRecord, AttesterSlashingStatus, BlockQueue,
+    Config, DetectedSlashingKind, Error, IndexedAttestationId, IndexedAttesterRecord,
+    ProposerSlashingStatus, ReadTransaction, RwTransaction, SimpleBatch, SlasherDB,
 };
 use parking_lot::Mutex;
 use slog::{debug, error, info, Logger};
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use types::{
     AttesterSlashing, Epoch, EthSpec, IndexedAttestation, ProposerSlashing, SignedBeaconBlockHeader,
 };
 
+/// The maximum number of slashings of each type to hold in memory from the log of detected
+/// slashings.
+pub const MAX_DETECTED_SLASHINGS: usize = 1024;
+
+/// A slashing detected by the slasher, and the time at which it was detected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedSlashing<T> {
+    /// The time at which the slashing was detected, as a duration since the UNIX epoch.
+    pub seen_timestamp: Duration,
+    pub slashing: T,
+}
+
 #[derive(Debug)]
 pub struct Slasher<E: EthSpec> {
     db: SlasherDB<E>,
@@ -23,6 +38,13 @@ pub struct Slasher<E: EthSpec> {
     block_queue: BlockQueue,
     attester_slashings: Mutex<HashSet<AttesterSlashing<E>>>,
     proposer_slashings: Mutex<HashSet<ProposerSlashing>>,
+    /// The most recently detected slashings, which are retained after being harvested.
+    ///
+    /// Detected slashings are stored in the database until they are pruned along with the rest
+    /// of the history, and the most recent `MAX_DETECTED_SLASHINGS` of each type are also held
+    /// here.
+    detected_attester_slashings: Mutex<VecDeque<DetectedSlashing<AttesterSlashing<E>>>>,
+    detected_proposer_slashings: Mutex<VecDeque<DetectedSlashing<ProposerSlashing>>>,
     config: Arc<Config>,
     log: Logger,
 }
@@ -32,6 +54,13 @@ impl<E: EthSpec> Slasher<E> {
         config.validate()?;
         let config = Arc::new(config);
         let db = SlasherDB::open(config.clone(), log.clone())?;
+
+        // Restore the log of detected slashings from before the slasher was last opened.
+        let mut txn = db.begin_rw_txn()?;
+        let detected_attester_slashings = load_detected_slashings(&db, &mut txn)?;
+        let detected_proposer_slashings = load_detected_slashings(&db, &mut txn)?;
+        drop(txn);
+
         let attester_slashings = Mutex::new(HashSet::new());
         let proposer_slashings = Mutex::new(HashSet::new());
         let attestation_queue = AttestationQueue::default();
@@ -42,6 +71,8 @@ impl<E: EthSpec> Slasher<E> {
             block_queue,
             attester_slashings,
             proposer_slashings,
+            detected_attester_slashings: Mutex::new(detected_attester_slashings),
+            detected_proposer_slashings: Mutex::new(detected_proposer_slashings),
             config,
             log,
         })
@@ -57,6 +88,32 @@ impl<E: EthSpec> Slasher<E> {
         std::mem::take(&mut self.proposer_slashings.lock())
     }
 
+    /// Return the most recently detected attester slashings, oldest first.
+    ///
+    /// Unlike `get_attester_slashings`, this does not remove any slashings from the slasher. Only
+    /// the last `MAX_DETECTED_SLASHINGS` slashings which have not been pruned from the database
+    /// are returned.
+    pub fn get_detected_attester_slashings(&self) -> Vec<DetectedSlashing<AttesterSlashing<E>>> {
+        self.detected_attester_slashings
+            .lock()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Return the most recently detected proposer slashings, oldest first.
+    ///
+    /// Unlike `get_proposer_slashings`, this does not remove any slashings from the slasher. Only
+    /// the last `MAX_DETECTED_SLASHINGS` slashings which have not been pruned from the database
+    /// are returned.
+    pub fn get_detected_proposer_slashings(&self) -> Vec<DetectedSlashing<ProposerSlashing>> {
+        self.detected_proposer_slashings
+            .lock()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
     pub fn config(&self) -> &Config {
         &self.config
     }
@@ -112,6 +169,7 @@ impl<E: EthSpec> Slasher<E> {
                 "Found {} new proposer slashings!",
                 slashings.len(),
             );
+            self.record_detected_slashings(txn, &self.detected_proposer_slashings, &slashings)?;
             self.proposer_slashings.lock().extend(slashings);
         }
 
@@ -226,6 +284,11 @@ impl<E: EthSpec> Slasher<E> {
                             slashings.len()
                         );
                     }
+                    self.record_detected_slashings(
+                        txn,
+                        &self.detected_attester_slashings,
+                        &slashings,
+                    )?;
                     self.attester_slashings.lock().extend(slashings);
                 }
                 Err(e) => {
@@ -256,6 +319,11 @@ impl<E: EthSpec> Slasher<E> {
                         slashings.len()
                     );
                 }
+                self.record_detected_slashings(
+                    txn,
+                    &self.detected_attester_slashings,
+                    &slashings,
+                )?;
                 self.attester_slashings.lock().extend(slashings);
             }
             Err(e) => {
@@ -348,8 +416,170 @@ impl<E: EthSpec> Slasher<E> {
         (keep, defer, drop_count)
     }
 
-    /// Prune unnecessary attestations and blocks from the on-disk database.
+    /// Return the min and max targets of `validator_index` for every epoch of the history at
+    /// `current_epoch`.
+    pub fn get_targets_for_validator(
+        &self,
+        validator_index: u64,
+        current_epoch: Epoch,
+    ) -> Result<Vec<MinMaxTargets>, Error> {
+        let mut txn = self.db.begin_ro_txn()?;
+        array::get_targets_for_validator(
+            &self.db,
+            &mut txn,
+            validator_index,
+            current_epoch,
+            &self.config,
+        )
+    }
+
+    /// Check whether `attestation` is slashable against the attestations in the database.
+    ///
+    /// Return the slashings that processing `attestation` during `current_epoch` would produce,
+    /// without storing the attestation or modifying the database.
+    pub fn check_attestation(
+        &self,
+        attestation: IndexedAttestation<E>,
+        current_epoch: Epoch,
+    ) -> Result<HashSet<AttesterSlashing<E>>, Error> {
+        let record = AttesterRecord::from(attestation.clone());
+        let (valid, _, _) = self.validate(
+            vec![IndexedAttesterRecord::new(attestation.clone(), record)],
+            current_epoch,
+        );
+        if valid.is_empty() {
+            return Err(Error::AttestationOutsideHistory {
+                source_epoch: attestation.data.source.epoch,
+                target_epoch: attestation.data.target.epoch,
+                current_epoch,
+            });
+        }
+
+        let mut txn = self.db.begin_ro_txn()?;
+        let mut slashings = HashSet::new();
+
+        for &validator_index in attestation.attesting_indices.iter() {
+            let slashing_status =
+                self.check_double_vote(&mut txn, validator_index, &attestation)?;
+            let slashing_status = if slashing_status == AttesterSlashingStatus::NotSlashable {
+                array::check_surround_for_validator::<_, MinTargetChunk>(
+                    &self.db,
+                    &mut txn,
+                    validator_index,
+                    &attestation,
+                    current_epoch,
+                    &self.config,
+                )?
+            } else {
+                slashing_status
+            };
+            let slashing_status = if slashing_status == AttesterSlashingStatus::NotSlashable {
+                array::check_surround_for_validator::<_, MaxTargetChunk>(
+                    &self.db,
+                    &mut txn,
+                    validator_index,
+                    &attestation,
+                    current_epoch,
+                    &self.config,
+                )?
+            } else {
+                slashing_status
+            };
+
+            if let Some(slashing) = slashing_status.into_slashing(&attestation) {
+                slashings.insert(slashing);
+            }
+        }
+
+        Ok(slashings)
+    }
+
+    /// Check whether `attestation` is a double vote by `validator_index`, without modifying the
+    /// database.
+    fn check_double_vote(
+        &self,
+        txn: &mut impl ReadTransaction,
+        validator_index: u64,
+        attestation: &IndexedAttestation<E>,
+    ) -> Result<AttesterSlashingStatus<E>, Error> {
+        let max_target = self.db.get_attester_max_target(validator_index, txn)?;
+        let Some(existing_record) = self.db.get_attester_record(
+            txn,
+            validator_index,
+            attestation.data.target.epoch,
+            max_target,
+        )?
+        else {
+            return Ok(AttesterSlashingStatus::NotSlashable);
+        };
+
+        let existing_attestation = self
+            .db
+            .get_indexed_attestation(txn, existing_record.indexed_attestation_id)?;
+        if attestation.is_double_vote(&existing_attestation) {
+            Ok(AttesterSlashingStatus::DoubleVote(Box::new(
+                existing_attestation,
+            )))
+        } else {
+            Ok(AttesterSlashingStatus::NotSlashable)
+        }
+    }
+
+    /// Prune unnecessary attestations, blocks and detected slashings from the on-disk database.
     pub fn prune_database(&self, current_epoch: Epoch) -> Result<(), Error> {
-        self.db.prune(current_epoch)
+        self.db.prune(current_epoch)?;
+
+        let min_epoch = self.db.min_history_epoch(current_epoch);
+        let slots_per_epoch = E::slots_per_epoch();
+        self.detected_attester_slashings
+            .lock()
+            .retain(|detected| detected.slashing.epoch(slots_per_epoch) >= min_epoch);
+        self.detected_proposer_slashings
+            .lock()
+            .retain(|detected| detected.slashing.epoch(slots_per_epoch) >= min_epoch);
+        Ok(())
     }
+
+    /// Store newly detected `slashings` in the database and append them to `detected`, evicting
+    /// the oldest slashings once there are more than `MAX_DETECTED_SLASHINGS`.
+    fn record_detected_slashings<'a, T: DetectedSlashingKind + Clone + 'a>(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        detected: &Mutex<VecDeque<DetectedSlashing<T>>>,
+        slashings: impl IntoIterator<Item = &'a T>,
+    ) -> Result<(), Error> {
+        let seen_timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        for slashing in slashings {
+            let new_detected = DetectedSlashing {
+                seen_timestamp,
+                slashing: slashing.clone(),
+            };
+            // The same slashing may be detected more than once, but is only recorded once.
+            if !self.db.store_detected_slashing(txn, &new_detected)? {
+                continue;
+            }
+
+            let mut detected = detected.lock();
+            if detected.len() >= MAX_DETECTED_SLASHINGS {
+                detected.pop_front();
+            }
+            detected.push_back(new_detected);
+        }
+        Ok(())
+    }
+}
+
+/// Load the most recent `MAX_DETECTED_SLASHINGS` detected slashings of type `T` from `db`,
+/// oldest first.
+fn load_detected_slashings<E: EthSpec, T: DetectedSlashingKind>(
+    db: &SlasherDB<E>,
+    txn: &mut RwTransaction<'_>,
+) -> Result<VecDeque<DetectedSlashing<T>>, Error> {
+    let mut detected = db.load_detected_slashings(txn)?;
+    detected.sort_by_key(|detected| detected.seen_timestamp);
+    let num_pruned = detected.len().saturating_sub(MAX_DETECTED_SLASHINGS);
+    Ok(detected.into_iter().skip(num_pruned).collect())
 }
@@ -0,0 +1,80 @@
+#![cfg(feature = "redb")]
+
+use logging::test_logger;
+use maplit::hashset;
+use slasher::{
+    copy_database,
+    test_utils::{att_slashing, block, indexed_att, E},
+    Config, DatabaseBackend, Slasher,
+};
+use tempfile::tempdir;
+use types::Epoch;
+
+#[test]
+fn in_memory_backend() {
+    let tempdir = tempdir().unwrap();
+    let mut config = Config::new(tempdir.path().into());
+    config.backend = DatabaseBackend::Memory;
+    let slasher = Slasher::<E>::open(config, test_logger()).unwrap();
+    let current_epoch = Epoch::new(4);
+
+    let att1 = indexed_att([0, 1], 1, 2, 0);
+    let att2 = indexed_att([1], 0, 3, 0);
+    slasher.accept_attestation(att1.clone());
+    slasher.process_queued(current_epoch).unwrap();
+    slasher.accept_attestation(att2.clone());
+    slasher.accept_block_header(block(1, 0, 0));
+    slasher.accept_block_header(block(1, 0, 1));
+    slasher.process_queued(current_epoch).unwrap();
+
+    assert_eq!(
+        slasher.get_attester_slashings(),
+        hashset![att_slashing(&att2, &att1)]
+    );
+    assert_eq!(slasher.get_proposer_slashings().len(), 1);
+
+    // Nothing is written to disk.
+    assert_eq!(std::fs::read_dir(tempdir.path()).unwrap().count(), 0);
+}
+
+#[test]
+#[cfg(feature = "lmdb")]
+fn copy_lmdb_to_redb() {
+    let input_dir = tempdir().unwrap();
+    let output_dir = tempdir().unwrap();
+    let current_epoch = Epoch::new(4);
+
+    let mut input = Config::new(input_dir.path().into());
+    input.backend = DatabaseBackend::Lmdb;
+    let mut output = Config::new(output_dir.path().into());
+    output.backend = DatabaseBackend::Redb;
+
+    let att1 = indexed_att([0, 1], 1, 2, 0);
+    {
+        let slasher = Slasher::<E>::open(input.clone(), test_logger()).unwrap();
+        slasher.accept_attestation(att1.clone());
+        slasher.accept_block_header(block(1, 0, 0));
+        slasher.process_queued(current_epoch).unwrap();
+    }
+
+    assert!(copy_database(&input, &output, &test_logger()).unwrap() > 0);
+
+    // The output database is not overwritten by a second copy.
+    assert!(matches!(
+        copy_database(&input, &output, &test_logger()),
+        Err(slasher::Error::CopyDestinationNotEmpty(_))
+    ));
+
+    // Slashings against the copied data are detected.
+    let slasher = Slasher::<E>::open(output, test_logger()).unwrap();
+    let att2 = indexed_att([1], 1, 2, 1);
+    slasher.accept_attestation(att2.clone());
+    slasher.accept_block_header(block(1, 0, 1));
+    slasher.process_queued(current_epoch).unwrap();
+
+    assert_eq!(
+        slasher.get_attester_slashings(),
+        hashset![att_slashing(&att1, &att2)]
+    );
+    assert_eq!(slasher.get_proposer_slashings().len(), 1);
+}
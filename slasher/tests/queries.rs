@@ -0,0 +1,190 @@
+#![cfg(any(feature = "mdbx", feature = "lmdb", feature = "redb"))]
+
+use logging::test_logger;
+use maplit::hashset;
+use slasher::{
+    test_utils::{att_slashing, block, indexed_att, E},
+    Config, Error, MinMaxTargets, Slasher,
+};
+#[cfg(feature = "lmdb")]
+use slasher::DatabaseBackend;
+use tempfile::tempdir;
+use types::Epoch;
+
+#[test]
+fn detected_slashings_are_retained() {
+    let tempdir = tempdir().unwrap();
+    let config = Config::new(tempdir.path().into());
+    let slasher = Slasher::<E>::open(config, test_logger()).unwrap();
+    let current_epoch = Epoch::new(4);
+
+    let att1 = indexed_att([0, 1], 1, 2, 0);
+    let att2 = indexed_att([1], 1, 2, 1);
+    slasher.accept_attestation(att1.clone());
+    slasher.process_queued(current_epoch).unwrap();
+    slasher.accept_attestation(att2.clone());
+    slasher.accept_block_header(block(1, 0, 0));
+    slasher.accept_block_header(block(1, 0, 1));
+    slasher.process_queued(current_epoch).unwrap();
+
+    // Harvesting the slashings does not remove them from the log.
+    assert_eq!(
+        slasher.get_attester_slashings(),
+        hashset![att_slashing(&att1, &att2)]
+    );
+    assert_eq!(slasher.get_proposer_slashings().len(), 1);
+
+    let attester_slashings = slasher.get_detected_attester_slashings();
+    assert_eq!(attester_slashings.len(), 1);
+    assert_eq!(attester_slashings[0].slashing, att_slashing(&att1, &att2));
+    assert!(attester_slashings[0].seen_timestamp.as_secs() > 0);
+    assert_eq!(slasher.get_detected_proposer_slashings().len(), 1);
+
+    // Detecting the same slashing again does not duplicate it.
+    slasher.accept_attestation(att2);
+    slasher.process_queued(current_epoch).unwrap();
+    assert_eq!(slasher.get_detected_attester_slashings().len(), 1);
+}
+
+#[test]
+#[cfg(feature = "lmdb")]
+fn detected_slashings_are_persisted_and_pruned() {
+    let tempdir = tempdir().unwrap();
+    let mut config = Config::new(tempdir.path().into());
+    config.backend = DatabaseBackend::Lmdb;
+    config.chunk_size = 4;
+    config.history_length = 16;
+    let current_epoch = Epoch::new(4);
+
+    let att1 = indexed_att([0], 1, 2, 0);
+    let att2 = indexed_att([0], 1, 2, 1);
+    let (attester_slashings, proposer_slashings) = {
+        let slasher = Slasher::<E>::open(config.clone(), test_logger()).unwrap();
+        slasher.accept_attestation(att1.clone());
+        slasher.accept_attestation(att2.clone());
+        slasher.accept_block_header(block(1, 0, 0));
+        slasher.accept_block_header(block(1, 0, 1));
+        slasher.process_queued(current_epoch).unwrap();
+        (
+            slasher.get_detected_attester_slashings(),
+            slasher.get_detected_proposer_slashings(),
+        )
+    };
+    assert_eq!(attester_slashings.len(), 1);
+    assert_eq!(proposer_slashings.len(), 1);
+
+    // The detected slashings and the times they were detected survive a restart.
+    let slasher = Slasher::<E>::open(config, test_logger()).unwrap();
+    assert_eq!(slasher.get_detected_attester_slashings(), attester_slashings);
+    assert_eq!(slasher.get_detected_proposer_slashings(), proposer_slashings);
+
+    // Slashings are pruned once they fall outside the history.
+    slasher.prune_database(Epoch::new(17)).unwrap();
+    assert_eq!(slasher.get_detected_attester_slashings(), attester_slashings);
+    slasher.prune_database(Epoch::new(18)).unwrap();
+    assert!(slasher.get_detected_attester_slashings().is_empty());
+    assert!(slasher.get_detected_proposer_slashings().is_empty());
+}
+
+#[test]
+fn target_arrays() {
+    let tempdir = tempdir().unwrap();
+    let mut config = Config::new(tempdir.path().into());
+    config.chunk_size = 4;
+    config.history_length = 16;
+    let slasher = Slasher::<E>::open(config, test_logger()).unwrap();
+    let current_epoch = Epoch::new(10);
+
+    // Nothing is known about the validator.
+    let targets = slasher.get_targets_for_validator(0, current_epoch).unwrap();
+    assert_eq!(targets.len(), 11);
+    assert!(targets
+        .iter()
+        .all(|targets| targets.min_target.is_none() && targets.max_target.is_none()));
+
+    slasher.accept_attestation(indexed_att([0], 3, 5, 0));
+    slasher.process_queued(current_epoch).unwrap();
+
+    let targets = slasher.get_targets_for_validator(0, current_epoch).unwrap();
+    assert_eq!(
+        targets[2],
+        MinMaxTargets {
+            epoch: Epoch::new(2),
+            min_target: Some(Epoch::new(5)),
+            max_target: None,
+        }
+    );
+    assert_eq!(
+        targets[4],
+        MinMaxTargets {
+            epoch: Epoch::new(4),
+            min_target: None,
+            max_target: Some(Epoch::new(5)),
+        }
+    );
+}
+
+#[test]
+fn check_attestation() {
+    let tempdir = tempdir().unwrap();
+    let config = Config::new(tempdir.path().into());
+    let slasher = Slasher::<E>::open(config, test_logger()).unwrap();
+    let current_epoch = Epoch::new(10);
+
+    let existing = indexed_att([0, 1, 2], 3, 5, 0);
+    slasher.accept_attestation(existing.clone());
+    slasher.process_queued(current_epoch).unwrap();
+
+    // The same attestation is not slashable.
+    assert!(slasher
+        .check_attestation(existing.clone(), current_epoch)
+        .unwrap()
+        .is_empty());
+
+    // Double vote.
+    let double_vote = indexed_att([2], 3, 5, 1);
+    assert_eq!(
+        slasher
+            .check_attestation(double_vote.clone(), current_epoch)
+            .unwrap(),
+        hashset![att_slashing(&existing, &double_vote)]
+    );
+
+    // Surrounding and surrounded votes.
+    let surrounding = indexed_att([1], 2, 6, 0);
+    assert_eq!(
+        slasher
+            .check_attestation(surrounding.clone(), current_epoch)
+            .unwrap(),
+        hashset![att_slashing(&surrounding, &existing)]
+    );
+    let surrounded = indexed_att([0, 3], 4, 4, 0);
+    assert_eq!(
+        slasher
+            .check_attestation(surrounded.clone(), current_epoch)
+            .unwrap(),
+        hashset![att_slashing(&existing, &surrounded)]
+    );
+
+    // Attestations by other validators are not slashable.
+    assert!(slasher
+        .check_attestation(indexed_att([3], 2, 6, 0), current_epoch)
+        .unwrap()
+        .is_empty());
+
+    // Attestations from the future can't be checked.
+    assert!(matches!(
+        slasher.check_attestation(indexed_att([0], 10, 11, 0), current_epoch),
+        Err(Error::AttestationOutsideHistory { .. })
+    ));
+
+    // Checking attestations does not store them or report slashings.
+    assert!(slasher.get_attester_slashings().is_empty());
+    assert!(slasher.get_detected_attester_slashings().is_empty());
+    slasher.accept_attestation(double_vote.clone());
+    slasher.process_queued(current_epoch).unwrap();
+    assert_eq!(
+        slasher.get_attester_slashings(),
+        hashset![att_slashing(&existing, &double_vote)]
+    );
+}
@@ -0,0 +1,130 @@
+//! A local stand-in for a remote signer which serves the Lighthouse signing channel protocol.
+//!
+//! The server holds its keys in memory and signs the `signingRoot` of each request, which allows
+//! the channel signing method to be tested without a Web3Signer process.
+use eth2_keystore::KeystoreBuilder;
+use futures::{SinkExt, StreamExt};
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use types::{Hash256, Keypair, PublicKeyBytes};
+use url::Url;
+use validator_client::{ChannelRequest, ChannelResponse};
+
+/// A testing rig which holds a running stand-in channel signer.
+pub struct ChannelSignerRig {
+    pub keypairs: Vec<Keypair>,
+    _keystore_dir: TempDir,
+    /// A keystore for the first of `keypairs`, for comparison with local signing.
+    pub keystore_path: PathBuf,
+    pub url: Url,
+    /// The number of request frames the server has received.
+    requests_received: Arc<AtomicUsize>,
+    server: JoinHandle<()>,
+}
+
+impl Drop for ChannelSignerRig {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+impl ChannelSignerRig {
+    pub async fn new(keypairs: Vec<Keypair>, keystore_password: &str) -> Self {
+        let keystore_dir = TempDir::new().unwrap();
+        let keystore = KeystoreBuilder::new(&keypairs[0], keystore_password.as_bytes(), "".into())
+            .unwrap()
+            .build()
+            .unwrap();
+        let keystore_path = keystore_dir.path().join("keystore.json");
+        keystore
+            .to_json_writer(File::create(&keystore_path).unwrap())
+            .unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+
+        let requests_received = Arc::new(AtomicUsize::new(0));
+        let server = tokio::spawn(serve(
+            listener,
+            Arc::new(keypairs.clone()),
+            requests_received.clone(),
+        ));
+
+        Self {
+            keypairs,
+            _keystore_dir: keystore_dir,
+            keystore_path,
+            url,
+            requests_received,
+            server,
+        }
+    }
+
+    pub fn requests_received(&self) -> usize {
+        self.requests_received.load(Ordering::Relaxed)
+    }
+}
+
+async fn serve(
+    listener: TcpListener,
+    keypairs: Arc<Vec<Keypair>>,
+    requests_received: Arc<AtomicUsize>,
+) {
+    while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(serve_connection(
+            stream,
+            keypairs.clone(),
+            requests_received.clone(),
+        ));
+    }
+}
+
+async fn serve_connection(
+    stream: TcpStream,
+    keypairs: Arc<Vec<Keypair>>,
+    requests_received: Arc<AtomicUsize>,
+) {
+    let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+    while let Some(Ok(message)) = socket.next().await {
+        let Message::Text(text) = message else {
+            continue;
+        };
+        requests_received.fetch_add(1, Ordering::Relaxed);
+
+        let request: ChannelRequest = serde_json::from_str(&text).unwrap();
+        let response =
+            match serde_json::from_value::<Hash256>(request.request["signingRoot"].clone()) {
+                Ok(signing_root) => ChannelResponse {
+                    id: request.id,
+                    signatures: request
+                        .pubkeys
+                        .iter()
+                        .map(|pubkey| {
+                            keypairs
+                                .iter()
+                                .find(|keypair| PublicKeyBytes::from(&keypair.pk) == *pubkey)
+                                .map(|keypair| keypair.sk.sign(signing_root))
+                        })
+                        .collect(),
+                    error: None,
+                },
+                Err(e) => ChannelResponse {
+                    id: request.id,
+                    signatures: vec![],
+                    error: Some(format!("invalid signing root: {e}")),
+                },
+            };
+
+        let text = serde_json::to_string(&response).unwrap();
+        if socket.send(Message::Text(text)).await.is_err() {
+            break;
+        }
+    }
+}
@@ -13,9 +13,11 @@
 //! it available via the `TEMP_DIR`.
 #![cfg(all(test, unix, not(debug_assertions)))]
 
+mod channel_signer;
 mod get_web3signer;
 
 mod tests {
+    use crate::channel_signer::ChannelSignerRig;
     use crate::get_web3signer::download_binary;
     use account_utils::validator_definitions::{
         SigningDefinition, ValidatorDefinition, ValidatorDefinitions, Web3SignerDefinition,
@@ -390,7 +392,8 @@ mod tests {
     /// another `ValidatorStore` using a local keystore and ensure that both `ValidatorStore`s
     /// behave identically.
     struct TestingRig {
-        _signer_rig: Web3SignerRig,
+        _signer_rig: Option<Web3SignerRig>,
+        _channel_signer_rig: Option<ChannelSignerRig>,
         validator_rigs: Vec<ValidatorStoreRig>,
         validator_pubkey: PublicKeyBytes,
     }
@@ -429,6 +432,7 @@ mod tests {
                         voting_keystore_path: signer_rig.keystore_path.clone(),
                         voting_keystore_password_path: None,
                         voting_keystore_password: Some(KEYSTORE_PASSWORD.to_string().into()),
+                        distributed: None,
                     },
                 };
                 ValidatorStoreRig::new(
@@ -457,6 +461,7 @@ mod tests {
                         request_timeout_ms: None,
                         client_identity_path: Some(client_identity_path()),
                         client_identity_password: Some(client_identity_password()),
+                        channel_url: None,
                     }),
                 };
                 ValidatorStoreRig::new(
@@ -469,12 +474,66 @@ mod tests {
             };
 
             Self {
-                _signer_rig: signer_rig,
+                _signer_rig: Some(signer_rig),
+                _channel_signer_rig: None,
                 validator_rigs: vec![local_signer_validator_store, remote_signer_validator_store],
                 validator_pubkey: PublicKeyBytes::from(&validator_pubkey),
             }
         }
 
+        /// As for `Self::new`, but with a local stand-in signer which is accessed via a signing
+        /// channel rather than a Web3Signer process.
+        pub async fn new_with_channel_signer(spec: ChainSpec) -> Self {
+            let signer_rig =
+                ChannelSignerRig::new(vec![testing_keypair()], KEYSTORE_PASSWORD).await;
+            let validator_pubkey = signer_rig.keypairs[0].pk.clone();
+
+            let local_signer_validator_store = {
+                let validator_definition = ValidatorDefinition {
+                    enabled: true,
+                    voting_public_key: validator_pubkey.clone(),
+                    graffiti: None,
+                    suggested_fee_recipient: None,
+                    gas_limit: None,
+                    builder_proposals: None,
+                    builder_boost_factor: None,
+                    prefer_builder_proposals: None,
+                    description: String::default(),
+                    signing_definition: SigningDefinition::LocalKeystore {
+                        voting_keystore_path: signer_rig.keystore_path.clone(),
+                        voting_keystore_password_path: None,
+                        voting_keystore_password: Some(KEYSTORE_PASSWORD.to_string().into()),
+                        distributed: None,
+                    },
+                };
+                ValidatorStoreRig::new(
+                    vec![validator_definition],
+                    SlashingProtectionConfig::default(),
+                    false,
+                    spec.clone(),
+                )
+                .await
+            };
+
+            let channel_signer_validator_store = ValidatorStoreRig::new(
+                vec![channel_validator_definition(
+                    &signer_rig,
+                    validator_pubkey.clone(),
+                )],
+                SlashingProtectionConfig::default(),
+                true,
+                spec,
+            )
+            .await;
+
+            Self {
+                _signer_rig: None,
+                _channel_signer_rig: Some(signer_rig),
+                validator_rigs: vec![local_signer_validator_store, channel_signer_validator_store],
+                validator_pubkey: PublicKeyBytes::from(&validator_pubkey),
+            }
+        }
+
         /// Run the `generate_sig` function across all validator stores on `self` and assert that
         /// they all return the same value.
         pub async fn assert_signatures_match<F, R, S>(
@@ -540,6 +599,32 @@ mod tests {
         }
     }
 
+    /// A definition for a validator which signs via the channel of `signer_rig`.
+    fn channel_validator_definition(
+        signer_rig: &ChannelSignerRig,
+        voting_public_key: PublicKey,
+    ) -> ValidatorDefinition {
+        ValidatorDefinition {
+            enabled: true,
+            voting_public_key,
+            graffiti: None,
+            suggested_fee_recipient: None,
+            gas_limit: None,
+            builder_proposals: None,
+            builder_boost_factor: None,
+            prefer_builder_proposals: None,
+            description: String::default(),
+            signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
+                url: signer_rig.url.to_string(),
+                root_certificate_path: None,
+                request_timeout_ms: None,
+                client_identity_path: None,
+                client_identity_password: None,
+                channel_url: Some(signer_rig.url.to_string()),
+            }),
+        }
+    }
+
     /// Get a generic, arbitrary attestation for signing.
     fn get_attestation() -> Attestation<E> {
         Attestation {
@@ -576,14 +661,19 @@ mod tests {
         let network_config = Eth2NetworkConfig::constant(network).unwrap().unwrap();
         let spec = &network_config.chain_spec::<E>().unwrap();
 
-        TestingRig::new(
+        let rig = TestingRig::new(
             network,
             SlashingProtectionConfig::default(),
             spec.clone(),
             listen_port,
         )
-        .await
-        .assert_signatures_match("randao_reveal", |pubkey, validator_store| async move {
+        .await;
+        assert_base_types(rig, spec).await;
+    }
+
+    /// Assert that all the "base" (phase 0) types are signed identically by the rigs of `rig`.
+    async fn assert_base_types(rig: TestingRig, spec: &ChainSpec) -> TestingRig {
+        rig.assert_signatures_match("randao_reveal", |pubkey, validator_store| async move {
             validator_store
                 .randao_reveal(pubkey, Epoch::new(0))
                 .await
@@ -638,7 +728,7 @@ mod tests {
                     .unwrap()
             },
         )
-        .await;
+        .await
     }
 
     /// Test all the Altair types.
@@ -927,4 +1017,71 @@ mod tests {
     async fn slashing_protection_enabled_locally() {
         test_lighthouse_slashing_protection(SlashingProtectionConfig { local: true }, 4254).await
     }
+
+    #[tokio::test]
+    async fn channel_signer_base_types() {
+        let network_config = Eth2NetworkConfig::constant("mainnet").unwrap().unwrap();
+        let spec = &network_config.chain_spec::<E>().unwrap();
+
+        let rig = TestingRig::new_with_channel_signer(spec.clone()).await;
+        assert_base_types(rig, spec).await;
+    }
+
+    /// Check that attestations with the same data are signed by many keys in a single request.
+    #[tokio::test]
+    async fn channel_signer_batches_attestations() {
+        let network_config = Eth2NetworkConfig::constant("mainnet").unwrap().unwrap();
+        let spec = network_config.chain_spec::<E>().unwrap();
+        let num_validators = 8;
+
+        let keypairs = (0..num_validators)
+            .map(|_| Keypair::random())
+            .collect::<Vec<_>>();
+        let signer_rig = ChannelSignerRig::new(keypairs.clone(), KEYSTORE_PASSWORD).await;
+        let validator_definitions = keypairs
+            .iter()
+            .map(|keypair| channel_validator_definition(&signer_rig, keypair.pk.clone()))
+            .collect();
+        let validator_rig = ValidatorStoreRig::new(
+            validator_definitions,
+            SlashingProtectionConfig::default(),
+            true,
+            spec.clone(),
+        )
+        .await;
+        let validator_store = validator_rig.validator_store.clone();
+
+        let attestations = futures::future::join_all(keypairs.iter().map(|keypair| {
+            let validator_store = validator_store.clone();
+            async move {
+                let mut attestation = get_attestation();
+                validator_store
+                    .sign_attestation(keypair.pk.compress(), 0, &mut attestation, Epoch::new(0))
+                    .await
+                    .unwrap();
+                attestation
+            }
+        }))
+        .await;
+
+        let domain = spec.get_domain(
+            Epoch::new(0),
+            Domain::BeaconAttester,
+            &spec.fork_at_epoch(Epoch::new(0)),
+            Hash256::repeat_byte(42),
+        );
+        for (keypair, attestation) in keypairs.iter().zip(&attestations) {
+            let signing_root = attestation.data.signing_root(domain);
+            assert!(attestation
+                .signature
+                .fast_aggregate_verify(signing_root, &[&keypair.pk]));
+        }
+        assert!(
+            signer_rig.requests_received() < num_validators,
+            "{} requests for {num_validators} signatures",
+            signer_rig.requests_received()
+        );
+
+        validator_rig.shutdown();
+    }
 }
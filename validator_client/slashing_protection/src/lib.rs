@@ -5,11 +5,14 @@ pub mod interchange;
 pub mod interchange_test;
 mod parallel_tests;
 mod registration_tests;
+pub mod replication;
+mod replication_tests;
 mod signed_attestation;
 mod signed_block;
 mod slashing_database;
 pub mod test_utils;
 
+pub use crate::replication::ReplicationRecord;
 pub use crate::signed_attestation::{InvalidAttestation, SignedAttestation};
 pub use crate::signed_block::{InvalidBlock, SignedBlock};
 pub use crate::slashing_database::{
@@ -38,6 +41,8 @@ pub enum NotSafe {
     SQLError(String),
     SQLPoolError(String),
     ConsistencyError,
+    /// The record could not be committed by all replicas of the database.
+    ReplicationFailed(String),
 }
 
 /// The attestation or block is safe to sign, and will not cause the signer to be slashed.
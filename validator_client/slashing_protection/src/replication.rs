@@ -0,0 +1,64 @@
+//! Replication of slashing protection records between validator clients.
+//!
+//! Once a new record has been committed to a slashing database, the validator client sends it to
+//! every replica of the database and only signs the message if every replica has durably committed
+//! the record too. Replicas apply records with `SlashingDatabase::apply_replicated_record`, which
+//! performs the usual slashing checks, so a replica refuses any record which conflicts with
+//! messages it has signed itself.
+//!
+//! The record is committed locally before it is replicated, so that the database isn't locked
+//! whilst waiting for the replicas. If replication fails the local record is kept, which prevents
+//! a conflicting message from being signed later. Two validator clients racing to sign conflicting
+//! messages each commit their own record first, so both refuse the other's record and neither
+//! signs.
+use crate::SigningRoot;
+use serde::{Deserialize, Serialize};
+use types::{Epoch, Hash256, PublicKeyBytes, Slot};
+
+/// A newly signed message, as replicated between slashing protection databases.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplicationRecord {
+    Block {
+        pubkey: PublicKeyBytes,
+        slot: Slot,
+        signing_root: Hash256,
+    },
+    Attestation {
+        pubkey: PublicKeyBytes,
+        source_epoch: Epoch,
+        target_epoch: Epoch,
+        signing_root: Hash256,
+    },
+}
+
+impl ReplicationRecord {
+    pub fn block(pubkey: PublicKeyBytes, slot: Slot, signing_root: SigningRoot) -> Self {
+        ReplicationRecord::Block {
+            pubkey,
+            slot,
+            signing_root: signing_root.to_hash256_raw(),
+        }
+    }
+
+    pub fn attestation(
+        pubkey: PublicKeyBytes,
+        source_epoch: Epoch,
+        target_epoch: Epoch,
+        signing_root: SigningRoot,
+    ) -> Self {
+        ReplicationRecord::Attestation {
+            pubkey,
+            source_epoch,
+            target_epoch,
+            signing_root: signing_root.to_hash256_raw(),
+        }
+    }
+
+    pub fn pubkey(&self) -> &PublicKeyBytes {
+        match self {
+            ReplicationRecord::Block { pubkey, .. }
+            | ReplicationRecord::Attestation { pubkey, .. } => pubkey,
+        }
+    }
+}
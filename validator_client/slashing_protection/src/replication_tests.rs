@@ -0,0 +1,113 @@
+#![cfg(test)]
+
+use crate::test_utils::*;
+use crate::*;
+use tempfile::{tempdir, TempDir};
+use types::{Epoch, Hash256, Slot};
+
+fn create_db(dir: &TempDir, name: &str) -> SlashingDatabase {
+    let db = SlashingDatabase::create(&dir.path().join(name)).unwrap();
+    db.register_validator(pubkey(0)).unwrap();
+    db
+}
+
+#[test]
+fn replicated_records_prevent_conflicts() {
+    let dir = tempdir().unwrap();
+    let replica = create_db(&dir, "replica.sqlite");
+    let pk = pubkey(0);
+
+    let block = ReplicationRecord::block(pk, Slot::new(1), Hash256::repeat_byte(1).into());
+    let attestation = ReplicationRecord::attestation(
+        pk,
+        Epoch::new(0),
+        Epoch::new(1),
+        Hash256::repeat_byte(1).into(),
+    );
+    assert_eq!(replica.apply_replicated_record(&block), Ok(Safe::Valid));
+    assert_eq!(
+        replica.apply_replicated_record(&attestation),
+        Ok(Safe::Valid)
+    );
+
+    // The replica refuses to sign conflicting messages.
+    assert!(matches!(
+        replica.check_and_insert_block_signing_root(
+            &pk,
+            Slot::new(1),
+            Hash256::repeat_byte(2).into()
+        ),
+        Err(NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(_)))
+    ));
+    assert!(matches!(
+        replica.check_and_insert_attestation_signing_root(
+            &pk,
+            Epoch::new(0),
+            Epoch::new(1),
+            Hash256::repeat_byte(2).into()
+        ),
+        Err(NotSafe::InvalidAttestation(InvalidAttestation::DoubleVote(
+            _
+        )))
+    ));
+
+    // Repeating a record is harmless.
+    assert_eq!(replica.apply_replicated_record(&block), Ok(Safe::SameData));
+}
+
+#[test]
+fn conflicting_records_refused() {
+    let dir = tempdir().unwrap();
+    let primary = create_db(&dir, "primary.sqlite");
+    let replica = create_db(&dir, "replica.sqlite");
+    let pk = pubkey(0);
+
+    // Both databases commit a record for conflicting messages before replicating it, so each
+    // refuses the record of the other and neither message is signed.
+    let slot = Slot::new(1);
+    for (db, byte) in [(&primary, 1), (&replica, 2)] {
+        assert_eq!(
+            db.check_and_insert_block_signing_root(&pk, slot, Hash256::repeat_byte(byte).into()),
+            Ok(Safe::Valid)
+        );
+    }
+    for (db, byte) in [(&replica, 1), (&primary, 2)] {
+        let record = ReplicationRecord::block(pk, slot, Hash256::repeat_byte(byte).into());
+        assert!(matches!(
+            db.apply_replicated_record(&record),
+            Err(NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(_)))
+        ));
+    }
+}
+
+#[test]
+fn replicated_record_for_disabled_validator() {
+    let dir = tempdir().unwrap();
+    let replica = create_db(&dir, "replica.sqlite");
+    let pk = pubkey(0);
+    replica
+        .with_transaction(|txn| {
+            let validator_id = replica.get_validator_id_ignoring_status(txn, &pk)?;
+            replica.update_validator_status(txn, validator_id, false)
+        })
+        .unwrap();
+
+    let record = ReplicationRecord::block(pk, Slot::new(1), Hash256::repeat_byte(1).into());
+    assert_eq!(replica.apply_replicated_record(&record), Ok(Safe::Valid));
+    assert_eq!(replica.apply_replicated_record(&record), Ok(Safe::SameData));
+
+    // The validator remains disabled.
+    assert_eq!(
+        replica.get_validator_id(&pk),
+        Err(NotSafe::DisabledValidator(pk))
+    );
+
+    // Records for unknown validators are accepted.
+    let record = ReplicationRecord::attestation(
+        pubkey(1),
+        Epoch::new(0),
+        Epoch::new(1),
+        Hash256::repeat_byte(1).into(),
+    );
+    assert_eq!(replica.apply_replicated_record(&record), Ok(Safe::Valid));
+}
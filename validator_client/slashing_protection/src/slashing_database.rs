@@ -2,6 +2,7 @@ use crate::interchange::{
     Interchange, InterchangeData, InterchangeMetadata, SignedAttestation as InterchangeAttestation,
     SignedBlock as InterchangeBlock,
 };
+use crate::replication::ReplicationRecord;
 use crate::signed_attestation::InvalidAttestation;
 use crate::signed_block::InvalidBlock;
 use crate::{signing_root_from_row, NotSafe, Safe, SignedAttestation, SignedBlock, SigningRoot};
@@ -670,6 +671,54 @@ impl SlashingDatabase {
         Ok(safe)
     }
 
+    /// Check and insert a record replicated from another validator client's database.
+    ///
+    /// The record is checked for slash safety in the same way as a message signed by this
+    /// validator client, and is not replicated any further. Unknown validators are registered, and
+    /// records are also accepted for disabled validators without enabling them.
+    pub fn apply_replicated_record(&self, record: &ReplicationRecord) -> Result<Safe, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+
+        let pubkey = record.pubkey();
+        let disabled_validator_id = match self.get_validator_id_with_status(&txn, pubkey)? {
+            Some((validator_id, false)) => Some(validator_id),
+            _ => None,
+        };
+        self.register_validators_in_txn(std::iter::once(pubkey), &txn)?;
+
+        let safe = match *record {
+            ReplicationRecord::Block {
+                pubkey,
+                slot,
+                signing_root,
+            } => self.check_and_insert_block_signing_root_txn(
+                &pubkey,
+                slot,
+                signing_root.into(),
+                &txn,
+            )?,
+            ReplicationRecord::Attestation {
+                pubkey,
+                source_epoch,
+                target_epoch,
+                signing_root,
+            } => self.check_and_insert_attestation_signing_root_txn(
+                &pubkey,
+                source_epoch,
+                target_epoch,
+                signing_root.into(),
+                &txn,
+            )?,
+        };
+
+        if let Some(validator_id) = disabled_validator_id {
+            self.update_validator_status(&txn, validator_id, false)?;
+        }
+        txn.commit()?;
+        Ok(safe)
+    }
+
     /// Import slashing protection from another client in the interchange format.
     ///
     /// This function will atomically import the entire interchange, failing if *any*
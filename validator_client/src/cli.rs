@@ -384,6 +384,27 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        ENABLING WEB3SIGNER'S SLASHING PROTECTION.")
                 .takes_value(false)
         )
+        .arg(
+            Arg::with_name("slashing-protection-replicas")
+                .long("slashing-protection-replicas")
+                .value_name("NETWORK_ADDRESSES")
+                .help("Comma-separated addresses to the HTTP APIs of other validator clients, to \
+                       which every new slashing protection record is replicated before signing. \
+                       A message is only signed once every replica has committed its record, \
+                       and is refused if any replica is unavailable or has signed a conflicting \
+                       message. Requires --slashing-protection-replica-peer-tokens.")
+                .takes_value(true)
+                .requires("slashing-protection-replica-peer-tokens")
+        )
+        .arg(
+            Arg::with_name("slashing-protection-replica-peer-tokens")
+                .long("slashing-protection-replica-peer-tokens")
+                .value_name("PEER_TOKEN_FILES")
+                .help("Comma-separated paths to the peer tokens (peer-api-token.txt) of the \
+                       validator clients in --slashing-protection-replicas, in the same order.")
+                .takes_value(true)
+                .requires("slashing-protection-replicas")
+        )
         /*
          * Experimental/development options.
          */
@@ -39,6 +39,10 @@ pub struct Config {
     pub disable_auto_discover: bool,
     /// If true, re-register existing validators in definitions.yml for slashing protection.
     pub init_slashing_protection: bool,
+    /// The HTTP APIs of validator clients to replicate slashing protection records to.
+    pub slashing_protection_replicas: Vec<SensitiveUrl>,
+    /// Paths to the peer tokens of `slashing_protection_replicas`, in the same order.
+    pub slashing_protection_replica_peer_tokens: Vec<PathBuf>,
     /// If true, use longer timeouts for requests made to the beacon node.
     pub use_long_timeouts: bool,
     /// Graffiti to be inserted everytime we create a block.
@@ -112,6 +116,8 @@ impl Default for Config {
             allow_unsynced_beacon_node: false,
             disable_auto_discover: false,
             init_slashing_protection: false,
+            slashing_protection_replicas: vec![],
+            slashing_protection_replica_peer_tokens: vec![],
             use_long_timeouts: false,
             graffiti: None,
             graffiti_file: None,
@@ -196,6 +202,30 @@ impl Config {
 
         config.disable_auto_discover = cli_args.is_present("disable-auto-discover");
         config.init_slashing_protection = cli_args.is_present("init-slashing-protection");
+
+        if let Some(replicas) = parse_optional::<String>(cli_args, "slashing-protection-replicas")?
+        {
+            config.slashing_protection_replicas = replicas
+                .split(',')
+                .map(SensitiveUrl::parse)
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("Unable to parse slashing protection replica URL: {:?}", e))?;
+        }
+        if let Some(tokens) =
+            parse_optional::<String>(cli_args, "slashing-protection-replica-peer-tokens")?
+        {
+            config.slashing_protection_replica_peer_tokens =
+                tokens.split(',').map(PathBuf::from).collect();
+        }
+        if config.slashing_protection_replicas.len()
+            != config.slashing_protection_replica_peer_tokens.len()
+        {
+            return Err(
+                "--slashing-protection-replica-peer-tokens must contain one peer token path for each \
+                 of --slashing-protection-replicas"
+                    .to_string(),
+            );
+        }
         config.use_long_timeouts = cli_args.is_present("use-long-timeouts");
 
         if let Some(graffiti_file_path) = cli_args.value_of("graffiti-file") {
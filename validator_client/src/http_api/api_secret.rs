@@ -1,7 +1,7 @@
 use eth2::lighthouse_vc::{PK_LEN, SECRET_PREFIX as PK_PREFIX};
 use filesystem::create_with_600_perms;
 use libsecp256k1::{Message, PublicKey, SecretKey};
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 use ring::digest::{digest, SHA256};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -23,6 +23,19 @@ pub const SK_LEN: usize = 32;
 /// value in a public forum.
 pub const PK_FILENAME: &str = "api-token.txt";
 
+/// The name of the file which stores the peer token.
+///
+/// The peer token is shared with other validator clients, such as the operators of the other key
+/// shares of a distributed validator. Unlike the API token, it only grants access to the routes
+/// used between validator clients.
+pub const PEER_TOKEN_FILENAME: &str = "peer-api-token.txt";
+
+/// The prefix of the peer token, which distinguishes it from an API token.
+pub const PEER_TOKEN_PREFIX: &str = "peer-token-";
+
+/// Length of the random part of the peer token, in bytes.
+pub const PEER_TOKEN_LEN: usize = 32;
+
 /// Contains a `secp256k1` keypair that is saved-to/loaded-from disk on instantiation. The keypair
 /// is used for authorization/authentication for requests/responses on the HTTP API.
 ///
@@ -35,10 +48,14 @@ pub const PK_FILENAME: &str = "api-token.txt";
 ///  The aforementioned scheme was first defined here:
 ///
 ///  https://github.com/sigp/lighthouse/issues/1269#issuecomment-649879855
+///
+/// A separate peer token is also saved-to/loaded-from disk, see `PEER_TOKEN_FILENAME`.
 pub struct ApiSecret {
     pk: PublicKey,
     sk: SecretKey,
     pk_path: PathBuf,
+    peer_token: String,
+    peer_token_path: PathBuf,
 }
 
 impl ApiSecret {
@@ -48,7 +65,8 @@ impl ApiSecret {
     /// The provided `dir` is a directory containing two files, `SK_FILENAME` and `PK_FILENAME`.
     ///
     /// If either the secret or public key files are missing on disk, create a new keypair and
-    /// write it to disk (over-writing any existing files).
+    /// write it to disk (over-writing any existing files). The peer token in `PEER_TOKEN_FILENAME`
+    /// is created independently if it is missing.
     pub fn create_or_open<P: AsRef<Path>>(dir: P) -> Result<Self, String> {
         let sk_path = dir.as_ref().join(SK_FILENAME);
         let pk_path = dir.as_ref().join(PK_FILENAME);
@@ -148,7 +166,43 @@ impl ApiSecret {
             ));
         }
 
-        Ok(Self { pk, sk, pk_path })
+        let peer_token_path = dir.as_ref().join(PEER_TOKEN_FILENAME);
+        if !peer_token_path.exists() {
+            let bytes: [u8; PEER_TOKEN_LEN] = thread_rng().gen();
+            create_with_600_perms(
+                &peer_token_path,
+                format!("{}{}", PEER_TOKEN_PREFIX, serde_utils::hex::encode(bytes)).as_bytes(),
+            )
+            .map_err(|e| {
+                format!(
+                    "Unable to create file with permissions for {:?}: {:?}",
+                    peer_token_path, e
+                )
+            })?;
+        }
+
+        let peer_token = fs::read(&peer_token_path)
+            .map_err(|e| format!("cannot read {}: {}", PEER_TOKEN_FILENAME, e))
+            .and_then(|bytes| {
+                String::from_utf8(bytes).map_err(|_| format!("{} is not utf8", PEER_TOKEN_FILENAME))
+            })
+            .and_then(|token| {
+                let token = token.trim_end();
+                match token.strip_prefix(PEER_TOKEN_PREFIX) {
+                    Some(stripped) if serde_utils::hex::decode(stripped).is_ok() => {
+                        Ok(token.to_string())
+                    }
+                    _ => Err(format!("unable to parse {}", PEER_TOKEN_FILENAME)),
+                }
+            })?;
+
+        Ok(Self {
+            pk,
+            sk,
+            pk_path,
+            peer_token,
+            peer_token_path,
+        })
     }
 
     /// Returns the public key of `self` as a 0x-prefixed hex string.
@@ -166,6 +220,16 @@ impl ApiSecret {
         self.pk_path.clone()
     }
 
+    /// Returns the peer token.
+    pub fn peer_token(&self) -> String {
+        self.peer_token.clone()
+    }
+
+    /// Returns the path for the peer token file.
+    pub fn peer_token_path(&self) -> PathBuf {
+        self.peer_token_path.clone()
+    }
+
     /// Returns the values of the `Authorization` header which indicate a valid incoming HTTP
     /// request.
     ///
@@ -182,7 +246,20 @@ impl ApiSecret {
     /// Returns a `warp` header which filters out request that have a missing or inaccurate
     /// `Authorization` header.
     pub fn authorization_header_filter(&self) -> warp::filters::BoxedFilter<()> {
-        let expected = self.auth_header_values();
+        Self::header_filter(self.auth_header_values())
+    }
+
+    /// Returns a `warp` header which filters out requests that have neither the peer token nor the
+    /// API token in their `Authorization` header.
+    ///
+    /// This filter must only be applied to the routes used between validator clients.
+    pub fn peer_authorization_header_filter(&self) -> warp::filters::BoxedFilter<()> {
+        let mut expected = self.auth_header_values();
+        expected.push(format!("Bearer {}", self.peer_token));
+        Self::header_filter(expected)
+    }
+
+    fn header_filter(expected: Vec<String>) -> warp::filters::BoxedFilter<()> {
         warp::any()
             .map(move || expected.clone())
             .and(warp::filters::header::header("Authorization"))
@@ -43,8 +43,14 @@ pub fn list<T: SlotClock + 'static, E: EthSpec>(
                     SigningMethod::LocalKeystore {
                         ref voting_keystore,
                         ..
+                    }
+                    | SigningMethod::DistributedKeystore {
+                        ref voting_keystore,
+                        ..
                     } => (voting_keystore.path(), Some(false)),
-                    SigningMethod::Web3Signer { .. } => (None, Some(true)),
+                    SigningMethod::Web3Signer { .. } | SigningMethod::Web3SignerChannel { .. } => {
+                        (None, Some(true))
+                    }
                 });
 
             SingleKeystoreResponse {
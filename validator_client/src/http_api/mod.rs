@@ -11,6 +11,7 @@ pub mod test_utils;
 use crate::http_api::graffiti::{delete_graffiti, get_graffiti, set_graffiti};
 
 use crate::http_api::create_signed_voluntary_exit::create_signed_voluntary_exit;
+use crate::signing_method::SigningMethod;
 use crate::{determine_graffiti, GraffitiFile, ValidatorStore};
 use account_utils::{
     mnemonic_from_phrase,
@@ -162,6 +163,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
     }
 
     let authorization_header_filter = ctx.api_secret.authorization_header_filter();
+    let peer_authorization_header_filter = ctx.api_secret.peer_authorization_header_filter();
     let mut api_token_path = ctx.api_secret.api_token_path();
 
     // Attempt to convert the path to an absolute path, but don't error if it fails.
@@ -640,6 +642,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                         client_identity_path: web3signer.client_identity_path,
                                         client_identity_password: web3signer
                                             .client_identity_password,
+                                        channel_url: None,
                                     },
                                 ),
                             })
@@ -658,6 +661,93 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // POST lighthouse/distributed/partial_signatures
+    let post_distributed_partial_signatures = warp::path("lighthouse")
+        .and(warp::path("distributed"))
+        .and(warp::path("partial_signatures"))
+        .and(warp::path::end())
+        // Handle errors within the route, so that they aren't masked by the rejection from the
+        // routes which require the API token.
+        .and(
+            warp::body::json()
+                .and(validator_store_filter.clone())
+                .and(signer.clone())
+                .and_then(
+                    |body: api_types::PartialSignatureRequest,
+                     validator_store: Arc<ValidatorStore<T, E>>,
+                     signer| {
+                        blocking_signed_json_task(signer, move || {
+                            let signing_method = validator_store
+                                .initialized_validators()
+                                .read()
+                                .signing_method(&body.voting_pubkey)
+                                .ok_or_else(|| {
+                                    warp_utils::reject::custom_not_found(format!(
+                                        "no validator for {:?}",
+                                        body.voting_pubkey
+                                    ))
+                                })?;
+
+                            match signing_method.as_ref() {
+                                SigningMethod::DistributedKeystore {
+                                    distributed_signer, ..
+                                } => distributed_signer
+                                    .receive_partial_signature(
+                                        body.signing_root,
+                                        body.share_index,
+                                        body.signature,
+                                    )
+                                    .map_err(|e| {
+                                        warp_utils::reject::custom_bad_request(format!(
+                                            "invalid partial signature: {:?}",
+                                            e
+                                        ))
+                                    }),
+                                _ => Err(warp_utils::reject::custom_bad_request(format!(
+                                    "{:?} is not a distributed validator",
+                                    body.voting_pubkey
+                                ))),
+                            }
+                        })
+                    },
+                )
+                .recover(warp_utils::reject::handle_rejection),
+        );
+
+    // POST lighthouse/slashing_protection/replicate
+    let post_slashing_protection_replicate = warp::path("lighthouse")
+        .and(warp::path("slashing_protection"))
+        .and(warp::path("replicate"))
+        .and(warp::path::end())
+        // Handle errors within the route, so that they aren't masked by the rejection from the
+        // routes which require the API token.
+        .and(
+            warp::body::json()
+                .and(validator_store_filter.clone())
+                .and(signer.clone())
+                .and_then(
+                    |body: api_types::ReplicateSlashingProtectionRequest,
+                     validator_store: Arc<ValidatorStore<T, E>>,
+                     signer| {
+                        blocking_signed_json_task(signer, move || {
+                            validator_store
+                                .apply_replicated_slashing_protection(
+                                    body.genesis_validators_root,
+                                    &body.record,
+                                )
+                                .map(|_| ())
+                                .map_err(|e| {
+                                    warp_utils::reject::custom_bad_request(format!(
+                                        "refused slashing protection record: {}",
+                                        e
+                                    ))
+                                })
+                        })
+                    },
+                )
+                .recover(warp_utils::reject::handle_rejection),
+        );
+
     // PATCH lighthouse/validators/{validator_pubkey}
     let patch_validators = warp::path("lighthouse")
         .and(warp::path("validators"))
@@ -1255,7 +1345,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
-    let routes = warp::any()
+    let api_token_routes = warp::any()
         .and(authorization_header_filter)
         // Note: it is critical that the `authorization_header_filter` is applied to all routes.
         // Keeping all the routes inside the following `and` is a reliable way to achieve this.
@@ -1302,7 +1392,14 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(delete_graffiti)
                         .recover(warp_utils::reject::handle_rejection),
                 )),
-        )
+        );
+
+    // The routes used between validator clients are accessible with either the peer token or the
+    // API token, while the peer token grants access to no other route.
+    let routes = warp::post()
+        .and(peer_authorization_header_filter)
+        .and(post_distributed_partial_signatures.or(post_slashing_protection_replicate))
+        .or(api_token_routes)
         // The auth route and logs  are the only routes that are allowed to be accessed without the API token.
         .or(warp::get().and(get_auth.or(get_log_events.boxed())))
         // Maps errors into HTTP responses.
@@ -1323,6 +1420,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         "HTTP API started";
         "listen_address" => listening_socket.to_string(),
         "api_token_file" => ?api_token_path,
+        "peer_token_file" => ?ctx.api_secret.peer_token_path(),
     );
 
     Ok((listening_socket, server))
@@ -134,6 +134,7 @@ fn import_single_remotekey<T: SlotClock + 'static, E: EthSpec>(
             request_timeout_ms: None,
             client_identity_path: None,
             client_identity_password: None,
+            channel_url: None,
         }),
     };
     handle
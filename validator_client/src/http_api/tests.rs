@@ -4,6 +4,7 @@
 mod keystores;
 
 use crate::doppelganger_service::DoppelgangerService;
+use crate::slashing_protection_replication::HttpReplicator;
 use crate::{
     http_api::{ApiSecret, Config as HttpConfig, Context},
     initialized_validators::InitializedValidators,
@@ -45,6 +46,7 @@ struct ApiTester {
     initialized_validators: Arc<RwLock<InitializedValidators>>,
     validator_store: Arc<ValidatorStore<TestingSlotClock, E>>,
     url: SensitiveUrl,
+    peer_token: String,
     slot_clock: TestingSlotClock,
     _validator_dir: TempDir,
     _test_runtime: TestRuntime,
@@ -76,6 +78,7 @@ impl ApiTester {
 
         let api_secret = ApiSecret::create_or_open(validator_dir.path()).unwrap();
         let api_pubkey = api_secret.api_token();
+        let peer_token = api_secret.peer_token();
 
         config.validator_dir = validator_dir.path().into();
         config.secrets_dir = secrets_dir.path().into();
@@ -154,6 +157,7 @@ impl ApiTester {
             initialized_validators,
             validator_store,
             url,
+            peer_token,
             slot_clock,
             _validator_dir: validator_dir,
             _test_runtime: test_runtime,
@@ -167,6 +171,14 @@ impl ApiTester {
         ValidatorClientHttpClient::new(self.url.clone(), invalid_pubkey.clone()).unwrap()
     }
 
+    pub fn peer_token_client(&self) -> ValidatorClientHttpClient {
+        let mut client = ValidatorClientHttpClient::new_unauthenticated(self.url.clone()).unwrap();
+        client
+            .add_auth_token(self.peer_token.clone().into())
+            .unwrap();
+        client
+    }
+
     pub async fn test_with_invalid_auth<F, A, T>(self, func: F) -> Self
     where
         F: Fn(ValidatorClientHttpClient) -> A,
@@ -961,9 +973,59 @@ async fn routes_with_invalid_auth() {
                 .set_graffiti(&PublicKeyBytes::empty(), GraffitiString::default())
                 .await
         })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_distributed_partial_signatures(&PartialSignatureRequest {
+                    voting_pubkey: PublicKeyBytes::empty(),
+                    signing_root: Hash256::zero(),
+                    share_index: 1,
+                    signature: Signature::empty(),
+                })
+                .await
+        })
         .await;
 }
 
+#[tokio::test]
+async fn peer_token_scope() {
+    let tester = ApiTester::new().await;
+    let peer_client = tester.peer_token_client();
+    let request = PartialSignatureRequest {
+        voting_pubkey: PublicKeyBytes::empty(),
+        signing_root: Hash256::zero(),
+        share_index: 1,
+        signature: Signature::empty(),
+    };
+
+    // The peer token and the API token both reach the partial signatures route, which has no
+    // validator for the public key.
+    for client in [&peer_client, &tester.client] {
+        match client
+            .post_lighthouse_distributed_partial_signatures(&request)
+            .await
+        {
+            Err(ApiError::ServerMessage(ApiErrorMessage { code: 404, .. })) => (),
+            other => panic!("expected not found error, got {:?}", other),
+        }
+    }
+
+    // The peer token doesn't grant access to any other route.
+    match peer_client.get_lighthouse_validators().await {
+        Err(ApiError::ServerMessage(ApiErrorMessage { code: 403, .. })) => (),
+        other => panic!("expected authorized error, got {:?}", other),
+    }
+    match peer_client
+        .delete_keystores(&DeleteKeystoresRequest {
+            pubkeys: vec![Keypair::random().pk.compress()],
+        })
+        .await
+    {
+        Err(ApiError::ServerMessage(ApiErrorMessage { code: 403, .. })) => (),
+        other => panic!("expected authorized error, got {:?}", other),
+    }
+}
+
 #[tokio::test]
 async fn simple_getters() {
     ApiTester::new()
@@ -1358,3 +1420,96 @@ async fn web3signer_validator_creation() {
         .assert_enabled_validators_count(1)
         .assert_validators_count(1);
 }
+
+#[tokio::test]
+async fn slashing_protection_replication() {
+    let tester = ApiTester::new().await;
+    let pubkey = PublicKeyBytes::empty();
+    let request = |genesis_validators_root, signing_root| ReplicateSlashingProtectionRequest {
+        genesis_validators_root,
+        record: ReplicationRecord::Attestation {
+            pubkey,
+            source_epoch: Epoch::new(0),
+            target_epoch: Epoch::new(1),
+            signing_root,
+        },
+    };
+
+    // The route is accessible with the peer token. Records are applied to the slashing database,
+    // including for unknown validators.
+    tester
+        .peer_token_client()
+        .post_lighthouse_slashing_protection_replicate(&request(
+            Hash256::repeat_byte(42),
+            Hash256::repeat_byte(1),
+        ))
+        .await
+        .unwrap();
+
+    // A record from another network is refused.
+    tester
+        .peer_token_client()
+        .post_lighthouse_slashing_protection_replicate(&request(
+            Hash256::repeat_byte(1),
+            Hash256::repeat_byte(1),
+        ))
+        .await
+        .unwrap_err();
+
+    // A record which conflicts with a committed record is refused, including with the API token.
+    tester
+        .client
+        .post_lighthouse_slashing_protection_replicate(&request(
+            Hash256::repeat_byte(42),
+            Hash256::repeat_byte(2),
+        ))
+        .await
+        .unwrap_err();
+}
+
+#[tokio::test]
+async fn slashing_protection_replicator() {
+    let replica = ApiTester::new().await;
+    let dir = tempdir().unwrap();
+    let peer_token_path = dir.path().join("peer-api-token.txt");
+    std::fs::write(&peer_token_path, &replica.peer_token).unwrap();
+
+    let genesis_validators_root = Hash256::repeat_byte(42);
+    let record = |signing_root| ReplicationRecord::Block {
+        pubkey: PublicKeyBytes::empty(),
+        slot: Slot::new(1),
+        signing_root,
+    };
+
+    // Records are replicated using the peer token, without blocking the runtime.
+    let replicator = HttpReplicator::new(
+        &[replica.url.clone()],
+        &[peer_token_path.clone()],
+        genesis_validators_root,
+    )
+    .unwrap();
+    replicator
+        .replicate(&record(Hash256::repeat_byte(1)))
+        .await
+        .unwrap();
+
+    // A record which conflicts with a record committed by the replica fails to replicate.
+    replicator
+        .replicate(&record(Hash256::repeat_byte(2)))
+        .await
+        .unwrap_err();
+
+    // Replication fails if any replica is unavailable.
+    let unavailable = SensitiveUrl::parse("http://127.0.0.1:1").unwrap();
+    let replicator = HttpReplicator::new(
+        &[replica.url.clone(), unavailable],
+        &[peer_token_path.clone(), peer_token_path],
+        genesis_validators_root,
+    )
+    .unwrap();
+    let error = replicator
+        .replicate(&record(Hash256::repeat_byte(1)))
+        .await
+        .unwrap_err();
+    assert!(error.contains("127.0.0.1:1"), "{}", error);
+}
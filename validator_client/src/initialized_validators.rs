@@ -6,20 +6,22 @@
 //! The `InitializedValidators` struct in this file serves as the source-of-truth of which
 //! validators are managed by this validator client.
 
-use crate::signing_method::SigningMethod;
+use crate::signing_method::{DistributedPeer, DistributedSigner, SigningMethod, Web3SignerChannel};
 use account_utils::{
     read_password, read_password_from_user, read_password_string,
     validator_definitions::{
-        self, SigningDefinition, ValidatorDefinition, ValidatorDefinitions, Web3SignerDefinition,
-        CONFIG_FILENAME,
+        self, DistributedDefinition, SigningDefinition, ValidatorDefinition, ValidatorDefinitions,
+        Web3SignerDefinition, CONFIG_FILENAME,
     },
     ZeroizeString,
 };
+use eth2::lighthouse_vc::http_client::ValidatorClientHttpClient;
 use eth2_keystore::Keystore;
 use lighthouse_metrics::set_gauge;
 use lockfile::{Lockfile, LockfileError};
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
 use reqwest::{Certificate, Client, Error as ReqwestError, Identity};
+use sensitive_url::SensitiveUrl;
 use slog::{debug, error, info, warn, Logger};
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
@@ -42,6 +44,11 @@ use crate::Config;
 /// that time is outside the synchronous assumptions of Eth2.
 const DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT: Duration = Duration::from_secs(12);
 
+/// Default time to wait for the partial signatures of a distributed validator's peers.
+///
+/// Set to a third of a slot, so that attestations can still be published on time.
+const DEFAULT_DISTRIBUTED_SIGNING_TIMEOUT: Duration = Duration::from_secs(4);
+
 // Use TTY instead of stdin to capture passwords from users.
 const USE_STDIN: bool = false;
 
@@ -110,6 +117,8 @@ pub enum Error {
     InvalidWeb3SignerClientIdentityCertificateFile(io::Error),
     InvalidWeb3SignerClientIdentityCertificate(ReqwestError),
     UnableToBuildWeb3SignerClient(ReqwestError),
+    /// Unable to build the TLS connector for a remote signer channel.
+    UnableToBuildWeb3SignerChannel(String),
     /// Unable to apply an action to a validator.
     InvalidActionOnValidator,
     UnableToReadValidatorPassword(String),
@@ -117,6 +126,16 @@ pub enum Error {
     UnableToSaveKeyCache(key_cache::Error),
     UnableToDecryptKeyCache(key_cache::Error),
     UnableToDeletePasswordFile(PathBuf, io::Error),
+    /// The key shares of a distributed validator are inconsistent with its definition.
+    InvalidDistributedValidator(String),
+    /// The public key combined from the key shares of a distributed validator does not match the
+    /// one in the definition.
+    DistributedPublicKeyMismatch {
+        definition: Box<PublicKey>,
+        combined: Box<PublicKey>,
+    },
+    /// Unable to build the HTTP API client for a peer of a distributed validator.
+    UnableToBuildDistributedPeerClient(String),
 }
 
 impl From<LockfileError> for Error {
@@ -145,12 +164,16 @@ impl InitializedValidator {
             SigningMethod::LocalKeystore {
                 ref voting_keystore_lockfile,
                 ..
+            }
+            | SigningMethod::DistributedKeystore {
+                ref voting_keystore_lockfile,
+                ..
             } => MutexGuard::try_map(voting_keystore_lockfile.lock(), |option_lockfile| {
                 option_lockfile.as_mut()
             })
             .ok(),
             // Web3Signer validators do not have any lockfiles.
-            SigningMethod::Web3Signer { .. } => None,
+            SigningMethod::Web3Signer { .. } | SigningMethod::Web3SignerChannel { .. } => None,
         }
     }
 
@@ -209,6 +232,7 @@ impl InitializedValidator {
         key_cache: &mut KeyCache,
         key_stores: &mut HashMap<PathBuf, Keystore>,
         web3_signer_client_map: &mut Option<HashMap<Web3SignerDefinition, Client>>,
+        web3_signer_channel_map: &mut HashMap<Web3SignerDefinition, Arc<Web3SignerChannel>>,
         config: &Config,
     ) -> Result<Self, Error> {
         if !def.enabled {
@@ -222,6 +246,7 @@ impl InitializedValidator {
                 voting_keystore_path,
                 voting_keystore_password_path,
                 voting_keystore_password,
+                distributed,
             } => {
                 use std::collections::hash_map::Entry::*;
                 let voting_keystore = match key_stores.entry(voting_keystore_path.clone()) {
@@ -274,12 +299,22 @@ impl InitializedValidator {
                     keypair
                 };
 
-                if voting_keypair.pk != def.voting_public_key {
-                    return Err(Error::VotingPublicKeyMismatch {
-                        definition: Box::new(def.voting_public_key),
-                        keystore: Box::new(voting_keypair.pk),
-                    });
-                }
+                // The keystore of a distributed validator holds a share of the voting key, which
+                // is instead checked against the definition once combined with the peers' shares.
+                let distributed_signer = match distributed {
+                    Some(distributed) => Some(build_distributed_signer(
+                        &def.voting_public_key,
+                        &voting_keypair.pk,
+                        distributed,
+                    )?),
+                    None if voting_keypair.pk != def.voting_public_key => {
+                        return Err(Error::VotingPublicKeyMismatch {
+                            definition: Box::new(def.voting_public_key),
+                            keystore: Box::new(voting_keypair.pk),
+                        });
+                    }
+                    None => None,
+                };
 
                 // Append a `.lock` suffix to the voting keystore.
                 let lockfile_path = get_lockfile_path(&voting_keystore_path)
@@ -287,11 +322,37 @@ impl InitializedValidator {
 
                 let voting_keystore_lockfile = Mutex::new(Some(Lockfile::new(lockfile_path)?));
 
-                SigningMethod::LocalKeystore {
-                    voting_keystore_path,
-                    voting_keystore_lockfile,
-                    voting_keystore: voting_keystore.clone(),
-                    voting_keypair: Arc::new(voting_keypair),
+                match distributed_signer {
+                    Some(distributed_signer) => SigningMethod::DistributedKeystore {
+                        voting_keystore_path,
+                        voting_keystore_lockfile,
+                        voting_keystore: voting_keystore.clone(),
+                        voting_keypair: Arc::new(voting_keypair),
+                        voting_public_key: def.voting_public_key,
+                        distributed_signer: Arc::new(distributed_signer),
+                    },
+                    None => SigningMethod::LocalKeystore {
+                        voting_keystore_path,
+                        voting_keystore_lockfile,
+                        voting_keystore: voting_keystore.clone(),
+                        voting_keypair: Arc::new(voting_keypair),
+                    },
+                }
+            }
+            SigningDefinition::Web3Signer(web3_signer) if web3_signer.channel_url.is_some() => {
+                // Share a single channel between all validators of the same remote signer.
+                let channel = match web3_signer_channel_map.get(&web3_signer) {
+                    Some(channel) => channel.clone(),
+                    None => {
+                        let channel = Arc::new(build_web3_signer_channel(&web3_signer)?);
+                        web3_signer_channel_map.insert(web3_signer, channel.clone());
+                        channel
+                    }
+                };
+
+                SigningMethod::Web3SignerChannel {
+                    channel,
+                    voting_public_key: def.voting_public_key,
                 }
             }
             SigningDefinition::Web3Signer(web3_signer) => {
@@ -363,6 +424,12 @@ impl InitializedValidator {
             SigningMethod::LocalKeystore { voting_keypair, .. } => &voting_keypair.pk,
             SigningMethod::Web3Signer {
                 voting_public_key, ..
+            }
+            | SigningMethod::Web3SignerChannel {
+                voting_public_key, ..
+            }
+            | SigningMethod::DistributedKeystore {
+                voting_public_key, ..
             } => voting_public_key,
         }
     }
@@ -432,6 +499,143 @@ fn build_web3_signer_client(
         .map_err(Error::UnableToBuildWeb3SignerClient)
 }
 
+fn build_web3_signer_channel(
+    web3_signer: &Web3SignerDefinition,
+) -> Result<Web3SignerChannel, Error> {
+    let channel_url = web3_signer
+        .channel_url
+        .as_deref()
+        .ok_or_else(|| Error::InvalidWeb3SignerUrl("missing channel_url".to_string()))?;
+    let url = Url::parse(channel_url).map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
+
+    let request_timeout = web3_signer
+        .request_timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT);
+
+    // Only build a TLS connector when there is custom TLS configuration, otherwise the default
+    // connector for the URL scheme is used.
+    let connector = if web3_signer.root_certificate_path.is_some()
+        || web3_signer.client_identity_path.is_some()
+        || web3_signer.client_identity_password.is_some()
+    {
+        let mut builder = native_tls::TlsConnector::builder();
+
+        if let Some(path) = &web3_signer.root_certificate_path {
+            let mut buf = Vec::new();
+            File::open(path)
+                .and_then(|mut file| file.read_to_end(&mut buf))
+                .map_err(Error::InvalidWeb3SignerRootCertificateFile)?;
+            let certificate = native_tls::Certificate::from_pem(&buf)
+                .map_err(|e| Error::UnableToBuildWeb3SignerChannel(e.to_string()))?;
+            builder.add_root_certificate(certificate);
+        }
+
+        if let Some(path) = &web3_signer.client_identity_path {
+            let password = web3_signer
+                .client_identity_password
+                .as_deref()
+                .ok_or(Error::MissingWeb3SignerClientIdentityPassword)?;
+            let mut buf = Vec::new();
+            File::open(path)
+                .and_then(|mut file| file.read_to_end(&mut buf))
+                .map_err(Error::InvalidWeb3SignerClientIdentityCertificateFile)?;
+            let identity = native_tls::Identity::from_pkcs12(&buf, password)
+                .map_err(|e| Error::UnableToBuildWeb3SignerChannel(e.to_string()))?;
+            builder.identity(identity);
+        } else if web3_signer.client_identity_password.is_some() {
+            return Err(Error::MissingWeb3SignerClientIdentityCertificateFile);
+        }
+
+        let connector = builder
+            .build()
+            .map_err(|e| Error::UnableToBuildWeb3SignerChannel(e.to_string()))?;
+        Some(tokio_tungstenite::Connector::NativeTls(connector))
+    } else {
+        None
+    };
+
+    Ok(Web3SignerChannel::new(url, connector, request_timeout))
+}
+
+/// Build the signer for a distributed validator, of which this validator client holds the key
+/// share with `public_key_share`.
+///
+/// The public key combined from the key shares must match `voting_public_key`, which ensures
+/// that the shares are consistent before any duties are performed.
+///
+/// The threshold must be a strict majority of the key shares. The validator clients don't agree
+/// on the messages they sign, so two disjoint groups of `threshold` validator clients could
+/// otherwise each produce a signature over a different message, which is slashable.
+fn build_distributed_signer(
+    voting_public_key: &PublicKey,
+    public_key_share: &PublicKey,
+    distributed: DistributedDefinition,
+) -> Result<DistributedSigner, Error> {
+    let num_shares = distributed.peers.len() + 1;
+    if distributed.threshold > num_shares || 2 * distributed.threshold <= num_shares {
+        return Err(Error::InvalidDistributedValidator(format!(
+            "threshold {} is invalid for {} key shares, it must be more than half of the key \
+             shares and no more than the number of key shares",
+            distributed.threshold, num_shares
+        )));
+    }
+
+    let mut shares = vec![(distributed.share_index, public_key_share)];
+    shares.extend(
+        distributed
+            .peers
+            .iter()
+            .map(|peer| (peer.share_index, &peer.public_key_share)),
+    );
+    shares.sort_unstable_by_key(|(share_index, _)| *share_index);
+    shares.truncate(distributed.threshold);
+    let combined = PublicKey::combine_shares(&shares)
+        .map_err(|e| Error::InvalidDistributedValidator(format!("{:?}", e)))?;
+    if combined != *voting_public_key {
+        return Err(Error::DistributedPublicKeyMismatch {
+            definition: Box::new(voting_public_key.clone()),
+            combined: Box::new(combined),
+        });
+    }
+
+    let peers = distributed
+        .peers
+        .into_iter()
+        .map(|peer| {
+            let url = SensitiveUrl::parse(&peer.url)
+                .map_err(|e| Error::UnableToBuildDistributedPeerClient(format!("{:?}", e)))?;
+            let peer_token =
+                ValidatorClientHttpClient::load_api_token_from_file(&peer.peer_token_path)
+                    .map_err(|e| Error::UnableToBuildDistributedPeerClient(e.to_string()))?;
+            let mut client = ValidatorClientHttpClient::new_unauthenticated(url.clone())
+                .map_err(|e| Error::UnableToBuildDistributedPeerClient(e.to_string()))?;
+            client
+                .add_auth_token(peer_token)
+                .map_err(|e| Error::UnableToBuildDistributedPeerClient(e.to_string()))?;
+            Ok(DistributedPeer {
+                share_index: peer.share_index,
+                public_key_share: peer.public_key_share,
+                url,
+                client,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let timeout = distributed
+        .timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_DISTRIBUTED_SIGNING_TIMEOUT);
+
+    Ok(DistributedSigner::new(
+        voting_public_key.clone(),
+        distributed.share_index,
+        distributed.threshold,
+        peers,
+        timeout,
+    ))
+}
+
 /// Try to unlock `keystore` at `keystore_path` by prompting the user via `stdin`.
 fn unlock_keystore_via_stdin_password(
     keystore: &Keystore,
@@ -484,6 +688,9 @@ pub struct InitializedValidators {
     validators: HashMap<PublicKeyBytes, InitializedValidator>,
     /// The clients used for communications with a remote signer.
     web3_signer_client_map: Option<HashMap<Web3SignerDefinition, Client>>,
+    /// The channels used for communications with a remote signer, for definitions with a
+    /// `channel_url`.
+    web3_signer_channel_map: HashMap<Web3SignerDefinition, Arc<Web3SignerChannel>>,
     /// For logging via `slog`.
     log: Logger,
     config: Config,
@@ -502,6 +709,7 @@ impl InitializedValidators {
             definitions,
             validators: HashMap::default(),
             web3_signer_client_map: None,
+            web3_signer_channel_map: HashMap::new(),
             config,
             log,
         };
@@ -662,6 +870,12 @@ impl InitializedValidators {
                 ref voting_keystore_lockfile,
                 ref voting_keystore,
                 ..
+            }
+            | SigningMethod::DistributedKeystore {
+                ref voting_keystore_path,
+                ref voting_keystore_lockfile,
+                ref voting_keystore,
+                ..
             } = *initialized_validator.signing_method
             {
                 // Drop the lock file so that it may be deleted. This is particularly important on
@@ -1151,6 +1365,7 @@ impl InitializedValidators {
                     voting_keystore_password_path,
                     voting_keystore_password,
                     voting_keystore_path,
+                    distributed,
                 } => {
                     let pw = if let Some(p) = voting_keystore_password {
                         p.as_ref().to_vec().into()
@@ -1165,7 +1380,17 @@ impl InitializedValidators {
                             .into()
                     };
                     passwords.push(pw);
-                    public_keys.push(def.voting_public_key.clone());
+                    // The keystore of a distributed validator holds a key share, rather than the
+                    // key of the voting public key.
+                    let public_key = if distributed.is_some() {
+                        key_stores
+                            .get(voting_keystore_path)
+                            .and_then(Keystore::public_key)
+                            .unwrap_or_else(|| def.voting_public_key.clone())
+                    } else {
+                        def.voting_public_key.clone()
+                    };
+                    public_keys.push(public_key);
                 }
                 // Remote signer validators don't interact with the key cache.
                 SigningDefinition::Web3Signer { .. } => (),
@@ -1248,6 +1473,7 @@ impl InitializedValidators {
                             &mut key_cache,
                             &mut key_stores,
                             &mut None,
+                            &mut HashMap::new(),
                             &self.config,
                         )
                         .await
@@ -1299,6 +1525,7 @@ impl InitializedValidators {
                             &mut key_cache,
                             &mut key_stores,
                             &mut self.web3_signer_client_map,
+                            &mut self.web3_signer_channel_map,
                             &self.config,
                         )
                         .await
@@ -1437,3 +1664,62 @@ impl InitializedValidators {
         self.definitions.as_mut_slice()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use account_utils::validator_definitions::DistributedPeerDefinition;
+    use types::SecretKey;
+
+    fn build(threshold: usize, num_shares: u64) -> Result<DistributedSigner, Error> {
+        let secret = SecretKey::random();
+        let indices = (1..=num_shares).collect::<Vec<_>>();
+        // Split with a valid threshold so that the shares are consistent with the validator.
+        let shares = secret.split(num_shares as usize / 2 + 1, &indices).unwrap();
+        let distributed = DistributedDefinition {
+            share_index: 1,
+            threshold,
+            timeout_ms: None,
+            peers: shares
+                .iter()
+                .zip(&indices)
+                .skip(1)
+                .map(|(share, share_index)| DistributedPeerDefinition {
+                    share_index: *share_index,
+                    public_key_share: share.public_key(),
+                    url: "http://127.0.0.1:1".to_string(),
+                    peer_token_path: PathBuf::from("/nonexistent/peer-api-token.txt"),
+                })
+                .collect(),
+        };
+        build_distributed_signer(&secret.public_key(), &shares[0].public_key(), distributed)
+    }
+
+    #[test]
+    fn distributed_threshold_must_be_a_majority() {
+        for (threshold, num_shares) in [(0, 3), (1, 2), (1, 3), (2, 4), (4, 3)] {
+            assert!(
+                matches!(
+                    build(threshold, num_shares),
+                    Err(Error::InvalidDistributedValidator(_))
+                ),
+                "{}-of-{} should be rejected",
+                threshold,
+                num_shares
+            );
+        }
+
+        // A majority threshold is accepted, and only fails later on the missing peer token.
+        for (threshold, num_shares) in [(2, 3), (3, 4), (4, 4)] {
+            assert!(
+                matches!(
+                    build(threshold, num_shares),
+                    Err(Error::UnableToBuildDistributedPeerClient(_))
+                ),
+                "{}-of-{} should be accepted",
+                threshold,
+                num_shares
+            );
+        }
+    }
+}
@@ -12,6 +12,7 @@ mod latency;
 mod notifier;
 mod preparation_service;
 mod signing_method;
+mod slashing_protection_replication;
 mod sync_committee_service;
 
 mod doppelganger_service;
@@ -26,6 +27,7 @@ use initialized_validators::InitializedValidators;
 use lighthouse_metrics::set_gauge;
 use monitoring_api::{MonitoringHttpClient, ProcessType};
 use sensitive_url::SensitiveUrl;
+pub use signing_method::{ChannelRequest, ChannelResponse};
 pub use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
 
 use crate::beacon_node_fallback::{
@@ -35,6 +37,7 @@ use crate::beacon_node_fallback::{
 use crate::doppelganger_service::DoppelgangerService;
 use crate::graffiti_file::GraffitiFile;
 use crate::initialized_validators::Error::UnableToOpenVotingKeystore;
+use crate::slashing_protection_replication::HttpReplicator;
 use account_utils::validator_definitions::ValidatorDefinitions;
 use attestation_service::{AttestationService, AttestationServiceBuilder};
 use block_service::{BlockService, BlockServiceBuilder};
@@ -419,7 +422,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             None
         };
 
-        let validator_store = Arc::new(ValidatorStore::new(
+        let validator_store = ValidatorStore::new(
             validators,
             slashing_protection,
             genesis_validators_root,
@@ -429,7 +432,24 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             &config,
             context.executor.clone(),
             log.clone(),
-        ));
+        );
+
+        // Replicate slashing protection records to other validator clients, if configured.
+        let validator_store = if config.slashing_protection_replicas.is_empty() {
+            Arc::new(validator_store)
+        } else {
+            let replicator = HttpReplicator::new(
+                &config.slashing_protection_replicas,
+                &config.slashing_protection_replica_peer_tokens,
+                genesis_validators_root,
+            )?;
+            info!(
+                log,
+                "Replicating slashing protection";
+                "replicas" => config.slashing_protection_replicas.len(),
+            );
+            Arc::new(validator_store.with_slashing_protection_replicator(replicator))
+        };
 
         // Ensure all validators are registered in doppelganger protection.
         validator_store.register_all_in_doppelganger_protection_if_enabled()?;
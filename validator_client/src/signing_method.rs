@@ -1,7 +1,10 @@
 //! Provides methods for obtaining validator signatures, including:
 //!
 //! - Via a local `Keypair`.
-//! - Via a remote signer (Web3Signer)
+//! - Via a remote signer (Web3Signer), either per-request over HTTP(S) or over a persistent
+//!   WebSocket connection.
+//! - Via a share of a distributed validator's key, combined with the partial signatures of other
+//!   validator clients.
 
 use crate::http_metrics::metrics;
 use eth2_keystore::Keystore;
@@ -15,9 +18,13 @@ use types::*;
 use url::Url;
 use web3signer::{ForkInfo, SigningRequest, SigningResponse};
 
+pub use distributed::{DistributedPeer, DistributedSigner};
 pub use web3signer::Web3SignerObject;
+pub use web3signer_channel::{ChannelRequest, ChannelResponse, Web3SignerChannel};
 
+mod distributed;
 mod web3signer;
+mod web3signer_channel;
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
@@ -31,6 +38,13 @@ pub enum Error {
     TokioJoin(String),
     MergeForkNotSupported,
     GenesisForkVersionRequired,
+    InsufficientPartialSignatures {
+        received: usize,
+        threshold: usize,
+    },
+    UnknownShareIndex(u64),
+    InvalidPartialSignature(u64),
+    UnableToCombinePartialSignatures(String),
 }
 
 /// Enumerates all messages that can be signed by a validator.
@@ -93,6 +107,24 @@ pub enum SigningMethod {
         http_client: Client,
         voting_public_key: PublicKey,
     },
+    /// A validator that defers to a remote signer for signing, over a connection that is shared
+    /// with the signer's other validators.
+    Web3SignerChannel {
+        channel: Arc<Web3SignerChannel>,
+        voting_public_key: PublicKey,
+    },
+    /// A validator whose key is split between several validator clients, of which this one holds
+    /// the share in an EIP-2335 keystore on the local filesystem.
+    DistributedKeystore {
+        voting_keystore_path: PathBuf,
+        voting_keystore_lockfile: Mutex<Option<Lockfile>>,
+        voting_keystore: Keystore,
+        /// The key share held by this validator client.
+        voting_keypair: Arc<Keypair>,
+        /// The public key of the validator, which is combined from the key shares.
+        voting_public_key: PublicKey,
+        distributed_signer: Arc<DistributedSigner>,
+    },
 }
 
 /// The additional information used to construct a signature. Mostly used for protection from replay
@@ -124,10 +156,12 @@ impl SigningMethod {
     ) -> bool {
         match self {
             // Slashing protection is ALWAYS required for local keys. DO NOT TURN THIS OFF.
-            SigningMethod::LocalKeystore { .. } => true,
+            SigningMethod::LocalKeystore { .. } | SigningMethod::DistributedKeystore { .. } => true,
             // Slashing protection is only required for remote signer keys when the configuration
             // dictates that it is desired.
-            SigningMethod::Web3Signer { .. } => enable_web3signer_slashing_protection,
+            SigningMethod::Web3Signer { .. } | SigningMethod::Web3SignerChannel { .. } => {
+                enable_web3signer_slashing_protection
+            }
         }
     }
 
@@ -190,55 +224,7 @@ impl SigningMethod {
                 let _timer =
                     metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::WEB3SIGNER]);
 
-                // Map the message into a Web3Signer type.
-                let object = match signable_message {
-                    SignableMessage::RandaoReveal(epoch) => {
-                        Web3SignerObject::RandaoReveal { epoch }
-                    }
-                    SignableMessage::BeaconBlock(block) => Web3SignerObject::beacon_block(block)?,
-                    SignableMessage::AttestationData(a) => Web3SignerObject::Attestation(a),
-                    SignableMessage::SignedAggregateAndProof(a) => {
-                        Web3SignerObject::AggregateAndProof(a)
-                    }
-                    SignableMessage::SelectionProof(slot) => {
-                        Web3SignerObject::AggregationSlot { slot }
-                    }
-                    SignableMessage::SyncSelectionProof(s) => {
-                        Web3SignerObject::SyncAggregatorSelectionData(s)
-                    }
-                    SignableMessage::SyncCommitteeSignature {
-                        beacon_block_root,
-                        slot,
-                    } => Web3SignerObject::SyncCommitteeMessage {
-                        beacon_block_root,
-                        slot,
-                    },
-                    SignableMessage::SignedContributionAndProof(c) => {
-                        Web3SignerObject::ContributionAndProof(c)
-                    }
-                    SignableMessage::ValidatorRegistration(v) => {
-                        Web3SignerObject::ValidatorRegistration(v)
-                    }
-                    SignableMessage::VoluntaryExit(e) => Web3SignerObject::VoluntaryExit(e),
-                };
-
-                // Determine the Web3Signer message type.
-                let message_type = object.message_type();
-
-                if matches!(
-                    object,
-                    Web3SignerObject::Deposit { .. } | Web3SignerObject::ValidatorRegistration(_)
-                ) && fork_info.is_some()
-                {
-                    return Err(Error::GenesisForkVersionRequired);
-                }
-
-                let request = SigningRequest {
-                    message_type,
-                    fork_info,
-                    signing_root,
-                    object,
-                };
+                let request = web3signer_request(signable_message, signing_root, fork_info)?;
 
                 // Request a signature from the Web3Signer instance via HTTP(S).
                 let response: SigningResponse = http_client
@@ -255,6 +241,95 @@ impl SigningMethod {
 
                 Ok(response.signature)
             }
+            SigningMethod::Web3SignerChannel {
+                channel,
+                voting_public_key,
+            } => {
+                let _timer = metrics::start_timer_vec(
+                    &metrics::SIGNING_TIMES,
+                    &[metrics::WEB3SIGNER_CHANNEL],
+                );
+
+                let request = web3signer_request(signable_message, signing_root, fork_info)?;
+                let request = serde_json::to_value(&request)
+                    .map_err(|e| Error::Web3SignerJsonParsingFailed(e.to_string()))?;
+
+                // Request a signature via the connection shared with other validators of the
+                // same remote signer.
+                channel
+                    .sign(voting_public_key.compress(), request, executor)
+                    .await
+            }
+            SigningMethod::DistributedKeystore {
+                voting_keypair,
+                distributed_signer,
+                ..
+            } => {
+                let _timer = metrics::start_timer_vec(
+                    &metrics::SIGNING_TIMES,
+                    &[metrics::DISTRIBUTED_KEYSTORE],
+                );
+
+                // Sign with this validator client's key share, as for a local keystore.
+                let voting_keypair = voting_keypair.clone();
+                let partial_signature = executor
+                    .spawn_blocking_handle(
+                        move || voting_keypair.sk.sign(signing_root),
+                        "distributed_keystore_signer",
+                    )
+                    .ok_or(Error::ShuttingDown)?
+                    .await
+                    .map_err(|e| Error::TokioJoin(e.to_string()))?;
+
+                distributed_signer
+                    .sign(signing_root, partial_signature, executor)
+                    .await
+            }
         }
     }
 }
+
+/// Map `signable_message` into a Web3Signer signing request.
+fn web3signer_request<'a, T: EthSpec, Payload: AbstractExecPayload<T>>(
+    signable_message: SignableMessage<'a, T, Payload>,
+    signing_root: Hash256,
+    fork_info: Option<ForkInfo>,
+) -> Result<SigningRequest<'a, T, Payload>, Error> {
+    // Map the message into a Web3Signer type.
+    let object = match signable_message {
+        SignableMessage::RandaoReveal(epoch) => Web3SignerObject::RandaoReveal { epoch },
+        SignableMessage::BeaconBlock(block) => Web3SignerObject::beacon_block(block)?,
+        SignableMessage::AttestationData(a) => Web3SignerObject::Attestation(a),
+        SignableMessage::SignedAggregateAndProof(a) => Web3SignerObject::AggregateAndProof(a),
+        SignableMessage::SelectionProof(slot) => Web3SignerObject::AggregationSlot { slot },
+        SignableMessage::SyncSelectionProof(s) => Web3SignerObject::SyncAggregatorSelectionData(s),
+        SignableMessage::SyncCommitteeSignature {
+            beacon_block_root,
+            slot,
+        } => Web3SignerObject::SyncCommitteeMessage {
+            beacon_block_root,
+            slot,
+        },
+        SignableMessage::SignedContributionAndProof(c) => Web3SignerObject::ContributionAndProof(c),
+        SignableMessage::ValidatorRegistration(v) => Web3SignerObject::ValidatorRegistration(v),
+        SignableMessage::VoluntaryExit(e) => Web3SignerObject::VoluntaryExit(e),
+    };
+
+    // Determine the Web3Signer message type.
+    let message_type = object.message_type();
+
+    if matches!(
+        object,
+        Web3SignerObject::Deposit { .. } | Web3SignerObject::ValidatorRegistration(_)
+    ) && fork_info.is_some()
+    {
+        return Err(Error::GenesisForkVersionRequired);
+    }
+
+    Ok(SigningRequest {
+        message_type,
+        fork_info,
+        signing_root,
+        object,
+    })
+}
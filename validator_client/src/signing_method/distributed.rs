@@ -0,0 +1,285 @@
+//! Signing as one operator of a distributed validator.
+//!
+//! The voting key of a distributed validator is split into shares held by several validator
+//! clients, any `threshold` of which are required to produce a signature. Each validator client
+//! performs the validator's duties as usual, signing with its own key share after the usual
+//! slashing protection checks. The resulting partial signature is sent to the other validator
+//! clients via their HTTP APIs, and the partial signatures they send in return are combined into
+//! the validator's signature.
+use super::Error;
+use eth2::lighthouse_vc::{http_client::ValidatorClientHttpClient, types::PartialSignatureRequest};
+use parking_lot::Mutex;
+use sensitive_url::SensitiveUrl;
+use slog::warn;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use task_executor::TaskExecutor;
+use tokio::sync::Notify;
+use types::{Hash256, PublicKey, Signature};
+
+/// The time after which partial signatures for a signing root are discarded.
+///
+/// This is two epochs on mainnet, which is longer than any validator client waits to combine the
+/// partial signatures for a message.
+const PARTIAL_SIGNATURE_RETENTION: Duration = Duration::from_secs(2 * 32 * 12);
+
+/// The maximum number of signing roots to hold partial signatures for.
+const MAX_PENDING_SIGNING_ROOTS: usize = 1_024;
+
+/// A validator client which holds another share of the validator's key.
+pub struct DistributedPeer {
+    pub share_index: u64,
+    pub public_key_share: PublicKey,
+    pub url: SensitiveUrl,
+    pub client: ValidatorClientHttpClient,
+}
+
+/// The partial signatures received for a single signing root.
+struct PartialSignatures {
+    first_seen: Instant,
+    signatures: HashMap<u64, Signature>,
+}
+
+/// Combines partial signatures from the validator clients of a distributed validator.
+pub struct DistributedSigner {
+    voting_public_key: PublicKey,
+    share_index: u64,
+    threshold: usize,
+    peers: Vec<DistributedPeer>,
+    timeout: Duration,
+    partial_signatures: Mutex<HashMap<Hash256, PartialSignatures>>,
+    partial_signature_received: Notify,
+}
+
+impl DistributedSigner {
+    pub fn new(
+        voting_public_key: PublicKey,
+        share_index: u64,
+        threshold: usize,
+        peers: Vec<DistributedPeer>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            voting_public_key,
+            share_index,
+            threshold,
+            peers,
+            timeout,
+            partial_signatures: Mutex::new(HashMap::new()),
+            partial_signature_received: Notify::new(),
+        }
+    }
+
+    /// Send `partial_signature` across `signing_root` by this validator client's key share to all
+    /// peers, and return the validator's signature once `threshold` partial signatures have been
+    /// received.
+    pub async fn sign(
+        &self,
+        signing_root: Hash256,
+        partial_signature: Signature,
+        executor: &TaskExecutor,
+    ) -> Result<Signature, Error> {
+        let request = PartialSignatureRequest {
+            voting_pubkey: self.voting_public_key.compress(),
+            signing_root,
+            share_index: self.share_index,
+            signature: partial_signature.clone(),
+        };
+        for peer in &self.peers {
+            let client = peer.client.clone();
+            let url = peer.url.clone();
+            let request = request.clone();
+            let log = executor.log().clone();
+            // Peers that are unavailable are tolerated as long as `threshold` are available, so
+            // a failure to reach a peer doesn't fail the signature.
+            executor.spawn(
+                async move {
+                    if let Err(e) = client
+                        .post_lighthouse_distributed_partial_signatures(&request)
+                        .await
+                    {
+                        warn!(
+                            log,
+                            "Unable to send partial signature to peer";
+                            "error" => %e,
+                            "peer" => %url,
+                            "share_index" => request.share_index,
+                            "voting_pubkey" => ?request.voting_pubkey,
+                        );
+                    }
+                },
+                "distributed_partial_signature",
+            );
+        }
+        self.insert_partial_signature(signing_root, self.share_index, partial_signature);
+
+        let deadline = tokio::time::Instant::now() + self.timeout;
+        loop {
+            // Register for notifications before checking, so that no signature can be missed.
+            let notified = self.partial_signature_received.notified();
+            if let Some(signature) = self.combine_partial_signatures(signing_root)? {
+                return Ok(signature);
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                let received = self
+                    .partial_signatures
+                    .lock()
+                    .get(&signing_root)
+                    .map_or(0, |partial| partial.signatures.len());
+                return Err(Error::InsufficientPartialSignatures {
+                    received,
+                    threshold: self.threshold,
+                });
+            }
+        }
+    }
+
+    /// Receive a partial signature from the peer holding the key share with `share_index`.
+    pub fn receive_partial_signature(
+        &self,
+        signing_root: Hash256,
+        share_index: u64,
+        signature: Signature,
+    ) -> Result<(), Error> {
+        let peer = self
+            .peers
+            .iter()
+            .find(|peer| peer.share_index == share_index)
+            .ok_or(Error::UnknownShareIndex(share_index))?;
+
+        if !signature.verify(&peer.public_key_share, signing_root) {
+            return Err(Error::InvalidPartialSignature(share_index));
+        }
+
+        self.insert_partial_signature(signing_root, share_index, signature);
+        Ok(())
+    }
+
+    fn insert_partial_signature(
+        &self,
+        signing_root: Hash256,
+        share_index: u64,
+        signature: Signature,
+    ) {
+        let mut partial_signatures = self.partial_signatures.lock();
+
+        partial_signatures
+            .retain(|_, partial| partial.first_seen.elapsed() < PARTIAL_SIGNATURE_RETENTION);
+        if partial_signatures.len() >= MAX_PENDING_SIGNING_ROOTS
+            && !partial_signatures.contains_key(&signing_root)
+        {
+            if let Some(oldest) = partial_signatures
+                .iter()
+                .min_by_key(|(_, partial)| partial.first_seen)
+                .map(|(root, _)| *root)
+            {
+                partial_signatures.remove(&oldest);
+            }
+        }
+
+        partial_signatures
+            .entry(signing_root)
+            .or_insert_with(|| PartialSignatures {
+                first_seen: Instant::now(),
+                signatures: HashMap::new(),
+            })
+            .signatures
+            .insert(share_index, signature);
+        drop(partial_signatures);
+
+        self.partial_signature_received.notify_waiters();
+    }
+
+    /// Combine the partial signatures for `signing_root`, if at least `threshold` are available.
+    fn combine_partial_signatures(
+        &self,
+        signing_root: Hash256,
+    ) -> Result<Option<Signature>, Error> {
+        let partial_signatures = self.partial_signatures.lock();
+        let Some(partial) = partial_signatures.get(&signing_root) else {
+            return Ok(None);
+        };
+        if partial.signatures.len() < self.threshold {
+            return Ok(None);
+        }
+
+        // Use the shares with the lowest indices so that all validator clients produce the same
+        // combination, although any `threshold` shares produce the same signature.
+        let mut shares = partial
+            .signatures
+            .iter()
+            .map(|(share_index, signature)| (*share_index, signature))
+            .collect::<Vec<_>>();
+        shares.sort_unstable_by_key(|(share_index, _)| *share_index);
+        shares.truncate(self.threshold);
+
+        let signature = Signature::combine_shares(&shares)
+            .map_err(|e| Error::UnableToCombinePartialSignatures(format!("{e:?}")))?;
+        if !signature.verify(&self.voting_public_key, signing_root) {
+            return Err(Error::UnableToCombinePartialSignatures(
+                "combined signature is invalid".to_string(),
+            ));
+        }
+        Ok(Some(signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::SecretKey;
+
+    fn signer(secret: &SecretKey, shares: &[SecretKey], share_index: u64) -> DistributedSigner {
+        let peers = shares
+            .iter()
+            .enumerate()
+            .map(|(i, share)| (i as u64 + 1, share))
+            .filter(|(index, _)| *index != share_index)
+            .map(|(index, share)| {
+                let url = SensitiveUrl::parse("http://127.0.0.1:1").unwrap();
+                DistributedPeer {
+                    share_index: index,
+                    public_key_share: share.public_key(),
+                    url: url.clone(),
+                    client: ValidatorClientHttpClient::new_unauthenticated(url).unwrap(),
+                }
+            })
+            .collect();
+        DistributedSigner::new(
+            secret.public_key(),
+            share_index,
+            2,
+            peers,
+            Duration::from_secs(1),
+        )
+    }
+
+    #[test]
+    fn combine_received_partial_signatures() {
+        let secret = SecretKey::random();
+        let shares = secret.split(2, &[1, 2, 3]).unwrap();
+        let signer = signer(&secret, &shares, 1);
+        let signing_root = Hash256::repeat_byte(1);
+
+        signer.insert_partial_signature(signing_root, 1, shares[0].sign(signing_root));
+        assert_eq!(signer.combine_partial_signatures(signing_root), Ok(None));
+
+        // Partial signatures must be from a known peer, and be valid for that peer's share.
+        assert_eq!(
+            signer.receive_partial_signature(signing_root, 4, shares[2].sign(signing_root)),
+            Err(Error::UnknownShareIndex(4))
+        );
+        assert_eq!(
+            signer.receive_partial_signature(signing_root, 3, shares[1].sign(signing_root)),
+            Err(Error::InvalidPartialSignature(3))
+        );
+
+        signer
+            .receive_partial_signature(signing_root, 3, shares[2].sign(signing_root))
+            .unwrap();
+        assert_eq!(
+            signer.combine_partial_signatures(signing_root),
+            Ok(Some(secret.sign(signing_root)))
+        );
+    }
+}
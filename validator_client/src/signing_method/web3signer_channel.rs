@@ -0,0 +1,448 @@
+//! A persistent, multiplexed WebSocket connection to a remote signer.
+//!
+//! Each `ChannelRequest` carries a Web3Signer signing request body and the keys that should sign
+//! it, tagged with an `id` that is echoed back in the matching `ChannelResponse`. This allows many
+//! requests to be in flight on the one connection at once. Requests for the same message that are
+//! queued at the same time (e.g. the attestations of a committee) are sent as a single request
+//! with many keys.
+use super::Error;
+use futures::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use task_executor::TaskExecutor;
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::Connector;
+use types::{PublicKeyBytes, Signature};
+use url::Url;
+
+/// A request to sign one message with one or more keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelRequest {
+    pub id: u64,
+    pub pubkeys: Vec<PublicKeyBytes>,
+    /// The body of a Web3Signer `/api/v1/eth2/sign` request.
+    pub request: serde_json::Value,
+}
+
+/// The response to the `ChannelRequest` with the same `id`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelResponse {
+    pub id: u64,
+    /// One signature for each of the request's `pubkeys`, in the same order, or `None` if the
+    /// signer refused to sign with that key.
+    #[serde(default)]
+    pub signatures: Vec<Option<Signature>>,
+    /// An error that prevented the signer from handling any of the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A signature that has been requested but not yet sent to the remote signer.
+struct QueuedSignature {
+    pubkey: PublicKeyBytes,
+    request: serde_json::Value,
+    response_tx: oneshot::Sender<Result<Signature, Error>>,
+}
+
+type ResponseSenders = Vec<oneshot::Sender<Result<Signature, Error>>>;
+
+/// A connection to a remote signer which is shared by all of its validators.
+///
+/// The connection is established when the first signature is requested, and re-established
+/// whenever a signature is requested after it has been closed.
+pub struct Web3SignerChannel {
+    url: Url,
+    connector: Option<Connector>,
+    request_timeout: Duration,
+    queue_tx: Mutex<Option<mpsc::UnboundedSender<QueuedSignature>>>,
+}
+
+impl Web3SignerChannel {
+    pub fn new(url: Url, connector: Option<Connector>, request_timeout: Duration) -> Self {
+        Self {
+            url,
+            connector,
+            request_timeout,
+            queue_tx: Mutex::new(None),
+        }
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Request a signature for the Web3Signer signing request body `request` from `pubkey`.
+    pub async fn sign(
+        &self,
+        pubkey: PublicKeyBytes,
+        request: serde_json::Value,
+        executor: &TaskExecutor,
+    ) -> Result<Signature, Error> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.queue_tx(executor)
+            .send(QueuedSignature {
+                pubkey,
+                request,
+                response_tx,
+            })
+            .map_err(|_| Error::ShuttingDown)?;
+
+        tokio::time::timeout(self.request_timeout, response_rx)
+            .await
+            .map_err(|_| {
+                Error::Web3SignerRequestFailed(format!(
+                    "no response from {} within {:?}",
+                    self.url, self.request_timeout
+                ))
+            })?
+            .map_err(|_| Error::ShuttingDown)?
+    }
+
+    /// Return the queue of the task which owns the connection, spawning it if necessary.
+    fn queue_tx(&self, executor: &TaskExecutor) -> mpsc::UnboundedSender<QueuedSignature> {
+        let mut queue_tx = self.queue_tx.lock();
+        match queue_tx.as_ref() {
+            Some(tx) if !tx.is_closed() => tx.clone(),
+            _ => {
+                let (tx, rx) = mpsc::unbounded_channel();
+                executor.spawn(
+                    run_channel(self.url.clone(), self.connector.clone(), rx),
+                    "web3signer_channel",
+                );
+                *queue_tx = Some(tx.clone());
+                tx
+            }
+        }
+    }
+}
+
+/// Serve queued signatures until the queue is closed, connecting to the signer as required.
+async fn run_channel(
+    url: Url,
+    connector: Option<Connector>,
+    mut queue_rx: mpsc::UnboundedReceiver<QueuedSignature>,
+) {
+    let mut queued = vec![];
+    loop {
+        // Wait for a signature to be requested before (re-)connecting, so that an idle channel
+        // does not repeatedly try to connect to an unavailable signer.
+        if queued.is_empty() {
+            match queue_rx.recv().await {
+                Some(signature) => queued.push(signature),
+                None => return,
+            }
+        }
+        match tokio_tungstenite::connect_async_tls_with_config(
+            url.as_str(),
+            None,
+            false,
+            connector.clone(),
+        )
+        .await
+        {
+            Ok((stream, _)) => {
+                queued = serve_connection(stream, std::mem::take(&mut queued), &mut queue_rx).await;
+                // Don't reconnect for signatures which no caller is waiting for.
+                queued.retain(|signature| !signature.response_tx.is_closed());
+            }
+            Err(e) => {
+                drain_queue(&mut queued, &mut queue_rx);
+                let error = format!("unable to connect to {url}: {e}");
+                for signature in queued.drain(..) {
+                    let _ = signature
+                        .response_tx
+                        .send(Err(Error::Web3SignerRequestFailed(error.clone())));
+                }
+            }
+        }
+    }
+}
+
+/// Send queued signatures over `stream` and dispatch the responses, until either the queue or the
+/// connection is closed.
+///
+/// Returns the signatures which were queued but not yet sent when the connection closed.
+async fn serve_connection<S>(
+    stream: S,
+    mut queued: Vec<QueuedSignature>,
+    queue_rx: &mut mpsc::UnboundedReceiver<QueuedSignature>,
+) -> Vec<QueuedSignature>
+where
+    S: futures::Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>>
+        + futures::Sink<Message, Error = tokio_tungstenite::tungstenite::Error>
+        + Unpin,
+{
+    let (mut sink, mut stream) = stream.split();
+    let mut in_flight: HashMap<u64, ResponseSenders> = HashMap::new();
+    let mut next_id = 0;
+
+    let error = loop {
+        prune_in_flight(&mut in_flight);
+        drain_queue(&mut queued, queue_rx);
+        if let Err(e) = send_batches(&mut sink, &mut queued, &mut in_flight, &mut next_id).await {
+            break format!("failed to send request: {e}");
+        }
+
+        tokio::select! {
+            signature = queue_rx.recv() => match signature {
+                Some(signature) => queued.push(signature),
+                None => {
+                    let _ = sink.close().await;
+                    return vec![];
+                }
+            },
+            message = stream.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    if let Err(e) = handle_response(&text, &mut in_flight, next_id) {
+                        break e;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => break "connection closed".to_string(),
+                Some(Ok(_)) => (),
+                Some(Err(e)) => break format!("connection failed: {e}"),
+            },
+        }
+    };
+
+    // Fail any signatures which will never receive a response. Signatures which are still queued
+    // will be sent on the next connection.
+    for response_tx in in_flight.into_values().flatten() {
+        let _ = response_tx.send(Err(Error::Web3SignerRequestFailed(error.clone())));
+    }
+    queued
+}
+
+/// Forget requests for which every caller has stopped waiting (e.g. after timing out), so that
+/// requests which the signer never responds to don't accumulate.
+fn prune_in_flight(in_flight: &mut HashMap<u64, ResponseSenders>) {
+    in_flight.retain(|_, response_txs| {
+        response_txs
+            .iter()
+            .any(|response_tx| !response_tx.is_closed())
+    });
+}
+
+/// Move all signatures waiting in `queue_rx` to `queued`, without waiting for more.
+fn drain_queue(
+    queued: &mut Vec<QueuedSignature>,
+    queue_rx: &mut mpsc::UnboundedReceiver<QueuedSignature>,
+) {
+    while let Ok(signature) = queue_rx.try_recv() {
+        queued.push(signature);
+    }
+}
+
+/// Send all `queued` signatures, combining signatures of the same message into one request.
+async fn send_batches<S>(
+    sink: &mut S,
+    queued: &mut Vec<QueuedSignature>,
+    in_flight: &mut HashMap<u64, ResponseSenders>,
+    next_id: &mut u64,
+) -> Result<(), tokio_tungstenite::tungstenite::Error>
+where
+    S: futures::Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+{
+    if queued.is_empty() {
+        return Ok(());
+    }
+
+    // Group by the serialized request, preserving the order in which messages were first queued.
+    let mut batches: Vec<(ChannelRequest, ResponseSenders)> = vec![];
+    let mut batch_indices: HashMap<String, usize> = HashMap::new();
+    for signature in queued.drain(..) {
+        let index = *batch_indices
+            .entry(signature.request.to_string())
+            .or_insert_with(|| {
+                batches.push((
+                    ChannelRequest {
+                        id: 0,
+                        pubkeys: vec![],
+                        request: signature.request,
+                    },
+                    vec![],
+                ));
+                batches.len() - 1
+            });
+        let (request, response_txs) = &mut batches[index];
+        request.pubkeys.push(signature.pubkey);
+        response_txs.push(signature.response_tx);
+    }
+
+    for (mut request, response_txs) in batches {
+        request.id = *next_id;
+        *next_id += 1;
+        in_flight.insert(request.id, response_txs);
+        let text =
+            serde_json::to_string(&request).expect("channel requests are always serializable");
+        sink.feed(Message::Text(text)).await?;
+    }
+    sink.flush().await
+}
+
+/// Dispatch the signatures in a `ChannelResponse` to their callers.
+///
+/// Returns an error if the response is invalid, after which the connection should not be used.
+/// Responses to requests which have already been answered or pruned are ignored.
+fn handle_response(
+    text: &str,
+    in_flight: &mut HashMap<u64, ResponseSenders>,
+    next_id: u64,
+) -> Result<(), String> {
+    let response: ChannelResponse =
+        serde_json::from_str(text).map_err(|e| format!("invalid response: {e}"))?;
+    let Some(response_txs) = in_flight.remove(&response.id) else {
+        if response.id < next_id {
+            return Ok(());
+        }
+        return Err(format!("response for unknown request {}", response.id));
+    };
+
+    if let Some(error) = response.error {
+        for response_tx in response_txs {
+            let _ = response_tx.send(Err(Error::Web3SignerRequestFailed(error.clone())));
+        }
+        return Ok(());
+    }
+
+    if response.signatures.len() != response_txs.len() {
+        return Err(format!(
+            "response {} has {} signatures for {} keys",
+            response.id,
+            response.signatures.len(),
+            response_txs.len()
+        ));
+    }
+
+    for (signature, response_tx) in response.signatures.into_iter().zip(response_txs) {
+        let _ = response_tx.send(signature.ok_or_else(|| {
+            Error::Web3SignerRequestFailed("remote signer refused to sign".to_string())
+        }));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::channel::mpsc as futures_mpsc;
+
+    fn queued(
+        pubkey: PublicKeyBytes,
+        request: serde_json::Value,
+    ) -> (QueuedSignature, oneshot::Receiver<Result<Signature, Error>>) {
+        let (response_tx, response_rx) = oneshot::channel();
+        (
+            QueuedSignature {
+                pubkey,
+                request,
+                response_tx,
+            },
+            response_rx,
+        )
+    }
+
+    #[tokio::test]
+    async fn identical_requests_are_batched() {
+        let keys = [
+            PublicKeyBytes::empty(),
+            PublicKeyBytes::deserialize(&[1; 48]).unwrap(),
+        ];
+        let attestation = serde_json::json!({ "type": "ATTESTATION" });
+        let randao = serde_json::json!({ "type": "RANDAO_REVEAL" });
+
+        let (a, mut a_rx) = queued(keys[0], attestation.clone());
+        let (b, mut b_rx) = queued(keys[0], randao);
+        let (c, mut c_rx) = queued(keys[1], attestation.clone());
+        let mut queue = vec![a, b, c];
+
+        let (mut sink, mut sent) = futures_mpsc::unbounded::<Message>();
+        let mut sink =
+            (&mut sink).sink_map_err(|_| tokio_tungstenite::tungstenite::Error::ConnectionClosed);
+        let mut in_flight = HashMap::new();
+        let mut next_id = 0;
+        send_batches(&mut sink, &mut queue, &mut in_flight, &mut next_id)
+            .await
+            .unwrap();
+
+        let requests: Vec<ChannelRequest> = (0..2)
+            .map(|_| match sent.try_next().unwrap().unwrap() {
+                Message::Text(text) => serde_json::from_str(&text).unwrap(),
+                _ => panic!("expected text message"),
+            })
+            .collect();
+        assert_eq!(requests[0].id, 0);
+        assert_eq!(requests[0].pubkeys, keys.to_vec());
+        assert_eq!(requests[0].request, attestation);
+        assert_eq!(requests[1].id, 1);
+        assert_eq!(requests[1].pubkeys, vec![keys[0]]);
+        assert_eq!(next_id, 2);
+
+        // Respond to the requests out of order.
+        let signature = Signature::empty();
+        let response = |id, signatures| {
+            serde_json::to_string(&ChannelResponse {
+                id,
+                signatures,
+                error: None,
+            })
+            .unwrap()
+        };
+        handle_response(&response(1, vec![None]), &mut in_flight, next_id).unwrap();
+        handle_response(
+            &response(0, vec![Some(signature.clone()), Some(signature.clone())]),
+            &mut in_flight,
+            next_id,
+        )
+        .unwrap();
+        assert!(in_flight.is_empty());
+
+        assert_eq!(a_rx.try_recv().unwrap(), Ok(signature.clone()));
+        assert!(b_rx.try_recv().unwrap().is_err());
+        assert_eq!(c_rx.try_recv().unwrap(), Ok(signature));
+
+        // Responses to requests which were never sent are rejected.
+        assert!(handle_response(&response(2, vec![]), &mut in_flight, next_id).is_err());
+    }
+
+    #[tokio::test]
+    async fn abandoned_requests_are_pruned() {
+        let (a, a_rx) = queued(
+            PublicKeyBytes::empty(),
+            serde_json::json!({ "type": "ATTESTATION" }),
+        );
+        let (b, b_rx) = queued(
+            PublicKeyBytes::empty(),
+            serde_json::json!({ "type": "BLOCK" }),
+        );
+        let mut queue = vec![a, b];
+
+        let (mut sink, _sent) = futures_mpsc::unbounded::<Message>();
+        let mut sink =
+            (&mut sink).sink_map_err(|_| tokio_tungstenite::tungstenite::Error::ConnectionClosed);
+        let mut in_flight = HashMap::new();
+        let mut next_id = 0;
+        send_batches(&mut sink, &mut queue, &mut in_flight, &mut next_id)
+            .await
+            .unwrap();
+        assert_eq!(in_flight.len(), 2);
+
+        // Only the request whose caller stopped waiting is pruned.
+        drop(a_rx);
+        prune_in_flight(&mut in_flight);
+        assert_eq!(in_flight.keys().collect::<Vec<_>>(), vec![&1]);
+
+        // A late response to the pruned request is ignored.
+        let late_response = serde_json::to_string(&ChannelResponse {
+            id: 0,
+            signatures: vec![Some(Signature::empty())],
+            error: None,
+        })
+        .unwrap();
+        handle_response(&late_response, &mut in_flight, next_id).unwrap();
+        assert_eq!(in_flight.len(), 1);
+        drop(b_rx);
+    }
+}
@@ -0,0 +1,95 @@
+//! Replication of slashing protection records to other validator clients via their HTTP APIs.
+//!
+//! See `slashing_protection::replication` for the replication protocol.
+use eth2::lighthouse_vc::{
+    http_client::ValidatorClientHttpClient, types::ReplicateSlashingProtectionRequest,
+};
+use futures::future::join_all;
+use sensitive_url::SensitiveUrl;
+use slashing_protection::ReplicationRecord;
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+use types::Hash256;
+
+/// The time to wait for a replica to commit a record.
+///
+/// Set to a third of a slot, so that attestations can still be published on time.
+const REPLICATION_TIMEOUT: Duration = Duration::from_secs(4);
+
+pub struct HttpReplicator {
+    replicas: Vec<(SensitiveUrl, ValidatorClientHttpClient)>,
+    genesis_validators_root: Hash256,
+}
+
+impl fmt::Debug for HttpReplicator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpReplicator")
+            .field("replicas", &self.replicas.len())
+            .field("genesis_validators_root", &self.genesis_validators_root)
+            .finish()
+    }
+}
+
+impl HttpReplicator {
+    /// Creates a replicator for the validator clients at `replicas`, which are accessed with the
+    /// peer tokens at `peer_token_paths`.
+    pub fn new(
+        replicas: &[SensitiveUrl],
+        peer_token_paths: &[PathBuf],
+        genesis_validators_root: Hash256,
+    ) -> Result<Self, String> {
+        let replicas = replicas
+            .iter()
+            .zip(peer_token_paths)
+            .map(|(url, peer_token_path)| {
+                let peer_token =
+                    ValidatorClientHttpClient::load_api_token_from_file(peer_token_path)
+                        .map_err(|e| format!("Unable to read replica peer token: {:?}", e))?;
+                let mut client = ValidatorClientHttpClient::new_unauthenticated(url.clone())
+                    .map_err(|e| format!("Unable to create replica HTTP client: {:?}", e))?;
+                client
+                    .add_auth_token(peer_token)
+                    .map_err(|e| format!("Unable to create replica HTTP client: {:?}", e))?;
+                Ok((url.clone(), client))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            replicas,
+            genesis_validators_root,
+        })
+    }
+
+    /// Replicate `record` to all replicas, returning only once each of them has durably committed
+    /// it.
+    ///
+    /// An error is returned if any replica has not committed the record, in which case the
+    /// message must not be signed.
+    pub async fn replicate(&self, record: &ReplicationRecord) -> Result<(), String> {
+        let request = ReplicateSlashingProtectionRequest {
+            genesis_validators_root: self.genesis_validators_root,
+            record: record.clone(),
+        };
+        let results = join_all(self.replicas.iter().map(|(url, client)| async {
+            tokio::time::timeout(
+                REPLICATION_TIMEOUT,
+                client.post_lighthouse_slashing_protection_replicate(&request),
+            )
+            .await
+            .map_err(|_| format!("{}: timed out", url))?
+            .map_err(|e| format!("{}: {}", url, e))
+        }))
+        .await;
+
+        let errors = results
+            .into_iter()
+            .filter_map(Result::err)
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join(", "))
+        }
+    }
+}
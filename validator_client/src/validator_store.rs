@@ -3,12 +3,13 @@ use crate::{
     http_metrics::metrics,
     initialized_validators::InitializedValidators,
     signing_method::{Error as SigningError, SignableMessage, SigningContext, SigningMethod},
+    slashing_protection_replication::HttpReplicator,
     Config,
 };
 use account_utils::validator_definitions::{PasswordStorage, ValidatorDefinition};
 use parking_lot::{Mutex, RwLock};
 use slashing_protection::{
-    interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingDatabase,
+    interchange::Interchange, InterchangeError, NotSafe, ReplicationRecord, Safe, SlashingDatabase,
 };
 use slog::{crit, error, info, warn, Logger};
 use slot_clock::SlotClock;
@@ -89,6 +90,7 @@ pub struct ValidatorStore<T, E: EthSpec> {
     validators: Arc<RwLock<InitializedValidators>>,
     slashing_protection: SlashingDatabase,
     slashing_protection_last_prune: Arc<Mutex<Epoch>>,
+    slashing_protection_replicator: Option<HttpReplicator>,
     genesis_validators_root: Hash256,
     spec: Arc<ChainSpec>,
     log: Logger,
@@ -124,6 +126,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             validators: Arc::new(RwLock::new(validators)),
             slashing_protection,
             slashing_protection_last_prune: Arc::new(Mutex::new(Epoch::new(0))),
+            slashing_protection_replicator: None,
             genesis_validators_root,
             spec: Arc::new(spec),
             log,
@@ -141,6 +144,14 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         }
     }
 
+    /// Replicate every new slashing protection record with `replicator` before signing.
+    ///
+    /// See `slashing_protection::replication` for details.
+    pub fn with_slashing_protection_replicator(mut self, replicator: HttpReplicator) -> Self {
+        self.slashing_protection_replicator = Some(replicator);
+        self
+    }
+
     /// Register all local validators in doppelganger protection to try and prevent instances of
     /// duplicate validators operating on the network at the same time.
     ///
@@ -612,11 +623,22 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         let slashing_status = if signing_method
             .requires_local_slashing_protection(self.enable_web3signer_slashing_protection)
         {
-            self.slashing_protection.check_and_insert_block_proposal(
+            let block_header = block.block_header();
+            match self.slashing_protection.check_and_insert_block_proposal(
                 &validator_pubkey,
-                &block.block_header(),
+                &block_header,
                 domain_hash,
-            )
+            ) {
+                Ok(Safe::Valid) => {
+                    self.replicate_slashing_protection(ReplicationRecord::block(
+                        validator_pubkey,
+                        block_header.slot,
+                        block_header.signing_root(domain_hash).into(),
+                    ))
+                    .await
+                }
+                other => other,
+            }
         } else {
             Ok(Safe::Valid)
         };
@@ -691,11 +713,22 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         let slashing_status = if signing_method
             .requires_local_slashing_protection(self.enable_web3signer_slashing_protection)
         {
-            self.slashing_protection.check_and_insert_attestation(
+            match self.slashing_protection.check_and_insert_attestation(
                 &validator_pubkey,
                 &attestation.data,
                 domain_hash,
-            )
+            ) {
+                Ok(Safe::Valid) => {
+                    self.replicate_slashing_protection(ReplicationRecord::attestation(
+                        validator_pubkey,
+                        attestation.data.source.epoch,
+                        attestation.data.target.epoch,
+                        attestation.data.signing_root(domain_hash).into(),
+                    ))
+                    .await
+                }
+                other => other,
+            }
         } else {
             Ok(Safe::Valid)
         };
@@ -1005,6 +1038,43 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         Ok(())
     }
 
+    /// Replicate a newly committed slashing protection record, if replication is enabled.
+    ///
+    /// The record has already been committed locally, so if replication fails it still prevents
+    /// conflicting messages from being signed, but the message itself must not be signed.
+    async fn replicate_slashing_protection(
+        &self,
+        record: ReplicationRecord,
+    ) -> Result<Safe, NotSafe> {
+        match &self.slashing_protection_replicator {
+            Some(replicator) => replicator
+                .replicate(&record)
+                .await
+                .map(|()| Safe::Valid)
+                .map_err(NotSafe::ReplicationFailed),
+            None => Ok(Safe::Valid),
+        }
+    }
+
+    /// Check and commit a slashing protection record replicated from another validator client.
+    ///
+    /// The record is refused if it was replicated from a validator client of another network.
+    pub fn apply_replicated_slashing_protection(
+        &self,
+        genesis_validators_root: Hash256,
+        record: &ReplicationRecord,
+    ) -> Result<Safe, String> {
+        if genesis_validators_root != self.genesis_validators_root {
+            return Err(format!(
+                "genesis validators root mismatch: expected {:?}, got {:?}",
+                self.genesis_validators_root, genesis_validators_root
+            ));
+        }
+        self.slashing_protection
+            .apply_replicated_record(record)
+            .map_err(|e| format!("{:?}", e))
+    }
+
     /// Export slashing protection data while also disabling the given keys in the database.
     ///
     /// If any key is unknown to the slashing protection database it will be silently omitted
@@ -0,0 +1,386 @@
+use super::common::*;
+use crate::move_validators::Validators;
+use crate::DumpConfig;
+use clap::{App, Arg, ArgMatches};
+use eth2::{
+    lighthouse_vc::std_types::{DeleteKeystoreStatus, DeleteKeystoresRequest},
+    SensitiveUrl,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+pub const CMD: &str = "delete";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const VALIDATORS_FLAG: &str = "validators";
+pub const SLASHING_PROTECTION_PATH_FLAG: &str = "slashing-protection-path";
+
+const NO_VALIDATORS_MSG: &str = "No validators present on validator client";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Deletes validators from a validator client using the HTTP API. The slashing \
+                protection data of the deleted validators is exported to a file in the \
+                EIP-3076 interchange format. The keystores are deleted from the validator \
+                client, use the \"export-keystores\" command to retain them.",
+        )
+        .arg(
+            Arg::with_name(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("A HTTP(S) address of a validator client using the keymanager-API.")
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATORS_FLAG)
+                .long(VALIDATORS_FLAG)
+                .value_name("STRING")
+                .help(
+                    "The validators to be deleted. Either a list of 0x-prefixed \
+                    validator pubkeys or the keyword \"all\".",
+                )
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(SLASHING_PROTECTION_PATH_FLAG)
+                .long(SLASHING_PROTECTION_PATH_FLAG)
+                .value_name("PATH_TO_JSON_FILE")
+                .help(
+                    "The path to a file which will be created to hold the slashing protection \
+                    data of the deleted validators. The file must not already exist.",
+                )
+                .required(true)
+                .takes_value(true),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct DeleteConfig {
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+    pub validators: Validators,
+    pub slashing_protection_path: PathBuf,
+}
+
+impl DeleteConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+            validators: Validators::from_cli(matches)?,
+            slashing_protection_path: clap_utils::parse_required(
+                matches,
+                SLASHING_PROTECTION_PATH_FLAG,
+            )?,
+        })
+    }
+}
+
+pub async fn cli_run<'a>(
+    matches: &'a ArgMatches<'a>,
+    dump_config: DumpConfig,
+) -> Result<(), String> {
+    let config = DeleteConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config).await
+    }
+}
+
+async fn run<'a>(config: DeleteConfig) -> Result<(), String> {
+    let DeleteConfig {
+        vc_url,
+        vc_token_path,
+        validators,
+        slashing_protection_path,
+    } = config;
+
+    // Check the file before deleting anything, the slashing protection data is only returned once.
+    if slashing_protection_path.exists() {
+        return Err(format!(
+            "The slashing protection file {:?} already exists",
+            slashing_protection_path
+        ));
+    }
+
+    let (http_client, keystores) = vc_http_client(vc_url.clone(), &vc_token_path).await?;
+
+    // Specific validators are requested even if they're not present on the validator client, so
+    // that the slashing protection data of validators deleted by an earlier, failed run can still
+    // be exported. The validator client reports them as `not_active`.
+    let pubkeys_to_delete = match validators {
+        Validators::All if keystores.is_empty() => return Err(NO_VALIDATORS_MSG.to_string()),
+        Validators::Specific(pubkeys) => pubkeys,
+        validators => validators.select(&keystores, &vc_url)?,
+    };
+    let count = pubkeys_to_delete.len();
+
+    // Create the file before deleting anything, so that the slashing protection data isn't lost
+    // if the file can't be written.
+    let mut slashing_protection_file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&slashing_protection_path)
+        .map_err(|e| format!("Failed to open {:?}: {:?}", slashing_protection_path, e))?;
+
+    // Delete all the validators in a single request so their slashing protection data is
+    // exported as a single interchange file.
+    let request = DeleteKeystoresRequest {
+        pubkeys: pubkeys_to_delete.clone(),
+    };
+    let response = match http_client.delete_keystores(&request).await {
+        Ok(response) => response,
+        Err(e) => {
+            // Remove the empty file so that the command can be run again.
+            let _ = fs::remove_file(&slashing_protection_path);
+            let pubkeys = pubkeys_to_delete
+                .iter()
+                .map(|pubkey| pubkey.as_hex_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            eprintln!(
+                "There was an error deleting validators and it's unclear which validators were \
+                deleted. The slashing protection data of any deleted validators can be exported \
+                by running this command again with --{} {}",
+                VALIDATORS_FLAG, pubkeys
+            );
+            return Err(format!("Deleting validators failed with {:?}", e));
+        }
+    };
+
+    eprintln!("Writing {:?}", slashing_protection_path);
+    serde_json::to_writer(&mut slashing_protection_file, &response.slashing_protection).map_err(
+        |e| {
+            format!(
+                "Failed to write JSON to {:?}: {:?}",
+                slashing_protection_path, e
+            )
+        },
+    )?;
+
+    if response.data.len() != count {
+        return Err(format!(
+            "Validator client returned {} statuses for {} validators",
+            response.data.len(),
+            count
+        ));
+    }
+
+    let mut failed = 0;
+    for (pubkey, status) in pubkeys_to_delete.iter().zip(&response.data) {
+        match status.status {
+            DeleteKeystoreStatus::Deleted => eprintln!("Deleted validator {:?}", pubkey),
+            DeleteKeystoreStatus::NotActive => eprintln!(
+                "Validator {:?} was not active, its slashing protection data was exported",
+                pubkey
+            ),
+            DeleteKeystoreStatus::NotFound => {
+                eprintln!("Validator {:?} was not found", pubkey);
+                failed += 1;
+            }
+            DeleteKeystoreStatus::Error => {
+                eprintln!(
+                    "Validator {:?} was not deleted: {:?}",
+                    pubkey, status.message
+                );
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!(
+            "Failed to delete {} of {} validators",
+            failed, count
+        ));
+    }
+
+    eprintln!("Done.");
+
+    Ok(())
+}
+
+// The tests use crypto and are too slow in debug.
+#[cfg(not(debug_assertions))]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::import_validators::tests::TestBuilder as ImportTestBuilder;
+    use eth2::lighthouse_vc::std_types::Interchange;
+    use std::fs;
+    use tempfile::{tempdir, TempDir};
+    use types::PublicKeyBytes;
+    use validator_client::http_api::test_utils::ApiTester;
+
+    const VC_TOKEN_FILE_NAME: &str = "vc_token.json";
+    const SLASHING_PROTECTION_FILE_NAME: &str = "slashing_protection.json";
+
+    struct TestBuilder {
+        vc: ApiTester,
+        dir: TempDir,
+    }
+
+    impl TestBuilder {
+        async fn new() -> Self {
+            Self {
+                vc: ApiTester::new().await,
+                dir: tempdir().unwrap(),
+            }
+        }
+
+        async fn with_validators(count: u32) -> Self {
+            let import_test_result = ImportTestBuilder::new()
+                .await
+                .create_validators(count, 0)
+                .await
+                .run_test()
+                .await;
+            assert!(import_test_result.result.is_ok());
+            Self {
+                vc: import_test_result.vc,
+                dir: tempdir().unwrap(),
+            }
+        }
+
+        async fn run_test<F>(self, gen_validators_enum: F) -> Result<(), String>
+        where
+            F: Fn(&[PublicKeyBytes]) -> Validators,
+        {
+            let vc_token_path = self.dir.path().join(VC_TOKEN_FILE_NAME);
+            fs::write(&vc_token_path, &self.vc.api_token).unwrap();
+            let slashing_protection_path = self.dir.path().join(SLASHING_PROTECTION_FILE_NAME);
+
+            let initial_pubkeys: Vec<_> = self
+                .vc
+                .client
+                .get_keystores()
+                .await
+                .unwrap()
+                .data
+                .into_iter()
+                .map(|k| k.validating_pubkey)
+                .collect();
+            let validators = gen_validators_enum(&initial_pubkeys);
+            let deleted_pubkeys = match &validators {
+                Validators::All => initial_pubkeys.clone(),
+                Validators::Specific(pubkeys) => pubkeys.clone(),
+                Validators::Count(_) => unreachable!("delete does not support --count"),
+            };
+
+            let result = run(DeleteConfig {
+                vc_url: self.vc.url.clone(),
+                vc_token_path,
+                validators,
+                slashing_protection_path: slashing_protection_path.clone(),
+            })
+            .await;
+
+            if result.is_ok() {
+                self.vc.ensure_key_cache_consistency().await;
+
+                let final_pubkeys: Vec<_> = self
+                    .vc
+                    .client
+                    .get_keystores()
+                    .await
+                    .unwrap()
+                    .data
+                    .into_iter()
+                    .map(|k| k.validating_pubkey)
+                    .collect();
+                assert_eq!(
+                    final_pubkeys.len(),
+                    initial_pubkeys.len() - deleted_pubkeys.len(),
+                    "the deleted validators should be removed from the vc"
+                );
+                for pubkey in &deleted_pubkeys {
+                    assert!(!final_pubkeys.contains(pubkey));
+                }
+
+                let interchange: Interchange =
+                    serde_json::from_str(&fs::read_to_string(&slashing_protection_path).unwrap())
+                        .unwrap();
+                assert_eq!(interchange.data.len(), deleted_pubkeys.len());
+                for record in &interchange.data {
+                    assert!(deleted_pubkeys.contains(&record.pubkey));
+                }
+            }
+
+            result
+        }
+    }
+
+    #[tokio::test]
+    async fn no_validators() {
+        let result = TestBuilder::new().await.run_test(|_| Validators::All).await;
+        assert_eq!(result, Err(NO_VALIDATORS_MSG.to_string()));
+    }
+
+    #[tokio::test]
+    async fn three_validators_delete_all() {
+        TestBuilder::with_validators(3)
+            .await
+            .run_test(|_| Validators::All)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn three_validators_delete_two() {
+        TestBuilder::with_validators(3)
+            .await
+            .run_test(|pubkeys| Validators::Specific(pubkeys[0..2].to_vec()))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn rerun_exports_deleted_validators() {
+        let builder = TestBuilder::with_validators(2).await;
+        let vc_token_path = builder.dir.path().join(VC_TOKEN_FILE_NAME);
+        fs::write(&vc_token_path, &builder.vc.api_token).unwrap();
+        let pubkey = builder.vc.client.get_keystores().await.unwrap().data[0].validating_pubkey;
+
+        for name in ["first.json", "second.json"] {
+            let slashing_protection_path = builder.dir.path().join(name);
+            run(DeleteConfig {
+                vc_url: builder.vc.url.clone(),
+                vc_token_path: vc_token_path.clone(),
+                validators: Validators::Specific(vec![pubkey]),
+                slashing_protection_path: slashing_protection_path.clone(),
+            })
+            .await
+            .unwrap();
+
+            // The validator is no longer on the VC after the first run, but its slashing
+            // protection data is exported again.
+            let interchange: Interchange =
+                serde_json::from_str(&fs::read_to_string(&slashing_protection_path).unwrap())
+                    .unwrap();
+            assert_eq!(interchange.data.len(), 1);
+            assert_eq!(interchange.data[0].pubkey, pubkey);
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_validator() {
+        TestBuilder::with_validators(1)
+            .await
+            .run_test(|_| Validators::Specific(vec![PublicKeyBytes::empty()]))
+            .await
+            .unwrap_err();
+    }
+}
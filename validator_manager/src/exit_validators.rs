@@ -0,0 +1,271 @@
+use super::common::*;
+use crate::move_validators::Validators;
+use crate::DumpConfig;
+use clap::{App, Arg, ArgMatches};
+use eth2::{BeaconNodeHttpClient, SensitiveUrl, Timeouts};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use types::{Epoch, SignedVoluntaryExit};
+
+pub const CMD: &str = "exit";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const VALIDATORS_FLAG: &str = "validators";
+pub const EXIT_EPOCH_FLAG: &str = "exit-epoch";
+pub const BEACON_NODE_FLAG: &str = "beacon-node";
+pub const NO_CONFIRMATION_FLAG: &str = "no-confirmation";
+
+pub const CONFIRMATION_PHRASE: &str = "Exit my validator";
+pub const WEBSITE_URL: &str = "https://lighthouse-book.sigmaprime.io/voluntary-exit.html";
+
+const BEACON_NODE_HTTP_TIMEOUT: Duration = Duration::from_secs(12);
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Signs voluntary exits for validators using the HTTP API of the validator client. \
+                The signed exits are printed to stdout as JSON. If a beacon node is provided \
+                the exits are also published to the network. Publishing an exit is \
+                irreversible, an exited validator can never be activated again.",
+        )
+        .arg(
+            Arg::with_name(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("A HTTP(S) address of a validator client using the keymanager-API.")
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATORS_FLAG)
+                .long(VALIDATORS_FLAG)
+                .value_name("STRING")
+                .help(
+                    "The validators to be exited. Either a list of 0x-prefixed \
+                    validator pubkeys or the keyword \"all\".",
+                )
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(EXIT_EPOCH_FLAG)
+                .long(EXIT_EPOCH_FLAG)
+                .value_name("EPOCH")
+                .help(
+                    "The epoch of the voluntary exits. Defaults to the current epoch of the \
+                    validator client.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(BEACON_NODE_FLAG)
+                .long(BEACON_NODE_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "A HTTP(S) address of a beacon node using the beacon-API. If this value \
+                    is provided, the signed exits are published to the network via this \
+                    beacon node, after confirming the exits by entering the exit phrase.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(NO_CONFIRMATION_FLAG)
+                .long(NO_CONFIRMATION_FLAG)
+                .help(
+                    "Publishes the exits without prompting for confirmation that you understand \
+                    the implications of a voluntary exit. This should be used with caution.",
+                )
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name(STDIN_INPUTS_FLAG)
+                .takes_value(false)
+                .hidden(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty."),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ExitConfig {
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+    pub validators: Validators,
+    pub exit_epoch: Option<Epoch>,
+    pub bn_url: Option<SensitiveUrl>,
+    pub no_confirmation: bool,
+    pub stdin_inputs: bool,
+}
+
+impl ExitConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+            validators: Validators::from_cli(matches)?,
+            exit_epoch: clap_utils::parse_optional(matches, EXIT_EPOCH_FLAG)?,
+            bn_url: clap_utils::parse_optional(matches, BEACON_NODE_FLAG)?,
+            no_confirmation: matches.is_present(NO_CONFIRMATION_FLAG),
+            stdin_inputs: cfg!(windows) || matches.is_present(STDIN_INPUTS_FLAG),
+        })
+    }
+}
+
+pub async fn cli_run<'a>(
+    matches: &'a ArgMatches<'a>,
+    dump_config: DumpConfig,
+) -> Result<(), String> {
+    let config = ExitConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        let exits = run(config).await?;
+        let json = serde_json::to_string_pretty(&exits)
+            .map_err(|e| format!("Unable to serialize voluntary exits: {:?}", e))?;
+        println!("{}", json);
+        Ok(())
+    }
+}
+
+async fn run<'a>(config: ExitConfig) -> Result<Vec<SignedVoluntaryExit>, String> {
+    let ExitConfig {
+        vc_url,
+        vc_token_path,
+        validators,
+        exit_epoch,
+        bn_url,
+        no_confirmation,
+        stdin_inputs,
+    } = config;
+
+    let (http_client, keystores) = vc_http_client(vc_url.clone(), &vc_token_path).await?;
+    let pubkeys_to_exit = validators.select(&keystores, &vc_url)?;
+
+    // Publishing an exit can't be undone, confirm it before signing anything.
+    if bn_url.is_some() && !no_confirmation {
+        eprintln!(
+            "Publishing voluntary exits for {} validators\n",
+            pubkeys_to_exit.len()
+        );
+        eprintln!("WARNING: THIS IS AN IRREVERSIBLE OPERATION\n");
+        eprintln!(
+            "PLEASE VISIT {} TO MAKE SURE YOU UNDERSTAND THE IMPLICATIONS OF A VOLUNTARY EXIT.",
+            WEBSITE_URL
+        );
+        eprintln!("Enter the exit phrase from the above URL to confirm the voluntary exits: ");
+        if account_utils::read_input_from_user(stdin_inputs)? != CONFIRMATION_PHRASE {
+            return Err(
+                "Did not publish voluntary exits. Please check that you entered the correct \
+                exit phrase."
+                    .to_string(),
+            );
+        }
+    }
+
+    // Sign all the exits before publishing any, so that a validator that can't be exited doesn't
+    // leave the others partially exited.
+    let mut exits = Vec::with_capacity(pubkeys_to_exit.len());
+    for pubkey in &pubkeys_to_exit {
+        let exit = http_client
+            .post_validator_voluntary_exit(pubkey, exit_epoch)
+            .await
+            .map_err(|e| format!("Failed to sign voluntary exit for {:?}: {:?}", pubkey, e))?
+            .data;
+        eprintln!(
+            "Signed voluntary exit for validator {:?} (index {}) at epoch {}",
+            pubkey, exit.message.validator_index, exit.message.epoch
+        );
+        exits.push(exit);
+    }
+
+    if let Some(bn_url) = bn_url {
+        let bn_http_client =
+            BeaconNodeHttpClient::new(bn_url, Timeouts::set_all(BEACON_NODE_HTTP_TIMEOUT));
+        for (pubkey, exit) in pubkeys_to_exit.iter().zip(&exits) {
+            bn_http_client
+                .post_beacon_pool_voluntary_exits(exit)
+                .await
+                .map_err(|e| {
+                    format!("Failed to publish voluntary exit for {:?}: {:?}", pubkey, e)
+                })?;
+            eprintln!("Published voluntary exit for validator {:?}", pubkey);
+        }
+    }
+
+    eprintln!("Done.");
+
+    Ok(exits)
+}
+
+// The tests use crypto and are too slow in debug.
+#[cfg(not(debug_assertions))]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::import_validators::tests::TestBuilder as ImportTestBuilder;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const VC_TOKEN_FILE_NAME: &str = "vc_token.json";
+
+    #[tokio::test]
+    async fn sign_exits_without_publishing() {
+        let import_test_result = ImportTestBuilder::new()
+            .await
+            .create_validators(2, 0)
+            .await
+            .run_test()
+            .await;
+        assert!(import_test_result.result.is_ok());
+        let vc = import_test_result.vc;
+
+        let pubkeys: Vec<_> = vc
+            .client
+            .get_keystores()
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .map(|k| k.validating_pubkey)
+            .collect();
+        // The validator client learns validator indices from the beacon node, there is no beacon
+        // node in this test so set them manually.
+        for (index, pubkey) in pubkeys.iter().enumerate() {
+            vc.initialized_validators
+                .write()
+                .set_index(pubkey, index as u64);
+        }
+
+        let dir = tempdir().unwrap();
+        let vc_token_path = dir.path().join(VC_TOKEN_FILE_NAME);
+        fs::write(&vc_token_path, &vc.api_token).unwrap();
+
+        let exit_epoch = Epoch::new(42);
+        let exits = run(ExitConfig {
+            vc_url: vc.url.clone(),
+            vc_token_path,
+            validators: Validators::Specific(pubkeys.clone()),
+            exit_epoch: Some(exit_epoch),
+            bn_url: None,
+            no_confirmation: false,
+            stdin_inputs: false,
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(exits.len(), pubkeys.len());
+        for (index, exit) in exits.iter().enumerate() {
+            assert_eq!(exit.message.validator_index, index as u64);
+            assert_eq!(exit.message.epoch, exit_epoch);
+        }
+    }
+}
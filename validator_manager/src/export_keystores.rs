@@ -0,0 +1,344 @@
+use super::common::*;
+use crate::create_validators::VALIDATORS_FILENAME;
+use crate::move_validators::{PasswordSource, Validators};
+use crate::DumpConfig;
+use clap::{App, Arg, ArgMatches};
+use eth2::{
+    lighthouse_vc::{
+        http_client::ValidatorClientHttpClient,
+        std_types::{DeleteKeystoreStatus, DeleteKeystoresRequest, InterchangeJsonStr, Status},
+        types::{ExportKeystoresResponse, SingleExportKeystoresResponse},
+    },
+    SensitiveUrl,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use types::PublicKeyBytes;
+
+pub const CMD: &str = "export-keystores";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const VALIDATORS_FLAG: &str = "validators";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+
+const NO_VALIDATORS_MSG: &str = "No validators present on validator client";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Exports validators from a validator client using the HTTP API. The validators \
+                are removed from the validator client and written, along with their keystore \
+                passwords and slashing protection data, to a JSON file which can be uploaded \
+                to another validator client using the \"import\" command. This command only \
+                supports validators signing via a keystore on the local file system.",
+        )
+        .arg(
+            Arg::with_name(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("A HTTP(S) address of a validator client using the keymanager-API.")
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATORS_FLAG)
+                .long(VALIDATORS_FLAG)
+                .value_name("STRING")
+                .help(
+                    "The validators to be exported. Either a list of 0x-prefixed \
+                    validator pubkeys or the keyword \"all\".",
+                )
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("DIRECTORY")
+                .help(
+                    "The path to a directory where the validators file will be created. \
+                    The directory will be created if it does not exist. The file contains \
+                    keystore passwords and should be stored securely.",
+                )
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(STDIN_INPUTS_FLAG)
+                .takes_value(false)
+                .hidden(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty."),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ExportConfig {
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+    pub validators: Validators,
+    pub output_path: PathBuf,
+    pub password_source: PasswordSource,
+}
+
+impl ExportConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+            validators: Validators::from_cli(matches)?,
+            output_path: clap_utils::parse_required(matches, OUTPUT_PATH_FLAG)?,
+            password_source: PasswordSource::Interactive {
+                stdin_inputs: cfg!(windows) || matches.is_present(STDIN_INPUTS_FLAG),
+            },
+        })
+    }
+}
+
+pub async fn cli_run<'a>(
+    matches: &'a ArgMatches<'a>,
+    dump_config: DumpConfig,
+) -> Result<(), String> {
+    let config = ExportConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config).await
+    }
+}
+
+async fn run<'a>(config: ExportConfig) -> Result<(), String> {
+    let ExportConfig {
+        vc_url,
+        vc_token_path,
+        validators,
+        output_path,
+        mut password_source,
+    } = config;
+
+    if !output_path.exists() {
+        fs::create_dir(&output_path)
+            .map_err(|e| format!("Failed to create {:?} directory: {:?}", output_path, e))?;
+    } else if !output_path.is_dir() {
+        return Err(format!("{:?} must be a directory", output_path));
+    }
+
+    let validators_path = output_path.join(VALIDATORS_FILENAME);
+    if validators_path.exists() {
+        return Err(format!(
+            "{:?} already exists, refusing to overwrite",
+            validators_path
+        ));
+    }
+
+    let (http_client, keystores) = vc_http_client(vc_url.clone(), &vc_token_path).await?;
+
+    if keystores.is_empty() {
+        return Err(NO_VALIDATORS_MSG.to_string());
+    }
+
+    let pubkeys_to_export = validators.select(&keystores, &vc_url)?;
+
+    let mut exported = Vec::with_capacity(pubkeys_to_export.len());
+    let result = export_validators(
+        &http_client,
+        &pubkeys_to_export,
+        &mut password_source,
+        &mut exported,
+    )
+    .await;
+
+    // Write the validators that were removed from the VC even if a later validator failed,
+    // otherwise they would need to be recovered from a mnemonic.
+    if !exported.is_empty() {
+        write_to_json_file(&validators_path, &exported)?;
+    }
+
+    result?;
+
+    eprintln!("Done.");
+
+    Ok(())
+}
+
+/// Removes each of the `pubkeys` from the VC, pushing them to `exported` as they're removed.
+async fn export_validators(
+    http_client: &ValidatorClientHttpClient,
+    pubkeys: &[PublicKeyBytes],
+    password_source: &mut PasswordSource,
+    exported: &mut Vec<ValidatorSpecification>,
+) -> Result<(), String> {
+    let count = pubkeys.len();
+    for (i, pubkey) in pubkeys.iter().enumerate() {
+        let request = DeleteKeystoresRequest {
+            pubkeys: vec![*pubkey],
+        };
+        let ExportKeystoresResponse {
+            mut data,
+            slashing_protection,
+        } = http_client
+            .delete_lighthouse_keystores(&request)
+            .await
+            .map_err(|e| format!("Exporting {:?} failed with {:?}", pubkey, e))?;
+
+        if data.len() != 1 {
+            return Err(format!(
+                "Unexpected number of exported validators from VC: {}",
+                data.len()
+            ));
+        }
+
+        let (voting_keystore, voting_keystore_password) = match data.pop() {
+            Some(SingleExportKeystoresResponse {
+                status:
+                    Status {
+                        status: DeleteKeystoreStatus::Deleted,
+                        ..
+                    },
+                validating_keystore: Some(keystore),
+                validating_keystore_password,
+            }) => {
+                let password = match validating_keystore_password {
+                    Some(password) => password,
+                    None => {
+                        eprintln!(
+                            "Validator {:?} requires a password, please provide it to continue \
+                            exporting validators. Failing to provide the correct password will \
+                            result in the keystore being removed from the VC without being \
+                            exported.",
+                            pubkey
+                        );
+                        password_source
+                            .read_keystore_password(pubkey, &keystore)
+                            .await
+                    }
+                };
+                (keystore, password)
+            }
+            Some(SingleExportKeystoresResponse {
+                status: Status { status, message },
+                ..
+            }) => {
+                return Err(format!(
+                    "Validator {:?} was not exported, VC returned status {:?} with message {:?}",
+                    pubkey, status, message
+                ));
+            }
+            None => return Err("VC responded with zero exported validators".to_string()),
+        };
+
+        exported.push(ValidatorSpecification {
+            voting_keystore,
+            voting_keystore_password,
+            slashing_protection: Some(InterchangeJsonStr(slashing_protection)),
+            fee_recipient: None,
+            gas_limit: None,
+            builder_proposals: None,
+            builder_boost_factor: None,
+            prefer_builder_proposals: None,
+            enabled: None,
+        });
+        eprintln!("Exported keystore {} of {}", i + 1, count);
+    }
+
+    Ok(())
+}
+
+// The tests use crypto and are too slow in debug.
+#[cfg(not(debug_assertions))]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::import_validators::tests::TestBuilder as ImportTestBuilder;
+    use tempfile::tempdir;
+
+    const VC_TOKEN_FILE_NAME: &str = "vc_token.json";
+
+    async fn export_and_import<F>(count: u32, gen_validators_enum: F)
+    where
+        F: Fn(&[PublicKeyBytes]) -> Validators,
+    {
+        let import_test_result = ImportTestBuilder::new()
+            .await
+            .create_validators(count, 0)
+            .await
+            .run_test()
+            .await;
+        assert!(import_test_result.result.is_ok());
+        let vc = import_test_result.vc;
+
+        let dir = tempdir().unwrap();
+        let vc_token_path = dir.path().join(VC_TOKEN_FILE_NAME);
+        fs::write(&vc_token_path, &vc.api_token).unwrap();
+        let output_path = dir.path().join("export");
+
+        let initial_keystores = vc.client.get_keystores().await.unwrap().data;
+        let initial_pubkeys: Vec<_> = initial_keystores
+            .iter()
+            .map(|k| k.validating_pubkey)
+            .collect();
+        let validators = gen_validators_enum(&initial_pubkeys);
+        let exported_pubkeys = match &validators {
+            Validators::All => initial_pubkeys.clone(),
+            Validators::Specific(pubkeys) => pubkeys.clone(),
+            Validators::Count(_) => unreachable!("export does not support --count"),
+        };
+
+        run(ExportConfig {
+            vc_url: vc.url.clone(),
+            vc_token_path,
+            validators,
+            output_path: output_path.clone(),
+            password_source: PasswordSource::Testing(<_>::default()),
+        })
+        .await
+        .unwrap();
+
+        vc.ensure_key_cache_consistency().await;
+        let final_keystores = vc.client.get_keystores().await.unwrap().data;
+        assert_eq!(
+            final_keystores.len(),
+            initial_keystores.len() - exported_pubkeys.len(),
+            "the exported validators should be removed from the vc"
+        );
+
+        let exported: Vec<ValidatorSpecification> = serde_json::from_str(
+            &fs::read_to_string(output_path.join(VALIDATORS_FILENAME)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(exported.len(), exported_pubkeys.len());
+
+        // The exported validators can be imported back to the VC.
+        for validator in exported {
+            let pubkey = validator.voting_keystore.public_key().unwrap().into();
+            assert!(exported_pubkeys.contains(&pubkey));
+            assert!(!final_keystores
+                .iter()
+                .any(|k| k.validating_pubkey == pubkey));
+            validator.upload(&vc.client, false).await.unwrap();
+        }
+        assert_eq!(
+            vc.client.get_keystores().await.unwrap().data.len(),
+            initial_keystores.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn three_validators_export_all() {
+        export_and_import(3, |_| Validators::All).await;
+    }
+
+    #[tokio::test]
+    async fn three_validators_export_one() {
+        export_and_import(3, |pubkeys| Validators::Specific(pubkeys[0..1].to_vec())).await;
+    }
+}
@@ -8,7 +8,11 @@ use types::EthSpec;
 
 pub mod common;
 pub mod create_validators;
+pub mod delete_validators;
+pub mod exit_validators;
+pub mod export_keystores;
 pub mod import_validators;
+pub mod list_validators;
 pub mod move_validators;
 
 pub const CMD: &str = "validator_manager";
@@ -45,6 +49,10 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         .subcommand(create_validators::cli_app())
         .subcommand(import_validators::cli_app())
         .subcommand(move_validators::cli_app())
+        .subcommand(list_validators::cli_app())
+        .subcommand(delete_validators::cli_app())
+        .subcommand(exit_validators::cli_app())
+        .subcommand(export_keystores::cli_app())
 }
 
 /// Run the account manager, returning an error if the operation did not succeed.
@@ -72,6 +80,18 @@ pub fn run<'a, T: EthSpec>(matches: &'a ArgMatches<'a>, env: Environment<T>) ->
                     (move_validators::CMD, Some(matches)) => {
                         move_validators::cli_run(matches, dump_config).await
                     }
+                    (list_validators::CMD, Some(matches)) => {
+                        list_validators::cli_run(matches, dump_config).await
+                    }
+                    (delete_validators::CMD, Some(matches)) => {
+                        delete_validators::cli_run(matches, dump_config).await
+                    }
+                    (exit_validators::CMD, Some(matches)) => {
+                        exit_validators::cli_run(matches, dump_config).await
+                    }
+                    (export_keystores::CMD, Some(matches)) => {
+                        export_keystores::cli_run(matches, dump_config).await
+                    }
                     ("", _) => Err("No command supplied. See --help.".to_string()),
                     (unknown, _) => Err(format!(
                         "{} is not a valid {} command. See --help.",
@@ -0,0 +1,144 @@
+use super::common::*;
+use crate::DumpConfig;
+use clap::{App, Arg, ArgMatches};
+use eth2::{
+    lighthouse_vc::std_types::{SingleKeystoreResponse, SingleListRemotekeysResponse},
+    SensitiveUrl,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub const CMD: &str = "list";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Lists the validators on a validator client using the HTTP API. Validators \
+                signing via a keystore on the local file system and validators signing via a \
+                remote signer (e.g., Web3Signer) are listed separately. The list is printed to \
+                stdout as JSON.",
+        )
+        .arg(
+            Arg::with_name(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("A HTTP(S) address of a validator client using the keymanager-API.")
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .takes_value(true),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ListConfig {
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+}
+
+impl ListConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+        })
+    }
+}
+
+/// The validators present on a validator client.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorList {
+    pub keystores: Vec<SingleKeystoreResponse>,
+    pub remote_keys: Vec<SingleListRemotekeysResponse>,
+}
+
+pub async fn cli_run<'a>(
+    matches: &'a ArgMatches<'a>,
+    dump_config: DumpConfig,
+) -> Result<(), String> {
+    let config = ListConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        let validators = run(config).await?;
+        let json = serde_json::to_string_pretty(&validators)
+            .map_err(|e| format!("Unable to serialize validator list: {:?}", e))?;
+        println!("{}", json);
+        Ok(())
+    }
+}
+
+async fn run<'a>(config: ListConfig) -> Result<ValidatorList, String> {
+    let ListConfig {
+        vc_url,
+        vc_token_path,
+    } = config;
+
+    let (http_client, keystores) = vc_http_client(vc_url, &vc_token_path).await?;
+
+    let remote_keys = http_client
+        .get_remotekeys()
+        .await
+        .map_err(|e| format!("Failed to list remote keys on VC: {:?}", e))?
+        .data;
+
+    Ok(ValidatorList {
+        keystores,
+        remote_keys,
+    })
+}
+
+// The tests use crypto and are too slow in debug.
+#[cfg(not(debug_assertions))]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::import_validators::tests::TestBuilder as ImportTestBuilder;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const VC_TOKEN_FILE_NAME: &str = "vc_token.json";
+
+    async fn list_validators(count: u32) -> ValidatorList {
+        let import_test_result = ImportTestBuilder::new()
+            .await
+            .create_validators(count, 0)
+            .await
+            .run_test()
+            .await;
+        assert!(import_test_result.result.is_ok());
+        let vc = import_test_result.vc;
+
+        let dir = tempdir().unwrap();
+        let vc_token_path = dir.path().join(VC_TOKEN_FILE_NAME);
+        fs::write(&vc_token_path, &vc.api_token).unwrap();
+
+        let validators = run(ListConfig {
+            vc_url: vc.url.clone(),
+            vc_token_path,
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            validators.keystores,
+            vc.client.get_keystores().await.unwrap().data
+        );
+        validators
+    }
+
+    #[tokio::test]
+    async fn three_validators() {
+        let validators = list_validators(3).await;
+        assert_eq!(validators.keystores.len(), 3);
+        assert!(validators.remote_keys.is_empty());
+    }
+}
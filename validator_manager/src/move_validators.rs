@@ -6,7 +6,7 @@ use eth2::{
     lighthouse_vc::{
         std_types::{
             DeleteKeystoreStatus, DeleteKeystoresRequest, ImportKeystoreStatus, InterchangeJsonStr,
-            Status,
+            KeystoreJsonStr, SingleKeystoreResponse, Status,
         },
         types::{ExportKeystoresResponse, SingleExportKeystoresResponse},
     },
@@ -64,6 +64,36 @@ impl PasswordSource {
                 .into()),
         }
     }
+
+    /// Reads the password for `keystore` from the user, retrying until the password decrypts
+    /// the keystore.
+    pub(crate) async fn read_keystore_password(
+        &mut self,
+        pubkey: &PublicKeyBytes,
+        keystore: &KeystoreJsonStr,
+    ) -> ZeroizeString {
+        loop {
+            match self.read_password(pubkey) {
+                Ok(password) => {
+                    if let Err(e) = keystore.decrypt_keypair(password.as_ref()) {
+                        eprintln!("Failed to decrypt keystore: {:?}", e);
+                    } else {
+                        break password;
+                    }
+                }
+                Err(e) => {
+                    eprintln!(
+                        "Retrying after error: {:?}. If this error persists the user will need to \
+                        manually recover their keystore for validator {:?} from the mnemonic.",
+                        e, pubkey
+                    );
+                }
+            }
+
+            // Add a sleep here to prevent spamming the console.
+            sleep(Duration::from_secs(1)).await;
+        }
+    }
 }
 
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
@@ -205,23 +235,10 @@ pub enum Validators {
     Specific(Vec<PublicKeyBytes>),
 }
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
-pub struct MoveConfig {
-    pub src_vc_url: SensitiveUrl,
-    pub src_vc_token_path: PathBuf,
-    pub dest_vc_url: SensitiveUrl,
-    pub dest_vc_token_path: PathBuf,
-    pub validators: Validators,
-    pub builder_proposals: Option<bool>,
-    pub builder_boost_factor: Option<u64>,
-    pub prefer_builder_proposals: Option<bool>,
-    pub fee_recipient: Option<Address>,
-    pub gas_limit: Option<u64>,
-    pub password_source: PasswordSource,
-}
-
-impl MoveConfig {
-    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+impl Validators {
+    /// Parses the `--validators` and `--count` flags. Subcommands which don't define `--count`
+    /// only accept `--validators`.
+    pub fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
         let count_flag = clap_utils::parse_optional(matches, COUNT_FLAG)?;
         let validators_flag = matches.value_of(VALIDATORS_FLAG);
         let validators = match (count_flag, validators_flag) {
@@ -241,6 +258,78 @@ impl MoveConfig {
                 Err("Cannot supply both --{VALIDATORS_FLAG} and --{COUNT_FLAG}.")?
             }
         };
+        Ok(validators)
+    }
+
+    /// Returns the public keys of the validators on a validator client with `keystores` that are
+    /// selected by `self`.
+    pub fn select(
+        self,
+        keystores: &[SingleKeystoreResponse],
+        vc_url: &SensitiveUrl,
+    ) -> Result<Vec<PublicKeyBytes>, String> {
+        match self {
+            Validators::All => Ok(keystores.iter().map(|v| v.validating_pubkey).collect()),
+            Validators::Count(count) => {
+                let mut viable_pubkeys: Vec<_> = keystores
+                    .iter()
+                    .filter(|v| !v.readonly.unwrap_or(true))
+                    .map(|v| v.validating_pubkey)
+                    .collect();
+                viable_pubkeys.sort_unstable_by_key(PublicKeyBytes::serialize);
+                viable_pubkeys
+                    .get(0..count)
+                    .ok_or_else(|| {
+                        format!(
+                            "Cannot select {} keystores since the validator client only has {} \
+                            keystores which are not read-only.",
+                            count,
+                            viable_pubkeys.len()
+                        )
+                    })
+                    .map(<[_]>::to_vec)
+            }
+            Validators::Specific(request_pubkeys) => {
+                let request_pubkeys_set: HashSet<_> = request_pubkeys.iter().collect();
+                let vc_pubkeys_set: HashSet<_> =
+                    keystores.iter().map(|v| &v.validating_pubkey).collect();
+                let difference = request_pubkeys_set
+                    .difference(&vc_pubkeys_set)
+                    .collect::<Vec<_>>();
+                if !difference.is_empty() {
+                    for pk in &difference {
+                        eprintln!("{:?} is not present on {:?}", pk, vc_url);
+                    }
+                    return Err(format!(
+                        "{} validators not found on {:?}",
+                        difference.len(),
+                        vc_url
+                    ));
+                }
+                Ok(request_pubkeys)
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MoveConfig {
+    pub src_vc_url: SensitiveUrl,
+    pub src_vc_token_path: PathBuf,
+    pub dest_vc_url: SensitiveUrl,
+    pub dest_vc_token_path: PathBuf,
+    pub validators: Validators,
+    pub builder_proposals: Option<bool>,
+    pub builder_boost_factor: Option<u64>,
+    pub prefer_builder_proposals: Option<bool>,
+    pub fee_recipient: Option<Address>,
+    pub gas_limit: Option<u64>,
+    pub password_source: PasswordSource,
+}
+
+impl MoveConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        let validators = Validators::from_cli(matches)?;
 
         Ok(Self {
             src_vc_url: clap_utils::parse_required(matches, SRC_VC_URL_FLAG)?,
@@ -308,47 +397,7 @@ async fn run<'a>(config: MoveConfig) -> Result<(), String> {
         return Err(NO_VALIDATORS_MSG.to_string());
     }
 
-    let pubkeys_to_move = match validators {
-        Validators::All => src_keystores.iter().map(|v| v.validating_pubkey).collect(),
-        Validators::Count(count) => {
-            let mut viable_pubkeys: Vec<_> = src_keystores
-                .iter()
-                .filter(|v| !v.readonly.unwrap_or(true))
-                .map(|v| v.validating_pubkey)
-                .collect();
-            viable_pubkeys.sort_unstable_by_key(PublicKeyBytes::serialize);
-            viable_pubkeys
-                .get(0..count)
-                .ok_or_else(|| {
-                    format!(
-                        "Cannot move {} keystores since source validator client only has {} \
-                        keystores which are able to be moved (not read-only).",
-                        count,
-                        viable_pubkeys.len()
-                    )
-                })?
-                .to_vec()
-        }
-        Validators::Specific(request_pubkeys) => {
-            let request_pubkeys_set: HashSet<_> = request_pubkeys.iter().collect();
-            let src_pubkeys_set: HashSet<_> =
-                src_keystores.iter().map(|v| &v.validating_pubkey).collect();
-            let difference = request_pubkeys_set
-                .difference(&src_pubkeys_set)
-                .collect::<Vec<_>>();
-            if !difference.is_empty() {
-                for pk in &difference {
-                    eprintln!("{:?} is not present on {:?}", pk, src_vc_url);
-                }
-                return Err(format!(
-                    "{} validators not found on {:?}",
-                    difference.len(),
-                    src_vc_url
-                ));
-            }
-            request_pubkeys
-        }
-    };
+    let pubkeys_to_move = validators.select(&src_keystores, &src_vc_url)?;
 
     let src_keystores_map: HashMap<_, _> = src_keystores
         .iter()
@@ -443,29 +492,10 @@ async fn run<'a>(config: MoveConfig) -> Result<(), String> {
                         pubkey_to_move
                     );
 
-                    // Read the password from the user, retrying if the password is incorrect.
-                    loop {
-                        match password_source.read_password(&pubkey_to_move) {
-                            Ok(password) => {
-                                if let Err(e) = keystore.decrypt_keypair(password.as_ref()) {
-                                    eprintln!("Failed to decrypt keystore: {:?}", e);
-                                } else {
-                                    break (keystore, password);
-                                }
-                            }
-                            Err(e) => {
-                                eprintln!(
-                                    "Retrying after error: {:?}. If this error persists the user will need to \
-                                        manually recover their keystore for validator {:?} from the mnemonic."
-                                    ,
-                                    e, pubkey_to_move
-                                );
-                            }
-                        }
-
-                        // Add a sleep here to prevent spamming the console.
-                        sleep(Duration::from_secs(1)).await;
-                    }
+                    let password = password_source
+                        .read_keystore_password(&pubkey_to_move, &keystore)
+                        .await;
+                    (keystore, password)
                 }
                 (None, password_opt) => {
                     eprintln!(